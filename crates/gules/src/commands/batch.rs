@@ -0,0 +1,143 @@
+//! Creating sessions in bulk from a task file (`gules create --file tasks.yaml`).
+//!
+//! The tasks are read by [`jules_core::task_file`].
+//!
+//! Fields missing from a task fall back to the command-line options, then
+//! to the project and global config, as for a single `create`.
+
+use anyhow::{Context, Result};
+use jules_cli::commands::CreateArgs;
+use jules_core::display::{icon, is_quiet};
+use jules_core::progress::Spinner;
+use jules_core::task_file::{load_tasks, BatchTask};
+use jules_core::{api_client, load_config};
+use jules_rs::types::session::Session;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::extended_commands::{
+    create_request, ensure_source_connected, print_sessions, with_git_source, OutputFormat,
+};
+
+/// `create` arguments for `task`, with `defaults` for missing fields
+pub fn task_args(task: &BatchTask, defaults: &CreateArgs) -> CreateArgs {
+    CreateArgs {
+        prompt: task.prompt.clone(),
+        source: task.source.clone().or_else(|| defaults.source.clone()),
+        title: task.title.clone().or_else(|| defaults.title.clone()),
+        branch: task.branch.clone().or_else(|| defaults.branch.clone()),
+        require_approval: task.require_approval.unwrap_or(defaults.require_approval),
+        automation_mode: task
+            .automation_mode
+            .clone()
+            .or_else(|| defaults.automation_mode.clone()),
+        template: defaults.template.clone(),
+    }
+}
+
+/// Create a session for every task of `file`, `concurrency` at a time, then
+/// print the created sessions in `format`
+pub async fn handle_create_batch(
+    file: &str,
+    defaults: CreateArgs,
+    concurrency: usize,
+    format: &str,
+) -> Result<()> {
+    if concurrency == 0 {
+        anyhow::bail!("--concurrency must be at least 1");
+    }
+    let output_format = OutputFormat::parse(format)?;
+    let tasks = load_tasks(file)?;
+
+    let config = load_config()?;
+    let client = api_client(&config)?;
+    let defaults = with_git_source(&client, defaults).await?;
+    // Resolve every request first, so a bad task creates nothing
+    let requests = tasks
+        .iter()
+        .enumerate()
+        .map(|(i, task)| {
+            create_request(&config, task_args(task, &defaults))
+                .with_context(|| format!("Task {} ({})", i + 1, task.label()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut sources: Vec<&str> = requests
+        .iter()
+        .map(|request| request.source_context.source.as_str())
+        .collect();
+    sources.sort_unstable();
+    sources.dedup();
+    for source in sources {
+        ensure_source_connected(&client, source).await?;
+    }
+
+    if !is_quiet() {
+        eprintln!("Creating {} sessions...", requests.len());
+    }
+
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let mut pending = JoinSet::new();
+    for (index, request) in requests.into_iter().enumerate() {
+        let permit = Arc::clone(&semaphore).acquire_owned().await?;
+        let client = client.clone();
+        pending.spawn(async move {
+            let result = client.create_session(request).await;
+            drop(permit);
+            (index, result)
+        });
+    }
+
+    let total = pending.len();
+    let spinner = Spinner::start(format!("Creating 0/{} sessions...", total));
+    let mut created: Vec<(usize, Session)> = Vec::new();
+    let mut errors = Vec::new();
+    while let Some(joined) = pending.join_next().await {
+        let (index, result) = joined?;
+        let label = tasks[index].label();
+        match result {
+            Ok(session) => {
+                if !is_quiet() {
+                    spinner
+                        .suspend(|| eprintln!("  {} {} → {}", icon("✓", "OK"), label, session.id));
+                }
+                created.push((index, session));
+            }
+            Err(e) => {
+                let e = e.context(format!("Task {} ({})", index + 1, label));
+                spinner.suspend(|| eprintln!("  {} {:#}", icon("✗", "FAIL"), e));
+                errors.push(e);
+            }
+        }
+        spinner.set_message(format!(
+            "Creating {}/{} sessions...",
+            created.len() + errors.len(),
+            total
+        ));
+    }
+    spinner.finish();
+
+    // Report in file order, whatever order the sessions were created in
+    created.sort_by_key(|(index, _)| *index);
+    let sessions: Vec<Session> = created.into_iter().map(|(_, session)| session).collect();
+    if !sessions.is_empty() {
+        if !is_quiet() {
+            eprintln!();
+        }
+        print_sessions(&sessions, output_format)?;
+    }
+
+    let failed = errors.len();
+    match errors.into_iter().next() {
+        Some(error) => Err(error.context(format!(
+            "Failed to create {} of {} session(s)",
+            failed, total
+        ))),
+        None => {
+            if !is_quiet() {
+                eprintln!("\n{} Created {} sessions", icon("✅", "OK"), total);
+            }
+            Ok(())
+        }
+    }
+}
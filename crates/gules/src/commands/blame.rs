@@ -0,0 +1,310 @@
+//! Finding the sessions behind a pull request or commit (`gules blame`).
+//!
+//! A pull request is traced to the sessions that list it among their
+//! outputs or are linked to its number locally. A commit is traced through
+//! session references in its message and the patches in the activity cache:
+//! the commit message Jules suggested and, as weak evidence only, the
+//! commit's parent being the one Jules started from. Only sessions whose
+//! activities are cached are found through their patches.
+
+use anyhow::Result;
+use jules_core::exit_code::{self, ExitError};
+use jules_core::git::{git, in_work_tree};
+use jules_core::github_url::{GitHubItemKind, GitHubItemUrl};
+use jules_core::issue_links::IssueLinks;
+use jules_core::patch_files::{collect_patches, PatchFile};
+use jules_core::session_cache::SessionStore;
+use jules_core::session_refs::SessionPatterns;
+use jules_core::{api_client, load_config, open_store};
+use jules_rs::types::session::Session;
+use serde_json::json;
+use std::path::Path;
+
+use super::delete::list_all_sessions;
+
+/// Shortest abbreviated commit SHA accepted, as git abbreviates by default
+const MIN_SHA_LENGTH: usize = 7;
+
+/// A session thought to have produced a change, and the evidence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameMatch {
+    pub session_id: String,
+    pub title: Option<String>,
+    pub reasons: Vec<String>,
+    /// Only weak evidence, which unrelated sessions can share
+    pub weak: bool,
+}
+
+/// Record `reason` for `session_id`, after any earlier evidence
+fn add_reason(matches: &mut Vec<BlameMatch>, session_id: &str, reason: String) {
+    add_evidence(matches, session_id, reason, false);
+}
+
+/// Record `reason` for `session_id`, which is `weak` evidence or not
+fn add_evidence(matches: &mut Vec<BlameMatch>, session_id: &str, reason: String, weak: bool) {
+    match matches.iter_mut().find(|m| m.session_id == session_id) {
+        Some(existing) => {
+            if !existing.reasons.contains(&reason) {
+                existing.reasons.push(reason);
+            }
+            existing.weak &= weak;
+        }
+        None => matches.push(BlameMatch {
+            session_id: session_id.to_string(),
+            title: None,
+            reasons: vec![reason],
+            weak,
+        }),
+    }
+}
+
+/// Fill in the titles of `matches` from `sessions`
+pub fn with_titles<'a>(
+    mut matches: Vec<BlameMatch>,
+    sessions: impl IntoIterator<Item = &'a Session>,
+) -> Vec<BlameMatch> {
+    for session in sessions {
+        if let Some(found) = matches.iter_mut().find(|m| m.session_id == session.id) {
+            found.title = session.title.clone();
+        }
+    }
+    matches
+}
+
+/// Whether `a` and `b` are the same pull request
+fn same_pull_request(a: &GitHubItemUrl, b: &GitHubItemUrl) -> bool {
+    a.kind == GitHubItemKind::PullRequest
+        && b.kind == GitHubItemKind::PullRequest
+        && a.host == b.host
+        && a.owner.eq_ignore_ascii_case(&b.owner)
+        && a.repo.eq_ignore_ascii_case(&b.repo)
+        && a.number == b.number
+}
+
+/// Sessions that created pull request `pr` or are linked to its number
+pub fn blame_pull_request(
+    pr: &GitHubItemUrl,
+    sessions: &[Session],
+    links: &IssueLinks,
+) -> Vec<BlameMatch> {
+    let mut matches = Vec::new();
+    for session in sessions {
+        let created = session.outputs.iter().any(|output| {
+            output
+                .pull_request
+                .as_ref()
+                .and_then(|pr| pr.url.as_deref())
+                .and_then(|url| GitHubItemUrl::parse(url).ok())
+                .is_some_and(|url| same_pull_request(&url, pr))
+        });
+        if created {
+            add_reason(
+                &mut matches,
+                &session.id,
+                "created the pull request".to_string(),
+            );
+        }
+    }
+    for link in links.for_issue(&pr.owner, &pr.repo, pr.number) {
+        add_reason(
+            &mut matches,
+            &link.session_id,
+            format!("linked to {} locally", link.issue_ref()),
+        );
+    }
+    with_titles(matches, sessions)
+}
+
+/// What is known about the commit `blame` looks for
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitInfo {
+    /// Full SHA if the commit is in a local repository, else as given
+    pub sha: String,
+    /// Parent SHAs, if the commit is in a local repository
+    pub parents: Vec<String>,
+    /// Commit message, if the commit is in a local repository
+    pub message: Option<String>,
+}
+
+/// Whether two SHAs, either possibly abbreviated, name the same commit
+fn same_commit(a: &str, b: &str) -> bool {
+    let (a, b) = (a.to_lowercase(), b.to_lowercase());
+    a.len().min(b.len()) >= MIN_SHA_LENGTH && (a.starts_with(&b) || b.starts_with(&a))
+}
+
+/// The first non-empty line of a commit message
+fn subject(message: &str) -> Option<&str> {
+    message.lines().map(str::trim).find(|line| !line.is_empty())
+}
+
+/// Sessions whose cached `patches` (by session ID) or references in the
+/// commit message tie them to `commit`, weak matches last
+///
+/// A patch made on top of the commit's parent is weak evidence: every
+/// session started from that commit shares it. One made on top of the commit
+/// itself is no evidence at all, as the session came after the commit.
+pub fn blame_commit(
+    commit: &CommitInfo,
+    patches: &[(String, Vec<PatchFile>)],
+    patterns: &SessionPatterns,
+) -> Vec<BlameMatch> {
+    let mut matches = Vec::new();
+    if let Some(message) = &commit.message {
+        for session_id in patterns.extract([message.as_str()]) {
+            add_reason(
+                &mut matches,
+                &session_id,
+                "mentioned in the commit message".to_string(),
+            );
+        }
+    }
+    let commit_subject = commit.message.as_deref().and_then(subject);
+    for (session_id, patches) in patches {
+        for patch in patches {
+            let suggested = patch.suggested_commit_message.as_deref().and_then(subject);
+            if commit_subject.is_some() && suggested == commit_subject {
+                add_reason(
+                    &mut matches,
+                    session_id,
+                    format!(
+                        "the commit message is the one suggested with {}",
+                        patch.file_name
+                    ),
+                );
+            }
+            let Some(base) = patch.base_commit_id.as_deref() else {
+                continue;
+            };
+            if commit
+                .parents
+                .iter()
+                .any(|parent| same_commit(base, parent))
+            {
+                add_evidence(
+                    &mut matches,
+                    session_id,
+                    format!("{} was made on top of the commit's parent", patch.file_name),
+                    true,
+                );
+            }
+        }
+    }
+    matches.sort_by_key(|m| m.weak);
+    matches
+}
+
+/// `commit` as the repository in the working directory knows it
+fn commit_info(commit: &str) -> Result<CommitInfo> {
+    let dir = Path::new(".");
+    let spec = format!("{}^{{commit}}", commit);
+    let sha = in_work_tree(dir)
+        .then(|| git(dir, &["rev-parse", "--verify", "--quiet", &spec]).ok())
+        .flatten()
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown commit {}: run blame in a repository that has it",
+                commit
+            )
+        })?;
+    let parents = git(dir, &["show", "-s", "--format=%P", &sha])?;
+    let message = git(dir, &["show", "-s", "--format=%B", &sha])?;
+    Ok(CommitInfo {
+        parents: parents.split_whitespace().map(str::to_string).collect(),
+        message: Some(message),
+        sha,
+    })
+}
+
+/// Find the sessions behind pull request `pr` or `commit` and print them in
+/// `format`; exits with code 2 if none is found
+///
+/// With `cached`, pull requests are looked up in the session cache instead
+/// of listing every session from the API.
+pub async fn handle_blame(
+    pr: Option<&str>,
+    commit: Option<&str>,
+    cached: bool,
+    format: &str,
+) -> Result<()> {
+    let json = match format.to_lowercase().as_str() {
+        "json" => true,
+        "table" => false,
+        _ => anyhow::bail!("Unknown format: {}. Valid options: table, json", format),
+    };
+    let config = load_config()?;
+
+    let (target, matches) = match (pr, commit) {
+        (Some(pr), _) => {
+            let pr = GitHubItemUrl::parse_pull_request(pr)?;
+            let sessions = if cached {
+                SessionStore::load()?.list().into_iter().cloned().collect()
+            } else {
+                list_all_sessions(&api_client(&config)?, config.cache.enabled).await?
+            };
+            let matches = blame_pull_request(&pr, &sessions, &IssueLinks::load()?);
+            (pr.web_url(), matches)
+        }
+        (None, Some(commit)) => {
+            let commit = commit_info(commit)?;
+            let store = open_store(&config.cache)?;
+            let mut patches = Vec::new();
+            for id in store.list_sessions()? {
+                match store.peek_session(&id) {
+                    Ok(Some(cache)) => {
+                        patches.push((cache.session_id.clone(), collect_patches(&cache.activities)))
+                    }
+                    Ok(None) => {}
+                    Err(e) => eprintln!("Warning: Skipping cache for session {}: {}", id, e),
+                }
+            }
+            let patterns = SessionPatterns::new(&config.github.session_patterns)?;
+            let matches = with_titles(
+                blame_commit(&commit, &patches, &patterns),
+                SessionStore::load()?.list(),
+            );
+            (format!("commit {}", commit.sha), matches)
+        }
+        (None, None) => anyhow::bail!("Give --pr or --commit"),
+    };
+
+    if json {
+        let matches: Vec<_> = matches
+            .iter()
+            .map(|m| {
+                json!({
+                    "session_id": m.session_id,
+                    "title": m.title,
+                    "reasons": m.reasons,
+                    "weak": m.weak,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&matches)?);
+    } else {
+        for (i, m) in matches.iter().enumerate() {
+            if m.weak && (i == 0 || !matches[i - 1].weak) {
+                if i > 0 {
+                    println!();
+                }
+                println!("Weak matches (only started from the commit's parent):");
+            }
+            println!("{}  {}", m.session_id, m.title.as_deref().unwrap_or(""));
+            for reason in &m.reasons {
+                println!("    - {}", reason);
+            }
+        }
+    }
+    if matches.is_empty() {
+        let hint = if commit.is_some() {
+            " among the cached sessions"
+        } else {
+            ""
+        };
+        return Err(ExitError::new(
+            exit_code::NOT_FOUND,
+            format!("No session found for {}{}", target, hint),
+        )
+        .into());
+    }
+    Ok(())
+}
@@ -1,13 +1,71 @@
 //! Cache management commands.
 //!
-//! Commands for managing the activity cache: stats, clear, delete.
+//! Commands for managing the activity cache: stats, clear, delete, verify,
+//! pin, export, import and warm. `stats` and `clear` also cover the cache of
+//! GitHub API responses.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use jules_core::activity_cache::*;
+use jules_core::cache_archive::{merge_session_caches, CacheArchive};
+use jules_core::github_cache::{clear_github_cache, ResponseCache};
+use jules_core::progress::Spinner;
+use jules_core::prompt::confirm;
+use jules_core::session_cache::remember_sessions;
+use jules_core::session_cache::{import_sessions, SessionStore};
+use jules_core::{
+    api_client, icon, is_quiet, load_config, open_store, ActivityStore, CacheBackend, SqliteCache,
+};
+use jules_rs::types::session::{Session, State};
+use jules_rs::JulesClient;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use super::filter_activities::refresh_activity_cache;
+
+/// Page size used when listing sessions to warm
+const WARM_PAGE_SIZE: u32 = 50;
+
+/// Open the configured cache backend
+///
+/// For the JSON backend this applies the `[cache]` settings first, so stats
+/// and on-disk formats reflect the current configuration.
+fn configured_store() -> Result<Box<dyn ActivityStore>> {
+    open_store(&load_config()?.cache)
+}
 
 /// Show cache statistics
-pub async fn handle_cache_stats() -> Result<()> {
-    let stats = get_cache_stats()?;
+///
+/// `sort` orders the per-session breakdown (default: next to be evicted
+/// first) and `top` limits it to the first N sessions.
+pub async fn handle_cache_stats(sort: Option<&str>, top: Option<usize>, json: bool) -> Result<()> {
+    let sort = sort.map(SessionStatsSort::parse).transpose()?;
+    let config = load_config()?;
+    let store = open_store(&config.cache)?;
+    let mut stats = store.stats()?;
+    stats.enabled = config.cache.enabled;
+    if let Some(sort) = sort {
+        stats.sort_sessions(sort);
+    }
+    if let Some(top) = top {
+        stats.sessions.truncate(top);
+    }
+
+    let responses = ResponseCache::open()?;
+    let (response_count, response_bytes) = responses.usage()?;
+
+    if json {
+        let mut value = serde_json::to_value(&stats).context("Failed to serialize stats")?;
+        value["github_responses"] = serde_json::json!({
+            "count": response_count,
+            "size_bytes": response_bytes,
+            "dir": responses.dir().display().to_string(),
+        });
+        let json = serde_json::to_string_pretty(&value).context("Failed to serialize stats")?;
+        println!("{}", json);
+        return Ok(());
+    }
 
     println!("Activity Cache Statistics");
     println!("═══════════════════════════");
@@ -16,59 +74,122 @@ pub async fn handle_cache_stats() -> Result<()> {
         if stats.enabled { "Enabled" } else { "Disabled" }
     );
     println!("Location: {}", stats.cache_dir);
+    println!(
+        "Encryption: {}",
+        if stats.encryption_enabled {
+            format!(
+                "Enabled ({}/{} sessions encrypted)",
+                stats.encrypted_sessions, stats.total_sessions
+            )
+        } else if stats.encrypted_sessions > 0 {
+            format!(
+                "Disabled ({} sessions still encrypted)",
+                stats.encrypted_sessions
+            )
+        } else {
+            "Disabled".to_string()
+        }
+    );
     println!();
     println!("Sessions: {}/{}", stats.total_sessions, stats.max_sessions);
     println!("Total Activities: {}", stats.total_activities);
+    println!("Disk Usage: {}", format_size(stats.total_size_bytes));
     println!(
-        "Disk Usage: {:.2} MiB",
-        stats.total_size_bytes as f64 / 1_048_576.0
+        "GitHub Responses: {} ({})",
+        response_count,
+        format_size(response_bytes)
     );
 
-    if stats.total_sessions > 0 {
+    let counters = &stats.metrics.total;
+    if let Some(rate) = counters.hit_rate() {
         println!();
-        println!("Cached Sessions:");
-        let sessions = list_cached_sessions()?;
-        for (i, session_id) in sessions.iter().enumerate() {
-            match load_session_cache(session_id) {
-                Ok(Some(cache)) => {
-                    println!(
-                        "  {}. {} ({} activities, updated {})",
-                        i + 1,
-                        session_id,
-                        cache.activities.len(),
-                        cache.last_updated.format("%Y-%m-%d %H:%M")
-                    );
-                }
-                Ok(None) => {
-                    // File deleted but metadata not yet updated - safe to ignore
-                }
-                Err(e) => {
-                    eprintln!(
-                        "Warning: Failed to load cache for session {}: {}",
-                        session_id, e
-                    );
-                }
-            }
+        println!(
+            "Hit Rate: {:.0}% ({} hits, {} incremental updates, {} misses)",
+            rate * 100.0,
+            counters.hits,
+            counters.incremental_updates,
+            counters.misses
+        );
+        println!("API Calls Saved: ~{}", counters.api_calls_saved);
+    }
+
+    if !stats.sessions.is_empty() {
+        println!();
+        if stats.sessions.len() < stats.total_sessions {
+            println!(
+                "Cached Sessions (top {} of {}):",
+                stats.sessions.len(),
+                stats.total_sessions
+            );
+        } else {
+            println!("Cached Sessions:");
+        }
+        println!(
+            "  {:>3}  {:<24} {:>10} {:>10}  {:<16} {:>5}",
+            "#", "SESSION", "ACTIVITIES", "SIZE", "UPDATED", "HITS"
+        );
+        for (i, session) in stats.sessions.iter().enumerate() {
+            let updated = session
+                .last_updated
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "unreadable".to_string());
+            println!(
+                "  {:>3}  {:<24} {:>10} {:>10}  {:<16} {:>5}{}",
+                i + 1,
+                session.session_id,
+                session.activities,
+                format_size(session.size_bytes),
+                updated,
+                session.counters.hits,
+                if session.pinned { "  [pinned]" } else { "" }
+            );
         }
     }
 
     Ok(())
 }
 
-/// Clear all cache
-pub async fn handle_cache_clear() -> Result<()> {
-    let stats = get_cache_stats()?;
+/// Human-readable byte count
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1_048_576 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.2} MiB", bytes as f64 / 1_048_576.0)
+    }
+}
 
-    if stats.total_sessions == 0 {
+/// Clear all cache, including cached GitHub responses, after confirmation
+/// unless `yes`
+pub async fn handle_cache_clear(yes: bool) -> Result<()> {
+    let mut store = configured_store()?;
+    let stats = store.stats()?;
+    let (responses, _) = ResponseCache::open()?.usage()?;
+
+    if stats.total_sessions == 0 && responses == 0 {
         println!("Cache is already empty.");
         return Ok(());
     }
 
-    clear_all_cache()?;
+    let question = format!(
+        "Clear the cache ({} sessions, {} activities, {} GitHub responses)?",
+        stats.total_sessions, stats.total_activities, responses
+    );
+    if !confirm(&question, yes)? {
+        println!("Cache not cleared.");
+        return Ok(());
+    }
+
+    store.clear()?;
+    clear_github_cache()?;
 
     println!(
-        "✅ Cleared cache ({} sessions, {} activities)",
-        stats.total_sessions, stats.total_activities
+        "{} Cleared cache ({} sessions, {} activities, {} GitHub responses)",
+        icon("✅", "OK"),
+        stats.total_sessions,
+        stats.total_activities,
+        responses
     );
 
     Ok(())
@@ -76,14 +197,356 @@ pub async fn handle_cache_clear() -> Result<()> {
 
 /// Delete cache for a specific session
 pub async fn handle_cache_delete(session_id: &str) -> Result<()> {
-    if load_session_cache(session_id)?.is_none() {
+    let mut store = configured_store()?;
+    // An unreadable cache still exists and can be deleted
+    let cached = store
+        .peek_session(session_id)
+        .map_or(true, |cache| cache.is_some());
+
+    if !cached {
         println!("No cache found for session: {}", session_id);
         return Ok(());
     }
 
-    delete_session_cache(session_id)?;
+    store.delete_session(session_id)?;
+
+    if !is_quiet() {
+        println!(
+            "{} Deleted cache for session: {}",
+            icon("✅", "OK"),
+            session_id
+        );
+    }
+
+    Ok(())
+}
+
+/// Check cache integrity, repairing problems with `--repair`
+pub async fn handle_cache_verify(repair: bool) -> Result<()> {
+    let config = load_config()?;
+
+    if config.cache.backend == CacheBackend::Sqlite {
+        let store = SqliteCache::open_configured(&config.cache)?;
+        let problems = store.integrity_check()?;
+        if problems.is_empty() {
+            println!(
+                "{} Cache database is healthy ({})",
+                icon("✅", "OK"),
+                store.path().display()
+            );
+        } else {
+            println!("{} Cache database is damaged:", icon("❌", "ERROR"));
+            for problem in &problems {
+                println!("  {}", problem);
+            }
+            println!("Run `gules cache clear` to start over.");
+        }
+        return Ok(());
+    }
+
+    let report = verify_cache(repair)?;
+    if report.repaired {
+        // A rebuilt metadata file starts from defaults; restore the settings
+        apply_cache_config(&config.cache)?;
+    }
+
+    if report.is_healthy() {
+        println!(
+            "{} Cache is healthy ({} session files)",
+            icon("✅", "OK"),
+            report.checked_files
+        );
+        return Ok(());
+    }
+
+    println!("Cache Verification");
+    println!("═══════════════════════════");
+    if report.metadata_corrupt {
+        println!("  metadata.json is corrupt");
+    }
+    for id in &report.missing_files {
+        println!("  {}: listed in metadata but no cache file", id);
+    }
+    for id in &report.orphaned_files {
+        println!("  {}: cache file not listed in metadata", id);
+    }
+    for file in &report.corrupt_files {
+        println!("  {}: unreadable ({})", file.session_id, file.error);
+    }
+    for id in &report.duplicate_entries {
+        println!("  {}: listed more than once in metadata", id);
+    }
+    for name in &report.stale_temp_files {
+        println!("  {}: left by an interrupted write", name);
+    }
+    println!();
+
+    if report.repaired {
+        println!(
+            "{} Repaired cache ({} corrupt files removed, {} sessions re-added)",
+            icon("✅", "OK"),
+            report.corrupt_files.len(),
+            report.orphaned_files.len()
+        );
+    } else {
+        println!("Run `gules cache verify --repair` to fix these problems.");
+    }
+
+    Ok(())
+}
+
+/// Export cached sessions (all, or the given ones) to an archive
+pub async fn handle_cache_export(path: &str, session_ids: &[String]) -> Result<()> {
+    let store = configured_store()?;
+    let ids = if session_ids.is_empty() {
+        store.list_sessions()?
+    } else {
+        session_ids
+            .iter()
+            .map(|id| cache_key(id))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let mut caches = Vec::new();
+    for id in &ids {
+        match store.peek_session(id)? {
+            Some(cache) => caches.push(cache),
+            None if !session_ids.is_empty() => {
+                anyhow::bail!("No cache found for session: {}", id)
+            }
+            None => {}
+        }
+    }
+
+    if caches.is_empty() {
+        println!("Cache is empty, nothing to export.");
+        return Ok(());
+    }
+
+    let sessions = SessionStore::load()
+        .map(|store| ids.iter().filter_map(|id| store.get(id).cloned()).collect())
+        .unwrap_or_default();
+
+    let archive = CacheArchive::new(caches, sessions);
+    archive.write(Path::new(path))?;
+
+    let activities: usize = archive.caches.iter().map(|c| c.activities.len()).sum();
+    println!(
+        "{} Exported {} sessions ({} activities) to {}",
+        icon("✅", "OK"),
+        archive.caches.len(),
+        activities,
+        path
+    );
+    if load_config()?.cache.encrypt {
+        eprintln!("Note: the archive is not encrypted.");
+    }
+
+    Ok(())
+}
+
+/// Import cached sessions from an archive, merging with the local cache
+pub async fn handle_cache_import(path: &str) -> Result<()> {
+    let archive = CacheArchive::read(Path::new(path))?;
+    let mut store = configured_store()?;
+
+    let max_sessions = load_config()?.cache.max_sessions;
+    if archive.caches.len() > max_sessions {
+        eprintln!(
+            "Warning: Archive has {} sessions but max_sessions is {}; older sessions will be evicted.",
+            archive.caches.len(),
+            max_sessions
+        );
+    }
+
+    let mut activities = 0;
+    for imported in &archive.caches {
+        let merged = match store.peek_session(&imported.session_id)? {
+            Some(local) => merge_session_caches(local, imported.clone()),
+            None => imported.clone(),
+        };
+        activities += merged.activities.len();
+
+        store.save_session(&merged)?;
+    }
+    let sessions = import_sessions(archive.sessions)?;
 
-    println!("✅ Deleted cache for session: {}", session_id);
+    println!(
+        "{} Imported {} sessions ({} activities, {} session records) from {}",
+        icon("✅", "OK"),
+        archive.caches.len(),
+        activities,
+        sessions,
+        path
+    );
+
+    Ok(())
+}
+
+/// Whether a session is still running (its activities can still change)
+pub(crate) fn is_active(session: &Session) -> bool {
+    matches!(
+        session.state,
+        Some(
+            State::Queued
+                | State::Planning
+                | State::AwaitingPlanApproval
+                | State::AwaitingUserFeedback
+                | State::InProgress
+        )
+    )
+}
+
+/// List up to `limit` sessions, keeping only active ones unless `all`
+pub(crate) async fn list_sessions_to_warm(
+    client: &JulesClient,
+    all: bool,
+    limit: usize,
+) -> Result<Vec<Session>> {
+    let mut sessions = Vec::new();
+    let mut page_token: Option<String> = None;
+
+    loop {
+        let response = client
+            .list_sessions(Some(WARM_PAGE_SIZE), page_token.as_deref())
+            .await?;
+        remember_sessions(&response.sessions);
+        sessions.extend(
+            response
+                .sessions
+                .into_iter()
+                .filter(|session| all || is_active(session)),
+        );
+
+        page_token = response.next_page_token;
+        if sessions.len() >= limit || page_token.is_none() {
+            break;
+        }
+    }
+
+    sessions.truncate(limit);
+    Ok(sessions)
+}
+
+/// Populate the activity cache for recent sessions, a few at a time
+pub async fn handle_cache_warm(state: &str, limit: usize, concurrency: usize) -> Result<()> {
+    let all = match state.to_lowercase().as_str() {
+        "active" => false,
+        "all" => true,
+        _ => anyhow::bail!("Unknown state: {}. Valid options: active, all", state),
+    };
+    if concurrency == 0 {
+        anyhow::bail!("--concurrency must be at least 1");
+    }
 
+    let config = load_config()?;
+    if !config.cache.enabled {
+        anyhow::bail!("Caching is disabled (set cache.enabled = true in the config)");
+    }
+    if config.cache.backend == CacheBackend::Json {
+        apply_cache_config(&config.cache)?;
+    }
+
+    let client = api_client(&config)?;
+    let sessions = list_sessions_to_warm(&client, all, limit).await?;
+    if sessions.is_empty() {
+        println!(
+            "{}",
+            if all {
+                "No sessions to warm."
+            } else {
+                "No active sessions to warm."
+            }
+        );
+        return Ok(());
+    }
+    if sessions.len() > config.cache.max_sessions {
+        eprintln!(
+            "Warning: Warming {} sessions but max_sessions is {}; older sessions will be evicted.",
+            sessions.len(),
+            config.cache.max_sessions
+        );
+    }
+
+    if !is_quiet() {
+        println!("Warming cache for {} sessions...", sessions.len());
+    }
+
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let cache_config = Arc::new(config.cache);
+    let mut tasks = JoinSet::new();
+    for session in sessions {
+        let permit = Arc::clone(&semaphore).acquire_owned().await?;
+        let client = client.clone();
+        let cache_config = Arc::clone(&cache_config);
+        tasks.spawn(async move {
+            let result = refresh_activity_cache(&client, &cache_config, &session.id).await;
+            drop(permit);
+            (session.id, result)
+        });
+    }
+
+    let total = tasks.len();
+    let spinner = Spinner::start(format!("Warming 0/{} sessions...", total));
+    let mut warmed = 0;
+    let mut failed = 0;
+    while let Some(joined) = tasks.join_next().await {
+        let (session_id, result) = joined?;
+        match result {
+            Ok(count) => {
+                warmed += 1;
+                if !is_quiet() {
+                    spinner.suspend(|| {
+                        println!(
+                            "  {} {} ({} activities)",
+                            icon("✓", "OK"),
+                            session_id,
+                            count
+                        )
+                    });
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                spinner.suspend(|| eprintln!("  {} {}: {:#}", icon("✗", "FAIL"), session_id, e));
+            }
+        }
+        spinner.set_message(format!("Warming {}/{} sessions...", warmed + failed, total));
+    }
+    spinner.finish();
+
+    if failed == 0 {
+        if !is_quiet() {
+            println!("\n{} Warmed {} sessions", icon("✅", "OK"), warmed);
+        }
+    } else {
+        println!();
+        println!("Warmed {} sessions, {} failed", warmed, failed);
+    }
+
+    Ok(())
+}
+
+/// Pin a session so its cache is never evicted
+pub async fn handle_cache_pin(session_id: &str) -> Result<()> {
+    if pin_session(session_id)? {
+        if !is_quiet() {
+            println!("{} Pinned session: {}", icon("📌", "OK"), session_id);
+        }
+    } else {
+        println!("Session is already pinned: {}", session_id);
+    }
+    Ok(())
+}
+
+/// Unpin a session
+pub async fn handle_cache_unpin(session_id: &str) -> Result<()> {
+    if unpin_session(session_id)? {
+        if !is_quiet() {
+            println!("{} Unpinned session: {}", icon("✅", "OK"), session_id);
+        }
+    } else {
+        println!("Session is not pinned: {}", session_id);
+    }
     Ok(())
 }
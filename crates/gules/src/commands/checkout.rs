@@ -0,0 +1,115 @@
+//! Checking out a session's changes as a local branch.
+//!
+//! Creates a branch (`jules/{session_id}` by default) from the commit Jules
+//! started from, applies the session's newest patch and commits it with the
+//! suggested commit message, so Jules' work can be continued locally.
+
+use anyhow::Result;
+use jules_core::display::{icon, is_quiet};
+use jules_core::git::{git, git_with_input, in_work_tree};
+use jules_core::patch_files::{collect_patches, PatchFile};
+use jules_rs::types::resource_name::SessionName;
+use std::path::Path;
+
+use super::diff::session_activities;
+
+/// Default branch name for a session's changes
+pub fn session_branch_name(session_id: &str) -> Result<String> {
+    Ok(format!(
+        "jules/{}",
+        SessionName::from_id_or_name(session_id)?.id()
+    ))
+}
+
+/// Create a branch with a session's changes in the current repository
+pub async fn handle_checkout(session_id: &str, branch: Option<&str>) -> Result<()> {
+    let dir = Path::new(".");
+    if !in_work_tree(dir) {
+        anyhow::bail!("Not inside a git repository");
+    }
+    if !git(dir, &["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+        anyhow::bail!("The working tree has uncommitted changes; commit or stash them first");
+    }
+
+    let branch = match branch {
+        Some(branch) => branch.to_string(),
+        None => session_branch_name(session_id)?,
+    };
+    let exists = git(
+        dir,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{}", branch),
+        ],
+    )
+    .is_ok();
+    if exists {
+        anyhow::bail!("Branch {} already exists", branch);
+    }
+
+    let activities = session_activities(session_id).await?;
+    let Some(patch) = collect_patches(&activities).pop() else {
+        anyhow::bail!("No patches found for session {}", session_id);
+    };
+
+    // Start from the commit Jules worked on when it's available locally
+    let base = patch
+        .base_commit_id
+        .as_deref()
+        .filter(|commit| git(dir, &["cat-file", "-e", &format!("{}^{{commit}}", commit)]).is_ok());
+    if let (None, Some(commit)) = (base, &patch.base_commit_id) {
+        eprintln!(
+            "Warning: Base commit {} not found locally (try 'git fetch'); branching from HEAD",
+            commit
+        );
+    }
+
+    let original = current_ref(dir)?;
+    let mut args = vec!["checkout", "--quiet", "-b", branch.as_str()];
+    args.extend(base);
+    git(dir, &args)?;
+
+    if let Err(e) = apply_and_commit(dir, &patch, session_id) {
+        // Leave the repository as it was; the tree was clean before
+        let _ = git(dir, &["reset", "--quiet", "--hard"]);
+        let _ = git(dir, &["checkout", "--quiet", &original]);
+        let _ = git(dir, &["branch", "--quiet", "-D", &branch]);
+        return Err(e.context(format!(
+            "Failed to apply the patch of session {}",
+            session_id
+        )));
+    }
+
+    if !is_quiet() {
+        println!(
+            "{} Checked out session {} on branch {}",
+            icon("✅", "OK"),
+            session_id,
+            branch
+        );
+    }
+    Ok(())
+}
+
+/// The current branch, or the commit when HEAD is detached
+fn current_ref(dir: &Path) -> Result<String> {
+    let branch = git(dir, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+    if branch == "HEAD" {
+        git(dir, &["rev-parse", "HEAD"])
+    } else {
+        Ok(branch)
+    }
+}
+
+fn apply_and_commit(dir: &Path, patch: &PatchFile, session_id: &str) -> Result<()> {
+    git_with_input(dir, &["apply", "--index", "-"], &patch.patch)?;
+    let message = patch
+        .suggested_commit_message
+        .clone()
+        .filter(|message| !message.trim().is_empty())
+        .unwrap_or_else(|| format!("Apply changes from Jules session {}", session_id));
+    git(dir, &["commit", "--quiet", "-m", &message])?;
+    Ok(())
+}
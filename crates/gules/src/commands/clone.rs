@@ -0,0 +1,64 @@
+//! Cloning a session: the same prompt on another branch or repository.
+
+use anyhow::Result;
+use jules_core::source_cache::expand_source;
+use jules_core::{api_client, load_config};
+use jules_rs::types::session::{CreateSessionRequest, GitHubRepoContext, Session, SourceContext};
+
+use crate::extended_commands::create_session_formatted;
+
+/// Request for a new session with the prompt and settings of `session`
+///
+/// The starting branch is kept unless `branch` is given or the session moves
+/// to another `source`, where it may not exist; `title` replaces the
+/// original title.
+pub fn clone_request(
+    session: &Session,
+    source: Option<String>,
+    branch: Option<String>,
+    title: Option<String>,
+) -> CreateSessionRequest {
+    let same_source = source
+        .as_deref()
+        .is_none_or(|source| source == session.source_context.source);
+    let branch = branch.or_else(|| {
+        session
+            .source_context
+            .github_repo_context
+            .as_ref()
+            .filter(|_| same_source)
+            .map(|context| context.starting_branch.clone())
+    });
+
+    CreateSessionRequest {
+        prompt: session.prompt.clone(),
+        source_context: SourceContext {
+            source: source.unwrap_or_else(|| session.source_context.source.clone()),
+            github_repo_context: branch
+                .map(|starting_branch| GitHubRepoContext { starting_branch }),
+        },
+        title: title.or_else(|| session.title.clone()),
+        require_plan_approval: session.require_plan_approval,
+        automation_mode: session.automation_mode,
+    }
+}
+
+/// Start a new session with the prompt of an existing one, optionally on
+/// another branch or source
+pub async fn handle_clone(
+    session_id: &str,
+    source: Option<String>,
+    branch: Option<String>,
+    title: Option<String>,
+    format: &str,
+) -> Result<()> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+
+    let source = source.as_deref().map(expand_source).transpose()?;
+    let session = client.get_session(session_id).await?;
+    let request = clone_request(&session, source, branch, title);
+    config.safety.check_source(&request.source_context.source)?;
+    create_session_formatted(&client, request, format).await?;
+    Ok(())
+}
@@ -0,0 +1,203 @@
+//! Background cache refresher.
+//!
+//! `gules daemon` periodically refreshes the activity caches of running
+//! sessions and publishes events (new activities, state changes) to the
+//! event log, so other commands can read them instead of polling the API.
+
+use anyhow::Result;
+use chrono::{Local, Utc};
+use jules_core::activity_cache::*;
+use jules_core::cache_events::*;
+use jules_core::metrics::export_metrics;
+use jules_core::session_cache::SessionStore;
+use jules_core::{api_client, load_config, open_store, ActivityStore, CacheConfig};
+use jules_rs::JulesClient;
+use std::collections::HashSet;
+use tokio::time::{sleep, Duration};
+
+use super::cache::{is_active, list_sessions_to_warm};
+use super::filter_activities::refresh_activity_cache;
+
+/// Recent sessions checked for state changes on each refresh
+const DAEMON_SESSION_LIMIT: usize = 100;
+
+/// IDs of the cached activities of a session (`None` if not cached)
+fn cached_activity_ids(
+    store: &dyn ActivityStore,
+    session_id: &str,
+) -> Result<Option<HashSet<String>>> {
+    let cache = store.peek_session(session_id)?;
+    Ok(cache.map(|cache| cache.activities.into_iter().map(|a| a.id).collect()))
+}
+
+/// One refresh cycle; returns the events it produced
+async fn refresh_once(client: &JulesClient, config: &CacheConfig) -> Result<Vec<CacheEvent>> {
+    let store = open_store(config)?;
+    let previous = SessionStore::load().unwrap_or_default();
+    let sessions = list_sessions_to_warm(client, true, DAEMON_SESSION_LIMIT).await?;
+
+    let mut events = Vec::new();
+    for session in &sessions {
+        let old_state = previous.get(&session.id).map(|cached| cached.session.state);
+        let state_changed = matches!(old_state, Some(old) if old != session.state);
+        if state_changed {
+            events.push(CacheEvent::new(
+                &session.id,
+                CacheEventKind::StateChanged {
+                    from: old_state
+                        .flatten()
+                        .map(|state| state.display_name().to_string()),
+                    to: session
+                        .state
+                        .map(|state| state.display_name())
+                        .unwrap_or("Unknown")
+                        .to_string(),
+                },
+            ));
+        }
+
+        // Finished sessions get one last refresh to pick up their final
+        // activities
+        if !is_active(session) && !state_changed {
+            continue;
+        }
+
+        let before = cached_activity_ids(store.as_ref(), &session.id)?;
+        if let Err(e) = refresh_activity_cache(client, config, &session.id).await {
+            eprintln!("Warning: Failed to refresh session {}: {:#}", session.id, e);
+            continue;
+        }
+
+        // No event for the initial fill of a session's cache
+        let after = cached_activity_ids(store.as_ref(), &session.id)?;
+        let (Some(before), Some(after)) = (before, after) else {
+            continue;
+        };
+        let mut new_ids: Vec<String> = after.difference(&before).cloned().collect();
+        if !new_ids.is_empty() {
+            new_ids.sort();
+            events.push(CacheEvent::new(
+                &session.id,
+                CacheEventKind::NewActivities {
+                    activity_ids: new_ids,
+                },
+            ));
+        }
+    }
+
+    Ok(events)
+}
+
+/// Print an event as a one-line summary
+fn print_event(event: &CacheEvent) {
+    let time = event.time.with_timezone(&Local).format("%H:%M:%S");
+    match &event.kind {
+        CacheEventKind::NewActivities { activity_ids } => println!(
+            "[{}] {}: {} new activities",
+            time,
+            event.session_id,
+            activity_ids.len()
+        ),
+        CacheEventKind::StateChanged { from, to } => println!(
+            "[{}] {}: {} → {}",
+            time,
+            event.session_id,
+            from.as_deref().unwrap_or("Unknown"),
+            to
+        ),
+    }
+}
+
+/// Run the background refresher until interrupted (or once with `once`)
+pub async fn handle_daemon(interval: u64, once: bool) -> Result<()> {
+    if interval == 0 && !once {
+        anyhow::bail!("--interval must be at least 1 second");
+    }
+    let config = load_config()?;
+    if !config.cache.enabled {
+        anyhow::bail!("Caching is disabled (set cache.enabled = true in the config)");
+    }
+    let client = export_metrics(api_client(&config)?, &config);
+
+    // Refresh running sessions on every cycle, whatever the configured TTL
+    let cache_config = CacheConfig {
+        ttl_seconds: 0,
+        ..config.cache
+    };
+
+    let mut status = DaemonStatus {
+        pid: std::process::id(),
+        started_at: Utc::now(),
+        last_refresh: None,
+        interval_seconds: interval,
+    };
+    if !once {
+        println!("Refreshing active sessions every {}s...", interval);
+        println!("Events are written to {}", get_events_path()?.display());
+        println!("Press Ctrl+C to stop\n");
+    }
+
+    loop {
+        match refresh_once(&client, &cache_config).await {
+            Ok(events) => {
+                events.iter().for_each(print_event);
+                append_events(&events)?;
+                status.last_refresh = Some(Utc::now());
+                status.save()?;
+            }
+            Err(e) => eprintln!("Error refreshing sessions: {:#}", e),
+        }
+
+        if once {
+            return Ok(());
+        }
+
+        tokio::select! {
+            _ = sleep(Duration::from_secs(interval)) => {}
+            _ = tokio::signal::ctrl_c() => {
+                let _ = std::fs::remove_file(get_daemon_status_path()?);
+                println!("\nStopped.");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Show whether a daemon is running
+pub async fn handle_daemon_status() -> Result<()> {
+    match DaemonStatus::load()? {
+        Some(status) if status.is_alive() => {
+            println!("Daemon running (pid {})", status.pid);
+            println!(
+                "Started: {}",
+                status
+                    .started_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+            );
+            if let Some(last) = status.last_refresh {
+                println!(
+                    "Last refresh: {}",
+                    last.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+                );
+            }
+            println!("Interval: {}s", status.interval_seconds);
+        }
+        _ => println!("Daemon not running. Start it with `gules daemon`."),
+    }
+    Ok(())
+}
+
+/// Print recent events as JSON lines
+pub async fn handle_daemon_events(session: Option<&str>, limit: usize) -> Result<()> {
+    let session = session.map(cache_key).transpose()?;
+    let events: Vec<CacheEvent> = read_events(None)?
+        .into_iter()
+        .filter(|event| session.as_ref().is_none_or(|id| &event.session_id == id))
+        .collect();
+
+    for event in events.iter().skip(events.len().saturating_sub(limit)) {
+        println!("{}", serde_json::to_string(event)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,481 @@
+//! Interactive dashboard (`gules monitor --tui`).
+//!
+//! A live session list, refreshed every poll interval, with a drill-down
+//! into the selected session's activities or diff. Keys act on the selected
+//! session: approve its plan, send it a message, or open it in the browser.
+//!
+//! [`Dashboard`] holds the state and turns key presses into
+//! [`DashboardAction`]s; the API calls and drawing happen in
+//! [`handle_monitor_tui`].
+
+use anyhow::Result;
+use chrono::Local;
+use jules_core::activity_cache::fetch_all_activities;
+use jules_core::browser::open_url;
+use jules_core::display::display_timestamp;
+use jules_core::metrics::export_metrics;
+use jules_core::patch_files::collect_patches;
+use jules_core::session_cache::remember_sessions;
+use jules_core::{api_client, load_config};
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use jules_rs::{JulesClient, State};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::io::{self, IsTerminal};
+use std::time::{Duration, Instant};
+
+use super::open::session_open_url;
+use crate::extended_commands::SessionFilter;
+
+/// How long to wait for a key before checking whether to poll again
+const KEY_POLL: Duration = Duration::from_millis(250);
+
+/// Lines scrolled by PageUp/PageDown in the detail views
+const PAGE_LINES: usize = 10;
+
+/// What the dashboard shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DashboardView {
+    /// The session list
+    #[default]
+    List,
+    /// Activities of the selected session
+    Activities,
+    /// Patches of the selected session
+    Diff,
+}
+
+/// Work the dashboard asks for in response to a key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DashboardAction {
+    Quit,
+    /// Poll the sessions now
+    Refresh,
+    ShowActivities(String),
+    ShowDiff(String),
+    /// Approve the plan of a session
+    Approve(String),
+    /// Send a message (second) to a session (first)
+    SendMessage(String, String),
+    /// Open a session in the browser
+    Open(String),
+}
+
+/// State of the dashboard between key presses and polls
+#[derive(Debug, Default)]
+pub struct Dashboard {
+    /// Sessions passing the monitor filters, as last polled
+    pub sessions: Vec<Session>,
+    /// Index of the selected session
+    pub selected: usize,
+    pub view: DashboardView,
+    /// Lines of the activities or diff view
+    pub detail: Vec<String>,
+    /// First line shown of the detail view
+    pub scroll: usize,
+    /// Message being written to the selected session
+    pub input: Option<String>,
+    /// Outcome of the last action or poll
+    pub status: String,
+}
+
+impl Dashboard {
+    /// Replace the session list, keeping the same session selected if it
+    /// is still listed
+    pub fn set_sessions(&mut self, sessions: Vec<Session>) {
+        let selected_id = self.selected_session().map(|session| session.id.clone());
+        self.sessions = sessions;
+        self.selected = selected_id
+            .and_then(|id| self.sessions.iter().position(|session| session.id == id))
+            .unwrap_or(self.selected)
+            .min(self.sessions.len().saturating_sub(1));
+    }
+
+    /// The session keys act on
+    pub fn selected_session(&self) -> Option<&Session> {
+        self.sessions.get(self.selected)
+    }
+
+    /// Show `lines` in a detail view, from the top
+    pub fn show_detail(&mut self, view: DashboardView, lines: Vec<String>) {
+        self.view = view;
+        self.detail = lines;
+        self.scroll = 0;
+    }
+
+    /// Update the state for a key press; returns the work it asks for
+    pub fn handle_key(&mut self, key: KeyCode) -> Option<DashboardAction> {
+        if let Some(input) = &mut self.input {
+            match key {
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Esc => self.input = None,
+                KeyCode::Enter => {
+                    let message = self.input.take().unwrap_or_default();
+                    let id = self.selected_session()?.id.clone();
+                    if !message.trim().is_empty() {
+                        return Some(DashboardAction::SendMessage(id, message));
+                    }
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        // Keys acting on the selected session work in every view
+        match key {
+            KeyCode::Char('a') => {
+                let session = self.selected_session()?;
+                if session.state == Some(State::AwaitingPlanApproval) {
+                    return Some(DashboardAction::Approve(session.id.clone()));
+                }
+                self.status = format!("Session {} has no plan awaiting approval", session.id);
+                return None;
+            }
+            KeyCode::Char('m') => {
+                self.selected_session()?;
+                self.input = Some(String::new());
+                return None;
+            }
+            KeyCode::Char('o') => {
+                return Some(DashboardAction::Open(self.selected_session()?.id.clone()));
+            }
+            KeyCode::Char('r') => return Some(DashboardAction::Refresh),
+            _ => {}
+        }
+
+        match self.view {
+            DashboardView::List => match key {
+                KeyCode::Char('q') | KeyCode::Esc => return Some(DashboardAction::Quit),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.selected = (self.selected + 1).min(self.sessions.len().saturating_sub(1));
+                }
+                KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Enter => {
+                    let id = self.selected_session()?.id.clone();
+                    return Some(DashboardAction::ShowActivities(id));
+                }
+                KeyCode::Char('d') => {
+                    return Some(DashboardAction::ShowDiff(
+                        self.selected_session()?.id.clone(),
+                    ));
+                }
+                _ => {}
+            },
+            DashboardView::Activities | DashboardView::Diff => {
+                let last_line = self.detail.len().saturating_sub(1);
+                match key {
+                    KeyCode::Char('q') | KeyCode::Esc | KeyCode::Left | KeyCode::Backspace => {
+                        self.view = DashboardView::List;
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        self.scroll = (self.scroll + 1).min(last_line);
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+                    KeyCode::PageDown | KeyCode::Char(' ') => {
+                        self.scroll = (self.scroll + PAGE_LINES).min(last_line);
+                    }
+                    KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE_LINES),
+                    KeyCode::Char('d') if self.view == DashboardView::Activities => {
+                        return Some(DashboardAction::ShowDiff(
+                            self.selected_session()?.id.clone(),
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Lines of the activities view, oldest first
+pub fn activity_lines(activities: &[Activity]) -> Vec<String> {
+    let mut sorted: Vec<&Activity> = activities.iter().collect();
+    sorted.sort_by(|a, b| a.create_time.cmp(&b.create_time));
+
+    let mut lines = Vec::new();
+    for activity in sorted {
+        lines.push(format!(
+            "{}  {}",
+            display_timestamp(&activity.create_time),
+            activity.activity_type()
+        ));
+        let content = activity
+            .content()
+            .or_else(|| activity.description.clone())
+            .unwrap_or_default();
+        lines.extend(content.lines().map(|line| format!("    {}", line)));
+    }
+    if lines.is_empty() {
+        lines.push("No activities yet.".to_string());
+    }
+    lines
+}
+
+/// Lines of the diff view
+pub fn diff_lines(activities: &[Activity]) -> Vec<String> {
+    let lines: Vec<String> = collect_patches(activities)
+        .iter()
+        .flat_map(|patch| patch.patch.lines().map(str::to_string))
+        .collect();
+    if lines.is_empty() {
+        return vec!["No patches yet.".to_string()];
+    }
+    lines
+}
+
+/// Run the interactive dashboard over the sessions passing `filter`,
+/// polling every `interval` seconds
+pub async fn handle_monitor_tui(filter: &SessionFilter, interval: u64) -> Result<()> {
+    if !io::stdout().is_terminal() {
+        anyhow::bail!("monitor --tui needs a terminal; use --format ndjson when piping");
+    }
+    let config = load_config()?;
+    let client = export_metrics(api_client(&config)?, &config);
+
+    let mut terminal = ratatui::init();
+    let result = run(
+        &mut terminal,
+        &client,
+        filter,
+        interval,
+        config.cache.enabled,
+    )
+    .await;
+    ratatui::restore();
+    result
+}
+
+async fn run(
+    terminal: &mut DefaultTerminal,
+    client: &JulesClient,
+    filter: &SessionFilter,
+    interval: u64,
+    cache_enabled: bool,
+) -> Result<()> {
+    let mut dashboard = Dashboard::default();
+    let mut last_poll: Option<Instant> = None;
+
+    loop {
+        if last_poll.is_none_or(|poll| poll.elapsed() >= Duration::from_secs(interval)) {
+            poll_sessions(client, filter, cache_enabled, &mut dashboard).await;
+            last_poll = Some(Instant::now());
+        }
+        terminal.draw(|frame| draw(frame, &dashboard))?;
+
+        if !event::poll(KEY_POLL)? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Ok(());
+        }
+        let Some(action) = dashboard.handle_key(key.code) else {
+            continue;
+        };
+
+        match action {
+            DashboardAction::Quit => return Ok(()),
+            DashboardAction::Refresh => last_poll = None,
+            action => {
+                dashboard.status = "Working...".to_string();
+                terminal.draw(|frame| draw(frame, &dashboard))?;
+                if let Err(e) = perform(client, action, &mut dashboard).await {
+                    dashboard.status = format!("Error: {:#}", e);
+                }
+                // Actions change sessions; show their new state soon
+                if dashboard.view == DashboardView::List {
+                    last_poll = None;
+                }
+            }
+        }
+    }
+}
+
+/// Fetch the sessions into `dashboard`; an error leaves the last list shown
+async fn poll_sessions(
+    client: &JulesClient,
+    filter: &SessionFilter,
+    cache_enabled: bool,
+    dashboard: &mut Dashboard,
+) {
+    match client.list_sessions(Some(100), None).await {
+        Ok(response) => {
+            if cache_enabled {
+                remember_sessions(&response.sessions);
+            }
+            let sessions = response
+                .sessions
+                .into_iter()
+                .filter(|session| filter.matches(session))
+                .collect();
+            dashboard.set_sessions(sessions);
+            if dashboard.status.is_empty() || dashboard.status.starts_with("Updated") {
+                dashboard.status = format!("Updated {}", Local::now().format("%H:%M:%S"));
+            }
+        }
+        Err(e) => dashboard.status = format!("Error fetching sessions: {}", e),
+    }
+}
+
+/// Carry out an action on a session, reporting the outcome in the status
+async fn perform(
+    client: &JulesClient,
+    action: DashboardAction,
+    dashboard: &mut Dashboard,
+) -> Result<()> {
+    match action {
+        DashboardAction::ShowActivities(id) => {
+            let activities = fetch_all_activities(client, &id).await?;
+            dashboard.show_detail(DashboardView::Activities, activity_lines(&activities));
+            dashboard.status = format!("Activities of session {}", id);
+        }
+        DashboardAction::ShowDiff(id) => {
+            let activities = fetch_all_activities(client, &id).await?;
+            dashboard.show_detail(DashboardView::Diff, diff_lines(&activities));
+            dashboard.status = format!("Diff of session {}", id);
+        }
+        DashboardAction::Approve(id) => {
+            client.approve_plan(&id).await?;
+            dashboard.status = format!("Plan approved for session {}", id);
+        }
+        DashboardAction::SendMessage(id, message) => {
+            client.send_message(&id, &message).await?;
+            dashboard.status = format!("Message sent to session {}", id);
+        }
+        DashboardAction::Open(id) => {
+            let session = client.get_session(&id).await?;
+            let url = session_open_url(&session, false)?;
+            open_url(&url)?;
+            dashboard.status = format!("Opened {}", url);
+        }
+        DashboardAction::Quit | DashboardAction::Refresh => {}
+    }
+    Ok(())
+}
+
+fn draw(frame: &mut Frame, dashboard: &Dashboard) {
+    let [main, status, help] = Layout::vertical([
+        Constraint::Min(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    match dashboard.view {
+        DashboardView::List => {
+            let rows = dashboard.sessions.iter().map(|session| {
+                let state = session
+                    .state
+                    .map(|state| state.display_name())
+                    .unwrap_or("Unknown");
+                Row::new([
+                    session.id.clone(),
+                    session.title.clone().unwrap_or_default(),
+                    state.to_string(),
+                    session
+                        .update_time
+                        .as_deref()
+                        .map(display_timestamp)
+                        .unwrap_or_default(),
+                ])
+                .style(Style::new().fg(state_color(session.state)))
+            });
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(20),
+                    Constraint::Fill(1),
+                    Constraint::Length(24),
+                    Constraint::Length(12),
+                ],
+            )
+            .header(Row::new(["ID", "Title", "State", "Updated"]).bold())
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .block(Block::bordered().title(format!(" Sessions ({}) ", dashboard.sessions.len())));
+            let mut state = TableState::default().with_selected(Some(dashboard.selected));
+            frame.render_stateful_widget(table, main, &mut state);
+        }
+        view => {
+            let title = match (view, dashboard.selected_session()) {
+                (DashboardView::Diff, Some(session)) => format!(" Diff: {} ", session.id),
+                (_, Some(session)) => format!(" Activities: {} ", session.id),
+                (_, None) => String::new(),
+            };
+            let lines: Vec<Line> = dashboard
+                .detail
+                .iter()
+                .skip(dashboard.scroll)
+                .map(|line| match view {
+                    DashboardView::Diff => diff_line(line),
+                    _ => Line::raw(line.as_str()),
+                })
+                .collect();
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::bordered().title(title)),
+                main,
+            );
+        }
+    }
+
+    let status_line = match &dashboard.input {
+        Some(input) => Line::from(vec![
+            Span::raw("Message: ").bold(),
+            Span::raw(input.as_str()),
+            Span::raw("█"),
+        ]),
+        None => Line::raw(dashboard.status.as_str()),
+    };
+    frame.render_widget(Paragraph::new(status_line), status);
+
+    let keys = match (&dashboard.input, dashboard.view) {
+        (Some(_), _) => "Enter send  Esc cancel",
+        (None, DashboardView::List) => {
+            "↑↓ select  Enter activities  d diff  a approve  m message  o open  r refresh  q quit"
+        }
+        (None, DashboardView::Activities) => {
+            "↑↓ scroll  d diff  a approve  m message  o open  Esc back"
+        }
+        (None, DashboardView::Diff) => "↑↓ scroll  a approve  m message  o open  Esc back",
+    };
+    frame.render_widget(Paragraph::new(keys).dim(), help);
+}
+
+/// A diff line colored like `git diff`
+fn diff_line(line: &str) -> Line<'_> {
+    let color = if line.starts_with("+++") || line.starts_with("---") {
+        Color::White
+    } else if line.starts_with('+') {
+        Color::Green
+    } else if line.starts_with('-') {
+        Color::Red
+    } else if line.starts_with("@@") {
+        Color::Cyan
+    } else {
+        return Line::raw(line);
+    };
+    Line::styled(line, Style::new().fg(color))
+}
+
+fn state_color(state: Option<State>) -> Color {
+    match state {
+        Some(State::Completed) => Color::Green,
+        Some(State::Failed) => Color::Red,
+        Some(State::Paused) => Color::DarkGray,
+        Some(State::AwaitingPlanApproval | State::AwaitingUserFeedback) => Color::Yellow,
+        _ => Color::Reset,
+    }
+}
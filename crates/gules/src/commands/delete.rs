@@ -0,0 +1,188 @@
+//! Deleting sessions, one at a time or in bulk by state and age.
+//!
+//! Deleted sessions are also dropped from the local session and activity
+//! caches, so they don't linger in `--cached` listings.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use jules_core::display::{display_sessions_table, icon, is_quiet};
+use jules_core::progress::Spinner;
+use jules_core::prompt::confirm;
+use jules_core::session_cache::{forget_session, remember_sessions};
+use jules_core::time_range::parse_duration;
+use jules_core::{api_client, load_config, open_store, CacheConfig};
+use jules_rs::types::session::Session;
+use jules_rs::JulesClient;
+
+use crate::extended_commands::state_matches;
+
+/// Page size when listing sessions to delete
+const LIST_PAGE_SIZE: u32 = 100;
+
+/// Values of `--state`
+const STATES: &[&str] = &["active", "completed", "failed", "paused"];
+
+/// Delete a single session after confirmation
+pub async fn handle_delete(session_id: &str, yes: bool) -> Result<()> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+
+    let session = client.get_session(session_id).await?;
+    let title = session.title.as_deref().unwrap_or("Untitled");
+    if !confirm(&format!("Delete session {} ({})?", session.id, title), yes)? {
+        println!("Session not deleted.");
+        return Ok(());
+    }
+
+    client.delete_session(&session.id).await?;
+    forget_deleted(&config.cache, &session.id);
+
+    if !is_quiet() {
+        println!("{} Deleted session: {}", icon("✅", "OK"), session.id);
+    }
+    Ok(())
+}
+
+/// Delete every session in `state` and/or created more than `older_than`
+/// ago (e.g. "30d"), after listing them and asking for confirmation
+pub async fn handle_delete_matching(
+    state: Option<&str>,
+    older_than: Option<&str>,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    if let Some(state) = state {
+        if !STATES.contains(&state.to_lowercase().as_str()) {
+            anyhow::bail!(
+                "Unknown state: {}. Valid options: {}",
+                state,
+                STATES.join(", ")
+            );
+        }
+    }
+    let cutoff = older_than
+        .map(parse_duration)
+        .transpose()?
+        .map(|age| Utc::now() - age);
+
+    let config = load_config()?;
+    let client = api_client(&config)?;
+
+    let sessions = list_all_sessions(&client, config.cache.enabled).await?;
+    let matching: Vec<Session> = sessions
+        .into_iter()
+        .filter(|session| is_match(session, state, cutoff))
+        .collect();
+
+    if matching.is_empty() {
+        println!("No sessions match.");
+        return Ok(());
+    }
+    let total = matching.len();
+    if dry_run {
+        display_sessions_table(&matching);
+        println!("{} session(s) would be deleted", total);
+        return Ok(());
+    }
+    if !is_quiet() {
+        display_sessions_table(&matching);
+    }
+    if !confirm(&format!("Delete {} session(s)?", total), yes)? {
+        println!("No sessions deleted.");
+        return Ok(());
+    }
+
+    let spinner = Spinner::start(format!("Deleting 0/{} sessions...", total));
+    let mut errors = Vec::new();
+    for (done, session) in matching.iter().enumerate() {
+        spinner.set_message(format!("Deleting {}/{} sessions...", done + 1, total));
+        match client.delete_session(&session.id).await {
+            Ok(()) => forget_deleted(&config.cache, &session.id),
+            Err(e) => {
+                spinner.suspend(|| {
+                    eprintln!(
+                        "{} Failed to delete session {}: {:#}",
+                        icon("❌", "ERROR"),
+                        session.id,
+                        e
+                    )
+                });
+                errors.push(e);
+            }
+        }
+    }
+    spinner.finish();
+
+    if !is_quiet() {
+        println!(
+            "{} Deleted {} of {} session(s)",
+            icon("✅", "OK"),
+            total - errors.len(),
+            total
+        );
+    }
+    let failed = errors.len();
+    match errors.into_iter().next() {
+        Some(error) => Err(error.context(format!("Failed to delete {} session(s)", failed))),
+        None => Ok(()),
+    }
+}
+
+/// Whether a session passes the state filter and was created before `cutoff`
+///
+/// Sessions without a state or creation time never match a filter on it.
+fn is_match(session: &Session, state: Option<&str>, cutoff: Option<DateTime<Utc>>) -> bool {
+    let state_ok = state.is_none_or(|filter| {
+        session
+            .state
+            .is_some_and(|session_state| state_matches(filter, session_state))
+    });
+    let age_ok = cutoff.is_none_or(|cutoff| {
+        session
+            .create_time
+            .as_deref()
+            .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+            .is_some_and(|created| created < cutoff)
+    });
+    state_ok && age_ok
+}
+
+/// Every session of the account, following all pages
+pub(crate) async fn list_all_sessions(
+    client: &JulesClient,
+    cache_enabled: bool,
+) -> Result<Vec<Session>> {
+    let spinner = Spinner::start("Listing sessions...");
+    let mut sessions = Vec::new();
+    let mut page_token: Option<String> = None;
+
+    loop {
+        let response = client
+            .list_sessions(Some(LIST_PAGE_SIZE), page_token.as_deref())
+            .await?;
+        if cache_enabled {
+            remember_sessions(&response.sessions);
+        }
+        sessions.extend(response.sessions);
+        spinner.set_message(format!("Listing sessions... {}", sessions.len()));
+
+        page_token = response.next_page_token;
+        if page_token.is_none() {
+            break;
+        }
+    }
+
+    spinner.finish();
+    Ok(sessions)
+}
+
+/// Drop a deleted session from the local caches (best effort)
+fn forget_deleted(cache: &CacheConfig, session_id: &str) {
+    if !cache.enabled {
+        return;
+    }
+    forget_session(session_id);
+    if let Err(e) = open_store(cache).and_then(|mut store| store.delete_session(session_id)) {
+        eprintln!("Warning: Failed to delete cached activities: {}", e);
+    }
+}
@@ -0,0 +1,179 @@
+//! Git patches produced by a session.
+//!
+//! Prints the patches from a session's change set artifacts as a colored
+//! diff or a `--stat` summary, or saves them as `.patch` files, so they can
+//! be applied with `git apply`.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use jules_core::activity_cache::fetch_all_activities;
+use jules_core::display::{display_width, pad_to_width, terminal_width};
+use jules_core::patch_files::{collect_patches, diff_stat, write_patch_files, FileStat};
+use jules_core::{api_client, load_config, open_store};
+use jules_rs::types::activity::Activity;
+use std::path::Path;
+
+use super::filter_activities::{refresh_activity_cache, sync_activity_cache};
+
+/// Activities of a session, from the cache when enabled
+pub(crate) async fn session_activities(session_id: &str) -> Result<Vec<Activity>> {
+    load_activities(session_id, false).await
+}
+
+/// Activities of a session fetched from the API even when the cache is
+/// within its TTL, for reads that must see the newest activity (e.g. the
+/// plan awaiting approval)
+pub(crate) async fn fresh_session_activities(session_id: &str) -> Result<Vec<Activity>> {
+    load_activities(session_id, true).await
+}
+
+async fn load_activities(session_id: &str, refresh: bool) -> Result<Vec<Activity>> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+
+    if !config.cache.enabled {
+        return fetch_all_activities(&client, session_id).await;
+    }
+    if refresh {
+        let mut store = open_store(&config.cache)?;
+        sync_activity_cache(&client, store.as_mut(), &config.cache, session_id).await?;
+    } else {
+        refresh_activity_cache(&client, &config.cache, session_id).await?;
+    }
+    let cached = open_store(&config.cache)?.peek_session(session_id)?;
+    Ok(cached.map(|cache| cache.activities).unwrap_or_default())
+}
+
+/// Print a session's patches, or save them to `save_dir`
+///
+/// `latest` keeps only the newest patch; `stat` prints per-file line counts
+/// instead of the diff.
+pub async fn handle_diff(
+    session_id: &str,
+    save_dir: Option<&str>,
+    latest: bool,
+    stat: bool,
+) -> Result<()> {
+    let activities = session_activities(session_id).await?;
+
+    let Some(dir) = save_dir else {
+        let mut patches = collect_patches(&activities);
+        if latest {
+            patches = patches.pop().into_iter().collect();
+        }
+        if patches.is_empty() {
+            eprintln!("No patches found for session {}", session_id);
+        } else if stat {
+            print_diff_stat(&diff_stat(patches.iter().map(|patch| patch.patch.as_str())));
+        } else {
+            for patch in patches {
+                for line in patch.patch.lines() {
+                    println!("{}", colorize_diff_line(line));
+                }
+            }
+        }
+        return Ok(());
+    };
+
+    let dir = Path::new(dir);
+    let paths = write_patch_files(dir, &activities)
+        .with_context(|| format!("Failed to save patches to {}", dir.display()))?;
+    if paths.is_empty() {
+        println!("No patches found for session {}", session_id);
+        return Ok(());
+    }
+    for path in &paths {
+        println!("{}", path.display());
+    }
+    println!(
+        "\nSaved {} patch(es). Apply with: git apply {}/*.patch",
+        paths.len(),
+        dir.display()
+    );
+    Ok(())
+}
+
+/// Color a diff line like `git diff` (no-op when colors are disabled)
+fn colorize_diff_line(line: &str) -> String {
+    if line.starts_with("diff ")
+        || line.starts_with("index ")
+        || line.starts_with("--- ")
+        || line.starts_with("+++ ")
+    {
+        line.bold().to_string()
+    } else if line.starts_with("@@") {
+        line.cyan().to_string()
+    } else if line.starts_with('+') {
+        line.green().to_string()
+    } else if line.starts_with('-') {
+        line.red().to_string()
+    } else {
+        line.to_string()
+    }
+}
+
+/// Widest `+`/`-` bar of `--stat` output
+const MAX_STAT_BAR: usize = 50;
+
+/// Print per-file counts and a summary line, like `git diff --stat`
+fn print_diff_stat(stats: &[FileStat]) {
+    let path_width = stats
+        .iter()
+        .map(|stat| display_width(&stat.path))
+        .max()
+        .unwrap_or(0);
+    let count_width = stats
+        .iter()
+        .map(|stat| (stat.insertions + stat.deletions).to_string().len())
+        .max()
+        .unwrap_or(1);
+    let most = stats
+        .iter()
+        .map(|stat| stat.insertions + stat.deletions)
+        .max()
+        .unwrap_or(0);
+    // Leave room for the path, the count and the separators
+    let bar_width = terminal_width()
+        .map(|width| width.saturating_sub(path_width + count_width + 5))
+        .unwrap_or(MAX_STAT_BAR)
+        .clamp(10, MAX_STAT_BAR);
+    let scale = |n: usize| {
+        if most <= bar_width {
+            n
+        } else {
+            // Any change gets at least one mark
+            (n * bar_width).div_ceil(most)
+        }
+    };
+
+    for stat in stats {
+        println!(
+            " {} | {:>width$} {}{}",
+            pad_to_width(&stat.path, path_width),
+            stat.insertions + stat.deletions,
+            "+".repeat(scale(stat.insertions)).green(),
+            "-".repeat(scale(stat.deletions)).red(),
+            width = count_width
+        );
+    }
+
+    let insertions: usize = stats.iter().map(|stat| stat.insertions).sum();
+    let deletions: usize = stats.iter().map(|stat| stat.deletions).sum();
+    println!(
+        " {} file{} changed, {} insertion{}(+), {} deletion{}(-)",
+        stats.len(),
+        plural(stats.len()),
+        insertions,
+        plural(insertions),
+        deletions,
+        plural(deletions)
+    );
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
@@ -0,0 +1,398 @@
+//! Configuration and connectivity diagnostics.
+//!
+//! `gules doctor` checks everything a command needs before it can talk to
+//! Jules (config files, API key, API reachability, GitHub token, gh CLI,
+//! cache directory)
+//! and prints a fix for each problem.
+
+use anyhow::Result;
+use jules_core::activity_cache::get_cache_dir;
+use jules_core::{
+    api_base_url, config_overrides, find_project_config, get_config_path, icon,
+    merge_project_config, read_config, Config,
+};
+use jules_rs::{HttpError, JulesClient, JulesConfig, RetryPolicy};
+use serde_json::json;
+use std::fs;
+use std::process::Command;
+
+use crate::github::{check_app, check_token, github_token};
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    /// Works, but something is missing or degraded
+    Warn,
+    /// Commands will fail until this is fixed
+    Fail,
+}
+
+/// Result of a diagnostic check
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+    /// What to do about a warning or failure
+    pub fix: Option<String>,
+}
+
+impl CheckStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "fail",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            CheckStatus::Ok => icon("✅", "OK"),
+            CheckStatus::Warn => icon("⚠️ ", "WARN"),
+            CheckStatus::Fail => icon("❌", "FAIL"),
+        }
+    }
+}
+
+impl Check {
+    fn ok(name: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &'static str, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &'static str, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Read the config file without creating it (unlike `load_config`)
+fn check_config() -> (Check, Option<Config>) {
+    let path = match get_config_path() {
+        Ok(path) => path,
+        Err(e) => {
+            return (
+                Check::fail("config", e.to_string(), "Set $HOME or $XDG_CONFIG_HOME"),
+                None,
+            )
+        }
+    };
+    if !path.exists() {
+        return (
+            Check::warn(
+                "config",
+                format!("No config file at {} (using defaults)", path.display()),
+                "Run 'gules init'",
+            ),
+            Some(Config::default()),
+        );
+    }
+
+    match read_config(&path) {
+        Ok(config) => (
+            Check::ok("config", format!("{} is valid", path.display())),
+            Some(config),
+        ),
+        Err(e) => (
+            Check::fail(
+                "config",
+                format!("{} is invalid: {:#}", path.display(), e),
+                "Fix the file with 'gules config edit'",
+            ),
+            None,
+        ),
+    }
+}
+
+/// Validate the project-local config, if there is one, and merge it over
+/// `config`
+fn check_project_config(config: Option<Config>) -> (Option<Check>, Option<Config>) {
+    let Some(path) = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| find_project_config(&cwd))
+    else {
+        return (None, config);
+    };
+    let Some(config) = config else {
+        return (None, None);
+    };
+
+    match merge_project_config(&config, &path) {
+        Ok(merged) => (
+            Some(Check::ok("project", format!("{} is valid", path.display()))),
+            Some(merged),
+        ),
+        Err(e) => (
+            Some(Check::fail(
+                "project",
+                format!("{:#}", e),
+                format!("Fix or remove {}", path.display()),
+            )),
+            Some(config),
+        ),
+    }
+}
+
+/// The API key commands would use, and where it comes from
+fn find_api_key(config: Option<&Config>) -> Option<(String, &'static str)> {
+    let overrides = config_overrides();
+    if let Some(key) = overrides.api_key {
+        return Some((key, "--api-key"));
+    }
+    let profile_key = overrides
+        .profile
+        .and_then(|name| config?.profiles.get(&name)?.api_key.clone());
+    if let Some(key) = profile_key {
+        return Some((key, "--profile"));
+    }
+    if let Ok(key) = std::env::var("JULES_API_KEY") {
+        if !key.trim().is_empty() {
+            return Some((key, "JULES_API_KEY"));
+        }
+    }
+    config
+        .and_then(|config| config.api_key.clone())
+        .filter(|key| !key.trim().is_empty())
+        .map(|key| (key, "config file"))
+}
+
+fn check_api_key(api_key: Option<&(String, &'static str)>) -> Check {
+    match api_key {
+        Some((_, source)) => Check::ok("api_key", format!("Found in {}", source)),
+        None => Check::fail(
+            "api_key",
+            "No API key configured",
+            "Get a key at https://jules.google.com/settings, then run \
+             'gules config set api_key <KEY>' or export JULES_API_KEY",
+        ),
+    }
+}
+
+pub(crate) async fn check_api(
+    api_key: Option<&(String, &'static str)>,
+    config: Option<&Config>,
+) -> Check {
+    let Some((api_key, _)) = api_key else {
+        return Check::warn("api", "Skipped (no API key)", "Configure an API key first");
+    };
+    let base_url = api_base_url(config.unwrap_or(&Config::default()));
+
+    let client = JulesClient::with_config(JulesConfig {
+        api_key: api_key.clone(),
+        base_url: base_url.clone(),
+    })
+    .with_retry_policy(RetryPolicy::none());
+
+    match client.list_sessions(Some(1), None).await {
+        Ok(_) => Check::ok("api", format!("{} is reachable", base_url)),
+        Err(e) => match e.downcast_ref::<HttpError>().map(|e| e.status) {
+            Some(401 | 403) => Check::fail(
+                "api",
+                format!("API key was rejected: {}", e),
+                "Check the key at https://jules.google.com/settings",
+            ),
+            Some(_) => Check::fail(
+                "api",
+                format!("API error: {}", e),
+                "Retry later; if it persists, check the API URL with 'gules config get api_url'",
+            ),
+            None => Check::fail(
+                "api",
+                format!("Cannot reach {}: {:#}", base_url, e),
+                "Check your network connection and proxy settings",
+            ),
+        },
+    }
+}
+
+/// Check the GitHub App installation or else the GitHub token, if any,
+/// against the GitHub API
+pub(crate) async fn check_github(config: Option<&Config>) -> Check {
+    if let Some(config) = config.filter(|config| config.github.has_app()) {
+        return match check_app(config).await {
+            Ok(info) => match info.permission_problem() {
+                Some(problem) => Check::warn(
+                    "github",
+                    format!("GitHub App installed on {}, but {}", info.account, problem),
+                    "Grant the app access to issues and pull requests in its settings on GitHub",
+                ),
+                None => Check::ok(
+                    "github",
+                    format!("GitHub App installed on {}", info.account),
+                ),
+            },
+            Err(e) => Check::fail(
+                "github",
+                format!("{:#}", e),
+                "Fix the github.app_* settings with 'gules config set'",
+            ),
+        };
+    }
+    let Some(token) = config.and_then(github_token) else {
+        return Check::ok("github", "No GitHub token; GitHub commands use the gh CLI");
+    };
+    match check_token(&token).await {
+        Ok(info) => match info.scope_problem() {
+            Some(problem) => Check::warn(
+                "github",
+                format!(
+                    "Token from {} for {}, but {}",
+                    token.source, info.login, problem
+                ),
+                "Create a token with the `repo` scope at https://github.com/settings/tokens",
+            ),
+            None => Check::ok(
+                "github",
+                format!("Token from {} for {}", token.source, info.login),
+            ),
+        },
+        Err(e) => Check::fail(
+            "github",
+            format!("{:#}", e),
+            "Replace the token, e.g. with 'gules github login'",
+        ),
+    }
+}
+
+/// Check the gh CLI, only needed for GitHub commands without a token
+pub(crate) fn check_gh(has_token: bool) -> Check {
+    let version = Command::new("gh").arg("--version").output();
+    let Some(version) = version.ok().filter(|output| output.status.success()) else {
+        if has_token {
+            return Check::ok(
+                "gh",
+                "GitHub CLI (gh) not found; using the GitHub API instead",
+            );
+        }
+        return Check::warn(
+            "gh",
+            "GitHub CLI (gh) not found and no GitHub token; issue-status and pr-status are unavailable",
+            "Set GITHUB_TOKEN or run 'gules github login', or install gh from https://cli.github.com",
+        );
+    };
+    let version = String::from_utf8_lossy(&version.stdout)
+        .lines()
+        .next()
+        .unwrap_or("gh")
+        .to_string();
+
+    let authenticated = Command::new("gh")
+        .args(["auth", "status"])
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if authenticated {
+        Check::ok("gh", format!("{} (authenticated)", version))
+    } else {
+        Check::warn(
+            "gh",
+            format!("{} (not authenticated)", version),
+            "Run 'gh auth login'",
+        )
+    }
+}
+
+fn check_cache_dir() -> Check {
+    let result = get_cache_dir().and_then(|dir| {
+        fs::create_dir_all(&dir)?;
+        let probe = dir.join(format!(".doctor.{}", std::process::id()));
+        fs::write(&probe, b"ok")?;
+        fs::remove_file(&probe)?;
+        Ok(dir)
+    });
+    match result {
+        Ok(dir) => Check::ok("cache", format!("{} is writable", dir.display())),
+        Err(e) => Check::fail(
+            "cache",
+            format!("Cache directory is not writable: {:#}", e),
+            "Fix the directory permissions, or disable caching with \
+             'gules config set cache.enabled false'",
+        ),
+    }
+}
+
+/// Run all checks
+pub async fn run_checks() -> Vec<Check> {
+    let (config_check, config) = check_config();
+    let (project_check, config) = check_project_config(config);
+    let config = config.map(|config| config_overrides().apply(config.clone()).unwrap_or(config));
+    let api_key = find_api_key(config.as_ref());
+
+    let mut checks = vec![config_check];
+    checks.extend(project_check);
+    checks.extend([
+        check_api_key(api_key.as_ref()),
+        check_api(api_key.as_ref(), config.as_ref()).await,
+        check_github(config.as_ref()).await,
+        check_gh(
+            config
+                .as_ref()
+                .is_some_and(|config| config.github.has_app() || github_token(config).is_some()),
+        ),
+        check_cache_dir(),
+    ]);
+    checks
+}
+
+/// Diagnose the configuration and connectivity
+pub async fn handle_doctor(json: bool) -> Result<()> {
+    let checks = run_checks().await;
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count();
+
+    if json {
+        let checks: Vec<_> = checks
+            .iter()
+            .map(|check| {
+                json!({
+                    "name": check.name,
+                    "status": check.status.as_str(),
+                    "message": check.message,
+                    "fix": check.fix,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&checks)?);
+    } else {
+        for check in &checks {
+            println!(
+                "{} {:<8} {}",
+                check.status.icon(),
+                check.name,
+                check.message
+            );
+            if let Some(fix) = &check.fix {
+                println!("   {:<8} → {}", "", fix);
+            }
+        }
+        println!();
+        if failed == 0 {
+            println!("No problems found.");
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} check(s) failed", failed);
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! Exporting a session to a standalone report (Markdown, HTML or JSON).
+//!
+//! The report holds the session details, prompt, and every activity oldest
+//! first: plan, progress, bash output, diffs and the pull request link.
+
+use anyhow::Result;
+use jules_core::atomic_file::write_atomic;
+use jules_core::display::{icon, is_quiet};
+use jules_core::html_output::session_html;
+use jules_core::markdown_output::session_markdown;
+use jules_core::{api_client, load_config};
+use std::path::Path;
+
+use super::diff::session_activities;
+
+/// Format of an exported report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "md" | "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            "json" => Ok(Self::Json),
+            _ => anyhow::bail!(
+                "Unknown export format: {}. Valid options: md, html, json",
+                s
+            ),
+        }
+    }
+
+    /// The format implied by a file extension (.html, .json), else Markdown
+    pub fn from_path(path: &str) -> Self {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("html" | "htm") => Self::Html,
+            Some("json") => Self::Json,
+            _ => Self::Markdown,
+        }
+    }
+}
+
+/// Write a session report to `output`, or to stdout
+///
+/// Without `format`, it follows the extension of `output` (Markdown by
+/// default).
+pub async fn handle_export(
+    session_id: &str,
+    format: Option<&str>,
+    output: Option<&str>,
+) -> Result<()> {
+    let format = match (format, output) {
+        (Some(format), _) => ExportFormat::parse(format)?,
+        (None, Some(output)) => ExportFormat::from_path(output),
+        (None, None) => ExportFormat::Markdown,
+    };
+
+    let config = load_config()?;
+    let client = api_client(&config)?;
+    let session = client.get_session(session_id).await?;
+
+    // Oldest first, so the report reads in order
+    let mut activities = session_activities(&session.id).await?;
+    activities.reverse();
+    let refs: Vec<_> = activities.iter().collect();
+
+    let report = match format {
+        ExportFormat::Markdown => session_markdown(&session, &refs),
+        ExportFormat::Html => session_html(&session, &refs),
+        ExportFormat::Json => {
+            let report = serde_json::json!({
+                "session": session,
+                "activities": activities,
+            });
+            serde_json::to_string_pretty(&report)? + "\n"
+        }
+    };
+
+    match output {
+        Some(path) => {
+            write_atomic(Path::new(path), report)?;
+            if !is_quiet() {
+                println!(
+                    "{} Exported session {} to {}",
+                    icon("✅", "OK"),
+                    session.id,
+                    path
+                );
+            }
+        }
+        None => print!("{}", report),
+    }
+    Ok(())
+}
@@ -4,7 +4,13 @@
 //! for efficient queries and offline access.
 
 use anyhow::{Context, Result};
-use jules_core::{activity_cache::*, get_api_key, load_config};
+use jules_core::activity_store::matches_query;
+use jules_core::display::save_response;
+use jules_core::patch_files::{prune_session_patches, save_session_patches};
+use jules_core::{
+    activity_cache::*, api_client, load_config, open_store, ActivityQuery, ActivityStore,
+    CacheConfig,
+};
 use jules_rs::{types::activity::Activity, JulesClient};
 
 /// Activity type filter
@@ -44,6 +50,19 @@ impl ActivityTypeFilter {
             Self::Failed => activity.session_failed.is_some(),
         }
     }
+
+    /// Type name as returned by `Activity::activity_type()`
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::AgentMessage => "Agent Messaged",
+            Self::UserMessage => "User Messaged",
+            Self::Plan => "Plan Generated",
+            Self::PlanApproved => "Plan Approved",
+            Self::Progress => "Progress Updated",
+            Self::Completed => "Session Completed",
+            Self::Failed => "Session Failed",
+        }
+    }
 }
 
 /// Output format
@@ -57,6 +76,14 @@ pub enum OutputFormat {
     Full,
     /// Content only (just the text, no metadata)
     ContentOnly,
+    /// Comma-separated values with a fixed column set
+    Csv,
+    /// Same data as JSON, as block-style YAML
+    Yaml,
+    /// Report for pasting into pull requests and issues
+    Markdown,
+    /// One compact JSON object per line
+    Ndjson,
 }
 
 impl OutputFormat {
@@ -66,8 +93,12 @@ impl OutputFormat {
             "table" => Ok(Self::Table),
             "full" => Ok(Self::Full),
             "content" | "content-only" => Ok(Self::ContentOnly),
+            "csv" => Ok(Self::Csv),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "ndjson" | "jsonl" => Ok(Self::Ndjson),
             _ => anyhow::bail!(
-                "Unknown output format: {}. Valid options: json, table, full, content-only",
+                "Unknown output format: {}. Valid options: json, table, full, content-only, csv, yaml, markdown, ndjson",
                 s
             ),
         }
@@ -75,92 +106,167 @@ impl OutputFormat {
 }
 
 /// Filter and fetch activities with caching
+///
+/// `query` holds the filters; its session is set to `session_id`. With an
+/// `output` file, the activities are saved there as JSON instead of printed.
 pub async fn filter_activities(
     session_id: &str,
-    last_n: Option<usize>,
-    type_filters: Vec<ActivityTypeFilter>,
-    has_bash_output: bool,
+    query: ActivityQuery,
     no_cache: bool,
     output_format: OutputFormat,
+    output: Option<&str>,
 ) -> Result<()> {
     // Load configuration
     let config = load_config()?;
-    let api_key = get_api_key(None, &config)?;
-    let client = JulesClient::new(api_key);
+    let client = api_client(&config)?;
 
     // Determine if caching is enabled
     let cache_enabled = config.cache.enabled && !no_cache;
-
-    // Get activities (from cache or API)
-    let activities = if cache_enabled {
-        get_activities_with_cache(&client, session_id).await?
-    } else {
-        fetch_all_activities(&client, session_id).await?
+    let query = ActivityQuery {
+        session_id: Some(session_id.to_string()),
+        ..query
     };
 
-    // Apply filters
-    let mut filtered = activities;
+    // Cached activities are filtered by the store (SQL for the SQLite backend)
+    let filtered = if cache_enabled {
+        let mut store = open_store(&config.cache)?;
+        ensure_cached(&client, store.as_mut(), &config.cache, session_id).await?;
 
-    // Filter by type
-    if !type_filters.is_empty() {
-        filtered.retain(|a| type_filters.iter().any(|f| f.matches(a)));
-    }
+        store.query(&query)?
+    } else {
+        let mut filtered = fetch_all_activities(&client, session_id).await?;
 
-    // Filter by bash output
-    if has_bash_output {
-        filtered.retain(|a| {
-            a.artifacts
-                .iter()
-                .any(|artifact| artifact.bash_output.is_some())
-        });
-    }
+        // Filter by type, bash output, failed commands and creation time
+        filtered.retain(|a| matches_query(a, &query));
+
+        // Take last N
+        if let Some(n) = query.limit {
+            filtered.truncate(n);
+        }
+        filtered
+    };
 
-    // Take last N
-    if let Some(n) = last_n {
-        filtered.truncate(n);
+    if let Some(path) = output {
+        let json = serde_json::to_value(&filtered).context("Failed to serialize activities")?;
+        return save_response(&json, Some(path.into()), true).await;
     }
 
     // Display results
-    display_activities(&filtered, output_format)?;
+    display_activities(&filtered, output_format)
+}
+
+/// Bring the configured cache backend up to date for a session
+///
+/// Returns the number of cached activities.
+pub(crate) async fn refresh_activity_cache(
+    client: &JulesClient,
+    config: &CacheConfig,
+    session_id: &str,
+) -> Result<usize> {
+    let mut store = open_store(config)?;
+    ensure_cached(client, store.as_mut(), config, session_id).await?;
+    let cached = store.peek_session(session_id)?;
+    Ok(cached.map(|cache| cache.activities.len()).unwrap_or(0))
+}
 
+/// Whether the cache can be served without an API call
+///
+/// Caches of completed sessions, and of running sessions younger than
+/// `ttl_seconds`, are fresh. An unreadable cache never is.
+fn is_store_fresh(store: &dyn ActivityStore, session_id: &str, ttl_seconds: u64) -> bool {
+    store.is_fresh(session_id, ttl_seconds).unwrap_or(false)
+}
+
+/// Serve a session from the cache or bring it up to date, recording how
+/// the read was served in the cache metrics
+async fn ensure_cached(
+    client: &JulesClient,
+    store: &mut dyn ActivityStore,
+    config: &CacheConfig,
+    session_id: &str,
+) -> Result<()> {
+    if !is_store_fresh(store, session_id, config.ttl_seconds) {
+        return sync_activity_cache(client, store, config, session_id).await;
+    }
+    let cached = store.peek_session(session_id)?;
+    record_access(
+        session_id,
+        CacheAccess::Hit,
+        cached.map_or(0, |cache| cache.activities.len()),
+    );
     Ok(())
 }
 
-/// Get activities with caching (incremental updates)
-async fn get_activities_with_cache(
+/// Best-effort update of the cache metrics (failures never break a command)
+fn record_access(session_id: &str, access: CacheAccess, cached_activities: usize) {
+    if let Err(e) = record_cache_access(session_id, access, cached_activities) {
+        eprintln!("Warning: Failed to update cache metrics: {:#}", e);
+    }
+}
+
+/// Fetch activities newer than the cached ones (everything if uncached)
+pub(crate) async fn sync_activity_cache(
     client: &JulesClient,
+    store: &mut dyn ActivityStore,
+    config: &CacheConfig,
     session_id: &str,
-) -> Result<Vec<Activity>> {
-    // Try to load from cache
-    let cached = load_session_cache(session_id)?;
-
-    if let Some(cache) = cached {
-        // Fetch only new activities using page token
-        let response = client
-            .list_activities(session_id, Some(50), cache.last_page_token.as_deref())
-            .await?;
-
-        // Update cache with new data
-        let updated_cache = update_cache_incremental(session_id, &response)?;
-        Ok(updated_cache.activities)
+) -> Result<()> {
+    // An unreadable cache (e.g. a half-written file) is dropped and
+    // refetched instead of failing the command
+    let known = match store.load_session(session_id) {
+        Ok(cached) => cached.map(|cache| cache.activities).unwrap_or_default(),
+        Err(e) => {
+            eprintln!(
+                "Warning: Ignoring unreadable cache for session {}: {:#}",
+                session_id, e
+            );
+            store.delete_session(session_id)?;
+            Vec::new()
+        }
+    };
+
+    let new_activities = fetch_new_activities(client, session_id, &known).await?;
+    let access = if known.is_empty() {
+        CacheAccess::Miss
     } else {
-        // No cache exists, fetch everything
-        let all_activities = fetch_all_activities(client, session_id).await?;
+        CacheAccess::IncrementalUpdate {
+            new_activities: new_activities.len(),
+        }
+    };
+    let cached_activities = known.len() + new_activities.len();
+    store.merge_activities(session_id, new_activities)?;
+    record_access(session_id, access, cached_activities);
+    if let Err(e) = retain_session_metrics(&store.list_sessions()?) {
+        eprintln!("Warning: Failed to update cache metrics: {:#}", e);
+    }
 
-        // Create initial cache
-        let response = jules_rs::types::activity::ListActivitiesResponse {
-            activities: all_activities.clone(),
-            next_page_token: None,
-        };
+    // Patch files are a plaintext convenience copy: never written next to an
+    // encrypted cache, and failing to write them never fails the sync
+    if config.encrypt {
+        return Ok(());
+    }
+    if let Err(e) = update_patch_files(store, session_id) {
+        eprintln!(
+            "Warning: Failed to extract patches for session {}: {:#}",
+            session_id, e
+        );
+    }
+    Ok(())
+}
 
-        update_cache_incremental(session_id, &response)?;
-        Ok(all_activities)
+/// Extract the session's git patches and drop those of evicted sessions
+fn update_patch_files(store: &dyn ActivityStore, session_id: &str) -> Result<()> {
+    if let Some(cache) = store.peek_session(session_id)? {
+        save_session_patches(session_id, &cache.activities)?;
     }
+    prune_session_patches(&store.list_sessions()?)
 }
 
 /// Display activities based on format
 fn display_activities(activities: &[Activity], format: OutputFormat) -> Result<()> {
-    if activities.is_empty() {
+    // CSV keeps its header so consumers still see the columns, and NDJSON
+    // stays machine-readable with no lines at all
+    if activities.is_empty() && !matches!(format, OutputFormat::Csv | OutputFormat::Ndjson) {
         println!("No activities found matching the filters.");
         return Ok(());
     }
@@ -245,6 +351,23 @@ fn display_activities(activities: &[Activity], format: OutputFormat) -> Result<(
                 }
             }
         }
+        OutputFormat::Csv => {
+            let refs: Vec<&Activity> = activities.iter().collect();
+            jules_core::csv_output::print_activities_csv(&refs)?;
+        }
+        OutputFormat::Yaml => {
+            print!("{}", jules_core::yaml_output::to_yaml(&activities)?);
+        }
+        OutputFormat::Markdown => {
+            let refs: Vec<&Activity> = activities.iter().collect();
+            print!(
+                "{}",
+                jules_core::markdown_output::activities_markdown(&refs)
+            );
+        }
+        OutputFormat::Ndjson => {
+            jules_core::ndjson_output::print_ndjson(activities)?;
+        }
     }
 
     Ok(())
@@ -0,0 +1,215 @@
+//! GitHub token management (`gules github`).
+//!
+//! `status` shows which token the GitHub integrations use, whose it is and
+//! whether its scopes suffice, or how the configured GitHub App is
+//! installed; `login` checks a token and stores it; `limits` shows how much
+//! of the API rate limits is left.
+
+use anyhow::{Context, Result};
+use chrono::{Local, Utc};
+use jules_core::display::{icon, is_quiet};
+use jules_core::github_cache::RateLimit;
+use jules_core::prompt::ask;
+use jules_core::{load_config, Config};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::io::{self, IsTerminal, Read};
+
+use crate::github::{
+    check_app, check_token, github_token, store_github_token, GitHubClient, GitHubToken,
+    TokenSource, TOKEN_ENV_VARS,
+};
+
+/// Show the GitHub token in use: its source, account and scopes; or the
+/// GitHub App installation, if one is set up
+pub async fn handle_github_status(format: &str) -> Result<()> {
+    let json = match format.to_lowercase().as_str() {
+        "json" => true,
+        "table" => false,
+        _ => anyhow::bail!("Unknown format: {}. Valid options: table, json", format),
+    };
+    let config = load_config()?;
+    if config.github.has_app() {
+        return app_status(&config, json).await;
+    }
+    let Some(token) = github_token(&config) else {
+        anyhow::bail!(
+            "No GitHub token. Set GITHUB_TOKEN, run 'gules github login', or set one with \
+             'gules config set github_token <TOKEN>'"
+        );
+    };
+    let info = check_token(&token).await?;
+    let problem = info.scope_problem();
+
+    if json {
+        let status = json!({
+            "login": info.login,
+            "source": token.source.to_string(),
+            "scopes": info.scopes,
+            "problem": problem,
+        });
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(());
+    }
+
+    println!("{} Logged in to GitHub as {}", icon("✓", "OK"), info.login);
+    println!("  Token: {}", token.source);
+    match &info.scopes {
+        Some(scopes) if scopes.is_empty() => println!("  Scopes: none"),
+        Some(scopes) => println!("  Scopes: {}", scopes.join(", ")),
+        None => println!("  Scopes: not reported (fine-grained token)"),
+    }
+    if let Some(problem) = problem {
+        eprintln!("Warning: {}", problem);
+    }
+    Ok(())
+}
+
+/// Show the GitHub App installation: its account, repositories and
+/// permissions
+async fn app_status(config: &Config, json: bool) -> Result<()> {
+    let info = check_app(config).await?;
+    let problem = info.permission_problem();
+
+    if json {
+        let status = json!({
+            "app_id": config.github.app_id,
+            "installation_id": config.github.app_installation_id,
+            "account": info.account,
+            "repository_selection": info.repository_selection,
+            "permissions": info.permissions.iter().cloned().collect::<BTreeMap<_, _>>(),
+            "problem": problem,
+        });
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} GitHub App {} installed on {}",
+        icon("✓", "OK"),
+        config.github.app_id.unwrap_or_default(),
+        info.account
+    );
+    println!(
+        "  Repositories: {}",
+        info.repository_selection.as_deref().unwrap_or("unknown")
+    );
+    let permissions: Vec<String> = info
+        .permissions
+        .iter()
+        .map(|(name, level)| format!("{}: {}", name, level))
+        .collect();
+    println!(
+        "  Permissions: {}",
+        if permissions.is_empty() {
+            "none".to_string()
+        } else {
+            permissions.join(", ")
+        }
+    );
+    if let Some(problem) = problem {
+        eprintln!("Warning: {}", problem);
+    }
+    Ok(())
+}
+
+/// Check a GitHub token and store it: read from stdin when piped, else
+/// asked for
+pub async fn handle_github_login() -> Result<()> {
+    let token = if io::stdin().is_terminal() {
+        ask("GitHub token (from https://github.com/settings/tokens):")?
+    } else {
+        let mut token = String::new();
+        io::stdin()
+            .read_to_string(&mut token)
+            .context("Failed to read the token from stdin")?;
+        token
+    };
+    let token = token.trim();
+    if token.is_empty() {
+        anyhow::bail!("No token given");
+    }
+
+    let info = check_token(&GitHubToken {
+        token: token.to_string(),
+        source: TokenSource::Login,
+    })
+    .await?;
+    if let Some(problem) = info.scope_problem() {
+        anyhow::bail!("Token not stored: {}", problem);
+    }
+
+    let source = store_github_token(token)?;
+    if !is_quiet() {
+        println!(
+            "{} Logged in to GitHub as {}; token stored in {}",
+            icon("✅", "OK"),
+            info.login,
+            source
+        );
+    }
+    if let Some(name) = TOKEN_ENV_VARS
+        .iter()
+        .find(|name| std::env::var(name).is_ok_and(|value| !value.trim().is_empty()))
+    {
+        eprintln!(
+            "Warning: {} is set and takes precedence over the stored token",
+            name
+        );
+    }
+    if load_config()?.github.has_app() {
+        eprintln!(
+            "Warning: a GitHub App is set up in the [github] settings and is used instead of \
+             any token"
+        );
+    }
+    Ok(())
+}
+
+/// Resources whose rate limits the GitHub integrations use up
+const USED_RESOURCES: [&str; 2] = ["core", "graphql"];
+
+/// Show how much of each GitHub API rate limit is left and when it resets
+pub async fn handle_github_limits(format: &str) -> Result<()> {
+    let json = match format.to_lowercase().as_str() {
+        "json" => true,
+        "table" => false,
+        _ => anyhow::bail!("Unknown format: {}. Valid options: table, json", format),
+    };
+    let config = load_config()?;
+    let github = GitHubClient::require(&config, "github limits")?;
+    let limits = github.rate_limits().await?;
+
+    if json {
+        let limits: Vec<&RateLimit> = limits.resources.values().collect();
+        println!("{}", serde_json::to_string_pretty(&limits)?);
+        return Ok(());
+    }
+
+    println!(
+        "{:<28} {:>8} {:>10} {:>8}  Resets",
+        "Resource", "Used", "Remaining", "Limit"
+    );
+    for limit in limits.resources.values() {
+        println!(
+            "{:<28} {:>8} {:>10} {:>8}  {}",
+            limit.resource,
+            limit.used,
+            limit.remaining,
+            limit.limit,
+            limit.reset.with_timezone(&Local).format("%H:%M")
+        );
+    }
+    let reserve = config.github.rate_limit_reserve();
+    for resource in USED_RESOURCES {
+        if let Some(limit) = limits.low(resource, reserve, Utc::now()) {
+            eprintln!(
+                "Warning: only {}; polling commands wait for the reset \
+                 (github.rate_limit_reserve is {})",
+                limit.summary(),
+                reserve
+            );
+        }
+    }
+    Ok(())
+}
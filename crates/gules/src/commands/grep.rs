@@ -0,0 +1,113 @@
+//! Full-text search across cached activities.
+//!
+//! Searches messages, bash commands and outputs, and git patches of every
+//! cached session, without calling the API. Plain text searches use the
+//! full-text index; regular expressions and searches of one kind of content
+//! scan the cached activities.
+
+use anyhow::{Context, Result};
+use jules_core::activity_cache::*;
+use jules_core::search_index::{grep_activities, grep_regex, ContentKind, SearchHit, SearchIndex};
+use jules_core::{load_config, open_store};
+
+/// Search cached activities for `pattern`, a regular expression with `regex`
+///
+/// `kind` restricts the search to bash, message or diff content.
+pub async fn handle_grep(
+    pattern: &str,
+    session_id: Option<&str>,
+    limit: usize,
+    format: &str,
+    regex: bool,
+    kind: Option<ContentKind>,
+) -> Result<()> {
+    let config = load_config()?;
+
+    if regex || kind.is_some() {
+        let matcher = grep_regex(pattern, !regex)?;
+        let store = open_store(&config.cache)?;
+        let sessions = match session_id {
+            Some(id) => vec![id.to_string()],
+            None => store.list_sessions()?,
+        };
+
+        let mut hits = Vec::new();
+        for id in &sessions {
+            match store.peek_session(id) {
+                Ok(Some(cache)) => hits.extend(grep_activities(
+                    &cache.session_id,
+                    &cache.activities,
+                    &matcher,
+                    kind,
+                )),
+                Ok(None) => {}
+                Err(e) => eprintln!("Warning: Skipping cache for session {}: {}", id, e),
+            }
+        }
+        // Newest first, like the index
+        hits.sort_by(|a, b| b.create_time.cmp(&a.create_time));
+        hits.truncate(limit);
+        return display_hits(&hits, format);
+    }
+
+    // Never write a plaintext index next to an encrypted cache
+    let mut index = if config.cache.encrypt {
+        SearchIndex::open_in_memory()?
+    } else {
+        SearchIndex::open_default()?
+    };
+
+    let store = open_store(&config.cache)?;
+    let sessions = store.list_sessions()?;
+    for id in &sessions {
+        match store.peek_session(id) {
+            Ok(Some(cache)) => refresh_session(&mut index, &cache)?,
+            Ok(None) => {}
+            Err(e) => eprintln!("Warning: Skipping cache for session {}: {}", id, e),
+        }
+    }
+    index.retain_sessions(&sessions)?;
+
+    let hits = index.search(pattern, session_id, limit)?;
+    display_hits(&hits, format)
+}
+
+/// Re-index a session if its cache changed since it was last indexed
+fn refresh_session(index: &mut SearchIndex, cache: &SessionCache) -> Result<()> {
+    if index.needs_update(&cache.session_id, cache.last_updated)? {
+        index.index_session(&cache.session_id, cache.last_updated, &cache.activities)?;
+    }
+    Ok(())
+}
+
+fn display_hits(hits: &[SearchHit], format: &str) -> Result<()> {
+    match format.to_lowercase().as_str() {
+        "json" => {
+            let json = serde_json::to_string_pretty(hits).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+        "table" => {
+            if hits.is_empty() {
+                println!("No cached activities match.");
+                return Ok(());
+            }
+            for hit in hits {
+                println!(
+                    "{}/{}  {}  {}",
+                    hit.session_id, hit.activity_id, hit.activity_type, hit.create_time
+                );
+                for line in hit.matches.iter().take(3) {
+                    println!("    {}", line);
+                }
+                if hit.matches.len() > 3 {
+                    println!("    ... {} more matching lines", hit.matches.len() - 3);
+                }
+            }
+        }
+        other => anyhow::bail!(
+            "Unknown output format: {}. Valid options: json, table",
+            other
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! Interactive setup (`gules init`).
+//!
+//! Asks for the API key and checks it against the API, reports whether the
+//! GitHub token or CLI works, offers the repository of the current
+//! directory as the default source, and saves the global config.
+
+use anyhow::Result;
+use jules_core::git::{git, github_repo};
+use jules_core::prompt::{ask, ask_yes_no};
+use jules_core::{get_config_path, icon, load_global_config, save_config, Config};
+use std::io::{self, IsTerminal};
+
+use super::doctor::{check_api, check_gh, check_github, CheckStatus};
+use crate::github::github_token;
+
+/// Walk through the configuration and save it
+pub async fn handle_init() -> Result<()> {
+    if !io::stdin().is_terminal() {
+        anyhow::bail!(
+            "gules init is interactive. Without a terminal, use \
+             'gules config set api_key <KEY>' or export JULES_API_KEY"
+        );
+    }
+
+    let path = get_config_path()?;
+    let mut config = load_global_config()?;
+    println!("Setting up gules ({})", path.display());
+
+    println!("\n1. Jules API key");
+    setup_api_key(&mut config).await?;
+
+    println!("\n2. GitHub access");
+    let has_token = config.github.has_app() || github_token(&config).is_some();
+    for check in [check_github(Some(&config)).await, check_gh(has_token)] {
+        println!("{} {}", check.status.icon(), check.message);
+        if let Some(fix) = &check.fix {
+            println!("   {}", fix);
+        }
+    }
+
+    println!("\n3. Default repository");
+    setup_default_source(&mut config)?;
+
+    save_config(&config)?;
+    println!(
+        "\n{} Configuration saved to {}",
+        icon("✅", "OK"),
+        path.display()
+    );
+    println!("Check everything with 'gules doctor', then create a session with 'gules create'.");
+    Ok(())
+}
+
+/// Ask for an API key until the API accepts one, or the user gives up
+async fn setup_api_key(config: &mut Config) -> Result<()> {
+    if config.api_key.is_some() && ask_yes_no("An API key is already configured. Keep it?", true)? {
+        return Ok(());
+    }
+
+    loop {
+        let key = ask("API key (from https://jules.google.com/settings; empty to skip):")?;
+        if key.is_empty() {
+            println!("Skipped. Set it later with 'gules config set api_key <KEY>'.");
+            return Ok(());
+        }
+
+        let check = check_api(Some(&(key.clone(), "prompt")), Some(config)).await;
+        println!("{} {}", check.status.icon(), check.message);
+        if check.status == CheckStatus::Ok {
+            config.api_key = Some(key);
+            return Ok(());
+        }
+        if !ask_yes_no("Try another key?", true)? {
+            if ask_yes_no("Save this key anyway?", false)? {
+                config.api_key = Some(key);
+            }
+            return Ok(());
+        }
+    }
+}
+
+/// Offer the GitHub repository of the current directory as default source
+fn setup_default_source(config: &mut Config) -> Result<()> {
+    let remote = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| git(&cwd, &["remote", "get-url", "origin"]).ok());
+    let Some((owner, repo)) = remote.as_deref().and_then(github_repo) else {
+        println!("No GitHub remote in the current directory; skipped.");
+        println!(
+            "Set one later with 'gules config set default_source sources/github/<owner>/<repo>'."
+        );
+        return Ok(());
+    };
+
+    let source = format!("sources/github/{}/{}", owner, repo);
+    if config.default_source.as_deref() == Some(source.as_str()) {
+        println!("{} Default source is {}", icon("✅", "OK"), source);
+        return Ok(());
+    }
+    if ask_yes_no(&format!("Use {} as the default source?", source), true)? {
+        config.default_source = Some(source);
+        config.default_owner = Some(owner);
+        config.default_repo = Some(repo);
+    }
+    Ok(())
+}
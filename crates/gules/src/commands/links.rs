@@ -0,0 +1,86 @@
+//! Issue-session links (`gules link issue`, `gules links list`).
+//!
+//! `create --from-issue` links the new session to its issue; `link issue`
+//! links any session by hand. `issue-status`, `report` and the completion
+//! comments of `watch --comment-on-issue` read the links.
+
+use anyhow::Result;
+use jules_core::display::{display_links_table, icon, is_quiet};
+use jules_core::issue_links::IssueLinks;
+use jules_core::{api_client, load_config};
+use jules_rs::SessionName;
+
+use crate::extended_commands::announce_on_issue;
+use crate::github::source_repo;
+
+/// Link a session to issue `issue` of `owner`/`repo` (default: the
+/// session's repository) and, with `comment`, announce it on the issue
+pub async fn handle_link_issue(
+    issue: u64,
+    session_id: &str,
+    owner: Option<String>,
+    repo: Option<String>,
+    comment: bool,
+) -> Result<()> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+    let session = client.get_session(session_id).await?;
+    let (owner, repo) = match (owner, repo) {
+        (Some(owner), Some(repo)) => (owner, repo),
+        _ => source_repo(&session.source_context.source).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Session {} isn't on a GitHub repository; pass --owner and --repo",
+                session.id
+            )
+        })?,
+    };
+
+    let mut links = IssueLinks::load()?;
+    if !links.link(&owner, &repo, issue, &session.id)? {
+        println!(
+            "Session {} is already linked to {}/{}#{}",
+            session.id, owner, repo, issue
+        );
+        return Ok(());
+    }
+    links.save()?;
+    if !is_quiet() {
+        println!(
+            "{} Linked session {} to {}/{}#{}",
+            icon("✅", "OK"),
+            session.id,
+            owner,
+            repo,
+            issue
+        );
+    }
+    if comment {
+        announce_on_issue(&config, &owner, &repo, issue, &session).await;
+    }
+    Ok(())
+}
+
+/// List issue-session links, optionally of one session, as a table or JSON
+pub fn handle_links_list(session_id: Option<&str>, format: &str) -> Result<()> {
+    let links = IssueLinks::load()?;
+    let session_id = session_id
+        .map(|id| SessionName::from_id_or_name(id).map(|name| name.id().to_string()))
+        .transpose()?;
+    let links: Vec<_> = links
+        .links
+        .into_iter()
+        .filter(|link| session_id.as_ref().is_none_or(|id| &link.session_id == id))
+        .collect();
+    match format.to_lowercase().as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&links)?),
+        "table" => {
+            if links.is_empty() {
+                println!("No issue links. Create a session with 'gules create --from-issue' or run 'gules link issue'.");
+            } else {
+                display_links_table(&links);
+            }
+        }
+        _ => anyhow::bail!("Unknown format: {}. Valid options: table, json", format),
+    }
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Chronological activity logs, optionally followed live (`logs -f`).
+//!
+//! Prints one line per activity, oldest first. With `--follow`, new
+//! activities are fetched incrementally (through the activity cache when it
+//! is enabled) and printed as they appear, until the session completes or
+//! fails.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use jules_core::activity_cache::fetch_new_activities;
+use jules_core::display::{display_activity_summary, log_timestamp};
+use jules_core::time_range::parse_time;
+use jules_core::{api_client, load_config, open_store, CacheConfig};
+use jules_rs::types::activity::Activity;
+use jules_rs::JulesClient;
+use std::collections::HashSet;
+use tokio::time::{sleep, Duration};
+
+use super::filter_activities::{sync_activity_cache, ActivityTypeFilter};
+
+/// Print a session's activities oldest first, and with `follow`, keep
+/// printing new ones every `interval` seconds
+///
+/// `since` (RFC 3339, a date, or a duration ago like "2h") hides older
+/// activities; `types` keeps only activities of those types.
+pub async fn handle_logs(
+    session_id: &str,
+    follow: bool,
+    types: &[ActivityTypeFilter],
+    since: Option<&str>,
+    interval: u64,
+) -> Result<()> {
+    let since = since.map(parse_time).transpose()?;
+    let config = load_config()?;
+    let client = api_client(&config)?;
+
+    let mut activities = updated_activities(&client, &config.cache, session_id, Vec::new()).await?;
+    let mut seen = HashSet::new();
+
+    loop {
+        let finished = print_new(&activities, &mut seen, types, since);
+        if !follow || finished {
+            return Ok(());
+        }
+
+        sleep(Duration::from_secs(interval)).await;
+        let known = std::mem::take(&mut activities);
+        activities =
+            match updated_activities(&client, &config.cache, session_id, known.clone()).await {
+                Ok(updated) => updated,
+                Err(e) => {
+                    eprintln!("Error fetching activities: {:#}", e);
+                    known
+                }
+            };
+    }
+}
+
+/// The session's activities, newest first, brought up to date incrementally
+async fn updated_activities(
+    client: &JulesClient,
+    cache: &CacheConfig,
+    session_id: &str,
+    known: Vec<Activity>,
+) -> Result<Vec<Activity>> {
+    if cache.enabled {
+        let mut store = open_store(cache)?;
+        sync_activity_cache(client, store.as_mut(), cache, session_id).await?;
+        let cached = store.peek_session(session_id)?;
+        return Ok(cached.map(|cache| cache.activities).unwrap_or_default());
+    }
+
+    let mut activities = fetch_new_activities(client, session_id, &known).await?;
+    activities.extend(known);
+    Ok(activities)
+}
+
+/// Print the activities not printed yet, oldest first
+///
+/// Returns whether the session has completed or failed.
+fn print_new(
+    activities: &[Activity],
+    seen: &mut HashSet<String>,
+    types: &[ActivityTypeFilter],
+    since: Option<DateTime<Utc>>,
+) -> bool {
+    let mut new: Vec<&Activity> = activities
+        .iter()
+        .filter(|activity| !seen.contains(&activity.id))
+        .collect();
+    // Timestamps compare as instants; their strings differ in precision
+    new.sort_by_key(|activity| DateTime::parse_from_rfc3339(&activity.create_time).ok());
+
+    let mut finished = false;
+    for activity in new {
+        seen.insert(activity.id.clone());
+        finished |= activity.session_completed.is_some() || activity.session_failed.is_some();
+        if is_shown(activity, types, since) {
+            print!("{} ", log_timestamp(&activity.create_time).dimmed());
+            display_activity_summary(activity);
+        }
+    }
+    finished
+}
+
+fn is_shown(
+    activity: &Activity,
+    types: &[ActivityTypeFilter],
+    since: Option<DateTime<Utc>>,
+) -> bool {
+    let type_ok = types.is_empty() || types.iter().any(|filter| filter.matches(activity));
+    let time_ok = since.is_none_or(|since| {
+        DateTime::parse_from_rfc3339(&activity.create_time)
+            .is_ok_and(|created| created.with_timezone(&Utc) >= since)
+    });
+    type_ok && time_ok
+}
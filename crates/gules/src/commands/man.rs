@@ -0,0 +1,55 @@
+//! Man pages generated from the command-line definition (`gules man`).
+//!
+//! `clap_mangen` renders a roff page (section 1) for the top-level command
+//! and every visible subcommand, named like `gules-cache-stats.1`, for distro
+//! packaging. The pages are built from the clap definition, so they always
+//! match `--help`.
+
+use anyhow::{Context, Result};
+use clap::Command;
+use clap_mangen::Man;
+use jules_core::display::{icon, is_quiet};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Write the man pages of `command` to `dir`, or print the top-level page
+pub fn handle_man(command: &Command, dir: Option<&str>) -> Result<()> {
+    let mut command = command.clone().disable_help_subcommand(true);
+    command.build();
+
+    let Some(dir) = dir else {
+        let mut page = Vec::new();
+        Man::new(command).render(&mut page)?;
+        io::stdout().write_all(&page)?;
+        return Ok(());
+    };
+
+    let dir = Path::new(dir);
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let count = write_pages(command, dir)?;
+    if !is_quiet() {
+        println!(
+            "{} Wrote {} man pages to {}",
+            icon("✅", "OK"),
+            count,
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+/// Write the pages of `command` and its visible subcommands, returning the count
+fn write_pages(command: Command, dir: &Path) -> Result<usize> {
+    let mut count = 1;
+    for subcommand in command.get_subcommands().filter(|s| !s.is_hide_set()) {
+        count += write_pages(subcommand.clone(), dir)?;
+    }
+
+    let man = Man::new(command);
+    let path = dir.join(man.get_filename());
+    let mut page = Vec::new();
+    man.render(&mut page)?;
+    fs::write(&path, page).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(count)
+}
@@ -1,10 +1,65 @@
 //! Extended CLI command implementations.
 //!
-//! This module contains extended command implementations for filtering
-//! and caching activities.
+//! This module contains extended command implementations for filtering,
+//! caching, searching and background refreshing of activities,
+//! creating sessions in bulk or through a local queue, reviewing plans,
+//! cloning sessions, extracting and checking out session patches, exporting
+//! reports, summarizing periods, deleting sessions, interactive setup,
+//! diagnosing the setup, managing the GitHub token, issue-session links,
+//! session pull requests, triaging labeled issues, tracing changes back to
+//! their sessions, the interactive monitor dashboard, local session notes,
+//! recently used sessions, and generating man pages.
 
+pub mod batch;
+pub mod blame;
 pub mod cache;
+pub mod checkout;
+pub mod clone;
+pub mod daemon;
+pub mod dashboard;
+pub mod delete;
+pub mod diff;
+pub mod doctor;
+pub mod export;
 pub mod filter_activities;
+pub mod github;
+pub mod grep;
+pub mod init;
+pub mod links;
+pub mod logs;
+pub mod man;
+pub mod note;
+pub mod open;
+pub mod plan;
+pub mod pr;
+pub mod queue;
+pub mod recent;
+pub mod report;
+pub mod triage;
 
 // Re-export command handlers
+pub use batch::*;
+pub use blame::*;
 pub use cache::*;
+pub use checkout::*;
+pub use clone::*;
+pub use daemon::*;
+pub use dashboard::*;
+pub use delete::*;
+pub use diff::*;
+pub use doctor::*;
+pub use export::*;
+pub use github::*;
+pub use grep::*;
+pub use init::*;
+pub use links::*;
+pub use logs::*;
+pub use man::*;
+pub use note::*;
+pub use open::*;
+pub use plan::*;
+pub use pr::*;
+pub use queue::*;
+pub use recent::*;
+pub use report::*;
+pub use triage::*;
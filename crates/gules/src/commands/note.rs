@@ -0,0 +1,80 @@
+//! Local notes on sessions (`gules note`).
+//!
+//! Notes are free-form text stored on this machine only, next to the
+//! session ID; `session --format full` includes them.
+
+use anyhow::Result;
+use jules_core::display::{display_timestamp, icon, is_quiet};
+use jules_core::editor::message_from_input;
+use jules_core::prompt::confirm;
+use jules_core::session_notes::NoteStore;
+
+/// Add a note to a session; `-` reads the text from stdin
+pub fn handle_note_add(session_id: &str, text: &str) -> Result<()> {
+    let text = message_from_input(Some(text), None, std::io::stdin())?;
+
+    let mut store = NoteStore::load()?;
+    let count = store.add(session_id, &text)?;
+    store.save()?;
+
+    if !is_quiet() {
+        println!(
+            "{} Noted on session {} ({} note(s))",
+            icon("✅", "OK"),
+            session_id,
+            count
+        );
+    }
+    Ok(())
+}
+
+/// Print the notes of a session, oldest first, as text or JSON
+pub fn handle_note_show(session_id: &str, format: &str) -> Result<()> {
+    let store = NoteStore::load()?;
+    let notes = store.notes(session_id);
+    match format.to_lowercase().as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(notes)?),
+        "table" => {
+            if notes.is_empty() {
+                println!("No notes on session {}.", session_id);
+            }
+            for note in notes {
+                println!("{}", display_timestamp(&note.added_at.to_rfc3339()));
+                for line in note.text.lines() {
+                    println!("    {}", line);
+                }
+            }
+        }
+        _ => anyhow::bail!("Unknown format: {}. Valid options: table, json", format),
+    }
+    Ok(())
+}
+
+/// Delete every note of a session, after confirmation unless `yes`
+pub fn handle_note_clear(session_id: &str, yes: bool) -> Result<()> {
+    let mut store = NoteStore::load()?;
+    let count = store.notes(session_id).len();
+    if count == 0 {
+        println!("No notes on session {}.", session_id);
+        return Ok(());
+    }
+    if !confirm(
+        &format!("Delete {} note(s) on session {}?", count, session_id),
+        yes,
+    )? {
+        println!("Notes not deleted.");
+        return Ok(());
+    }
+
+    store.clear(session_id)?;
+    store.save()?;
+    if !is_quiet() {
+        println!(
+            "{} Deleted {} note(s) on session {}",
+            icon("✅", "OK"),
+            count,
+            session_id
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! Opening a session or its pull request in the browser.
+
+use anyhow::Result;
+use jules_core::browser::open_url;
+use jules_core::display::is_quiet;
+use jules_core::{api_client, load_config};
+use jules_rs::types::session::Session;
+
+/// The URL to open for a session: its pull request with `pr`, otherwise
+/// the session page
+pub fn session_open_url(session: &Session, pr: bool) -> Result<String> {
+    if pr {
+        return session
+            .outputs
+            .iter()
+            .find_map(|output| output.pull_request.as_ref()?.url.clone())
+            .ok_or_else(|| anyhow::anyhow!("Session {} has no pull request yet", session.id));
+    }
+    session
+        .url
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("Session {} has no URL", session.id))
+}
+
+/// Open a session (or with `pr`, its pull request) in the browser
+pub async fn handle_open(session_id: &str, pr: bool) -> Result<()> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+    let session = client.get_session(session_id).await?;
+
+    let url = session_open_url(&session, pr)?;
+    if !is_quiet() {
+        println!("Opening {}", url);
+    }
+    open_url(&url)
+}
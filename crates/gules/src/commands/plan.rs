@@ -0,0 +1,206 @@
+//! Interactive plan review (`gules plan`).
+//!
+//! Shows the latest plan of a session step by step and, while the session
+//! awaits approval, asks whether to approve it, reject it with feedback
+//! (sent as a message, so Jules revises the plan) or leave it for later.
+//! `create --auto-approve` approves the plan of a new session unattended.
+
+use anyhow::Result;
+use jules_core::display::{display_plan, icon, is_quiet};
+use jules_core::editor::edit_text;
+use jules_core::progress::Spinner;
+use jules_core::prompt::ask;
+use jules_core::{api_client, load_config};
+use jules_rs::types::activity::{Activity, Plan};
+use jules_rs::{HttpError, JulesClient, State};
+use std::io::{self, IsTerminal};
+use tokio::time::{sleep, Duration};
+
+use super::diff::{fresh_session_activities, session_activities};
+
+/// Consecutive failed status checks after which `auto_approve_plan` gives up
+const MAX_FETCH_FAILURES: u32 = 5;
+
+/// Answer to the plan review prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanChoice {
+    Approve,
+    Reject,
+    Skip,
+}
+
+impl PlanChoice {
+    /// Parse an answer: a[pprove], r[eject], or s[kip] (also empty)
+    pub fn parse(answer: &str) -> Option<Self> {
+        match answer.trim().to_lowercase().as_str() {
+            "a" | "approve" => Some(Self::Approve),
+            "r" | "reject" => Some(Self::Reject),
+            "" | "s" | "skip" => Some(Self::Skip),
+            _ => None,
+        }
+    }
+}
+
+/// The most recently generated plan among `activities`
+pub fn latest_plan(activities: &[Activity]) -> Option<&Plan> {
+    activities
+        .iter()
+        .filter_map(|activity| {
+            let plan = &activity.plan_generated.as_ref()?.plan;
+            Some((activity.create_time.as_str(), plan))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, plan)| plan)
+}
+
+/// Show the latest plan of a session, then approve it, reject it with
+/// feedback, or skip, as the user chooses
+pub async fn handle_plan(session_id: &str) -> Result<()> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+
+    let session = client.get_session(session_id).await?;
+    let state = session.state.unwrap_or(State::StateUnspecified);
+    // A cache within its TTL may predate the plan awaiting approval
+    let activities = if state == State::AwaitingPlanApproval {
+        fresh_session_activities(session_id).await?
+    } else {
+        session_activities(session_id).await?
+    };
+    let Some(plan) = latest_plan(&activities) else {
+        anyhow::bail!(
+            "Session {} has no plan yet ({})",
+            session_id,
+            state.display_name().to_lowercase()
+        );
+    };
+
+    if let Some(title) = &session.title {
+        println!("{}\n", title);
+    }
+    display_plan(plan);
+    println!();
+
+    if state != State::AwaitingPlanApproval {
+        println!(
+            "Session is {}; there is no plan awaiting approval.",
+            state.display_name().to_lowercase()
+        );
+        return Ok(());
+    }
+    if !io::stdin().is_terminal() {
+        println!(
+            "Approve with 'gules approve-plan {0}', or reject it with feedback using \
+             'gules send-message {0} <FEEDBACK>'.",
+            session_id
+        );
+        return Ok(());
+    }
+
+    let choice = loop {
+        let answer = ask("[a]pprove, [r]eject with feedback, or [s]kip?")?;
+        match PlanChoice::parse(&answer) {
+            Some(choice) => break choice,
+            None => eprintln!("Please answer a, r or s."),
+        }
+    };
+
+    match choice {
+        PlanChoice::Approve => {
+            client.approve_plan(session_id).await?;
+            if !is_quiet() {
+                println!("{} Plan approved", icon("✅", "OK"));
+            }
+        }
+        PlanChoice::Reject => {
+            let feedback = match ask("Feedback (empty to write it in the editor):")? {
+                feedback if feedback.is_empty() => edit_text("")?.trim_end().to_string(),
+                feedback => feedback,
+            };
+            if feedback.trim().is_empty() {
+                anyhow::bail!("Empty feedback, nothing sent; the plan still awaits approval");
+            }
+            client.send_message(session_id, &feedback).await?;
+            if !is_quiet() {
+                println!(
+                    "{} Feedback sent; Jules will revise the plan",
+                    icon("✅", "OK")
+                );
+            }
+        }
+        PlanChoice::Skip => {
+            if !is_quiet() {
+                println!("Skipped; the plan still awaits approval.");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Wait until a session's plan awaits approval, then approve it, showing
+/// the plan first with `show_plan`
+///
+/// Fails if the session stops in another state first, e.g. asking for
+/// feedback or failing, since there is no plan to approve then, and on a
+/// client error (e.g. 404) or after `MAX_FETCH_FAILURES` failed checks in a row.
+pub async fn auto_approve_plan(
+    client: &JulesClient,
+    session_id: &str,
+    interval: u64,
+    show_plan: bool,
+) -> Result<()> {
+    let spinner = Spinner::start(format!("Waiting for the plan of session {}...", session_id));
+    let mut failures = 0;
+    loop {
+        match client.get_session(session_id).await {
+            Ok(session) => match session.state.unwrap_or(State::StateUnspecified) {
+                State::AwaitingPlanApproval => break,
+                state @ (State::Completed
+                | State::Failed
+                | State::Paused
+                | State::AwaitingUserFeedback) => {
+                    spinner.finish();
+                    anyhow::bail!(
+                        "Session {} is {} without a plan to approve",
+                        session_id,
+                        state.display_name().to_lowercase()
+                    );
+                }
+                _ => failures = 0,
+            },
+            Err(e) => {
+                failures += 1;
+                // Retrying won't fix a missing session or a rejected key
+                let permanent = e
+                    .downcast_ref::<HttpError>()
+                    .is_some_and(|e| (400..500).contains(&e.status) && !e.is_rate_limited());
+                if permanent || failures >= MAX_FETCH_FAILURES {
+                    spinner.finish();
+                    return Err(e.context(format!("Failed to fetch session {}", session_id)));
+                }
+                spinner.suspend(|| {
+                    eprintln!("Warning: Failed to fetch session {}: {:#}", session_id, e)
+                });
+            }
+        }
+        sleep(Duration::from_secs(interval)).await;
+    }
+    spinner.finish();
+
+    if show_plan {
+        let activities = fresh_session_activities(session_id).await?;
+        if let Some(plan) = latest_plan(&activities) {
+            display_plan(plan);
+            println!();
+        }
+    }
+    client.approve_plan(session_id).await?;
+    if !is_quiet() {
+        eprintln!(
+            "{} Plan approved for session {}",
+            icon("✅", "OK"),
+            session_id
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,434 @@
+//! Working with the pull request a session opened (`gules pr`).
+//!
+//! `checkout` fetches the PR's branch into the current repository, so
+//! Jules' work can be reviewed and tested locally; `merge` merges the PR
+//! once its checks passed and no reviewer requested changes; `feedback`
+//! sends the PR's unresolved review comments to the session, and
+//! `ci-watch` sends it the logs of failed checks until CI passes.
+
+use anyhow::{Context, Result};
+use jules_core::display::{icon, is_quiet};
+use jules_core::exit_code::{self, ExitError};
+use jules_core::git::{git, github_repo, in_work_tree};
+use jules_core::github_url::GitHubItemUrl;
+use jules_core::{api_client, load_config};
+use jules_rs::JulesClient;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use crate::extended_commands::handle_watch;
+use crate::github::{
+    ci_failure_message, log_excerpt, review_feedback, Check, GitHubClient, PullRequestStatus,
+};
+
+/// The pull request a session opened
+pub(crate) async fn session_pull_request(
+    client: &JulesClient,
+    session_id: &str,
+) -> Result<GitHubItemUrl> {
+    let session = client.get_session(session_id).await?;
+    let url = session
+        .outputs
+        .iter()
+        .find_map(|output| output.pull_request.as_ref()?.url.clone())
+        .ok_or_else(|| anyhow::anyhow!("Session {} has no pull request yet", session.id))?;
+    GitHubItemUrl::parse_pull_request(&url)
+}
+
+/// Check out the branch of a session's pull request in the current
+/// repository, as `branch` (default: the PR's branch, else `pr-<NUMBER>`)
+///
+/// The PR's `refs/pull/<NUMBER>/head` is fetched with git; if that fails,
+/// `gh pr checkout` is tried when the GitHub CLI is installed.
+pub async fn handle_pr_checkout(session_id: &str, branch: Option<&str>) -> Result<()> {
+    let dir = Path::new(".");
+    if !in_work_tree(dir) {
+        anyhow::bail!("Not inside a git repository");
+    }
+    if !git(dir, &["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+        anyhow::bail!("The working tree has uncommitted changes; commit or stash them first");
+    }
+
+    let config = load_config()?;
+    let client = api_client(&config)?;
+    let pr = session_pull_request(&client, session_id).await?;
+    let github = GitHubClient::detect(&config)?;
+
+    let branch = match branch {
+        Some(branch) => branch.to_string(),
+        None => pr_branch(github.as_ref(), &pr).await,
+    };
+    let fetched = fetch_pr(dir, &pr, &branch);
+    match (fetched, &github) {
+        (Ok(()), _) => {}
+        (Err(e), Some(GitHubClient::Cli)) => {
+            if !is_quiet() {
+                eprintln!("Fetching with git failed ({:#}); trying gh", e);
+            }
+            checkout_via_gh(&pr, &branch)?;
+        }
+        (Err(e), _) => return Err(e),
+    }
+
+    if !is_quiet() {
+        println!(
+            "{} Checked out {} on branch {}",
+            icon("✅", "OK"),
+            pr,
+            branch
+        );
+    }
+    Ok(())
+}
+
+/// The PR's own branch name when GitHub can tell, else `pr-<NUMBER>`
+async fn pr_branch(github: Option<&GitHubClient>, pr: &GitHubItemUrl) -> String {
+    let head_branch = match github {
+        Some(github) => github
+            .pull_request_status(pr)
+            .await
+            .ok()
+            .and_then(|status| status.head_branch),
+        None => None,
+    };
+    head_branch.unwrap_or_else(|| format!("pr-{}", pr.number))
+}
+
+/// Fetch the PR's head into `branch` and check it out; a branch that
+/// already exists is only fast-forwarded, so local commits are never lost
+fn fetch_pr(dir: &Path, pr: &GitHubItemUrl, branch: &str) -> Result<()> {
+    let remote = pr_remote(dir, pr);
+    let head = format!("refs/pull/{}/head", pr.number);
+    let current = git(dir, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+    if current == branch {
+        git(dir, &["pull", "--quiet", "--ff-only", &remote, &head])?;
+    } else {
+        git(
+            dir,
+            &["fetch", &remote, &format!("{}:refs/heads/{}", head, branch)],
+        )?;
+        git(dir, &["checkout", "--quiet", branch])?;
+    }
+    Ok(())
+}
+
+/// The remote of the PR's repository, or its URL when no remote points at it
+fn pr_remote(dir: &Path, pr: &GitHubItemUrl) -> String {
+    let remotes = git(dir, &["remote"]).unwrap_or_default();
+    remotes
+        .lines()
+        .find(|remote| {
+            pr.is_github_com()
+                && git(dir, &["remote", "get-url", remote])
+                    .ok()
+                    .and_then(|url| github_repo(&url))
+                    .is_some_and(|(owner, repo)| {
+                        owner.eq_ignore_ascii_case(&pr.owner) && repo.eq_ignore_ascii_case(&pr.repo)
+                    })
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("https://{}/{}/{}.git", pr.host, pr.owner, pr.repo))
+}
+
+/// Check out the PR with `gh pr checkout`
+fn checkout_via_gh(pr: &GitHubItemUrl, branch: &str) -> Result<()> {
+    let status = Command::new("gh")
+        .arg("pr")
+        .arg("checkout")
+        .arg(pr.number.to_string())
+        .arg("--repo")
+        .arg(pr.repo_spec())
+        .arg("--branch")
+        .arg(branch)
+        .status()
+        .context("Failed to run gh pr checkout")?;
+    if !status.success() {
+        anyhow::bail!("gh pr checkout failed for {}", pr);
+    }
+    Ok(())
+}
+
+/// Merge a session's pull request (squashed with `squash`) if it is open,
+/// conflict-free, its checks passed and no reviewer requested changes
+///
+/// With `when_green`, checks that are still running are polled every
+/// `interval` seconds instead of refusing, for at most `timeout` (exit code
+/// [`exit_code::WATCH_TIMEOUT`]).
+pub async fn handle_pr_merge(
+    session_id: &str,
+    squash: bool,
+    when_green: bool,
+    allow_no_checks: bool,
+    interval: u64,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+    let pr = session_pull_request(&client, session_id).await?;
+    let github = GitHubClient::require(&config, "pr merge")?;
+
+    let reserve = config.github.rate_limit_reserve();
+    let green = wait_until_green(&github, &pr, when_green, allow_no_checks, interval, reserve);
+    let status = within(timeout, &pr, green).await?;
+
+    github
+        .merge_pull_request(&pr, squash, status.head_sha.as_deref())
+        .await?;
+    if !is_quiet() {
+        let how = if squash { " (squashed)" } else { "" };
+        println!("{} Merged {}{}", icon("✅", "OK"), pr, how);
+    }
+    Ok(())
+}
+
+/// Run `wait`, which waits on the checks of `pr`, for at most `timeout`
+async fn within<T>(
+    timeout: Option<Duration>,
+    pr: &GitHubItemUrl,
+    wait: impl std::future::Future<Output = Result<T>>,
+) -> Result<T> {
+    let Some(timeout) = timeout else {
+        return wait.await;
+    };
+    tokio::time::timeout(timeout, wait)
+        .await
+        .unwrap_or_else(|_| {
+            Err(ExitError::new(
+                exit_code::WATCH_TIMEOUT,
+                format!(
+                    "Timed out after {}s waiting for the checks of {}",
+                    timeout.as_secs(),
+                    pr
+                ),
+            )
+            .into())
+        })
+}
+
+/// The pull request's status once nothing blocks merging it, waiting for
+/// running checks with `when_green` (keeping `reserve` GitHub API requests)
+///
+/// A pull request without checks, e.g. one CI hasn't picked up yet, isn't
+/// green: `when_green` waits for checks to appear, and only
+/// `allow_no_checks` merges it without any.
+pub async fn wait_until_green(
+    github: &GitHubClient,
+    pr: &GitHubItemUrl,
+    when_green: bool,
+    allow_no_checks: bool,
+    interval: u64,
+    reserve: u64,
+) -> Result<PullRequestStatus> {
+    loop {
+        let status = github.pull_request_status(pr).await?;
+        let blockers = status.merge_blockers();
+        if !blockers.is_empty() {
+            anyhow::bail!("Not merging {}: {}", pr, blockers.join("; "));
+        }
+        let waiting_for = match status.checks_state() {
+            "none" if allow_no_checks => return Ok(status),
+            "none" => "it has no checks yet",
+            "pending" => "checks are still running",
+            _ => return Ok(status),
+        };
+        if !when_green {
+            anyhow::bail!(
+                "Not merging {}: {} (use --when-green to wait for them{})",
+                pr,
+                waiting_for,
+                if status.checks.is_empty() {
+                    ", or --allow-no-checks to merge without checks"
+                } else {
+                    ""
+                }
+            );
+        }
+        if !is_quiet() {
+            if status.checks.is_empty() {
+                eprintln!(
+                    "{} Waiting for checks to start on {}...",
+                    icon("⏳", "..."),
+                    pr
+                );
+            } else {
+                let pending = status.checks.iter().filter(|c| c.pending()).count();
+                eprintln!(
+                    "{} Waiting for {} check(s) on {}...",
+                    icon("⏳", "..."),
+                    pending,
+                    pr
+                );
+            }
+        }
+        github.pause(Duration::from_secs(interval), reserve).await;
+    }
+}
+
+/// Send the unresolved review comments of a session's pull request to the
+/// session, then with `watch` watch it like `gules watch` (in `format`)
+///
+/// With `dry_run`, the message is printed instead of sent.
+pub async fn handle_pr_feedback(
+    session_id: &str,
+    dry_run: bool,
+    watch: bool,
+    interval: u64,
+    format: &str,
+) -> Result<()> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+    let pr = session_pull_request(&client, session_id).await?;
+    let github = GitHubClient::require(&config, "pr feedback")?;
+
+    let threads = github.review_threads(&pr).await?;
+    if threads.is_empty() {
+        if !is_quiet() {
+            println!("No unresolved review comments on {}", pr);
+        }
+        return Ok(());
+    }
+    let message = review_feedback(&pr, &threads);
+    if dry_run {
+        print!("{}", message);
+        return Ok(());
+    }
+
+    client.send_message(session_id, &message).await?;
+    if !is_quiet() {
+        eprintln!(
+            "{} Sent {} unresolved review thread(s) on {} to session {}",
+            icon("✅", "OK"),
+            threads.len(),
+            pr,
+            session_id
+        );
+    }
+    if watch {
+        handle_watch(session_id, interval, None, format, None, false).await?;
+    }
+    Ok(())
+}
+
+/// Lines of each failed job's log sent to the session
+const LOG_EXCERPT_LINES: usize = 60;
+
+/// Watch the checks of a session's pull request every `interval` seconds
+/// until they pass (or the PR is merged or closed), sending the session the
+/// failed checks and their log excerpts whenever a commit fails CI
+///
+/// Gives up after `max_fixes` requests to fix the same PR, and after
+/// `timeout` with exit code [`exit_code::WATCH_TIMEOUT`]. Once no more than
+/// `github.rate_limit_reserve` GitHub API requests are left, waits for the
+/// rate limit to reset between polls.
+pub async fn handle_pr_ci_watch(
+    session_id: &str,
+    interval: u64,
+    timeout: Option<Duration>,
+    max_fixes: u32,
+) -> Result<()> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+    let pr = session_pull_request(&client, session_id).await?;
+    let github = GitHubClient::require(&config, "pr ci-watch")?;
+
+    let reserve = config.github.rate_limit_reserve();
+    let watch = watch_ci(
+        &client, &github, session_id, &pr, interval, reserve, max_fixes,
+    );
+    within(timeout, &pr, watch).await
+}
+
+/// Poll the checks of `pr` for [`handle_pr_ci_watch`], keeping `reserve`
+/// GitHub API requests
+async fn watch_ci(
+    client: &JulesClient,
+    github: &GitHubClient,
+    session_id: &str,
+    pr: &GitHubItemUrl,
+    interval: u64,
+    reserve: u64,
+    max_fixes: u32,
+) -> Result<()> {
+    let quiet = is_quiet();
+    if !quiet {
+        eprintln!("Watching the checks of {} (Ctrl+C to stop)", pr);
+    }
+    // The commit whose failure was last sent, so each is sent once
+    let mut reported: Option<String> = None;
+    let mut fixes = 0;
+    let mut last_state = "";
+    loop {
+        let status = github.pull_request_status(pr).await?;
+        if let Some(state @ ("MERGED" | "CLOSED")) = status.state.as_deref() {
+            if !quiet {
+                println!("{} is {}; stopped watching", pr, state.to_lowercase());
+            }
+            return Ok(());
+        }
+
+        let state = status.checks_state();
+        if state != last_state && !quiet {
+            eprintln!("Checks: {}", state);
+        }
+        last_state = state;
+        match state {
+            "passing" => {
+                if !quiet {
+                    println!("{} All checks passed on {}", icon("✅", "OK"), pr);
+                }
+                return Ok(());
+            }
+            "failing" if status.head_sha != reported => {
+                if fixes == max_fixes {
+                    anyhow::bail!(
+                        "Checks still fail on {} after {} request(s) to fix them",
+                        pr,
+                        fixes
+                    );
+                }
+                let failures = failed_checks_with_logs(github, pr, &status).await;
+                client
+                    .send_message(session_id, &ci_failure_message(pr, &failures))
+                    .await?;
+                fixes += 1;
+                reported = status.head_sha.clone();
+                if !quiet {
+                    let names: Vec<&str> = failures.iter().map(|(c, _)| c.name.as_str()).collect();
+                    eprintln!(
+                        "{} Asked session {} to fix {}; waiting for a new commit",
+                        icon("🔧", "*"),
+                        session_id,
+                        names.join(", ")
+                    );
+                }
+            }
+            _ => {}
+        }
+        github.pause(Duration::from_secs(interval), reserve).await;
+    }
+}
+
+/// The failed checks of `status`, each with an excerpt of its GitHub
+/// Actions log if it could be fetched
+async fn failed_checks_with_logs(
+    github: &GitHubClient,
+    pr: &GitHubItemUrl,
+    status: &PullRequestStatus,
+) -> Vec<(Check, Option<String>)> {
+    let mut failures = Vec::new();
+    for check in status.checks.iter().filter(|c| !c.passed() && !c.pending()) {
+        let log = match check.actions_job_id() {
+            Some(job_id) => match github.job_log(pr, job_id).await {
+                Ok(log) => Some(log_excerpt(&log, LOG_EXCERPT_LINES)),
+                Err(e) => {
+                    eprintln!("Warning: {:#}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+        failures.push((check.clone(), log));
+    }
+    failures
+}
@@ -0,0 +1,215 @@
+//! Local queue of prompts submitted one at a time (`gules queue`).
+//!
+//! `queue add` resolves and stores session requests without creating
+//! anything; `queue run` submits them in order, optionally waiting for each
+//! session to finish first, to stay within Jules' concurrent session limit.
+
+use anyhow::{Context, Result};
+use jules_cli::commands::CreateArgs;
+use jules_core::display::{display_queue_table, icon, is_quiet};
+use jules_core::editor::prompt_from_input;
+use jules_core::progress::Spinner;
+use jules_core::task_file::load_tasks;
+use jules_core::task_queue::{get_queue_path, TaskQueue};
+use jules_core::{api_client, load_config};
+use jules_rs::{JulesClient, State};
+use tokio::time::{sleep, Duration};
+
+use super::batch::task_args;
+use crate::extended_commands::create_request;
+
+/// Queue a session for `args`, or one per task of `file` (with `args` as
+/// defaults)
+pub fn handle_queue_add(args: CreateArgs, file: Option<&str>) -> Result<()> {
+    let config = load_config()?;
+    let requests = match file {
+        Some(file) => load_tasks(file)?
+            .iter()
+            .enumerate()
+            .map(|(i, task)| {
+                create_request(&config, task_args(task, &args))
+                    .with_context(|| format!("Task {} ({})", i + 1, task.label()))
+            })
+            .collect::<Result<Vec<_>>>()?,
+        None => {
+            let prompt = prompt_from_input(&args.prompt, std::io::stdin())?;
+            vec![create_request(&config, CreateArgs { prompt, ..args })?]
+        }
+    };
+
+    let mut queue = TaskQueue::load()?;
+    let ids: Vec<u64> = requests
+        .into_iter()
+        .map(|request| queue.push(request))
+        .collect();
+    queue.save()?;
+
+    if !is_quiet() {
+        match ids.as_slice() {
+            [id] => println!(
+                "{} Queued task {} ({} pending)",
+                icon("✅", "OK"),
+                id,
+                queue.tasks.len()
+            ),
+            _ => println!(
+                "{} Queued {} tasks ({} pending)",
+                icon("✅", "OK"),
+                ids.len(),
+                queue.tasks.len()
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// List the queued tasks as a table or JSON
+pub fn handle_queue_list(format: &str) -> Result<()> {
+    let queue = TaskQueue::load()?;
+    match format.to_lowercase().as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&queue.tasks)?),
+        "table" => {
+            if queue.tasks.is_empty() {
+                println!("The queue is empty.");
+            } else {
+                display_queue_table(&queue.tasks);
+            }
+        }
+        _ => anyhow::bail!("Unknown format: {}. Valid options: table, json", format),
+    }
+    Ok(())
+}
+
+/// Drop tasks from the queue without submitting them
+pub fn handle_queue_remove(ids: &[u64]) -> Result<()> {
+    let mut queue = TaskQueue::load()?;
+    let missing: Vec<String> = ids
+        .iter()
+        .filter(|id| queue.remove(**id).is_none())
+        .map(|id| id.to_string())
+        .collect();
+    queue.save()?;
+
+    if !missing.is_empty() {
+        anyhow::bail!("No queued task with ID {}", missing.join(", "));
+    }
+    if !is_quiet() {
+        println!(
+            "{} Removed {} task(s) ({} pending)",
+            icon("✅", "OK"),
+            ids.len(),
+            queue.tasks.len()
+        );
+    }
+    Ok(())
+}
+
+/// Submit queued tasks in order, at most `limit`; with `wait`, each
+/// session must finish (or need input) before the next one is submitted
+///
+/// A task leaves the queue once its session is created. If creating one
+/// fails, it stays at the front of the queue and the run stops.
+pub async fn handle_queue_run(wait: bool, interval: u64, limit: Option<usize>) -> Result<()> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+
+    let mut submitted = 0;
+    while limit.is_none_or(|limit| submitted < limit) {
+        // Reload every time, so tasks queued during the run are picked up
+        let Some(task) = TaskQueue::load()?.front().cloned() else {
+            break;
+        };
+        // Tasks may have been queued before the source was disallowed
+        config
+            .safety
+            .check_source(&task.request.source_context.source)
+            .with_context(|| format!("Task {} stays queued", task.id))?;
+
+        let spinner = Spinner::start(format!("Creating session for task {}...", task.id));
+        let session = client
+            .create_session(task.request.clone())
+            .await
+            .with_context(|| format!("Failed to submit task {}; it stays queued", task.id));
+        spinner.finish();
+        let session = session?;
+
+        let mut queue = TaskQueue::load()?;
+        queue.remove(task.id);
+        queue.save()?;
+        submitted += 1;
+        if !is_quiet() {
+            println!(
+                "{} Task {} ({}) → session {}",
+                icon("✓", "OK"),
+                task.id,
+                task.label(),
+                session.id
+            );
+        }
+
+        if wait {
+            let state = wait_for_session(&client, &session.id, interval).await?;
+            if !is_quiet() {
+                println!(
+                    "  Session {} is {}",
+                    session.id,
+                    state.display_name().to_lowercase()
+                );
+            }
+        }
+    }
+
+    if !is_quiet() {
+        let pending = TaskQueue::load()?.tasks.len();
+        match submitted {
+            0 => println!(
+                "Nothing to submit; add tasks with 'gules queue add' ({})",
+                get_queue_path()?.display()
+            ),
+            _ => println!(
+                "\n{} Submitted {} task(s), {} pending",
+                icon("✅", "OK"),
+                submitted,
+                pending
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Poll a session until it no longer runs on its own
+async fn wait_for_session(client: &JulesClient, session_id: &str, interval: u64) -> Result<State> {
+    let spinner = Spinner::start(format!("Waiting for session {}...", session_id));
+    loop {
+        match client.get_session(session_id).await {
+            Ok(session) => {
+                let state = session.state.unwrap_or(State::StateUnspecified);
+                if is_settled(state) {
+                    spinner.finish();
+                    return Ok(state);
+                }
+                spinner.set_message(format!(
+                    "Waiting for session {} ({})...",
+                    session_id,
+                    state.display_name().to_lowercase()
+                ));
+            }
+            Err(e) => spinner
+                .suspend(|| eprintln!("Warning: Failed to fetch session {}: {:#}", session_id, e)),
+        }
+        sleep(Duration::from_secs(interval)).await;
+    }
+}
+
+/// Whether a session stopped working: finished, paused, or waiting for the
+/// user, who may not be around while the queue runs
+fn is_settled(state: State) -> bool {
+    matches!(
+        state,
+        State::Completed
+            | State::Failed
+            | State::Paused
+            | State::AwaitingPlanApproval
+            | State::AwaitingUserFeedback
+    )
+}
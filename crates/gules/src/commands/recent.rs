@@ -0,0 +1,127 @@
+//! Recently used sessions (`gules recent`) and session ID shortcuts.
+//!
+//! Creating, viewing or watching a session remembers it; any SESSION_ID
+//! argument then accepts `@1` for the most recent one, `@2` for the one
+//! before, and so on. An unambiguous prefix of a cached or recent session
+//! ID works too, and a session that isn't found gets close matches
+//! suggested.
+
+use anyhow::Result;
+use jules_core::activity_cache::cache_key;
+use jules_core::display::{display_recent_table, is_quiet};
+use jules_core::recent_sessions::{parse_recent_ref, RecentSessions};
+use jules_core::session_cache::SessionStore;
+use jules_core::session_match::{match_prefix, suggest_ids, PrefixMatch};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// SESSION_ID arguments passed through as given, for suggestions
+static LOOKED_UP: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Resolve a SESSION_ID argument: `@N` becomes the ID of the Nth most
+/// recently used session, and an unambiguous prefix of a known session ID
+/// becomes that ID
+pub fn resolve_session_ref(value: &str) -> Result<String> {
+    if parse_recent_ref(value).is_some() {
+        return RecentSessions::load()?.resolve(value);
+    }
+    let known = known_sessions();
+    match match_prefix(value, known.keys().map(String::as_str)) {
+        PrefixMatch::Unique(id) => {
+            if !is_quiet() {
+                eprintln!("Session {} is {}", value, id);
+            }
+            Ok(id)
+        }
+        PrefixMatch::Ambiguous(ids) => anyhow::bail!(
+            "Session ID prefix '{}' is ambiguous; it matches {}",
+            value,
+            ids.join(", ")
+        ),
+        PrefixMatch::NoMatch => {
+            if let Ok(mut looked_up) = LOOKED_UP.lock() {
+                looked_up.push(value.to_string());
+            }
+            Ok(value.to_string())
+        }
+    }
+}
+
+/// Print known sessions close to the SESSION_ID arguments given, after a
+/// session wasn't found
+pub fn print_session_suggestions() {
+    let looked_up = LOOKED_UP.lock().map(|ids| ids.clone()).unwrap_or_default();
+    if looked_up.is_empty() {
+        return;
+    }
+    let known = known_sessions();
+    let mut suggestions: Vec<String> = Vec::new();
+    for value in looked_up {
+        let Ok(id) = cache_key(&value) else {
+            continue;
+        };
+        for suggestion in suggest_ids(&id, known.keys().map(String::as_str), 3) {
+            if !suggestions.contains(&suggestion) {
+                suggestions.push(suggestion);
+            }
+        }
+    }
+    if suggestions.is_empty() {
+        return;
+    }
+    eprintln!("\nDid you mean:");
+    for id in suggestions {
+        match known.get(&id).and_then(Option::as_deref) {
+            Some(title) => eprintln!("    {}  {}", id, title),
+            None => eprintln!("    {}", id),
+        }
+    }
+}
+
+/// IDs of the cached and recently used sessions, with their titles
+///
+/// Unreadable caches count as empty: they only make shortcuts unavailable.
+fn known_sessions() -> BTreeMap<String, Option<String>> {
+    let mut known: BTreeMap<String, Option<String>> = RecentSessions::load()
+        .unwrap_or_default()
+        .sessions
+        .into_iter()
+        .map(|session| (session.id, session.title))
+        .collect();
+    for (id, cached) in SessionStore::load().unwrap_or_default().sessions {
+        known.insert(id, cached.session.title);
+    }
+    known
+}
+
+/// Remember a session as the most recently used one, keeping its known
+/// title when `title` is `None`
+///
+/// Failing to do so only warns, since it must not fail the command itself.
+pub fn track_recent(session_id: &str, title: Option<&str>) {
+    let result = RecentSessions::load().and_then(|mut recent| {
+        recent.touch(session_id, title)?;
+        recent.save()
+    });
+    if let Err(e) = result {
+        eprintln!("Warning: Failed to remember recent session: {:#}", e);
+    }
+}
+
+/// List recently used sessions, most recent first, as a table or JSON
+pub fn handle_recent(limit: usize, format: &str) -> Result<()> {
+    let recent = RecentSessions::load()?;
+    let sessions = &recent.sessions[..limit.min(recent.sessions.len())];
+    match format.to_lowercase().as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(sessions)?),
+        "table" => {
+            if sessions.is_empty() {
+                println!("No recent sessions.");
+            } else {
+                display_recent_table(sessions);
+            }
+        }
+        _ => anyhow::bail!("Unknown format: {}. Valid options: table, json", format),
+    }
+    Ok(())
+}
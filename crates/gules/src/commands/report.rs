@@ -0,0 +1,140 @@
+//! Digest of the sessions of a period (`gules report --since 7d`).
+
+use anyhow::Result;
+use jules_core::display::is_quiet;
+use jules_core::issue_links::IssueLinks;
+use jules_core::progress::Spinner;
+use jules_core::session_report::{failure_reason, in_report, issues_suffix, SessionReport};
+use jules_core::time_range::parse_time;
+use jules_core::{api_client, load_config};
+use jules_rs::types::session::{Session, State};
+use std::collections::HashMap;
+
+use super::delete::list_all_sessions;
+use super::diff::session_activities;
+
+/// Summarize the sessions created between `since` and `until` (optionally
+/// of one source) as a table, Markdown or JSON
+pub async fn handle_report(
+    since: &str,
+    until: &str,
+    source: Option<&str>,
+    format: &str,
+) -> Result<()> {
+    let format = format.to_lowercase();
+    if !["table", "markdown", "md", "json"].contains(&format.as_str()) {
+        anyhow::bail!(
+            "Unknown report format: {}. Valid options: table, markdown, json",
+            format
+        );
+    }
+    let since = parse_time(since)?;
+    let until = parse_time(until)?;
+    if since > until {
+        anyhow::bail!("--since must be before --until");
+    }
+
+    let config = load_config()?;
+    let client = api_client(&config)?;
+    let sessions: Vec<Session> = list_all_sessions(&client, config.cache.enabled)
+        .await?
+        .into_iter()
+        .filter(|session| in_report(session, since, until, source))
+        .collect();
+
+    let failure_reasons = failure_reasons(&sessions).await;
+    let links = IssueLinks::load().unwrap_or_else(|e| {
+        eprintln!("Warning: {:#}", e);
+        IssueLinks::default()
+    });
+    let report = SessionReport::new(&sessions, &failure_reasons, since, until, source)
+        .with_issue_links(&links);
+
+    match format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        "markdown" | "md" => print!("{}", report.to_markdown()),
+        _ => print_report(&report),
+    }
+    Ok(())
+}
+
+/// Reasons of the failed sessions, from their activities (best effort)
+async fn failure_reasons(sessions: &[Session]) -> HashMap<String, String> {
+    let failed: Vec<&Session> = sessions
+        .iter()
+        .filter(|session| session.state == Some(State::Failed))
+        .collect();
+    let spinner = Spinner::start(format!("Fetching failure reasons 0/{}...", failed.len()));
+    let mut reasons = HashMap::new();
+    for (done, session) in failed.iter().enumerate() {
+        spinner.set_message(format!(
+            "Fetching failure reasons {}/{}...",
+            done + 1,
+            failed.len()
+        ));
+        match session_activities(&session.id).await {
+            Ok(activities) => {
+                if let Some(reason) = failure_reason(&activities) {
+                    reasons.insert(session.id.clone(), reason);
+                }
+            }
+            Err(e) if !is_quiet() => spinner.suspend(|| {
+                eprintln!(
+                    "Warning: Failed to fetch activities of session {}: {:#}",
+                    session.id, e
+                )
+            }),
+            Err(_) => {}
+        }
+    }
+    spinner.finish();
+    reasons
+}
+
+fn print_report(report: &SessionReport) {
+    let heading = format!("Jules Report: {}", report.period());
+    println!("{}", heading);
+    println!("{}", "═".repeat(heading.chars().count()));
+    if let Some(source) = &report.source {
+        println!("Source: {}", source);
+    }
+    println!("Sessions: {}", report.total_sessions);
+    if let Some(average) = report.average_duration() {
+        println!("Average Duration: {}", average);
+    }
+    println!("Pull Requests: {}", report.pull_requests.len());
+
+    if !report.states.is_empty() {
+        let width = report
+            .states
+            .iter()
+            .map(|count| count.state.len())
+            .max()
+            .unwrap_or(0);
+        println!();
+        println!("By State:");
+        for count in &report.states {
+            println!("  {:<width$}  {}", count.state, count.count, width = width);
+        }
+    }
+    if !report.pull_requests.is_empty() {
+        println!();
+        println!("Pull Requests:");
+        for pr in &report.pull_requests {
+            println!("  {} ({}){}", pr.title, pr.url, issues_suffix(&pr.issues));
+        }
+    }
+    if !report.failures.is_empty() {
+        println!();
+        println!("Failures:");
+        for failure in &report.failures {
+            println!(
+                "  {} [{}]{}: {}",
+                failure.title,
+                failure.session_id,
+                issues_suffix(&failure.issues),
+                failure.reason.as_deref().unwrap_or("Unknown reason")
+            );
+        }
+    }
+}
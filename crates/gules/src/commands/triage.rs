@@ -0,0 +1,222 @@
+//! Starting sessions on a labeled issue backlog (`gules triage`).
+//!
+//! Every open issue with the label gets a session, unless one is already
+//! linked to it, so triage can be rerun (e.g. from cron) as issues are
+//! labeled. At most `--max-active` sessions run on the repository at once;
+//! the remaining issues wait for a later run, as does everything while the
+//! GitHub API rate limit is down to `github.rate_limit_reserve`.
+
+use anyhow::Result;
+use jules_cli::commands::CreateArgs;
+use jules_core::display::{icon, is_quiet};
+use jules_core::issue_links::IssueLinks;
+use jules_core::session_report::source_matches;
+use jules_core::{api_client, load_config};
+use jules_rs::types::session::Session;
+use jules_rs::{JulesClient, State};
+
+use super::delete::list_all_sessions;
+use crate::extended_commands::{
+    announce_on_issue, create_request, is_terminal, issue_repo, print_sessions, record_issue_link,
+    OutputFormat,
+};
+use crate::github::{GitHubClient, GitHubIssue};
+
+/// What `triage` does with each labeled issue
+#[derive(Debug, Default)]
+pub struct TriagePlan {
+    /// Issues to start sessions on now
+    pub start: Vec<GitHubIssue>,
+    /// Issues that already have a session
+    pub linked: Vec<GitHubIssue>,
+    /// Issues left for a later run, to stay within the active session cap
+    pub deferred: Vec<GitHubIssue>,
+}
+
+/// Split `issues` into those already linked to a session, those to start
+/// sessions on while fewer than `max_active` sessions are `active`, and the
+/// rest
+pub fn plan_triage(
+    issues: Vec<GitHubIssue>,
+    links: &IssueLinks,
+    active: usize,
+    max_active: usize,
+) -> TriagePlan {
+    let mut plan = TriagePlan::default();
+    let mut slots = max_active.saturating_sub(active);
+    for issue in issues {
+        if !links
+            .for_issue(&issue.owner, &issue.repo, issue.number)
+            .is_empty()
+        {
+            plan.linked.push(issue);
+        } else if slots > 0 {
+            slots -= 1;
+            plan.start.push(issue);
+        } else {
+            plan.deferred.push(issue);
+        }
+    }
+    plan
+}
+
+/// Options of `gules triage`
+#[derive(Debug, Clone, Default)]
+pub struct TriageOptions {
+    pub label: String,
+    /// Repository of the issues (default: the git checkout's, else the
+    /// configured default source's)
+    pub owner: Option<String>,
+    pub repo: Option<String>,
+    /// Most sessions running on the repository at once
+    pub max_active: usize,
+    /// Include the issues' comments in the prompts
+    pub with_comments: bool,
+    /// Comment the session link on each issue
+    pub comment: bool,
+    /// Starting branch of the sessions
+    pub branch: Option<String>,
+    pub require_approval: bool,
+    /// Only print what would be done
+    pub dry_run: bool,
+}
+
+/// Start a session on each open issue with `options.label` that has none
+/// yet, within `options.max_active`, and print the sessions in `format`
+pub async fn handle_triage(options: TriageOptions, format: &str) -> Result<()> {
+    if options.max_active == 0 {
+        anyhow::bail!("--max-active must be at least 1");
+    }
+    let output_format = OutputFormat::parse(format)?;
+    let config = load_config()?;
+    let (owner, repo) = match (options.owner.clone(), options.repo.clone()) {
+        (Some(owner), Some(repo)) => (owner, repo),
+        _ => issue_repo(&config, None)?,
+    };
+    let source = format!("sources/github/{}/{}", owner, repo);
+    let github = GitHubClient::require(&config, "triage")?;
+    let reserve = config.github.rate_limit_reserve();
+    if let Some(limit) = github.low_rate_limit(reserve) {
+        anyhow::bail!(
+            "Not triaging {}/{}: only {} (github.rate_limit_reserve is {})",
+            owner,
+            repo,
+            limit.summary(),
+            reserve
+        );
+    }
+    let client = api_client(&config)?;
+
+    let issues = github.open_issues(&owner, &repo, &options.label).await?;
+    let active = list_all_sessions(&client, config.cache.enabled)
+        .await?
+        .iter()
+        .filter(|session| source_matches(session, &source))
+        .filter(|session| !is_terminal(session.state.unwrap_or(State::StateUnspecified)))
+        .count();
+    let plan = plan_triage(issues, &IssueLinks::load()?, active, options.max_active);
+
+    if !is_quiet() || options.dry_run {
+        eprintln!(
+            "{}/{}: {} open issue(s) labeled {}, {} with a session; {} active session(s) of at \
+             most {}",
+            owner,
+            repo,
+            plan.start.len() + plan.linked.len() + plan.deferred.len(),
+            options.label,
+            plan.linked.len(),
+            active,
+            options.max_active
+        );
+    }
+    if options.dry_run {
+        for issue in &plan.start {
+            println!("would start  #{} {}", issue.number, issue.title);
+        }
+        for issue in &plan.deferred {
+            println!("deferred     #{} {}", issue.number, issue.title);
+        }
+        return Ok(());
+    }
+
+    let mut sessions: Vec<Session> = Vec::new();
+    let mut errors = Vec::new();
+    for issue in &plan.start {
+        match start_session(&config, &client, &github, issue, &source, &options).await {
+            Ok(session) => {
+                if !is_quiet() {
+                    eprintln!(
+                        "  {} #{} {} → {}",
+                        icon("✓", "OK"),
+                        issue.number,
+                        issue.title,
+                        session.id
+                    );
+                }
+                sessions.push(session);
+            }
+            Err(e) => {
+                let e = e.context(format!("Issue #{} ({})", issue.number, issue.title));
+                eprintln!("  {} {:#}", icon("✗", "FAIL"), e);
+                errors.push(e);
+            }
+        }
+    }
+
+    if !sessions.is_empty() {
+        print_sessions(&sessions, output_format)?;
+    }
+    if !is_quiet() && !plan.deferred.is_empty() {
+        eprintln!(
+            "{} issue(s) left for a later run (--max-active {})",
+            plan.deferred.len(),
+            options.max_active
+        );
+    }
+    let failed = errors.len();
+    match errors.into_iter().next() {
+        Some(error) => Err(error.context(format!(
+            "Failed to start {} of {} session(s)",
+            failed,
+            plan.start.len()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Create a session on `issue` in `source`, link it to the issue and, with
+/// `options.comment`, comment the link there
+async fn start_session(
+    config: &jules_core::config::Config,
+    client: &JulesClient,
+    github: &GitHubClient,
+    issue: &GitHubIssue,
+    source: &str,
+    options: &TriageOptions,
+) -> Result<Session> {
+    let issue = if options.with_comments {
+        &github
+            .issue(&issue.owner, &issue.repo, issue.number, true)
+            .await?
+    } else {
+        issue
+    };
+    let request = create_request(
+        config,
+        CreateArgs {
+            prompt: issue.prompt(None),
+            source: Some(source.to_string()),
+            title: Some(issue.title.clone()),
+            branch: options.branch.clone(),
+            require_approval: options.require_approval,
+            automation_mode: None,
+            template: None,
+        },
+    )?;
+    let session = client.create_session(request).await?;
+    record_issue_link(issue, &session.id);
+    if options.comment {
+        announce_on_issue(config, &issue.owner, &issue.repo, issue.number, &session).await;
+    }
+    Ok(session)
+}
@@ -3,19 +3,60 @@
 //! Commands that require external dependencies or advanced features
 //! not available in the basic gules-cli crate.
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use chrono::Local;
-use jules_core::config::load_config;
+use jules_cli::commands::CreateArgs;
+use jules_core::activity_cache::fetch_all_activities;
+use jules_core::config::{api_client, load_config};
+use jules_core::csv_output::{print_activities_csv, print_sessions_csv, print_sources_csv};
+use jules_core::display::{icon, is_quiet, save_response};
+use jules_core::editor::{edit_text, non_empty_prompt, prompt_from_input};
+use jules_core::exit_code::{self, ExitError};
+use jules_core::git::repo_context;
+use jules_core::github_url::GitHubItemUrl;
+use jules_core::issue_links::IssueLinks;
+use jules_core::markdown_output::{activities_markdown, session_markdown, sessions_markdown};
+use jules_core::metrics::export_metrics;
+use jules_core::ndjson_output::{print_ndjson, WatchEvent};
+use jules_core::progress::Spinner;
+use jules_core::session_cache::{remember_session, remember_sessions, SessionStore};
+use jules_core::session_notes::NoteStore;
+use jules_core::session_refs::SessionPatterns;
+use jules_core::session_report::source_matches;
+use jules_core::sorting::{sort_activities, sort_sessions, ActivitySort, SessionSort, SortOrder};
+use jules_core::source_cache::{
+    expand_source, find_source, not_connected_message, refresh_sources, SourceStore,
+};
+use jules_core::template_output::{activity_record, print_templated, session_record};
+use jules_core::time_range::TimeRange;
+use jules_core::webhook::{parse_webhook_url, post_webhook, WebhookPayload};
+use jules_core::yaml_output::to_yaml;
+use jules_rs::types::session::Session;
 use jules_rs::JulesClient;
-use std::process::Command;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
 use tokio::time::{sleep, Duration};
 
+use crate::commands::{auto_approve_plan, track_recent};
+use crate::github::{
+    session_link_comment, session_outcome_comment, source_repo, GitHubClient, GitHubIssue,
+    LinkedPullRequest, PullRequestStatus,
+};
+
 /// Output format for CLI commands
 #[derive(Debug, Clone)]
 pub enum OutputFormat {
     Json,
     Table,
     Full,
+    /// Comma-separated values with a fixed column set
+    Csv,
+    /// Same data as JSON, as block-style YAML
+    Yaml,
+    /// Report for pasting into pull requests and issues
+    Markdown,
+    /// One compact JSON object per line
+    Ndjson,
 }
 
 impl OutputFormat {
@@ -24,8 +65,12 @@ impl OutputFormat {
             "json" => Ok(Self::Json),
             "table" => Ok(Self::Table),
             "full" => Ok(Self::Full),
+            "csv" => Ok(Self::Csv),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "ndjson" | "jsonl" => Ok(Self::Ndjson),
             _ => anyhow::bail!(
-                "Unknown output format: {}. Valid options: json, table, full",
+                "Unknown output format: {}. Valid options: json, table, full, csv, yaml, markdown, ndjson",
                 s
             ),
         }
@@ -34,37 +79,14 @@ impl OutputFormat {
 
 /// Handle issue-status command (requires gh CLI)
 pub async fn handle_issue_status(issue: u32, owner: &str, repo: &str) -> Result<()> {
-    // Check if gh CLI is available
-    if !is_gh_cli_available() {
-        anyhow::bail!(
-            "GitHub CLI (gh) is required for the issue-status command.\n\
-             Install from: https://cli.github.com\n\
-             \n\
-             Installation options:\n\
-             - Linux (apt):   sudo apt install gh\n\
-             - Linux (dnf):   sudo dnf install gh\n\
-             - macOS (brew):  brew install gh\n\
-             - Windows:       winget install --id GitHub.cli\n\
-             - Or download from: https://github.com/cli/cli/releases"
-        );
-    }
-
     // Load API key
     let config = load_config()?;
-    let api_key = config
-        .api_key
-        .context("API key not configured. Run 'gules config init'")?;
-    let client = JulesClient::new(&api_key);
-
-    // Get issue comments via gh CLI
-    let comments = get_issue_comments_via_gh(owner, repo, issue)?;
-
-    // Parse comments for Jules session IDs
-    let session_ids = extract_jules_session_ids(&comments);
+    let client = api_client(&config)?;
 
+    let session_ids = issue_session_ids(&config, owner, repo, issue, "issue-status").await?;
     if session_ids.is_empty() {
         println!(
-            "No Jules sessions found in {}/{}#{} comments",
+            "No Jules sessions linked to or mentioned in {}/{}#{}",
             owner, repo, issue
         );
         return Ok(());
@@ -113,80 +135,305 @@ pub async fn handle_issue_status(issue: u32, owner: &str, repo: &str) -> Result<
     Ok(())
 }
 
-/// Handle pr-status command (requires gh CLI)
-pub async fn handle_pr_status(session_id: &str) -> Result<()> {
+/// Handle pr-status command, with the PR's checks, reviews and
+/// mergeability from GitHub when reachable
+///
+/// `format` is `table` (default) or `json`.
+pub async fn handle_pr_status(session_id: &str, format: &str) -> Result<()> {
+    let json = match format.to_lowercase().as_str() {
+        "json" => true,
+        "table" => false,
+        _ => anyhow::bail!("Unknown format: {}. Valid options: table, json", format),
+    };
     // Load API key
     let config = load_config()?;
-    let api_key = config
-        .api_key
-        .context("API key not configured. Run 'gules config init'")?;
-    let client = JulesClient::new(&api_key);
+    let client = api_client(&config)?;
 
     // Get session details
     let session = client.get_session(session_id).await?;
 
-    // Extract PR info from session outputs
-    if session.outputs.is_empty() {
-        println!("No outputs found for session {}", session_id);
-        return Ok(());
+    let prs: Vec<_> = session
+        .outputs
+        .into_iter()
+        .filter_map(|output| output.pull_request)
+        .collect();
+    let github = if prs.is_empty() {
+        None
+    } else {
+        GitHubClient::detect(&config)?
+    };
+
+    let mut results = Vec::new();
+    for pr in &prs {
+        let status = match (&github, pr.url.as_deref()) {
+            (Some(github), Some(url)) => Some(get_pr_status(github, url).await),
+            _ => None,
+        };
+        results.push(status);
     }
 
-    let mut found_pr = false;
-    for output in session.outputs {
-        if let Some(pr) = output.pull_request {
-            found_pr = true;
-            println!("PR Information for session {}:\n", session_id);
-            let title = pr.title.as_deref().unwrap_or("[No title]");
-            let url = pr.url.as_deref().unwrap_or("[No URL]");
-            let description = pr.description.as_deref().unwrap_or("[No description]");
-            println!("  Title: {}", title);
-            println!("  URL: {}", url);
-            println!("  Description: {}", description);
-
-            // Optionally fetch PR details via gh CLI
-            if is_gh_cli_available() {
-                if let Some(pr_url) = pr.url.as_ref() {
-                    if let Ok(pr_details) = get_pr_details_via_gh(pr_url) {
-                        println!("\nGitHub PR Details:");
-                        for (key, value) in pr_details {
-                            println!("  {}: {}", key, value);
-                        }
-                    }
+    if json {
+        let prs: Vec<_> = prs
+            .iter()
+            .zip(&results)
+            .map(|(pr, status)| {
+                let mut value = json!({
+                    "title": pr.title,
+                    "url": pr.url,
+                    "description": pr.description,
+                    "github": null,
+                });
+                match status {
+                    Some(Ok(status)) => value["github"] = pr_status_json(status),
+                    Some(Err(e)) => value["github_error"] = json!(format!("{:#}", e)),
+                    None => {}
                 }
-            } else {
-                println!("\nNote: Install GitHub CLI (gh) for detailed PR status.");
+                value
+            })
+            .collect();
+        let output = json!({ "session_id": session_id, "pull_requests": prs });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if prs.is_empty() {
+        println!("No PR found in outputs for session {}", session_id);
+        return Ok(());
+    }
+    for (pr, status) in prs.iter().zip(results) {
+        println!("PR Information for session {}:\n", session_id);
+        let title = pr.title.as_deref().unwrap_or("[No title]");
+        let url = pr.url.as_deref().unwrap_or("[No URL]");
+        let description = pr.description.as_deref().unwrap_or("[No description]");
+        println!("  Title: {}", title);
+        println!("  URL: {}", url);
+        println!("  Description: {}", description);
+
+        match status {
+            Some(Ok(status)) => print_pr_status(&status),
+            Some(Err(e)) => eprintln!("\nWarning: Couldn't fetch PR details: {:#}", e),
+            None if github.is_none() => {
+                println!(
+                    "\nNote: Set GITHUB_TOKEN (or run 'gules github login'), or install GitHub CLI (gh), for detailed PR status."
+                );
                 println!("  https://cli.github.com");
             }
+            None => {}
         }
     }
 
-    if !found_pr {
-        println!("No PR found in outputs for session {}", session_id);
+    Ok(())
+}
+
+/// Print a PR's GitHub status under the PR information
+fn print_pr_status(status: &PullRequestStatus) {
+    println!("\nGitHub PR Details:");
+    if let Some(state) = &status.state {
+        let draft = if status.draft { " (draft)" } else { "" };
+        println!("  State: {}{}", state, draft);
+    }
+    let fields = [
+        ("Title", &status.title),
+        ("Author", &status.author),
+        ("Created", &status.created_at),
+        ("Merged", &status.merged_at),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            println!("  {}: {}", label, value);
+        }
+    }
+    if status.merged_at.is_none() {
+        let mergeable = match status.mergeable {
+            Some(true) => "yes",
+            Some(false) => "no (conflicts)",
+            None => "unknown",
+        };
+        println!("  Mergeable: {}", mergeable);
+    }
+    if let Some(files) = status.changed_files {
+        println!(
+            "  Changed files: {} (+{} -{})",
+            files,
+            status.additions.unwrap_or(0),
+            status.deletions.unwrap_or(0)
+        );
     }
 
-    Ok(())
+    let passed = status.checks.iter().filter(|c| c.passed()).count();
+    let pending = status.checks.iter().filter(|c| c.pending()).count();
+    let failed = status.checks.len() - passed - pending;
+    match status.checks_state() {
+        "none" => println!("  Checks: none"),
+        state => println!(
+            "  Checks: {} ({} passed, {} failed, {} pending)",
+            state, passed, failed, pending
+        ),
+    }
+    for check in status.checks.iter().filter(|c| !c.passed()) {
+        match &check.conclusion {
+            Some(conclusion) => {
+                println!("    {} {}: {}", icon("✗", "FAIL"), check.name, conclusion)
+            }
+            None => println!("    … {}: pending", check.name),
+        }
+    }
+
+    println!("  Reviews: {}", status.review_state().replace('_', " "));
+    for review in &status.reviews {
+        println!("    {}: {}", review.reviewer, review.state);
+    }
+}
+
+/// A PR's GitHub status as JSON, with the overall check and review states
+fn pr_status_json(status: &PullRequestStatus) -> serde_json::Value {
+    let checks: Vec<_> = status
+        .checks
+        .iter()
+        .map(|check| {
+            json!({
+                "name": check.name,
+                "conclusion": check.conclusion,
+                "details_url": check.details_url,
+            })
+        })
+        .collect();
+    let reviews: Vec<_> = status
+        .reviews
+        .iter()
+        .map(|review| json!({ "reviewer": review.reviewer, "state": review.state }))
+        .collect();
+    json!({
+        "state": status.state,
+        "title": status.title,
+        "author": status.author,
+        "created_at": status.created_at,
+        "merged_at": status.merged_at,
+        "draft": status.draft,
+        "head_branch": status.head_branch,
+        "head_sha": status.head_sha,
+        "mergeable": status.mergeable,
+        "changed_files": status.changed_files,
+        "additions": status.additions,
+        "deletions": status.deletions,
+        "checks_state": status.checks_state(),
+        "checks": checks,
+        "review_state": status.review_state(),
+        "reviews": reviews,
+    })
 }
 
 /// Handle watch command with real-time monitoring
-pub async fn handle_watch(session_id: &str, interval: u64) -> Result<()> {
+///
+/// `format` is `table` (default) or `ndjson`, which streams events instead.
+/// With a `timeout`, gives up once it elapses and exits with
+/// [`exit_code::WATCH_TIMEOUT`]. The session is reported to `webhook`, else
+/// the configured one, once it finishes, and with `comment_on_issue` (or
+/// `notifications.issue_comments`) on the issues it is linked to.
+pub async fn handle_watch(
+    session_id: &str,
+    interval: u64,
+    timeout: Option<Duration>,
+    format: &str,
+    webhook: Option<&str>,
+    comment_on_issue: bool,
+) -> Result<()> {
+    let watch = watch_session(session_id, interval, format, webhook, comment_on_issue);
+    with_timeout(session_id, timeout, watch).await
+}
+
+/// Run `follow`, which waits on a session, for at most `timeout`
+async fn with_timeout(
+    session_id: &str,
+    timeout: Option<Duration>,
+    follow: impl std::future::Future<Output = Result<()>>,
+) -> Result<()> {
+    let Some(timeout) = timeout else {
+        return follow.await;
+    };
+    tokio::time::timeout(timeout, follow)
+        .await
+        .unwrap_or_else(|_| {
+            Err(ExitError::new(
+                exit_code::WATCH_TIMEOUT,
+                format!(
+                    "Timed out after {}s waiting for session {}",
+                    timeout.as_secs(),
+                    session_id
+                ),
+            )
+            .into())
+        })
+}
+
+/// Watch a session until it reaches a terminal state, then report it to
+/// `webhook` (else the configured `notifications.webhook_url`) and, with
+/// `comment_on_issue` or `notifications.issue_comments`, its linked issues
+async fn watch_session(
+    session_id: &str,
+    interval: u64,
+    format: &str,
+    webhook: Option<&str>,
+    comment_on_issue: bool,
+) -> Result<()> {
+    let ndjson = parse_stream_format(format)?;
+
     // Load API key
     let config = load_config()?;
-    let api_key = config
-        .api_key
-        .context("API key not configured. Run 'gules config init'")?;
-    let client = JulesClient::new(&api_key);
+    let client = api_client(&config)?;
 
-    println!(
-        "Watching session {} (polling every {}s)...",
-        session_id, interval
-    );
-    println!("Press Ctrl+C to stop monitoring\n");
+    let webhook = webhook.or(config.notifications.webhook_url.as_deref());
+    if let Some(url) = webhook {
+        parse_webhook_url(url)?;
+    }
+
+    track_recent(session_id, None);
+    let session = if ndjson {
+        watch_ndjson(&client, session_id, interval).await?
+    } else {
+        watch_table(&client, session_id, interval).await?
+    };
+    let comment_on_issue = comment_on_issue || config.notifications.issue_comments;
+    if webhook.is_some() || comment_on_issue {
+        let payload = finished_payload(&client, &session).await;
+        if let Some(url) = webhook {
+            if let Err(e) = post_webhook(url, &payload).await {
+                eprintln!("Warning: {:#}", e);
+            }
+        }
+        if comment_on_issue {
+            report_on_issues(&config, &payload).await;
+        }
+    }
+    watch_outcome(
+        session_id,
+        session.state.unwrap_or(jules_rs::State::StateUnspecified),
+    )
+}
+
+/// Print a session's status until it reaches a terminal state, and return
+/// it in that state
+async fn watch_table(client: &JulesClient, session_id: &str, interval: u64) -> Result<Session> {
+    let quiet = is_quiet();
+    if !quiet {
+        println!(
+            "Watching session {} (polling every {}s)...",
+            session_id, interval
+        );
+        println!("Press Ctrl+C to stop monitoring\n");
+    }
 
     let mut last_activity_count = 0;
 
     loop {
         // Get current session status
         match client.get_session(session_id).await {
+            Ok(session) if quiet => {
+                // Only the outcome matters
+                if session.state.is_some_and(is_terminal) {
+                    return Ok(session);
+                }
+            }
             Ok(session) => {
                 // Display session header
                 println!("\n─── Session Status ────────────────────────────");
@@ -198,17 +445,13 @@ pub async fn handle_watch(session_id: &str, interval: u64) -> Result<()> {
                     println!("Created: {}", create_time);
                 }
 
-                // Check if session is in terminal state
-                let is_terminal = matches!(
-                    session.state,
-                    Some(jules_rs::State::Completed)
-                        | Some(jules_rs::State::Failed)
-                        | Some(jules_rs::State::Paused)
-                );
-
-                if is_terminal {
-                    println!("\n✓ Session reached terminal state: {:?}", session.state);
-                    break;
+                if let Some(state) = session.state.filter(|state| is_terminal(*state)) {
+                    println!(
+                        "\n{} Session reached terminal state: {:?}",
+                        icon("✓", "OK"),
+                        state
+                    );
+                    return Ok(session);
                 }
 
                 // Try to fetch latest activities
@@ -238,78 +481,213 @@ pub async fn handle_watch(session_id: &str, interval: u64) -> Result<()> {
 
         sleep(Duration::from_secs(interval)).await;
     }
+}
 
-    Ok(())
+/// Message sent by `resume` when none is given
+const DEFAULT_RESUME_MESSAGE: &str = "Please continue.";
+
+/// Largest page the API returns when listing sessions
+const SESSION_PAGE_SIZE: u32 = 100;
+
+/// Pages `sessions` reads at most while looking for `--limit` matches
+const MAX_SESSION_PAGES: usize = 10;
+
+/// Send a message to a session awaiting feedback (by default, asking it to
+/// continue), then watch it like `handle_watch`
+pub async fn handle_resume(
+    session_id: &str,
+    message: Option<&str>,
+    interval: u64,
+    format: &str,
+) -> Result<()> {
+    // Reject a bad format before sending anything
+    parse_stream_format(format)?;
+
+    let config = load_config()?;
+    let client = api_client(&config)?;
+
+    let session = client.get_session(session_id).await?;
+    let state = session.state.unwrap_or(jules_rs::State::StateUnspecified);
+    if state != jules_rs::State::AwaitingUserFeedback {
+        anyhow::bail!(
+            "Session {} is {}, not awaiting feedback. Use 'gules send-message' to message it anyway",
+            session_id,
+            state.display_name().to_lowercase()
+        );
+    }
+
+    client
+        .send_message(session_id, message.unwrap_or(DEFAULT_RESUME_MESSAGE))
+        .await?;
+    if !is_quiet() {
+        eprintln!("Message sent to session {}", session_id);
+    }
+
+    handle_watch(session_id, interval, None, format, None, false).await
+}
+
+/// Whether `watch` stops at this state
+pub(crate) fn is_terminal(state: jules_rs::State) -> bool {
+    matches!(
+        state,
+        jules_rs::State::Completed | jules_rs::State::Failed | jules_rs::State::Paused
+    )
+}
+
+/// Result of a watch that ended in `state`: failed and paused sessions exit
+/// with their own codes, so scripts can branch on them
+fn watch_outcome(session_id: &str, state: jules_rs::State) -> Result<()> {
+    match state {
+        jules_rs::State::Failed => Err(ExitError::new(
+            exit_code::SESSION_FAILED,
+            format!("Session {} failed", session_id),
+        )
+        .into()),
+        jules_rs::State::Paused => Err(ExitError::new(
+            exit_code::SESSION_PAUSED,
+            format!("Session {} is paused", session_id),
+        )
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// Summary of a finished session, with the failure reason from its
+/// activities if it failed
+async fn finished_payload(client: &JulesClient, session: &Session) -> WebhookPayload {
+    let activities = match session.state {
+        Some(jules_rs::State::Failed) => client
+            .list_activities(&session.id, Some(50), None)
+            .await
+            .map(|response| response.activities)
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    WebhookPayload::new(session, &activities)
+}
+
+/// Comment the outcome of a completed or failed session on the issues it is
+/// linked to; failures are only warnings, since the session's outcome is
+/// what the exit code reports
+async fn report_on_issues(config: &jules_core::config::Config, payload: &WebhookPayload) {
+    let Some(comment) = session_outcome_comment(payload) else {
+        return;
+    };
+    let links = match IssueLinks::load() {
+        Ok(links) => links,
+        Err(e) => {
+            eprintln!("Warning: {:#}", e);
+            return;
+        }
+    };
+    let links = links.for_session(&payload.session_id);
+    if links.is_empty() {
+        if !is_quiet() {
+            eprintln!(
+                "Note: Session {} isn't linked to a GitHub issue; nothing to comment on",
+                payload.session_id
+            );
+        }
+        return;
+    }
+    let github = match GitHubClient::detect(config) {
+        Ok(Some(github)) => github,
+        Ok(None) => {
+            eprintln!("Warning: Couldn't comment on the linked issues: GitHub can't be reached");
+            return;
+        }
+        Err(e) => {
+            eprintln!("Warning: Couldn't comment on the linked issues: {:#}", e);
+            return;
+        }
+    };
+    for link in links {
+        match github
+            .comment_on_issue(&link.owner, &link.repo, link.issue, &comment)
+            .await
+        {
+            Ok(()) if !is_quiet() => eprintln!(
+                "{} Reported the outcome on {}/{}#{}",
+                icon("💬", "*"),
+                link.owner,
+                link.repo,
+                link.issue
+            ),
+            Ok(()) => {}
+            Err(e) => eprintln!("Warning: {:#}", e),
+        }
+    }
 }
 
 /// Handle monitor command for all sessions
-pub async fn handle_monitor(interval: u64) -> Result<()> {
+///
+/// `format` is `table` (default) or `ndjson`, which streams events instead.
+/// Only sessions passing `filter` are shown. With `changes_only`, the table
+/// is printed once and then only sessions that appeared or changed state.
+pub async fn handle_monitor(
+    filter: &SessionFilter,
+    interval: u64,
+    changes_only: bool,
+    format: &str,
+) -> Result<()> {
+    let ndjson = parse_stream_format(format)?;
+
     // Load API key
     let config = load_config()?;
-    let api_key = config
-        .api_key
-        .context("API key not configured. Run 'gules config init'")?;
-    let client = JulesClient::new(&api_key);
+    let client = export_metrics(api_client(&config)?, &config);
 
-    println!("Monitoring all sessions (polling every {}s)...", interval);
-    println!("Press Ctrl+C to stop monitoring\n");
+    if ndjson {
+        return monitor_ndjson(&client, filter, interval, config.cache.enabled).await;
+    }
+
+    if !is_quiet() {
+        println!("Monitoring all sessions (polling every {}s)...", interval);
+        println!("Press Ctrl+C to stop monitoring\n");
+    }
 
+    let mut tracker = MonitorTracker::default();
+    let mut first_poll = true;
     loop {
         // Get all sessions
         match client.list_sessions(Some(100), None).await {
             Ok(response) => {
                 let sessions = response.sessions;
+                if config.cache.enabled {
+                    remember_sessions(&sessions);
+                }
 
-                if sessions.is_empty() {
-                    println!("No sessions found");
+                let changes = tracker.update(filter, &sessions);
+                if changes_only && !first_poll {
+                    print_session_changes(&changes);
                 } else {
-                    println!(
-                        "\n─── Sessions Summary ─────────────────────────── ({} sessions)",
-                        sessions.len()
-                    );
-                    println!(
-                        "{:<20} {:<25} {:<15} {:<20}",
-                        "ID", "Title", "State", "Created"
-                    );
-                    println!("{}", "─".repeat(80));
-
-                    for session in &sessions {
-                        let title = session
-                            .title
-                            .as_deref()
-                            .unwrap_or("(no title)")
-                            .chars()
-                            .take(25)
-                            .collect::<String>();
-
-                        let state_str = session
-                            .state
-                            .as_ref()
-                            .map(|s| format!("{:?}", s))
-                            .unwrap_or_else(|| "Unknown".to_string());
-
-                        let created = session
-                            .create_time
-                            .as_deref()
-                            .unwrap_or("N/A")
-                            .chars()
-                            .take(19)
-                            .collect::<String>();
+                    let shown: Vec<_> = sessions
+                        .into_iter()
+                        .filter(|session| filter.matches(session))
+                        .collect();
+                    print_sessions_summary(&shown);
+                    println!("\nLast updated: {}", Local::now().format("%H:%M:%S"));
+                }
+                first_poll = false;
+            }
+            Err(e) => {
+                eprintln!("Error fetching sessions: {}", e);
 
+                // Offline: show the last known state from the session cache
+                if let Ok(store) = SessionStore::load() {
+                    if let Some(refreshed) = store.last_refresh() {
+                        let sessions: Vec<_> = store
+                            .list()
+                            .into_iter()
+                            .filter(|session| filter.matches(session))
+                            .cloned()
+                            .collect();
+                        print_sessions_summary(&sessions);
                         println!(
-                            "{:<20} {:<25} {:<15} {:<20}",
-                            session.id.chars().take(20).collect::<String>(),
-                            title,
-                            state_str.chars().take(15).collect::<String>(),
-                            created
+                            "\n(cached, last refreshed {})",
+                            refreshed.with_timezone(&Local).format("%H:%M:%S")
                         );
                     }
                 }
-
-                println!("\nLast updated: {}", Local::now().format("%H:%M:%S"));
-            }
-            Err(e) => {
-                eprintln!("Error fetching sessions: {}", e);
             }
         }
 
@@ -317,202 +695,502 @@ pub async fn handle_monitor(interval: u64) -> Result<()> {
     }
 }
 
-// ─────────────────────────────────────────────────────────────────────────
-// Helper Functions
-// ─────────────────────────────────────────────────────────────────────────
-
-/// Check if gh CLI is available
-fn is_gh_cli_available() -> bool {
-    Command::new("gh")
-        .arg("--version")
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
-}
-
-/// Get issue comments via gh CLI
-fn get_issue_comments_via_gh(owner: &str, repo: &str, issue: u32) -> Result<Vec<String>> {
-    let output = Command::new("gh")
-        .arg("issue")
-        .arg("view")
-        .arg(issue.to_string())
-        .arg("--repo")
-        .arg(format!("{}/{}", owner, repo))
-        .arg("--json")
-        .arg("comments")
-        .output()
-        .context("Failed to run gh CLI")?;
-
-    if !output.status.success() {
-        anyhow::bail!("gh CLI failed: {}", String::from_utf8_lossy(&output.stderr));
-    }
-
-    // Parse JSON output
-    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
-
-    // Extract comment bodies
-    let comments = json["comments"]
-        .as_array()
-        .unwrap_or(&vec![])
-        .iter()
-        .filter_map(|c| c["body"].as_str())
-        .map(|s| s.to_string())
-        .collect();
-
-    Ok(comments)
+/// A difference `monitor` found between two polls
+#[derive(Debug)]
+pub enum SessionChange<'a> {
+    /// A session shown for the first time
+    New(&'a Session),
+    /// A shown session whose state changed, from the previous state
+    State(Option<jules_rs::State>, &'a Session),
 }
 
-/// Extract Jules session IDs from comments
-fn extract_jules_session_ids(comments: &[String]) -> Vec<String> {
-    let mut session_ids = Vec::new();
-    let patterns = [
-        r"sessions/([a-zA-Z0-9_-]+)",
-        r"session[:\s]+([a-zA-Z0-9_-]+)",
-        r"https://console\.cloud\.google\.com/[^/]*/([a-zA-Z0-9_-]+)",
-    ];
+/// States of the sessions `monitor` shows, to tell what changed between
+/// polls
+#[derive(Debug, Default)]
+pub struct MonitorTracker {
+    states: HashMap<String, Option<jules_rs::State>>,
+}
 
-    for comment in comments {
-        for pattern_str in &patterns {
-            if let Ok(pattern) = regex::Regex::new(pattern_str) {
-                for cap in pattern.captures_iter(comment) {
-                    if let Some(session_id) = cap.get(1) {
-                        let id = session_id.as_str().to_string();
-                        if !session_ids.contains(&id) {
-                            session_ids.push(id);
-                        }
-                    }
+impl MonitorTracker {
+    /// Record a poll and return what changed since the previous one
+    ///
+    /// A shown session that changes state is reported even when its new
+    /// state no longer passes `filter` (e.g. an active session completing);
+    /// it is then dropped from the tracked sessions.
+    pub fn update<'a>(
+        &mut self,
+        filter: &SessionFilter,
+        sessions: &'a [Session],
+    ) -> Vec<SessionChange<'a>> {
+        let mut changes = Vec::new();
+        for session in sessions {
+            let shown = filter.matches(session);
+            match self.states.get(&session.id) {
+                Some(previous) if *previous != session.state => {
+                    changes.push(SessionChange::State(*previous, session))
                 }
+                Some(_) => {}
+                None if shown => changes.push(SessionChange::New(session)),
+                None => {}
+            }
+            if shown {
+                self.states.insert(session.id.clone(), session.state);
+            } else {
+                self.states.remove(&session.id);
             }
         }
+        changes
     }
+}
 
-    session_ids
+/// Print one line per change for `monitor --changes-only`
+fn print_session_changes(changes: &[SessionChange]) {
+    let time = Local::now().format("%H:%M:%S");
+    for change in changes {
+        let (from, session) = match change {
+            SessionChange::New(session) => ("new".to_string(), *session),
+            SessionChange::State(from, session) => (state_label(*from), *session),
+        };
+        let title = session
+            .title
+            .as_deref()
+            .unwrap_or("(no title)")
+            .chars()
+            .take(25)
+            .collect::<String>();
+        println!(
+            "{}  {:<20} {:<25} {} → {}",
+            time,
+            session.id.chars().take(20).collect::<String>(),
+            title,
+            from,
+            state_label(session.state)
+        );
+    }
+}
+
+/// A session state as the monitor table shows it
+fn state_label(state: Option<jules_rs::State>) -> String {
+    state
+        .map(|state| format!("{:?}", state))
+        .unwrap_or_else(|| "Unknown".to_string())
 }
 
-/// Get PR details via gh CLI
-fn get_pr_details_via_gh(pr_url: &str) -> Result<Vec<(String, String)>> {
-    // Extract owner/repo/pr-number from URL
-    // Format: https://github.com/{owner}/{repo}/pull/{number}
-    let parts: Vec<&str> = pr_url.split('/').collect();
-    if parts.len() < 7 || parts[4] != "pull" {
-        anyhow::bail!("Invalid PR URL format");
+/// Whether a `watch`/`monitor` format selects NDJSON events
+fn parse_stream_format(format: &str) -> Result<bool> {
+    match OutputFormat::parse(format)? {
+        OutputFormat::Table => Ok(false),
+        OutputFormat::Ndjson => Ok(true),
+        _ => anyhow::bail!(
+            "Unsupported format for streaming: {}. Use table or ndjson",
+            format
+        ),
     }
+}
 
-    let owner = parts[3];
-    let repo = parts[4];
-    let pr_number = parts[6];
+/// Stream a session as NDJSON events until it reaches a terminal state,
+/// and return it in that state
+///
+/// Emits the session once, then every new activity (oldest first) and every
+/// state change. Errors go to stderr so stdout stays parseable.
+async fn watch_ndjson(client: &JulesClient, session_id: &str, interval: u64) -> Result<Session> {
+    let mut last_state = None;
+    let mut seen_activities = HashSet::new();
+    let mut first_poll = true;
 
-    let output = Command::new("gh")
-        .arg("pr")
-        .arg("view")
-        .arg(pr_number)
-        .arg("--repo")
-        .arg(format!("{}/{}", owner, repo))
-        .arg("--json")
-        .arg("state,title,author,createdAt,mergedAt")
-        .output()
-        .context("Failed to run gh PR view")?;
+    loop {
+        match client.get_session(session_id).await {
+            Ok(session) => {
+                if first_poll {
+                    print_ndjson([WatchEvent::session(&session)])?;
+                    first_poll = false;
+                } else if session.state != last_state {
+                    print_ndjson([WatchEvent::state_change(last_state, &session)])?;
+                }
+                last_state = session.state;
+
+                match client.list_activities(session_id, Some(50), None).await {
+                    Ok(response) => {
+                        let mut new: Vec<_> = response
+                            .activities
+                            .into_iter()
+                            .filter(|activity| seen_activities.insert(activity.id.clone()))
+                            .collect();
+                        new.sort_by(|a, b| a.create_time.cmp(&b.create_time));
+                        print_ndjson(
+                            new.iter()
+                                .map(|activity| WatchEvent::activity(session_id, activity)),
+                        )?;
+                    }
+                    Err(e) => eprintln!("Error fetching activities: {}", e),
+                }
+
+                if session.state.is_some_and(is_terminal) {
+                    return Ok(session);
+                }
+            }
+            Err(e) => eprintln!("Error fetching session status: {}", e),
+        }
 
-    if !output.status.success() {
-        anyhow::bail!("gh PR view failed");
+        sleep(Duration::from_secs(interval)).await;
     }
+}
+
+/// Stream the sessions passing `filter` as NDJSON events
+///
+/// Every session is emitted when first seen; afterwards only state changes.
+async fn monitor_ndjson(
+    client: &JulesClient,
+    filter: &SessionFilter,
+    interval: u64,
+    cache_enabled: bool,
+) -> Result<()> {
+    let mut tracker = MonitorTracker::default();
 
-    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    loop {
+        match client.list_sessions(Some(100), None).await {
+            Ok(response) => {
+                let sessions = response.sessions;
+                if cache_enabled {
+                    remember_sessions(&sessions);
+                }
 
-    let mut details = vec![];
-    if let Some(state) = json["state"].as_str() {
-        details.push(("State".to_string(), state.to_string()));
+                print_ndjson(tracker.update(filter, &sessions).into_iter().map(
+                    |change| match change {
+                        SessionChange::New(session) => WatchEvent::session(session),
+                        SessionChange::State(from, session) => {
+                            WatchEvent::state_change(from, session)
+                        }
+                    },
+                ))?;
+            }
+            Err(e) => eprintln!("Error fetching sessions: {}", e),
+        }
+
+        sleep(Duration::from_secs(interval)).await;
     }
-    if let Some(title) = json["title"].as_str() {
-        details.push(("Title".to_string(), title.to_string()));
+}
+
+/// Print the monitor summary table
+fn print_sessions_summary(sessions: &[jules_rs::types::session::Session]) {
+    if sessions.is_empty() {
+        println!("No sessions found");
+        return;
     }
-    if let Some(author) = json["author"]["login"].as_str() {
-        details.push(("Author".to_string(), author.to_string()));
+
+    println!(
+        "\n─── Sessions Summary ─────────────────────────── ({} sessions)",
+        sessions.len()
+    );
+    println!(
+        "{:<20} {:<25} {:<15} {:<20}",
+        "ID", "Title", "State", "Created"
+    );
+    println!("{}", "─".repeat(80));
+
+    for session in sessions {
+        let title = session
+            .title
+            .as_deref()
+            .unwrap_or("(no title)")
+            .chars()
+            .take(25)
+            .collect::<String>();
+
+        let state_str = state_label(session.state);
+
+        let created = session
+            .create_time
+            .as_deref()
+            .unwrap_or("N/A")
+            .chars()
+            .take(19)
+            .collect::<String>();
+
+        println!(
+            "{:<20} {:<25} {:<15} {:<20}",
+            session.id.chars().take(20).collect::<String>(),
+            title,
+            state_str.chars().take(15).collect::<String>(),
+            created
+        );
     }
-    if let Some(created) = json["createdAt"].as_str() {
-        details.push(("Created".to_string(), created.to_string()));
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Helper Functions
+// ─────────────────────────────────────────────────────────────────────────
+
+/// Sessions of an issue: those linked to it locally, then those mentioned
+/// in its comments and in the descriptions, branch names and commit
+/// messages of the pull requests linked to it (see `github.session_patterns`)
+///
+/// GitHub is only required without local links; with them, failing to read
+/// the comments is a warning. Failing to read the linked pull requests
+/// always is.
+pub(crate) async fn issue_session_ids(
+    config: &jules_core::config::Config,
+    owner: &str,
+    repo: &str,
+    issue: u32,
+    command: &str,
+) -> Result<Vec<String>> {
+    let patterns = SessionPatterns::new(&config.github.session_patterns)?;
+    let mut session_ids: Vec<String> = IssueLinks::load()?
+        .for_issue(owner, repo, issue.into())
+        .iter()
+        .map(|link| link.session_id.clone())
+        .collect();
+    let github = if session_ids.is_empty() {
+        Some(GitHubClient::require(config, command)?)
+    } else {
+        GitHubClient::detect(config)?
+    };
+    let Some(github) = github else {
+        return Ok(session_ids);
+    };
+
+    let mut texts = match github.issue_comments(owner, repo, issue).await {
+        Ok(comments) => comments,
+        Err(e) if session_ids.is_empty() => return Err(e),
+        Err(e) => {
+            eprintln!("Warning: Only showing locally linked sessions: {:#}", e);
+            return Ok(session_ids);
+        }
+    };
+    match github.linked_pull_requests(owner, repo, issue.into()).await {
+        Ok(pull_requests) => texts.extend(
+            pull_requests
+                .iter()
+                .flat_map(LinkedPullRequest::texts)
+                .map(str::to_string),
+        ),
+        Err(e) => eprintln!("Warning: Only searching the issue's comments: {:#}", e),
     }
-    if let Some(merged) = json["mergedAt"].as_str() {
-        details.push(("Merged".to_string(), merged.to_string()));
+    for session_id in patterns.extract(texts.iter().map(String::as_str)) {
+        if !session_ids.contains(&session_id) {
+            session_ids.push(session_id);
+        }
     }
+    Ok(session_ids)
+}
 
-    Ok(details)
+/// Get the status of the PR at `pr_url` from GitHub
+async fn get_pr_status(github: &GitHubClient, pr_url: &str) -> Result<PullRequestStatus> {
+    let pr = GitHubItemUrl::parse_pull_request(pr_url)?;
+    github.pull_request_status(&pr).await
 }
 
 // ─────────────────────────────────────────────────────────────────────────
 // Formatted Output Handlers
 // ─────────────────────────────────────────────────────────────────────────
 
+/// How a formatted handler prints its results
+pub struct OutputOptions<'a> {
+    /// Output format name, see [`OutputFormat::parse`]
+    pub format: &'a str,
+    /// Output template or the name of one (`--template`); overrides the format
+    pub template: Option<&'a str>,
+    /// File to save the results to as JSON instead of printing them
+    pub output: Option<&'a str>,
+}
+
 /// Handle sessions command with format support
+///
+/// With `cached`, sessions come from the local session cache instead of the API.
 pub async fn handle_sessions_formatted(
-    state: Option<String>,
-    search: Option<String>,
+    filter: &SessionFilter,
     limit: u32,
-    format: &str,
+    cached: bool,
+    sort: Option<SortOrder<SessionSort>>,
+    options: &OutputOptions<'_>,
 ) -> Result<()> {
     let config = load_config()?;
-    let api_key = config.api_key.context("API key not configured")?;
-    let client = JulesClient::new(&api_key);
+    let template = options
+        .template
+        .map(|spec| config.output_template(spec))
+        .transpose()?;
+
+    // Filters apply before --limit, so it counts matching sessions
+    let matches = |session: &Session| filter.matches(session);
+    let mut filtered: Vec<Session> = if cached {
+        let store = SessionStore::load()?;
+        match store.last_refresh() {
+            Some(refreshed) if !is_quiet() => eprintln!(
+                "Using cached sessions (last refreshed {})",
+                refreshed.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+            ),
+            Some(_) => {}
+            None => anyhow::bail!(
+                "No cached sessions. Run 'gules sessions' while online to populate the cache."
+            ),
+        }
+        store
+            .list()
+            .into_iter()
+            .filter(|session| matches(session))
+            .take(limit as usize)
+            .cloned()
+            .collect()
+    } else {
+        let client = api_client(&config)?;
+        list_matching_sessions(&client, limit as usize, filter, config.cache.enabled).await?
+    };
+    if let Some(order) = sort {
+        sort_sessions(&mut filtered, order);
+    }
 
-    let response = client.list_sessions(Some(limit), None).await?;
-    let sessions = response.sessions;
+    if let Some(path) = options.output {
+        return save_response(&serde_json::to_value(&filtered)?, Some(path.into()), true).await;
+    }
+    if let Some(template) = template {
+        print_templated(&template, filtered.iter().map(session_record))?;
+        return Ok(());
+    }
 
-    // Apply filters
-    let filtered: Vec<_> = sessions
-        .into_iter()
-        .filter(|session| {
-            // State filter
-            if let Some(ref state_filter) = state {
-                if let Some(ref session_state) = session.state {
-                    let state_matches = match state_filter.to_lowercase().as_str() {
-                        "active" => matches!(
-                            session_state,
-                            jules_rs::State::Queued
-                                | jules_rs::State::Planning
-                                | jules_rs::State::AwaitingPlanApproval
-                                | jules_rs::State::AwaitingUserFeedback
-                                | jules_rs::State::InProgress
-                        ),
-                        "completed" => matches!(session_state, jules_rs::State::Completed),
-                        "failed" => matches!(session_state, jules_rs::State::Failed),
-                        "paused" => matches!(session_state, jules_rs::State::Paused),
-                        _ => true,
-                    };
-                    if !state_matches {
-                        return false;
-                    }
-                }
-            }
+    print_sessions(&filtered, OutputFormat::parse(options.format)?)
+}
 
-            // Search filter
-            if let Some(ref search_term) = search {
-                let search_lower = search_term.to_lowercase();
-                let title_match = session
-                    .title
-                    .as_ref()
-                    .map(|t| t.to_lowercase().contains(&search_lower))
-                    .unwrap_or(false);
-                let prompt_match = session.prompt.to_lowercase().contains(&search_lower);
-                if !title_match && !prompt_match {
-                    return false;
-                }
+/// Client-side filters of `sessions` (and `active`, `completed`, `failed`)
+#[derive(Debug, Clone, Default)]
+pub struct SessionFilter {
+    /// State group: active, completed, failed or paused
+    pub state: Option<String>,
+    /// Text in the title or prompt (case-insensitive)
+    pub search: Option<String>,
+    /// Source as a full name (`sources/github/owner/repo`) or `owner/repo`
+    pub source: Option<String>,
+    /// Range the session was created in
+    pub created: TimeRange,
+}
+
+impl SessionFilter {
+    /// Sessions in one state group
+    pub fn state(state: &str, search: Option<String>) -> Self {
+        Self {
+            state: Some(state.to_string()),
+            search,
+            ..Default::default()
+        }
+    }
+
+    /// Whether no filter is set, so every session matches
+    pub fn is_empty(&self) -> bool {
+        self.state.is_none()
+            && self.search.is_none()
+            && self.source.is_none()
+            && self.created.is_unbounded()
+    }
+
+    /// Whether a session passes every filter
+    pub fn matches(&self, session: &Session) -> bool {
+        // Sessions without a state pass any state filter
+        if let (Some(filter), Some(state)) = (&self.state, session.state) {
+            if !state_matches(filter, state) {
+                return false;
             }
+        }
+        if let Some(term) = &self.search {
+            let term = term.to_lowercase();
+            let title_match = session
+                .title
+                .as_ref()
+                .is_some_and(|title| title.to_lowercase().contains(&term));
+            if !title_match && !session.prompt.to_lowercase().contains(&term) {
+                return false;
+            }
+        }
+        if let Some(source) = &self.source {
+            if !source_matches(session, source) {
+                return false;
+            }
+        }
+        self.created
+            .contains(session.create_time.as_deref().unwrap_or_default())
+    }
+}
 
-            true
-        })
-        .collect();
+/// Up to `limit` sessions that pass `filter`, newest first, following
+/// pages until enough match
+///
+/// Without filters, pages of `limit` sessions are enough; with them, full
+/// pages are read so `limit` doesn't shrink the search. At most
+/// [`MAX_SESSION_PAGES`] pages are read; if that stops the search early, a
+/// warning says so.
+async fn list_matching_sessions(
+    client: &JulesClient,
+    limit: usize,
+    filter: &SessionFilter,
+    cache_enabled: bool,
+) -> Result<Vec<Session>> {
+    let page_size = if filter.is_empty() {
+        limit.clamp(1, SESSION_PAGE_SIZE as usize) as u32
+    } else {
+        SESSION_PAGE_SIZE
+    };
+    let mut found = Vec::new();
+    let mut scanned = 0;
+    let mut page_token: Option<String> = None;
+
+    for _ in 0..MAX_SESSION_PAGES {
+        let response = client
+            .list_sessions(Some(page_size), page_token.as_deref())
+            .await?;
+        if cache_enabled {
+            remember_sessions(&response.sessions);
+        }
+        scanned += response.sessions.len();
+        found.extend(
+            response
+                .sessions
+                .into_iter()
+                .filter(|session| filter.matches(session)),
+        );
 
-    // Output based on format
-    let output_format = OutputFormat::parse(format)?;
+        page_token = response.next_page_token;
+        if found.len() >= limit || page_token.is_none() {
+            found.truncate(limit);
+            return Ok(found);
+        }
+    }
+
+    eprintln!(
+        "Warning: Stopped after the newest {} sessions ({} pages) with {} of {} results; \
+         older sessions were not searched",
+        scanned,
+        MAX_SESSION_PAGES,
+        found.len(),
+        limit
+    );
+    Ok(found)
+}
+
+/// Print a list of sessions in `output_format`
+pub(crate) fn print_sessions(
+    sessions: &[jules_rs::types::session::Session],
+    output_format: OutputFormat,
+) -> Result<()> {
     match output_format {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&filtered)?);
+            println!("{}", serde_json::to_string_pretty(sessions)?);
         }
         OutputFormat::Table => {
-            jules_core::display::display_sessions_table(&filtered);
+            jules_core::display::display_sessions_table(sessions);
+        }
+        OutputFormat::Csv => {
+            print_sessions_csv(sessions)?;
+        }
+        OutputFormat::Yaml => {
+            print!("{}", to_yaml(&sessions)?);
+        }
+        OutputFormat::Ndjson => {
+            print_ndjson(sessions)?;
+        }
+        OutputFormat::Markdown => {
+            print!("{}", sessions_markdown(sessions));
         }
         OutputFormat::Full => {
-            for session in &filtered {
+            for session in sessions {
                 println!("{}", serde_json::to_string_pretty(&session)?);
                 println!("─────────────────────────────────────────");
             }
@@ -522,22 +1200,82 @@ pub async fn handle_sessions_formatted(
     Ok(())
 }
 
+/// Whether a session state passes a `--state` filter (active, completed,
+/// failed or paused); unknown filters match everything
+pub(crate) fn state_matches(filter: &str, state: jules_rs::State) -> bool {
+    match filter.to_lowercase().as_str() {
+        "active" => matches!(
+            state,
+            jules_rs::State::Queued
+                | jules_rs::State::Planning
+                | jules_rs::State::AwaitingPlanApproval
+                | jules_rs::State::AwaitingUserFeedback
+                | jules_rs::State::InProgress
+        ),
+        "completed" => matches!(state, jules_rs::State::Completed),
+        "failed" => matches!(state, jules_rs::State::Failed),
+        "paused" => matches!(state, jules_rs::State::Paused),
+        _ => true,
+    }
+}
+
 /// Handle session command with format support
-pub async fn handle_session_formatted(id: &str, format: &str) -> Result<()> {
+pub async fn handle_session_formatted(id: &str, options: &OutputOptions<'_>) -> Result<()> {
     let config = load_config()?;
-    let api_key = config.api_key.context("API key not configured")?;
-    let client = JulesClient::new(&api_key);
+    let template = options
+        .template
+        .map(|spec| config.output_template(spec))
+        .transpose()?;
+    let client = api_client(&config)?;
 
     let session = client.get_session(id).await?;
+    if config.cache.enabled {
+        remember_session(&session);
+    }
+    track_recent(&session.id, session.title.as_deref());
 
-    let output_format = OutputFormat::parse(format)?;
+    if let Some(path) = options.output {
+        return save_response(&serde_json::to_value(&session)?, Some(path.into()), true).await;
+    }
+    if let Some(template) = template {
+        print_templated(&template, [session_record(&session)])?;
+        return Ok(());
+    }
+
+    let output_format = OutputFormat::parse(options.format)?;
     match output_format {
-        OutputFormat::Json | OutputFormat::Full => {
+        OutputFormat::Json => {
             println!("{}", serde_json::to_string_pretty(&session)?);
         }
+        OutputFormat::Full => {
+            // Full output adds the local notes on the session
+            let mut value = serde_json::to_value(&session)?;
+            let notes = NoteStore::load()?;
+            let notes = notes.notes(&session.id);
+            if !notes.is_empty() {
+                value["notes"] = serde_json::to_value(notes)?;
+            }
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        }
         OutputFormat::Table => {
             jules_core::display::display_sessions_table(&[session]);
         }
+        OutputFormat::Csv => {
+            print_sessions_csv(&[session])?;
+        }
+        OutputFormat::Yaml => {
+            print!("{}", to_yaml(&session)?);
+        }
+        OutputFormat::Ndjson => {
+            print_ndjson([&session])?;
+        }
+        OutputFormat::Markdown => {
+            // Oldest first, so the report reads in order
+            let mut activities = fetch_all_activities(&client, &session.id).await?;
+            activities.reverse();
+            let refs: Vec<_> = activities.iter().collect();
+            print!("{}", session_markdown(&session, &refs));
+        }
     }
 
     Ok(())
@@ -549,7 +1287,13 @@ pub async fn handle_active_formatted(
     limit: u32,
     format: &str,
 ) -> Result<()> {
-    handle_sessions_formatted(Some("active".to_string()), search, limit, format).await
+    let options = OutputOptions {
+        format,
+        template: None,
+        output: None,
+    };
+    let filter = SessionFilter::state("active", search);
+    handle_sessions_formatted(&filter, limit, false, None, &options).await
 }
 
 /// Handle completed sessions with format support
@@ -558,7 +1302,13 @@ pub async fn handle_completed_formatted(
     limit: u32,
     format: &str,
 ) -> Result<()> {
-    handle_sessions_formatted(Some("completed".to_string()), search, limit, format).await
+    let options = OutputOptions {
+        format,
+        template: None,
+        output: None,
+    };
+    let filter = SessionFilter::state("completed", search);
+    handle_sessions_formatted(&filter, limit, false, None, &options).await
 }
 
 /// Handle failed sessions with format support
@@ -567,22 +1317,246 @@ pub async fn handle_failed_formatted(
     limit: u32,
     format: &str,
 ) -> Result<()> {
-    handle_sessions_formatted(Some("failed".to_string()), search, limit, format).await
+    let options = OutputOptions {
+        format,
+        template: None,
+        output: None,
+    };
+    let filter = SessionFilter::state("failed", search);
+    handle_sessions_formatted(&filter, limit, false, None, &options).await
 }
 
 /// Handle create command with format support
+///
+/// `follow` says what happens once the session exists: approving its plan
+/// and watching it like `handle_watch`, so the exit code reflects how it
+/// ended. With `follow.watch`, `format` must be a watch format.
 pub async fn handle_create_formatted(
-    prompt: String,
-    source: String,
-    title: Option<String>,
-    branch: Option<String>,
-    require_approval: bool,
-    automation_mode: &str,
+    args: CreateArgs,
     format: &str,
+    follow: FollowOptions,
+    origin: Option<&IssueOrigin>,
 ) -> Result<()> {
     let config = load_config()?;
-    let api_key = config.api_key.context("API key not configured")?;
-    let client = JulesClient::new(&api_key);
+    let client = api_client(&config)?;
+
+    let args = with_git_source(&client, args).await?;
+    let request = create_request(&config, args)?;
+    ensure_source_connected(&client, &request.source_context.source).await?;
+
+    // Reject a bad format before creating anything. The NDJSON stream starts
+    // with the session itself, so it isn't printed on creation as well.
+    let session = if follow.watch && parse_stream_format(format)? {
+        let spinner = Spinner::start("Creating session...");
+        let session = client.create_session(request).await;
+        spinner.finish();
+        session?
+    } else {
+        create_session_formatted(&client, request, format).await?
+    };
+    if let Some(origin) = origin {
+        record_issue_link(&origin.issue, &session.id);
+        if origin.comment {
+            let issue = &origin.issue;
+            announce_on_issue(&config, &issue.owner, &issue.repo, issue.number, &session).await;
+        }
+    }
+    if !follow.watch && !follow.auto_approve {
+        return Ok(());
+    }
+
+    let show_plan = !is_quiet() && matches!(OutputFormat::parse(format)?, OutputFormat::Table);
+    let follow_up = async {
+        if follow.auto_approve {
+            auto_approve_plan(&client, &session.id, follow.interval, show_plan).await?;
+        }
+        if follow.watch {
+            watch_session(&session.id, follow.interval, format, None, false).await?;
+        }
+        Ok(())
+    };
+    with_timeout(&session.id, follow.timeout, follow_up).await
+}
+
+/// Fetch issue `number` for `create --from-issue`, returning the prompt
+/// asking to resolve it, followed by `instructions` (`-` reads them from
+/// stdin), and the issue
+///
+/// The issue is looked up in `owner`/`repo`, else the repository of
+/// `source`, of the git checkout in the current directory, or of the
+/// configured default source.
+pub async fn issue_prompt(
+    number: u64,
+    owner: Option<String>,
+    repo: Option<String>,
+    source: Option<&str>,
+    with_comments: bool,
+    instructions: Option<String>,
+) -> Result<(String, GitHubIssue)> {
+    let config = load_config()?;
+    let (owner, repo) = match (owner, repo) {
+        (Some(owner), Some(repo)) => (owner, repo),
+        _ => issue_repo(&config, source)?,
+    };
+    let instructions = instructions
+        .map(|text| prompt_from_input(&text, std::io::stdin()))
+        .transpose()?;
+
+    let github = GitHubClient::require(&config, "create --from-issue")?;
+    let spinner = Spinner::start(format!("Fetching issue {}/{}#{}...", owner, repo, number));
+    let issue = github.issue(&owner, &repo, number, with_comments).await;
+    spinner.finish();
+    let issue = issue?;
+    Ok((issue.prompt(instructions.as_deref()), issue))
+}
+
+/// The GitHub repository of `source`, of the git checkout in the current
+/// directory, or of the configured default source
+pub(crate) fn issue_repo(
+    config: &jules_core::config::Config,
+    source: Option<&str>,
+) -> Result<(String, String)> {
+    let from_source = |source: &str| -> Result<(String, String)> {
+        let source = expand_source(source)?;
+        source_repo(&source).ok_or_else(|| anyhow::anyhow!("{} is not a GitHub repository", source))
+    };
+    if let Some(source) = source {
+        return from_source(source);
+    }
+    if let Some(context) = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| repo_context(&cwd))
+    {
+        return Ok((context.owner, context.repo));
+    }
+    match &config.default_source {
+        Some(source) => from_source(source),
+        None => anyhow::bail!(
+            "Can't tell which repository the issue is in. Pass --owner and --repo, or --source"
+        ),
+    }
+}
+
+/// The issue a session is created for (`create --from-issue`)
+pub struct IssueOrigin {
+    pub issue: GitHubIssue,
+    /// Comment the session link on the issue
+    pub comment: bool,
+}
+
+/// Comment the link to `session` on issue `number` of `owner`/`repo`, so
+/// teammates and `issue-status` find it; warns on failure
+pub(crate) async fn announce_on_issue(
+    config: &jules_core::config::Config,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    session: &Session,
+) {
+    let body = session_link_comment(&session.id, session.url.as_deref());
+    let result = match GitHubClient::detect(config) {
+        Ok(Some(github)) => github.comment_on_issue(owner, repo, number, &body).await,
+        Ok(None) => Err(anyhow::anyhow!("GitHub can't be reached")),
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) if !is_quiet() => {
+            eprintln!(
+                "{} Linked the session on {}/{}#{}",
+                icon("💬", "*"),
+                owner,
+                repo,
+                number
+            )
+        }
+        Ok(()) => {}
+        Err(e) => eprintln!(
+            "Warning: Couldn't comment on {}/{}#{}: {:#}",
+            owner, repo, number, e
+        ),
+    }
+}
+
+/// Remember that a session works on `issue`, warning on failure
+pub(crate) fn record_issue_link(issue: &GitHubIssue, session_id: &str) {
+    let result = IssueLinks::load().and_then(|mut links| {
+        links.link(&issue.owner, &issue.repo, issue.number, session_id)?;
+        links.save()
+    });
+    if let Err(e) = result {
+        eprintln!("Warning: Failed to record the issue link: {:#}", e);
+    }
+}
+
+/// What `create` does after creating a session
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FollowOptions {
+    /// Watch the session until it finishes
+    pub watch: bool,
+    /// Approve the session's plan once it's ready
+    pub auto_approve: bool,
+    /// Poll interval in seconds
+    pub interval: u64,
+    /// Give up after this long
+    pub timeout: Option<Duration>,
+}
+
+/// `args` with the source and branch of the git checkout in the current
+/// directory, if no `--source` is given and the repository is a Jules source
+///
+/// The checkout's branch is used only when it was pushed and no `--branch`
+/// is given. Otherwise the configured defaults apply as usual.
+pub(crate) async fn with_git_source(client: &JulesClient, args: CreateArgs) -> Result<CreateArgs> {
+    if args.source.is_some() {
+        return Ok(args);
+    }
+    let Some(context) = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| repo_context(&cwd))
+    else {
+        return Ok(args);
+    };
+
+    let source = format!("sources/github/{}/{}", context.owner, context.repo);
+    if find_source(client, &source).await?.is_none() {
+        if !is_quiet() {
+            eprintln!(
+                "Note: {}/{} is not a Jules source; using the configured source",
+                context.owner, context.repo
+            );
+        }
+        return Ok(args);
+    }
+
+    let branch = args.branch.or(context.branch);
+    if !is_quiet() {
+        match &branch {
+            Some(branch) => eprintln!("Using {} on branch {}", source, branch),
+            None => eprintln!("Using {}", source),
+        }
+    }
+    Ok(CreateArgs {
+        source: Some(source),
+        branch,
+        ..args
+    })
+}
+
+/// Session request for `create` arguments
+///
+/// Command-line arguments win over project and global defaults.
+pub(crate) fn create_request(
+    config: &jules_core::config::Config,
+    args: CreateArgs,
+) -> Result<jules_rs::types::session::CreateSessionRequest> {
+    let source = expand_source(&config.resolve_source(args.source)?)?;
+    config.safety.check_source(&source)?;
+    let prompt = config.render_prompt(args.template.as_deref(), &args.prompt)?;
+    let branch = args.branch.or_else(|| config.default_branch.clone());
+    let automation_mode = args
+        .automation_mode
+        .or_else(|| config.automation_mode.clone())
+        .unwrap_or_else(|| "AUTO_CREATE_PR".to_string());
 
     // Parse automation mode
     let automation = match automation_mode.to_uppercase().as_str() {
@@ -592,49 +1566,129 @@ pub async fn handle_create_formatted(
 
     // Build source context with optional branch
     let source_context = jules_rs::types::session::SourceContext {
-        source: source.clone(),
+        source,
         github_repo_context: branch
             .map(|b| jules_rs::types::session::GitHubRepoContext { starting_branch: b }),
     };
 
-    let request = jules_rs::types::session::CreateSessionRequest {
-        prompt: prompt.clone(),
-        title,
+    Ok(jules_rs::types::session::CreateSessionRequest {
+        prompt,
+        title: args.title,
         source_context,
-        require_plan_approval: Some(require_approval),
+        require_plan_approval: Some(args.require_approval),
         automation_mode: Some(automation),
+    })
+}
+
+/// The prompt of `create`: the argument, stdin for `-`, or with `edit`,
+/// written in the editor starting from the argument expanded with the
+/// template
+///
+/// Returns the prompt and the template still to apply (none after editing).
+pub fn read_create_prompt(
+    prompt: Option<String>,
+    edit: bool,
+    template: Option<String>,
+) -> Result<(String, Option<String>)> {
+    let prompt = prompt.unwrap_or_default();
+    if !edit {
+        return Ok((prompt_from_input(&prompt, std::io::stdin())?, template));
+    }
+
+    let draft = match prompt.as_str() {
+        "-" => prompt_from_input(&prompt, std::io::stdin())?,
+        _ => prompt,
     };
+    let draft = load_config()?.render_prompt(template.as_deref(), &draft)?;
+    Ok((non_empty_prompt(edit_text(&draft)?)?, None))
+}
+
+/// Create a session and print it in `format`
+pub(crate) async fn create_session_formatted(
+    client: &JulesClient,
+    request: jules_rs::types::session::CreateSessionRequest,
+    format: &str,
+) -> Result<Session> {
+    let output_format = OutputFormat::parse(format)?;
 
+    let spinner = Spinner::start("Creating session...");
     let session = client.create_session(request).await?;
+    spinner.finish();
+    track_recent(&session.id, session.title.as_deref());
 
-    let output_format = OutputFormat::parse(format)?;
     match output_format {
         OutputFormat::Json | OutputFormat::Full => {
             println!("{}", serde_json::to_string_pretty(&session)?);
         }
         OutputFormat::Table => {
-            println!("✓ Session created successfully");
-            jules_core::display::display_sessions_table(&[session]);
+            if !is_quiet() {
+                println!("{} Session created successfully", icon("✓", "OK"));
+            }
+            jules_core::display::display_sessions_table(std::slice::from_ref(&session));
+        }
+        OutputFormat::Csv => {
+            print_sessions_csv(std::slice::from_ref(&session))?;
+        }
+        OutputFormat::Yaml => {
+            print!("{}", to_yaml(&session)?);
+        }
+        OutputFormat::Ndjson => {
+            print_ndjson([&session])?;
+        }
+        OutputFormat::Markdown => {
+            print!("{}", session_markdown(&session, &[]));
         }
     }
 
-    Ok(())
+    Ok(session)
 }
 
 /// Handle sources command with format support
+///
+/// With `cached`, sources come from the local sources cache instead of the API.
 pub async fn handle_sources_formatted(
     filter: Option<String>,
     limit: u32,
     format: &str,
+    cached: bool,
 ) -> Result<()> {
     let config = load_config()?;
-    let api_key = config.api_key.context("API key not configured")?;
-    let client = JulesClient::new(&api_key);
 
-    let response = client
-        .list_sources(filter.as_deref(), Some(limit), None)
-        .await?;
-    let sources = response.sources;
+    let sources = if cached {
+        if filter.is_some() {
+            anyhow::bail!("--filter is evaluated by the API and cannot be combined with --cached");
+        }
+        let store = SourceStore::load()?;
+        match store.refreshed_at {
+            Some(refreshed) if !is_quiet() => eprintln!(
+                "Using cached sources (last refreshed {})",
+                refreshed.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+            ),
+            Some(_) => {}
+            None => anyhow::bail!("No cached sources. Run 'gules sources refresh' first."),
+        }
+        store.sources.into_iter().take(limit as usize).collect()
+    } else {
+        let client = api_client(&config)?;
+
+        let spinner = Spinner::start("Fetching sources...");
+        let response = client
+            .list_sources(filter.as_deref(), Some(limit), None)
+            .await?;
+        spinner.finish();
+
+        // A complete, unfiltered listing is as good as a refresh
+        if config.cache.enabled && filter.is_none() && response.next_page_token.is_none() {
+            let result = SourceStore::load().and_then(|mut store| {
+                store.replace(response.sources.clone());
+                store.save()
+            });
+            if let Err(e) = result {
+                eprintln!("Warning: Failed to update sources cache: {}", e);
+            }
+        }
+        response.sources
+    };
 
     let output_format = OutputFormat::parse(format)?;
     match output_format {
@@ -644,6 +1698,18 @@ pub async fn handle_sources_formatted(
         OutputFormat::Table => {
             jules_core::display::print_sources_table(&sources);
         }
+        OutputFormat::Csv => {
+            print_sources_csv(&sources)?;
+        }
+        OutputFormat::Yaml => {
+            print!("{}", to_yaml(&sources)?);
+        }
+        OutputFormat::Ndjson => {
+            print_ndjson(&sources)?;
+        }
+        OutputFormat::Markdown => {
+            anyhow::bail!("Markdown output is available for sessions and activities only");
+        }
         OutputFormat::Full => {
             for source in &sources {
                 println!("{}", serde_json::to_string_pretty(&source)?);
@@ -655,13 +1721,80 @@ pub async fn handle_sources_formatted(
     Ok(())
 }
 
+/// Handle `sources check`: whether `source` is connected to Jules, with
+/// instructions to connect it if not (exit code 2)
+///
+/// Always asks the API, so a repository connected a moment ago is found.
+pub async fn handle_sources_check(source: &str, format: &str) -> Result<()> {
+    let json = match format.to_lowercase().as_str() {
+        "json" => true,
+        "table" => false,
+        _ => anyhow::bail!("Unknown format: {}. Valid options: table, json", format),
+    };
+    let config = load_config()?;
+    let client = api_client(&config)?;
+    let name = expand_source(source)?;
+
+    let found = refresh_sources(&client).await?.find(&name).cloned();
+    if json {
+        let status = serde_json::json!({
+            "source": name,
+            "connected": found.is_some(),
+            "details": found,
+        });
+        println!("{}", serde_json::to_string_pretty(&status)?);
+    }
+    let Some(found) = found else {
+        return Err(ExitError::new(exit_code::NOT_FOUND, not_connected_message(&name)).into());
+    };
+    if json {
+        return Ok(());
+    }
+
+    println!("{} {} is connected to Jules", icon("✓", "OK"), found.name);
+    if let Some(repo) = &found.github_repo {
+        if let Some(branch) = &repo.default_branch {
+            println!("  Default branch: {}", branch.display_name);
+        }
+        if let Some(private) = repo.is_private {
+            println!("  Private: {}", if private { "yes" } else { "no" });
+        }
+    }
+    Ok(())
+}
+
+/// Fail with instructions to connect it unless `name` is a Jules source,
+/// instead of the API's bare error on creating a session
+pub(crate) async fn ensure_source_connected(client: &JulesClient, name: &str) -> Result<()> {
+    match find_source(client, name).await? {
+        Some(_) => Ok(()),
+        None => Err(ExitError::new(exit_code::NOT_FOUND, not_connected_message(name)).into()),
+    }
+}
+
+/// Handle `sources refresh`: fetch every source into the local cache
+pub async fn handle_sources_refresh() -> Result<()> {
+    let config = load_config()?;
+    let client = api_client(&config)?;
+
+    let store = refresh_sources(&client).await?;
+    if !is_quiet() {
+        println!(
+            "{} Cached {} source(s)",
+            icon("✅", "OK"),
+            store.sources.len()
+        );
+    }
+
+    Ok(())
+}
+
 /// Handle source command with format support
 pub async fn handle_source_formatted(id: &str, format: &str) -> Result<()> {
     let config = load_config()?;
-    let api_key = config.api_key.context("API key not configured")?;
-    let client = JulesClient::new(&api_key);
+    let client = api_client(&config)?;
 
-    let source = client.get_source(id).await?;
+    let source = client.get_source(&expand_source(id)?).await?;
 
     let output_format = OutputFormat::parse(format)?;
     match output_format {
@@ -671,23 +1804,66 @@ pub async fn handle_source_formatted(id: &str, format: &str) -> Result<()> {
         OutputFormat::Table => {
             jules_core::display::print_sources_table(&[source]);
         }
+        OutputFormat::Csv => {
+            print_sources_csv(&[source])?;
+        }
+        OutputFormat::Yaml => {
+            print!("{}", to_yaml(&source)?);
+        }
+        OutputFormat::Ndjson => {
+            print_ndjson([&source])?;
+        }
+        OutputFormat::Markdown => {
+            anyhow::bail!("Markdown output is available for sessions and activities only");
+        }
     }
 
     Ok(())
 }
 
 /// Handle activities command with format support
-pub async fn handle_activities_formatted(session_id: &str, limit: u32, format: &str) -> Result<()> {
+///
+/// With a time `range`, every page is fetched and `limit` applies to the
+/// activities in the range.
+pub async fn handle_activities_formatted(
+    session_id: &str,
+    limit: u32,
+    sort: Option<SortOrder<ActivitySort>>,
+    range: TimeRange,
+    options: &OutputOptions<'_>,
+) -> Result<()> {
     let config = load_config()?;
-    let api_key = config.api_key.context("API key not configured")?;
-    let client = JulesClient::new(&api_key);
+    let template = options
+        .template
+        .map(|spec| config.output_template(spec))
+        .transpose()?;
+    let client = api_client(&config)?;
+
+    // A time range may match activities beyond the first page
+    let mut activities = if range.is_unbounded() {
+        client
+            .list_activities(session_id, Some(limit), None)
+            .await?
+            .activities
+    } else {
+        let mut activities = fetch_all_activities(&client, session_id).await?;
+        activities.retain(|activity| range.contains(&activity.create_time));
+        activities.truncate(limit as usize);
+        activities
+    };
+    if let Some(order) = sort {
+        sort_activities(&mut activities, order);
+    }
 
-    let response = client
-        .list_activities(session_id, Some(limit), None)
-        .await?;
-    let activities = response.activities;
+    if let Some(path) = options.output {
+        return save_response(&serde_json::to_value(&activities)?, Some(path.into()), true).await;
+    }
+    if let Some(template) = template {
+        print_templated(&template, activities.iter().map(activity_record))?;
+        return Ok(());
+    }
 
-    let output_format = OutputFormat::parse(format)?;
+    let output_format = OutputFormat::parse(options.format)?;
     match output_format {
         OutputFormat::Json => {
             println!("{}", serde_json::to_string_pretty(&activities)?);
@@ -696,6 +1872,20 @@ pub async fn handle_activities_formatted(session_id: &str, limit: u32, format: &
             let refs: Vec<_> = activities.iter().collect();
             jules_core::display::print_activities_table(&refs);
         }
+        OutputFormat::Csv => {
+            let refs: Vec<_> = activities.iter().collect();
+            print_activities_csv(&refs)?;
+        }
+        OutputFormat::Yaml => {
+            print!("{}", to_yaml(&activities)?);
+        }
+        OutputFormat::Ndjson => {
+            print_ndjson(&activities)?;
+        }
+        OutputFormat::Markdown => {
+            let refs: Vec<_> = activities.iter().collect();
+            print!("{}", activities_markdown(&refs));
+        }
         OutputFormat::Full => {
             for activity in &activities {
                 println!("{}", serde_json::to_string_pretty(&activity)?);
@@ -714,8 +1904,7 @@ pub async fn handle_activity_formatted(
     format: &str,
 ) -> Result<()> {
     let config = load_config()?;
-    let api_key = config.api_key.context("API key not configured")?;
-    let client = JulesClient::new(&api_key);
+    let client = api_client(&config)?;
 
     let activity = client.get_activity(session_id, activity_id).await?;
 
@@ -728,6 +1917,18 @@ pub async fn handle_activity_formatted(
             let refs = vec![&activity];
             jules_core::display::print_activities_table(&refs);
         }
+        OutputFormat::Csv => {
+            print_activities_csv(&[&activity])?;
+        }
+        OutputFormat::Yaml => {
+            print!("{}", to_yaml(&activity)?);
+        }
+        OutputFormat::Ndjson => {
+            print_ndjson([&activity])?;
+        }
+        OutputFormat::Markdown => {
+            print!("{}", activities_markdown(&[&activity]));
+        }
     }
 
     Ok(())
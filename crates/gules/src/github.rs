@@ -0,0 +1,1778 @@
+//! GitHub access for the issue and pull request integrations.
+//!
+//! Calls the GitHub API directly (through octocrab) when a token is
+//! available, so containers and CI runners need nothing else installed;
+//! without a token, an installed GitHub CLI (`gh`) is used instead.
+//!
+//! The token comes from `GITHUB_TOKEN`, then `GH_TOKEN`, then the
+//! `github_token` setting, then (with the `keyring` feature) the system
+//! keyring, where `gules github login` stores it. Where personal tokens are
+//! not allowed, a GitHub App installation configured in `[github]` is used
+//! instead of any token.
+//!
+//! API calls go through `call_api`, which revalidates cached GET responses
+//! with their ETags and records the rate limit (see
+//! [`jules_core::github_cache`]).
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use http::header::{HeaderMap, HeaderValue, ETAG, IF_NONE_MATCH};
+use http::{Method, StatusCode};
+use jsonwebtoken::EncodingKey;
+use jules_core::display::{icon, is_quiet};
+use jules_core::github_cache::{record_rate_limits, RateLimit, RateLimits, ResponseCache};
+use jules_core::github_url::{GitHubItemUrl, GITHUB_HOST};
+use jules_core::webhook::WebhookPayload;
+use jules_core::Config;
+use jules_rs::State;
+use octocrab::models::checks::ListCheckRuns;
+use octocrab::models::issues::{Comment, Issue};
+use octocrab::models::pulls::{self, PullRequest, ReviewState};
+use octocrab::models::{AppId, InstallationId, IssueState};
+use octocrab::Octocrab;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Cache of GET responses, set up from the `[cache]` settings by
+/// [`GitHubClient::detect`]; `None` when disabled
+static RESPONSE_CACHE: OnceLock<Option<ResponseCache>> = OnceLock::new();
+
+/// Environment variables holding a GitHub token, in order of precedence
+pub const TOKEN_ENV_VARS: [&str; 2] = ["GITHUB_TOKEN", "GH_TOKEN"];
+
+/// Keyring service and account the token is stored under
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE: &str = "gules";
+#[cfg(feature = "keyring")]
+const KEYRING_ACCOUNT: &str = "github_token";
+
+/// Scopes of a classic token that give access to repositories; either one
+/// is enough for public repositories, only `repo` for private ones
+pub const REPO_SCOPES: [&str; 2] = ["repo", "public_repo"];
+
+/// Where the GitHub token came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenSource {
+    Env(&'static str),
+    Config,
+    #[cfg(feature = "keyring")]
+    Keyring,
+    /// Given to `gules github login`, not stored yet
+    Login,
+    /// Issued to the GitHub App installation in the `[github]` settings
+    App,
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env(name) => f.write_str(name),
+            Self::Config => f.write_str("the github_token setting"),
+            #[cfg(feature = "keyring")]
+            Self::Keyring => f.write_str("the system keyring"),
+            Self::Login => f.write_str("the given token"),
+            Self::App => f.write_str("the GitHub App installation"),
+        }
+    }
+}
+
+/// A GitHub token and where it came from
+#[derive(Clone)]
+pub struct GitHubToken {
+    pub token: String,
+    pub source: TokenSource,
+}
+
+/// What GitHub reports about a token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenInfo {
+    /// The account the token belongs to
+    pub login: String,
+    /// Scopes of a classic token; `None` for fine-grained tokens, whose
+    /// permissions GitHub doesn't report
+    pub scopes: Option<Vec<String>>,
+}
+
+impl TokenInfo {
+    /// Parse the `X-OAuth-Scopes` header of a GitHub response
+    pub fn parse_scopes(header: Option<&str>) -> Option<Vec<String>> {
+        header.map(|scopes| {
+            scopes
+                .split(',')
+                .map(str::trim)
+                .filter(|scope| !scope.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+    }
+
+    /// Why the token can't be used for the GitHub integrations, if it can't
+    pub fn scope_problem(&self) -> Option<String> {
+        let scopes = self.scopes.as_ref()?;
+        if scopes
+            .iter()
+            .any(|scope| REPO_SCOPES.contains(&scope.as_str()))
+        {
+            return None;
+        }
+        Some(format!(
+            "the token has no `repo` scope (it has: {}), so issues and pull requests are out of reach",
+            if scopes.is_empty() {
+                "none".to_string()
+            } else {
+                scopes.join(", ")
+            }
+        ))
+    }
+}
+
+/// Permissions of a GitHub App installation the integrations need at least
+/// read access to
+pub const APP_PERMISSIONS: [&str; 2] = ["issues", "pull_requests"];
+
+/// What GitHub reports about the configured GitHub App installation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppInstallationInfo {
+    /// The organization or account the app is installed on
+    pub account: String,
+    /// `all` or `selected` repositories
+    pub repository_selection: Option<String>,
+    /// Granted permissions and their levels, like `issues: write`
+    pub permissions: Vec<(String, String)>,
+}
+
+impl AppInstallationInfo {
+    /// Parse an installation from the GitHub API
+    pub fn from_json(installation: &serde_json::Value) -> Self {
+        let mut permissions: Vec<(String, String)> = installation["permissions"]
+            .as_object()
+            .map(|permissions| {
+                permissions
+                    .iter()
+                    .filter_map(|(name, level)| Some((name.clone(), level.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        permissions.sort();
+        Self {
+            account: installation["account"]["login"]
+                .as_str()
+                .unwrap_or("unknown")
+                .to_string(),
+            repository_selection: installation["repository_selection"]
+                .as_str()
+                .map(str::to_string),
+            permissions,
+        }
+    }
+
+    /// Why the installation can't be used for the GitHub integrations, if
+    /// it can't
+    pub fn permission_problem(&self) -> Option<String> {
+        let missing: Vec<&str> = APP_PERMISSIONS
+            .into_iter()
+            .filter(|name| !self.permissions.iter().any(|(granted, _)| granted == name))
+            .collect();
+        (!missing.is_empty()).then(|| {
+            format!(
+                "the app has no access to {}, so the integrations can't use them",
+                missing.join(" or ").replace('_', " ")
+            )
+        })
+    }
+}
+
+/// A check run or commit status on a pull request's head commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: String,
+    /// Lowercase conclusion, like `success` or `failure`; `None` while the
+    /// check is queued or running
+    pub conclusion: Option<String>,
+    /// Where the check's results are, like a GitHub Actions job page
+    pub details_url: Option<String>,
+}
+
+impl Check {
+    /// Whether the check finished without failing (skipped and neutral
+    /// checks count as passed)
+    pub fn passed(&self) -> bool {
+        matches!(
+            self.conclusion.as_deref(),
+            Some("success" | "neutral" | "skipped")
+        )
+    }
+
+    /// Whether the check is still queued or running
+    pub fn pending(&self) -> bool {
+        self.conclusion.is_none()
+    }
+
+    /// The GitHub Actions job behind the check, from a details URL like
+    /// `https://github.com/OWNER/REPO/actions/runs/1/job/2`
+    pub fn actions_job_id(&self) -> Option<u64> {
+        let url = self.details_url.as_deref()?;
+        let (_, job) = url.split_once("/actions/runs/")?.1.split_once("/job/")?;
+        let digits = job.split(|c: char| !c.is_ascii_digit()).next()?;
+        digits.parse().ok()
+    }
+}
+
+/// A reviewer's verdict on a pull request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Review {
+    pub reviewer: String,
+    /// `APPROVED`, `CHANGES_REQUESTED`, `COMMENTED` or `DISMISSED`
+    pub state: String,
+}
+
+/// What GitHub knows about a pull request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PullRequestStatus {
+    /// `OPEN`, `CLOSED` or `MERGED`
+    pub state: Option<String>,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub created_at: Option<String>,
+    pub merged_at: Option<String>,
+    pub draft: bool,
+    /// The branch the changes are on
+    pub head_branch: Option<String>,
+    /// The newest commit, which the checks ran on
+    pub head_sha: Option<String>,
+    /// Whether it merges without conflicts; `None` while GitHub works it out
+    pub mergeable: Option<bool>,
+    pub changed_files: Option<u64>,
+    pub additions: Option<u64>,
+    pub deletions: Option<u64>,
+    pub checks: Vec<Check>,
+    /// Each reviewer's latest verdict, see [`latest_reviews`]
+    pub reviews: Vec<Review>,
+}
+
+impl PullRequestStatus {
+    /// Overall state of the checks: `failing` if any failed, else `pending`
+    /// if any are running, else `passing`; `none` without checks
+    pub fn checks_state(&self) -> &'static str {
+        if self.checks.is_empty() {
+            "none"
+        } else if self.checks.iter().any(|c| !c.passed() && !c.pending()) {
+            "failing"
+        } else if self.checks.iter().any(Check::pending) {
+            "pending"
+        } else {
+            "passing"
+        }
+    }
+
+    /// Overall review verdict: `changes_requested` if any reviewer asked for
+    /// changes, else `approved` if any approved, else `none`
+    pub fn review_state(&self) -> &'static str {
+        let has = |state: &str| self.reviews.iter().any(|r| r.state == state);
+        if has("CHANGES_REQUESTED") {
+            "changes_requested"
+        } else if has("APPROVED") {
+            "approved"
+        } else {
+            "none"
+        }
+    }
+
+    /// Why the pull request shouldn't be merged yet, besides checks that are
+    /// still running: it isn't open, is a draft, conflicts, has failed checks
+    /// or a reviewer requested changes. Required approvals are left to the
+    /// repository's branch protection.
+    pub fn merge_blockers(&self) -> Vec<String> {
+        let mut blockers = Vec::new();
+        match self.state.as_deref() {
+            Some("OPEN") | None => {}
+            Some(state) => blockers.push(format!("it is {}", state.to_lowercase())),
+        }
+        if self.draft {
+            blockers.push("it is a draft".to_string());
+        }
+        if self.mergeable == Some(false) {
+            blockers.push("it has merge conflicts".to_string());
+        }
+        let failed: Vec<&str> = self
+            .checks
+            .iter()
+            .filter(|c| !c.passed() && !c.pending())
+            .map(|c| c.name.as_str())
+            .collect();
+        if !failed.is_empty() {
+            blockers.push(format!("checks failed: {}", failed.join(", ")));
+        }
+        let requested: Vec<&str> = self
+            .reviews
+            .iter()
+            .filter(|r| r.state == "CHANGES_REQUESTED")
+            .map(|r| r.reviewer.as_str())
+            .collect();
+        if !requested.is_empty() {
+            blockers.push(format!("changes requested by {}", requested.join(", ")));
+        }
+        blockers
+    }
+}
+
+/// Each reviewer's latest verdict from `reviews` in submission order, like
+/// GitHub counts them: comments don't override an approval or a request for
+/// changes, and a dismissal clears it
+pub fn latest_reviews(reviews: Vec<Review>) -> Vec<Review> {
+    let mut latest: Vec<Review> = Vec::new();
+    for review in reviews {
+        match latest.iter_mut().find(|r| r.reviewer == review.reviewer) {
+            Some(existing) => {
+                if review.state != "COMMENTED" || existing.state == "COMMENTED" {
+                    *existing = review;
+                }
+            }
+            None => latest.push(review),
+        }
+    }
+    latest
+}
+
+/// A comment on a GitHub issue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueComment {
+    pub author: String,
+    pub body: String,
+}
+
+/// A GitHub issue, as a task for a session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubIssue {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    /// Only fetched when asked for
+    pub comments: Vec<IssueComment>,
+}
+
+impl GitHubIssue {
+    /// A prompt asking to resolve the issue: its title, body and comments,
+    /// then any extra `instructions`
+    pub fn prompt(&self, instructions: Option<&str>) -> String {
+        let mut prompt = format!(
+            "Resolve GitHub issue #{} in {}/{}: {}\n",
+            self.number, self.owner, self.repo, self.title
+        );
+        if let Some(body) = self.body.as_deref().map(str::trim) {
+            if !body.is_empty() {
+                prompt.push_str(&format!("\n{}\n", body));
+            }
+        }
+        if !self.comments.is_empty() {
+            prompt.push_str("\nComments on the issue:\n");
+            for comment in &self.comments {
+                prompt.push_str(&format!(
+                    "\n@{}:\n{}\n",
+                    comment.author,
+                    comment.body.trim()
+                ));
+            }
+        }
+        if let Some(instructions) = instructions.map(str::trim) {
+            if !instructions.is_empty() {
+                prompt.push_str(&format!("\nAdditional instructions:\n{}\n", instructions));
+            }
+        }
+        prompt
+    }
+}
+
+/// A conversation on a pull request's diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewThread {
+    pub path: String,
+    /// The line it is on; `None` once later commits removed the line
+    pub line: Option<u64>,
+    /// The first comment, then the replies
+    pub comments: Vec<IssueComment>,
+}
+
+/// GraphQL query for the review threads of a pull request (REST doesn't
+/// tell whether a thread is resolved)
+const REVIEW_THREADS_QUERY: &str = "query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100) {
+        nodes {
+          isResolved
+          path
+          line
+          comments(first: 50) { nodes { author { login } body } }
+        }
+      }
+    }
+  }
+}";
+
+/// The unresolved threads in a response to the review threads query
+pub fn unresolved_review_threads(response: &serde_json::Value) -> Result<Vec<ReviewThread>> {
+    let threads = graphql_data(response)?["repository"]["pullRequest"]["reviewThreads"]["nodes"]
+        .as_array()
+        .context("Unexpected response to the review threads query")?;
+    Ok(threads
+        .iter()
+        .filter(|thread| thread["isResolved"] == false)
+        .map(|thread| ReviewThread {
+            path: thread["path"].as_str().unwrap_or_default().to_string(),
+            line: thread["line"].as_u64(),
+            comments: thread["comments"]["nodes"]
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .map(|comment| IssueComment {
+                    author: comment["author"]["login"]
+                        .as_str()
+                        .unwrap_or("ghost")
+                        .to_string(),
+                    body: comment["body"].as_str().unwrap_or_default().to_string(),
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// A pull request that mentions or closes an issue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedPullRequest {
+    pub url: String,
+    pub body: String,
+    pub head_branch: String,
+    pub commit_messages: Vec<String>,
+}
+
+impl LinkedPullRequest {
+    /// The text that may refer to sessions: description, branch name and
+    /// commit messages
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        [self.body.as_str(), self.head_branch.as_str()]
+            .into_iter()
+            .chain(self.commit_messages.iter().map(String::as_str))
+    }
+}
+
+/// GraphQL query for the pull requests that mention an issue or are
+/// connected to it in the sidebar
+const LINKED_PULL_REQUESTS_QUERY: &str = "query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    issue(number: $number) {
+      timelineItems(first: 100, itemTypes: [CROSS_REFERENCED_EVENT, CONNECTED_EVENT]) {
+        nodes {
+          ... on CrossReferencedEvent { source { ...linkedPullRequest } }
+          ... on ConnectedEvent { subject { ...linkedPullRequest } }
+        }
+      }
+    }
+  }
+}
+fragment linkedPullRequest on PullRequest {
+  url
+  body
+  headRefName
+  commits(first: 100) { nodes { commit { message } } }
+}";
+
+/// The pull requests in a response to the linked pull requests query, each
+/// once
+pub fn parse_linked_pull_requests(response: &serde_json::Value) -> Result<Vec<LinkedPullRequest>> {
+    let items = graphql_data(response)?["repository"]["issue"]["timelineItems"]["nodes"]
+        .as_array()
+        .context("Unexpected response to the linked pull requests query")?;
+    let mut pull_requests: Vec<LinkedPullRequest> = Vec::new();
+    for item in items {
+        let pr = if item["source"].is_object() {
+            &item["source"]
+        } else {
+            &item["subject"]
+        };
+        // Mentions from issues have no pull request fields
+        let Some(url) = pr["url"].as_str() else {
+            continue;
+        };
+        if pull_requests.iter().any(|known| known.url == url) {
+            continue;
+        }
+        pull_requests.push(LinkedPullRequest {
+            url: url.to_string(),
+            body: pr["body"].as_str().unwrap_or_default().to_string(),
+            head_branch: pr["headRefName"].as_str().unwrap_or_default().to_string(),
+            commit_messages: pr["commits"]["nodes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|node| node["commit"]["message"].as_str())
+                .map(str::to_string)
+                .collect(),
+        });
+    }
+    Ok(pull_requests)
+}
+
+/// The `data` of a GraphQL response, or its errors
+fn graphql_data(response: &serde_json::Value) -> Result<&serde_json::Value> {
+    if let Some(errors) = response["errors"].as_array() {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter_map(|e| e["message"].as_str())
+            .collect();
+        anyhow::bail!("GitHub GraphQL error: {}", messages.join("; "));
+    }
+    Ok(&response["data"])
+}
+
+/// The end of a GitHub Actions job log: at most `max_lines` lines up to the
+/// last `##[error]` line (or the end), without timestamps
+pub fn log_excerpt(log: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = log.lines().map(strip_log_timestamp).collect();
+    let end = lines
+        .iter()
+        .rposition(|line| line.contains("##[error]"))
+        .map_or(lines.len(), |i| i + 1);
+    lines[end.saturating_sub(max_lines)..end].join("\n")
+}
+
+/// A log line without the timestamp Actions prefixes it with, like
+/// `2025-01-01T10:00:00.1234567Z `
+fn strip_log_timestamp(line: &str) -> &str {
+    match line.split_once(' ') {
+        Some((stamp, rest))
+            if stamp.ends_with('Z')
+                && chrono::DateTime::parse_from_rfc3339(stamp.trim_start_matches('\u{feff}'))
+                    .is_ok() =>
+        {
+            rest
+        }
+        _ => line,
+    }
+}
+
+/// A message asking a session to fix the failed checks of `pr`, each with
+/// an excerpt of its log when one could be fetched
+pub fn ci_failure_message(pr: &GitHubItemUrl, failures: &[(Check, Option<String>)]) -> String {
+    let mut message = format!(
+        "CI is failing on {}. Please fix it and update the pull request.\n",
+        pr
+    );
+    for (check, log) in failures {
+        message.push_str(&format!(
+            "\nFailed check: {} ({})\n",
+            check.name,
+            check.conclusion.as_deref().unwrap_or("failure")
+        ));
+        if let Some(url) = &check.details_url {
+            message.push_str(&format!("{}\n", url));
+        }
+        if let Some(log) = log.as_deref().filter(|log| !log.trim().is_empty()) {
+            message.push_str(&format!("```\n{}\n```\n", log.trim_end()));
+        }
+    }
+    message
+}
+
+/// A message asking a session to address the review `threads` of `pr`
+pub fn review_feedback(pr: &GitHubItemUrl, threads: &[ReviewThread]) -> String {
+    let mut message = format!(
+        "Please address the unresolved review comments on {} and update the pull request:\n",
+        pr
+    );
+    for (i, thread) in threads.iter().enumerate() {
+        let line = match thread.line {
+            Some(line) => format!("line {}", line),
+            None => "outdated".to_string(),
+        };
+        message.push_str(&format!("\n{}. {} ({}):\n", i + 1, thread.path, line));
+        for comment in &thread.comments {
+            message.push_str(&format!("@{}: {}\n", comment.author, comment.body.trim()));
+        }
+    }
+    message
+}
+
+/// Owner and name of the GitHub repository of a source like
+/// `sources/github/octo/app`
+pub fn source_repo(source: &str) -> Option<(String, String)> {
+    let (owner, repo) = source.strip_prefix("sources/github/")?.split_once('/')?;
+    (!owner.is_empty() && !repo.is_empty() && !repo.contains('/'))
+        .then(|| (owner.to_string(), repo.to_string()))
+}
+
+/// The comment announcing a session on the issue it works on
+///
+/// Names the session as `sessions/<ID>`, which `issue-status` looks for.
+pub fn session_link_comment(session_id: &str, url: Option<&str>) -> String {
+    let mut comment = format!(
+        "Jules session `sessions/{}` is working on this issue.\n",
+        session_id
+    );
+    if let Some(url) = url {
+        comment.push_str(&format!("\n{}\n", url));
+    }
+    comment.push_str("\n<sub>Posted by gules</sub>\n");
+    comment
+}
+
+/// The comment reporting a finished session on the issue it worked on:
+/// its pull request, or why it failed; `None` unless it completed or failed
+pub fn session_outcome_comment(payload: &WebhookPayload) -> Option<String> {
+    let mut comment = match payload.state? {
+        State::Completed => {
+            let mut comment = format!(
+                "✅ Jules session `sessions/{}` completed.\n",
+                payload.session_id
+            );
+            match &payload.pr_url {
+                Some(pr_url) => comment.push_str(&format!("\nPull request: {}\n", pr_url)),
+                None => comment.push_str("\nIt didn't open a pull request.\n"),
+            }
+            comment
+        }
+        State::Failed => {
+            let mut comment = format!(
+                "❌ Jules session `sessions/{}` failed.\n",
+                payload.session_id
+            );
+            if let Some(reason) = &payload.failure_reason {
+                comment.push_str(&format!("\nReason: {}\n", reason));
+            }
+            comment
+        }
+        _ => return None,
+    };
+    if let Some(url) = &payload.url {
+        comment.push_str(&format!("\nSession: {}\n", url));
+    }
+    comment.push_str("\n<sub>Posted by gules</sub>\n");
+    Some(comment)
+}
+
+/// How GitHub is reached
+pub enum GitHubClient {
+    /// The GitHub REST API, authenticated with a token
+    Api {
+        octocrab: Box<Octocrab>,
+        source: TokenSource,
+    },
+    /// The GitHub CLI, with its own authentication
+    Cli,
+}
+
+/// The GitHub token: `GITHUB_TOKEN`, then `GH_TOKEN`, then the
+/// `github_token` setting, then the system keyring
+pub fn github_token(config: &Config) -> Option<GitHubToken> {
+    let from_env = TOKEN_ENV_VARS.iter().find_map(|name| {
+        let token = std::env::var(name).ok()?;
+        let source = TokenSource::Env(name);
+        (!token.trim().is_empty()).then_some(GitHubToken { token, source })
+    });
+    from_env
+        .or_else(|| {
+            let token = config.github_token.clone()?;
+            let source = TokenSource::Config;
+            (!token.trim().is_empty()).then_some(GitHubToken { token, source })
+        })
+        .or_else(keyring_token)
+}
+
+/// The token stored in the system keyring
+#[cfg(feature = "keyring")]
+fn keyring_token() -> Option<GitHubToken> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT).ok()?;
+    let token = entry.get_password().ok()?;
+    Some(GitHubToken {
+        token,
+        source: TokenSource::Keyring,
+    })
+}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_token() -> Option<GitHubToken> {
+    None
+}
+
+/// Store a token where [`github_token`] finds it: the system keyring with
+/// the `keyring` feature, else the `github_token` setting
+pub fn store_github_token(token: &str) -> Result<TokenSource> {
+    #[cfg(feature = "keyring")]
+    {
+        keyring::Entry::new(KEYRING_SERVICE, KEYRING_ACCOUNT)
+            .and_then(|entry| entry.set_password(token))
+            .context("Failed to store the GitHub token in the system keyring")?;
+        Ok(TokenSource::Keyring)
+    }
+    #[cfg(not(feature = "keyring"))]
+    {
+        let mut config = jules_core::load_global_config()?;
+        config.github_token = Some(token.to_string());
+        jules_core::save_config(&config)?;
+        Ok(TokenSource::Config)
+    }
+}
+
+/// Check if gh CLI is available
+pub fn is_gh_cli_available() -> bool {
+    Command::new("gh")
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// A GitHub API client authenticated with `token`
+pub fn api_client(token: &GitHubToken) -> Result<Octocrab> {
+    Octocrab::builder()
+        .personal_token(token.token.clone())
+        .build()
+        .context("Failed to create GitHub client")
+}
+
+/// The GitHub App in the `[github]` settings, authenticated with its
+/// private key, and the ID of its installation; `None` if no app is set up
+fn github_app(config: &Config) -> Result<Option<(Octocrab, InstallationId)>> {
+    let settings = &config.github;
+    if !settings.has_app() {
+        return Ok(None);
+    }
+    let (Some(app_id), Some(installation_id), Some(key_path)) = (
+        settings.app_id,
+        settings.app_installation_id,
+        settings.app_private_key_path.as_deref(),
+    ) else {
+        let missing: Vec<&str> = [
+            ("github.app_id", settings.app_id.is_none()),
+            (
+                "github.app_installation_id",
+                settings.app_installation_id.is_none(),
+            ),
+            (
+                "github.app_private_key_path",
+                settings.app_private_key_path.is_none(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, missing)| missing.then_some(name))
+        .collect();
+        anyhow::bail!(
+            "The GitHub App settings are incomplete: set {}",
+            missing.join(" and ")
+        );
+    };
+    let pem = std::fs::read(key_path)
+        .with_context(|| format!("Failed to read the GitHub App private key {}", key_path))?;
+    let key = EncodingKey::from_rsa_pem(&pem)
+        .with_context(|| format!("{} is not a PEM-encoded RSA private key", key_path))?;
+    let octocrab = Octocrab::builder()
+        .app(AppId(app_id), key)
+        .build()
+        .context("Failed to create GitHub client")?;
+    Ok(Some((octocrab, InstallationId(installation_id))))
+}
+
+/// A GitHub API client acting as the installation of the GitHub App in the
+/// `[github]` settings, if one is set up
+///
+/// Installation tokens are requested as needed and renewed before they
+/// expire, so long-running commands like `webhook` keep working.
+pub fn app_client(config: &Config) -> Result<Option<Octocrab>> {
+    Ok(github_app(config)?.map(|(app, installation)| app.installation(installation)))
+}
+
+/// Ask GitHub about the installation of the GitHub App in the `[github]`
+/// settings, which also checks the app ID and private key
+pub async fn check_app(config: &Config) -> Result<AppInstallationInfo> {
+    let Some((app, installation)) = github_app(config)? else {
+        anyhow::bail!("No GitHub App is set up in the [github] settings");
+    };
+    let response = app
+        ._get(format!("/app/installations/{}", installation))
+        .await
+        .map_err(|e| plain_error(&e))
+        .context("Failed to reach the GitHub API")?;
+    let status = response.status();
+    let body = app
+        .body_to_string(response)
+        .await
+        .map_err(|e| plain_error(&e))
+        .context("Failed to read the GitHub API response")?;
+    match status.as_u16() {
+        401 => anyhow::bail!(
+            "GitHub rejected the GitHub App (HTTP 401): check github.app_id and that \
+             github.app_private_key_path is one of the app's current private keys"
+        ),
+        404 => anyhow::bail!(
+            "GitHub App installation {} not found (HTTP 404): check github.app_installation_id",
+            installation
+        ),
+        _ if !status.is_success() => anyhow::bail!(
+            "GitHub API error checking the GitHub App installation (HTTP {})",
+            status.as_u16()
+        ),
+        _ => {}
+    }
+    let installation: serde_json::Value =
+        serde_json::from_str(&body).context("Failed to parse the GitHub App installation")?;
+    Ok(AppInstallationInfo::from_json(&installation))
+}
+
+/// Ask GitHub who `token` belongs to and what it may do
+///
+/// Fails with a hint naming the token's source if GitHub rejects it.
+pub async fn check_token(token: &GitHubToken) -> Result<TokenInfo> {
+    let octocrab = api_client(token)?;
+    let response = octocrab
+        ._get("/user")
+        .await
+        .map_err(|e| plain_error(&e))
+        .context("Failed to reach the GitHub API")?;
+    let status = response.status();
+    let scopes = response
+        .headers()
+        .get("x-oauth-scopes")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = octocrab
+        .body_to_string(response)
+        .await
+        .map_err(|e| plain_error(&e))
+        .context("Failed to read the GitHub API response")?;
+    if status == 401 {
+        anyhow::bail!(
+            "GitHub rejected the token from {} (HTTP 401): it is invalid, expired or revoked",
+            token.source
+        );
+    }
+    if !status.is_success() {
+        anyhow::bail!(
+            "GitHub API error checking the token from {} (HTTP {})",
+            token.source,
+            status.as_u16()
+        );
+    }
+    let user: serde_json::Value =
+        serde_json::from_str(&body).context("Failed to parse the GitHub user")?;
+    Ok(TokenInfo {
+        login: user["login"].as_str().unwrap_or("unknown").to_string(),
+        scopes: TokenInfo::parse_scopes(scopes.as_deref()),
+    })
+}
+
+/// An octocrab error as a plain message, without the backtrace octocrab
+/// includes in it
+fn plain_error(error: &octocrab::Error) -> anyhow::Error {
+    if let octocrab::Error::GitHub { source, .. } = error {
+        return anyhow::anyhow!(
+            "GitHub API error {}: {}",
+            source.status_code.as_u16(),
+            source.message
+        );
+    }
+    let message = error.to_string();
+    let message = message.lines().next().unwrap_or_default().to_string();
+    let root = std::iter::successors(std::error::Error::source(error), |e| e.source()).last();
+    match root {
+        Some(root) if !message.contains(&root.to_string()) => {
+            anyhow::anyhow!("{}: {}", message, root)
+        }
+        _ => anyhow::anyhow!(message),
+    }
+}
+
+/// Explain a failed GitHub API call made with the token from `source`
+fn api_error(error: octocrab::Error, source: TokenSource) -> anyhow::Error {
+    let (status, rate_limited) = match &error {
+        octocrab::Error::GitHub { source, .. } => (
+            Some(source.status_code.as_u16()),
+            source.message.to_lowercase().contains("rate limit"),
+        ),
+        _ => (None, false),
+    };
+    let hint = match (status, source) {
+        (Some(403 | 429), _) if rate_limited => {
+            "The GitHub API rate limit is used up; see when it resets with 'gules github limits'"
+                .to_string()
+        }
+        (Some(403), TokenSource::App) => {
+            "The GitHub App lacks permission; check its permissions with 'gules github status'"
+                .to_string()
+        }
+        (Some(404), TokenSource::App) => {
+            "Not found, or the GitHub App isn't installed on the repository".to_string()
+        }
+        (Some(401), _) => format!(
+            "GitHub rejected the token from {}: it is invalid, expired or revoked",
+            source
+        ),
+        (Some(403), _) => format!(
+            "The token from {} lacks permission; check its scopes with 'gules github status'",
+            source
+        ),
+        (Some(404), _) => format!(
+            "Not found, or the token from {} can't access it (private repositories need the `repo` scope)",
+            source
+        ),
+        _ => return plain_error(&error),
+    };
+    plain_error(&error).context(hint)
+}
+
+/// Items per page when listing through the GitHub API
+const PAGE_SIZE: usize = 100;
+
+/// Record the rate limit GitHub reported in `headers`
+fn record_rate_limit(headers: &HeaderMap) {
+    let limit =
+        RateLimit::from_headers(|name| headers.get(name).and_then(|value| value.to_str().ok()));
+    if let Some(limit) = limit {
+        if let Err(e) = record_rate_limits([limit]) {
+            eprintln!("Warning: Failed to record the GitHub rate limit: {:#}", e);
+        }
+    }
+}
+
+/// Call `route` of the GitHub API with `method` (GET, POST or PUT) and JSON
+/// `body`, returning the response body and recording the rate limit
+///
+/// GET responses are cached with their ETag and revalidated, so an
+/// unchanged resource comes back as `304 Not Modified`, which doesn't count
+/// against the rate limit.
+async fn call_api(
+    octocrab: &Octocrab,
+    source: TokenSource,
+    method: Method,
+    route: &str,
+    body: Option<&serde_json::Value>,
+) -> Result<String> {
+    let cache = RESPONSE_CACHE
+        .get()
+        .and_then(Option::as_ref)
+        .filter(|_| method == Method::GET);
+    let cached = cache.as_ref().and_then(|cache| cache.get(route));
+    let response = match method {
+        Method::GET => {
+            let mut headers = HeaderMap::new();
+            if let Some(etag) = cached
+                .as_ref()
+                .and_then(|cached| HeaderValue::from_str(&cached.etag).ok())
+            {
+                headers.insert(IF_NONE_MATCH, etag);
+            }
+            octocrab._get_with_headers(route, Some(headers)).await
+        }
+        Method::PUT => octocrab._put(route, body).await,
+        _ => octocrab._post(route, body).await,
+    }
+    .map_err(|e| api_error(e, source))?;
+    record_rate_limit(response.headers());
+    if response.status() == StatusCode::NOT_MODIFIED {
+        if let (Some(cache), Some(cached)) = (cache, cached) {
+            // Rewritten so pruning counts from the last use
+            if let Err(e) = cache.put(route, &cached.etag, &cached.body) {
+                eprintln!("Warning: Failed to cache the GitHub response: {:#}", e);
+            }
+            return Ok(cached.body);
+        }
+    }
+
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let response = octocrab::map_github_error(response)
+        .await
+        .map_err(|e| api_error(e, source))?;
+    let text = octocrab
+        .body_to_string(response)
+        .await
+        .map_err(|e| api_error(e, source))?;
+    if let (Some(cache), Some(etag)) = (cache, etag) {
+        if let Err(e) = cache.put(route, &etag, &text) {
+            eprintln!("Warning: Failed to cache the GitHub response: {:#}", e);
+        }
+    }
+    Ok(text)
+}
+
+/// GET `route` of the GitHub REST API as `T` (see [`call_api`])
+async fn get_json<T: DeserializeOwned>(
+    octocrab: &Octocrab,
+    source: TokenSource,
+    route: &str,
+) -> Result<T> {
+    let text = call_api(octocrab, source, Method::GET, route, None).await?;
+    serde_json::from_str(&text).context("Failed to parse the GitHub API response")
+}
+
+/// Every item of the list at `route` of the GitHub REST API, page by page
+async fn get_all<T: DeserializeOwned>(
+    octocrab: &Octocrab,
+    source: TokenSource,
+    route: &str,
+) -> Result<Vec<T>> {
+    let separator = if route.contains('?') { '&' } else { '?' };
+    let mut items = Vec::new();
+    for page in 1.. {
+        let route = format!("{}{}per_page={}&page={}", route, separator, PAGE_SIZE, page);
+        let batch: Vec<T> = get_json(octocrab, source, &route).await?;
+        let last = batch.len() < PAGE_SIZE;
+        items.extend(batch);
+        if last {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+impl GitHubClient {
+    /// The GitHub API as the configured GitHub App or with a token if
+    /// either is available, else the gh CLI if installed
+    pub fn detect(config: &Config) -> Result<Option<Self>> {
+        RESPONSE_CACHE.get_or_init(|| {
+            ResponseCache::open_configured(&config.cache).unwrap_or_else(|e| {
+                eprintln!("Warning: GitHub responses won't be cached: {:#}", e);
+                None
+            })
+        });
+        if let Some(octocrab) = app_client(config)? {
+            return Ok(Some(Self::Api {
+                octocrab: Box::new(octocrab),
+                source: TokenSource::App,
+            }));
+        }
+        if let Some(token) = github_token(config) {
+            return Ok(Some(Self::Api {
+                octocrab: Box::new(api_client(&token)?),
+                source: token.source,
+            }));
+        }
+        Ok(is_gh_cli_available().then_some(Self::Cli))
+    }
+
+    /// Like [`detect`](Self::detect), but fails with setup instructions if
+    /// GitHub can't be reached, naming the `command` that needs it
+    pub fn require(config: &Config, command: &str) -> Result<Self> {
+        Self::detect(config)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "The {} command needs access to GitHub.\n\
+                 \n\
+                 Either set a GitHub token (with read access to issues and pull requests):\n\
+                 - export GITHUB_TOKEN=<token>\n\
+                 - or run 'gules github login'\n\
+                 - or set up a GitHub App installation (see 'gules github status')\n\
+                 \n\
+                 Or install and log in to the GitHub CLI (gh):\n\
+                 - Linux (apt):   sudo apt install gh\n\
+                 - Linux (dnf):   sudo dnf install gh\n\
+                 - macOS (brew):  brew install gh\n\
+                 - Windows:       winget install --id GitHub.cli\n\
+                 - Or download from: https://github.com/cli/cli/releases",
+                command
+            )
+        })
+    }
+
+    /// Bodies of the comments on an issue, oldest first
+    pub async fn issue_comments(&self, owner: &str, repo: &str, issue: u32) -> Result<Vec<String>> {
+        match self {
+            Self::Api { octocrab, source } => {
+                let route = format!("/repos/{}/{}/issues/{}/comments", owner, repo, issue);
+                let comments: Vec<Comment> =
+                    get_all(octocrab, *source, &route).await.with_context(|| {
+                        format!("Failed to fetch comments of {}/{}#{}", owner, repo, issue)
+                    })?;
+                Ok(comments
+                    .into_iter()
+                    .filter_map(|comment| comment.body)
+                    .collect())
+            }
+            Self::Cli => get_issue_comments_via_gh(owner, repo, issue),
+        }
+    }
+
+    /// Open issues of `owner`/`repo` with `label`, oldest first, without
+    /// their comments
+    pub async fn open_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        label: &str,
+    ) -> Result<Vec<GitHubIssue>> {
+        let mut issues = match self {
+            Self::Api { octocrab, source } => {
+                let route = format!(
+                    "/repos/{}/{}/issues?state=open&labels={}",
+                    owner,
+                    repo,
+                    urlencoding::encode(label)
+                );
+                get_all::<Issue>(octocrab, *source, &route)
+                    .await
+                    .with_context(|| format!("Failed to list the issues of {}/{}", owner, repo))?
+                    .into_iter()
+                    // The issues API lists pull requests too
+                    .filter(|issue| issue.pull_request.is_none())
+                    .map(|issue| GitHubIssue {
+                        owner: owner.to_string(),
+                        repo: repo.to_string(),
+                        number: issue.number,
+                        title: issue.title,
+                        body: issue.body,
+                        comments: Vec::new(),
+                    })
+                    .collect()
+            }
+            Self::Cli => list_issues_via_gh(owner, repo, label)?,
+        };
+        issues.sort_by_key(|issue| issue.number);
+        Ok(issues)
+    }
+
+    /// Title and body of an issue, with its comments if `with_comments`
+    pub async fn issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        with_comments: bool,
+    ) -> Result<GitHubIssue> {
+        let mut issue = match self {
+            Self::Api { octocrab, source } => {
+                let route = format!("/repos/{}/{}/issues/{}", owner, repo, number);
+                let issue: Issue = get_json(octocrab, *source, &route)
+                    .await
+                    .with_context(|| format!("Failed to fetch {}/{}#{}", owner, repo, number))?;
+                if issue.pull_request.is_some() {
+                    anyhow::bail!(
+                        "{}/{}#{} is a pull request, not an issue",
+                        owner,
+                        repo,
+                        number
+                    );
+                }
+                let mut comments = Vec::new();
+                if with_comments {
+                    comments =
+                        get_all::<Comment>(octocrab, *source, &format!("{}/comments", route))
+                            .await
+                            .context("Failed to fetch issue comments")?
+                            .into_iter()
+                            .filter_map(|comment| {
+                                Some(IssueComment {
+                                    author: comment.user.login,
+                                    body: comment.body?,
+                                })
+                            })
+                            .collect();
+                }
+                GitHubIssue {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    number,
+                    title: issue.title,
+                    body: issue.body,
+                    comments,
+                }
+            }
+            Self::Cli => get_issue_via_gh(owner, repo, number)?,
+        };
+        // gh always includes them
+        if !with_comments {
+            issue.comments.clear();
+        }
+        Ok(issue)
+    }
+
+    /// Comment `body` on an issue
+    pub async fn comment_on_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        body: &str,
+    ) -> Result<()> {
+        match self {
+            Self::Api { octocrab, source } => {
+                let route = format!("/repos/{}/{}/issues/{}/comments", owner, repo, number);
+                let body = serde_json::json!({ "body": body });
+                call_api(octocrab, *source, Method::POST, &route, Some(&body))
+                    .await
+                    .with_context(|| {
+                        format!("Failed to comment on {}/{}#{}", owner, repo, number)
+                    })?;
+                Ok(())
+            }
+            Self::Cli => {
+                let output = Command::new("gh")
+                    .arg("issue")
+                    .arg("comment")
+                    .arg(number.to_string())
+                    .arg("--repo")
+                    .arg(format!("{}/{}", owner, repo))
+                    .arg("--body")
+                    .arg(body)
+                    .output()
+                    .context("Failed to run gh CLI")?;
+                if !output.status.success() {
+                    anyhow::bail!(
+                        "gh issue comment failed for {}/{}#{}: {}",
+                        owner,
+                        repo,
+                        number,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// State, checks, reviews and mergeability of a pull request
+    ///
+    /// Pull requests on enterprise hosts are only reachable through `gh`.
+    pub async fn pull_request_status(&self, pr: &GitHubItemUrl) -> Result<PullRequestStatus> {
+        match self {
+            Self::Api { .. } if !pr.is_github_com() => Err(enterprise_host_error(pr)),
+            Self::Api { octocrab, source } => {
+                let route = format!("/repos/{}/{}/pulls/{}", pr.owner, pr.repo, pr.number);
+                let details: PullRequest = get_json(octocrab, *source, &route)
+                    .await
+                    .with_context(|| format!("Failed to fetch {}", pr))?;
+
+                let reviews =
+                    get_all::<pulls::Review>(octocrab, *source, &format!("{}/reviews", route))
+                        .await
+                        .with_context(|| format!("Failed to fetch the reviews of {}", pr))?
+                        .into_iter()
+                        .filter_map(|review| {
+                            let state = match review.state? {
+                                ReviewState::Approved => "APPROVED",
+                                ReviewState::ChangesRequested => "CHANGES_REQUESTED",
+                                ReviewState::Commented => "COMMENTED",
+                                ReviewState::Dismissed => "DISMISSED",
+                                _ => return None,
+                            };
+                            Some(Review {
+                                reviewer: review.user?.login,
+                                state: state.to_string(),
+                            })
+                        })
+                        .collect();
+
+                let route = format!(
+                    "/repos/{}/{}/commits/{}/check-runs?per_page={}",
+                    pr.owner, pr.repo, details.head.sha, PAGE_SIZE
+                );
+                let checks = get_json::<ListCheckRuns>(octocrab, *source, &route)
+                    .await
+                    .with_context(|| format!("Failed to fetch the checks of {}", pr))?
+                    .check_runs
+                    .into_iter()
+                    .map(|run| Check {
+                        name: run.name,
+                        conclusion: run.conclusion.map(|c| c.to_lowercase()),
+                        details_url: run.details_url,
+                    })
+                    .collect();
+
+                let state = match (&details.merged_at, &details.state) {
+                    (Some(_), _) => Some("MERGED"),
+                    (None, Some(IssueState::Open)) => Some("OPEN"),
+                    (None, Some(IssueState::Closed)) => Some("CLOSED"),
+                    _ => None,
+                };
+                Ok(PullRequestStatus {
+                    state: state.map(str::to_string),
+                    title: details.title,
+                    author: details.user.map(|user| user.login),
+                    created_at: details.created_at.map(|at| at.to_rfc3339()),
+                    merged_at: details.merged_at.map(|at| at.to_rfc3339()),
+                    draft: details.draft.unwrap_or(false),
+                    head_branch: Some(details.head.ref_field.clone()),
+                    head_sha: Some(details.head.sha.clone()),
+                    mergeable: details.mergeable,
+                    changed_files: details.changed_files,
+                    additions: details.additions,
+                    deletions: details.deletions,
+                    checks,
+                    reviews: latest_reviews(reviews),
+                })
+            }
+            Self::Cli => get_pr_status_via_gh(pr),
+        }
+    }
+
+    /// The unresolved review threads of a pull request
+    pub async fn review_threads(&self, pr: &GitHubItemUrl) -> Result<Vec<ReviewThread>> {
+        if matches!(self, Self::Api { .. }) && !pr.is_github_com() {
+            return Err(enterprise_host_error(pr));
+        }
+        let variables =
+            serde_json::json!({"owner": pr.owner, "repo": pr.repo, "number": pr.number});
+        self.graphql(&pr.host, REVIEW_THREADS_QUERY, &variables)
+            .await
+            .and_then(|response| unresolved_review_threads(&response))
+            .with_context(|| format!("Failed to fetch the review comments of {}", pr))
+    }
+
+    /// The pull requests that mention or close an issue on github.com
+    pub async fn linked_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue: u64,
+    ) -> Result<Vec<LinkedPullRequest>> {
+        let variables = serde_json::json!({"owner": owner, "repo": repo, "number": issue});
+        self.graphql(GITHUB_HOST, LINKED_PULL_REQUESTS_QUERY, &variables)
+            .await
+            .and_then(|response| parse_linked_pull_requests(&response))
+            .with_context(|| {
+                format!(
+                    "Failed to fetch the pull requests linked to {}/{}#{}",
+                    owner, repo, issue
+                )
+            })
+    }
+
+    /// Run a GraphQL query on `host`; string `variables` are passed as
+    /// strings, others as JSON
+    async fn graphql(
+        &self,
+        host: &str,
+        query: &str,
+        variables: &serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        match self {
+            Self::Api { octocrab, source } => {
+                let body = serde_json::json!({"query": query, "variables": variables});
+                let text =
+                    call_api(octocrab, *source, Method::POST, "/graphql", Some(&body)).await?;
+                serde_json::from_str(&text).context("Failed to parse the GitHub GraphQL response")
+            }
+            Self::Cli => {
+                let mut command = Command::new("gh");
+                command
+                    .arg("api")
+                    .arg("graphql")
+                    .arg("--hostname")
+                    .arg(host)
+                    .arg("-f")
+                    .arg(format!("query={}", query));
+                for (name, value) in variables.as_object().into_iter().flatten() {
+                    match value.as_str() {
+                        Some(value) => command.arg("-f").arg(format!("{}={}", name, value)),
+                        None => command.arg("-F").arg(format!("{}={}", name, value)),
+                    };
+                }
+                let output = command.output().context("Failed to run gh CLI")?;
+                if !output.status.success() {
+                    anyhow::bail!(
+                        "gh api graphql failed: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                serde_json::from_slice(&output.stdout)
+                    .context("Failed to parse gh api graphql output")
+            }
+        }
+    }
+
+    /// The log of a GitHub Actions job in the repository of `pr`
+    pub async fn job_log(&self, pr: &GitHubItemUrl, job_id: u64) -> Result<String> {
+        let route = format!(
+            "/repos/{}/{}/actions/jobs/{}/logs",
+            pr.owner, pr.repo, job_id
+        );
+        match self {
+            Self::Api { .. } if !pr.is_github_com() => Err(enterprise_host_error(pr)),
+            Self::Api { octocrab, source } => {
+                let fetch = async {
+                    // The API redirects to the log file
+                    let response = octocrab._get(route.as_str()).await?;
+                    record_rate_limit(response.headers());
+                    let response = octocrab.follow_location_to_data(response).await?;
+                    let response = octocrab::map_github_error(response).await?;
+                    octocrab.body_to_string(response).await
+                };
+                fetch
+                    .await
+                    .map_err(|e| api_error(e, *source))
+                    .with_context(|| format!("Failed to fetch the log of job {}", job_id))
+            }
+            Self::Cli => {
+                let output = Command::new("gh")
+                    .arg("api")
+                    .arg("--hostname")
+                    .arg(&pr.host)
+                    .arg(route.trim_start_matches('/'))
+                    .output()
+                    .context("Failed to run gh CLI")?;
+                if !output.status.success() {
+                    anyhow::bail!(
+                        "gh api failed to fetch the log of job {}: {}",
+                        job_id,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+        }
+    }
+
+    /// Merge a pull request, squashing its commits with `squash`; with
+    /// `head_sha`, GitHub refuses if commits were pushed after it
+    pub async fn merge_pull_request(
+        &self,
+        pr: &GitHubItemUrl,
+        squash: bool,
+        head_sha: Option<&str>,
+    ) -> Result<()> {
+        match self {
+            Self::Api { .. } if !pr.is_github_com() => Err(enterprise_host_error(pr)),
+            Self::Api { octocrab, source } => {
+                let route = format!("/repos/{}/{}/pulls/{}/merge", pr.owner, pr.repo, pr.number);
+                let mut body =
+                    serde_json::json!({ "merge_method": if squash { "squash" } else { "merge" } });
+                if let Some(sha) = head_sha {
+                    body["sha"] = sha.into();
+                }
+                let merged: pulls::Merge =
+                    call_api(octocrab, *source, Method::PUT, &route, Some(&body))
+                        .await
+                        .and_then(|text| {
+                            serde_json::from_str(&text)
+                                .context("Failed to parse the GitHub API response")
+                        })
+                        .with_context(|| format!("Failed to merge {}", pr))?;
+                if !merged.merged {
+                    anyhow::bail!(
+                        "GitHub didn't merge {}: {}",
+                        pr,
+                        merged.message.unwrap_or_default()
+                    );
+                }
+                Ok(())
+            }
+            Self::Cli => {
+                let mut command = Command::new("gh");
+                command
+                    .arg("pr")
+                    .arg("merge")
+                    .arg(pr.number.to_string())
+                    .arg("--repo")
+                    .arg(pr.repo_spec())
+                    .arg(if squash { "--squash" } else { "--merge" });
+                if let Some(sha) = head_sha {
+                    command.arg("--match-head-commit").arg(sha);
+                }
+                let output = command.output().context("Failed to run gh CLI")?;
+                if !output.status.success() {
+                    anyhow::bail!(
+                        "gh pr merge failed for {}: {}",
+                        pr,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// The rate limit of each GitHub API resource; asking doesn't count
+    /// against them
+    pub async fn rate_limits(&self) -> Result<RateLimits> {
+        let response: serde_json::Value = match self {
+            Self::Api { octocrab, source } => {
+                let text = call_api(octocrab, *source, Method::GET, "/rate_limit", None)
+                    .await
+                    .context("Failed to fetch the GitHub rate limits")?;
+                serde_json::from_str(&text).context("Failed to parse the GitHub rate limits")?
+            }
+            Self::Cli => {
+                let output = Command::new("gh")
+                    .arg("api")
+                    .arg("rate_limit")
+                    .output()
+                    .context("Failed to run gh CLI")?;
+                if !output.status.success() {
+                    anyhow::bail!(
+                        "gh api failed to fetch the rate limits: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                serde_json::from_slice(&output.stdout).context("Failed to parse gh api output")?
+            }
+        };
+        let limits = RateLimits::from_json(&response);
+        if let Err(e) = record_rate_limits(limits.resources.values().cloned()) {
+            eprintln!("Warning: Failed to record the GitHub rate limits: {:#}", e);
+        }
+        Ok(limits)
+    }
+
+    /// The REST API rate limit last reported, if no more than `reserve`
+    /// requests of it are left; always `None` through `gh`, whose requests
+    /// aren't tracked
+    pub fn low_rate_limit(&self, reserve: u64) -> Option<RateLimit> {
+        match self {
+            Self::Api { .. } => RateLimits::load()
+                .ok()?
+                .low("core", reserve, Utc::now())
+                .cloned(),
+            Self::Cli => None,
+        }
+    }
+
+    /// Wait `interval` before polling again, or until the rate limit resets
+    /// if no more than `reserve` requests of it are left
+    pub async fn pause(&self, interval: Duration, reserve: u64) {
+        let mut wait = interval;
+        if let Some(limit) = self.low_rate_limit(reserve) {
+            if let Some(reset) = limit.wait_for(reserve, Utc::now()) {
+                if !is_quiet() {
+                    eprintln!(
+                        "{} Only {}; waiting for the reset (github.rate_limit_reserve is {})",
+                        icon("⏳", "..."),
+                        limit.summary(),
+                        reserve
+                    );
+                }
+                wait = wait.max(reset);
+            }
+        }
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// The error for a pull request on an enterprise host, which the token
+/// client can't reach
+fn enterprise_host_error(pr: &GitHubItemUrl) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{} is on {}, which GITHUB_TOKEN doesn't reach; install GitHub CLI (gh) and \
+         run 'gh auth login --hostname {}'",
+        pr,
+        pr.host,
+        pr.host
+    )
+}
+
+/// Get issue comments via gh CLI
+fn get_issue_comments_via_gh(owner: &str, repo: &str, issue: u32) -> Result<Vec<String>> {
+    let output = Command::new("gh")
+        .arg("issue")
+        .arg("view")
+        .arg(issue.to_string())
+        .arg("--repo")
+        .arg(format!("{}/{}", owner, repo))
+        .arg("--json")
+        .arg("comments")
+        .output()
+        .context("Failed to run gh CLI")?;
+
+    if !output.status.success() {
+        anyhow::bail!("gh CLI failed: {}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    // Parse JSON output
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+    // Extract comment bodies
+    let comments = json["comments"]
+        .as_array()
+        .unwrap_or(&vec![])
+        .iter()
+        .filter_map(|c| c["body"].as_str())
+        .map(|s| s.to_string())
+        .collect();
+
+    Ok(comments)
+}
+
+/// Get an issue and its comments via gh CLI
+fn get_issue_via_gh(owner: &str, repo: &str, number: u64) -> Result<GitHubIssue> {
+    let output = Command::new("gh")
+        .arg("issue")
+        .arg("view")
+        .arg(number.to_string())
+        .arg("--repo")
+        .arg(format!("{}/{}", owner, repo))
+        .arg("--json")
+        .arg("title,body,comments")
+        .output()
+        .context("Failed to run gh CLI")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "gh issue view failed for {}/{}#{}: {}",
+            owner,
+            repo,
+            number,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let comments = json["comments"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|comment| {
+            Some(IssueComment {
+                author: comment["author"]["login"].as_str()?.to_string(),
+                body: comment["body"].as_str()?.to_string(),
+            })
+        })
+        .collect();
+
+    Ok(GitHubIssue {
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        number,
+        title: json["title"].as_str().unwrap_or_default().to_string(),
+        body: json["body"].as_str().map(str::to_string),
+        comments,
+    })
+}
+
+/// List the open issues with `label` via gh CLI
+fn list_issues_via_gh(owner: &str, repo: &str, label: &str) -> Result<Vec<GitHubIssue>> {
+    let output = Command::new("gh")
+        .arg("issue")
+        .arg("list")
+        .arg("--repo")
+        .arg(format!("{}/{}", owner, repo))
+        .arg("--label")
+        .arg(label)
+        .arg("--state")
+        .arg("open")
+        .arg("--limit")
+        .arg("1000")
+        .arg("--json")
+        .arg("number,title,body")
+        .output()
+        .context("Failed to run gh CLI")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "gh issue list failed for {}/{}: {}",
+            owner,
+            repo,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    Ok(json
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|issue| {
+            Some(GitHubIssue {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+                number: issue["number"].as_u64()?,
+                title: issue["title"].as_str().unwrap_or_default().to_string(),
+                body: issue["body"].as_str().map(str::to_string),
+                comments: Vec::new(),
+            })
+        })
+        .collect())
+}
+
+/// Get PR status via gh CLI
+fn get_pr_status_via_gh(pr: &GitHubItemUrl) -> Result<PullRequestStatus> {
+    let output = Command::new("gh")
+        .arg("pr")
+        .arg("view")
+        .arg(pr.number.to_string())
+        .arg("--repo")
+        .arg(pr.repo_spec())
+        .arg("--json")
+        .arg(
+            "state,title,author,createdAt,mergedAt,isDraft,headRefName,headRefOid,mergeable,\
+             changedFiles,additions,deletions,reviews,statusCheckRollup",
+        )
+        .output()
+        .context("Failed to run gh PR view")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "gh PR view failed for {}: {}",
+            pr,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let text = |key: &str| json[key].as_str().map(str::to_string);
+
+    // Check runs have a name and a conclusion once completed; commit
+    // statuses have a context and a state
+    let checks = json["statusCheckRollup"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|check| {
+            let name = check["name"].as_str().or(check["context"].as_str());
+            let conclusion = match check["conclusion"].as_str() {
+                Some(conclusion) if !conclusion.is_empty() => Some(conclusion.to_lowercase()),
+                Some(_) => None,
+                None => match check["state"].as_str() {
+                    Some("SUCCESS") => Some("success".to_string()),
+                    Some("FAILURE" | "ERROR") => Some("failure".to_string()),
+                    _ => None,
+                },
+            };
+            let details_url = check["detailsUrl"]
+                .as_str()
+                .or(check["targetUrl"].as_str())
+                .filter(|url| !url.is_empty());
+            Check {
+                name: name.unwrap_or("unnamed").to_string(),
+                conclusion,
+                details_url: details_url.map(str::to_string),
+            }
+        })
+        .collect();
+    let reviews = json["reviews"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|review| {
+            Some(Review {
+                reviewer: review["author"]["login"].as_str()?.to_string(),
+                state: review["state"].as_str()?.to_string(),
+            })
+        })
+        .collect();
+
+    Ok(PullRequestStatus {
+        state: text("state"),
+        title: text("title"),
+        author: json["author"]["login"].as_str().map(str::to_string),
+        created_at: text("createdAt"),
+        merged_at: text("mergedAt"),
+        draft: json["isDraft"].as_bool().unwrap_or(false),
+        head_branch: text("headRefName"),
+        head_sha: text("headRefOid"),
+        mergeable: match json["mergeable"].as_str() {
+            Some("MERGEABLE") => Some(true),
+            Some("CONFLICTING") => Some(false),
+            _ => None,
+        },
+        changed_files: json["changedFiles"].as_u64(),
+        additions: json["additions"].as_u64(),
+        deletions: json["deletions"].as_u64(),
+        checks,
+        reviews: latest_reviews(reviews),
+    })
+}
@@ -4,3 +4,4 @@
 
 pub mod commands;
 pub mod extended_commands;
+pub mod github;
@@ -7,14 +7,22 @@
 //!
 //! ## Feature Flags
 //!
-//! - `mcp`: Enable basic MCP server with SDK tools only (9 tools)
-//! - `extended-mcp`: Enable extended MCP server with SDK + extended tools (11 tools)
+//! - `mcp`: Enable basic MCP server with SDK tools only (10 tools)
+//! - `extended-mcp`: Enable extended MCP server with SDK + extended tools (12 tools)
+//! - `keyring`: Store the GitHub token in the system keyring (`gules github login`)
 
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, CommandFactory, FromArgMatches, Parser, Subcommand};
 use jules_cli::commands::*;
+use jules_core::editor::message_from_input;
+use jules_core::exit_code;
+use jules_core::sorting::{ActivitySort, SessionSort, SortOrder};
+use jules_core::time_range::TimeRange;
+use std::process::ExitCode;
+use std::time::Duration;
 
 mod commands;
 mod extended_commands;
+mod github;
 
 #[cfg(feature = "mcp")]
 mod mcp;
@@ -52,6 +60,38 @@ struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
 
+    /// Colored output: auto, always, never (default: config `color`, else auto)
+    #[arg(long, global = true, value_name = "WHEN")]
+    color: Option<String>,
+
+    /// Timestamps: relative, iso, local (default: config `display.time_format`, else relative)
+    #[arg(long, global = true, value_name = "FORMAT")]
+    time_format: Option<String>,
+
+    /// Table columns, e.g. id,state,title,created,pr (default: config `display.columns.<command>`)
+    #[arg(long, global = true, value_name = "COLUMNS")]
+    columns: Option<String>,
+
+    /// Output format: json, table, full, csv, yaml, markdown, ndjson (default: config `display.default_format`, else per command)
+    #[arg(long, global = true, value_name = "FORMAT")]
+    format: Option<String>,
+
+    /// Jules API key for this invocation (default: --profile, JULES_API_KEY, then config `api_key`)
+    #[arg(long, global = true, value_name = "KEY")]
+    api_key: Option<String>,
+
+    /// Account from the config's [profiles.<NAME>] table for this invocation
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Jules API base URL, e.g. a mock server (default: config `api_url`, else the public API)
+    #[arg(long, global = true, value_name = "URL")]
+    base_url: Option<String>,
+
+    /// Suppress decorative output; results and errors are still printed
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
     /// Run as MCP server instead of CLI
     #[cfg(feature = "mcp")]
     #[arg(long)]
@@ -68,66 +108,116 @@ enum Commands {
         /// Search text in session titles or prompts
         #[arg(long, value_name = "TEXT")]
         search: Option<String>,
-        /// Maximum number of sessions (1-100, default: 50)
+        /// Only sessions on this source: sources/github/owner/repo or owner/repo
+        #[arg(long, value_name = "SOURCE")]
+        source: Option<String>,
+        /// Only sessions created since this time: RFC 3339, a date, or a duration ago (e.g. 7d)
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Only sessions created before this time (same formats as --since)
+        #[arg(long, value_name = "TIME")]
+        until: Option<String>,
+        /// Maximum number of matching sessions; pages are followed until enough match (default: 50)
         #[arg(long, default_value = "50", value_name = "NUM")]
         limit: u32,
-        /// Output format: json, table, full (default: json)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
+        /// List sessions from the local cache without calling the API
+        #[arg(long)]
+        cached: bool,
+        /// Sort client-side by: created, updated, state, title
+        #[arg(long, value_name = "KEY")]
+        sort: Option<String>,
+        /// Reverse the --sort order
+        #[arg(long, requires = "sort")]
+        desc: bool,
+        /// Jinja2 output template, e.g. '{{id}} {{state}} {{title}}', or the name of
+        /// one in [output_templates]; overrides --format
+        #[arg(long, value_name = "TEMPLATE")]
+        template: Option<String>,
+        /// Write the results as JSON to this file instead of printing them
+        #[arg(short, long, value_name = "FILE", conflicts_with = "template")]
+        output: Option<String>,
     },
     /// Get detailed information about a specific session
     Session {
-        /// Session ID (long numeric string)
-        #[arg(value_name = "SESSION_ID")]
+        /// Session ID or resource name (e.g. 123 or sessions/123)
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
         id: String,
-        /// Output format: json, table, full (default: json)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
+        /// Jinja2 output template, e.g. '{{id}} {{state}} {{title}}', or the name of
+        /// one in [output_templates]; overrides --format
+        #[arg(long, value_name = "TEMPLATE")]
+        template: Option<String>,
+        /// Write the results as JSON to this file instead of printing them
+        #[arg(short, long, value_name = "FILE", conflicts_with = "template")]
+        output: Option<String>,
     },
     /// List only active sessions (convenience filter)
     Active {
         /// Search text in titles and prompts
         #[arg(long, value_name = "TEXT")]
         search: Option<String>,
-        /// Maximum number of results (1-100)
+        /// Maximum number of matching sessions
         #[arg(long, default_value = "50", value_name = "NUM")]
         limit: u32,
-        /// Output format: json, table, full (default: json)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
     },
     /// List only completed sessions (convenience filter)
     Completed {
         /// Search text in titles and prompts
         #[arg(long, value_name = "TEXT")]
         search: Option<String>,
-        /// Maximum number of results (1-100)
+        /// Maximum number of matching sessions
         #[arg(long, default_value = "50", value_name = "NUM")]
         limit: u32,
-        /// Output format: json, table, full (default: json)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
     },
     /// List only failed sessions (convenience filter)
     Failed {
         /// Search text in titles and prompts
         #[arg(long, value_name = "TEXT")]
         search: Option<String>,
-        /// Maximum number of results (1-100)
+        /// Maximum number of matching sessions
         #[arg(long, default_value = "50", value_name = "NUM")]
         limit: u32,
-        /// Output format: json, table, full (default: json)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
     },
     /// Create a new Jules AI coding session
+    #[command(group(ArgGroup::new("follow").args(["watch", "auto_approve"]).multiple(true)))]
     Create {
-        /// Task description for Jules (be specific!)
-        #[arg(value_name = "PROMPT")]
-        prompt: String,
-        /// Source repository (format: sources/github/owner/repo)
+        /// Task description for Jules (be specific!); `-` reads it from stdin.
+        /// With --from-issue, extra instructions after the issue
+        #[arg(
+            value_name = "PROMPT",
+            required_unless_present_any = ["edit", "file", "from_issue"]
+        )]
+        prompt: Option<String>,
+        /// Write the prompt in $VISUAL/$EDITOR, starting from PROMPT and --template
+        #[arg(short, long)]
+        edit: bool,
+        /// Create a session for each task of a YAML or JSON file (prompt,
+        /// source, branch, title); the other options apply to every task
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["prompt", "edit"])]
+        file: Option<String>,
+        /// Sessions created in parallel with --file
+        #[arg(long, default_value = "4", value_name = "NUM", requires = "file")]
+        concurrency: usize,
+        /// Work on this GitHub issue: the prompt asks to resolve it and the
+        /// title is the issue's
+        #[arg(long, value_name = "NUMBER", conflicts_with = "file")]
+        from_issue: Option<u64>,
+        /// Repository owner of --from-issue (default: from --source, the git
+        /// checkout or default_source)
+        #[arg(long, value_name = "OWNER", requires_all = ["from_issue", "repo"])]
+        owner: Option<String>,
+        /// Repository name of --from-issue
+        #[arg(long, value_name = "REPO", requires_all = ["from_issue", "owner"])]
+        repo: Option<String>,
+        /// Include the issue's comments, with their authors, in the prompt
+        #[arg(long, requires = "from_issue")]
+        with_comments: bool,
+        /// Don't comment the session link on the --from-issue issue
+        #[arg(long, requires = "from_issue")]
+        no_issue_comment: bool,
+        /// Source repository (format: sources/github/owner/repo; default:
+        /// default_source from .gules.toml or the config)
         #[arg(short, long, value_name = "SOURCE")]
-        source: String,
+        source: Option<String>,
         /// Optional session title (shown in UI)
         #[arg(long, value_name = "TITLE")]
         title: Option<String>,
@@ -138,78 +228,148 @@ enum Commands {
         #[arg(long, default_value = "false")]
         require_approval: bool,
         /// Automation mode: AUTO_CREATE_PR or MANUAL (default: AUTO_CREATE_PR)
-        #[arg(long, default_value = "AUTO_CREATE_PR", value_name = "MODE")]
-        automation_mode: String,
-        /// Output format: json, table, full (default: json)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
+        #[arg(long, value_name = "MODE")]
+        automation_mode: Option<String>,
+        /// Prompt template from the [templates] table of the config
+        #[arg(long, value_name = "NAME")]
+        template: Option<String>,
+        /// Watch the new session until it finishes; exits non-zero if it
+        /// fails, pauses or times out
+        #[arg(long, conflicts_with = "file")]
+        watch: bool,
+        /// Approve the plan as soon as it's ready (implies --require-approval)
+        #[arg(long, conflicts_with = "file")]
+        auto_approve: bool,
+        /// Poll interval in seconds for --watch and --auto-approve
+        #[arg(
+            long,
+            default_value = "10",
+            value_name = "SECONDS",
+            requires = "follow"
+        )]
+        interval: u64,
+        /// Stop waiting after this many seconds (exit code 12)
+        #[arg(long, value_name = "SECONDS", requires = "follow")]
+        timeout: Option<u64>,
     },
     /// List available code sources/repositories
     Sources {
+        #[command(subcommand)]
+        action: Option<SourcesCommands>,
         /// AIP-160 filter (e.g., "name=sources/github/owner/repo")
         #[arg(long, value_name = "FILTER")]
         filter: Option<String>,
         /// Maximum number of results (1-100)
         #[arg(long, default_value = "50", value_name = "NUM")]
         limit: u32,
-        /// Output format: json, table, full (default: json)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
+        /// List sources from the local cache without calling the API
+        #[arg(long)]
+        cached: bool,
     },
     /// Get detailed information about a specific source
     Source {
         /// Source ID (format: sources/github/owner/repo)
         #[arg(value_name = "SOURCE_ID")]
         id: String,
-        /// Output format: json, table, full (default: json)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
     },
     /// List all activities in a session
     Activities {
         /// Session ID to list activities for
-        #[arg(value_name = "SESSION_ID")]
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
         session_id: String,
         /// Maximum number of activities (1-100)
         #[arg(long, default_value = "50", value_name = "NUM")]
         limit: u32,
-        /// Output format: json, table, full (default: json)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
+        /// Sort client-side by: created, type, originator
+        #[arg(long, value_name = "KEY")]
+        sort: Option<String>,
+        /// Reverse the --sort order
+        #[arg(long, requires = "sort")]
+        desc: bool,
+        /// Only activities created since this time: RFC 3339, a date, or a duration ago (e.g. 2h)
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Only activities created before this time (same formats as --since)
+        #[arg(long, value_name = "TIME")]
+        until: Option<String>,
+        /// Jinja2 output template, e.g. '{{id}} {{state}} {{title}}', or the name of
+        /// one in [output_templates]; overrides --format
+        #[arg(long, value_name = "TEMPLATE")]
+        template: Option<String>,
+        /// Write the results as JSON to this file instead of printing them
+        #[arg(short, long, value_name = "FILE", conflicts_with = "template")]
+        output: Option<String>,
     },
     /// Get detailed information about a specific activity
     Activity {
         /// Session ID containing the activity
-        #[arg(value_name = "SESSION_ID")]
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
         session_id: String,
-        /// Activity ID (long numeric string)
+        /// Activity ID or full activity resource name
         #[arg(value_name = "ACTIVITY_ID")]
         activity_id: String,
-        /// Output format: json, table, full (default: json)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
     },
     /// Send a message to an active Jules session
     SendMessage {
         /// Session ID to send message to
-        #[arg(value_name = "SESSION_ID")]
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
         session_id: String,
-        /// Message text (be clear and specific)
-        #[arg(value_name = "MESSAGE")]
-        message: String,
+        /// Message text (be clear and specific); `-` reads it from stdin
+        #[arg(value_name = "MESSAGE", required_unless_present = "file")]
+        message: Option<String>,
+        /// Read the message from a file (`-` for stdin)
+        #[arg(short, long, value_name = "FILE", conflicts_with = "message")]
+        file: Option<String>,
     },
     /// Approve the execution plan for a session
     ApprovePlan {
         /// Session ID with pending plan approval
-        #[arg(value_name = "SESSION_ID")]
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
+        session_id: String,
+    },
+    /// Review a session's plan step by step, then approve or reject it
+    Plan {
+        /// Session ID
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
+        session_id: String,
+    },
+    /// Cancel a session that is still running
+    Cancel {
+        /// Session ID to cancel
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
         session_id: String,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Delete a session, or all sessions matching --state/--older-than
+    Delete {
+        /// Session ID to delete
+        #[arg(
+            value_name = "SESSION_ID",
+            value_parser = session_ref,
+            required_unless_present_any = ["state", "older_than"]
+        )]
+        session_id: Option<String>,
+        /// Delete sessions in this state: active, completed, failed, or paused
+        #[arg(long, value_name = "STATE", conflicts_with = "session_id")]
+        state: Option<String>,
+        /// Delete sessions created longer ago than this, e.g. 30d or 12h
+        #[arg(long, value_name = "AGE", conflicts_with = "session_id")]
+        older_than: Option<String>,
+        /// List the matching sessions without deleting them
+        #[arg(long, conflicts_with = "session_id")]
+        dry_run: bool,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
     /// Manage configuration
     Config {
         #[command(subcommand)]
         action: ConfigCommands,
     },
-    /// Check Jules sessions linked to a GitHub issue (requires gh CLI)
+    /// Check Jules sessions linked to a GitHub issue (requires GITHUB_TOKEN or gh CLI)
     IssueStatus {
         /// GitHub issue number
         #[arg(value_name = "ISSUE_NUM")]
@@ -221,30 +381,72 @@ enum Commands {
         #[arg(short, long, value_name = "REPO")]
         repo: String,
     },
-    /// Find the GitHub PR created by a Jules session (requires gh CLI)
+    /// Show the GitHub PR created by a Jules session: its checks, reviews and mergeability
     PrStatus {
         /// Session ID that created the PR
-        #[arg(value_name = "SESSION_ID")]
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
         session_id: String,
     },
     /// Continuously monitor session until completion
     Watch {
         /// Session ID
+        #[arg(value_parser = session_ref)]
         session_id: String,
         /// Poll interval in seconds
         #[arg(short, long, default_value = "10")]
         interval: u64,
+        /// Stop watching after this many seconds (exit code 12)
+        #[arg(long, value_name = "SECONDS")]
+        timeout: Option<u64>,
+        /// Print one JSON object per state change and new activity
+        /// (same as --format ndjson)
+        #[arg(long)]
+        events: bool,
+        /// POST a JSON summary to this URL when the session finishes
+        /// (default: config `notifications.webhook_url`)
+        #[arg(long, value_name = "URL")]
+        webhook: Option<String>,
+        /// Comment the outcome on the GitHub issues the session is linked to
+        /// once it completes or fails (default: config
+        /// `notifications.issue_comments`)
+        #[arg(long)]
+        comment_on_issue: bool,
+    },
+    /// Send a message to a session awaiting feedback and watch it
+    Resume {
+        /// Session ID
+        #[arg(value_parser = session_ref)]
+        session_id: String,
+        /// Message to send (default: "Please continue.")
+        message: Option<String>,
+        /// Poll interval in seconds
+        #[arg(short, long, default_value = "10")]
+        interval: u64,
     },
     /// Continuously monitor all sessions
     Monitor {
         /// Poll interval in seconds
         #[arg(short, long, default_value = "30")]
         interval: u64,
+        /// Only sessions in this state: active, completed, failed, or paused
+        #[arg(long, value_name = "STATE")]
+        state: Option<String>,
+        /// Only sessions on this source: sources/github/owner/repo or owner/repo
+        #[arg(long, value_name = "SOURCE")]
+        source: Option<String>,
+        /// After the first table, print only sessions that appeared or
+        /// changed state (old → new)
+        #[arg(long)]
+        changes_only: bool,
+        /// Interactive dashboard: browse sessions, their activities and
+        /// diffs, approve plans, send messages and open sessions
+        #[arg(long, conflicts_with = "changes_only")]
+        tui: bool,
     },
     /// Filter and search session activities with caching
     FilterActivities {
         /// Session ID to filter activities for
-        #[arg(value_name = "SESSION_ID")]
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
         session_id: String,
         /// Get only the last N activities
         #[arg(long, value_name = "N")]
@@ -256,25 +458,392 @@ enum Commands {
         /// Filter activities with bash output (test errors, command outputs)
         #[arg(long)]
         has_bash_output: bool,
+        /// Only bash commands that exited with a non-zero code
+        #[arg(long)]
+        failed_only: bool,
+        /// Only activities created since this time: RFC 3339, a date, or a duration ago (e.g. 2h)
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Only activities created before this time (same formats as --since)
+        #[arg(long, value_name = "TIME")]
+        until: Option<String>,
         /// Disable cache and fetch fresh from API
         #[arg(long)]
         no_cache: bool,
-        /// Output format: json (default, machine-readable), table (human-readable), full (detailed), content-only (text only)
-        #[arg(long, default_value = "json", value_name = "FORMAT")]
-        format: String,
+        /// Write the results as JSON to this file instead of printing them
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Print a session's activities oldest first, optionally following new ones
+    Logs {
+        /// Session ID
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
+        session_id: String,
+        /// Keep printing new activities until the session completes or fails
+        #[arg(short, long)]
+        follow: bool,
+        /// Only these activity types (comma-separated)
+        /// Types: agent-message, user-message, plan, progress, completed, failed, error
+        #[arg(long, value_name = "TYPES", value_delimiter = ',')]
+        r#type: Vec<String>,
+        /// Only activities since this time: RFC 3339, a date, or a duration ago (e.g. 2h)
+        #[arg(long, value_name = "TIME")]
+        since: Option<String>,
+        /// Poll interval in seconds with --follow
+        #[arg(short, long, default_value = "5")]
+        interval: u64,
+    },
+    /// Search cached activities (messages, bash output, patches)
+    Grep {
+        /// Text to search for (case-insensitive substring, or regex with -E)
+        #[arg(value_name = "PATTERN")]
+        pattern: String,
+        /// Only search this session
+        #[arg(long, value_name = "SESSION_ID", value_parser = session_ref)]
+        session: Option<String>,
+        /// Treat PATTERN as a (case-insensitive) regular expression
+        #[arg(short = 'E', long)]
+        regex: bool,
+        /// Only search this kind of content: bash, message, diff
+        #[arg(long, value_name = "TYPE")]
+        r#type: Option<String>,
+        /// Maximum number of matching activities
+        #[arg(long, default_value = "50", value_name = "NUM")]
+        limit: usize,
+    },
+    /// Print a session's git patches, or save them as .patch files
+    Diff {
+        /// Session ID
+        #[arg(value_parser = session_ref)]
+        session_id: String,
+        /// Show every patch of the session (default)
+        #[arg(long, conflicts_with = "latest")]
+        all: bool,
+        /// Show only the newest patch
+        #[arg(long)]
+        latest: bool,
+        /// Show per-file line counts instead of the diff
+        #[arg(long)]
+        stat: bool,
+        /// Save patches as numbered .patch files in this directory
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["latest", "stat"])]
+        save: Option<String>,
+    },
+    /// Create a local branch with a session's changes, committed
+    Checkout {
+        /// Session ID
+        #[arg(value_parser = session_ref)]
+        session_id: String,
+        /// Branch to create (default: jules/<SESSION_ID>)
+        #[arg(long, value_name = "NAME")]
+        branch: Option<String>,
+    },
+    /// Export a session to a standalone report (prompt, plan, timeline, diffs, PR)
+    Export {
+        /// Session ID
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
+        session_id: String,
+        /// File to write (default: stdout)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Summarize the sessions of a period: states, durations, PRs, failures
+    Report {
+        /// Start of the period: RFC 3339, a date, or a duration ago (e.g. 7d)
+        #[arg(long, default_value = "7d", value_name = "TIME")]
+        since: String,
+        /// End of the period
+        #[arg(long, default_value = "now", value_name = "TIME")]
+        until: String,
+        /// Only sessions of this source (e.g. sources/github/octo/app or octo/app)
+        #[arg(long, value_name = "SOURCE")]
+        source: Option<String>,
+    },
+    /// Start a new session with the prompt of an existing one
+    Clone {
+        /// Session to clone
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
+        session_id: String,
+        /// Source repository for the new session (format: sources/github/owner/repo;
+        /// default: the original's)
+        #[arg(short, long, value_name = "SOURCE")]
+        source: Option<String>,
+        /// Starting branch (default: the original's, unless --source changes)
+        #[arg(long, value_name = "BRANCH")]
+        branch: Option<String>,
+        /// Title of the new session (default: the original's)
+        #[arg(long, value_name = "TITLE")]
+        title: Option<String>,
+    },
+    /// Open a session, or its pull request, in the browser
+    Open {
+        /// Session ID
+        #[arg(value_parser = session_ref)]
+        session_id: String,
+        /// Open the session's pull request instead
+        #[arg(long)]
+        pr: bool,
+    },
+    /// Set up gules interactively: API key, GitHub CLI, default repository
+    Init,
+    /// Generate roff man pages for gules and all its subcommands
+    Man {
+        /// Write gules.1 and a page per subcommand (gules-cache-stats.1, ...)
+        /// to this directory instead of printing the gules(1) page
+        #[arg(long, value_name = "DIR")]
+        dir: Option<String>,
+    },
+    /// Check the configuration, API key, API connectivity, gh CLI and cache
+    Doctor {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Refresh caches of active sessions in the background and publish events
+    Daemon {
+        #[command(subcommand)]
+        action: Option<DaemonCommands>,
+        /// Refresh interval in seconds
+        #[arg(short, long, default_value = "60")]
+        interval: u64,
+        /// Refresh once and exit
+        #[arg(long)]
+        once: bool,
     },
     /// Manage activity cache
     Cache {
         #[command(subcommand)]
         action: CacheCommands,
     },
+    /// Queue prompts locally and submit them one at a time
+    Queue {
+        #[command(subcommand)]
+        action: QueueCommands,
+    },
+    /// List recently used sessions; SESSION_ID arguments accept `@1` for the most recent
+    Recent {
+        /// Maximum number of sessions
+        #[arg(long, default_value = "10", value_name = "NUM")]
+        limit: usize,
+    },
+    /// Work with the pull request a session opened
+    Pr {
+        #[command(subcommand)]
+        action: PrCommands,
+    },
+    /// Link a session to a GitHub issue
+    Link {
+        #[command(subcommand)]
+        action: LinkCommands,
+    },
+    /// Show the links between GitHub issues and sessions
+    Links {
+        #[command(subcommand)]
+        action: LinksCommands,
+    },
+    /// Start a session on each open GitHub issue with a label, commenting
+    /// the session link on the issue
+    Triage {
+        /// Label of the issues to start sessions on
+        #[arg(long, value_name = "LABEL")]
+        label: String,
+        /// Repository owner (default: from the git checkout or default_source)
+        #[arg(long, value_name = "OWNER", requires = "repo")]
+        owner: Option<String>,
+        /// Repository name
+        #[arg(long, value_name = "REPO", requires = "owner")]
+        repo: Option<String>,
+        /// Most sessions running on the repository at once; the other
+        /// issues are left for a later run
+        #[arg(long, value_name = "NUM", default_value = "3")]
+        max_active: usize,
+        /// Include each issue's comments in its prompt
+        #[arg(long)]
+        with_comments: bool,
+        /// Don't comment the session link on the issues
+        #[arg(long)]
+        no_issue_comment: bool,
+        /// Starting branch of the sessions (default: main)
+        #[arg(long, value_name = "BRANCH")]
+        branch: Option<String>,
+        /// Require plan approval before the sessions start working
+        #[arg(long)]
+        require_approval: bool,
+        /// Only list the issues that would get a session
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Find the session that produced a pull request or commit
+    Blame {
+        /// Pull request URL
+        #[arg(
+            long,
+            value_name = "URL",
+            required_unless_present = "commit",
+            conflicts_with = "commit"
+        )]
+        pr: Option<String>,
+        /// Commit SHA or any other revision, in a repository that has it;
+        /// matched against the cached sessions' patches
+        #[arg(long, value_name = "SHA")]
+        commit: Option<String>,
+        /// Search the session cache instead of listing every session
+        #[arg(long, requires = "pr")]
+        cached: bool,
+    },
+    /// Check or store the GitHub token used by the GitHub integrations
+    Github {
+        #[command(subcommand)]
+        action: GithubCommands,
+    },
+    /// Keep local notes on a session: `note <SESSION_ID> <TEXT>` adds one
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Note {
+        #[command(subcommand)]
+        action: Option<NoteCommands>,
+        /// Session ID
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref, required = true)]
+        session_id: Option<String>,
+        /// Note text; `-` reads it from stdin
+        #[arg(value_name = "TEXT", required = true)]
+        text: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PrCommands {
+    /// Check out the branch of a session's PR in the current repository
+    Checkout {
+        /// Session ID
+        #[arg(value_parser = session_ref)]
+        session_id: String,
+        /// Local branch name (default: the PR's branch)
+        #[arg(long, value_name = "NAME")]
+        branch: Option<String>,
+    },
+    /// Merge a session's PR once its checks passed and no reviewer
+    /// requested changes
+    Merge {
+        /// Session ID
+        #[arg(value_parser = session_ref)]
+        session_id: String,
+        /// Squash the PR's commits into one
+        #[arg(long)]
+        squash: bool,
+        /// Wait for running checks instead of refusing to merge
+        #[arg(long)]
+        when_green: bool,
+        /// Merge even if the PR has no checks (by default a PR without
+        /// checks isn't green)
+        #[arg(long)]
+        allow_no_checks: bool,
+        /// Poll interval in seconds while waiting for checks
+        #[arg(short, long, default_value = "30", requires = "when_green")]
+        interval: u64,
+        /// Stop waiting for checks after this many seconds (exit code 12)
+        #[arg(long, value_name = "SECONDS", requires = "when_green")]
+        timeout: Option<u64>,
+    },
+    /// Send the unresolved review comments on a session's PR to the session
+    Feedback {
+        /// Session ID
+        #[arg(value_parser = session_ref)]
+        session_id: String,
+        /// Print the message instead of sending it
+        #[arg(long, conflicts_with = "watch")]
+        dry_run: bool,
+        /// Watch the session after sending the comments
+        #[arg(long)]
+        watch: bool,
+        /// Poll interval in seconds while watching
+        #[arg(short, long, default_value = "10", requires = "watch")]
+        interval: u64,
+    },
+    /// Watch the checks of a session's PR and send failures with their
+    /// logs to the session until CI passes
+    CiWatch {
+        /// Session ID
+        #[arg(value_parser = session_ref)]
+        session_id: String,
+        /// Poll interval in seconds
+        #[arg(short, long, default_value = "60")]
+        interval: u64,
+        /// Stop watching after this many seconds (exit code 12)
+        #[arg(long, value_name = "SECONDS")]
+        timeout: Option<u64>,
+        /// Give up after asking the session this many times to fix CI
+        #[arg(long, value_name = "N", default_value = "3")]
+        max_fixes: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum LinkCommands {
+    /// Link a session to a GitHub issue and comment the link on the issue
+    Issue {
+        /// Issue number
+        #[arg(value_name = "ISSUE")]
+        issue: u64,
+        /// Session ID
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
+        session_id: String,
+        /// Repository owner (default: that of the session's source)
+        #[arg(long, value_name = "OWNER", requires = "repo")]
+        owner: Option<String>,
+        /// Repository name
+        #[arg(long, value_name = "REPO", requires = "owner")]
+        repo: Option<String>,
+        /// Don't comment the session link on the issue
+        #[arg(long)]
+        no_comment: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum LinksCommands {
+    /// List issue-session links, oldest first
+    List {
+        /// Only the links of this session
+        #[arg(long, value_name = "SESSION_ID", value_parser = session_ref)]
+        session: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum GithubCommands {
+    /// Show where the GitHub token comes from, whose it is and its scopes
+    Status,
+    /// Check a GitHub token (from stdin, or asked for) and store it
+    Login,
+    /// Show how much of each GitHub API rate limit is left and when it
+    /// resets
+    Limits,
+}
+
+#[derive(Subcommand)]
+enum NoteCommands {
+    /// Print the notes of a session, oldest first
+    Show {
+        /// Session ID
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
+        session_id: String,
+    },
+    /// Delete every note of a session
+    Clear {
+        /// Session ID
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
+        session_id: String,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
 }
 
 #[derive(Subcommand)]
 enum ConfigCommands {
     /// Show current configuration
     Show,
-    /// Initialize configuration
+    /// Set up the configuration interactively (same as `gules init`)
     Init,
     /// Set a configuration value
     Set {
@@ -283,25 +852,347 @@ enum ConfigCommands {
         /// Value to set
         value: String,
     },
+    /// Print a configuration value
+    Get {
+        /// Key to print (e.g. cache.max_sessions)
+        key: String,
+    },
+    /// Remove a configuration value, restoring its default
+    Unset {
+        /// Key to remove
+        key: String,
+    },
+    /// Open the config file in $EDITOR
+    Edit,
+    /// Print the JSON Schema of the config file
+    Schema,
+}
+
+#[derive(Subcommand)]
+enum SourcesCommands {
+    /// Fetch all sources and update the local sources cache
+    Refresh,
+    /// Check that a repository is connected to Jules, or show how to
+    /// connect it (exit code 2 if it isn't)
+    Check {
+        /// Source as sources/github/owner/repo, owner/repo or repo
+        #[arg(value_name = "SOURCE")]
+        source: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum DaemonCommands {
+    /// Show whether a daemon is running
+    Status,
+    /// Print recent events (JSON lines)
+    Events {
+        /// Only events of this session
+        #[arg(long, value_name = "SESSION_ID", value_parser = session_ref)]
+        session: Option<String>,
+        /// Maximum number of events
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum QueueCommands {
+    /// Add a prompt (or the tasks of a file) to the queue
+    Add {
+        /// Task description for Jules; `-` reads it from stdin
+        #[arg(value_name = "PROMPT", required_unless_present = "file")]
+        prompt: Option<String>,
+        /// Queue each task of a YAML or JSON file, like `create --file`
+        #[arg(long, value_name = "FILE", conflicts_with = "prompt")]
+        file: Option<String>,
+        /// Source repository (default: default_source from .gules.toml or the config)
+        #[arg(short, long, value_name = "SOURCE")]
+        source: Option<String>,
+        /// Optional session title
+        #[arg(long, value_name = "TITLE")]
+        title: Option<String>,
+        /// Starting branch for GitHub repos (default: main)
+        #[arg(long, value_name = "BRANCH")]
+        branch: Option<String>,
+        /// Require plan approval before execution
+        #[arg(long)]
+        require_approval: bool,
+        /// Automation mode: AUTO_CREATE_PR or MANUAL (default: AUTO_CREATE_PR)
+        #[arg(long, value_name = "MODE")]
+        automation_mode: Option<String>,
+        /// Prompt template from the [templates] table of the config
+        #[arg(long, value_name = "NAME")]
+        template: Option<String>,
+    },
+    /// List the queued tasks
+    List,
+    /// Submit queued tasks in order, one at a time
+    Run {
+        /// Wait for each session to finish (or need input) before the next
+        #[arg(short, long)]
+        wait: bool,
+        /// Poll interval in seconds while waiting
+        #[arg(short, long, default_value = "30", requires = "wait")]
+        interval: u64,
+        /// Submit at most N tasks
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+    },
+    /// Remove tasks from the queue without submitting them
+    Remove {
+        /// Task IDs (from `gules queue list`)
+        #[arg(required = true)]
+        ids: Vec<u64>,
+    },
 }
 
 #[derive(Subcommand)]
 enum CacheCommands {
     /// Show cache statistics
-    Stats,
+    Stats {
+        /// Sort sessions by: size, activities, updated, hits (default: eviction order)
+        #[arg(long, value_name = "KEY")]
+        sort: Option<String>,
+        /// Only list the first N sessions
+        #[arg(long, value_name = "N")]
+        top: Option<usize>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Clear all cached activities
-    Clear,
+    Clear {
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
     /// Delete cache for a specific session
     Delete {
         /// Session ID to delete cache for
-        #[arg(value_name = "SESSION_ID")]
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
+        session_id: String,
+    },
+    /// Check cache files against the metadata and optionally repair them
+    Verify {
+        /// Delete unreadable files and rebuild the metadata
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Export cached sessions to a .tar.zst archive
+    Export {
+        /// Archive file to write (e.g. cache.tar.zst)
+        #[arg(value_name = "FILE")]
+        path: String,
+        /// Only export this session (repeatable; default: all cached sessions)
+        #[arg(long = "session", value_name = "SESSION_ID", value_parser = session_ref)]
+        sessions: Vec<String>,
+    },
+    /// Import cached sessions from an archive created by `cache export`
+    Import {
+        /// Archive file to read
+        #[arg(value_name = "FILE")]
+        path: String,
+    },
+    /// Prefetch activities of recent sessions into the cache
+    Warm {
+        /// Which sessions to warm: active or all
+        #[arg(long, default_value = "active")]
+        state: String,
+        /// Maximum number of sessions to warm
+        #[arg(long, default_value = "20")]
+        limit: usize,
+        /// Number of sessions fetched in parallel
+        #[arg(long, default_value = "4")]
+        concurrency: usize,
+    },
+    /// Pin a session so its cache is never evicted
+    Pin {
+        /// Session ID or resource name (sessions/{id})
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
         session_id: String,
     },
+    /// Unpin a session, making its cache subject to eviction again
+    Unpin {
+        /// Session ID or resource name (sessions/{id})
+        #[arg(value_name = "SESSION_ID", value_parser = session_ref)]
+        session_id: String,
+    },
+}
+
+/// Parse a SESSION_ID argument, resolving `@N` to a recently used session
+/// and an unambiguous prefix to the full ID
+fn session_ref(value: &str) -> anyhow::Result<String> {
+    commands::resolve_session_ref(value)
+}
+
+/// Config for display settings, without creating the config file
+///
+/// A missing or invalid config falls back to the defaults; commands that
+/// need the config report the error themselves.
+fn display_config() -> jules_core::Config {
+    let config = jules_core::get_config_path()
+        .ok()
+        .filter(|path| path.exists())
+        .and_then(|path| jules_core::read_config(&path).ok())
+        .unwrap_or_default();
+    std::env::current_dir()
+        .ok()
+        .and_then(|cwd| jules_core::find_project_config(&cwd))
+        .and_then(|path| jules_core::merge_project_config(&config, &path).ok())
+        .unwrap_or(config)
+}
+
+/// Formats of the commands that print sessions, sources or activities
+const DATA_FORMATS: &[&str] = &["json", "table", "full", "csv", "yaml", "markdown", "ndjson"];
+
+/// Formats of the commands that follow sessions as they run
+const STREAM_FORMATS: &[&str] = &["table", "ndjson"];
+
+/// Formats of the commands that act on a session and print it afterwards
+const ACTION_FORMATS: &[&str] = &["table", "json"];
+
+/// Formats of `filter-activities`, which can also print bare content
+const FILTER_FORMATS: &[&str] = &[
+    "json",
+    "table",
+    "full",
+    "csv",
+    "yaml",
+    "markdown",
+    "ndjson",
+    "content-only",
+];
+
+/// The `--format` flag and the configured default format
+struct FormatChoice {
+    flag: Option<String>,
+    configured: Option<String>,
+}
+
+impl FormatChoice {
+    /// The flag if given, else the configured format if the command
+    /// supports it, else `default`
+    fn pick(&self, default: &str, supported: &[&str]) -> String {
+        self.chosen(supported)
+            .unwrap_or_else(|| default.to_string())
+    }
+
+    /// The flag if given, else the configured format if the command
+    /// supports it
+    ///
+    /// The flag is returned even when unsupported, so the command reports
+    /// it; a configured format a command cannot print is skipped instead.
+    fn chosen(&self, supported: &[&str]) -> Option<String> {
+        self.flag.clone().or_else(|| {
+            self.configured
+                .as_ref()
+                .filter(|format| supported.contains(&format.to_lowercase().as_str()))
+                .cloned()
+        })
+    }
+
+    /// `--output` saves JSON, so an explicit non-JSON format is an error
+    fn check_output(&self, output: Option<&str>) -> anyhow::Result<()> {
+        match (&self.flag, output) {
+            (Some(format), Some(_)) if !format.eq_ignore_ascii_case("json") => {
+                anyhow::bail!(
+                    "--output saves JSON and cannot be combined with --format {}",
+                    format
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Whether a command printing a confirmation (table) or the session (json)
+/// should print JSON
+fn is_json(format: &str) -> anyhow::Result<bool> {
+    match format.to_lowercase().as_str() {
+        "json" => Ok(true),
+        "table" => Ok(false),
+        _ => anyhow::bail!("Unknown format: {}. Valid options: table, json", format),
+    }
 }
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+async fn main() -> ExitCode {
+    let matches = match Cli::command().try_get_matches() {
+        Ok(matches) => matches,
+        Err(e) => {
+            // Help and version go to stdout and succeed
+            let _ = e.print();
+            return ExitCode::from(if e.use_stderr() {
+                exit_code::USAGE
+            } else {
+                exit_code::SUCCESS
+            });
+        }
+    };
+
+    match run(matches).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            let code = exit_code::exit_code(&e);
+            if code == exit_code::NOT_FOUND {
+                commands::print_session_suggestions();
+            }
+            ExitCode::from(code)
+        }
+    }
+}
+
+async fn run(matches: clap::ArgMatches) -> anyhow::Result<()> {
+    let cli = Cli::from_arg_matches(&matches)?;
+    jules_core::set_quiet(cli.quiet);
+    jules_core::set_config_overrides(jules_core::ConfigOverrides {
+        api_key: cli.api_key.clone(),
+        profile: cli.profile.clone(),
+        base_url: cli.base_url.clone(),
+    });
+
+    let config = display_config();
+    let color = match &cli.color {
+        Some(mode) => jules_core::ColorMode::parse(mode)?,
+        None => config.color,
+    };
+    jules_core::set_color_mode(color);
+    let time_format = match &cli.time_format {
+        Some(format) => jules_core::TimeFormat::parse(format)?,
+        None => config.display.time_format,
+    };
+    // Warn rather than fail, so `config set` can still fix a bad value
+    let timezone = config.display.timezone().unwrap_or_else(|e| {
+        eprintln!("Warning: {}", e);
+        None
+    });
+    jules_core::set_time_display(time_format, timezone);
+    let columns = cli.columns.clone().or_else(|| {
+        matches
+            .subcommand_name()
+            .and_then(|command| config.display.columns.get(command).cloned())
+    });
+    if let Some(columns) = &columns {
+        jules_core::table_columns::validate_columns(columns)?;
+    }
+    jules_core::table_columns::set_table_columns(columns);
+    let configured = config.display.default_format.clone();
+    if let Some(format) = configured
+        .as_deref()
+        .filter(|format| !FILTER_FORMATS.contains(&format.to_lowercase().as_str()))
+    {
+        eprintln!(
+            "Warning: Unknown display.default_format '{}'. Valid options: {}",
+            format,
+            DATA_FORMATS.join(", ")
+        );
+    }
+    let formats = FormatChoice {
+        flag: cli.format.clone(),
+        configured,
+    };
 
     // Check if running as MCP server
     #[cfg(feature = "mcp")]
@@ -314,131 +1205,366 @@ async fn main() -> anyhow::Result<()> {
         Some(Commands::Sessions {
             state,
             search,
+            source,
+            since,
+            until,
             limit,
-            format,
+            cached,
+            template,
+            output,
+            sort,
+            desc,
         }) => {
-            extended_commands::handle_sessions_formatted(state, search, limit, &format).await?;
-        }
-        Some(Commands::Session { id, format }) => {
-            extended_commands::handle_session_formatted(&id, &format).await?;
+            formats.check_output(output.as_deref())?;
+            let format = formats.pick("json", DATA_FORMATS);
+            let sort = sort
+                .map(|key| SessionSort::parse(&key).map(|key| SortOrder::new(key, desc)))
+                .transpose()?;
+            let filter = extended_commands::SessionFilter {
+                state,
+                search,
+                source,
+                created: TimeRange::parse(since.as_deref(), until.as_deref())?,
+            };
+            let options = extended_commands::OutputOptions {
+                format: &format,
+                template: template.as_deref(),
+                output: output.as_deref(),
+            };
+            extended_commands::handle_sessions_formatted(&filter, limit, cached, sort, &options)
+                .await?;
         }
-        Some(Commands::Active {
-            search,
-            limit,
-            format,
+        Some(Commands::Session {
+            id,
+            template,
+            output,
         }) => {
+            formats.check_output(output.as_deref())?;
+            let format = formats.pick("json", DATA_FORMATS);
+            let options = extended_commands::OutputOptions {
+                format: &format,
+                template: template.as_deref(),
+                output: output.as_deref(),
+            };
+            extended_commands::handle_session_formatted(&id, &options).await?;
+        }
+        Some(Commands::Active { search, limit }) => {
+            let format = formats.pick("json", DATA_FORMATS);
             extended_commands::handle_active_formatted(search, limit, &format).await?;
         }
-        Some(Commands::Completed {
-            search,
-            limit,
-            format,
-        }) => {
+        Some(Commands::Completed { search, limit }) => {
+            let format = formats.pick("json", DATA_FORMATS);
             extended_commands::handle_completed_formatted(search, limit, &format).await?;
         }
-        Some(Commands::Failed {
-            search,
-            limit,
-            format,
-        }) => {
+        Some(Commands::Failed { search, limit }) => {
+            let format = formats.pick("json", DATA_FORMATS);
             extended_commands::handle_failed_formatted(search, limit, &format).await?;
         }
         Some(Commands::Create {
             prompt,
+            edit,
+            file,
+            concurrency,
+            from_issue,
+            owner,
+            repo,
+            with_comments,
+            no_issue_comment,
             source,
             title,
             branch,
             require_approval,
             automation_mode,
-            format,
+            template,
+            watch,
+            auto_approve,
+            interval,
+            timeout,
         }) => {
-            extended_commands::handle_create_formatted(
-                prompt,
-                source,
-                title,
-                branch,
-                require_approval,
-                &automation_mode,
-                &format,
-            )
-            .await?;
+            if let Some(file) = file {
+                let defaults = CreateArgs {
+                    prompt: String::new(),
+                    source,
+                    title,
+                    branch,
+                    require_approval,
+                    automation_mode,
+                    template,
+                };
+                let format = formats.pick("table", DATA_FORMATS);
+                commands::handle_create_batch(&file, defaults, concurrency, &format).await?;
+            } else {
+                let (prompt, issue) = match from_issue {
+                    Some(number) => {
+                        let (prompt, issue) = extended_commands::issue_prompt(
+                            number,
+                            owner,
+                            repo,
+                            source.as_deref(),
+                            with_comments,
+                            prompt,
+                        )
+                        .await?;
+                        (Some(prompt), Some(issue))
+                    }
+                    None => (prompt, None),
+                };
+                let (prompt, template) =
+                    extended_commands::read_create_prompt(prompt, edit, template)?;
+                let (source, title) = match &issue {
+                    Some(issue) => (
+                        source.or_else(|| {
+                            Some(format!("sources/github/{}/{}", issue.owner, issue.repo))
+                        }),
+                        title.or_else(|| Some(issue.title.clone())),
+                    ),
+                    None => (source, title),
+                };
+                let args = CreateArgs {
+                    prompt,
+                    source,
+                    title,
+                    branch,
+                    require_approval: require_approval || auto_approve,
+                    automation_mode,
+                    template,
+                };
+                let follow = extended_commands::FollowOptions {
+                    watch,
+                    auto_approve,
+                    interval,
+                    timeout: timeout.map(Duration::from_secs),
+                };
+                let format = if watch {
+                    formats.pick("table", STREAM_FORMATS)
+                } else {
+                    formats.pick("json", DATA_FORMATS)
+                };
+                let origin = issue.map(|issue| extended_commands::IssueOrigin {
+                    issue,
+                    comment: !no_issue_comment,
+                });
+                extended_commands::handle_create_formatted(args, &format, follow, origin.as_ref())
+                    .await?;
+            }
+        }
+        Some(Commands::Sources {
+            action: Some(SourcesCommands::Refresh),
+            ..
+        }) => {
+            extended_commands::handle_sources_refresh().await?;
         }
         Some(Commands::Sources {
+            action: Some(SourcesCommands::Check { source }),
+            ..
+        }) => {
+            let format = formats.pick("table", &["table", "json"]);
+            extended_commands::handle_sources_check(&source, &format).await?;
+        }
+        Some(Commands::Sources {
+            action: None,
             filter,
             limit,
-            format,
+            cached,
         }) => {
-            extended_commands::handle_sources_formatted(filter, limit, &format).await?;
+            let format = formats.pick("json", DATA_FORMATS);
+            extended_commands::handle_sources_formatted(filter, limit, &format, cached).await?;
         }
-        Some(Commands::Source { id, format }) => {
+        Some(Commands::Source { id }) => {
+            let format = formats.pick("json", DATA_FORMATS);
             extended_commands::handle_source_formatted(&id, &format).await?;
         }
         Some(Commands::Activities {
             session_id,
             limit,
-            format,
+            sort,
+            desc,
+            since,
+            until,
+            template,
+            output,
         }) => {
-            extended_commands::handle_activities_formatted(&session_id, limit, &format).await?;
+            formats.check_output(output.as_deref())?;
+            let format = formats.pick("json", DATA_FORMATS);
+            let sort = sort
+                .map(|key| ActivitySort::parse(&key).map(|key| SortOrder::new(key, desc)))
+                .transpose()?;
+            let range = TimeRange::parse(since.as_deref(), until.as_deref())?;
+            let options = extended_commands::OutputOptions {
+                format: &format,
+                template: template.as_deref(),
+                output: output.as_deref(),
+            };
+            extended_commands::handle_activities_formatted(
+                &session_id,
+                limit,
+                sort,
+                range,
+                &options,
+            )
+            .await?;
         }
         Some(Commands::Activity {
             session_id,
             activity_id,
-            format,
         }) => {
+            let format = formats.pick("json", DATA_FORMATS);
             extended_commands::handle_activity_formatted(&session_id, &activity_id, &format)
                 .await?;
         }
         Some(Commands::SendMessage {
             session_id,
             message,
+            file,
         }) => {
+            let message =
+                message_from_input(message.as_deref(), file.as_deref(), std::io::stdin())?;
             let args = SendMessageArgs {
                 session_id,
                 message,
+                json: is_json(&formats.pick("table", ACTION_FORMATS))?,
             };
             handle_send_message(args).await?;
         }
+        Some(Commands::Plan { session_id }) => {
+            commands::handle_plan(&session_id).await?;
+        }
         Some(Commands::ApprovePlan { session_id }) => {
-            let args = ApprovePlanArgs { session_id };
+            let args = ApprovePlanArgs {
+                session_id,
+                json: is_json(&formats.pick("table", ACTION_FORMATS))?,
+            };
             handle_approve_plan(args).await?;
         }
+        Some(Commands::Cancel { session_id, yes }) => {
+            let args = CancelArgs { session_id, yes };
+            handle_cancel(args).await?;
+        }
+        Some(Commands::Delete {
+            session_id,
+            state,
+            older_than,
+            dry_run,
+            yes,
+        }) => match session_id {
+            Some(session_id) => commands::handle_delete(&session_id, yes).await?,
+            None => {
+                commands::handle_delete_matching(
+                    state.as_deref(),
+                    older_than.as_deref(),
+                    dry_run,
+                    yes,
+                )
+                .await?
+            }
+        },
         Some(Commands::Config { action }) => match action {
             ConfigCommands::Show => {
                 let args = ConfigShowArgs;
                 handle_config_show(args).await?;
             }
             ConfigCommands::Init => {
-                let args = ConfigInitArgs;
-                handle_config_init(args).await?;
+                commands::handle_init().await?;
             }
             ConfigCommands::Set { key, value } => {
                 let args = ConfigSetArgs { key, value };
                 handle_config_set(args).await?;
             }
+            ConfigCommands::Get { key } => {
+                let args = ConfigGetArgs { key };
+                handle_config_get(args).await?;
+            }
+            ConfigCommands::Unset { key } => {
+                let args = ConfigUnsetArgs { key };
+                handle_config_unset(args).await?;
+            }
+            ConfigCommands::Edit => {
+                let args = ConfigEditArgs;
+                handle_config_edit(args).await?;
+            }
+            ConfigCommands::Schema => {
+                let args = ConfigSchemaArgs;
+                handle_config_schema(args).await?;
+            }
         },
         Some(Commands::IssueStatus { issue, owner, repo }) => {
             extended_commands::handle_issue_status(issue, &owner, &repo).await?;
         }
         Some(Commands::PrStatus { session_id }) => {
-            extended_commands::handle_pr_status(&session_id).await?;
+            extended_commands::handle_pr_status(
+                &session_id,
+                &formats.pick("table", &["table", "json"]),
+            )
+            .await?;
         }
         Some(Commands::Watch {
             session_id,
             interval,
+            timeout,
+            events,
+            webhook,
+            comment_on_issue,
+        }) => {
+            let format = match (events, &formats.flag) {
+                (true, Some(flag)) if !flag.eq_ignore_ascii_case("ndjson") => anyhow::bail!(
+                    "--events streams NDJSON and cannot be combined with --format {}",
+                    flag
+                ),
+                (true, _) => "ndjson".to_string(),
+                (false, _) => formats.pick("table", STREAM_FORMATS),
+            };
+            let timeout = timeout.map(Duration::from_secs);
+            extended_commands::handle_watch(
+                &session_id,
+                interval,
+                timeout,
+                &format,
+                webhook.as_deref(),
+                comment_on_issue,
+            )
+            .await?;
+        }
+        Some(Commands::Resume {
+            session_id,
+            message,
+            interval,
         }) => {
-            extended_commands::handle_watch(&session_id, interval).await?;
+            let format = formats.pick("table", STREAM_FORMATS);
+            extended_commands::handle_resume(&session_id, message.as_deref(), interval, &format)
+                .await?;
         }
-        Some(Commands::Monitor { interval }) => {
-            extended_commands::handle_monitor(interval).await?;
+        Some(Commands::Monitor {
+            interval,
+            state,
+            source,
+            changes_only,
+            tui,
+        }) => {
+            let filter = extended_commands::SessionFilter {
+                state,
+                source,
+                ..Default::default()
+            };
+            if tui {
+                commands::handle_monitor_tui(&filter, interval).await?;
+            } else {
+                let format = formats.pick("table", STREAM_FORMATS);
+                extended_commands::handle_monitor(&filter, interval, changes_only, &format).await?;
+            }
         }
         Some(Commands::FilterActivities {
             session_id,
             last,
             r#type,
             has_bash_output,
+            failed_only,
+            since,
+            until,
             no_cache,
-            format,
+            output,
         }) => {
+            formats.check_output(output.as_deref())?;
+            let format = formats.pick("json", FILTER_FORMATS);
             use commands::filter_activities::*;
 
             // Parse type filters
@@ -450,27 +1576,299 @@ async fn main() -> anyhow::Result<()> {
 
             // Parse output format
             let output_format = OutputFormat::parse(&format)?;
+            let range = TimeRange::parse(since.as_deref(), until.as_deref())?;
 
+            let query = jules_core::ActivityQuery {
+                activity_types: type_filters
+                    .iter()
+                    .map(|f| f.type_name().to_string())
+                    .collect(),
+                since: range.since_rfc3339(),
+                until: range.until_rfc3339(),
+                has_bash_output,
+                failed_only,
+                limit: last,
+                ..Default::default()
+            };
             filter_activities(
                 &session_id,
-                last,
-                type_filters,
-                has_bash_output,
+                query,
                 no_cache,
                 output_format,
+                output.as_deref(),
             )
             .await?;
         }
+        Some(Commands::Logs {
+            session_id,
+            follow,
+            r#type,
+            since,
+            interval,
+        }) => {
+            let types = r#type
+                .iter()
+                .map(|s| commands::filter_activities::ActivityTypeFilter::parse(s))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            commands::handle_logs(&session_id, follow, &types, since.as_deref(), interval).await?;
+        }
+        Some(Commands::Grep {
+            pattern,
+            session,
+            regex,
+            r#type,
+            limit,
+        }) => {
+            let format = formats.pick("table", &["table", "json"]);
+            let kind = r#type
+                .as_deref()
+                .map(jules_core::search_index::ContentKind::parse)
+                .transpose()?;
+            commands::handle_grep(&pattern, session.as_deref(), limit, &format, regex, kind)
+                .await?;
+        }
+        Some(Commands::Diff {
+            session_id,
+            all: _,
+            latest,
+            stat,
+            save,
+        }) => {
+            commands::handle_diff(&session_id, save.as_deref(), latest, stat).await?;
+        }
+        Some(Commands::Checkout { session_id, branch }) => {
+            commands::handle_checkout(&session_id, branch.as_deref()).await?;
+        }
+        Some(Commands::Export { session_id, output }) => {
+            let format = formats.chosen(&["markdown", "md", "html", "json"]);
+            commands::handle_export(&session_id, format.as_deref(), output.as_deref()).await?;
+        }
+        Some(Commands::Report {
+            since,
+            until,
+            source,
+        }) => {
+            let format = formats.pick("table", &["table", "markdown", "json"]);
+            commands::handle_report(&since, &until, source.as_deref(), &format).await?;
+        }
+        Some(Commands::Clone {
+            session_id,
+            source,
+            branch,
+            title,
+        }) => {
+            let format = formats.pick("json", DATA_FORMATS);
+            commands::handle_clone(&session_id, source, branch, title, &format).await?;
+        }
+        Some(Commands::Open { session_id, pr }) => {
+            commands::handle_open(&session_id, pr).await?;
+        }
+        Some(Commands::Init) => {
+            commands::handle_init().await?;
+        }
+        Some(Commands::Man { dir }) => {
+            commands::handle_man(&Cli::command(), dir.as_deref())?;
+        }
+        Some(Commands::Doctor { json }) => {
+            commands::handle_doctor(json).await?;
+        }
+        Some(Commands::Daemon {
+            action,
+            interval,
+            once,
+        }) => match action {
+            None => commands::handle_daemon(interval, once).await?,
+            Some(DaemonCommands::Status) => commands::handle_daemon_status().await?,
+            Some(DaemonCommands::Events { session, limit }) => {
+                commands::handle_daemon_events(session.as_deref(), limit).await?
+            }
+        },
+        Some(Commands::Queue { action }) => match action {
+            QueueCommands::Add {
+                prompt,
+                file,
+                source,
+                title,
+                branch,
+                require_approval,
+                automation_mode,
+                template,
+            } => {
+                let args = CreateArgs {
+                    prompt: prompt.unwrap_or_default(),
+                    source,
+                    title,
+                    branch,
+                    require_approval,
+                    automation_mode,
+                    template,
+                };
+                commands::handle_queue_add(args, file.as_deref())?;
+            }
+            QueueCommands::List => {
+                commands::handle_queue_list(&formats.pick("table", &["table", "json"]))?
+            }
+            QueueCommands::Run {
+                wait,
+                interval,
+                limit,
+            } => commands::handle_queue_run(wait, interval, limit).await?,
+            QueueCommands::Remove { ids } => commands::handle_queue_remove(&ids)?,
+        },
+        Some(Commands::Recent { limit }) => {
+            commands::handle_recent(limit, &formats.pick("table", &["table", "json"]))?;
+        }
+        Some(Commands::Pr { action }) => match action {
+            PrCommands::Checkout { session_id, branch } => {
+                commands::handle_pr_checkout(&session_id, branch.as_deref()).await?
+            }
+            PrCommands::Merge {
+                session_id,
+                squash,
+                when_green,
+                allow_no_checks,
+                interval,
+                timeout,
+            } => {
+                commands::handle_pr_merge(
+                    &session_id,
+                    squash,
+                    when_green,
+                    allow_no_checks,
+                    interval,
+                    timeout.map(Duration::from_secs),
+                )
+                .await?
+            }
+            PrCommands::Feedback {
+                session_id,
+                dry_run,
+                watch,
+                interval,
+            } => {
+                let format = formats.pick("table", STREAM_FORMATS);
+                commands::handle_pr_feedback(&session_id, dry_run, watch, interval, &format).await?
+            }
+            PrCommands::CiWatch {
+                session_id,
+                interval,
+                timeout,
+                max_fixes,
+            } => {
+                commands::handle_pr_ci_watch(
+                    &session_id,
+                    interval,
+                    timeout.map(Duration::from_secs),
+                    max_fixes,
+                )
+                .await?
+            }
+        },
+        Some(Commands::Link {
+            action:
+                LinkCommands::Issue {
+                    issue,
+                    session_id,
+                    owner,
+                    repo,
+                    no_comment,
+                },
+        }) => commands::handle_link_issue(issue, &session_id, owner, repo, !no_comment).await?,
+        Some(Commands::Links {
+            action: LinksCommands::List { session },
+        }) => commands::handle_links_list(
+            session.as_deref(),
+            &formats.pick("table", &["table", "json"]),
+        )?,
+        Some(Commands::Triage {
+            label,
+            owner,
+            repo,
+            max_active,
+            with_comments,
+            no_issue_comment,
+            branch,
+            require_approval,
+            dry_run,
+        }) => {
+            let options = commands::TriageOptions {
+                label,
+                owner,
+                repo,
+                max_active,
+                with_comments,
+                comment: !no_issue_comment,
+                branch,
+                require_approval,
+                dry_run,
+            };
+            commands::handle_triage(options, &formats.pick("table", DATA_FORMATS)).await?
+        }
+        Some(Commands::Blame { pr, commit, cached }) => {
+            commands::handle_blame(
+                pr.as_deref(),
+                commit.as_deref(),
+                cached,
+                &formats.pick("table", &["table", "json"]),
+            )
+            .await?
+        }
+        Some(Commands::Github { action }) => match action {
+            GithubCommands::Status => {
+                commands::handle_github_status(&formats.pick("table", &["table", "json"])).await?
+            }
+            GithubCommands::Login => commands::handle_github_login().await?,
+            GithubCommands::Limits => {
+                commands::handle_github_limits(&formats.pick("table", &["table", "json"])).await?
+            }
+        },
+        Some(Commands::Note {
+            action,
+            session_id,
+            text,
+        }) => match (action, session_id, text) {
+            (Some(NoteCommands::Show { session_id }), _, _) => {
+                commands::handle_note_show(&session_id, &formats.pick("table", &["table", "json"]))?
+            }
+            (Some(NoteCommands::Clear { session_id, yes }), _, _) => {
+                commands::handle_note_clear(&session_id, yes)?
+            }
+            (None, Some(session_id), Some(text)) => commands::handle_note_add(&session_id, &text)?,
+            // clap requires both arguments without a subcommand
+            (None, _, _) => unreachable!("note needs SESSION_ID and TEXT"),
+        },
         Some(Commands::Cache { action }) => match action {
-            CacheCommands::Stats => {
-                commands::handle_cache_stats().await?;
+            CacheCommands::Stats { sort, top, json } => {
+                commands::handle_cache_stats(sort.as_deref(), top, json).await?;
             }
-            CacheCommands::Clear => {
-                commands::handle_cache_clear().await?;
+            CacheCommands::Clear { yes } => {
+                commands::handle_cache_clear(yes).await?;
             }
             CacheCommands::Delete { session_id } => {
                 commands::handle_cache_delete(&session_id).await?;
             }
+            CacheCommands::Verify { repair } => {
+                commands::handle_cache_verify(repair).await?;
+            }
+            CacheCommands::Export { path, sessions } => {
+                commands::handle_cache_export(&path, &sessions).await?;
+            }
+            CacheCommands::Import { path } => {
+                commands::handle_cache_import(&path).await?;
+            }
+            CacheCommands::Warm {
+                state,
+                limit,
+                concurrency,
+            } => {
+                commands::handle_cache_warm(&state, limit, concurrency).await?;
+            }
+            CacheCommands::Pin { session_id } => {
+                commands::handle_cache_pin(&session_id).await?;
+            }
+            CacheCommands::Unpin { session_id } => {
+                commands::handle_cache_unpin(&session_id).await?;
+            }
         },
         None => {
             println!("No command specified. Use --help for usage information.");
@@ -484,13 +1882,13 @@ async fn main() -> anyhow::Result<()> {
 async fn run_mcp_server() -> anyhow::Result<()> {
     #[cfg(feature = "extended-mcp")]
     {
-        // Extended MCP server with SDK + extended tools (11 tools)
+        // Extended MCP server with SDK + extended tools (12 tools)
         mcp::start_extended_mcp_server().await
     }
 
     #[cfg(not(feature = "extended-mcp"))]
     {
-        // Basic MCP server with SDK tools only (9 tools)
+        // Basic MCP server with SDK tools only (10 tools)
         jules_mcp::start_mcp_server().await
     }
 }
@@ -9,6 +9,7 @@
 //! when rmcp supports tool composition/extension.
 
 use anyhow::Result;
+use jules_core::config::SafetyConfig;
 use jules_rs::JulesClient;
 use rmcp::{
     handler::server::{tool::ToolRouter, wrapper::Parameters},
@@ -21,17 +22,20 @@ use rmcp::{
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use super::extended_tools::{IssueStatusArgs, WatchSessionArgs};
+use super::extended_tools::{
+    handle_issue_status, handle_watch_session, IssueStatusArgs, WatchSessionArgs,
+};
 
 // Re-use AppState from jules-mcp
 use jules_mcp::server::AppState;
 
 // Delegate to SDK tool handlers from jules-mcp (no logic duplication)
 use jules_mcp::tools::{
-    handle_approve_plan, handle_create_session, handle_get_activity, handle_get_session,
-    handle_get_source, handle_list_activities, handle_list_sessions, handle_list_sources,
-    handle_send_message, ApprovePlanArgs, CreateSessionArgs, GetActivityArgs, GetSessionArgs,
-    GetSourceArgs, ListActivitiesArgs, ListSessionsArgs, ListSourcesArgs, SendMessageArgs,
+    handle_approve_plan, handle_cancel_session, handle_create_session, handle_get_activity,
+    handle_get_session, handle_get_source, handle_list_activities, handle_list_sessions,
+    handle_list_sources, handle_send_message, ApprovePlanArgs, CancelSessionArgs,
+    CreateSessionArgs, GetActivityArgs, GetSessionArgs, GetSourceArgs, ListActivitiesArgs,
+    ListSessionsArgs, ListSourcesArgs, SendMessageArgs,
 };
 
 #[derive(Clone)]
@@ -42,9 +46,10 @@ pub struct GalesExtendedServer {
 
 #[tool_router]
 impl GalesExtendedServer {
-    pub fn new(client: JulesClient) -> Self {
+    pub fn new(client: JulesClient, safety: SafetyConfig) -> Self {
         let state = AppState {
             client: Arc::new(Mutex::new(client)),
+            safety: Arc::new(safety),
         };
         Self {
             state,
@@ -58,7 +63,7 @@ impl GalesExtendedServer {
         Ok(())
     }
 
-    // === SDK Tools (9 total - delegated to jules-mcp handlers) ===
+    // === SDK Tools (10 total - delegated to jules-mcp handlers) ===
     // NOTE: Tool registration required by rmcp, but handlers reuse jules-mcp logic
 
     #[tool(
@@ -103,6 +108,14 @@ impl GalesExtendedServer {
         handle_approve_plan(&self.state, args).await
     }
 
+    #[tool(description = "Cancel a Jules session that is still running")]
+    async fn cancel_session(
+        &self,
+        Parameters(args): Parameters<CancelSessionArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        handle_cancel_session(&self.state, args).await
+    }
+
     #[tool(description = "List available sources (repositories)")]
     async fn list_sources(
         &self,
@@ -142,70 +155,7 @@ impl GalesExtendedServer {
         &self,
         Parameters(args): Parameters<WatchSessionArgs>,
     ) -> Result<CallToolResult, McpError> {
-        // Inline extended logic
-        let start_time = std::time::Instant::now();
-        let max_duration = std::time::Duration::from_secs(args.max_wait);
-        let mut last_state = String::new();
-
-        loop {
-            if start_time.elapsed() > max_duration {
-                return Ok(CallToolResult::success(vec![Content::text(format!(
-                    "Timeout: Session did not complete within {} seconds",
-                    args.max_wait
-                ))]));
-            }
-
-            let client = self.state.client.lock().await;
-            let session = client
-                .get_session(&args.session_id)
-                .await
-                .map_err(|e| McpError::internal_error(format!("API error: {}", e), None))?;
-
-            if let Some(state_val) = session.state {
-                let state_str = state_val.display_name().to_string();
-                if state_str != last_state {
-                    last_state = state_str;
-                }
-
-                use jules_rs::types::State;
-                if state_val == State::Completed || state_val == State::Failed {
-                    let title = session
-                        .title
-                        .clone()
-                        .unwrap_or_else(|| "No title".to_string());
-                    let url = session.url.clone().unwrap_or_default();
-                    let pr_url = session
-                        .outputs
-                        .iter()
-                        .find_map(|output| output.pull_request.as_ref())
-                        .map(|pr| pr.url.clone())
-                        .unwrap_or_default();
-
-                    let mut result = format!(
-                        "Session {} - Final state: {}\nTitle: {}\nURL: {}",
-                        args.session_id,
-                        state_val.display_name(),
-                        title,
-                        url
-                    );
-
-                    if !pr_url.is_empty() {
-                        result.push_str(&format!("\nPR created: {}", pr_url));
-                    }
-
-                    return Ok(CallToolResult::success(vec![
-                        Content::text(result),
-                        Content::resource(ResourceContents::text(
-                            serde_json::to_string_pretty(&session).unwrap(),
-                            format!("gules://session/{}", args.session_id),
-                        )),
-                    ]));
-                }
-            }
-
-            drop(client);
-            tokio::time::sleep(tokio::time::Duration::from_secs(args.interval)).await;
-        }
+        handle_watch_session(&self.state, args).await
     }
 
     #[tool(description = "Check Jules sessions linked to a GitHub issue")]
@@ -213,21 +163,7 @@ impl GalesExtendedServer {
         &self,
         Parameters(args): Parameters<IssueStatusArgs>,
     ) -> Result<CallToolResult, McpError> {
-        // This tool requires gh CLI integration
-        Ok(CallToolResult::success(vec![Content::text(format!(
-            "Checking issue #{} in {}/{} for Jules sessions...\n\n\
-             Note: The issue-status command requires GitHub CLI (gh) to be installed.\n\
-             \n\
-             To use this feature:\n\
-             1. Install gh CLI: https://cli.github.com\n\
-             2. Run: gules issue-status {} --owner {} --repo {}\n\
-             \n\
-             The CLI version provides full GitHub integration including:\n\
-             - Reading issue comments for Jules session IDs\n\
-             - Fetching session details from Jules API\n\
-             - Displaying PR information if available",
-            args.issue, args.owner, args.repo, args.issue, args.owner, args.repo
-        ))]))
+        handle_issue_status(&self.state, args).await
     }
 }
 
@@ -248,12 +184,13 @@ impl ServerHandler for GalesExtendedServer {
             },
             instructions: Some(
                 "Gules Extended MCP Server - Full-featured MCP for Google's Jules AI coding agent.\n\n\
-                 SDK Tools (9 tools):\n\
+                 SDK Tools (10 tools):\n\
                  - create_session: Create a new coding session\n\
                  - get_session: Get details of a session\n\
                  - list_sessions: List all sessions\n\
                  - send_message: Send a message to a session\n\
                  - approve_plan: Approve a plan in a session\n\
+                 - cancel_session: Cancel a running session\n\
                  - list_sources: List available sources\n\
                  - get_source: Get details of a source\n\
                  - list_activities: List activities in a session\n\
@@ -280,17 +217,13 @@ impl ServerHandler for GalesExtendedServer {
 pub async fn start_extended_mcp_server() -> Result<()> {
     // Load configuration
     let config = jules_core::config::load_config()?;
-    let api_key = config.api_key.ok_or_else(|| {
-        anyhow::anyhow!(
-            "API key not found. Please run 'gules config init' or set JULES_API_KEY environment variable"
-        )
-    })?;
 
     // Create client
-    let client = JulesClient::new(api_key);
+    let client =
+        jules_core::metrics::export_metrics(jules_core::config::api_client(&config)?, &config);
 
     // Create and run the server
-    let server = GalesExtendedServer::new(client);
+    let server = GalesExtendedServer::new(client, config.safety);
     if let Err(e) = server.serve_stdio().await {
         return Err(anyhow::anyhow!("MCP server error: {}", e));
     }
@@ -3,6 +3,7 @@
 //! These tools provide additional functionality beyond the pure SDK,
 //! including session monitoring and GitHub integration.
 
+use jules_core::load_config;
 use jules_mcp::server::AppState;
 use jules_rs::types::State;
 use rmcp::model::*;
@@ -10,6 +11,8 @@ use rmcp::ErrorData as McpError;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::extended_commands::issue_session_ids;
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct WatchSessionArgs {
     /// Session ID to watch
@@ -81,7 +84,7 @@ pub async fn handle_watch_session(
                     .outputs
                     .iter()
                     .find_map(|output| output.pull_request.as_ref())
-                    .map(|pr| pr.url.clone())
+                    .and_then(|pr| pr.url.clone())
                     .unwrap_or_default();
 
                 let mut result = format!(
@@ -112,23 +115,70 @@ pub async fn handle_watch_session(
 }
 
 /// Handler for issue_status tool (extended feature)
+///
+/// Reads the issue's comments from GitHub (token or gh CLI) and reports the
+/// Jules sessions they reference.
 pub async fn handle_issue_status(
-    _state: &AppState,
+    state: &AppState,
     args: IssueStatusArgs,
 ) -> Result<CallToolResult, McpError> {
-    // This tool requires gh CLI integration
-    Ok(CallToolResult::success(vec![Content::text(format!(
-        "Checking issue #{} in {}/{} for Jules sessions...\n\n\
-         Note: The issue-status command requires GitHub CLI (gh) to be installed.\n\
-         \n\
-         To use this feature:\n\
-         1. Install gh CLI: https://cli.github.com\n\
-         2. Run: gules issue-status {} --owner {} --repo {}\n\
-         \n\
-         The CLI version provides full GitHub integration including:\n\
-         - Reading issue comments for Jules session IDs\n\
-         - Fetching session details from Jules API\n\
-         - Displaying PR information if available",
-        args.issue, args.owner, args.repo, args.issue, args.owner, args.repo
-    ))]))
+    let tool_error = |e: anyhow::Error| McpError::internal_error(format!("{:#}", e), None);
+    let config = load_config().map_err(tool_error)?;
+    let session_ids =
+        issue_session_ids(&config, &args.owner, &args.repo, args.issue, "issue_status")
+            .await
+            .map_err(tool_error)?;
+    if session_ids.is_empty() {
+        return Ok(CallToolResult::success(vec![Content::text(format!(
+            "No Jules sessions linked to or mentioned in {}/{}#{}",
+            args.owner, args.repo, args.issue
+        ))]));
+    }
+
+    let client_guard = state.client.lock().await;
+    let mut result = format!(
+        "Found {} Jules session(s) for {}/{}#{}:",
+        session_ids.len(),
+        args.owner,
+        args.repo,
+        args.issue
+    );
+    let mut sessions = Vec::new();
+    for session_id in session_ids {
+        match client_guard.get_session(&session_id).await {
+            Ok(session) => {
+                let state = session.state.unwrap_or(State::StateUnspecified);
+                result.push_str(&format!(
+                    "\n\nSession {} - {}\nTitle: {}",
+                    session.id,
+                    state.display_name(),
+                    session.title.as_deref().unwrap_or("No title")
+                ));
+                for pr in session
+                    .outputs
+                    .iter()
+                    .filter_map(|output| output.pull_request.as_ref())
+                {
+                    if let Some(url) = &pr.url {
+                        result.push_str(&format!("\nPR: {}", url));
+                    }
+                }
+                sessions.push(session);
+            }
+            Err(e) => {
+                result.push_str(&format!(
+                    "\n\nFailed to fetch session {}: {}",
+                    session_id, e
+                ));
+            }
+        }
+    }
+
+    Ok(CallToolResult::success(vec![
+        Content::text(result),
+        Content::resource(ResourceContents::text(
+            serde_json::to_string_pretty(&sessions).unwrap(),
+            format!("gules://issue/{}/{}/{}", args.owner, args.repo, args.issue),
+        )),
+    ]))
 }
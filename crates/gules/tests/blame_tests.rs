@@ -0,0 +1,141 @@
+//! Tests for tracing pull requests and commits back to sessions with
+//! `gules blame`.
+
+mod common;
+
+use gules::commands::{blame_commit, blame_pull_request, CommitInfo};
+use jules_core::github_url::GitHubItemUrl;
+use jules_core::issue_links::IssueLinks;
+use jules_core::patch_files::PatchFile;
+use jules_core::session_refs::SessionPatterns;
+use jules_rs::types::session::Session;
+
+fn session(id: &str, pr_url: Option<&str>) -> Session {
+    let outputs: Vec<_> = pr_url
+        .map(|url| serde_json::json!({"pullRequest": {"url": url}}))
+        .into_iter()
+        .collect();
+    common::session(
+        id,
+        serde_json::json!({
+            "prompt": "p",
+            "title": format!("Session {}", id),
+            "outputs": outputs,
+        }),
+    )
+}
+
+fn patch(file_name: &str, base: &str, message: &str) -> PatchFile {
+    PatchFile {
+        file_name: file_name.to_string(),
+        activity_id: "a1".to_string(),
+        base_commit_id: Some(base.to_string()),
+        suggested_commit_message: Some(message.to_string()),
+        patch: String::new(),
+    }
+}
+
+#[test]
+fn test_blame_pull_request() {
+    let pr = GitHubItemUrl::parse_pull_request("https://github.com/octo/app/pull/7").unwrap();
+    let sessions = vec![
+        session("1", Some("https://github.com/Octo/App/pull/7/files")),
+        session("2", Some("https://github.com/octo/app/pull/8")),
+        session("3", None),
+    ];
+    let mut links = IssueLinks::default();
+    links.link("octo", "app", 7, "3").unwrap();
+    links.link("octo", "app", 7, "1").unwrap();
+
+    let matches = blame_pull_request(&pr, &sessions, &links);
+    let found: Vec<(&str, &[String])> = matches
+        .iter()
+        .map(|m| (m.session_id.as_str(), m.reasons.as_slice()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (
+                "1",
+                &[
+                    "created the pull request".to_string(),
+                    "linked to octo/app#7 locally".to_string()
+                ][..]
+            ),
+            ("3", &["linked to octo/app#7 locally".to_string()][..]),
+        ]
+    );
+    assert_eq!(matches[1].title.as_deref(), Some("Session 3"));
+}
+
+#[test]
+fn test_blame_commit_by_patches_and_message() {
+    let parent = "1111111111111111111111111111111111111111";
+    let commit = CommitInfo {
+        sha: "2222222222222222222222222222222222222222".to_string(),
+        parents: vec![parent.to_string()],
+        message: Some("Fix the login bug\n\nSee sessions/9\n".to_string()),
+    };
+    let patches = vec![
+        (
+            "6".to_string(),
+            vec![patch("001-b1.patch", "1111111", "Something else")],
+        ),
+        (
+            "5".to_string(),
+            vec![patch("001-a1.patch", parent, "Fix the login bug")],
+        ),
+        // Made after the commit, on top of it
+        (
+            "8".to_string(),
+            vec![patch("001-d1.patch", "2222222", "Later work")],
+        ),
+        (
+            "7".to_string(),
+            vec![patch("001-c1.patch", "3333333", "Unrelated")],
+        ),
+    ];
+
+    let matches = blame_commit(&commit, &patches, &SessionPatterns::default());
+    let found: Vec<(&str, Vec<&str>, bool)> = matches
+        .iter()
+        .map(|m| {
+            (
+                m.session_id.as_str(),
+                m.reasons.iter().map(String::as_str).collect(),
+                m.weak,
+            )
+        })
+        .collect();
+    // Sessions only started from the parent come last, as weak matches
+    assert_eq!(
+        found,
+        vec![
+            ("9", vec!["mentioned in the commit message"], false),
+            (
+                "5",
+                vec![
+                    "the commit message is the one suggested with 001-a1.patch",
+                    "001-a1.patch was made on top of the commit's parent",
+                ],
+                false
+            ),
+            (
+                "6",
+                vec!["001-b1.patch was made on top of the commit's parent"],
+                true
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_blame_commit_needs_long_enough_shas() {
+    let commit = CommitInfo {
+        sha: "2222222".to_string(),
+        parents: vec!["111".to_string()],
+        message: None,
+    };
+    let patches = vec![("6".to_string(), vec![patch("001-b1.patch", "1111111", "m")])];
+    assert!(blame_commit(&commit, &patches, &SessionPatterns::default()).is_empty());
+}
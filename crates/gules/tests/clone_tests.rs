@@ -0,0 +1,63 @@
+//! Tests for building the request of `gules clone`.
+
+mod common;
+
+use gules::commands::clone_request;
+use jules_rs::types::session::Session;
+use serde_json::json;
+
+fn session() -> Session {
+    common::session(
+        "123",
+        json!({
+            "title": "Fix retries",
+            "prompt": "Retry failed uploads",
+            "sourceContext": {
+                "source": "sources/github/octo/api",
+                "githubRepoContext": { "startingBranch": "develop" }
+            },
+            "requirePlanApproval": true,
+        }),
+    )
+}
+
+fn branch(request: &jules_rs::types::session::CreateSessionRequest) -> Option<&str> {
+    request
+        .source_context
+        .github_repo_context
+        .as_ref()
+        .map(|context| context.starting_branch.as_str())
+}
+
+#[test]
+fn test_clone_keeps_prompt_and_settings() {
+    let request = clone_request(&session(), None, None, None);
+    assert_eq!(request.prompt, "Retry failed uploads");
+    assert_eq!(request.source_context.source, "sources/github/octo/api");
+    assert_eq!(branch(&request), Some("develop"));
+    assert_eq!(request.title.as_deref(), Some("Fix retries"));
+    assert_eq!(request.require_plan_approval, Some(true));
+}
+
+#[test]
+fn test_clone_to_another_branch() {
+    let request = clone_request(&session(), None, Some("release".to_string()), None);
+    assert_eq!(branch(&request), Some("release"));
+}
+
+#[test]
+fn test_clone_to_another_source_drops_the_branch() {
+    let other = Some("sources/github/octo/worker".to_string());
+    let request = clone_request(&session(), other.clone(), None, None);
+    assert_eq!(request.source_context.source, "sources/github/octo/worker");
+    assert_eq!(branch(&request), None);
+
+    let request = clone_request(
+        &session(),
+        other,
+        Some("main".to_string()),
+        Some("Port".into()),
+    );
+    assert_eq!(branch(&request), Some("main"));
+    assert_eq!(request.title.as_deref(), Some("Port"));
+}
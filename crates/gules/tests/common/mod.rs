@@ -0,0 +1,19 @@
+//! Fixtures shared by the integration tests.
+
+use jules_rs::types::session::Session;
+use serde_json::{json, Value};
+
+/// A session with `fields` (API JSON, e.g. `{"state": "COMPLETED"}`) set
+/// over a minimal one: prompt "Fix the bug" on `sources/github/octo/app`
+pub fn session(id: &str, fields: Value) -> Session {
+    let mut value = json!({
+        "name": format!("sessions/{}", id),
+        "id": id,
+        "prompt": "Fix the bug",
+        "sourceContext": { "source": "sources/github/octo/app" },
+    });
+    if let (Some(base), Value::Object(fields)) = (value.as_object_mut(), fields) {
+        base.extend(fields);
+    }
+    serde_json::from_value(value).unwrap()
+}
@@ -0,0 +1,164 @@
+//! Tests for the `gules monitor --tui` dashboard state.
+
+mod common;
+
+use gules::commands::{activity_lines, Dashboard, DashboardAction, DashboardView};
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use ratatui::crossterm::event::KeyCode;
+use serde_json::json;
+
+fn session(id: &str, state: &str) -> Session {
+    common::session(
+        id,
+        json!({
+            "state": state,
+            "prompt": "Fix retries",
+            "sourceContext": { "source": "sources/github/octo/api" },
+        }),
+    )
+}
+
+fn dashboard() -> Dashboard {
+    let mut dashboard = Dashboard::default();
+    dashboard.set_sessions(vec![
+        session("1", "IN_PROGRESS"),
+        session("2", "AWAITING_PLAN_APPROVAL"),
+        session("3", "COMPLETED"),
+    ]);
+    dashboard
+}
+
+#[test]
+fn test_navigation_stays_in_bounds() {
+    let mut dashboard = dashboard();
+    assert_eq!(dashboard.handle_key(KeyCode::Up), None);
+    assert_eq!(dashboard.selected, 0);
+
+    for _ in 0..5 {
+        dashboard.handle_key(KeyCode::Char('j'));
+    }
+    assert_eq!(dashboard.selected_session().unwrap().id, "3");
+}
+
+#[test]
+fn test_selection_follows_the_session_across_polls() {
+    let mut dashboard = dashboard();
+    dashboard.handle_key(KeyCode::Down);
+    assert_eq!(dashboard.selected_session().unwrap().id, "2");
+
+    // A new session listed first doesn't move the selection
+    dashboard.set_sessions(vec![
+        session("4", "QUEUED"),
+        session("1", "IN_PROGRESS"),
+        session("2", "AWAITING_PLAN_APPROVAL"),
+    ]);
+    assert_eq!(dashboard.selected_session().unwrap().id, "2");
+
+    // Nor does it fall off the end when the selected session goes away
+    dashboard.set_sessions(vec![session("4", "QUEUED")]);
+    assert_eq!(dashboard.selected, 0);
+    dashboard.set_sessions(Vec::new());
+    assert_eq!(dashboard.selected_session().map(|s| s.id.as_str()), None);
+    assert_eq!(dashboard.handle_key(KeyCode::Enter), None);
+}
+
+#[test]
+fn test_approve_only_plans_awaiting_approval() {
+    let mut dashboard = dashboard();
+    assert_eq!(dashboard.handle_key(KeyCode::Char('a')), None);
+    assert!(dashboard.status.contains("no plan awaiting approval"));
+
+    dashboard.handle_key(KeyCode::Down);
+    assert_eq!(
+        dashboard.handle_key(KeyCode::Char('a')),
+        Some(DashboardAction::Approve("2".to_string()))
+    );
+}
+
+#[test]
+fn test_message_input() {
+    let mut dashboard = dashboard();
+    assert_eq!(dashboard.handle_key(KeyCode::Char('m')), None);
+    for c in "go on!".chars() {
+        // Keys type into the message instead of acting, even `q`
+        assert_eq!(dashboard.handle_key(KeyCode::Char(c)), None);
+    }
+    dashboard.handle_key(KeyCode::Backspace);
+    assert_eq!(
+        dashboard.handle_key(KeyCode::Enter),
+        Some(DashboardAction::SendMessage(
+            "1".to_string(),
+            "go on".to_string()
+        ))
+    );
+    assert_eq!(dashboard.input, None);
+
+    // Esc and empty messages send nothing
+    dashboard.handle_key(KeyCode::Char('m'));
+    dashboard.handle_key(KeyCode::Char('x'));
+    assert_eq!(dashboard.handle_key(KeyCode::Esc), None);
+    assert_eq!(dashboard.input, None);
+    dashboard.handle_key(KeyCode::Char('m'));
+    assert_eq!(dashboard.handle_key(KeyCode::Enter), None);
+}
+
+#[test]
+fn test_detail_views() {
+    let mut dashboard = dashboard();
+    assert_eq!(
+        dashboard.handle_key(KeyCode::Enter),
+        Some(DashboardAction::ShowActivities("1".to_string()))
+    );
+
+    dashboard.show_detail(DashboardView::Activities, vec!["a".into(), "b".into()]);
+    for _ in 0..3 {
+        dashboard.handle_key(KeyCode::Down);
+    }
+    assert_eq!(dashboard.scroll, 1);
+    assert_eq!(
+        dashboard.handle_key(KeyCode::Char('d')),
+        Some(DashboardAction::ShowDiff("1".to_string()))
+    );
+    assert_eq!(
+        dashboard.handle_key(KeyCode::Char('o')),
+        Some(DashboardAction::Open("1".to_string()))
+    );
+
+    // q goes back from a detail view, and quits from the list
+    assert_eq!(dashboard.handle_key(KeyCode::Char('q')), None);
+    assert_eq!(dashboard.view, DashboardView::List);
+    assert_eq!(
+        dashboard.handle_key(KeyCode::Char('q')),
+        Some(DashboardAction::Quit)
+    );
+}
+
+#[test]
+fn test_activity_lines_are_oldest_first() {
+    let activities: Vec<Activity> = serde_json::from_value(json!([
+        {
+            "name": "sessions/1/activities/b",
+            "id": "b",
+            "createTime": "2025-01-03T13:00:00Z",
+            "originator": "agent",
+            "agentMessaged": { "agentMessage": "Done.\nAll tests pass." }
+        },
+        {
+            "name": "sessions/1/activities/a",
+            "id": "a",
+            "createTime": "2025-01-03T10:00:00Z",
+            "originator": "user",
+            "userMessaged": { "userMessage": "Fix it" }
+        }
+    ]))
+    .unwrap();
+
+    let lines = activity_lines(&activities);
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].ends_with("User Messaged"));
+    assert_eq!(lines[1], "    Fix it");
+    assert!(lines[2].ends_with("Agent Messaged"));
+    assert_eq!(lines[4], "    All tests pass.");
+    assert_eq!(activity_lines(&[]), vec!["No activities yet."]);
+}
@@ -0,0 +1,27 @@
+//! Tests for the report formats of `gules export`.
+
+use gules::commands::ExportFormat;
+
+#[test]
+fn test_parse_format() {
+    assert_eq!(ExportFormat::parse("md").unwrap(), ExportFormat::Markdown);
+    assert_eq!(
+        ExportFormat::parse("Markdown").unwrap(),
+        ExportFormat::Markdown
+    );
+    assert_eq!(ExportFormat::parse("html").unwrap(), ExportFormat::Html);
+    assert_eq!(ExportFormat::parse("JSON").unwrap(), ExportFormat::Json);
+    assert!(ExportFormat::parse("pdf").is_err());
+}
+
+#[test]
+fn test_format_from_path() {
+    assert_eq!(ExportFormat::from_path("report.html"), ExportFormat::Html);
+    assert_eq!(
+        ExportFormat::from_path("out/report.HTM"),
+        ExportFormat::Html
+    );
+    assert_eq!(ExportFormat::from_path("session.json"), ExportFormat::Json);
+    assert_eq!(ExportFormat::from_path("report.md"), ExportFormat::Markdown);
+    assert_eq!(ExportFormat::from_path("report"), ExportFormat::Markdown);
+}
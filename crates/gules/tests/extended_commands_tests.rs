@@ -1,6 +1,11 @@
 //! Integration tests for extended commands (watch, monitor, issue-status, pr-status)
 
-use gules::extended_commands::OutputFormat;
+mod common;
+
+use gules::extended_commands::{MonitorTracker, OutputFormat, SessionChange, SessionFilter};
+use jules_core::time_range::TimeRange;
+use jules_rs::types::session::Session;
+use serde_json::json;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -153,7 +158,7 @@ fn test_monitor_initialization() {
 #[test]
 fn test_monitor_session_collection() {
     // Test that monitor can track multiple sessions
-    let sessions = vec![
+    let sessions = [
         MockSession::new("session-1"),
         MockSession::new("session-2"),
         MockSession::new("session-3"),
@@ -169,7 +174,7 @@ fn test_monitor_session_collection() {
 #[test]
 fn test_monitor_state_distribution() {
     // Test that monitor correctly tracks different session states
-    let sessions = vec![
+    let sessions = [
         MockSession::new("s1"),
         MockSession::new("s2").completed(),
         MockSession::new("s3"),
@@ -514,3 +519,166 @@ fn test_github_comment_parsing_edge_cases() {
         );
     }
 }
+
+// ─────────────────────────────────────────────────────────────────────────
+// SESSION FILTER TESTS
+// ─────────────────────────────────────────────────────────────────────────
+
+fn session(state: Option<&str>, title: Option<&str>, prompt: &str) -> Session {
+    common::session(
+        "1",
+        json!({
+            "state": state,
+            "title": title,
+            "prompt": prompt,
+            "sourceContext": { "source": "sources/github/octo/api" },
+            "createTime": "2025-01-15T10:00:00Z",
+        }),
+    )
+}
+
+fn filter(state: Option<&str>, search: Option<&str>) -> SessionFilter {
+    SessionFilter {
+        state: state.map(str::to_string),
+        search: search.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_session_filter_state() {
+    let running = session(Some("IN_PROGRESS"), None, "Fix retries");
+    let done = session(Some("COMPLETED"), None, "Fix retries");
+
+    assert!(filter(Some("active"), None).matches(&running));
+    assert!(!filter(Some("active"), None).matches(&done));
+    assert!(SessionFilter::state("completed", None).matches(&done));
+    // Sessions without a state pass any state filter
+    assert!(filter(Some("failed"), None).matches(&session(None, None, "x")));
+}
+
+#[test]
+fn test_session_filter_search_title_or_prompt() {
+    let titled = session(Some("COMPLETED"), Some("Upload Retries"), "Fix the client");
+
+    assert!(filter(None, Some("retries")).matches(&titled));
+    assert!(filter(None, Some("CLIENT")).matches(&titled));
+    assert!(!filter(None, Some("auth")).matches(&titled));
+    assert!(!filter(Some("failed"), Some("retries")).matches(&titled));
+    assert!(filter(None, None).matches(&titled));
+}
+
+#[test]
+fn test_session_filter_source_and_created() {
+    let session = session(Some("COMPLETED"), None, "Fix retries");
+    let by_source = |source: &str| SessionFilter {
+        source: Some(source.to_string()),
+        ..Default::default()
+    };
+    assert!(by_source("sources/github/octo/api").matches(&session));
+    assert!(by_source("octo/api").matches(&session));
+    assert!(!by_source("octo/web").matches(&session));
+
+    let created = |since: Option<&str>, until: Option<&str>| SessionFilter {
+        created: TimeRange::parse(since, until).unwrap(),
+        ..Default::default()
+    };
+    assert!(created(Some("2025-01-15"), None).matches(&session));
+    assert!(!created(Some("2025-01-16"), None).matches(&session));
+    // --until is exclusive
+    assert!(!created(None, Some("2025-01-15T10:00:00Z")).matches(&session));
+    assert!(created(Some("2025-01-01"), Some("2025-02-01")).matches(&session));
+}
+
+#[test]
+fn test_session_filter_is_empty() {
+    assert!(SessionFilter::default().is_empty());
+    assert!(!filter(None, Some("retries")).is_empty());
+    assert!(!SessionFilter {
+        created: TimeRange::parse(Some("2025-01-15"), None).unwrap(),
+        ..Default::default()
+    }
+    .is_empty());
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// MONITOR CHANGE TESTS
+// ─────────────────────────────────────────────────────────────────────────
+
+fn monitored(id: &str, state: &str) -> Session {
+    serde_json::from_value(json!({
+        "name": format!("sessions/{}", id),
+        "id": id,
+        "state": state,
+        "prompt": "Fix retries",
+        "sourceContext": { "source": "sources/github/octo/api" },
+    }))
+    .unwrap()
+}
+
+/// Changes as (session ID, previous state or "new", current state)
+fn describe(changes: &[SessionChange]) -> Vec<(String, String, String)> {
+    changes
+        .iter()
+        .map(|change| match change {
+            SessionChange::New(session) => {
+                (session.id.clone(), "new".to_string(), format!("{:?}", session.state))
+            }
+            SessionChange::State(from, session) => (
+                session.id.clone(),
+                format!("{:?}", from),
+                format!("{:?}", session.state),
+            ),
+        })
+        .collect()
+}
+
+#[test]
+fn test_monitor_tracker_reports_new_and_changed_sessions() {
+    let filter = SessionFilter::default();
+    let mut tracker = MonitorTracker::default();
+
+    let first = [monitored("1", "IN_PROGRESS"), monitored("2", "QUEUED")];
+    assert_eq!(tracker.update(&filter, &first).len(), 2);
+    // Nothing changed
+    assert!(tracker.update(&filter, &first).is_empty());
+
+    let second = [
+        monitored("1", "COMPLETED"),
+        monitored("2", "QUEUED"),
+        monitored("3", "PLANNING"),
+    ];
+    assert_eq!(
+        describe(&tracker.update(&filter, &second)),
+        vec![
+            (
+                "1".to_string(),
+                "Some(InProgress)".to_string(),
+                "Some(Completed)".to_string()
+            ),
+            (
+                "3".to_string(),
+                "new".to_string(),
+                "Some(Planning)".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_monitor_tracker_reports_sessions_leaving_the_filter_once() {
+    let active = SessionFilter::state("active", None);
+    let mut tracker = MonitorTracker::default();
+
+    let first = [monitored("1", "IN_PROGRESS"), monitored("2", "COMPLETED")];
+    assert_eq!(describe(&tracker.update(&active, &first)).len(), 1);
+
+    // The active session completing is still reported...
+    let second = [monitored("1", "COMPLETED"), monitored("2", "COMPLETED")];
+    let changes = describe(&tracker.update(&active, &second));
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].2, "Some(Completed)");
+
+    // ...but only once, and sessions that never matched never are
+    assert!(tracker.update(&active, &second).is_empty());
+}
@@ -292,7 +292,7 @@ fn test_multiple_type_filters() {
     });
 
     // Create filters
-    let filters = vec![
+    let filters = [
         ActivityTypeFilter::AgentMessage,
         ActivityTypeFilter::UserMessage,
     ];
@@ -331,7 +331,7 @@ fn test_bash_output_filtering_logic() {
     let activity_no_artifacts = create_test_activity("3");
 
     // Filter logic: has_bash_output = true
-    let activities = vec![
+    let activities = [
         activity_with_bash.clone(),
         activity_without_bash.clone(),
         activity_no_artifacts.clone(),
@@ -352,7 +352,7 @@ fn test_bash_output_filtering_logic() {
 
 #[test]
 fn test_last_n_filtering() {
-    let activities = vec![
+    let activities = [
         create_test_activity("1"),
         create_test_activity("2"),
         create_test_activity("3"),
@@ -373,7 +373,8 @@ fn test_last_n_filtering() {
     assert_eq!(last_1[0].id, "1");
 
     // Take more than available
-    let all: Vec<_> = activities.iter().take(10).collect();
+    let requested = activities.len() + 5;
+    let all: Vec<_> = activities.iter().take(requested).collect();
     assert_eq!(all.len(), 5);
 }
 
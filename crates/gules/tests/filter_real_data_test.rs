@@ -32,7 +32,7 @@ fn test_filter_real_activities_json() {
 
     // Count total activities
     println!("Total activities: {}", activities.len());
-    assert!(activities.len() > 0, "Expected activities in sample file");
+    assert!(!activities.is_empty(), "Expected activities in sample file");
     println!("Found {} activities in sample file", activities.len());
 
     // Count by type
@@ -95,7 +95,7 @@ fn test_filter_real_activities_json() {
 
     println!("\nProgress activities: {}", filtered_progress.len());
     assert!(
-        filtered_progress.len() > 0,
+        !filtered_progress.is_empty(),
         "Expected some progress activities"
     );
 
@@ -125,7 +125,7 @@ fn test_filter_real_activities_json() {
 
     // At least the first activity should have bash output
     assert!(
-        with_bash.len() >= 1,
+        !with_bash.is_empty(),
         "Expected at least 1 activity with bash output"
     );
 
@@ -178,7 +178,7 @@ fn test_filter_real_activities_json() {
         progress_with_bash.len()
     );
     assert!(
-        progress_with_bash.len() >= 1,
+        !progress_with_bash.is_empty(),
         "Expected at least 1 progress activity with bash output"
     );
 }
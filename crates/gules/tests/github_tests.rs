@@ -0,0 +1,550 @@
+//! Tests for GitHub token checks, issue prompts, pull request status, and
+//! review and CI feedback.
+
+use gules::github::{
+    ci_failure_message, latest_reviews, log_excerpt, parse_linked_pull_requests, review_feedback,
+    session_link_comment, session_outcome_comment, source_repo, unresolved_review_threads,
+    AppInstallationInfo, Check, GitHubClient, GitHubIssue, IssueComment, PullRequestStatus, Review,
+    ReviewThread, TokenInfo, TokenSource,
+};
+use jules_core::github_url::GitHubItemUrl;
+use jules_core::webhook::WebhookPayload;
+use jules_core::Config;
+use jules_rs::State;
+
+fn info(scopes: Option<&str>) -> TokenInfo {
+    TokenInfo {
+        login: "octocat".to_string(),
+        scopes: TokenInfo::parse_scopes(scopes),
+    }
+}
+
+#[test]
+fn test_parse_scopes() {
+    assert_eq!(
+        TokenInfo::parse_scopes(Some("repo, read:org,workflow")),
+        Some(vec![
+            "repo".to_string(),
+            "read:org".to_string(),
+            "workflow".to_string()
+        ])
+    );
+    assert_eq!(TokenInfo::parse_scopes(Some("")), Some(vec![]));
+    // Fine-grained tokens don't report scopes
+    assert_eq!(TokenInfo::parse_scopes(None), None);
+}
+
+#[test]
+fn test_repo_scopes_are_enough() {
+    assert_eq!(info(Some("repo, workflow")).scope_problem(), None);
+    assert_eq!(info(Some("public_repo")).scope_problem(), None);
+    assert_eq!(info(None).scope_problem(), None);
+}
+
+#[test]
+fn test_missing_repo_scope() {
+    let problem = info(Some("read:org, gist")).scope_problem().unwrap();
+    assert!(problem.contains("no `repo` scope"), "{}", problem);
+    assert!(problem.contains("read:org, gist"), "{}", problem);
+
+    let problem = info(Some("")).scope_problem().unwrap();
+    assert!(problem.contains("it has: none"), "{}", problem);
+}
+
+#[test]
+fn test_token_source_display() {
+    assert_eq!(TokenSource::Env("GITHUB_TOKEN").to_string(), "GITHUB_TOKEN");
+    assert_eq!(TokenSource::Config.to_string(), "the github_token setting");
+    assert_eq!(TokenSource::App.to_string(), "the GitHub App installation");
+}
+
+#[test]
+fn test_app_installation_permissions() {
+    let info = AppInstallationInfo::from_json(&serde_json::json!({
+        "account": {"login": "acme"},
+        "repository_selection": "all",
+        "permissions": {"pull_requests": "write", "issues": "write", "metadata": "read"},
+    }));
+    assert_eq!(info.account, "acme");
+    assert_eq!(info.repository_selection.as_deref(), Some("all"));
+    assert_eq!(
+        info.permissions,
+        vec![
+            ("issues".to_string(), "write".to_string()),
+            ("metadata".to_string(), "read".to_string()),
+            ("pull_requests".to_string(), "write".to_string()),
+        ]
+    );
+    assert_eq!(info.permission_problem(), None);
+
+    let info = AppInstallationInfo::from_json(&serde_json::json!({
+        "account": {"login": "acme"},
+        "permissions": {"metadata": "read"},
+    }));
+    let problem = info.permission_problem().unwrap();
+    assert!(
+        problem.contains("no access to issues or pull requests"),
+        "{}",
+        problem
+    );
+}
+
+#[test]
+fn test_incomplete_app_settings() {
+    let mut config = Config::default();
+    config.github.app_id = Some(1);
+    let error = GitHubClient::detect(&config).err().unwrap().to_string();
+    assert_eq!(
+        error,
+        "The GitHub App settings are incomplete: set github.app_installation_id and \
+         github.app_private_key_path"
+    );
+}
+
+#[test]
+fn test_invalid_app_private_key() {
+    let path = std::env::temp_dir().join(format!("gules-app-key-{}.pem", std::process::id()));
+    std::fs::write(&path, "not a key").unwrap();
+    let mut config = Config::default();
+    config.github.app_id = Some(1);
+    config.github.app_installation_id = Some(2);
+    config.github.app_private_key_path = Some(path.display().to_string());
+    let error = GitHubClient::detect(&config).err().unwrap().to_string();
+    std::fs::remove_file(&path).unwrap();
+    assert!(
+        error.contains("is not a PEM-encoded RSA private key"),
+        "{}",
+        error
+    );
+
+    config.github.app_private_key_path = Some("/nonexistent/key.pem".to_string());
+    let error = GitHubClient::detect(&config).err().unwrap().to_string();
+    assert!(
+        error.contains("Failed to read the GitHub App private key"),
+        "{}",
+        error
+    );
+}
+
+fn check(name: &str, conclusion: Option<&str>) -> Check {
+    Check {
+        name: name.to_string(),
+        conclusion: conclusion.map(str::to_string),
+        details_url: None,
+    }
+}
+
+fn review(reviewer: &str, state: &str) -> Review {
+    Review {
+        reviewer: reviewer.to_string(),
+        state: state.to_string(),
+    }
+}
+
+#[test]
+fn test_checks_state() {
+    let mut status = PullRequestStatus::default();
+    assert_eq!(status.checks_state(), "none");
+
+    status.checks = vec![
+        check("build", Some("success")),
+        check("docs", Some("skipped")),
+    ];
+    assert_eq!(status.checks_state(), "passing");
+
+    status.checks.push(check("test", None));
+    assert_eq!(status.checks_state(), "pending");
+
+    status.checks.push(check("lint", Some("timed_out")));
+    assert_eq!(status.checks_state(), "failing");
+}
+
+#[test]
+fn test_latest_reviews() {
+    let reviews = latest_reviews(vec![
+        review("alice", "CHANGES_REQUESTED"),
+        review("bob", "COMMENTED"),
+        review("alice", "APPROVED"),
+        review("alice", "COMMENTED"),
+        review("bob", "APPROVED"),
+        review("carol", "COMMENTED"),
+    ]);
+    assert_eq!(
+        reviews,
+        vec![
+            review("alice", "APPROVED"),
+            review("bob", "APPROVED"),
+            review("carol", "COMMENTED"),
+        ]
+    );
+
+    // A dismissal replaces the verdict
+    let reviews = latest_reviews(vec![
+        review("alice", "CHANGES_REQUESTED"),
+        review("alice", "DISMISSED"),
+    ]);
+    assert_eq!(reviews, vec![review("alice", "DISMISSED")]);
+}
+
+#[test]
+fn test_review_state() {
+    let mut status = PullRequestStatus::default();
+    assert_eq!(status.review_state(), "none");
+
+    status.reviews = vec![review("bob", "COMMENTED")];
+    assert_eq!(status.review_state(), "none");
+
+    status.reviews.push(review("alice", "APPROVED"));
+    assert_eq!(status.review_state(), "approved");
+
+    status.reviews.push(review("carol", "CHANGES_REQUESTED"));
+    assert_eq!(status.review_state(), "changes_requested");
+}
+
+#[test]
+fn test_merge_blockers() {
+    let mut status = PullRequestStatus {
+        state: Some("OPEN".to_string()),
+        mergeable: Some(true),
+        checks: vec![check("build", Some("success")), check("test", None)],
+        reviews: vec![review("alice", "APPROVED")],
+        ..Default::default()
+    };
+    // Running checks are left to the caller
+    assert!(status.merge_blockers().is_empty());
+
+    status.draft = true;
+    status.mergeable = Some(false);
+    status.checks.push(check("lint", Some("failure")));
+    status.reviews.push(review("bob", "CHANGES_REQUESTED"));
+    assert_eq!(
+        status.merge_blockers(),
+        vec![
+            "it is a draft",
+            "it has merge conflicts",
+            "checks failed: lint",
+            "changes requested by bob",
+        ]
+    );
+
+    let merged = PullRequestStatus {
+        state: Some("MERGED".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(merged.merge_blockers(), vec!["it is merged"]);
+}
+
+fn issue() -> GitHubIssue {
+    GitHubIssue {
+        owner: "kiwina".to_string(),
+        repo: "gules".to_string(),
+        number: 12,
+        title: "Crash on empty config".to_string(),
+        body: Some("Steps:\n1. Empty the config\n".to_string()),
+        comments: vec![],
+    }
+}
+
+#[test]
+fn test_issue_prompt() {
+    assert_eq!(
+        issue().prompt(None),
+        "Resolve GitHub issue #12 in kiwina/gules: Crash on empty config\n\
+         \n\
+         Steps:\n\
+         1. Empty the config\n"
+    );
+
+    let issue = GitHubIssue {
+        body: None,
+        ..issue()
+    };
+    assert_eq!(
+        issue.prompt(Some("  ")),
+        "Resolve GitHub issue #12 in kiwina/gules: Crash on empty config\n"
+    );
+}
+
+#[test]
+fn test_issue_prompt_with_comments_and_instructions() {
+    let issue = GitHubIssue {
+        comments: vec![IssueComment {
+            author: "alice".to_string(),
+            body: "Also happens with a missing file\n".to_string(),
+        }],
+        ..issue()
+    };
+    let prompt = issue.prompt(Some("Add a regression test"));
+    assert!(
+        prompt.ends_with(
+            "\nComments on the issue:\n\
+             \n\
+             @alice:\n\
+             Also happens with a missing file\n\
+             \n\
+             Additional instructions:\n\
+             Add a regression test\n"
+        ),
+        "{}",
+        prompt
+    );
+}
+
+#[test]
+fn test_unresolved_review_threads() {
+    let response = serde_json::json!({"data": {"repository": {"pullRequest": {"reviewThreads": {
+        "nodes": [
+            {
+                "isResolved": false,
+                "path": "src/lib.rs",
+                "line": 12,
+                "comments": {"nodes": [
+                    {"author": {"login": "alice"}, "body": "Handle the error here"},
+                    {"author": null, "body": "Agreed"},
+                ]},
+            },
+            {
+                "isResolved": true,
+                "path": "src/main.rs",
+                "line": 3,
+                "comments": {"nodes": [{"author": {"login": "bob"}, "body": "Typo"}]},
+            },
+            {
+                "isResolved": false,
+                "path": "README.md",
+                "line": null,
+                "comments": {"nodes": [{"author": {"login": "bob"}, "body": "Outdated docs"}]},
+            },
+        ]
+    }}}}});
+    let threads = unresolved_review_threads(&response).unwrap();
+    assert_eq!(threads.len(), 2);
+    assert_eq!(threads[0].path, "src/lib.rs");
+    assert_eq!(threads[0].line, Some(12));
+    assert_eq!(threads[0].comments[1].author, "ghost");
+    assert_eq!(threads[1].line, None);
+
+    let errors = serde_json::json!({"errors": [{"message": "Could not resolve to a Repository"}]});
+    let error = unresolved_review_threads(&errors).unwrap_err().to_string();
+    assert!(error.contains("Could not resolve"), "{}", error);
+}
+
+#[test]
+fn test_parse_linked_pull_requests() {
+    let pr = serde_json::json!({
+        "url": "https://github.com/kiwina/gules/pull/7",
+        "body": "Fixes #12, from sessions/123",
+        "headRefName": "jules-123-fix-config",
+        "commits": {"nodes": [
+            {"commit": {"message": "Handle empty config"}},
+            {"commit": {"message": "Add a test"}},
+        ]},
+    });
+    let response = serde_json::json!({"data": {"repository": {"issue": {"timelineItems": {
+        "nodes": [
+            {"source": pr},
+            // Mentioned from another issue
+            {"source": {}},
+            // Connected in the sidebar, and mentioned above
+            {"subject": pr},
+        ]
+    }}}}});
+    let pull_requests = parse_linked_pull_requests(&response).unwrap();
+    assert_eq!(pull_requests.len(), 1);
+    assert_eq!(
+        pull_requests[0].texts().collect::<Vec<_>>(),
+        vec![
+            "Fixes #12, from sessions/123",
+            "jules-123-fix-config",
+            "Handle empty config",
+            "Add a test",
+        ]
+    );
+
+    let errors = serde_json::json!({"errors": [{"message": "Could not resolve to an Issue"}]});
+    assert!(parse_linked_pull_requests(&errors).is_err());
+}
+
+#[test]
+fn test_review_feedback() {
+    let pr = GitHubItemUrl::parse("https://github.com/kiwina/gules/pull/7").unwrap();
+    let comment = |author: &str, body: &str| IssueComment {
+        author: author.to_string(),
+        body: body.to_string(),
+    };
+    let threads = vec![
+        ReviewThread {
+            path: "src/lib.rs".to_string(),
+            line: Some(12),
+            comments: vec![
+                comment("alice", "Handle the error here\n"),
+                comment("bob", "Agreed"),
+            ],
+        },
+        ReviewThread {
+            path: "README.md".to_string(),
+            line: None,
+            comments: vec![comment("bob", "Outdated docs")],
+        },
+    ];
+    assert_eq!(
+        review_feedback(&pr, &threads),
+        "Please address the unresolved review comments on kiwina/gules#7 and update the \
+         pull request:\n\
+         \n\
+         1. src/lib.rs (line 12):\n\
+         @alice: Handle the error here\n\
+         @bob: Agreed\n\
+         \n\
+         2. README.md (outdated):\n\
+         @bob: Outdated docs\n"
+    );
+}
+
+#[test]
+fn test_actions_job_id() {
+    let mut check = check("test", Some("failure"));
+    assert_eq!(check.actions_job_id(), None);
+
+    check.details_url =
+        Some("https://github.com/kiwina/gules/actions/runs/123/job/456?pr=7".to_string());
+    assert_eq!(check.actions_job_id(), Some(456));
+
+    // Commit statuses from other CI services link elsewhere
+    check.details_url = Some("https://ci.example.com/builds/456".to_string());
+    assert_eq!(check.actions_job_id(), None);
+}
+
+#[test]
+fn test_log_excerpt() {
+    let log = "\u{feff}2025-01-01T10:00:00.1234567Z ##[group]Run cargo test\n\
+               2025-01-01T10:00:01.0000000Z test a ... ok\n\
+               2025-01-01T10:00:02.0000000Z test b ... FAILED\n\
+               2025-01-01T10:00:03.0000000Z ##[error]Process completed with exit code 101.\n\
+               2025-01-01T10:00:04.0000000Z Post job cleanup.\n";
+    assert_eq!(
+        log_excerpt(log, 2),
+        "test b ... FAILED\n##[error]Process completed with exit code 101."
+    );
+    // Without an error line, the end of the log
+    assert_eq!(log_excerpt("one\ntwo\nthree", 2), "two\nthree");
+}
+
+#[test]
+fn test_ci_failure_message() {
+    let pr = GitHubItemUrl::parse("https://github.com/kiwina/gules/pull/7").unwrap();
+    let mut test = check("test", Some("failure"));
+    test.details_url = Some("https://github.com/kiwina/gules/actions/runs/1/job/2".to_string());
+    let failures = vec![
+        (test, Some("test b ... FAILED\n".to_string())),
+        (check("lint", Some("timed_out")), None),
+    ];
+    assert_eq!(
+        ci_failure_message(&pr, &failures),
+        "CI is failing on kiwina/gules#7. Please fix it and update the pull request.\n\
+         \n\
+         Failed check: test (failure)\n\
+         https://github.com/kiwina/gules/actions/runs/1/job/2\n\
+         ```\n\
+         test b ... FAILED\n\
+         ```\n\
+         \n\
+         Failed check: lint (timed_out)\n"
+    );
+}
+
+#[test]
+fn test_session_link_comment() {
+    let comment = session_link_comment("123", Some("https://jules.google.com/session/123"));
+    // issue-status finds sessions by their resource name
+    assert!(comment.contains("`sessions/123`"), "{}", comment);
+    assert!(
+        comment.contains("\nhttps://jules.google.com/session/123\n"),
+        "{}",
+        comment
+    );
+
+    let comment = session_link_comment("123", None);
+    assert!(!comment.contains("https://"), "{}", comment);
+}
+
+fn payload(state: State) -> WebhookPayload {
+    WebhookPayload {
+        event: "session_finished",
+        session_id: "123".to_string(),
+        title: Some("Fix the crash".to_string()),
+        state: Some(state),
+        source: "sources/github/kiwina/gules".to_string(),
+        url: Some("https://jules.google.com/session/123".to_string()),
+        pr_url: None,
+        failure_reason: None,
+        timestamp: "2025-01-03T13:00:00+00:00".to_string(),
+    }
+}
+
+#[test]
+fn test_session_outcome_comment_completed() {
+    let comment = session_outcome_comment(&WebhookPayload {
+        pr_url: Some("https://github.com/kiwina/gules/pull/7".to_string()),
+        ..payload(State::Completed)
+    })
+    .unwrap();
+    assert!(
+        comment.starts_with("✅ Jules session `sessions/123` completed."),
+        "{}",
+        comment
+    );
+    assert!(
+        comment.contains("Pull request: https://github.com/kiwina/gules/pull/7"),
+        "{}",
+        comment
+    );
+    assert!(
+        comment.contains("Session: https://jules.google.com/session/123"),
+        "{}",
+        comment
+    );
+
+    let comment = session_outcome_comment(&payload(State::Completed)).unwrap();
+    assert!(
+        comment.contains("didn't open a pull request"),
+        "{}",
+        comment
+    );
+}
+
+#[test]
+fn test_session_outcome_comment_failed() {
+    let comment = session_outcome_comment(&WebhookPayload {
+        failure_reason: Some("Tests kept failing".to_string()),
+        ..payload(State::Failed)
+    })
+    .unwrap();
+    assert!(
+        comment.starts_with("❌ Jules session `sessions/123` failed."),
+        "{}",
+        comment
+    );
+    assert!(
+        comment.contains("Reason: Tests kept failing"),
+        "{}",
+        comment
+    );
+}
+
+#[test]
+fn test_session_outcome_comment_unfinished() {
+    assert_eq!(session_outcome_comment(&payload(State::Paused)), None);
+    assert_eq!(session_outcome_comment(&payload(State::InProgress)), None);
+}
+
+#[test]
+fn test_source_repo() {
+    assert_eq!(
+        source_repo("sources/github/kiwina/gules"),
+        Some(("kiwina".to_string(), "gules".to_string()))
+    );
+    assert_eq!(source_repo("sources/gitlab/kiwina/gules"), None);
+    assert_eq!(source_repo("sources/github/kiwina"), None);
+    assert_eq!(source_repo("sources/github/kiwina/gules/extra"), None);
+}
@@ -0,0 +1,48 @@
+//! Tests for man page generation.
+
+use clap::{Arg, ArgAction, Command};
+use gules::commands::handle_man;
+use std::fs;
+
+fn command() -> Command {
+    Command::new("tool")
+        .version("1.2.3")
+        .about("Does things")
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .action(ArgAction::SetTrue)
+                .help("More output"),
+        )
+        .subcommand(
+            Command::new("run")
+                .about("Run a task")
+                .arg(Arg::new("task").value_name("TASK").help("Task name"))
+                .subcommand(Command::new("now").about("Run it now")),
+        )
+        .subcommand(Command::new("secret").hide(true))
+}
+
+#[test]
+fn test_a_page_per_visible_command() {
+    let dir = tempfile::tempdir().unwrap();
+    handle_man(&command(), dir.path().to_str()).unwrap();
+
+    let mut names: Vec<String> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["tool-run-now.1", "tool-run.1", "tool.1"]);
+
+    let top = fs::read_to_string(dir.path().join("tool.1")).unwrap();
+    assert!(top.contains(".TH tool 1"));
+    assert!(top.contains("Does things"));
+    assert!(top.contains("tool\\-run(1)"));
+    assert!(!top.contains("secret"));
+
+    let run = fs::read_to_string(dir.path().join("tool-run.1")).unwrap();
+    assert!(run.contains("tool\\-run \\- Run a task"));
+    assert!(run.contains("Task name"));
+}
@@ -0,0 +1,44 @@
+//! Tests for resolving the URL opened by `gules open`.
+
+mod common;
+
+use gules::commands::session_open_url;
+use jules_rs::types::session::Session;
+use serde_json::json;
+
+fn session(url: Option<&str>, pr_url: Option<&str>) -> Session {
+    let outputs = match pr_url {
+        Some(pr_url) => json!([{ "pullRequest": { "url": pr_url } }]),
+        None => json!([]),
+    };
+    common::session("123", json!({ "url": url, "outputs": outputs }))
+}
+
+#[test]
+fn test_session_open_url_uses_session_page() {
+    let session = session(
+        Some("https://jules.google.com/session/123"),
+        Some("https://github.com/octo/app/pull/7"),
+    );
+    assert_eq!(
+        session_open_url(&session, false).unwrap(),
+        "https://jules.google.com/session/123"
+    );
+}
+
+#[test]
+fn test_session_open_url_pr() {
+    let session = session(None, Some("https://github.com/octo/app/pull/7"));
+    assert_eq!(
+        session_open_url(&session, true).unwrap(),
+        "https://github.com/octo/app/pull/7"
+    );
+    assert!(session_open_url(&session, false).is_err());
+}
+
+#[test]
+fn test_session_open_url_without_pr() {
+    let session = session(Some("https://jules.google.com/session/123"), None);
+    let error = session_open_url(&session, true).unwrap_err();
+    assert_eq!(error.to_string(), "Session 123 has no pull request yet");
+}
@@ -0,0 +1,114 @@
+//! Tests for reviewing plans with `gules plan`.
+
+use gules::commands::{auto_approve_plan, latest_plan, PlanChoice};
+use jules_rs::types::activity::Activity;
+use jules_rs::{JulesClient, JulesConfig};
+use serde_json::{json, Value};
+
+fn activity(id: &str, create_time: &str, kind: Value) -> Activity {
+    let mut value = json!({
+        "name": format!("sessions/1/activities/{}", id),
+        "id": id,
+        "createTime": create_time,
+        "originator": "agent",
+    });
+    value
+        .as_object_mut()
+        .unwrap()
+        .extend(kind.as_object().unwrap().clone());
+    serde_json::from_value(value).unwrap()
+}
+
+fn plan(id: &str, step: &str) -> Value {
+    json!({
+        "planGenerated": {
+            "plan": { "id": id, "steps": [{ "id": "s1", "title": step }] }
+        }
+    })
+}
+
+#[test]
+fn test_latest_plan_is_the_newest() {
+    // Newest first, as the activities come from the cache
+    let activities = vec![
+        activity(
+            "3",
+            "2025-10-14T10:05:00Z",
+            json!({ "agentMessaged": { "agentMessage": "Revised" } }),
+        ),
+        activity("2", "2025-10-14T10:04:00Z", plan("p2", "Revised step")),
+        activity("1", "2025-10-14T10:00:00Z", plan("p1", "First step")),
+    ];
+    assert_eq!(
+        latest_plan(&activities).map(|plan| plan.id.as_str()),
+        Some("p2")
+    );
+
+    let mut oldest_first = activities;
+    oldest_first.reverse();
+    assert_eq!(
+        latest_plan(&oldest_first).map(|plan| plan.id.as_str()),
+        Some("p2")
+    );
+}
+
+#[test]
+fn test_latest_plan_without_plan() {
+    let activities = vec![activity(
+        "1",
+        "2025-10-14T10:00:00Z",
+        json!({ "agentMessaged": { "agentMessage": "Hi" } }),
+    )];
+    assert!(latest_plan(&activities).is_none());
+}
+
+#[test]
+fn test_parse_plan_choice() {
+    assert_eq!(PlanChoice::parse("a"), Some(PlanChoice::Approve));
+    assert_eq!(PlanChoice::parse(" Approve "), Some(PlanChoice::Approve));
+    assert_eq!(PlanChoice::parse("r"), Some(PlanChoice::Reject));
+    assert_eq!(PlanChoice::parse("REJECT"), Some(PlanChoice::Reject));
+    assert_eq!(PlanChoice::parse(""), Some(PlanChoice::Skip));
+    assert_eq!(PlanChoice::parse("s"), Some(PlanChoice::Skip));
+    assert_eq!(PlanChoice::parse("yes"), None);
+}
+
+fn client(server: &mockito::Server) -> JulesClient {
+    JulesClient::with_config(JulesConfig {
+        api_key: "test-key".to_string(),
+        base_url: server.url(),
+    })
+}
+
+#[tokio::test]
+async fn test_auto_approve_fails_fast_on_client_errors() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/sessions/1")
+        .with_status(404)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let err = auto_approve_plan(&client(&server), "1", 0, false)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Failed to fetch session 1"));
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_auto_approve_gives_up_after_repeated_failures() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("GET", "/sessions/1")
+        .with_status(500)
+        .expect(5)
+        .create_async()
+        .await;
+
+    assert!(auto_approve_plan(&client(&server), "1", 0, false)
+        .await
+        .is_err());
+    mock.assert_async().await;
+}
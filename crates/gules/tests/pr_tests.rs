@@ -0,0 +1,77 @@
+//! Tests for merging a session's pull request once it is green.
+
+use gules::commands::wait_until_green;
+use gules::github::{GitHubClient, TokenSource};
+use jules_core::github_url::GitHubItemUrl;
+use std::time::Duration;
+
+const PULL_REQUEST: &str = r#"{
+    "url": "https://api.github.com/repos/octo/app/pulls/7",
+    "id": 1,
+    "number": 7,
+    "state": "open",
+    "title": "Fix the login bug",
+    "draft": false,
+    "mergeable": true,
+    "head": {"ref": "fix-login", "sha": "abc123", "label": "octo:fix-login"},
+    "base": {"ref": "main", "sha": "def456", "label": "octo:main"}
+}"#;
+
+/// A client for the GitHub API mocked by `server`, serving an open pull
+/// request without check runs
+async fn pull_request_without_checks(server: &mut mockito::ServerGuard) -> GitHubClient {
+    server
+        .mock("GET", "/repos/octo/app/pulls/7")
+        .with_body(PULL_REQUEST)
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/repos/octo/app/pulls/7/reviews?per_page=100&page=1")
+        .with_body("[]")
+        .create_async()
+        .await;
+    server
+        .mock(
+            "GET",
+            "/repos/octo/app/commits/abc123/check-runs?per_page=100",
+        )
+        .with_body(r#"{"total_count": 0, "check_runs": []}"#)
+        .create_async()
+        .await;
+    let octocrab = octocrab::Octocrab::builder()
+        .base_uri(server.url())
+        .unwrap()
+        .personal_token("test-token".to_string())
+        .build()
+        .unwrap();
+    GitHubClient::Api {
+        octocrab: Box::new(octocrab),
+        source: TokenSource::Env("GITHUB_TOKEN"),
+    }
+}
+
+#[tokio::test]
+async fn test_pull_request_without_checks_is_not_green() {
+    let mut server = mockito::Server::new_async().await;
+    let github = pull_request_without_checks(&mut server).await;
+    let pr = GitHubItemUrl::parse_pull_request("https://github.com/octo/app/pull/7").unwrap();
+
+    let error = wait_until_green(&github, &pr, false, false, 0, 0)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("no checks yet"), "{}", error);
+
+    // --when-green keeps waiting for checks to appear
+    let waiting = tokio::time::timeout(
+        Duration::from_millis(300),
+        wait_until_green(&github, &pr, true, false, 0, 0),
+    )
+    .await;
+    assert!(waiting.is_err());
+
+    let status = wait_until_green(&github, &pr, false, true, 0, 0)
+        .await
+        .unwrap();
+    assert_eq!(status.checks_state(), "none");
+}
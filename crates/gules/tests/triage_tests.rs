@@ -0,0 +1,46 @@
+//! Tests for picking the issues `gules triage` starts sessions on.
+
+use gules::commands::plan_triage;
+use gules::github::GitHubIssue;
+use jules_core::issue_links::IssueLinks;
+
+fn issue(number: u64) -> GitHubIssue {
+    GitHubIssue {
+        owner: "kiwina".to_string(),
+        repo: "gules".to_string(),
+        number,
+        title: format!("Issue {}", number),
+        body: None,
+        comments: vec![],
+    }
+}
+
+fn numbers(issues: &[GitHubIssue]) -> Vec<u64> {
+    issues.iter().map(|issue| issue.number).collect()
+}
+
+#[test]
+fn test_plan_triage_skips_linked_issues() {
+    let mut links = IssueLinks::default();
+    links.link("Kiwina", "gules", 2, "sessions/42").unwrap();
+
+    let plan = plan_triage(vec![issue(1), issue(2), issue(3)], &links, 0, 5);
+    assert_eq!(numbers(&plan.start), vec![1, 3]);
+    assert_eq!(numbers(&plan.linked), vec![2]);
+    assert!(plan.deferred.is_empty());
+}
+
+#[test]
+fn test_plan_triage_respects_max_active() {
+    let links = IssueLinks::default();
+    let issues = || vec![issue(1), issue(2), issue(3), issue(4)];
+
+    let plan = plan_triage(issues(), &links, 1, 3);
+    assert_eq!(numbers(&plan.start), vec![1, 2]);
+    assert_eq!(numbers(&plan.deferred), vec![3, 4]);
+
+    // Already at the cap: everything waits
+    let plan = plan_triage(issues(), &links, 4, 3);
+    assert!(plan.start.is_empty());
+    assert_eq!(numbers(&plan.deferred), vec![1, 2, 3, 4]);
+}
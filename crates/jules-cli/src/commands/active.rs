@@ -4,8 +4,7 @@
 
 use anyhow::Result;
 use clap::Args;
-use jules_core::{get_api_key, load_config};
-use jules_rs::JulesClient;
+use jules_core::{api_client, load_config};
 
 #[derive(Args)]
 pub struct ActiveArgs {
@@ -22,11 +21,8 @@ pub async fn handle_active(args: ActiveArgs) -> Result<()> {
     // Load configuration
     let config = load_config()?;
 
-    // Get API key
-    let api_key = get_api_key(None, &config)?;
-
     // Create client
-    let client = JulesClient::new(api_key);
+    let client = api_client(&config)?;
 
     // Get sessions (SDK returns Response object)
     let response = client.list_sessions(Some(50), None).await?;
@@ -1,8 +1,7 @@
 //! Get single activity command implementation.
 
 use anyhow::Result;
-use jules_core::{get_api_key, load_config};
-use jules_rs::JulesClient;
+use jules_core::{api_client, load_config};
 
 /// Arguments for the activity command
 pub struct ActivityArgs {
@@ -15,11 +14,8 @@ pub async fn handle_activity(args: ActivityArgs) -> Result<()> {
     // Load configuration
     let config = load_config()?;
 
-    // Get API key
-    let api_key = get_api_key(None, &config)?;
-
     // Create client
-    let client = JulesClient::new(api_key);
+    let client = api_client(&config)?;
 
     // Call SDK method
     let activity = client
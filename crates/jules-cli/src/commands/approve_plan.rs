@@ -1,12 +1,13 @@
 //! Approve plan command implementation.
 
 use anyhow::Result;
-use jules_core::{get_api_key, load_config};
-use jules_rs::JulesClient;
+use jules_core::{api_client, load_config};
 
 /// Arguments for the approve_plan command
 pub struct ApprovePlanArgs {
     pub session_id: String,
+    /// Print the updated session as JSON instead of a confirmation
+    pub json: bool,
 }
 
 /// Handle the approve_plan command
@@ -14,15 +15,18 @@ pub async fn handle_approve_plan(args: ApprovePlanArgs) -> Result<()> {
     // Load configuration
     let config = load_config()?;
 
-    // Get API key
-    let api_key = get_api_key(None, &config)?;
-
     // Create client
-    let client = JulesClient::new(api_key);
+    let client = api_client(&config)?;
 
     // Call SDK method
     client.approve_plan(&args.session_id).await?;
 
+    if args.json {
+        let session = client.get_session(&args.session_id).await?;
+        println!("{}", serde_json::to_string_pretty(&session)?);
+        return Ok(());
+    }
+
     // Display confirmation
     println!(
         "Plan approved successfully for session: {}",
@@ -0,0 +1,48 @@
+//! Cancel command implementation.
+
+use anyhow::Result;
+use jules_core::prompt::confirm;
+use jules_core::{api_client, load_config};
+use jules_rs::types::session::State;
+
+/// Arguments for the cancel command
+pub struct CancelArgs {
+    pub session_id: String,
+    /// Skip the confirmation prompt
+    pub yes: bool,
+}
+
+/// Handle the cancel command
+pub async fn handle_cancel(args: CancelArgs) -> Result<()> {
+    // Load configuration
+    let config = load_config()?;
+
+    // Create client
+    let client = api_client(&config)?;
+
+    // Finished sessions can't be cancelled
+    let session = client.get_session(&args.session_id).await?;
+    if let Some(state @ (State::Completed | State::Failed)) = session.state {
+        anyhow::bail!(
+            "Session {} is already {}",
+            session.id,
+            state.display_name().to_lowercase()
+        );
+    }
+
+    let title = session.title.as_deref().unwrap_or("Untitled");
+    if !confirm(
+        &format!("Cancel session {} ({})?", session.id, title),
+        args.yes,
+    )? {
+        println!("Session not cancelled.");
+        return Ok(());
+    }
+
+    // Call SDK method
+    client.cancel_session(&session.id).await?;
+
+    println!("Session cancelled: {}", session.id);
+
+    Ok(())
+}
@@ -2,9 +2,14 @@
 //!
 //! Manages Jules CLI configuration.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Args;
-use jules_core::{get_config_path, load_config, save_config, Config};
+use jules_core::editor::edit_file;
+use jules_core::{
+    config_schema, find_project_config, get_config_path, get_config_value, icon, load_config,
+    load_global_config, save_config, set_config_value, unset_config_value, Config,
+};
+use std::fs;
 
 #[derive(Args)]
 pub struct ConfigShowArgs;
@@ -34,9 +39,35 @@ pub async fn handle_config_show(_args: ConfigShowArgs) -> Result<()> {
         "Default Repo: {}",
         config.default_repo.as_deref().unwrap_or("Not set")
     );
+    println!(
+        "Default Source: {}",
+        config.default_source.as_deref().unwrap_or("Not set")
+    );
+    println!(
+        "Default Branch: {}",
+        config.default_branch.as_deref().unwrap_or("Not set")
+    );
+    println!(
+        "Automation Mode: {}",
+        config.automation_mode.as_deref().unwrap_or("Default")
+    );
+    if !config.templates.is_empty() {
+        let names: Vec<&str> = config.templates.keys().map(String::as_str).collect();
+        println!("Prompt Templates: {}", names.join(", "));
+    }
+    if !config.output_templates.is_empty() {
+        let names: Vec<&str> = config.output_templates.keys().map(String::as_str).collect();
+        println!("Output Templates: {}", names.join(", "));
+    }
 
     let config_file = get_config_path()?;
     println!("Config file: {}", config_file.display());
+    if let Some(project_file) = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| find_project_config(&cwd))
+    {
+        println!("Project config: {}", project_file.display());
+    }
 
     Ok(())
 }
@@ -61,7 +92,8 @@ pub async fn handle_config_init(_args: ConfigInitArgs) -> Result<()> {
     save_config(&config)?;
 
     println!(
-        "✅ Configuration file initialized at: {}",
+        "{} Configuration file initialized at: {}",
+        icon("✅", "OK"),
         config_path.display()
     );
     println!("You can now set your API key with: gules config set api_key YOUR_API_KEY");
@@ -71,37 +103,101 @@ pub async fn handle_config_init(_args: ConfigInitArgs) -> Result<()> {
 
 #[derive(Args)]
 pub struct ConfigSetArgs {
-    /// Configuration key to set
+    /// Configuration key to set (dotted for nested settings, e.g. cache.max_sessions)
     pub key: String,
     /// Value to set
     pub value: String,
 }
 
 pub async fn handle_config_set(args: ConfigSetArgs) -> Result<()> {
-    let mut config = load_config()?;
+    let mut config = load_global_config()?;
+    set_config_value(&mut config, &args.key, &args.value)?;
+    save_config(&config)?;
 
-    match args.key.as_str() {
-        "api_key" => {
-            config.api_key = Some(args.value.clone());
-            println!("✅ API key set successfully");
-        }
-        "api_url" => {
-            config.api_url = Some(args.value.clone());
-            println!("✅ API URL set to: {}", args.value);
-        }
-        "default_owner" => {
-            config.default_owner = Some(args.value.clone());
-            println!("✅ Default owner set to: {}", args.value);
-        }
-        "default_repo" => {
-            config.default_repo = Some(args.value.clone());
-            println!("✅ Default repo set to: {}", args.value);
-        }
-        _ => {
-            anyhow::bail!("Unknown configuration key: {}. Supported keys: api_key, api_url, default_owner, default_repo", args.key);
-        }
+    if args.key == "api_key" {
+        println!("{} API key set successfully", icon("✅", "OK"));
+    } else {
+        let value = get_config_value(&config, &args.key)?
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| args.value.clone());
+        println!("{} {} set to: {}", icon("✅", "OK"), args.key, value);
     }
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct ConfigGetArgs {
+    /// Configuration key to print (dotted for nested settings, e.g. cache.ttl_seconds)
+    pub key: String,
+}
+
+pub async fn handle_config_get(args: ConfigGetArgs) -> Result<()> {
+    let config = load_config()?;
+    match get_config_value(&config, &args.key)? {
+        // Print strings bare so the output can be used in scripts
+        Some(toml::Value::String(value)) => println!("{}", value),
+        Some(toml::Value::Table(table)) => print!("{}", toml::to_string_pretty(&table)?),
+        Some(value) => println!("{}", value),
+        None => anyhow::bail!("{} is not set", args.key),
+    }
+    Ok(())
+}
 
+#[derive(Args)]
+pub struct ConfigUnsetArgs {
+    /// Configuration key to remove (its default applies again)
+    pub key: String,
+}
+
+pub async fn handle_config_unset(args: ConfigUnsetArgs) -> Result<()> {
+    let mut config = load_global_config()?;
+    if !unset_config_value(&mut config, &args.key)? {
+        println!("{} is not set", args.key);
+        return Ok(());
+    }
     save_config(&config)?;
+
+    match get_config_value(&config, &args.key)? {
+        Some(default) => println!(
+            "{} {} reset to default: {}",
+            icon("✅", "OK"),
+            args.key,
+            default
+        ),
+        None => println!("{} {} unset", icon("✅", "OK"), args.key),
+    }
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct ConfigEditArgs;
+
+pub async fn handle_config_edit(_args: ConfigEditArgs) -> Result<()> {
+    // Creates the file with defaults if it does not exist yet
+    load_global_config()?;
+    let config_path = get_config_path()?;
+
+    edit_file(&config_path)?;
+
+    let contents = fs::read_to_string(&config_path).context("Failed to read config file")?;
+    if let Err(e) = toml::from_str::<Config>(&contents) {
+        anyhow::bail!(
+            "Config file is invalid, fix it with 'gules config edit': {}",
+            e.message()
+        );
+    }
+    println!(
+        "{} Configuration saved: {}",
+        icon("✅", "OK"),
+        config_path.display()
+    );
+    Ok(())
+}
+
+#[derive(Args)]
+pub struct ConfigSchemaArgs;
+
+pub async fn handle_config_schema(_args: ConfigSchemaArgs) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&config_schema())?);
     Ok(())
 }
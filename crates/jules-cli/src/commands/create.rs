@@ -4,20 +4,17 @@
 
 use anyhow::Result;
 use clap::Args;
-use jules_core::{get_api_key, load_config};
-use jules_rs::{
-    types::session::{CreateSessionRequest, SourceContext},
-    JulesClient,
-};
+use jules_core::{api_client, icon, load_config};
+use jules_rs::types::session::{CreateSessionRequest, SourceContext};
 
 #[derive(Args)]
 pub struct CreateArgs {
     /// Session prompt/description
     pub prompt: String,
 
-    /// Source repository ID
+    /// Source repository ID (default: default_source from .gules.toml or the config)
     #[arg(short, long)]
-    pub source: String,
+    pub source: Option<String>,
 
     /// Optional session title
     #[arg(long)]
@@ -34,21 +31,35 @@ pub struct CreateArgs {
     /// Automation mode: AUTO_CREATE_PR or MANUAL
     #[arg(long, value_name = "MODE")]
     pub automation_mode: Option<String>,
+
+    /// Prompt template from the [templates] table of the config
+    #[arg(long, value_name = "NAME")]
+    pub template: Option<String>,
 }
 
 pub async fn handle_create(args: CreateArgs) -> Result<()> {
     // Load configuration
     let config = load_config()?;
 
-    // Get API key
-    let api_key = get_api_key(None, &config)?;
-
     // Create client
-    let client = JulesClient::new(api_key);
+    let client = api_client(&config)?;
+
+    // Fill in project defaults
+    let source = config.resolve_source(args.source.clone())?;
+    config.safety.check_source(&source)?;
+    let prompt = config.render_prompt(args.template.as_deref(), &args.prompt)?;
+    let branch = args
+        .branch
+        .clone()
+        .or_else(|| config.default_branch.clone());
+    let automation_mode = args
+        .automation_mode
+        .clone()
+        .or_else(|| config.automation_mode.clone());
 
     // Build GitHub repo context if branch is specified
     let github_repo_context =
-        args.branch
+        branch
             .as_ref()
             .map(|branch| jules_rs::types::session::GitHubRepoContext {
                 starting_branch: branch.clone(),
@@ -56,7 +67,7 @@ pub async fn handle_create(args: CreateArgs) -> Result<()> {
 
     // Parse automation mode
     let automation_mode =
-        args.automation_mode
+        automation_mode
             .as_ref()
             .and_then(|mode| match mode.to_uppercase().as_str() {
                 "AUTO_CREATE_PR" => Some(jules_rs::types::session::AutomationMode::AutoCreatePr),
@@ -68,9 +79,9 @@ pub async fn handle_create(args: CreateArgs) -> Result<()> {
 
     // Build request - pure SDK interface
     let request = CreateSessionRequest {
-        prompt: args.prompt.clone(),
+        prompt,
         source_context: SourceContext {
-            source: args.source.clone(),
+            source,
             github_repo_context,
         },
         title: args.title.clone(),
@@ -86,7 +97,7 @@ pub async fn handle_create(args: CreateArgs) -> Result<()> {
     let session = client.create_session(request).await?;
 
     // Display success message
-    println!("✅ Session created successfully!");
+    println!("{} Session created successfully!", icon("✅", "OK"));
     println!("Session ID: {}", session.id);
     println!("Name: {}", session.name);
 
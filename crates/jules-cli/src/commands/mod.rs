@@ -7,6 +7,7 @@ pub mod active;
 pub mod activities;
 pub mod activity;
 pub mod approve_plan;
+pub mod cancel;
 pub mod completed;
 pub mod config_cmd;
 pub mod create;
@@ -22,6 +23,7 @@ pub use active::*;
 pub use activities::*;
 pub use activity::*;
 pub use approve_plan::*;
+pub use cancel::*;
 pub use completed::*;
 pub use config_cmd::*;
 pub use create::*;
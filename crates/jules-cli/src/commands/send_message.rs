@@ -1,13 +1,14 @@
 //! Send message command implementation.
 
 use anyhow::Result;
-use jules_core::{get_api_key, load_config};
-use jules_rs::JulesClient;
+use jules_core::{api_client, load_config};
 
 /// Arguments for the send_message command
 pub struct SendMessageArgs {
     pub session_id: String,
     pub message: String,
+    /// Print the updated session as JSON instead of a confirmation
+    pub json: bool,
 }
 
 /// Handle the send_message command
@@ -15,15 +16,18 @@ pub async fn handle_send_message(args: SendMessageArgs) -> Result<()> {
     // Load configuration
     let config = load_config()?;
 
-    // Get API key
-    let api_key = get_api_key(None, &config)?;
-
     // Create client
-    let client = JulesClient::new(api_key);
+    let client = api_client(&config)?;
 
     // Call SDK method
     client.send_message(&args.session_id, &args.message).await?;
 
+    if args.json {
+        let session = client.get_session(&args.session_id).await?;
+        println!("{}", serde_json::to_string_pretty(&session)?);
+        return Ok(());
+    }
+
     // Display confirmation
     println!("Message sent successfully to session: {}", args.session_id);
     println!();
@@ -9,38 +9,42 @@ use jules_cli::commands::*;
 fn test_create_args_minimal() {
     let args = CreateArgs {
         prompt: "Fix bug".to_string(),
-        source: "github.com/user/repo".to_string(),
+        source: Some("github.com/user/repo".to_string()),
         title: None,
         branch: None,
         require_approval: false,
         automation_mode: None,
+        template: None,
     };
 
     assert_eq!(args.prompt, "Fix bug");
-    assert_eq!(args.source, "github.com/user/repo");
+    assert_eq!(args.source.as_deref(), Some("github.com/user/repo"));
     assert!(args.title.is_none());
     assert!(args.branch.is_none());
-    assert_eq!(args.require_approval, false);
+    assert!(!args.require_approval);
     assert!(args.automation_mode.is_none());
+    assert!(args.template.is_none());
 }
 
 #[test]
 fn test_create_args_full() {
     let args = CreateArgs {
         prompt: "Complex task".to_string(),
-        source: "github.com/owner/repo".to_string(),
+        source: Some("github.com/owner/repo".to_string()),
         title: Some("My Task".to_string()),
         branch: Some("feature".to_string()),
         require_approval: true,
         automation_mode: Some("AUTO_CREATE_PR".to_string()),
+        template: Some("bugfix".to_string()),
     };
 
     assert_eq!(args.prompt, "Complex task");
-    assert_eq!(args.source, "github.com/owner/repo");
+    assert_eq!(args.source.as_deref(), Some("github.com/owner/repo"));
     assert_eq!(args.title, Some("My Task".to_string()));
     assert_eq!(args.branch, Some("feature".to_string()));
-    assert_eq!(args.require_approval, true);
+    assert!(args.require_approval);
     assert_eq!(args.automation_mode, Some("AUTO_CREATE_PR".to_string()));
+    assert_eq!(args.template.as_deref(), Some("bugfix"));
 }
 
 #[test]
@@ -57,6 +61,7 @@ fn test_send_message_args() {
     let args = SendMessageArgs {
         session_id: "67890".to_string(),
         message: "Continue with the plan".to_string(),
+        json: false,
     };
 
     assert_eq!(args.session_id, "67890");
@@ -67,9 +72,22 @@ fn test_send_message_args() {
 fn test_approve_plan_args() {
     let args = ApprovePlanArgs {
         session_id: "plan-123".to_string(),
+        json: true,
     };
 
     assert_eq!(args.session_id, "plan-123");
+    assert!(args.json);
+}
+
+#[test]
+fn test_cancel_args() {
+    let args = CancelArgs {
+        session_id: "cancel-123".to_string(),
+        yes: true,
+    };
+
+    assert_eq!(args.session_id, "cancel-123");
+    assert!(args.yes);
 }
 
 #[test]
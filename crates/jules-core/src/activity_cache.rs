@@ -4,28 +4,81 @@
 //! - Fast filtering without repeated API calls
 //! - Incremental updates using page tokens
 //! - FIFO eviction when max sessions reached
+//! - TTL expiration for sessions that are still running
+//! - Optional zstd compression and encryption of session files
+//! - An index of bash outputs for exit-code queries (see [`crate::bash_index`])
 //! - Cache management (clear, delete specific sessions)
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use jules_rs::types::activity::{Activity, ListActivitiesResponse};
+use jules_rs::types::resource_name::SessionName;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::atomic_file::{is_temp_file, write_atomic};
+use crate::bash_index::{BashIndex, BASH_INDEX_FILE_NAME};
+use crate::cache_crypto::CacheKey;
+use crate::progress::Spinner;
+use crate::{cache_crypto, cache_lock};
 
 /// Maximum number of activities to fetch from API
 const MAX_ACTIVITIES_TO_FETCH: usize = 100;
 /// Page size for API pagination
 const ACTIVITIES_PAGE_SIZE: u32 = 50;
 
+/// Safety limit on pages walked by an incremental update
+const MAX_SYNC_PAGES: usize = 20;
+/// Default lifetime of a cache for a session that is still running
+pub const DEFAULT_CACHE_TTL_SECONDS: u64 = 300;
+
+fn default_ttl_seconds() -> u64 {
+    DEFAULT_CACHE_TTL_SECONDS
+}
+
+/// Which cached session is evicted first when a limit is exceeded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EvictionPolicy {
+    /// Oldest cached session first
+    #[default]
+    Fifo,
+    /// Least recently read or written session first
+    Lru,
+}
+
 /// Cache configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ActivityCacheConfig {
     /// Enable activity caching
     pub enabled: bool,
-    /// Maximum number of sessions to cache (FIFO eviction)
+    /// Maximum number of sessions to cache
     pub max_sessions: usize,
+    /// Eviction order when `max_sessions` or `max_size_mb` is exceeded
+    #[serde(default)]
+    pub eviction: EvictionPolicy,
+    /// Maximum total size of session files in MiB (unlimited when unset)
+    #[serde(default)]
+    pub max_size_mb: Option<u64>,
+    /// Seconds before the cache of a running session is refreshed
+    /// (completed and failed sessions never expire; 0 always refreshes)
+    #[serde(default = "default_ttl_seconds")]
+    pub ttl_seconds: u64,
+    /// Store session files zstd-compressed (`{id}.json.zst`)
+    #[serde(default = "default_compress")]
+    pub compress: bool,
+    /// Encrypt session files at rest (see [`crate::cache_crypto`])
+    #[serde(default)]
+    pub encrypt: bool,
+}
+
+fn default_compress() -> bool {
+    true
 }
 
 impl Default for ActivityCacheConfig {
@@ -33,6 +86,11 @@ impl Default for ActivityCacheConfig {
         Self {
             enabled: true,
             max_sessions: 50,
+            eviction: EvictionPolicy::default(),
+            max_size_mb: None,
+            ttl_seconds: DEFAULT_CACHE_TTL_SECONDS,
+            compress: default_compress(),
+            encrypt: false,
         }
     }
 }
@@ -44,7 +102,8 @@ pub struct SessionCache {
     pub session_id: String,
     /// All cached activities (ordered by creation time, newest first)
     pub activities: Vec<Activity>,
-    /// Last page token used (for incremental updates)
+    /// Page token returned by the last fetch (kept for compatibility;
+    /// incremental updates are keyed on activity IDs and creation times)
     pub last_page_token: Option<String>,
     /// When this cache was last updated
     pub last_updated: DateTime<Utc>,
@@ -52,13 +111,160 @@ pub struct SessionCache {
     pub created_at: DateTime<Utc>,
 }
 
-/// Cache metadata for FIFO eviction
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl SessionCache {
+    /// Whether the session reached a terminal state, so no new activities will arrive
+    pub fn is_terminal(&self) -> bool {
+        self.activities
+            .iter()
+            .any(|a| a.session_completed.is_some() || a.session_failed.is_some())
+    }
+
+    /// Whether the cache can be served without asking the API
+    pub fn is_fresh(&self, ttl_seconds: u64) -> bool {
+        is_cache_fresh(self.last_updated, self.is_terminal(), ttl_seconds)
+    }
+}
+
+/// Terminal sessions are always fresh; running ones expire after `ttl_seconds`
+pub fn is_cache_fresh(last_updated: DateTime<Utc>, terminal: bool, ttl_seconds: u64) -> bool {
+    if terminal {
+        return true;
+    }
+    let age = Utc::now().signed_duration_since(last_updated);
+    age < chrono::Duration::seconds(ttl_seconds.min(i64::MAX as u64) as i64)
+}
+
+/// Version of the on-disk format of the metadata and session files
+///
+/// Bump it when either schema changes and add a step to `migrate_json`, so
+/// caches written by older versions are upgraded instead of failing to parse.
+pub const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// Cache metadata for eviction
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheMetadata {
-    /// Session IDs in order of first access (oldest first)
+    /// Format version ([`CACHE_FORMAT_VERSION`]; 0 for unversioned caches)
+    #[serde(default)]
+    pub version: u32,
+    /// Session IDs in eviction order (next to evict first): order of first
+    /// caching for FIFO, order of last access for LRU
     pub access_order: Vec<String>,
     /// Cache configuration
     pub config: ActivityCacheConfig,
+    /// Session IDs that are never evicted
+    #[serde(default)]
+    pub pinned: Vec<String>,
+    /// Hit/miss counters
+    #[serde(default)]
+    pub metrics: CacheMetrics,
+}
+
+impl Default for CacheMetadata {
+    fn default() -> Self {
+        Self {
+            version: CACHE_FORMAT_VERSION,
+            access_order: Vec::new(),
+            config: ActivityCacheConfig::default(),
+            pinned: Vec::new(),
+            metrics: CacheMetrics::default(),
+        }
+    }
+}
+
+/// How a read of a session's activities was served
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheAccess {
+    /// From the cache, without calling the API
+    Hit,
+    /// Not cached: every activity was fetched
+    Miss,
+    /// Stale cache: only the new activities were fetched
+    IncrementalUpdate { new_activities: usize },
+}
+
+/// Cache effectiveness counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheCounters {
+    pub hits: u64,
+    pub misses: u64,
+    pub incremental_updates: u64,
+    /// Estimated API requests avoided: the pages a full fetch would have
+    /// needed, minus those actually fetched
+    pub api_calls_saved: u64,
+}
+
+impl CacheCounters {
+    /// Total number of recorded reads
+    pub fn reads(&self) -> u64 {
+        self.hits + self.misses + self.incremental_updates
+    }
+
+    /// Share of reads served without fetching every activity (0.0-1.0)
+    pub fn hit_rate(&self) -> Option<f64> {
+        let reads = self.reads();
+        (reads > 0).then(|| (self.hits + self.incremental_updates) as f64 / reads as f64)
+    }
+
+    fn record(&mut self, access: CacheAccess, api_calls_saved: u64) {
+        match access {
+            CacheAccess::Hit => self.hits += 1,
+            CacheAccess::Miss => self.misses += 1,
+            CacheAccess::IncrementalUpdate { .. } => self.incremental_updates += 1,
+        }
+        self.api_calls_saved += api_calls_saved;
+    }
+}
+
+/// Counters persisted in the cache metadata
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheMetrics {
+    /// All reads since the cache was created (kept when sessions are evicted)
+    #[serde(default)]
+    pub total: CacheCounters,
+    /// Reads per cached session
+    #[serde(default)]
+    pub sessions: BTreeMap<String, CacheCounters>,
+}
+
+/// Parse a metadata or session file, upgrading older format versions
+///
+/// Returns the parsed value and the version it was stored in. Files written
+/// by a newer gules are rejected.
+fn parse_versioned<T: DeserializeOwned>(bytes: &[u8]) -> Result<(T, u32)> {
+    let mut value: serde_json::Value = serde_json::from_slice(bytes)?;
+    let version = value
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(0) as u32;
+    if version > CACHE_FORMAT_VERSION {
+        anyhow::bail!(
+            "written by a newer version of gules (cache format {}, supported {})",
+            version,
+            CACHE_FORMAT_VERSION
+        );
+    }
+    if version < CACHE_FORMAT_VERSION {
+        migrate_json(&mut value, version);
+    }
+    Ok((serde_json::from_value(value)?, version))
+}
+
+/// Upgrade a metadata or session file from `version` to the current format
+fn migrate_json(value: &mut serde_json::Value, from: u32) {
+    debug_assert!(from < CACHE_FORMAT_VERSION);
+    // Version 0 (unversioned) files have the same fields as version 1. Later
+    // schema changes add steps here, e.g. `if from < 2 { ... }`
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".to_string(), CACHE_FORMAT_VERSION.into());
+    }
+}
+
+/// A session file as stored on disk
+#[derive(Serialize)]
+struct VersionedSessionCache<'a> {
+    version: u32,
+    #[serde(flatten)]
+    cache: &'a SessionCache,
 }
 
 /// Get the cache directory path
@@ -68,166 +274,1026 @@ pub fn get_cache_dir() -> Result<PathBuf> {
     Ok(cache_dir.join("gules").join("activities"))
 }
 
-/// Get cache file path for a session
-fn get_session_cache_path(session_id: &str) -> Result<PathBuf> {
-    let cache_dir = get_cache_dir()?;
-    fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
-    Ok(cache_dir.join(format!("{}.json", session_id)))
+/// Normalize a session ID or resource name (`sessions/123`) to the cache key
+///
+/// The key is the bare session ID, so both spellings share one cache entry and
+/// a malformed ID can never escape the cache directory.
+pub fn cache_key(session_id: &str) -> Result<String> {
+    let name = SessionName::from_id_or_name(session_id)?;
+    Ok(name.id().to_string())
 }
 
-/// Get metadata file path
-fn get_metadata_path() -> Result<PathBuf> {
-    let cache_dir = get_cache_dir()?;
-    fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
-    Ok(cache_dir.join("metadata.json"))
-}
+/// Lock file serializing cache updates across processes
+const LOCK_FILE_NAME: &str = ".lock";
 
-/// Load cache metadata
-pub fn load_metadata() -> Result<CacheMetadata> {
-    let metadata_path = get_metadata_path()?;
+/// Metadata file name inside the cache directory
+const METADATA_FILE_NAME: &str = "metadata.json";
 
-    if !metadata_path.exists() {
-        let metadata = CacheMetadata::default();
-        save_metadata(&metadata)?;
-        return Ok(metadata);
+/// zstd level for session files (fast, still shrinks patches and logs ~5-10x)
+const ZSTD_LEVEL: i32 = 3;
+
+/// On-disk format of a session file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StorageFormat {
+    compress: bool,
+    encrypt: bool,
+    version: u32,
+}
+
+impl From<&ActivityCacheConfig> for StorageFormat {
+    fn from(config: &ActivityCacheConfig) -> Self {
+        Self {
+            compress: config.compress,
+            encrypt: config.encrypt,
+            version: CACHE_FORMAT_VERSION,
+        }
     }
+}
 
-    let contents = fs::read_to_string(&metadata_path).context("Failed to read metadata")?;
-    serde_json::from_str(&contents).context("Failed to parse metadata")
+/// A file cache directory: session files, metadata and the bash index
+///
+/// The free functions of this module work on the default directory
+/// ([`get_cache_dir`]) with the process-wide key; a `CacheDir` can point
+/// anywhere and carry its own key (e.g. in tests).
+#[derive(Clone)]
+pub struct CacheDir {
+    path: PathBuf,
+    key: Option<CacheKey>,
 }
 
-/// Save cache metadata
-pub fn save_metadata(metadata: &CacheMetadata) -> Result<()> {
-    let metadata_path = get_metadata_path()?;
-    let contents =
-        serde_json::to_string_pretty(metadata).context("Failed to serialize metadata")?;
-    fs::write(&metadata_path, contents).context("Failed to write metadata")?;
-    Ok(())
+impl fmt::Debug for CacheDir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheDir")
+            .field("path", &self.path)
+            .finish()
+    }
 }
 
-/// Load cached activities for a session
-pub fn load_session_cache(session_id: &str) -> Result<Option<SessionCache>> {
-    let cache_path = get_session_cache_path(session_id)?;
+impl CacheDir {
+    /// A cache in `path`, using the process-wide key for encryption
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            key: None,
+        }
+    }
 
-    if !cache_path.exists() {
-        return Ok(None);
+    /// The cache in the default directory
+    pub fn open_default() -> Result<Self> {
+        Ok(Self::new(get_cache_dir()?))
     }
 
-    let contents = fs::read_to_string(&cache_path)
-        .context(format!("Failed to read cache for session {}", session_id))?;
+    /// Seal and open session files with `key` instead of the process-wide one
+    pub fn with_key(mut self, key: CacheKey) -> Self {
+        self.key = Some(key);
+        self
+    }
 
-    let cache: SessionCache = serde_json::from_str(&contents)
-        .context(format!("Failed to parse cache for session {}", session_id))?;
+    /// The directory of the cache
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
 
-    Ok(Some(cache))
-}
+    fn key(&self) -> Result<&CacheKey> {
+        match &self.key {
+            Some(key) => Ok(key),
+            None => cache_crypto::shared_key(),
+        }
+    }
 
-/// Save cached activities for a session
-pub fn save_session_cache(cache: &SessionCache) -> Result<()> {
-    let cache_path = get_session_cache_path(&cache.session_id)?;
-    let contents =
-        serde_json::to_string_pretty(cache).context("Failed to serialize session cache")?;
-    fs::write(&cache_path, contents).context("Failed to write session cache")?;
-
-    // Update metadata access order
-    let mut metadata = load_metadata()?;
-
-    // Remove from current position (if exists)
-    metadata.access_order.retain(|id| id != &cache.session_id);
-
-    // Add to end (most recently accessed)
-    metadata.access_order.push(cache.session_id.clone());
-
-    // FIFO eviction if needed
-    if metadata.access_order.len() > metadata.config.max_sessions {
-        let to_remove_count = metadata.access_order.len() - metadata.config.max_sessions;
-        let evicted_sessions: Vec<String> =
-            metadata.access_order.drain(..to_remove_count).collect();
-        for session_id in evicted_sessions {
-            let cache_path = get_session_cache_path(&session_id)?;
-            if cache_path.exists() {
-                fs::remove_file(&cache_path).context(format!(
-                    "Failed to delete evicted cache for session {}",
-                    session_id
-                ))?;
+    /// Run a cache read-modify-write under the cache directory lock
+    fn with_cache_lock<T>(&self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        cache_lock::with_lock(&self.path.join(LOCK_FILE_NAME), f)
+    }
+
+    /// Get cache file path for a session (uncompressed)
+    fn get_session_cache_path(&self, session_id: &str) -> Result<PathBuf> {
+        let key = cache_key(session_id)?;
+        let cache_dir = &self.path;
+        fs::create_dir_all(cache_dir).context("Failed to create cache directory")?;
+        Ok(cache_dir.join(format!("{}.json", key)))
+    }
+
+    /// Get compressed cache file path for a session
+    fn get_compressed_cache_path(&self, session_id: &str) -> Result<PathBuf> {
+        Ok(self
+            .get_session_cache_path(session_id)?
+            .with_extension("json.zst"))
+    }
+
+    /// Remove both the compressed and uncompressed file for a session
+    fn remove_session_files(&self, session_id: &str) -> Result<()> {
+        for path in [
+            self.get_session_cache_path(session_id)?,
+            self.get_compressed_cache_path(session_id)?,
+        ] {
+            if path.exists() {
+                fs::remove_file(&path)
+                    .context(format!("Failed to delete cache for session {}", session_id))?;
             }
         }
+        Ok(())
     }
 
-    save_metadata(&metadata)?;
-    Ok(())
-}
+    /// Write a session cache in the requested format, removing the other file
+    fn write_session_file(&self, cache: &SessionCache, format: StorageFormat) -> Result<()> {
+        let json_path = self.get_session_cache_path(&cache.session_id)?;
+        let zst_path = self.get_compressed_cache_path(&cache.session_id)?;
 
-/// Delete cache for a specific session
-pub fn delete_session_cache(session_id: &str) -> Result<()> {
-    let cache_path = get_session_cache_path(session_id)?;
+        let versioned = VersionedSessionCache {
+            version: format.version,
+            cache,
+        };
+        let mut contents = if format.compress {
+            let contents =
+                serde_json::to_vec(&versioned).context("Failed to serialize session cache")?;
+            zstd::encode_all(contents.as_slice(), ZSTD_LEVEL)
+                .context("Failed to compress session cache")?
+        } else {
+            serde_json::to_vec_pretty(&versioned).context("Failed to serialize session cache")?
+        };
+        if format.encrypt {
+            contents = self.key()?.seal(&contents)?;
+        }
+
+        let (path, other) = if format.compress {
+            (zst_path, json_path)
+        } else {
+            (json_path, zst_path)
+        };
+        write_atomic(&path, contents).context("Failed to write session cache")?;
+        if other.exists() {
+            fs::remove_file(&other).context("Failed to remove old session cache file")?;
+        }
+        Ok(())
+    }
+
+    /// Read a session cache from whichever file exists (compressed preferred)
+    ///
+    /// Also returns the format the data was stored in.
+    fn read_session_file(&self, session_id: &str) -> Result<Option<(SessionCache, StorageFormat)>> {
+        let zst_path = self.get_compressed_cache_path(session_id)?;
+        let json_path = self.get_session_cache_path(session_id)?;
+
+        let (path, compress) = if zst_path.exists() {
+            (zst_path, true)
+        } else if json_path.exists() {
+            (json_path, false)
+        } else {
+            return Ok(None);
+        };
+
+        let mut bytes =
+            fs::read(&path).context(format!("Failed to read cache for session {}", session_id))?;
+        let encrypt = cache_crypto::is_encrypted(&bytes);
+        if encrypt {
+            bytes = self.key()?.open(&bytes).context(format!(
+                "Failed to decrypt cache for session {}",
+                session_id
+            ))?;
+        }
+        if compress {
+            bytes = zstd::decode_all(bytes.as_slice()).context(format!(
+                "Failed to decompress cache for session {}",
+                session_id
+            ))?;
+        }
+
+        let (cache, version): (SessionCache, u32) = parse_versioned(&bytes)
+            .context(format!("Failed to parse cache for session {}", session_id))?;
+        Ok(Some((
+            cache,
+            StorageFormat {
+                compress,
+                encrypt,
+                version,
+            },
+        )))
+    }
+
+    /// Whether the stored file for a session is encrypted (reads only the header)
+    fn is_session_file_encrypted(&self, session_id: &str) -> bool {
+        use std::io::Read;
+
+        [
+            self.get_compressed_cache_path(session_id),
+            self.get_session_cache_path(session_id),
+        ]
+        .into_iter()
+        .flatten()
+        .find(|path| path.exists())
+        .and_then(|path| fs::File::open(path).ok())
+        .map(|file| {
+            let mut header = Vec::with_capacity(cache_crypto::MAGIC.len());
+            let _ = file
+                .take(cache_crypto::MAGIC.len() as u64)
+                .read_to_end(&mut header);
+            cache_crypto::is_encrypted(&header)
+        })
+        .unwrap_or(false)
+    }
+
+    /// Get metadata file path
+    fn get_metadata_path(&self) -> Result<PathBuf> {
+        let cache_dir = &self.path;
+        fs::create_dir_all(cache_dir).context("Failed to create cache directory")?;
+        Ok(cache_dir.join(METADATA_FILE_NAME))
+    }
+
+    /// Load cache metadata
+    pub fn load_metadata(&self) -> Result<CacheMetadata> {
+        let metadata_path = self.get_metadata_path()?;
+
+        if !metadata_path.exists() {
+            let metadata = CacheMetadata::default();
+            self.save_metadata(&metadata)?;
+            return Ok(metadata);
+        }
+
+        let contents = fs::read_to_string(&metadata_path).context("Failed to read metadata")?;
+        let stored_version = serde_json::from_str::<serde_json::Value>(&contents)
+            .ok()
+            .and_then(|value| value.get("version")?.as_u64())
+            .unwrap_or(0) as u32;
+
+        match parse_versioned::<CacheMetadata>(contents.as_bytes()) {
+            Ok((metadata, version)) => {
+                if version < CACHE_FORMAT_VERSION {
+                    self.save_metadata(&metadata)?;
+                }
+                Ok(metadata)
+            }
+            // A cache from an older version that cannot be upgraded is discarded
+            // rather than failing every command
+            Err(e) if stored_version < CACHE_FORMAT_VERSION && !is_syntax_error(&e) => {
+                eprintln!(
+                    "Warning: Clearing activity cache in unsupported format {}: {:#}",
+                    stored_version, e
+                );
+                self.with_cache_lock(|| {
+                    self.remove_cache_entries()?;
+                    let metadata = CacheMetadata::default();
+                    self.save_metadata(&metadata)?;
+                    Ok(metadata)
+                })
+            }
+            Err(e) => Err(e.context(
+                "Failed to parse metadata (run `gules cache verify --repair` or `gules cache clear`)",
+            )),
+        }
+    }
+
+    /// Save cache metadata
+    pub fn save_metadata(&self, metadata: &CacheMetadata) -> Result<()> {
+        let metadata_path = self.get_metadata_path()?;
+        let contents =
+            serde_json::to_string_pretty(metadata).context("Failed to serialize metadata")?;
+        write_atomic(&metadata_path, contents).context("Failed to write metadata")?;
+        Ok(())
+    }
+
+    /// Get the bash index file path
+    fn get_bash_index_path(&self) -> Result<PathBuf> {
+        Ok(self.path.join(BASH_INDEX_FILE_NAME))
+    }
+
+    /// Load the bash output index (empty for encrypted caches)
+    pub fn load_bash_index(&self) -> Result<BashIndex> {
+        Ok(BashIndex::load_from(&self.get_bash_index_path()?))
+    }
+
+    /// Modify the bash index and drop sessions that are no longer cached
+    ///
+    /// Must be called under the cache lock. For encrypted caches the index is
+    /// removed instead, as it would hold commands in plaintext.
+    fn update_bash_index(
+        &self,
+        metadata: &CacheMetadata,
+        f: impl FnOnce(&mut BashIndex),
+    ) -> Result<()> {
+        let path = self.get_bash_index_path()?;
+        if metadata.config.encrypt {
+            if path.exists() {
+                fs::remove_file(&path).context("Failed to remove bash index")?;
+            }
+            return Ok(());
+        }
+
+        let mut index = BashIndex::load_from(&path);
+        f(&mut index);
+        index.retain_sessions(&metadata.access_order);
+        index.save_to(&path)
+    }
+
+    /// Load cached activities for a session
+    ///
+    /// Files stored in another format (e.g. uncompressed files left by older
+    /// versions) are rewritten in the configured format on first read.
+    ///
+    /// With the LRU policy this counts as an access and moves the session to the
+    /// back of the eviction order; use [`peek_session_cache`] to avoid that.
+    pub fn load_session_cache(&self, session_id: &str) -> Result<Option<SessionCache>> {
+        self.with_cache_lock(|| {
+            let Some((cache, stored)) = self.read_session_file(session_id)? else {
+                return Ok(None);
+            };
+
+            let mut metadata = self.load_metadata()?;
+            let desired = StorageFormat::from(&metadata.config);
+            if stored != desired {
+                self.write_session_file(&cache, desired)?;
+            }
+
+            if metadata.config.eviction == EvictionPolicy::Lru {
+                let key = cache_key(session_id)?;
+                if metadata.access_order.last() != Some(&key)
+                    && metadata.access_order.contains(&key)
+                {
+                    metadata.access_order.retain(|id| id != &key);
+                    metadata.access_order.push(key);
+                    self.save_metadata(&metadata)?;
+                }
+            }
+
+            Ok(Some(cache))
+        })
+    }
+
+    /// Load cached activities without counting as an access or migrating files
+    pub fn peek_session_cache(&self, session_id: &str) -> Result<Option<SessionCache>> {
+        Ok(self.read_session_file(session_id)?.map(|(cache, _)| cache))
+    }
+
+    /// Save cached activities for a session
+    pub fn save_session_cache(&self, cache: &SessionCache) -> Result<()> {
+        self.with_cache_lock(|| {
+            let key = cache_key(&cache.session_id)?;
+            let mut metadata = self.load_metadata()?;
+            self.write_session_file(cache, StorageFormat::from(&metadata.config))?;
+
+            // Update metadata access order: FIFO keeps the first position, LRU moves
+            // the session to the end (most recently accessed)
+            let present = metadata.access_order.contains(&key);
+            if !present || metadata.config.eviction == EvictionPolicy::Lru {
+                metadata.access_order.retain(|id| id != &key);
+                metadata.access_order.push(key.clone());
+            }
+
+            for session_id in self.select_evictions(&metadata, Some(&key)) {
+                self.remove_session_files(&session_id)?;
+                metadata.access_order.retain(|id| id != &session_id);
+                metadata.metrics.sessions.remove(&session_id);
+            }
+
+            self.save_metadata(&metadata)?;
+            self.update_bash_index(&metadata, |index| {
+                index.update_session(&key, &cache.activities)
+            })
+        })
+    }
+
+    /// Evict sessions until the cache fits its configured limits
+    ///
+    /// Returns the IDs of the evicted sessions.
+    pub fn enforce_cache_limits(&self) -> Result<Vec<String>> {
+        self.with_cache_lock(|| {
+            let mut metadata = self.load_metadata()?;
+            let evicted = self.select_evictions(&metadata, None);
+            if !evicted.is_empty() {
+                for session_id in &evicted {
+                    self.remove_session_files(session_id)?;
+                }
+                metadata.access_order.retain(|id| !evicted.contains(id));
+                metadata
+                    .metrics
+                    .sessions
+                    .retain(|id, _| !evicted.contains(id));
+                self.save_metadata(&metadata)?;
+                self.update_bash_index(&metadata, |_| {})?;
+            }
+            Ok(evicted)
+        })
+    }
+
+    /// Total size of the files stored for a session
+    fn session_file_size(&self, session_id: &str) -> u64 {
+        [
+            self.get_session_cache_path(session_id),
+            self.get_compressed_cache_path(session_id),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum()
+    }
+
+    /// Sessions to evict so the cache fits `max_sessions` and `max_size_mb`
+    ///
+    /// Walks `access_order` from the front; pinned sessions and `keep` (the
+    /// session just written) are never evicted.
+    fn select_evictions(&self, metadata: &CacheMetadata, keep: Option<&str>) -> Vec<String> {
+        let config = &metadata.config;
+        let max_bytes = config.max_size_mb.map(|mb| mb.saturating_mul(1024 * 1024));
+
+        let mut remaining = metadata.access_order.len();
+        let mut total_bytes: u64 = match max_bytes {
+            Some(_) => metadata
+                .access_order
+                .iter()
+                .map(|id| self.session_file_size(id))
+                .sum(),
+            None => 0,
+        };
+
+        let mut evicted = Vec::new();
+        for session_id in &metadata.access_order {
+            let over_count = remaining > config.max_sessions;
+            let over_size = max_bytes.is_some_and(|max| total_bytes > max);
+            if !over_count && !over_size {
+                break;
+            }
+            if keep == Some(session_id.as_str()) || metadata.pinned.contains(session_id) {
+                continue;
+            }
+
+            if max_bytes.is_some() {
+                total_bytes = total_bytes.saturating_sub(self.session_file_size(session_id));
+            }
+            remaining -= 1;
+            evicted.push(session_id.clone());
+        }
+        evicted
+    }
+
+    /// Delete cache for a specific session
+    pub fn delete_session_cache(&self, session_id: &str) -> Result<()> {
+        self.with_cache_lock(|| {
+            self.remove_session_files(session_id)?;
+
+            // Update metadata
+            let key = cache_key(session_id)?;
+            let mut metadata = self.load_metadata()?;
+            metadata.access_order.retain(|id| id != &key);
+            metadata.metrics.sessions.remove(&key);
+            self.save_metadata(&metadata)?;
+            self.update_bash_index(&metadata, |_| {})?;
+
+            Ok(())
+        })
+    }
+
+    /// Clear all cached activities
+    pub fn clear_all_cache(&self) -> Result<()> {
+        self.with_cache_lock(|| {
+            let metadata = self.load_metadata().unwrap_or_default();
+            self.remove_cache_entries()?;
+
+            // Recreate empty cache, keeping the configuration, pins and the
+            // lifetime counters
+            self.save_metadata(&CacheMetadata {
+                access_order: Vec::new(),
+                config: metadata.config,
+                pinned: metadata.pinned,
+                metrics: CacheMetrics {
+                    total: metadata.metrics.total,
+                    sessions: BTreeMap::new(),
+                },
+                ..Default::default()
+            })?;
+
+            Ok(())
+        })
+    }
+
+    /// Remove everything in the cache directory but the lock file, which other
+    /// processes may be waiting on
+    fn remove_cache_entries(&self) -> Result<()> {
+        let cache_dir = &self.path;
+        for entry in fs::read_dir(cache_dir).context("Failed to read cache directory")? {
+            let entry = entry?;
+            if entry.file_name() == LOCK_FILE_NAME {
+                continue;
+            }
+            let path = entry.path();
+            if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            }
+            .context("Failed to clear cache directory")?;
+        }
+        Ok(())
+    }
+
+    /// Rewrite every cached session in the format given by `config`
+    ///
+    /// Returns the number of session files that were converted.
+    pub fn migrate_cache_files(&self, config: &ActivityCacheConfig) -> Result<usize> {
+        self.with_cache_lock(|| {
+            let metadata = self.load_metadata()?;
+            let desired = StorageFormat::from(config);
+            let mut migrated = 0;
+
+            for session_id in &metadata.access_order {
+                if let Some((cache, stored)) = self.read_session_file(session_id)? {
+                    if stored != desired {
+                        self.write_session_file(&cache, desired)?;
+                        migrated += 1;
+                    }
+                }
+            }
 
-    if cache_path.exists() {
-        fs::remove_file(&cache_path)
-            .context(format!("Failed to delete cache for session {}", session_id))?;
+            Ok(migrated)
+        })
     }
 
-    // Update metadata
-    let mut metadata = load_metadata()?;
-    metadata.access_order.retain(|id| id != session_id);
-    save_metadata(&metadata)?;
+    /// Apply the user's `[cache]` settings to the cache metadata
+    ///
+    /// Converts existing session files when compression or encryption changes.
+    pub fn apply_cache_config(&self, config: &crate::config::CacheConfig) -> Result<()> {
+        self.with_cache_lock(|| {
+            let mut metadata = self.load_metadata()?;
+            let desired = ActivityCacheConfig {
+                enabled: config.enabled,
+                max_sessions: config.max_sessions,
+                eviction: config.eviction,
+                max_size_mb: config.max_size_mb,
+                ttl_seconds: config.ttl_seconds,
+                compress: config.compress,
+                encrypt: config.encrypt,
+            };
 
-    Ok(())
+            let format_changed =
+                StorageFormat::from(&metadata.config) != StorageFormat::from(&desired);
+
+            if metadata.config != desired {
+                metadata.config = desired.clone();
+                self.save_metadata(&metadata)?;
+                self.update_bash_index(&metadata, |_| {})?;
+            }
+            if format_changed {
+                self.migrate_cache_files(&desired)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Check the cache files against the metadata
+    ///
+    /// Detects metadata entries without a file, session files missing from the
+    /// metadata, files that cannot be read (e.g. half-written), duplicate
+    /// entries, temporary files left by interrupted writes and an unparseable
+    /// `metadata.json`. With `repair`, corrupt and temporary files are deleted
+    /// and the metadata is rebuilt from the readable files; orphaned sessions
+    /// are re-added in order of their creation.
+    pub fn verify_cache(&self, repair: bool) -> Result<VerifyReport> {
+        self.with_cache_lock(|| {
+            let mut report = VerifyReport::default();
+            let cache_dir = &self.path;
+            if !cache_dir.exists() {
+                return Ok(report);
+            }
+
+            let metadata_path = self.get_metadata_path()?;
+            let mut metadata = if metadata_path.exists() {
+                let contents =
+                    fs::read_to_string(&metadata_path).context("Failed to read metadata")?;
+                serde_json::from_str(&contents).unwrap_or_else(|_| {
+                    report.metadata_corrupt = true;
+                    CacheMetadata::default()
+                })
+            } else {
+                CacheMetadata::default()
+            };
+
+            let mut on_disk = BTreeSet::new();
+            for entry in fs::read_dir(cache_dir).context("Failed to read cache directory")? {
+                let name = entry?.file_name();
+                let Some(name) = name.to_str() else {
+                    continue;
+                };
+                if is_temp_file(name) {
+                    report.stale_temp_files.push(name.to_string());
+                } else if let Some(id) = session_id_from_file_name(name) {
+                    on_disk.insert(id);
+                }
+            }
+            report.checked_files = on_disk.len();
+
+            // Without the key every encrypted file would look corrupt
+            if on_disk.iter().any(|id| self.is_session_file_encrypted(id)) {
+                self.key()?;
+            }
+
+            let mut seen = HashSet::new();
+            for id in &metadata.access_order {
+                if !seen.insert(id) {
+                    report.duplicate_entries.push(id.clone());
+                } else if !on_disk.contains(id) {
+                    report.missing_files.push(id.clone());
+                }
+            }
+
+            let mut orphans = Vec::new();
+            for id in &on_disk {
+                let error = match self.read_session_file(id) {
+                    Ok(Some((cache, _))) if cache.session_id == *id => {
+                        if !seen.contains(id) {
+                            orphans.push((cache.created_at, id.clone()));
+                        }
+                        continue;
+                    }
+                    Ok(Some((cache, _))) => format!("File contains session {}", cache.session_id),
+                    Ok(None) => continue,
+                    Err(e) => format!("{:#}", e),
+                };
+                report.corrupt_files.push(CorruptFile {
+                    session_id: id.clone(),
+                    error,
+                });
+            }
+            orphans.sort();
+            report.orphaned_files = orphans.iter().map(|(_, id)| id.clone()).collect();
+
+            if repair && !report.is_healthy() {
+                let corrupt: Vec<&String> =
+                    report.corrupt_files.iter().map(|c| &c.session_id).collect();
+                for id in &corrupt {
+                    self.remove_session_files(id)?;
+                }
+                for name in &report.stale_temp_files {
+                    fs::remove_file(cache_dir.join(name))
+                        .context("Failed to remove temporary cache file")?;
+                }
+
+                let mut kept = HashSet::new();
+                metadata.access_order.retain(|id| {
+                    on_disk.contains(id) && !corrupt.contains(&id) && kept.insert(id.clone())
+                });
+                metadata
+                    .access_order
+                    .extend(report.orphaned_files.iter().cloned());
+                self.save_metadata(&metadata)?;
+                report.repaired = true;
+            }
+
+            Ok(report)
+        })
+    }
+
+    /// List all cached session IDs
+    pub fn list_cached_sessions(&self) -> Result<Vec<String>> {
+        let metadata = self.load_metadata()?;
+        Ok(metadata.access_order.clone())
+    }
+
+    /// Record how a read of a session was served
+    ///
+    /// `cached_activities` is the number of activities in the cache after the
+    /// read; it determines how many API pages a full fetch would have needed.
+    pub fn record_cache_access(
+        &self,
+        session_id: &str,
+        access: CacheAccess,
+        cached_activities: usize,
+    ) -> Result<()> {
+        let page_size = ACTIVITIES_PAGE_SIZE as usize;
+        let full_fetch_pages = cached_activities.div_ceil(page_size).max(1);
+        let fetched_pages = match access {
+            CacheAccess::Hit => 0,
+            CacheAccess::Miss => full_fetch_pages,
+            CacheAccess::IncrementalUpdate { new_activities } => new_activities / page_size + 1,
+        };
+        let saved = full_fetch_pages.saturating_sub(fetched_pages) as u64;
+
+        self.with_cache_lock(|| {
+            let key = cache_key(session_id)?;
+            let mut metadata = self.load_metadata()?;
+            metadata.metrics.total.record(access, saved);
+            metadata
+                .metrics
+                .sessions
+                .entry(key)
+                .or_default()
+                .record(access, saved);
+            self.save_metadata(&metadata)
+        })
+    }
+
+    /// Drop the per-session counters of sessions not in `session_ids`
+    ///
+    /// For backends that evict without going through the metadata (SQLite).
+    pub fn retain_session_metrics(&self, session_ids: &[String]) -> Result<()> {
+        self.with_cache_lock(|| {
+            let mut metadata = self.load_metadata()?;
+            let before = metadata.metrics.sessions.len();
+            metadata
+                .metrics
+                .sessions
+                .retain(|id, _| session_ids.contains(id));
+            if metadata.metrics.sessions.len() != before {
+                self.save_metadata(&metadata)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Hit/miss counters of the cache
+    pub fn cache_metrics(&self) -> Result<CacheMetrics> {
+        Ok(self.load_metadata()?.metrics)
+    }
+
+    /// Pin a session so it is never evicted
+    ///
+    /// Returns `false` if the session was already pinned. Sessions can be pinned
+    /// before they are cached.
+    pub fn pin_session(&self, session_id: &str) -> Result<bool> {
+        self.with_cache_lock(|| {
+            let key = cache_key(session_id)?;
+            let mut metadata = self.load_metadata()?;
+            if metadata.pinned.contains(&key) {
+                return Ok(false);
+            }
+            metadata.pinned.push(key);
+            self.save_metadata(&metadata)?;
+            Ok(true)
+        })
+    }
+
+    /// Unpin a session, making it subject to eviction again
+    ///
+    /// Returns `false` if the session was not pinned.
+    pub fn unpin_session(&self, session_id: &str) -> Result<bool> {
+        self.with_cache_lock(|| {
+            let key = cache_key(session_id)?;
+            let mut metadata = self.load_metadata()?;
+            let before = metadata.pinned.len();
+            metadata.pinned.retain(|id| id != &key);
+            if metadata.pinned.len() == before {
+                return Ok(false);
+            }
+            self.save_metadata(&metadata)?;
+            Ok(true)
+        })
+    }
+
+    /// Pinned session IDs
+    pub fn pinned_sessions(&self) -> Result<Vec<String>> {
+        Ok(self.load_metadata()?.pinned)
+    }
+
+    /// Get cache statistics
+    pub fn get_cache_stats(&self) -> Result<CacheStats> {
+        let metadata = self.load_metadata()?;
+        let cache_dir = &self.path;
+
+        let mut sessions = Vec::with_capacity(metadata.access_order.len());
+        let mut encrypted_sessions = 0;
+
+        for session_id in &metadata.access_order {
+            if self.is_session_file_encrypted(session_id) {
+                encrypted_sessions += 1;
+            }
+            let cache = self.peek_session_cache(session_id).ok().flatten();
+            sessions.push(SessionStats {
+                session_id: session_id.clone(),
+                activities: cache.as_ref().map_or(0, |c| c.activities.len()),
+                size_bytes: self.session_file_size(session_id),
+                last_updated: cache.map(|c| c.last_updated),
+                pinned: metadata.pinned.contains(session_id),
+                counters: metadata
+                    .metrics
+                    .sessions
+                    .get(session_id)
+                    .copied()
+                    .unwrap_or_default(),
+            });
+        }
+
+        Ok(CacheStats {
+            enabled: metadata.config.enabled,
+            total_sessions: metadata.access_order.len(),
+            max_sessions: metadata.config.max_sessions,
+            total_activities: sessions.iter().map(|s| s.activities).sum(),
+            total_size_bytes: sessions.iter().map(|s| s.size_bytes).sum(),
+            cache_dir: cache_dir.display().to_string(),
+            encryption_enabled: metadata.config.encrypt,
+            encrypted_sessions,
+            metrics: metadata.metrics,
+            sessions,
+        })
+    }
+
+    /// Update cache with new API response (incremental update)
+    pub fn update_cache_incremental(
+        &self,
+        session_id: &str,
+        response: &ListActivitiesResponse,
+    ) -> Result<SessionCache> {
+        self.with_cache_lock(|| {
+            let now = Utc::now();
+            let key = cache_key(session_id)?;
+
+            let mut cache = self
+                .load_session_cache(&key)?
+                .unwrap_or_else(|| SessionCache {
+                    session_id: key,
+                    activities: Vec::new(),
+                    last_page_token: None,
+                    last_updated: now,
+                    created_at: now,
+                });
+
+            // Merge new activities (deduplication)
+            cache.activities = merge_activities(cache.activities, response.activities.clone());
+
+            // Update metadata
+            cache.last_page_token = response.next_page_token.clone();
+            cache.last_updated = now;
+
+            // Save to disk
+            self.save_session_cache(&cache)?;
+
+            Ok(cache)
+        })
+    }
+}
+
+/// [`CacheDir::load_metadata`] in the default cache directory
+pub fn load_metadata() -> Result<CacheMetadata> {
+    CacheDir::open_default()?.load_metadata()
 }
 
-/// Clear all cached activities
+/// [`CacheDir::save_metadata`] in the default cache directory
+pub fn save_metadata(metadata: &CacheMetadata) -> Result<()> {
+    CacheDir::open_default()?.save_metadata(metadata)
+}
+
+/// [`CacheDir::load_bash_index`] in the default cache directory
+pub fn load_bash_index() -> Result<BashIndex> {
+    CacheDir::open_default()?.load_bash_index()
+}
+
+/// [`CacheDir::load_session_cache`] in the default cache directory
+pub fn load_session_cache(session_id: &str) -> Result<Option<SessionCache>> {
+    CacheDir::open_default()?.load_session_cache(session_id)
+}
+
+/// [`CacheDir::peek_session_cache`] in the default cache directory
+pub fn peek_session_cache(session_id: &str) -> Result<Option<SessionCache>> {
+    CacheDir::open_default()?.peek_session_cache(session_id)
+}
+
+/// [`CacheDir::save_session_cache`] in the default cache directory
+pub fn save_session_cache(cache: &SessionCache) -> Result<()> {
+    CacheDir::open_default()?.save_session_cache(cache)
+}
+
+/// [`CacheDir::enforce_cache_limits`] in the default cache directory
+pub fn enforce_cache_limits() -> Result<Vec<String>> {
+    CacheDir::open_default()?.enforce_cache_limits()
+}
+
+/// [`CacheDir::delete_session_cache`] in the default cache directory
+pub fn delete_session_cache(session_id: &str) -> Result<()> {
+    CacheDir::open_default()?.delete_session_cache(session_id)
+}
+
+/// [`CacheDir::clear_all_cache`] in the default cache directory
 pub fn clear_all_cache() -> Result<()> {
-    let cache_dir = get_cache_dir()?;
+    CacheDir::open_default()?.clear_all_cache()
+}
 
-    if cache_dir.exists() {
-        fs::remove_dir_all(&cache_dir).context("Failed to clear cache directory")?;
-    }
+/// [`CacheDir::migrate_cache_files`] in the default cache directory
+pub fn migrate_cache_files(config: &ActivityCacheConfig) -> Result<usize> {
+    CacheDir::open_default()?.migrate_cache_files(config)
+}
 
-    // Recreate empty cache
-    fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
-    save_metadata(&CacheMetadata::default())?;
+/// [`CacheDir::apply_cache_config`] in the default cache directory
+pub fn apply_cache_config(config: &crate::config::CacheConfig) -> Result<()> {
+    CacheDir::open_default()?.apply_cache_config(config)
+}
 
-    Ok(())
+/// [`CacheDir::verify_cache`] in the default cache directory
+pub fn verify_cache(repair: bool) -> Result<VerifyReport> {
+    CacheDir::open_default()?.verify_cache(repair)
 }
 
-/// List all cached session IDs
+/// [`CacheDir::list_cached_sessions`] in the default cache directory
 pub fn list_cached_sessions() -> Result<Vec<String>> {
-    let metadata = load_metadata()?;
-    Ok(metadata.access_order.clone())
+    CacheDir::open_default()?.list_cached_sessions()
+}
+
+/// [`CacheDir::record_cache_access`] in the default cache directory
+pub fn record_cache_access(
+    session_id: &str,
+    access: CacheAccess,
+    cached_activities: usize,
+) -> Result<()> {
+    CacheDir::open_default()?.record_cache_access(session_id, access, cached_activities)
+}
+
+/// [`CacheDir::retain_session_metrics`] in the default cache directory
+pub fn retain_session_metrics(session_ids: &[String]) -> Result<()> {
+    CacheDir::open_default()?.retain_session_metrics(session_ids)
+}
+
+/// [`CacheDir::cache_metrics`] in the default cache directory
+pub fn cache_metrics() -> Result<CacheMetrics> {
+    CacheDir::open_default()?.cache_metrics()
+}
+
+/// [`CacheDir::pin_session`] in the default cache directory
+pub fn pin_session(session_id: &str) -> Result<bool> {
+    CacheDir::open_default()?.pin_session(session_id)
+}
+
+/// [`CacheDir::unpin_session`] in the default cache directory
+pub fn unpin_session(session_id: &str) -> Result<bool> {
+    CacheDir::open_default()?.unpin_session(session_id)
 }
 
-/// Get cache statistics
+/// [`CacheDir::pinned_sessions`] in the default cache directory
+pub fn pinned_sessions() -> Result<Vec<String>> {
+    CacheDir::open_default()?.pinned_sessions()
+}
+
+/// [`CacheDir::get_cache_stats`] in the default cache directory
 pub fn get_cache_stats() -> Result<CacheStats> {
-    let metadata = load_metadata()?;
-    let cache_dir = get_cache_dir()?;
+    CacheDir::open_default()?.get_cache_stats()
+}
 
-    let mut total_activities = 0;
-    let mut total_size_bytes = 0u64;
+/// [`CacheDir::update_cache_incremental`] in the default cache directory
+pub fn update_cache_incremental(
+    session_id: &str,
+    response: &ListActivitiesResponse,
+) -> Result<SessionCache> {
+    CacheDir::open_default()?.update_cache_incremental(session_id, response)
+}
 
-    for session_id in &metadata.access_order {
-        if let Ok(Some(cache)) = load_session_cache(session_id) {
-            total_activities += cache.activities.len();
-        }
+/// Whether parsing failed because the file is not valid JSON (corruption
+/// rather than an old schema)
+fn is_syntax_error(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<serde_json::Error>()
+        .is_some_and(|e| e.is_syntax() || e.is_eof())
+}
 
-        if let Ok(path) = get_session_cache_path(session_id) {
-            if let Ok(meta) = fs::metadata(&path) {
-                total_size_bytes += meta.len();
-            }
-        }
+/// A session file that could not be read
+#[derive(Debug, Clone, Serialize)]
+pub struct CorruptFile {
+    pub session_id: String,
+    pub error: String,
+}
+
+/// Result of [`verify_cache`]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerifyReport {
+    /// Number of session files found on disk
+    pub checked_files: usize,
+    /// `metadata.json` could not be parsed
+    pub metadata_corrupt: bool,
+    /// Sessions listed in the metadata without a cache file
+    pub missing_files: Vec<String>,
+    /// Readable session files not listed in the metadata
+    pub orphaned_files: Vec<String>,
+    /// Session files that fail to decrypt, decompress or parse
+    pub corrupt_files: Vec<CorruptFile>,
+    /// Sessions listed more than once in the metadata
+    pub duplicate_entries: Vec<String>,
+    /// Temporary files left by interrupted writes
+    pub stale_temp_files: Vec<String>,
+    /// Whether the problems were repaired
+    pub repaired: bool,
+}
+
+impl VerifyReport {
+    /// Whether no problems were found
+    pub fn is_healthy(&self) -> bool {
+        !self.metadata_corrupt
+            && self.missing_files.is_empty()
+            && self.orphaned_files.is_empty()
+            && self.corrupt_files.is_empty()
+            && self.duplicate_entries.is_empty()
+            && self.stale_temp_files.is_empty()
     }
+}
 
-    Ok(CacheStats {
-        enabled: metadata.config.enabled,
-        total_sessions: metadata.access_order.len(),
-        max_sessions: metadata.config.max_sessions,
-        total_activities,
-        total_size_bytes,
-        cache_dir: cache_dir.display().to_string(),
-    })
+/// Session ID of a cache file name (`{id}.json` or `{id}.json.zst`)
+fn session_id_from_file_name(name: &str) -> Option<String> {
+    if name == METADATA_FILE_NAME || name == BASH_INDEX_FILE_NAME {
+        return None;
+    }
+    let id = name
+        .strip_suffix(".json.zst")
+        .or_else(|| name.strip_suffix(".json"))?;
+    cache_key(id).ok().filter(|key| key == id)
 }
 
 /// Cache statistics
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct CacheStats {
     pub enabled: bool,
     pub total_sessions: usize,
@@ -235,6 +1301,69 @@ pub struct CacheStats {
     pub total_activities: usize,
     pub total_size_bytes: u64,
     pub cache_dir: String,
+    /// Whether new session files are written encrypted
+    pub encryption_enabled: bool,
+    /// Number of session files currently encrypted on disk
+    pub encrypted_sessions: usize,
+    /// Hit/miss counters
+    pub metrics: CacheMetrics,
+    /// Per-session breakdown, next to be evicted first
+    pub sessions: Vec<SessionStats>,
+}
+
+/// Statistics of one cached session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionStats {
+    pub session_id: String,
+    pub activities: usize,
+    /// Size on disk (for SQLite, the size of the stored activity data)
+    pub size_bytes: u64,
+    /// `None` if the cache could not be read
+    pub last_updated: Option<DateTime<Utc>>,
+    pub pinned: bool,
+    pub counters: CacheCounters,
+}
+
+/// Order of the per-session breakdown in [`CacheStats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStatsSort {
+    /// Largest first
+    Size,
+    /// Most activities first
+    Activities,
+    /// Most recently updated first
+    Updated,
+    /// Most cache hits first
+    Hits,
+}
+
+impl SessionStatsSort {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "size" => Ok(Self::Size),
+            "activities" => Ok(Self::Activities),
+            "updated" | "last-updated" => Ok(Self::Updated),
+            "hits" => Ok(Self::Hits),
+            _ => anyhow::bail!(
+                "Unknown sort key: {}. Valid options: size, activities, updated, hits",
+                s
+            ),
+        }
+    }
+}
+
+impl CacheStats {
+    /// Sort the per-session breakdown (stable, so ties keep eviction order)
+    pub fn sort_sessions(&mut self, sort: SessionStatsSort) {
+        use std::cmp::Reverse;
+
+        match sort {
+            SessionStatsSort::Size => self.sessions.sort_by_key(|s| Reverse(s.size_bytes)),
+            SessionStatsSort::Activities => self.sessions.sort_by_key(|s| Reverse(s.activities)),
+            SessionStatsSort::Updated => self.sessions.sort_by_key(|s| Reverse(s.last_updated)),
+            SessionStatsSort::Hits => self.sessions.sort_by_key(|s| Reverse(s.counters.hits)),
+        }
+    }
 }
 
 /// Merge new activities into cache (deduplication by ID)
@@ -258,34 +1387,6 @@ pub fn merge_activities(existing: Vec<Activity>, new_activities: Vec<Activity>)
     result
 }
 
-/// Update cache with new API response (incremental update)
-pub fn update_cache_incremental(
-    session_id: &str,
-    response: &ListActivitiesResponse,
-) -> Result<SessionCache> {
-    let now = Utc::now();
-
-    let mut cache = load_session_cache(session_id)?.unwrap_or_else(|| SessionCache {
-        session_id: session_id.to_string(),
-        activities: Vec::new(),
-        last_page_token: None,
-        last_updated: now,
-        created_at: now,
-    });
-
-    // Merge new activities (deduplication)
-    cache.activities = merge_activities(cache.activities, response.activities.clone());
-
-    // Update metadata
-    cache.last_page_token = response.next_page_token.clone();
-    cache.last_updated = now;
-
-    // Save to disk
-    save_session_cache(&cache)?;
-
-    Ok(cache)
-}
-
 /// Fetch all activities with pagination (up to MAX_ACTIVITIES_TO_FETCH)
 pub async fn fetch_all_activities(
     client: &jules_rs::JulesClient,
@@ -293,6 +1394,7 @@ pub async fn fetch_all_activities(
 ) -> Result<Vec<Activity>> {
     let mut all_activities = Vec::new();
     let mut page_token: Option<String> = None;
+    let spinner = Spinner::start("Fetching activities...");
 
     // Fetch up to MAX_ACTIVITIES_TO_FETCH activities total
     while all_activities.len() < MAX_ACTIVITIES_TO_FETCH {
@@ -305,6 +1407,10 @@ pub async fn fetch_all_activities(
             .await?;
 
         all_activities.extend(response.activities);
+        spinner.set_message(format!(
+            "Fetching activities... {} so far",
+            all_activities.len()
+        ));
 
         // Check if there's more data
         if response.next_page_token.is_none() || all_activities.len() >= 100 {
@@ -313,9 +1419,68 @@ pub async fn fetch_all_activities(
 
         page_token = response.next_page_token;
     }
+    spinner.finish();
 
     // Sort by creation time (newest first)
     all_activities.sort_by(|a, b| b.create_time.cmp(&a.create_time));
 
     Ok(all_activities)
 }
+
+/// Fetch activities that are not in `known` (the cached activities)
+///
+/// Walks pages from the start of the listing until a page reaches already
+/// cached history: an activity with a known ID or a creation time no newer
+/// than the newest cached one. Pages are only treated as the boundary when
+/// they are ordered newest first, so an oldest-first listing is walked to the
+/// end instead of leaving a gap. Page tokens are never reused across runs,
+/// so expired tokens cannot break updates.
+pub async fn fetch_new_activities(
+    client: &jules_rs::JulesClient,
+    session_id: &str,
+    known: &[Activity],
+) -> Result<Vec<Activity>> {
+    if known.is_empty() {
+        return fetch_all_activities(client, session_id).await;
+    }
+
+    let known_ids: HashSet<&str> = known.iter().map(|a| a.id.as_str()).collect();
+    let newest_known = known.iter().map(|a| a.create_time.as_str()).max();
+
+    let mut new_activities = Vec::new();
+    let mut page_token: Option<String> = None;
+
+    for _ in 0..MAX_SYNC_PAGES {
+        let response = client
+            .list_activities(
+                session_id,
+                Some(ACTIVITIES_PAGE_SIZE),
+                page_token.as_deref(),
+            )
+            .await?;
+
+        let newest_first = response
+            .activities
+            .windows(2)
+            .all(|pair| pair[0].create_time >= pair[1].create_time);
+        let reached_known = response.activities.iter().any(|a| {
+            known_ids.contains(a.id.as_str())
+                || newest_known.is_some_and(|newest| a.create_time.as_str() <= newest)
+        });
+
+        new_activities.extend(
+            response
+                .activities
+                .into_iter()
+                .filter(|a| !known_ids.contains(a.id.as_str())),
+        );
+
+        if (reached_known && newest_first) || response.next_page_token.is_none() {
+            break;
+        }
+        page_token = response.next_page_token;
+    }
+
+    new_activities.sort_by(|a, b| b.create_time.cmp(&a.create_time));
+    Ok(new_activities)
+}
@@ -0,0 +1,451 @@
+//! Storage-agnostic interface to the activity cache.
+//!
+//! [`ActivityStore`] abstracts over the cache backends: per-session files
+//! ([`FileStore`], the default), a single SQLite database
+//! ([`crate::SqliteCache`]) and a non-persistent [`MemoryStore`] for tests.
+//! Commands open the configured backend with [`open_store`] and never need
+//! to know which one they talk to.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use jules_rs::types::activity::{Activity, ListActivitiesResponse};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::activity_cache::{
+    self, cache_key, merge_activities, CacheDir, CacheStats, SessionCache, SessionStats,
+};
+use crate::bash_index::BashIndexEntry;
+use crate::config::{CacheBackend, CacheConfig};
+use crate::patch_files;
+use crate::sqlite_cache::{ActivityQuery, SqliteCache};
+
+/// A cache of session activities
+pub trait ActivityStore: Send {
+    /// Load a session's cache, counting as an access (e.g. for LRU eviction)
+    fn load_session(&self, session_id: &str) -> Result<Option<SessionCache>>;
+
+    /// Load a session's cache without side effects
+    fn peek_session(&self, session_id: &str) -> Result<Option<SessionCache>> {
+        self.load_session(session_id)
+    }
+
+    /// Replace a session's cache
+    fn save_session(&mut self, cache: &SessionCache) -> Result<()>;
+
+    /// Merge newly fetched activities into a session's cache (deduplicated
+    /// by ID), creating it if needed
+    fn merge_activities(&mut self, session_id: &str, activities: Vec<Activity>) -> Result<()>;
+
+    /// Remove a session's cache
+    fn delete_session(&mut self, session_id: &str) -> Result<()>;
+
+    /// Remove every cached session
+    fn clear(&mut self) -> Result<()>;
+
+    /// Cached session IDs, next to be evicted first
+    fn list_sessions(&self) -> Result<Vec<String>>;
+
+    /// Evict sessions until the configured limits are met
+    fn evict(&mut self) -> Result<()>;
+
+    /// Cache statistics
+    fn stats(&self) -> Result<CacheStats>;
+
+    /// Whether a session's cache can be served without an API call
+    fn is_fresh(&self, session_id: &str, ttl_seconds: u64) -> Result<bool> {
+        Ok(self
+            .peek_session(session_id)?
+            .is_some_and(|cache| cache.is_fresh(ttl_seconds)))
+    }
+
+    /// Cached activities matching `query`, newest first
+    fn query(&self, query: &ActivityQuery) -> Result<Vec<Activity>> {
+        default_query(self, query)
+    }
+}
+
+/// [`ActivityStore::query`] by loading and filtering whole sessions
+fn default_query<S: ActivityStore + ?Sized>(
+    store: &S,
+    query: &ActivityQuery,
+) -> Result<Vec<Activity>> {
+    let sessions = match &query.session_id {
+        Some(session_id) => vec![cache_key(session_id)?],
+        None => store.list_sessions()?,
+    };
+
+    let mut activities = Vec::new();
+    for session_id in &sessions {
+        let cache = if query.session_id.is_some() {
+            store.load_session(session_id)?
+        } else {
+            store.peek_session(session_id)?
+        };
+        if let Some(cache) = cache {
+            activities.extend(
+                cache
+                    .activities
+                    .into_iter()
+                    .filter(|activity| matches_query(activity, query)),
+            );
+        }
+    }
+
+    sort_and_limit(&mut activities, query.limit);
+    Ok(activities)
+}
+
+fn sort_and_limit(activities: &mut Vec<Activity>, limit: Option<usize>) {
+    activities.sort_by(|a, b| b.create_time.cmp(&a.create_time));
+    if let Some(limit) = limit {
+        activities.truncate(limit);
+    }
+}
+
+/// Whether an activity passes the filters of `query` (ignores session/limit)
+pub fn matches_query(activity: &Activity, query: &ActivityQuery) -> bool {
+    if !query.activity_types.is_empty() {
+        let activity_type = activity.activity_type();
+        if !query.activity_types.contains(&activity_type) {
+            return false;
+        }
+    }
+
+    let bash = activity
+        .artifacts
+        .iter()
+        .find_map(|artifact| artifact.bash_output.as_ref());
+    if query.has_bash_output && bash.is_none() {
+        return false;
+    }
+    if let Some(code) = query.exit_code {
+        if bash.and_then(|b| b.exit_code) != Some(code) {
+            return false;
+        }
+    }
+    if query.failed_only && bash.and_then(|b| b.exit_code).is_none_or(|code| code == 0) {
+        return false;
+    }
+
+    let created = parse_time(&activity.create_time);
+    if let Some(since) = query.since.as_deref().and_then(parse_time) {
+        if created.is_none_or(|created| created < since) {
+            return false;
+        }
+    }
+    if let Some(until) = query.until.as_deref().and_then(parse_time) {
+        if created.is_none_or(|created| created >= until) {
+            return false;
+        }
+    }
+    true
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+fn response(activities: Vec<Activity>) -> ListActivitiesResponse {
+    ListActivitiesResponse {
+        activities,
+        next_page_token: None,
+    }
+}
+
+/// Open the backend selected in the `[cache]` config
+///
+/// For the file backend the settings are applied to the cache metadata
+/// first, converting existing files if the storage format changed. The
+/// SQLite backend is rejected with `encrypt` set.
+pub fn open_store(config: &CacheConfig) -> Result<Box<dyn ActivityStore>> {
+    match config.backend {
+        CacheBackend::Sqlite => Ok(Box::new(SqliteCache::open_configured(config)?)),
+        CacheBackend::Json => {
+            let dir = CacheDir::open_default()?;
+            dir.apply_cache_config(config)?;
+            Ok(Box::new(FileStore::new(dir)))
+        }
+    }
+}
+
+/// The per-session file cache in a [`CacheDir`] (by default
+/// [`activity_cache::get_cache_dir`])
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    dir: CacheDir,
+}
+
+impl FileStore {
+    /// A file store in `dir`
+    pub fn new(dir: CacheDir) -> Self {
+        Self { dir }
+    }
+}
+
+impl ActivityStore for FileStore {
+    fn load_session(&self, session_id: &str) -> Result<Option<SessionCache>> {
+        self.dir.load_session_cache(session_id)
+    }
+
+    fn peek_session(&self, session_id: &str) -> Result<Option<SessionCache>> {
+        self.dir.peek_session_cache(session_id)
+    }
+
+    fn save_session(&mut self, cache: &SessionCache) -> Result<()> {
+        self.dir.save_session_cache(cache)
+    }
+
+    fn merge_activities(&mut self, session_id: &str, activities: Vec<Activity>) -> Result<()> {
+        self.dir
+            .update_cache_incremental(session_id, &response(activities))?;
+        Ok(())
+    }
+
+    fn delete_session(&mut self, session_id: &str) -> Result<()> {
+        self.dir.delete_session_cache(session_id)?;
+        patch_files::remove_session_patches(session_id)
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.dir.clear_all_cache()
+    }
+
+    fn list_sessions(&self) -> Result<Vec<String>> {
+        self.dir.list_cached_sessions()
+    }
+
+    fn evict(&mut self) -> Result<()> {
+        self.dir.enforce_cache_limits()?;
+        Ok(())
+    }
+
+    fn stats(&self) -> Result<CacheStats> {
+        self.dir.get_cache_stats()
+    }
+
+    /// Bash output filters consult the bash index first, so sessions without
+    /// a matching command are never loaded
+    fn query(&self, query: &ActivityQuery) -> Result<Vec<Activity>> {
+        if !has_bash_filter(query) {
+            return default_query(self, query);
+        }
+
+        let index = self.dir.load_bash_index()?;
+        let sessions = match &query.session_id {
+            Some(session_id) => vec![cache_key(session_id)?],
+            None => self.list_sessions()?,
+        };
+
+        let mut activities = Vec::new();
+        for session_id in &sessions {
+            // Sessions cached before the index existed are scanned in full
+            let candidates: Option<HashSet<&str>> = index.contains(session_id).then(|| {
+                index
+                    .entries(session_id)
+                    .iter()
+                    .filter(|entry| entry_matches(entry, query))
+                    .map(|entry| entry.activity_id.as_str())
+                    .collect()
+            });
+            if candidates.as_ref().is_some_and(HashSet::is_empty) {
+                continue;
+            }
+
+            let cache = if query.session_id.is_some() {
+                self.load_session(session_id)?
+            } else {
+                self.peek_session(session_id)?
+            };
+            if let Some(cache) = cache {
+                activities.extend(cache.activities.into_iter().filter(|activity| {
+                    candidates
+                        .as_ref()
+                        .is_none_or(|ids| ids.contains(activity.id.as_str()))
+                        && matches_query(activity, query)
+                }));
+            }
+        }
+
+        sort_and_limit(&mut activities, query.limit);
+        Ok(activities)
+    }
+}
+
+fn has_bash_filter(query: &ActivityQuery) -> bool {
+    query.has_bash_output || query.failed_only || query.exit_code.is_some()
+}
+
+/// Whether an index entry passes the bash output filters of `query`
+fn entry_matches(entry: &BashIndexEntry, query: &ActivityQuery) -> bool {
+    query
+        .exit_code
+        .is_none_or(|code| entry.exit_code == Some(code))
+        && (!query.failed_only || entry.failed())
+}
+
+impl ActivityStore for SqliteCache {
+    fn load_session(&self, session_id: &str) -> Result<Option<SessionCache>> {
+        SqliteCache::load_session(self, session_id)
+    }
+
+    fn peek_session(&self, session_id: &str) -> Result<Option<SessionCache>> {
+        SqliteCache::peek_session(self, session_id)
+    }
+
+    fn save_session(&mut self, cache: &SessionCache) -> Result<()> {
+        SqliteCache::save_session(self, cache)
+    }
+
+    fn merge_activities(&mut self, session_id: &str, activities: Vec<Activity>) -> Result<()> {
+        self.update_incremental(session_id, &response(activities))
+    }
+
+    fn delete_session(&mut self, session_id: &str) -> Result<()> {
+        SqliteCache::delete_session(self, session_id)?;
+        patch_files::remove_session_patches(session_id)
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        SqliteCache::clear(self)?;
+        patch_files::prune_session_patches(&[])
+    }
+
+    fn list_sessions(&self) -> Result<Vec<String>> {
+        SqliteCache::list_sessions(self)
+    }
+
+    fn evict(&mut self) -> Result<()> {
+        SqliteCache::evict(self)
+    }
+
+    fn stats(&self) -> Result<CacheStats> {
+        let metrics = activity_cache::cache_metrics()?;
+        let mut stats = SqliteCache::stats(self, true)?;
+        for session in &mut stats.sessions {
+            if let Some(counters) = metrics.sessions.get(&session.session_id) {
+                session.counters = *counters;
+            }
+        }
+        stats.metrics = metrics;
+        Ok(stats)
+    }
+
+    fn is_fresh(&self, session_id: &str, ttl_seconds: u64) -> Result<bool> {
+        SqliteCache::is_fresh(self, session_id, ttl_seconds)
+    }
+
+    fn query(&self, query: &ActivityQuery) -> Result<Vec<Activity>> {
+        SqliteCache::query(self, query)
+    }
+}
+
+/// Non-persistent store with FIFO eviction, for tests and one-off runs
+#[derive(Debug, Clone)]
+pub struct MemoryStore {
+    sessions: BTreeMap<String, SessionCache>,
+    /// Session IDs in insertion order (oldest first)
+    order: Vec<String>,
+    max_sessions: usize,
+}
+
+impl MemoryStore {
+    pub fn new(max_sessions: usize) -> Self {
+        Self {
+            sessions: BTreeMap::new(),
+            order: Vec::new(),
+            max_sessions,
+        }
+    }
+}
+
+impl ActivityStore for MemoryStore {
+    fn load_session(&self, session_id: &str) -> Result<Option<SessionCache>> {
+        Ok(self.sessions.get(&cache_key(session_id)?).cloned())
+    }
+
+    fn save_session(&mut self, cache: &SessionCache) -> Result<()> {
+        let key = cache_key(&cache.session_id)?;
+        if !self.order.contains(&key) {
+            self.order.push(key.clone());
+        }
+        self.sessions.insert(
+            key.clone(),
+            SessionCache {
+                session_id: key,
+                ..cache.clone()
+            },
+        );
+        self.evict()
+    }
+
+    fn merge_activities(&mut self, session_id: &str, activities: Vec<Activity>) -> Result<()> {
+        let now = Utc::now();
+        let key = cache_key(session_id)?;
+        let mut cache = self.load_session(&key)?.unwrap_or_else(|| SessionCache {
+            session_id: key,
+            activities: Vec::new(),
+            last_page_token: None,
+            last_updated: now,
+            created_at: now,
+        });
+        cache.activities = merge_activities(cache.activities, activities);
+        cache.last_updated = now;
+        self.save_session(&cache)
+    }
+
+    fn delete_session(&mut self, session_id: &str) -> Result<()> {
+        let key = cache_key(session_id)?;
+        self.sessions.remove(&key);
+        self.order.retain(|id| id != &key);
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        self.sessions.clear();
+        self.order.clear();
+        Ok(())
+    }
+
+    fn list_sessions(&self) -> Result<Vec<String>> {
+        Ok(self.order.clone())
+    }
+
+    fn evict(&mut self) -> Result<()> {
+        let excess = self.order.len().saturating_sub(self.max_sessions);
+        for session_id in self.order.drain(..excess) {
+            self.sessions.remove(&session_id);
+        }
+        Ok(())
+    }
+
+    fn stats(&self) -> Result<CacheStats> {
+        let sessions: Vec<SessionStats> = self
+            .order
+            .iter()
+            .filter_map(|id| self.sessions.get(id))
+            .map(|cache| SessionStats {
+                session_id: cache.session_id.clone(),
+                activities: cache.activities.len(),
+                size_bytes: 0,
+                last_updated: Some(cache.last_updated),
+                pinned: false,
+                counters: Default::default(),
+            })
+            .collect();
+
+        Ok(CacheStats {
+            enabled: true,
+            total_sessions: sessions.len(),
+            max_sessions: self.max_sessions,
+            total_activities: sessions.iter().map(|s| s.activities).sum(),
+            total_size_bytes: 0,
+            cache_dir: "(memory)".to_string(),
+            encryption_enabled: false,
+            encrypted_sessions: 0,
+            metrics: Default::default(),
+            sessions,
+        })
+    }
+}
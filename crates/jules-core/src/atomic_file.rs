@@ -0,0 +1,49 @@
+//! Crash-safe file writes.
+//!
+//! Cache files are written to a temporary file in the same directory, flushed
+//! to disk and then renamed over the target, so a crash or full disk leaves
+//! either the old or the new contents, never a half-written file.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Suffix of temporary files; leftovers from crashes can be removed safely
+pub const TEMP_SUFFIX: &str = ".tmp";
+
+/// Temporary path next to `path`, unique per process
+fn temp_path(path: &Path) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow::anyhow!("Invalid file path: {}", path.display()))?;
+    Ok(path.with_file_name(format!(
+        ".{}.{}{}",
+        file_name,
+        std::process::id(),
+        TEMP_SUFFIX
+    )))
+}
+
+/// Atomically replace the contents of `path`
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    let temp = temp_path(path)?;
+
+    let result = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(contents.as_ref())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result.with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Whether `file_name` is a temporary file left by [`write_atomic`]
+pub fn is_temp_file(file_name: &str) -> bool {
+    file_name.starts_with('.') && file_name.ends_with(TEMP_SUFFIX)
+}
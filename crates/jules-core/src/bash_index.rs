@@ -0,0 +1,115 @@
+//! Index of bash artifacts in the file-based activity cache.
+//!
+//! `bash_index.json` in the activity cache directory lists, per session, the
+//! command, exit code and time of every bash output. Queries for bash output
+//! (e.g. `filter-activities --failed-only`) consult it to skip sessions
+//! without matching commands instead of loading and scanning every session
+//! file. The SQLite backend keeps the same data in indexed columns.
+//!
+//! The index holds commands in plaintext, so it is not kept for encrypted
+//! caches. Sessions missing from the index (cached by older versions) are
+//! scanned as before and indexed on their next update.
+
+use anyhow::{Context, Result};
+use jules_rs::types::activity::Activity;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::atomic_file::write_atomic;
+
+/// File name of the index in the activity cache directory
+pub const BASH_INDEX_FILE_NAME: &str = "bash_index.json";
+
+/// A bash output in a cached activity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BashIndexEntry {
+    pub activity_id: String,
+    pub create_time: String,
+    pub command: Option<String>,
+    pub exit_code: Option<i32>,
+}
+
+impl BashIndexEntry {
+    /// Whether the command exited with a non-zero code
+    pub fn failed(&self) -> bool {
+        self.exit_code.is_some_and(|code| code != 0)
+    }
+}
+
+/// Bash outputs of cached sessions, keyed by bare session ID
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BashIndex {
+    #[serde(default)]
+    pub sessions: BTreeMap<String, Vec<BashIndexEntry>>,
+}
+
+impl BashIndex {
+    /// Load the index from `path` (empty if missing or unreadable)
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the index to `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string(self).context("Failed to serialize bash index")?;
+        write_atomic(path, contents).context("Failed to write bash index")
+    }
+
+    /// Replace the entries of a session
+    pub fn update_session(&mut self, session_id: &str, activities: &[Activity]) {
+        self.sessions
+            .insert(session_id.to_string(), index_entries(activities));
+    }
+
+    /// Drop sessions not in `session_ids`
+    pub fn retain_sessions(&mut self, session_ids: &[String]) {
+        self.sessions.retain(|id, _| session_ids.contains(id));
+    }
+
+    /// Whether a session has been indexed
+    pub fn contains(&self, session_id: &str) -> bool {
+        self.sessions.contains_key(session_id)
+    }
+
+    /// Entries of a session (empty if not indexed)
+    pub fn entries(&self, session_id: &str) -> &[BashIndexEntry] {
+        self.sessions.get(session_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Failed commands across all sessions as `(session_id, entry)`, newest
+    /// first
+    pub fn failed(&self) -> Vec<(&str, &BashIndexEntry)> {
+        let mut failed: Vec<(&str, &BashIndexEntry)> = self
+            .sessions
+            .iter()
+            .flat_map(|(id, entries)| entries.iter().map(move |entry| (id.as_str(), entry)))
+            .filter(|(_, entry)| entry.failed())
+            .collect();
+        failed.sort_by(|a, b| b.1.create_time.cmp(&a.1.create_time));
+        failed
+    }
+}
+
+/// Index entries for the bash outputs of `activities`, in the same order
+pub fn index_entries(activities: &[Activity]) -> Vec<BashIndexEntry> {
+    activities
+        .iter()
+        .flat_map(|activity| {
+            activity
+                .artifacts
+                .iter()
+                .filter_map(|artifact| artifact.bash_output.as_ref())
+                .map(|bash| BashIndexEntry {
+                    activity_id: activity.id.clone(),
+                    create_time: activity.create_time.clone(),
+                    command: bash.command.clone(),
+                    exit_code: bash.exit_code,
+                })
+        })
+        .collect()
+}
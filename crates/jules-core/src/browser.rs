@@ -0,0 +1,41 @@
+//! Opening URLs in the user's browser.
+
+use anyhow::{Context, Result};
+use std::process::{Command, Stdio};
+
+/// The browser command from `$BROWSER`, falling back to the platform opener
+pub fn browser_command() -> String {
+    std::env::var("BROWSER")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(target_os = "macos") {
+                "open".to_string()
+            } else if cfg!(windows) {
+                "explorer".to_string()
+            } else {
+                "xdg-open".to_string()
+            }
+        })
+}
+
+/// Open `url` in the browser without waiting for it
+///
+/// The browser command may include arguments (e.g. `firefox --new-tab`).
+pub fn open_url(url: &str) -> Result<()> {
+    let browser = browser_command();
+    let mut parts = browser.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No browser configured (set $BROWSER)"))?;
+
+    Command::new(program)
+        .args(parts)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to start browser '{}' for {}", browser, url))?;
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! Portable archives of cached sessions.
+//!
+//! `gules cache export` packs cached activities and session metadata into a
+//! zstd-compressed tar file, so an investigation can be moved to another
+//! machine or attached to a bug report and analysed offline with
+//! `gules cache import`.
+//!
+//! Layout:
+//!
+//! ```text
+//! manifest.json            format version, creation time, session IDs
+//! activities/{id}.json     one `SessionCache` per session
+//! sessions.json            cached `Session` objects, when known
+//! ```
+//!
+//! Activities are stored as plain JSON whatever the local storage format, so
+//! archives of encrypted caches are readable anywhere (and contain the
+//! decrypted data).
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::activity_cache::{cache_key, merge_activities, SessionCache};
+use crate::session_cache::CachedSession;
+
+/// Archive format version written by this build
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_PATH: &str = "manifest.json";
+const SESSIONS_PATH: &str = "sessions.json";
+const ACTIVITIES_DIR: &str = "activities/";
+
+/// zstd level for archives (written once, so favour size)
+const ZSTD_LEVEL: i32 = 9;
+
+/// Description of an archive's contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Version of gules that wrote the archive
+    pub created_by: String,
+    pub session_ids: Vec<String>,
+}
+
+/// Contents of a cache archive
+#[derive(Debug, Clone)]
+pub struct CacheArchive {
+    pub manifest: ArchiveManifest,
+    /// Cached activities, one entry per session
+    pub caches: Vec<SessionCache>,
+    /// Cached session metadata for the archived sessions
+    pub sessions: Vec<CachedSession>,
+}
+
+impl CacheArchive {
+    /// Build an archive from cached sessions
+    pub fn new(caches: Vec<SessionCache>, sessions: Vec<CachedSession>) -> Self {
+        Self {
+            manifest: ArchiveManifest {
+                format_version: ARCHIVE_FORMAT_VERSION,
+                created_at: Utc::now(),
+                created_by: format!("gules {}", env!("CARGO_PKG_VERSION")),
+                session_ids: caches.iter().map(|c| c.session_id.clone()).collect(),
+            },
+            caches,
+            sessions,
+        }
+    }
+
+    /// Write the archive to `path` as a zstd-compressed tar file
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create archive {}", path.display()))?;
+        let encoder = zstd::Encoder::new(file, ZSTD_LEVEL)?;
+        let mut builder = tar::Builder::new(encoder);
+
+        append_json(&mut builder, MANIFEST_PATH, &self.manifest)?;
+        for cache in &self.caches {
+            let key = cache_key(&cache.session_id)?;
+            append_json(
+                &mut builder,
+                &format!("{}{}.json", ACTIVITIES_DIR, key),
+                cache,
+            )?;
+        }
+        if !self.sessions.is_empty() {
+            append_json(&mut builder, SESSIONS_PATH, &self.sessions)?;
+        }
+
+        builder
+            .into_inner()?
+            .finish()
+            .context("Failed to finish archive")?;
+        Ok(())
+    }
+
+    /// Read an archive written by [`CacheArchive::write`]
+    pub fn read(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open archive {}", path.display()))?;
+        let decoder = zstd::Decoder::new(file)?;
+        let mut archive = tar::Archive::new(decoder);
+
+        let mut manifest: Option<ArchiveManifest> = None;
+        let mut caches = Vec::new();
+        let mut sessions = Vec::new();
+
+        for entry in archive.entries().context("Failed to read archive")? {
+            let mut entry = entry.context("Failed to read archive entry")?;
+            let name = entry.path()?.to_string_lossy().into_owned();
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents)?;
+
+            if name == MANIFEST_PATH {
+                manifest =
+                    Some(serde_json::from_slice(&contents).context("Invalid archive manifest")?);
+            } else if name == SESSIONS_PATH {
+                sessions = serde_json::from_slice(&contents)
+                    .context("Invalid session metadata in archive")?;
+            } else if name.starts_with(ACTIVITIES_DIR) {
+                let cache: SessionCache = serde_json::from_slice(&contents)
+                    .with_context(|| format!("Invalid session cache {} in archive", name))?;
+                // Reject names that would not round-trip as cache keys
+                cache_key(&cache.session_id)?;
+                caches.push(cache);
+            }
+        }
+
+        let manifest =
+            manifest.ok_or_else(|| anyhow::anyhow!("Not a gules cache archive (no manifest)"))?;
+        if manifest.format_version > ARCHIVE_FORMAT_VERSION {
+            anyhow::bail!(
+                "Archive format version {} is newer than supported ({}); upgrade gules",
+                manifest.format_version,
+                ARCHIVE_FORMAT_VERSION
+            );
+        }
+
+        Ok(Self {
+            manifest,
+            caches,
+            sessions,
+        })
+    }
+}
+
+/// Combine an imported session cache with the local one
+///
+/// Activities are merged by ID; the page token and timestamps of whichever
+/// copy was updated last are kept.
+pub fn merge_session_caches(local: SessionCache, imported: SessionCache) -> SessionCache {
+    let (newer, older) = if imported.last_updated > local.last_updated {
+        (imported, local)
+    } else {
+        (local, imported)
+    };
+
+    SessionCache {
+        activities: merge_activities(older.activities, newer.activities),
+        created_at: newer.created_at.min(older.created_at),
+        ..newer
+    }
+}
+
+fn append_json<W: std::io::Write, T: Serialize>(
+    builder: &mut tar::Builder<W>,
+    path: &str,
+    value: &T,
+) -> Result<()> {
+    let data = serde_json::to_vec_pretty(value).context("Failed to serialize archive entry")?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, path, data.as_slice())
+        .with_context(|| format!("Failed to add {} to archive", path))
+}
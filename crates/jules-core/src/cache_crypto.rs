@@ -0,0 +1,144 @@
+//! Encryption at rest for activity cache files.
+//!
+//! Cached bash outputs and diffs can contain secrets, so session files can
+//! optionally be sealed with XChaCha20-Poly1305. The 256-bit key comes from
+//! the `GULES_CACHE_KEY` environment variable when set (any passphrase; it is
+//! hashed with SHA-256), otherwise, with the `keyring` feature, from the OS
+//! keyring, where a random key is generated on first use.
+//!
+//! Encrypted files start with [`MAGIC`], so readers can tell them apart from
+//! plain (or zstd-compressed) files regardless of the file name.
+
+#[cfg(feature = "keyring")]
+use anyhow::Context;
+use anyhow::Result;
+#[cfg(feature = "keyring")]
+use base64::engine::general_purpose::STANDARD as BASE64;
+#[cfg(feature = "keyring")]
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+
+/// Environment variable holding the cache passphrase
+pub const CACHE_KEY_ENV: &str = "GULES_CACHE_KEY";
+
+/// Header of every encrypted cache file (format version 1)
+pub const MAGIC: &[u8] = b"GULESENC1";
+
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE: &str = "gules";
+#[cfg(feature = "keyring")]
+const KEYRING_USER: &str = "cache-key";
+const NONCE_LEN: usize = 24;
+
+/// Symmetric key used to seal cache files
+#[derive(Clone)]
+pub struct CacheKey(Key);
+
+impl CacheKey {
+    /// Derive a key from a passphrase
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        let digest = Sha256::digest(passphrase.as_bytes());
+        Self(*Key::from_slice(&digest))
+    }
+
+    /// Load the key from `GULES_CACHE_KEY`, or from the OS keyring
+    ///
+    /// A random key is generated and stored in the keyring on first use.
+    /// Without the `keyring` feature only `GULES_CACHE_KEY` is read.
+    pub fn load() -> Result<Self> {
+        if let Ok(passphrase) = std::env::var(CACHE_KEY_ENV) {
+            if !passphrase.is_empty() {
+                return Ok(Self::from_passphrase(&passphrase));
+            }
+        }
+        Self::load_from_keyring()
+    }
+
+    #[cfg(feature = "keyring")]
+    fn load_from_keyring() -> Result<Self> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+            .context("Failed to access the OS keyring")?;
+
+        match entry.get_password() {
+            Ok(encoded) => {
+                let bytes = BASE64
+                    .decode(encoded.trim())
+                    .context("Invalid cache key stored in keyring")?;
+                if bytes.len() != 32 {
+                    anyhow::bail!("Invalid cache key stored in keyring: expected 32 bytes");
+                }
+                Ok(Self(*Key::from_slice(&bytes)))
+            }
+            Err(keyring::Error::NoEntry) => {
+                let key = XChaCha20Poly1305::generate_key(&mut OsRng);
+                entry
+                    .set_password(&BASE64.encode(key))
+                    .context("Failed to store cache key in the OS keyring")?;
+                Ok(Self(key))
+            }
+            Err(e) => Err(e).context(format!(
+                "Failed to read cache key from the OS keyring (set {} instead)",
+                CACHE_KEY_ENV
+            )),
+        }
+    }
+
+    #[cfg(not(feature = "keyring"))]
+    fn load_from_keyring() -> Result<Self> {
+        anyhow::bail!(
+            "Set {} to encrypt the cache (built without the keyring feature)",
+            CACHE_KEY_ENV
+        )
+    }
+
+    /// Encrypt `plaintext` into a self-describing envelope
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let cipher = XChaCha20Poly1305::new(&self.0);
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt cache data"))?;
+
+        let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
+    }
+
+    /// Decrypt an envelope produced by [`CacheKey::seal`]
+    pub fn open(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let body = data
+            .strip_prefix(MAGIC)
+            .ok_or_else(|| anyhow::anyhow!("Cache data is not encrypted"))?;
+        if body.len() < NONCE_LEN {
+            anyhow::bail!("Encrypted cache data is truncated");
+        }
+
+        let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+        XChaCha20Poly1305::new(&self.0)
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                anyhow::anyhow!("Failed to decrypt cache data (wrong or changed cache key?)")
+            })
+    }
+}
+
+/// Process-wide key, loaded once on first use
+pub fn shared_key() -> Result<&'static CacheKey> {
+    static KEY: OnceLock<CacheKey> = OnceLock::new();
+
+    if let Some(key) = KEY.get() {
+        return Ok(key);
+    }
+    let key = CacheKey::load()?;
+    Ok(KEY.get_or_init(|| key))
+}
+
+/// Whether `data` is an encrypted cache envelope
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
@@ -0,0 +1,158 @@
+//! Events published by the background cache refresher (`gules daemon`).
+//!
+//! The daemon appends one JSON object per line to `events.jsonl` in the
+//! gules cache directory whenever a refresh finds new activities or a session
+//! changes state, and records a heartbeat in `daemon.json`. Other commands
+//! can read these instead of polling the API themselves.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::atomic_file::write_atomic;
+use crate::cache_lock::with_lock;
+
+/// Events kept in the log; older ones are dropped when it grows past this
+pub const MAX_EVENTS: usize = 1000;
+
+/// What happened to a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum CacheEventKind {
+    /// New activities were added to the cache
+    NewActivities { activity_ids: Vec<String> },
+    /// The session's state changed (display names, e.g. "In Progress")
+    StateChanged { from: Option<String>, to: String },
+}
+
+/// A single event in the log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheEvent {
+    pub time: DateTime<Utc>,
+    pub session_id: String,
+    #[serde(flatten)]
+    pub kind: CacheEventKind,
+}
+
+impl CacheEvent {
+    pub fn new(session_id: &str, kind: CacheEventKind) -> Self {
+        Self {
+            time: Utc::now(),
+            session_id: session_id.to_string(),
+            kind,
+        }
+    }
+}
+
+/// Get the event log path
+pub fn get_events_path() -> Result<PathBuf> {
+    let cache_dir =
+        dirs::cache_dir().ok_or_else(|| anyhow::anyhow!("Could not determine cache directory"))?;
+    Ok(cache_dir.join("gules").join("events.jsonl"))
+}
+
+/// Append events to the log at `path`, keeping at most [`MAX_EVENTS`]
+pub fn append_events_to(path: &Path, events: &[CacheEvent]) -> Result<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    with_lock(&path.with_extension("jsonl.lock"), || {
+        let mut lines = String::new();
+        for event in events {
+            lines.push_str(&serde_json::to_string(event).context("Failed to serialize event")?);
+            lines.push('\n');
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .context("Failed to open event log")?;
+        file.write_all(lines.as_bytes())
+            .context("Failed to write event log")?;
+
+        // Trim the log once it is well past the limit
+        let contents = fs::read_to_string(path).context("Failed to read event log")?;
+        let count = contents.lines().count();
+        if count > MAX_EVENTS + MAX_EVENTS / 10 {
+            let kept: Vec<&str> = contents.lines().skip(count - MAX_EVENTS).collect();
+            write_atomic(path, kept.join("\n") + "\n")?;
+        }
+        Ok(())
+    })
+}
+
+/// Append events to the default log
+pub fn append_events(events: &[CacheEvent]) -> Result<()> {
+    append_events_to(&get_events_path()?, events)
+}
+
+/// Read events from the log at `path` newer than `since` (all if `None`)
+///
+/// Lines that cannot be parsed are skipped.
+pub fn read_events_from(path: &Path, since: Option<DateTime<Utc>>) -> Result<Vec<CacheEvent>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(path).context("Failed to read event log")?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CacheEvent>(line).ok())
+        .filter(|event| since.is_none_or(|since| event.time > since))
+        .collect())
+}
+
+/// Read events from the default log
+pub fn read_events(since: Option<DateTime<Utc>>) -> Result<Vec<CacheEvent>> {
+    read_events_from(&get_events_path()?, since)
+}
+
+/// Heartbeat of a running daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    pub last_refresh: Option<DateTime<Utc>>,
+    pub interval_seconds: u64,
+}
+
+/// Get the daemon status file path
+pub fn get_daemon_status_path() -> Result<PathBuf> {
+    let cache_dir =
+        dirs::cache_dir().ok_or_else(|| anyhow::anyhow!("Could not determine cache directory"))?;
+    Ok(cache_dir.join("gules").join("daemon.json"))
+}
+
+impl DaemonStatus {
+    /// Load the status of the last daemon, if any
+    pub fn load() -> Result<Option<Self>> {
+        let path = get_daemon_status_path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path).context("Failed to read daemon status")?;
+        Ok(serde_json::from_str(&contents).ok())
+    }
+
+    /// Save the status
+    pub fn save(&self) -> Result<()> {
+        let path = get_daemon_status_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create cache directory")?;
+        }
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize daemon status")?;
+        write_atomic(&path, contents)
+    }
+
+    /// Whether the daemon has refreshed recently (within two intervals)
+    pub fn is_alive(&self) -> bool {
+        let since = self.last_refresh.unwrap_or(self.started_at);
+        let grace = Duration::seconds((self.interval_seconds.max(1) * 2) as i64);
+        Utc::now() - since <= grace
+    }
+}
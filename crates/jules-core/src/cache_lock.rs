@@ -0,0 +1,55 @@
+//! Advisory file locking for cache updates.
+//!
+//! Several gules processes (say the CLI and the MCP server) can update the
+//! cache at the same time. Every read-modify-write of cache files runs under
+//! an exclusive lock on a lock file next to the data, so concurrent updates
+//! are serialized instead of overwriting each other. Locks are reentrant
+//! within a thread, so locked operations can call each other.
+
+use anyhow::{Context, Result};
+use fs2::FileExt;
+use std::cell::RefCell;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+thread_local! {
+    /// Lock files currently held by this thread
+    static HELD: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Releases the thread's claim on a lock path, even if `f` panics
+struct Held<'a>(&'a Path);
+
+impl Drop for Held<'_> {
+    fn drop(&mut self) {
+        HELD.with(|held| held.borrow_mut().retain(|path| path != self.0));
+    }
+}
+
+/// Run `f` while holding an exclusive lock on `lock_path`
+///
+/// Blocks until other processes release the lock. The lock file is created
+/// if needed and is never deleted.
+pub fn with_lock<T>(lock_path: &Path, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    if HELD.with(|held| held.borrow().iter().any(|path| path == lock_path)) {
+        return f();
+    }
+
+    if let Some(parent) = lock_path.parent() {
+        fs::create_dir_all(parent).context("Failed to create cache directory")?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path)
+        .with_context(|| format!("Failed to open lock file {}", lock_path.display()))?;
+    FileExt::lock_exclusive(&file)
+        .with_context(|| format!("Failed to lock {}", lock_path.display()))?;
+
+    HELD.with(|held| held.borrow_mut().push(lock_path.to_path_buf()));
+    let _held = Held(lock_path);
+
+    // The lock is released when `file` is closed
+    f()
+}
@@ -1,30 +1,381 @@
 use anyhow::{Context, Result};
+use jules_rs::{JulesClient, JulesConfig};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::activity_cache::EvictionPolicy;
+use crate::display::{icon, ColorMode, DisplayTimezone, TimeFormat};
+use crate::github_cache::DEFAULT_RATE_LIMIT_RESERVE;
+use crate::template_output::Template;
 
 pub const DEFAULT_JULES_API_BASE: &str = "https://jules.googleapis.com/v1alpha";
 
-#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+/// File name of the project-local config, looked up from the working
+/// directory upwards
+pub const PROJECT_CONFIG_FILE_NAME: &str = ".gules.toml";
+
+/// Placeholder for the user's prompt in a prompt template
+pub const PROMPT_PLACEHOLDER: &str = "{prompt}";
+
+/// gules configuration (`config.toml` and project-local `.gules.toml`)
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Debug)]
 pub struct Config {
     #[serde(default)]
     pub api_key: Option<String>,
     #[serde(default)]
     pub api_url: Option<String>,
+    /// GitHub token for the GitHub integrations; `GITHUB_TOKEN` and
+    /// `GH_TOKEN` take precedence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_token: Option<String>,
     #[serde(default)]
     pub default_owner: Option<String>,
     #[serde(default)]
     pub default_repo: Option<String>,
+    /// Source used by `create` without `--source` (e.g. sources/github/owner/repo)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_source: Option<String>,
+    /// Starting branch used by `create` without `--branch`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+    /// Automation mode used by `create` without `--automation-mode`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub automation_mode: Option<String>,
+    /// File the daemon, monitor and MCP server write Prometheus metrics of
+    /// their Jules API requests to, e.g. for node_exporter's textfile
+    /// collector
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_file: Option<String>,
+    /// Named prompt templates for `create --template`; `{prompt}` is replaced
+    /// by the prompt
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, String>,
+    /// Named output templates for `--template` on sessions, session and
+    /// activities (e.g. `short = "{{id}} {{state}} {{title}}"`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub output_templates: BTreeMap<String, String>,
+    /// Color mode when `--color` is not given: "auto" (default), "always" or "never"
+    #[serde(default)]
+    pub color: ColorMode,
+    #[serde(default)]
+    pub display: DisplayConfig,
     #[serde(default)]
     pub cache: CacheConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub github: GitHubConfig,
+    #[serde(default)]
+    pub safety: SafetyConfig,
+    /// Named accounts selected with `--profile`, e.g. `[profiles.work]`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// API settings of a named account (`[profiles.<name>]`), applied over the
+/// top-level ones when selected with `--profile`
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+}
+
+/// Settings given on the command line for a single invocation
+/// (`--api-key`, `--profile`, `--base-url`)
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct ConfigOverrides {
+    pub api_key: Option<String>,
+    pub profile: Option<String>,
+    pub base_url: Option<String>,
+}
+
+impl ConfigOverrides {
+    /// `config` with the selected profile, then the explicit API key and
+    /// base URL, applied over it
+    pub fn apply(&self, mut config: Config) -> Result<Config> {
+        if let Some(name) = &self.profile {
+            let profile = config.profiles.get(name).cloned().ok_or_else(|| {
+                let known: Vec<&str> = config.profiles.keys().map(String::as_str).collect();
+                anyhow::anyhow!(
+                    "Unknown profile '{}' (available: {})",
+                    name,
+                    if known.is_empty() {
+                        "none".to_string()
+                    } else {
+                        known.join(", ")
+                    }
+                )
+            })?;
+            config.api_key = profile.api_key.or(config.api_key);
+            config.api_url = profile.api_url.or(config.api_url);
+        }
+        if let Some(key) = &self.api_key {
+            config.api_key = Some(key.clone());
+        }
+        if let Some(url) = &self.base_url {
+            config.api_url = Some(url.clone());
+        }
+        Ok(config)
+    }
+}
+
+static OVERRIDES: RwLock<Option<ConfigOverrides>> = RwLock::new(None);
+
+/// Apply command-line settings to every config loaded with [`load_config`]
+/// and to [`get_api_key`]
+pub fn set_config_overrides(overrides: ConfigOverrides) {
+    if let Ok(mut current) = OVERRIDES.write() {
+        *current = Some(overrides);
+    }
+}
+
+/// The settings given on the command line (none by default)
+pub fn config_overrides() -> ConfigOverrides {
+    OVERRIDES
+        .read()
+        .ok()
+        .and_then(|overrides| overrides.clone())
+        .unwrap_or_default()
+}
+
+impl Config {
+    /// The given source, or `default_source`
+    pub fn resolve_source(&self, source: Option<String>) -> Result<String> {
+        source
+            .or_else(|| self.default_source.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No source given. Pass --source or set default_source in {} or the config file",
+                    PROJECT_CONFIG_FILE_NAME
+                )
+            })
+    }
+
+    /// Expand `prompt` with the named template (the prompt as is without one)
+    ///
+    /// Templates without a `{prompt}` placeholder get the prompt appended.
+    pub fn render_prompt(&self, template: Option<&str>, prompt: &str) -> Result<String> {
+        let Some(name) = template else {
+            return Ok(prompt.to_string());
+        };
+        let template = self.templates.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.templates.keys().map(String::as_str).collect();
+            anyhow::anyhow!(
+                "Unknown prompt template '{}' (available: {})",
+                name,
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            )
+        })?;
+
+        if template.contains(PROMPT_PLACEHOLDER) {
+            Ok(template.replace(PROMPT_PLACEHOLDER, prompt))
+        } else {
+            Ok(format!("{}\n\n{}", template.trim_end(), prompt))
+        }
+    }
+
+    /// The output template for `--template`: `spec` itself if it contains a
+    /// `{{` or `{%` tag, otherwise the named template from `output_templates`
+    pub fn output_template(&self, spec: &str) -> Result<Template> {
+        if spec.contains("{{") || spec.contains("{%") {
+            return Template::parse(spec);
+        }
+        let template = self.output_templates.get(spec).ok_or_else(|| {
+            let known: Vec<&str> = self.output_templates.keys().map(String::as_str).collect();
+            anyhow::anyhow!(
+                "Unknown output template '{}' (available: {})",
+                spec,
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            )
+        })?;
+        Template::parse(template)
+    }
+}
+
+/// How human-readable output shows data (`[display]`)
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Debug)]
+pub struct DisplayConfig {
+    /// Time zone of displayed timestamps: "utc", "local" or an offset such
+    /// as "+02:00" (default: local for `time_format = "local"`, else utc)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Timestamp format when `--time-format` is not given: "relative"
+    /// (default), "iso" or "local"
+    #[serde(default)]
+    pub time_format: TimeFormat,
+    /// Table columns per command when `--columns` is not given, e.g.
+    /// `sessions = "id,state,title"`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub columns: BTreeMap<String, String>,
+    /// Output format when `--format` is not given, e.g. "table"; commands
+    /// that cannot print it keep their own default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_format: Option<String>,
+}
+
+/// Where to report finished sessions (`[notifications]`)
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Debug)]
+pub struct NotificationsConfig {
+    /// URL that `watch` POSTs a JSON summary to when the session finishes,
+    /// unless `--webhook` is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    /// Whether `watch` comments the outcome of a session that completes or
+    /// fails on the GitHub issues it is linked to
+    #[serde(default)]
+    pub issue_comments: bool,
+}
+
+/// How the GitHub integrations behave (`[github]`)
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Debug)]
+pub struct GitHubConfig {
+    /// Regular expressions finding session IDs (the first capture group) in
+    /// issue comments, pull request descriptions, commit messages and
+    /// branch names for `issue-status`; empty for the built-in ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub session_patterns: Vec<String>,
+    /// ID of a GitHub App to call GitHub as, instead of with a token; needs
+    /// `app_installation_id` and `app_private_key_path` too
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<u64>,
+    /// ID of the app's installation on the organization or account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_installation_id: Option<u64>,
+    /// Path of the app's PEM-encoded private key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_private_key_path: Option<String>,
+    /// GitHub API requests left for other commands: polling commands like
+    /// `pr ci-watch` wait for the rate limit to reset rather than go below
+    /// it, and `triage` doesn't start (default 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_reserve: Option<u64>,
+}
+
+impl GitHubConfig {
+    /// Whether any of the GitHub App settings is set
+    pub fn has_app(&self) -> bool {
+        self.app_id.is_some()
+            || self.app_installation_id.is_some()
+            || self.app_private_key_path.is_some()
+    }
+
+    /// The `rate_limit_reserve` setting, or its default
+    pub fn rate_limit_reserve(&self) -> u64 {
+        self.rate_limit_reserve
+            .unwrap_or(DEFAULT_RATE_LIMIT_RESERVE)
+    }
+}
+
+/// Guardrails on the sessions gules starts (`[safety]`); only the global
+/// config sets them, so a project config can't loosen them
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Debug)]
+pub struct SafetyConfig {
+    /// Sources sessions may be started on, as `sources/github/owner/repo`,
+    /// `owner/repo`, or `owner/*` for all of an owner's repositories; empty
+    /// allows any source
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_sources: Vec<String>,
+}
+
+impl SafetyConfig {
+    /// Whether sessions may be started on `source`
+    pub fn allows_source(&self, source: &str) -> bool {
+        if self.allowed_sources.is_empty() {
+            return true;
+        }
+        let source = source_id(source);
+        self.allowed_sources.iter().any(|allowed| {
+            let allowed = source_id(allowed);
+            match allowed.strip_suffix("/*") {
+                Some(owner) => source
+                    .rsplit_once('/')
+                    .is_some_and(|(source_owner, _)| source_owner.eq_ignore_ascii_case(owner)),
+                None => source.eq_ignore_ascii_case(allowed),
+            }
+        })
+    }
+
+    /// Fail unless sessions may be started on `source`
+    pub fn check_source(&self, source: &str) -> Result<()> {
+        if self.allows_source(source) {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Sessions on {} are not allowed: it is not in safety.allowed_sources ({})",
+            source,
+            self.allowed_sources.join(", ")
+        )
+    }
+}
+
+/// `owner/repo` of a GitHub source given as `sources/github/owner/repo` or
+/// `owner/repo`; other sources without the `sources/` prefix
+fn source_id(source: &str) -> &str {
+    let source = source.trim();
+    let source = source.strip_prefix("sources/").unwrap_or(source);
+    source.strip_prefix("github/").unwrap_or(source)
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+impl DisplayConfig {
+    /// The configured time zone, if any
+    pub fn timezone(&self) -> Result<Option<DisplayTimezone>> {
+        self.timezone
+            .as_deref()
+            .map(DisplayTimezone::parse)
+            .transpose()
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug)]
 pub struct CacheConfig {
     #[serde(default = "default_cache_enabled")]
     pub enabled: bool,
     #[serde(default = "default_max_sessions")]
     pub max_sessions: usize,
+    /// Eviction policy: "fifo" (default) or "lru"
+    #[serde(default)]
+    pub eviction: EvictionPolicy,
+    /// Maximum total size of cached session files (SQLite: activities) in MiB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+    /// Storage backend for cached activities
+    #[serde(default)]
+    pub backend: CacheBackend,
+    /// Seconds before the cache of a running session is refreshed
+    /// (completed and failed sessions never expire; 0 always refreshes)
+    #[serde(default = "default_ttl_seconds")]
+    pub ttl_seconds: u64,
+    /// Store JSON cache files zstd-compressed
+    #[serde(default = "default_cache_compress")]
+    pub compress: bool,
+    /// Encrypt JSON cache files at rest (key from GULES_CACHE_KEY or the OS keyring)
+    #[serde(default)]
+    pub encrypt: bool,
+}
+
+/// Activity cache storage backend
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    /// One JSON file per session (default)
+    #[default]
+    Json,
+    /// Single SQLite database with indexed queries
+    Sqlite,
 }
 
 fn default_cache_enabled() -> bool {
@@ -35,11 +386,25 @@ fn default_max_sessions() -> usize {
     50
 }
 
+fn default_cache_compress() -> bool {
+    true
+}
+
+fn default_ttl_seconds() -> u64 {
+    crate::activity_cache::DEFAULT_CACHE_TTL_SECONDS
+}
+
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
             enabled: default_cache_enabled(),
             max_sessions: default_max_sessions(),
+            eviction: EvictionPolicy::default(),
+            max_size_mb: None,
+            backend: CacheBackend::default(),
+            ttl_seconds: default_ttl_seconds(),
+            compress: default_cache_compress(),
+            encrypt: false,
         }
     }
 }
@@ -50,24 +415,110 @@ pub fn get_config_path() -> Result<PathBuf> {
     Ok(config_dir.join("gules").join("config.toml"))
 }
 
+/// Load the global config merged with the project-local config, if any
+///
+/// This is what commands should use. Use [`load_global_config`] to modify
+/// and save the global config.
+///
+/// Settings given on the command line ([`set_config_overrides`]) are
+/// applied last.
 pub fn load_config() -> Result<Config> {
+    let config = load_global_config()?;
+    let config = match std::env::current_dir()
+        .ok()
+        .and_then(|cwd| find_project_config(&cwd))
+    {
+        Some(path) => merge_project_config(&config, &path)?,
+        None => config,
+    };
+    config_overrides().apply(config)
+}
+
+/// Load the global config, creating it with defaults if it does not exist
+pub fn load_global_config() -> Result<Config> {
     let config_path = get_config_path()?;
 
     if !config_path.exists() {
         let config = Config::default();
         save_config(&config)?;
 
-        println!("ℹ Created default config at: {}", config_path.display());
-        println!("💡 Edit this file to set defaults (API key, default repo, etc.)");
+        println!(
+            "{} Created default config at: {}",
+            icon("ℹ", "INFO"),
+            config_path.display()
+        );
+        println!(
+            "{} Edit this file to set defaults (API key, default repo, etc.)",
+            icon("💡", "TIP")
+        );
 
         return Ok(config);
     }
 
-    let contents = fs::read_to_string(&config_path).context("Failed to read config file")?;
+    read_config(&config_path)
+}
+
+/// Read and parse a config file without creating it
+pub fn read_config(path: &Path) -> Result<Config> {
+    let contents = fs::read_to_string(path).context("Failed to read config file")?;
 
     toml::from_str(&contents).context("Failed to parse config file")
 }
 
+/// JSON Schema of the config file
+///
+/// Every setting is optional, so it also validates project-local configs.
+pub fn config_schema() -> serde_json::Value {
+    schemars::schema_for!(Config).to_value()
+}
+
+/// Find the project-local config in `start` or its closest ancestor
+pub fn find_project_config(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// `config` with the settings of the project-local config at `path` merged
+/// over it
+///
+/// Tables are merged key by key, so a project file setting `cache.ttl_seconds`
+/// keeps the other cache settings of the global config.
+pub fn merge_project_config(config: &Config, path: &Path) -> Result<Config> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let project: toml::Table =
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))?;
+    if project.contains_key("safety") {
+        anyhow::bail!(
+            "Invalid {}: [safety] can only be set in the global config",
+            path.display()
+        );
+    }
+
+    let mut root = toml::Value::try_from(config).context("Failed to serialize config")?;
+    let table = root.as_table_mut().expect("config serializes to a table");
+    merge_tables(table, project);
+
+    root.try_into().map_err(|e: toml::de::Error| {
+        anyhow::anyhow!("Invalid {}: {}", path.display(), e.message())
+    })
+}
+
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => {
+                merge_tables(base, overlay)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 pub fn save_config(config: &Config) -> Result<()> {
     let config_path = get_config_path()?;
 
@@ -82,8 +533,121 @@ pub fn save_config(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Look up a value by dotted key (e.g. `cache.max_sessions`)
+///
+/// Returns `None` for unknown keys and for optional settings that are unset.
+pub fn get_config_value(config: &Config, key: &str) -> Result<Option<toml::Value>> {
+    let root = toml::Value::try_from(config).context("Failed to serialize config")?;
+    Ok(lookup(&root, key).cloned())
+}
+
+/// Set a value by dotted key (e.g. `cache.enabled false`)
+///
+/// `value` is parsed as a TOML value (number, boolean, quoted string, array)
+/// and falls back to a plain string, then checked against the type of the
+/// setting. Unknown keys are rejected.
+pub fn set_config_value(config: &mut Config, key: &str, value: &str) -> Result<()> {
+    let parsed = parse_config_value(value);
+    let updated = with_config_value(config, key, parsed.clone()).or_else(|e| {
+        // `default_repo 123` is still a string
+        match parsed {
+            toml::Value::String(_) => Err(e),
+            _ => with_config_value(config, key, toml::Value::String(value.to_string()))
+                .map_err(|_| e),
+        }
+    })?;
+    *config = updated;
+    Ok(())
+}
+
+/// Remove a value by dotted key, restoring its default
+///
+/// Returns `false` if the key was not set.
+pub fn unset_config_value(config: &mut Config, key: &str) -> Result<bool> {
+    let mut root = toml::Value::try_from(&*config).context("Failed to serialize config")?;
+
+    let (parents, name) = match key.rsplit_once('.') {
+        Some((parents, name)) => (Some(parents), name),
+        None => (None, key),
+    };
+    let parent = match parents {
+        Some(parents) => lookup_mut(&mut root, parents),
+        None => Some(&mut root),
+    };
+    let removed = parent
+        .and_then(toml::Value::as_table_mut)
+        .and_then(|table| table.remove(name));
+    if removed.is_none() {
+        return Ok(false);
+    }
+
+    *config = root
+        .try_into()
+        .map_err(|e: toml::de::Error| anyhow::anyhow!("Cannot unset {}: {}", key, e.message()))?;
+    Ok(true)
+}
+
+/// Parse a command-line value as TOML, or as a plain string
+fn parse_config_value(value: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", value))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()))
+}
+
+/// `config` with `key` set to `value`, validated by round-tripping through
+/// [`Config`]
+fn with_config_value(config: &Config, key: &str, value: toml::Value) -> Result<Config> {
+    let mut root = toml::Value::try_from(config).context("Failed to serialize config")?;
+
+    let (parents, name) = match key.rsplit_once('.') {
+        Some((parents, name)) => (Some(parents), name),
+        None => (None, key),
+    };
+    let mut table = root.as_table_mut().expect("config serializes to a table");
+    for part in parents.into_iter().flat_map(|p| p.split('.')) {
+        table = table
+            .entry(part)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| anyhow::anyhow!("Unknown configuration key: {}", key))?;
+    }
+    table.insert(name.to_string(), value.clone());
+
+    let updated: Config = root.try_into().map_err(|e: toml::de::Error| {
+        anyhow::anyhow!("Invalid value for {}: {}", key, e.message())
+    })?;
+
+    // Unknown keys are silently dropped by deserialization
+    let stored = toml::Value::try_from(&updated).context("Failed to serialize config")?;
+    if lookup(&stored, key) != Some(&value) {
+        anyhow::bail!("Unknown configuration key: {}", key);
+    }
+    Ok(updated)
+}
+
+fn lookup<'a>(root: &'a toml::Value, key: &str) -> Option<&'a toml::Value> {
+    key.split('.').try_fold(root, |value, part| value.get(part))
+}
+
+fn lookup_mut<'a>(root: &'a mut toml::Value, key: &str) -> Option<&'a mut toml::Value> {
+    key.split('.')
+        .try_fold(root, |value, part| value.get_mut(part))
+}
+
+/// The API key: `cli_key`, then `--api-key`, then the key of the
+/// `--profile` account, then `JULES_API_KEY`, then the config
 pub fn get_api_key(cli_key: Option<String>, config: &Config) -> Result<String> {
-    if let Some(key) = cli_key {
+    let overrides = config_overrides();
+    if let Some(key) = cli_key.or(overrides.api_key) {
+        return Ok(key);
+    }
+
+    // An account picked for this invocation beats the environment
+    let profile_key = overrides
+        .profile
+        .and_then(|name| config.profiles.get(&name)?.api_key.clone());
+    if let Some(key) = profile_key {
         return Ok(key);
     }
 
@@ -97,10 +661,27 @@ pub fn get_api_key(cli_key: Option<String>, config: &Config) -> Result<String> {
 
     anyhow::bail!(
         "API key not found. Set it via:\n  \
-         1. --api-key flag\n  \
+         1. --api-key flag (or --profile with a [profiles.<name>] api_key)\n  \
          2. JULES_API_KEY environment variable\n  \
          3. Edit config file: {:?}\n\n\
          Get your API key from: https://jules.google.com/settings",
         get_config_path().unwrap_or_default()
     );
 }
+
+/// The API base URL: `--base-url`, then the `api_url` setting, then the
+/// public Jules API
+pub fn api_base_url(config: &Config) -> String {
+    config_overrides()
+        .base_url
+        .or_else(|| config.api_url.clone())
+        .unwrap_or_else(|| DEFAULT_JULES_API_BASE.to_string())
+}
+
+/// A client for the configured account and API base URL
+pub fn api_client(config: &Config) -> Result<JulesClient> {
+    Ok(JulesClient::with_config(JulesConfig {
+        api_key: get_api_key(None, config)?,
+        base_url: api_base_url(config),
+    }))
+}
@@ -0,0 +1,180 @@
+//! CSV output for list commands.
+//!
+//! Each record type has a fixed column set, so scripts and spreadsheets can
+//! rely on the header. Fields are quoted per RFC 4180 when they contain a
+//! comma, quote or line break; nested data (artifacts, branches) is reduced to
+//! counts or the first relevant value.
+
+use anyhow::Result;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use jules_rs::types::source::Source;
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Columns of `sessions --format csv`
+pub const SESSION_CSV_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "state",
+    "create_time",
+    "update_time",
+    "source",
+    "branch",
+    "pr_url",
+    "url",
+    "prompt",
+];
+
+/// Columns of `activities --format csv`
+pub const ACTIVITY_CSV_COLUMNS: &[&str] = &[
+    "id",
+    "create_time",
+    "type",
+    "originator",
+    "content",
+    "bash_command",
+    "bash_exit_code",
+    "artifacts",
+];
+
+/// Columns of `sources --format csv`
+pub const SOURCE_CSV_COLUMNS: &[&str] = &[
+    "id",
+    "owner",
+    "repo",
+    "private",
+    "default_branch",
+    "branches",
+];
+
+/// Quote a field if it contains a delimiter, quote or line break
+pub fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Write a header and rows as CSV (CRLF line endings, per RFC 4180)
+pub fn write_csv<W: Write>(
+    out: &mut W,
+    columns: &[&str],
+    rows: impl IntoIterator<Item = Vec<String>>,
+) -> io::Result<()> {
+    let line = |fields: Vec<String>| {
+        fields
+            .iter()
+            .map(|field| csv_escape(field))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    write!(
+        out,
+        "{}\r\n",
+        line(columns.iter().map(|c| c.to_string()).collect())
+    )?;
+    for row in rows {
+        debug_assert_eq!(row.len(), columns.len());
+        write!(out, "{}\r\n", line(row))?;
+    }
+    out.flush()
+}
+
+/// API name of an enum value (e.g. `IN_PROGRESS`)
+fn enum_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+/// CSV fields of a session, in [`SESSION_CSV_COLUMNS`] order
+pub fn session_csv_row(session: &Session) -> Vec<String> {
+    let pr_url = session
+        .outputs
+        .iter()
+        .filter_map(|output| output.pull_request.as_ref()?.url.clone())
+        .next();
+    vec![
+        session.id.clone(),
+        session.title.clone().unwrap_or_default(),
+        session.state.as_ref().map(enum_name).unwrap_or_default(),
+        session.create_time.clone().unwrap_or_default(),
+        session.update_time.clone().unwrap_or_default(),
+        session.source_context.source.clone(),
+        session
+            .source_context
+            .github_repo_context
+            .as_ref()
+            .map(|context| context.starting_branch.clone())
+            .unwrap_or_default(),
+        pr_url.unwrap_or_default(),
+        session.url.clone().unwrap_or_default(),
+        session.prompt.clone(),
+    ]
+}
+
+/// CSV fields of an activity, in [`ACTIVITY_CSV_COLUMNS`] order
+///
+/// The bash columns hold the first bash output of the activity.
+pub fn activity_csv_row(activity: &Activity) -> Vec<String> {
+    let bash = activity
+        .artifacts
+        .iter()
+        .find_map(|artifact| artifact.bash_output.as_ref());
+    vec![
+        activity.id.clone(),
+        activity.create_time.clone(),
+        activity.activity_type(),
+        activity.originator.clone(),
+        activity.content().unwrap_or_default(),
+        bash.and_then(|bash| bash.command.clone())
+            .unwrap_or_default(),
+        bash.and_then(|bash| bash.exit_code)
+            .map(|code| code.to_string())
+            .unwrap_or_default(),
+        activity.artifacts.len().to_string(),
+    ]
+}
+
+/// CSV fields of a source, in [`SOURCE_CSV_COLUMNS`] order
+pub fn source_csv_row(source: &Source) -> Vec<String> {
+    let repo = source.github_repo.as_ref();
+    vec![
+        source.id.clone(),
+        repo.map(|repo| repo.owner.clone()).unwrap_or_default(),
+        repo.map(|repo| repo.repo.clone()).unwrap_or_default(),
+        repo.and_then(|repo| repo.is_private)
+            .map(|private| private.to_string())
+            .unwrap_or_default(),
+        repo.and_then(|repo| repo.default_branch.as_ref())
+            .map(|branch| branch.display_name.clone())
+            .unwrap_or_default(),
+        repo.map(|repo| repo.branches.len().to_string())
+            .unwrap_or_default(),
+    ]
+}
+
+/// Print sessions as CSV
+pub fn print_sessions_csv(sessions: &[Session]) -> Result<()> {
+    let rows = sessions.iter().map(session_csv_row);
+    write_csv(&mut io::stdout().lock(), SESSION_CSV_COLUMNS, rows)?;
+    Ok(())
+}
+
+/// Print activities as CSV
+pub fn print_activities_csv(activities: &[&Activity]) -> Result<()> {
+    let rows = activities.iter().map(|activity| activity_csv_row(activity));
+    write_csv(&mut io::stdout().lock(), ACTIVITY_CSV_COLUMNS, rows)?;
+    Ok(())
+}
+
+/// Print sources as CSV
+pub fn print_sources_csv(sources: &[Source]) -> Result<()> {
+    let rows = sources.iter().map(source_csv_row);
+    write_csv(&mut io::stdout().lock(), SOURCE_CSV_COLUMNS, rows)?;
+    Ok(())
+}
@@ -1,12 +1,271 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, Local, SecondsFormat, Utc};
 use colored::*;
 use jules_rs::types::activity::{Activity, Artifact, Plan};
 use jules_rs::types::session::{Session, State};
 use jules_rs::types::source::Source;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs;
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::issue_links::IssueLink;
+use crate::recent_sessions::RecentSession;
+use crate::table_columns::{
+    selected_columns, ActivityColumn, SessionColumn, SourceColumn, TableColumn,
+};
+use crate::task_queue::QueuedTask;
+
+/// When to use colors (and emoji) in terminal output
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    /// Only when stdout is a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => anyhow::bail!(
+                "Unknown color mode: {}. Valid options: auto, always, never",
+                s
+            ),
+        }
+    }
+
+    /// Whether this mode enables colors in the current environment
+    ///
+    /// `auto` follows <https://no-color.org>: a non-empty `NO_COLOR`
+    /// disables colors, `CLICOLOR_FORCE` enables them even when piped.
+    pub fn enabled(self) -> bool {
+        let env_set = |name: &str| std::env::var_os(name).is_some_and(|v| !v.is_empty());
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto if env_set("NO_COLOR") => false,
+            Self::Auto if env_set("CLICOLOR_FORCE") => true,
+            Self::Auto => std::io::stdout().is_terminal(),
+        }
+    }
+}
+
+/// How timestamps are shown in human-readable output
+#[derive(Serialize, Deserialize, JsonSchema, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeFormat {
+    /// "5m ago", falling back to the date after a week
+    #[default]
+    Relative,
+    /// Full RFC 3339, e.g. 2025-01-01T09:30:00Z
+    Iso,
+    /// Date and time of day, e.g. 2025-01-01 10:30:00
+    Local,
+}
+
+impl TimeFormat {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "relative" => Ok(Self::Relative),
+            "iso" => Ok(Self::Iso),
+            "local" => Ok(Self::Local),
+            _ => anyhow::bail!(
+                "Unknown time format: {}. Valid options: relative, iso, local",
+                s
+            ),
+        }
+    }
+}
+
+/// Time zone of displayed timestamps
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisplayTimezone {
+    Utc,
+    /// The system time zone
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl DisplayTimezone {
+    /// Parse "utc", "local" or an offset such as "+02:00"
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "utc" | "z" => Ok(Self::Utc),
+            "local" => Ok(Self::Local),
+            offset => offset.parse().map(Self::Fixed).map_err(|_| {
+                anyhow::anyhow!(
+                    "Unknown timezone: {}. Use utc, local or an offset such as +02:00",
+                    s
+                )
+            }),
+        }
+    }
+
+    fn convert(self, time: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Self::Utc => time.fixed_offset(),
+            Self::Local => time.with_timezone(&Local).fixed_offset(),
+            Self::Fixed(offset) => time.with_timezone(&offset),
+        }
+    }
+}
+
+/// Process-wide timestamp settings used by [`display_timestamp`]
+static TIME_DISPLAY: RwLock<(TimeFormat, Option<DisplayTimezone>)> =
+    RwLock::new((TimeFormat::Relative, None));
+
+/// Apply a time format and zone to all subsequent output
+///
+/// Without a zone, `relative` and `iso` use UTC and `local` the system zone.
+pub fn set_time_display(format: TimeFormat, timezone: Option<DisplayTimezone>) {
+    if let Ok(mut settings) = TIME_DISPLAY.write() {
+        *settings = (format, timezone);
+    }
+}
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Suppress decorative output (banners, progress notes, success messages)
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether decorative output is suppressed (`--quiet`)
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Terminal column width of `s` (wide CJK and emoji characters count as 2)
+pub fn display_width(s: &str) -> usize {
+    s.width()
+}
+
+/// Shorten `s` to at most `max_width` columns, ending in "..." if cut
+///
+/// Cuts between grapheme clusters, so multi-byte characters, combining
+/// marks and emoji sequences are never split.
+pub fn truncate_to_width(s: &str, max_width: usize) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+    let ellipsis = if max_width >= 3 { "..." } else { "" };
+    let budget = max_width - ellipsis.len();
+
+    let mut truncated = String::new();
+    let mut width = 0;
+    for grapheme in s.graphemes(true) {
+        let grapheme_width = grapheme.width();
+        if width + grapheme_width > budget {
+            break;
+        }
+        truncated.push_str(grapheme);
+        width += grapheme_width;
+    }
+    truncated.push_str(ellipsis);
+    truncated
+}
+
+/// Pad `s` with spaces to `width` columns (`format!("{:<N}")` counts chars,
+/// not columns)
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let padding = width.saturating_sub(s.width());
+    format!("{}{}", s, " ".repeat(padding))
+}
+
+/// Width of the terminal in columns, or `None` when stdout is not a terminal
+///
+/// A positive `COLUMNS` takes precedence, so piped output can be laid out
+/// for a given width too.
+pub fn terminal_width() -> Option<usize> {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok())
+        .filter(|columns| *columns > 0)
+        .or_else(|| comfy_table::Table::new().width().map(usize::from))
+}
+
+/// Widths of space-separated table columns, given each column's content
+/// width and its (minimum, maximum) width
+///
+/// Columns are sized to their content within their bounds. With a known
+/// terminal width, columns whose bounds differ shrink towards their minimum
+/// when the table is too wide (widest first), or grow past their maximum to
+/// fit their content when there is room to spare. Fixed-width columns never
+/// change, so a table of minimums may still overflow.
+pub fn fit_column_widths(
+    columns: &[(usize, (usize, usize))],
+    available: Option<usize>,
+) -> Vec<usize> {
+    let mut widths: Vec<usize> = columns
+        .iter()
+        .map(|(content, (min, max))| (*content).clamp(*min, *max))
+        .collect();
+    let Some(available) = available else {
+        return widths;
+    };
+    let available = available.saturating_sub(columns.len().saturating_sub(1));
+    let total: usize = widths.iter().sum();
+
+    if total > available {
+        for _ in 0..total - available {
+            let Some(i) = (0..widths.len())
+                .filter(|&i| widths[i] > columns[i].1 .0)
+                .max_by_key(|&i| widths[i] - columns[i].1 .0)
+            else {
+                break;
+            };
+            widths[i] -= 1;
+        }
+    } else {
+        for _ in 0..available - total {
+            let Some(i) = (0..widths.len())
+                .filter(|&i| {
+                    let (content, (min, max)) = columns[i];
+                    min < max && widths[i] < content
+                })
+                .max_by_key(|&i| columns[i].0 - widths[i])
+            else {
+                break;
+            };
+            widths[i] += 1;
+        }
+    }
+    widths
+}
+
+/// Apply a color mode to all subsequent output
+pub fn set_color_mode(mode: ColorMode) {
+    colored::control::set_override(mode.enabled());
+}
+
+/// Whether output is colored
+pub fn colors_enabled() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+/// `emoji` for colored output, `plain` when colors are disabled
+///
+/// Emoji widths vary between terminals and they clutter logs, so plain
+/// output sticks to ASCII markers.
+pub fn icon(emoji: &'static str, plain: &'static str) -> &'static str {
+    if colors_enabled() {
+        emoji
+    } else {
+        plain
+    }
+}
 
 pub fn print_sessions_table(sessions: &[&Value]) {
     if sessions.is_empty() {
@@ -19,7 +278,7 @@ pub fn print_sessions_table(sessions: &[&Value]) {
 
     for session in sessions {
         if let Some(title) = session.get("title").and_then(|v| v.as_str()) {
-            max_title_len = max_title_len.max(title.len().min(50));
+            max_title_len = max_title_len.max(display_width(title).min(50));
         }
     }
 
@@ -58,17 +317,8 @@ pub fn print_sessions_table(sessions: &[&Value]) {
             .and_then(|v| v.as_str())
             .unwrap_or("unknown");
 
-        let truncated_title = if title.len() > max_title_len {
-            format!("{}...", &title[..max_title_len - 3])
-        } else {
-            title.to_string()
-        };
-
-        let truncated_id = if id.len() > 20 {
-            format!("{}...", &id[..17])
-        } else {
-            id.to_string()
-        };
+        let truncated_title = pad_to_width(&truncate_to_width(title, max_title_len), max_title_len);
+        let truncated_id = truncate_to_width(id, id_len);
 
         let state_display = parse_state_for_display(state);
 
@@ -91,13 +341,8 @@ pub fn print_sessions_table(sessions: &[&Value]) {
         };
 
         println!(
-            "{:<width_title$} {:<20} {:<11} {:<12} {:<6}",
-            truncated_title,
-            truncated_id,
-            state_display,
-            create_time,
-            pr_indicator,
-            width_title = max_title_len
+            "{} {:<20} {:<11} {:<12} {:<6}",
+            truncated_title, truncated_id, state_display, create_time, pr_indicator,
         );
     }
 
@@ -107,92 +352,252 @@ pub fn print_sessions_table(sessions: &[&Value]) {
     );
 }
 
+/// Print sessions as a table, with the columns selected by `--columns`
 pub fn display_sessions_table(sessions: &[Session]) {
     if sessions.is_empty() {
         return;
     }
 
-    let mut max_title_len = 20;
-    let id_len = 20;
-    let pr_len = 6;
+    let columns = selected_columns::<SessionColumn>();
+    let rows = sessions
+        .iter()
+        .map(|session| {
+            columns
+                .iter()
+                .map(|column| session_cell(session, *column))
+                .collect()
+        })
+        .collect();
+    print_text_table(
+        &columns
+            .iter()
+            .map(|column| (column.header(), session_column_width(*column)))
+            .collect::<Vec<_>>(),
+        rows,
+    );
+}
 
-    for session in sessions {
-        if let Some(title) = &session.title {
-            max_title_len = max_title_len.max(title.len().min(50));
-        }
+/// Print the tasks of the local queue as a table
+pub fn display_queue_table(tasks: &[QueuedTask]) {
+    if tasks.is_empty() {
+        return;
     }
 
-    let state_len = 11;
-    let time_len = 12; // For timestamps like "2h ago" or "Oct 14, 2025"
-
-    println!(
-        "{}",
-        "─".repeat(max_title_len + id_len + state_len + time_len + pr_len + 13)
+    let rows = tasks
+        .iter()
+        .map(|task| {
+            let source = &task.request.source_context.source;
+            let branch = task
+                .request
+                .source_context
+                .github_repo_context
+                .as_ref()
+                .map(|context| context.starting_branch.clone())
+                .unwrap_or_else(|| "-".to_string());
+            vec![
+                TextCell::plain(task.id.to_string()),
+                TextCell::plain(task.label().to_string()),
+                TextCell::plain(
+                    source
+                        .strip_prefix("sources/")
+                        .unwrap_or(source)
+                        .to_string(),
+                ),
+                TextCell::plain(branch),
+                TextCell::plain(display_timestamp(&task.added_at.to_rfc3339())),
+            ]
+        })
+        .collect();
+    print_text_table(
+        &[
+            ("ID", (4, 8)),
+            ("Task", (20, 50)),
+            ("Source", (20, 45)),
+            ("Branch", (10, 25)),
+            ("Added", (12, 25)),
+        ],
+        rows,
     );
-    println!(
-        "{:<width_title$} {:<20} {:<11} {:<12} {:<6}",
-        "Title",
-        "Session ID",
-        "State",
-        "Created",
-        "PR",
-        width_title = max_title_len
+}
+
+/// Print recently used sessions as a table, numbered like their `@N`
+/// shortcuts
+pub fn display_recent_table(sessions: &[RecentSession]) {
+    if sessions.is_empty() {
+        return;
+    }
+
+    let rows = sessions
+        .iter()
+        .enumerate()
+        .map(|(index, session)| {
+            vec![
+                TextCell::plain(format!("@{}", index + 1)),
+                TextCell::plain(session.id.clone()),
+                TextCell::plain(session.title.clone().unwrap_or_else(|| "-".to_string())),
+                TextCell::plain(display_timestamp(&session.used_at.to_rfc3339())),
+            ]
+        })
+        .collect();
+    print_text_table(
+        &[
+            ("#", (3, 4)),
+            ("ID", (20, 22)),
+            ("Title", (20, 50)),
+            ("Used", (12, 25)),
+        ],
+        rows,
     );
-    println!(
-        "{}",
-        "─".repeat(max_title_len + id_len + state_len + time_len + pr_len + 13)
+}
+
+/// Print issue-session links as a table
+pub fn display_links_table(links: &[IssueLink]) {
+    if links.is_empty() {
+        return;
+    }
+
+    let rows = links
+        .iter()
+        .map(|link| {
+            vec![
+                TextCell::plain(link.issue_ref()),
+                TextCell::plain(link.session_id.clone()),
+                TextCell::plain(display_timestamp(&link.linked_at.to_rfc3339())),
+            ]
+        })
+        .collect();
+    print_text_table(
+        &[
+            ("Issue", (12, 40)),
+            ("Session", (20, 22)),
+            ("Linked", (12, 25)),
+        ],
+        rows,
     );
+}
 
-    for session in sessions {
-        let id = &session.id;
-        let title = session.title.as_deref().unwrap_or("Untitled");
+/// Minimum and maximum width of a sessions table column
+fn session_column_width(column: SessionColumn) -> (usize, usize) {
+    match column {
+        SessionColumn::Title => (20, 50),
+        SessionColumn::Id => (20, 20),
+        SessionColumn::State => (11, 24),
+        // "2h ago", "Oct 14, 2025" or a full ISO timestamp
+        SessionColumn::Created | SessionColumn::Updated => (12, 25),
+        SessionColumn::Source => (20, 45),
+        SessionColumn::Branch => (10, 25),
+        SessionColumn::Pr => (6, 6),
+    }
+}
 
-        let state_str = session
-            .state
-            .as_ref()
-            .map(|s| format!("{:?}", s))
-            .unwrap_or("unknown".to_string());
+fn session_cell(session: &Session, column: SessionColumn) -> TextCell {
+    let time = |time: &Option<String>| {
+        time.as_deref()
+            .map(display_timestamp)
+            .unwrap_or_else(|| "-".to_string())
+    };
+    match column {
+        SessionColumn::Title => {
+            TextCell::plain(session.title.as_deref().unwrap_or("Untitled").to_string())
+        }
+        SessionColumn::Id => TextCell::plain(session.id.clone()),
+        SessionColumn::State => {
+            let state = session
+                .state
+                .as_ref()
+                .map(|s| format!("{:?}", s))
+                .unwrap_or("unknown".to_string());
+            TextCell::styled(state, parse_state_for_display)
+        }
+        SessionColumn::Created => TextCell::plain(time(&session.create_time)),
+        SessionColumn::Updated => TextCell::plain(time(&session.update_time)),
+        SessionColumn::Source => TextCell::plain(session.source_context.source.clone()),
+        SessionColumn::Branch => TextCell::plain(
+            session
+                .source_context
+                .github_repo_context
+                .as_ref()
+                .map(|context| context.starting_branch.clone())
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        SessionColumn::Pr => {
+            if session
+                .outputs
+                .iter()
+                .any(|output| output.pull_request.is_some())
+            {
+                TextCell::styled("✓".to_string(), |mark| mark.green())
+            } else {
+                TextCell::plain("-".to_string())
+            }
+        }
+    }
+}
 
-        let truncated_title = if title.len() > max_title_len {
-            format!("{}...", &title[..max_title_len - 3])
-        } else {
-            title.to_string()
-        };
+/// A cell of a plain-text table; the style is applied after padding, so
+/// color codes don't count towards the column width
+struct TextCell {
+    text: String,
+    style: fn(&str) -> ColoredString,
+}
 
-        let truncated_id = if id.len() > 20 {
-            format!("{}...", &id[..17])
-        } else {
-            id.to_string()
-        };
+impl TextCell {
+    fn plain(text: String) -> Self {
+        Self::styled(text, |text| text.normal())
+    }
 
-        let state_display = parse_state_for_display(&state_str);
+    fn styled(text: String, style: fn(&str) -> ColoredString) -> Self {
+        Self { text, style }
+    }
+}
 
-        let create_time = session
-            .create_time
-            .as_ref()
-            .map(|t| display_timestamp(t))
-            .unwrap_or_else(|| "-".to_string());
+/// Print a table framed by rules, sizing each column to its content within
+/// its (minimum, maximum) width, adjusted to the terminal width, and
+/// truncating longer cells
+fn print_text_table(columns: &[(&str, (usize, usize))], rows: Vec<Vec<TextCell>>) {
+    let content_widths: Vec<(usize, (usize, usize))> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, (header, bounds))| {
+            let content = rows
+                .iter()
+                .map(|row: &Vec<TextCell>| display_width(&row[i].text))
+                .chain([display_width(header)])
+                .max()
+                .unwrap_or(0);
+            (content, *bounds)
+        })
+        .collect();
+    let widths = fit_column_widths(&content_widths, terminal_width());
+    let rule = "─".repeat(widths.iter().sum::<usize>() + widths.len().saturating_sub(1));
 
-        // For now, assume no PR info in Session struct
-        let pr_indicator = "-".to_string();
+    println!("{}", rule);
+    let header: Vec<String> = columns
+        .iter()
+        .zip(&widths)
+        .map(|((header, _), width)| pad_to_width(header, *width))
+        .collect();
+    println!("{}", header.join(" ").trim_end());
+    println!("{}", rule);
 
-        println!(
-            "{:<width_title$} {:<20} {:<11} {:<12} {:<6}",
-            truncated_title,
-            truncated_id,
-            state_display,
-            create_time,
-            pr_indicator,
-            width_title = max_title_len
-        );
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| {
+                let text = pad_to_width(&truncate_to_width(&cell.text, *width), *width);
+                // Style the text, not the padding
+                let trimmed = text.trim_end();
+                format!("{}{}", (cell.style)(trimmed), &text[trimmed.len()..])
+            })
+            .collect();
+        println!("{}", cells.join(" ").trim_end());
     }
 
-    println!(
-        "{}",
-        "─".repeat(max_title_len + id_len + state_len + time_len + pr_len + 13)
-    );
+    println!("{}", rule);
 }
 
+/// Write `response` as JSON to `output_path` with a notice, or print it
 pub async fn save_response(
     response: &Value,
     output_path: Option<PathBuf>,
@@ -207,13 +612,20 @@ pub async fn save_response(
     if let Some(path) = output_path {
         fs::write(&path, &json_str)
             .with_context(|| format!("Failed to write to file: {}", path.display()))?;
-        println!("{} Response saved to: {}", "✓".green(), path.display());
+        if !is_quiet() {
+            println!(
+                "{} Response saved to: {}",
+                icon("✓", "OK").green(),
+                path.display()
+            );
+        }
     } else {
         println!("{}", json_str);
     }
 
     Ok(())
 }
+
 fn parse_state_for_display(state_str: &str) -> colored::ColoredString {
     // Parse the state string into State enum
     let state = match state_str {
@@ -247,10 +659,52 @@ fn parse_state_for_display(state_str: &str) -> colored::ColoredString {
 }
 
 /// Display timestamp in human-readable format for CLI (concise) - use ISO format in JSON
+///
+/// Follows the settings of [`set_time_display`].
 pub fn display_timestamp(timestamp: &str) -> String {
-    match DateTime::parse_from_rfc3339(timestamp) {
-        Ok(dt) => {
-            let utc_dt = dt.with_timezone(&Utc);
+    let (format, timezone) = TIME_DISPLAY
+        .read()
+        .map(|settings| *settings)
+        .unwrap_or_default();
+    format_timestamp(timestamp, format, timezone)
+}
+
+/// Format a timestamp for log-style output: always absolute (ISO with
+/// `--time-format iso`, otherwise local time), in the configured timezone
+pub fn log_timestamp(timestamp: &str) -> String {
+    let (format, timezone) = TIME_DISPLAY
+        .read()
+        .map(|settings| *settings)
+        .unwrap_or_default();
+    let format = match format {
+        TimeFormat::Iso => TimeFormat::Iso,
+        TimeFormat::Relative | TimeFormat::Local => TimeFormat::Local,
+    };
+    format_timestamp(timestamp, format, timezone)
+}
+
+/// Format an RFC 3339 timestamp; unparseable input is returned as is
+pub fn format_timestamp(
+    timestamp: &str,
+    format: TimeFormat,
+    timezone: Option<DisplayTimezone>,
+) -> String {
+    let Ok(dt) = DateTime::parse_from_rfc3339(timestamp) else {
+        return timestamp.to_string();
+    };
+    let utc_dt = dt.with_timezone(&Utc);
+
+    match format {
+        TimeFormat::Iso => timezone
+            .unwrap_or(DisplayTimezone::Utc)
+            .convert(utc_dt)
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+        TimeFormat::Local => timezone
+            .unwrap_or(DisplayTimezone::Local)
+            .convert(utc_dt)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        TimeFormat::Relative => {
             let now = Utc::now();
             let duration = now.signed_duration_since(utc_dt);
 
@@ -264,13 +718,13 @@ pub fn display_timestamp(timestamp: &str) -> String {
                 format!("{}d ago", duration.num_days())
             } else {
                 // For older timestamps, show date
-                utc_dt.format("%b %d, %Y").to_string()
+                timezone
+                    .unwrap_or(DisplayTimezone::Utc)
+                    .convert(utc_dt)
+                    .format("%b %d, %Y")
+                    .to_string()
             }
         }
-        Err(_) => {
-            // If parsing fails, return the original timestamp
-            timestamp.to_string()
-        }
     }
 }
 
@@ -281,31 +735,23 @@ pub fn display_activity_summary(activity: &Activity) {
             if let Some(msg) = &activity.agent_messaged {
                 // Truncate long messages for CLI
                 let message = msg.agent_message.as_deref().unwrap_or("[Empty message]");
-                let preview = if message.len() > 80 {
-                    format!("{}...", &message[..77])
-                } else {
-                    message.to_string()
-                };
-                println!("{} Agent: {}", "💬".blue(), preview);
+                let preview = truncate_to_width(message, 80);
+                println!("{} Agent: {}", icon("💬", "*").blue(), preview);
             }
         }
         "User Messaged" => {
             if let Some(msg) = &activity.user_messaged {
                 // Truncate long messages for CLI
                 let message = msg.user_message.as_deref().unwrap_or("[Empty message]");
-                let preview = if message.len() > 80 {
-                    format!("{}...", &message[..77])
-                } else {
-                    message.to_string()
-                };
-                println!("{} User: {}", "👤".green(), preview);
+                let preview = truncate_to_width(message, 80);
+                println!("{} User: {}", icon("👤", ">").green(), preview);
             }
         }
-        "Progress Update" => {
+        "Progress Updated" => {
             if let Some(progress) = &activity.progress_updated {
                 // Show only title, not full description (too verbose for CLI)
                 let title = progress.title.as_deref().unwrap_or("Progress update");
-                println!("{} {}", "⚙".blue(), title);
+                println!("{} {}", icon("⚙", "-").blue(), title);
 
                 // Show artifact summaries if present
                 for artifact in &activity.artifacts {
@@ -317,43 +763,47 @@ pub fn display_activity_summary(activity: &Activity) {
             if let Some(plan_generated) = &activity.plan_generated {
                 display_plan_summary(&plan_generated.plan);
             } else {
-                println!("{} {}", "📋".yellow(), "Plan generated".bold());
+                println!("{} {}", icon("📋", "#").yellow(), "Plan generated".bold());
             }
         }
         "Plan Approved" => {
-            println!("{} {}", "✓".green(), "Plan approved".bold());
+            println!("{} {}", icon("✓", "OK").green(), "Plan approved".bold());
         }
         "Session Completed" => {
-            println!("{} {}", "✓".green(), "Session completed".bold());
+            println!("{} {}", icon("✓", "OK").green(), "Session completed".bold());
         }
         "Session Failed" => {
             if let Some(failed) = &activity.session_failed {
                 let reason = failed.reason.as_deref().unwrap_or("[Unknown reason]");
-                println!("{} Session failed: {}", "✗".red(), reason);
+                println!("{} Session failed: {}", icon("✗", "FAIL").red(), reason);
             } else {
-                println!("{} {}", "✗".red(), "Session failed".bold());
+                println!("{} {}", icon("✗", "FAIL").red(), "Session failed".bold());
             }
         }
         _ => {
             // Show activity type for unknown types
-            println!("{} {}", "❓".dimmed(), activity.activity_type().dimmed());
+            println!(
+                "{} {}",
+                icon("❓", "?").dimmed(),
+                activity.activity_type().dimmed()
+            );
         }
     }
 }
 
 /// Display plan summary for CLI (concise) - use JSON for full details
 pub fn display_plan_summary(plan: &Plan) {
-    println!("{} Plan with {} steps", "📋".yellow(), plan.steps.len());
+    println!(
+        "{} Plan with {} steps",
+        icon("📋", "#").yellow(),
+        plan.steps.len()
+    );
 
     // Show first 3 step titles for context (truncated if long)
     for (i, step) in plan.steps.iter().enumerate().take(3) {
         let step_num = i + 1;
         let title = step.title.as_deref().unwrap_or("[Untitled step]");
-        let title_preview = if title.len() > 60 {
-            format!("{}...", &title[..57])
-        } else {
-            title.to_string()
-        };
+        let title_preview = truncate_to_width(title, 60);
         println!("  {}. {}", step_num, title_preview.dimmed());
     }
 
@@ -364,21 +814,42 @@ pub fn display_plan_summary(plan: &Plan) {
     }
 }
 
+/// Display a plan in full for review: every step with its description
+pub fn display_plan(plan: &Plan) {
+    println!(
+        "{} Plan with {} steps",
+        icon("📋", "#").yellow(),
+        plan.steps.len()
+    );
+
+    for (i, step) in plan.steps.iter().enumerate() {
+        let title = step.title.as_deref().unwrap_or("[Untitled step]");
+        println!();
+        println!("{}", format!("{}. {}", i + 1, title).bold());
+        if let Some(description) = step.description.as_deref() {
+            for line in description.trim().lines() {
+                println!("   {}", line);
+            }
+        }
+    }
+}
+
 /// Display artifact summary for CLI (concise) - use JSON for full content
 pub fn display_artifact_summary(artifact: &Artifact) {
     if let Some(bash) = &artifact.bash_output {
         // Show command and exit code, truncate long commands
         let command = bash.command.as_deref().unwrap_or("[Empty command]");
-        let cmd_preview = if command.len() > 50 {
-            format!("{}...", &command[..47])
-        } else {
-            command.to_string()
-        };
+        let cmd_preview = truncate_to_width(command, 50);
         let exit_status = bash
             .exit_code
             .map(|c| format!("{}", c))
             .unwrap_or_else(|| "unknown".to_string());
-        println!("  {} {} (exit: {})", "🐚".cyan(), cmd_preview, exit_status);
+        println!(
+            "  {} {} (exit: {})",
+            icon("🐚", "$").cyan(),
+            cmd_preview,
+            exit_status
+        );
     }
 
     if let Some(change_set) = &artifact.change_set {
@@ -389,112 +860,92 @@ pub fn display_artifact_summary(artifact: &Artifact) {
                 let removed = unidiff.matches("-\n").count();
                 println!(
                     "  {} Git patch: {} added, {} removed",
-                    "📝".yellow(),
+                    icon("📝", "+").yellow(),
                     added,
                     removed
                 );
             } else {
-                println!("  {} Git patch (no diff available)", "📝".yellow());
+                println!(
+                    "  {} Git patch (no diff available)",
+                    icon("📝", "+").yellow()
+                );
             }
         }
     }
 
     if let Some(media) = &artifact.media {
         let mime_type = media.mime_type.as_deref().unwrap_or("[Unknown type]");
-        println!("  {} Media: {}", "🖼".purple(), mime_type);
+        println!("  {} Media: {}", icon("🖼", "~").purple(), mime_type);
     }
 }
 
-/// Print sources in a formatted table
+/// Print sources in a formatted table, with the columns selected by `--columns`
 pub fn print_sources_table(sources: &[Source]) {
     if sources.is_empty() {
         return;
     }
 
-    let owner_len = 15;
-    let repo_len = 25;
-    let id_len = 30;
-    let private_len = 7;
-    let branches_len = 9;
-
-    println!(
-        "{}",
-        "─".repeat(owner_len + repo_len + id_len + private_len + branches_len + 13)
-    );
-    println!(
-        "{:<width_owner$} {:<width_repo$} {:<width_id$} {:<width_private$} {:<width_branches$}",
-        "Owner",
-        "Repository",
-        "Source ID",
-        "Private",
-        "Branches",
-        width_owner = owner_len,
-        width_repo = repo_len,
-        width_id = id_len,
-        width_private = private_len,
-        width_branches = branches_len
-    );
-    println!(
-        "{}",
-        "─".repeat(owner_len + repo_len + id_len + private_len + branches_len + 13)
+    let columns = selected_columns::<SourceColumn>();
+    let rows = sources
+        .iter()
+        .map(|source| {
+            columns
+                .iter()
+                .map(|column| source_cell(source, *column))
+                .collect()
+        })
+        .collect();
+    print_text_table(
+        &columns
+            .iter()
+            .map(|column| (column.header(), source_column_width(*column)))
+            .collect::<Vec<_>>(),
+        rows,
     );
+}
 
-    for source in sources {
-        let (owner, repo, is_private, branches_count) =
-            if let Some(github_repo) = &source.github_repo {
-                (
-                    github_repo.owner.clone(),
-                    github_repo.repo.clone(),
-                    github_repo.is_private.unwrap_or(false),
-                    github_repo.branches.len(),
-                )
-            } else {
-                ("unknown".to_string(), "unknown".to_string(), false, 0)
-            };
-
-        let truncated_owner = if owner.len() > owner_len {
-            format!("{}...", &owner[..owner_len - 3])
-        } else {
-            owner
-        };
-
-        let truncated_repo = if repo.len() > repo_len {
-            format!("{}...", &repo[..repo_len - 3])
-        } else {
-            repo
-        };
-
-        let truncated_id = if source.id.len() > id_len {
-            format!("{}...", &source.id[..id_len - 3])
-        } else {
-            source.id.clone()
-        };
-
-        let private_display = if is_private {
-            "Yes".red()
-        } else {
-            "No".green()
-        };
-
-        println!(
-            "{:<width_owner$} {:<width_repo$} {:<width_id$} {:<width_private$} {:<width_branches$}",
-            truncated_owner,
-            truncated_repo,
-            truncated_id,
-            private_display,
-            branches_count,
-            width_owner = owner_len,
-            width_repo = repo_len,
-            width_id = id_len,
-            width_private = private_len,
-            width_branches = branches_len
-        );
+/// Minimum and maximum width of a sources table column
+fn source_column_width(column: SourceColumn) -> (usize, usize) {
+    match column {
+        SourceColumn::Owner => (15, 15),
+        SourceColumn::Repo => (25, 25),
+        SourceColumn::Id => (30, 30),
+        SourceColumn::Private => (7, 7),
+        SourceColumn::Branches => (9, 9),
+        SourceColumn::DefaultBranch => (14, 25),
     }
+}
 
-    println!(
-        "{}",
-        "─".repeat(owner_len + repo_len + id_len + private_len + branches_len + 13)
-    );
+fn source_cell(source: &Source, column: SourceColumn) -> TextCell {
+    let repo = source.github_repo.as_ref();
+    match column {
+        SourceColumn::Owner => TextCell::plain(
+            repo.map(|repo| repo.owner.clone())
+                .unwrap_or_else(|| "unknown".to_string()),
+        ),
+        SourceColumn::Repo => TextCell::plain(
+            repo.map(|repo| repo.repo.clone())
+                .unwrap_or_else(|| "unknown".to_string()),
+        ),
+        SourceColumn::Id => TextCell::plain(source.id.clone()),
+        SourceColumn::Private => {
+            if repo.and_then(|repo| repo.is_private).unwrap_or(false) {
+                TextCell::styled("Yes".to_string(), |text| text.red())
+            } else {
+                TextCell::styled("No".to_string(), |text| text.green())
+            }
+        }
+        SourceColumn::Branches => TextCell::plain(
+            repo.map(|repo| repo.branches.len())
+                .unwrap_or(0)
+                .to_string(),
+        ),
+        SourceColumn::DefaultBranch => TextCell::plain(
+            repo.and_then(|repo| repo.default_branch.as_ref())
+                .map(|branch| branch.display_name.clone())
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+    }
 }
 
 pub fn print_activities_table(activities: &[&Activity]) {
@@ -507,41 +958,79 @@ pub fn print_activities_table(activities: &[&Activity]) {
         presets::UTF8_FULL_CONDENSED, Cell, CellAlignment, ContentArrangement, Table,
     };
 
+    let columns = selected_columns::<ActivityColumn>();
+    let width = terminal_width();
+    let rows: Vec<Vec<String>> = activities
+        .iter()
+        .map(|activity| {
+            columns
+                .iter()
+                .map(|column| activity_cell(activity, *column))
+                .collect()
+        })
+        .collect();
+
+    // Content gets at least one full line of whatever the other columns
+    // leave, and wraps on narrow terminals
+    let content_width = width
+        .map(|width| {
+            let others: usize = columns
+                .iter()
+                .enumerate()
+                .filter(|(_, column)| **column != ActivityColumn::Content)
+                .map(|(i, column)| {
+                    rows.iter()
+                        .flat_map(|row| row[i].lines())
+                        .chain([column.header()])
+                        .map(display_width)
+                        .max()
+                        .unwrap_or(0)
+                })
+                .sum();
+            // Each column adds a border and two spaces of padding
+            width.saturating_sub(others + 3 * columns.len() + 1)
+        })
+        .unwrap_or(0)
+        .max(ACTIVITY_CONTENT_WIDTH);
+
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL_CONDENSED)
         .set_content_arrangement(ContentArrangement::Dynamic)
-        .set_header(vec!["Info", "Time", "Content"]);
-
-    for activity in activities {
-        // Prepare ID and Type for the Info column (2 rows)
-        let id = &activity.id;
-        let activity_type = activity.activity_type();
-        let info_cell = format!("{}\n{}", id, activity_type);
-
-        // Format time
-        let time = display_timestamp(&activity.create_time);
-
-        // Prepare content (truncate to 80 chars, remove newlines)
-        let content = activity.content().unwrap_or_else(|| "-".to_string());
-        let display_content = content
-            .replace('\n', " ")
-            .replace("  ", " ")
-            .chars()
-            .take(80)
-            .collect::<String>();
-        let final_content = if content.len() > 80 {
-            format!("{}...", display_content)
-        } else {
-            display_content
-        };
+        .set_header(columns.iter().map(|column| column.header()));
+    if let Some(width) = width {
+        table.set_width(u16::try_from(width).unwrap_or(u16::MAX));
+    }
 
-        table.add_row(vec![
-            Cell::new(info_cell).set_alignment(CellAlignment::Left),
-            Cell::new(time).set_alignment(CellAlignment::Left),
-            Cell::new(final_content).set_alignment(CellAlignment::Left),
-        ]);
+    for row in rows {
+        table.add_row(columns.iter().zip(row).map(|(column, text)| {
+            let text = if *column == ActivityColumn::Content {
+                truncate_to_width(&text, content_width)
+            } else {
+                text
+            };
+            Cell::new(text).set_alignment(CellAlignment::Left)
+        }));
     }
 
     println!("{table}");
 }
+
+/// Activity content is cut at this width, or at the room left on wider terminals
+const ACTIVITY_CONTENT_WIDTH: usize = 80;
+
+fn activity_cell(activity: &Activity, column: ActivityColumn) -> String {
+    match column {
+        // ID and type on two lines
+        ActivityColumn::Info => format!("{}\n{}", activity.id, activity.activity_type()),
+        ActivityColumn::Id => activity.id.clone(),
+        ActivityColumn::Type => activity.activity_type(),
+        ActivityColumn::Time => display_timestamp(&activity.create_time),
+        ActivityColumn::Originator => activity.originator.clone(),
+        ActivityColumn::Content => {
+            // One line; truncated to the table width by the caller
+            let content = activity.content().unwrap_or_else(|| "-".to_string());
+            content.replace('\n', " ").replace("  ", " ")
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Opening files in the user's editor, and reading long text (prompts and
+//! messages) from the editor, stdin or a file.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+
+/// The editor command from `$VISUAL` or `$EDITOR`, falling back to the
+/// platform default
+pub fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        })
+}
+
+/// Open `path` in the editor and wait for it to exit
+///
+/// The editor command may include arguments (e.g. `code --wait`).
+pub fn edit_file(path: &Path) -> Result<()> {
+    let editor = editor_command();
+    let mut parts = editor.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No editor configured (set $EDITOR)"))?;
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to start editor '{}'", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", editor, status);
+    }
+    Ok(())
+}
+
+/// Edit `initial` text in the editor and return the result
+///
+/// The text goes through a temporary Markdown file, removed afterwards.
+pub fn edit_text(initial: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("gules-{}.md", std::process::id()));
+    fs::write(&path, initial).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let edited = edit_file(&path).and_then(|_| {
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))
+    });
+    let _ = fs::remove_file(&path);
+    edited
+}
+
+/// The text of a prompt argument: `-` reads it from `input` (stdin)
+///
+/// Fails on an empty prompt, so an aborted edit or empty pipe doesn't start
+/// a session.
+pub fn prompt_from_input(prompt: &str, mut input: impl Read) -> Result<String> {
+    let text = if prompt == "-" {
+        let mut text = String::new();
+        input
+            .read_to_string(&mut text)
+            .context("Failed to read the prompt from stdin")?;
+        text
+    } else {
+        prompt.to_string()
+    };
+    non_empty_prompt(text)
+}
+
+/// The text of a message to send: the contents of `file`, or the `message`
+/// argument; `-` for either reads `input` (stdin)
+///
+/// Trailing whitespace is dropped but indentation is kept, so stack traces
+/// and test output arrive as written. Fails on an empty message.
+pub fn message_from_input(
+    message: Option<&str>,
+    file: Option<&str>,
+    mut input: impl Read,
+) -> Result<String> {
+    let text = match (file, message) {
+        (Some("-"), _) | (None, Some("-")) => {
+            let mut text = String::new();
+            input
+                .read_to_string(&mut text)
+                .context("Failed to read the message from stdin")?;
+            text
+        }
+        (Some(path), _) => {
+            fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?
+        }
+        (None, message) => message.unwrap_or_default().to_string(),
+    };
+    let text = text.trim_end();
+    if text.trim().is_empty() {
+        anyhow::bail!("Empty message, nothing sent");
+    }
+    Ok(text.to_string())
+}
+
+/// `text` without trailing whitespace, or an error if nothing is left
+pub fn non_empty_prompt(text: String) -> Result<String> {
+    let text = text.trim_end();
+    if text.trim().is_empty() {
+        anyhow::bail!("Empty prompt, no session created");
+    }
+    Ok(text.to_string())
+}
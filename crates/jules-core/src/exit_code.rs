@@ -0,0 +1,81 @@
+//! Process exit codes for scripting.
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0    | Success |
+//! | 1    | Other error |
+//! | 2    | Not found (HTTP 404) |
+//! | 3    | API or network error |
+//! | 4    | Timeout |
+//! | 5    | Authentication failed (HTTP 401/403) |
+//! | 10   | Watched session failed |
+//! | 11   | Watched session paused |
+//! | 12   | Timed out watching a session or waiting for PR checks |
+//! | 64   | Invalid command-line usage |
+//!
+//! Commands pick a code explicitly with [`ExitError`]; other errors are
+//! classified by [`exit_code`] from their cause chain.
+
+use jules_rs::types::error::HttpError;
+use std::fmt;
+
+pub const SUCCESS: u8 = 0;
+pub const ERROR: u8 = 1;
+pub const NOT_FOUND: u8 = 2;
+pub const API_ERROR: u8 = 3;
+pub const TIMEOUT: u8 = 4;
+pub const AUTH_FAILED: u8 = 5;
+pub const SESSION_FAILED: u8 = 10;
+pub const SESSION_PAUSED: u8 = 11;
+pub const WATCH_TIMEOUT: u8 = 12;
+/// `EX_USAGE` from sysexits.h; clap's default of 2 would clash with [`NOT_FOUND`]
+pub const USAGE: u8 = 64;
+
+/// An error that exits the process with a specific code
+#[derive(Debug, Clone)]
+pub struct ExitError {
+    pub code: u8,
+    message: String,
+}
+
+impl ExitError {
+    pub fn new(code: u8, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ExitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ExitError {}
+
+/// The exit code for an error, from the first classifiable cause
+pub fn exit_code(error: &anyhow::Error) -> u8 {
+    for cause in error.chain() {
+        if let Some(exit_error) = cause.downcast_ref::<ExitError>() {
+            return exit_error.code;
+        }
+        if let Some(http_error) = cause.downcast_ref::<HttpError>() {
+            return match http_error.status {
+                404 => NOT_FOUND,
+                401 | 403 => AUTH_FAILED,
+                408 | 504 => TIMEOUT,
+                _ => API_ERROR,
+            };
+        }
+        if let Some(request_error) = cause.downcast_ref::<reqwest::Error>() {
+            return if request_error.is_timeout() {
+                TIMEOUT
+            } else {
+                API_ERROR
+            };
+        }
+    }
+    ERROR
+}
@@ -0,0 +1,96 @@
+//! Running git in a local repository.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+/// Run git in `dir` and return its trimmed stdout
+pub fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = git_command(dir, args)
+        .output()
+        .context("Failed to run git (is it installed?)")?;
+    check(args, output)
+}
+
+/// Run git in `dir` with `input` on stdin (e.g. `git apply -`)
+pub fn git_with_input(dir: &Path, args: &[&str], input: &str) -> Result<String> {
+    let mut child = git_command(dir, args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run git (is it installed?)")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .context("Failed to write to git")?;
+    }
+    check(args, child.wait_with_output().context("Failed to run git")?)
+}
+
+/// Whether `dir` is inside a git work tree
+pub fn in_work_tree(dir: &Path) -> bool {
+    git(dir, &["rev-parse", "--is-inside-work-tree"]).is_ok_and(|out| out == "true")
+}
+
+/// Owner and repository of a GitHub remote URL (HTTPS or SSH)
+pub fn github_repo(remote_url: &str) -> Option<(String, String)> {
+    let url = remote_url.trim();
+    let path = [
+        "https://github.com/",
+        "http://github.com/",
+        "ssh://git@github.com/",
+        "git@github.com:",
+    ]
+    .iter()
+    .find_map(|prefix| url.strip_prefix(prefix))?;
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some((owner.to_string(), repo.to_string()))
+}
+
+/// GitHub repository of a local checkout, and its current branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoContext {
+    pub owner: String,
+    pub repo: String,
+    /// The current branch, if it tracks a branch on origin (so it was pushed)
+    pub branch: Option<String>,
+}
+
+/// Repository and branch of the checkout at `dir`, from its `origin` remote
+/// and upstream; `None` outside a checkout or without a GitHub origin
+pub fn repo_context(dir: &Path) -> Option<RepoContext> {
+    let remote = git(dir, &["remote", "get-url", "origin"]).ok()?;
+    let (owner, repo) = github_repo(&remote)?;
+    let branch = git(dir, &["rev-parse", "--abbrev-ref", "@{upstream}"])
+        .ok()
+        .and_then(|upstream| upstream.strip_prefix("origin/").map(str::to_string));
+    Some(RepoContext {
+        owner,
+        repo,
+        branch,
+    })
+}
+
+fn git_command(dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(dir).args(args);
+    command
+}
+
+fn check(args: &[&str], output: Output) -> Result<String> {
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,325 @@
+//! Cache of GitHub API responses and rate limits.
+//!
+//! GET responses are kept with their ETag, so asking again sends
+//! `If-None-Match` and an unchanged resource comes back as
+//! `304 Not Modified`, which doesn't count against the rate limit. Responses
+//! unused for a week are dropped, as are the oldest once they take up more
+//! than 20 MiB, and none are kept with `cache.encrypt` set. The rate
+//! limit GitHub reports with each response is recorded per resource
+//! (`core`, `graphql`, ...), so polling commands like `pr ci-watch` can
+//! slow down before they use it up and `gules github limits` can show it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::atomic_file::write_atomic;
+use crate::config::CacheConfig;
+
+/// Requests of a resource left for other commands when polling commands
+/// pause, unless `github.rate_limit_reserve` says otherwise
+pub const DEFAULT_RATE_LIMIT_RESERVE: u64 = 100;
+
+/// Cached responses unused for longer are dropped
+pub const MAX_RESPONSE_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// The oldest cached responses are dropped beyond this total size
+pub const MAX_RESPONSES_BYTES: u64 = 20 * 1024 * 1024;
+
+/// The rate limit of one GitHub API resource, as last reported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// `core` for the REST API, `graphql`, `search`, ...
+    pub resource: String,
+    pub limit: u64,
+    pub remaining: u64,
+    pub used: u64,
+    /// When `remaining` goes back to `limit`
+    pub reset: DateTime<Utc>,
+}
+
+impl RateLimit {
+    /// The rate limit in the `x-ratelimit-*` headers of a response, looked
+    /// up by (lowercase) name with `header`
+    pub fn from_headers<'a>(header: impl Fn(&str) -> Option<&'a str>) -> Option<Self> {
+        let number = |name: &str| header(name)?.trim().parse::<u64>().ok();
+        let limit = number("x-ratelimit-limit")?;
+        let remaining = number("x-ratelimit-remaining")?;
+        let reset = Utc
+            .timestamp_opt(i64::try_from(number("x-ratelimit-reset")?).ok()?, 0)
+            .single()?;
+        Some(Self {
+            resource: header("x-ratelimit-resource")
+                .unwrap_or("core")
+                .trim()
+                .to_string(),
+            limit,
+            remaining,
+            used: number("x-ratelimit-used").unwrap_or(limit.saturating_sub(remaining)),
+            reset,
+        })
+    }
+
+    /// The rate limit of `resource` in an entry of the `resources` object
+    /// returned by `GET /rate_limit`
+    pub fn from_json(resource: &str, value: &serde_json::Value) -> Option<Self> {
+        let limit = value["limit"].as_u64()?;
+        let remaining = value["remaining"].as_u64()?;
+        Some(Self {
+            resource: resource.to_string(),
+            limit,
+            remaining,
+            used: value["used"]
+                .as_u64()
+                .unwrap_or(limit.saturating_sub(remaining)),
+            reset: Utc.timestamp_opt(value["reset"].as_i64()?, 0).single()?,
+        })
+    }
+
+    /// How long to wait for the reset if no more than `reserve` requests are
+    /// left at `now`; `None` if there are more, or the limit has reset
+    pub fn wait_for(&self, reserve: u64, now: DateTime<Utc>) -> Option<Duration> {
+        if self.remaining > reserve || self.reset <= now {
+            return None;
+        }
+        (self.reset - now).to_std().ok()
+    }
+
+    /// E.g. `12 of 5000 core requests left until 14:05`
+    pub fn summary(&self) -> String {
+        format!(
+            "{} of {} {} requests left until {}",
+            self.remaining,
+            self.limit,
+            self.resource,
+            self.reset.with_timezone(&Local).format("%H:%M")
+        )
+    }
+}
+
+/// The last rate limit reported for each resource
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RateLimits {
+    #[serde(default)]
+    pub resources: BTreeMap<String, RateLimit>,
+}
+
+/// Get the GitHub response cache directory
+pub fn get_github_cache_dir() -> Result<PathBuf> {
+    let cache_dir =
+        dirs::cache_dir().ok_or_else(|| anyhow::anyhow!("Could not determine cache directory"))?;
+    Ok(cache_dir.join("gules").join("github"))
+}
+
+/// Remove the GitHub response cache and the recorded rate limits
+pub fn clear_github_cache() -> Result<()> {
+    let dir = get_github_cache_dir()?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir).context("Failed to clear the GitHub cache")?;
+    }
+    Ok(())
+}
+
+/// Get the rate limits file path
+pub fn get_rate_limits_path() -> Result<PathBuf> {
+    Ok(get_github_cache_dir()?.join("rate_limits.json"))
+}
+
+impl RateLimits {
+    /// Load the rate limits from the default location (empty if missing)
+    pub fn load() -> Result<Self> {
+        Self::load_from(&get_rate_limits_path()?)
+    }
+
+    /// Load the rate limits from `path` (empty if missing)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).context("Failed to read GitHub rate limits")?;
+        serde_json::from_str(&contents).context("Failed to parse GitHub rate limits")
+    }
+
+    /// Save the rate limits to `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create cache directory")?;
+        }
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize GitHub rate limits")?;
+        write_atomic(path, contents).context("Failed to write GitHub rate limits")?;
+        Ok(())
+    }
+
+    /// The rate limits in the response to `GET /rate_limit`
+    pub fn from_json(response: &serde_json::Value) -> Self {
+        let resources = response["resources"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(resource, value)| {
+                Some((resource.clone(), RateLimit::from_json(resource, value)?))
+            })
+            .collect();
+        Self { resources }
+    }
+
+    /// Replace the rate limit of its resource with `limit`
+    pub fn record(&mut self, limit: RateLimit) {
+        self.resources.insert(limit.resource.clone(), limit);
+    }
+
+    /// The rate limit of `resource` if no more than `reserve` requests of it
+    /// are left at `now`
+    pub fn low(&self, resource: &str, reserve: u64, now: DateTime<Utc>) -> Option<&RateLimit> {
+        self.resources
+            .get(resource)
+            .filter(|limit| limit.wait_for(reserve, now).is_some())
+    }
+}
+
+/// Record `limits` in the rate limits file at the default location
+pub fn record_rate_limits(limits: impl IntoIterator<Item = RateLimit>) -> Result<()> {
+    let path = get_rate_limits_path()?;
+    let mut stored = RateLimits::load_from(&path).unwrap_or_default();
+    for limit in limits {
+        stored.record(limit);
+    }
+    stored.save_to(&path)
+}
+
+/// A GET response kept for revalidation with its ETag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub url: String,
+    pub etag: String,
+    pub body: String,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// GET responses by URL, one file each
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+}
+
+impl ResponseCache {
+    /// The cache in the default location
+    pub fn open() -> Result<Self> {
+        Ok(Self::new(get_github_cache_dir()?.join("responses")))
+    }
+
+    /// The cache in the default location, unless the `[cache]` settings
+    /// disable it or ask for encryption, which it doesn't support
+    ///
+    /// With `encrypt` set, responses cached before are removed.
+    pub fn open_configured(config: &CacheConfig) -> Result<Option<Self>> {
+        let cache = Self::open()?;
+        if config.encrypt {
+            cache.clear()?;
+        }
+        Ok((config.enabled && !config.encrypt).then_some(cache))
+    }
+
+    /// The cache in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Location of the cached responses
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        let hash = Sha256::digest(url.as_bytes());
+        let name: String = hash.iter().map(|byte| format!("{:02x}", byte)).collect();
+        self.dir.join(format!("{}.json", name))
+    }
+
+    /// The cached response for `url`, if any
+    pub fn get(&self, url: &str) -> Option<CachedResponse> {
+        let contents = fs::read_to_string(self.path(url)).ok()?;
+        serde_json::from_str::<CachedResponse>(&contents)
+            .ok()
+            .filter(|cached| cached.url == url)
+    }
+
+    /// Keep `body`, the response for `url` with `etag`, then prune the cache
+    /// to [`MAX_RESPONSE_AGE`] and [`MAX_RESPONSES_BYTES`]
+    pub fn put(&self, url: &str, etag: &str, body: &str) -> Result<()> {
+        fs::create_dir_all(&self.dir).context("Failed to create cache directory")?;
+        let cached = CachedResponse {
+            url: url.to_string(),
+            etag: etag.to_string(),
+            body: body.to_string(),
+            fetched_at: Utc::now(),
+        };
+        let contents =
+            serde_json::to_string(&cached).context("Failed to serialize GitHub response")?;
+        write_atomic(&self.path(url), contents).context("Failed to write GitHub response cache")?;
+        self.prune(MAX_RESPONSE_AGE, MAX_RESPONSES_BYTES)?;
+        Ok(())
+    }
+
+    /// Cached response files with their size and modification time, oldest
+    /// first
+    fn entries(&self) -> Result<Vec<(PathBuf, u64, SystemTime)>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir).context("Failed to read GitHub response cache")? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Ok(metadata) = fs::metadata(&path) else {
+                continue;
+            };
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            entries.push((path, metadata.len(), modified));
+        }
+        entries.sort_by_key(|(_, _, modified)| *modified);
+        Ok(entries)
+    }
+
+    /// Drop responses written more than `max_age` ago, then the oldest until
+    /// the rest fit in `max_bytes`; returns how many were dropped
+    pub fn prune(&self, max_age: Duration, max_bytes: u64) -> Result<usize> {
+        let entries = self.entries()?;
+        let cutoff = SystemTime::now()
+            .checked_sub(max_age)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        let mut removed = 0;
+        for (path, size, modified) in entries {
+            if modified >= cutoff && total <= max_bytes {
+                break;
+            }
+            fs::remove_file(&path).context("Failed to prune GitHub response cache")?;
+            total = total.saturating_sub(size);
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Number and total size of the cached responses
+    pub fn usage(&self) -> Result<(usize, u64)> {
+        let entries = self.entries()?;
+        Ok((entries.len(), entries.iter().map(|(_, size, _)| size).sum()))
+    }
+
+    /// Remove every cached response
+    pub fn clear(&self) -> Result<()> {
+        if self.dir.exists() {
+            fs::remove_dir_all(&self.dir).context("Failed to clear GitHub response cache")?;
+        }
+        Ok(())
+    }
+}
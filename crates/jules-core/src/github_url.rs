@@ -0,0 +1,126 @@
+//! Parsing GitHub pull request and issue URLs.
+//!
+//! Accepts github.com and GitHub Enterprise hosts, and tolerates whatever
+//! follows the number: `/files`, `/commits/<sha>`, a query or a fragment.
+
+use anyhow::{Context, Result};
+use reqwest::Url;
+use std::fmt;
+
+/// Host of public GitHub
+pub const GITHUB_HOST: &str = "github.com";
+
+/// What a GitHub URL points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitHubItemKind {
+    PullRequest,
+    Issue,
+}
+
+impl GitHubItemKind {
+    /// The path segment naming this kind, as in `/owner/repo/pull/1`
+    fn segment(self) -> &'static str {
+        match self {
+            Self::PullRequest => "pull",
+            Self::Issue => "issues",
+        }
+    }
+}
+
+/// A pull request or issue on a GitHub host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubItemUrl {
+    /// Host name, `github.com` or an enterprise host
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+    pub kind: GitHubItemKind,
+    pub number: u64,
+}
+
+impl GitHubItemUrl {
+    /// Parse a pull request or issue URL
+    pub fn parse(url: &str) -> Result<Self> {
+        let parsed =
+            Url::parse(url.trim()).with_context(|| format!("Invalid GitHub URL: {}", url))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            anyhow::bail!("Invalid GitHub URL: {} (expected http or https)", url);
+        }
+        let host = parsed
+            .host_str()
+            .with_context(|| format!("Invalid GitHub URL: {} (no host)", url))?
+            .to_lowercase();
+        let host = host.strip_prefix("www.").unwrap_or(&host).to_string();
+
+        let segments: Vec<&str> = parsed
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+        let [owner, repo, kind, number, ..] = segments[..] else {
+            anyhow::bail!(
+                "Invalid GitHub URL: {} (expected https://HOST/OWNER/REPO/pull/NUMBER)",
+                url
+            );
+        };
+        let kind = match kind {
+            "pull" | "pulls" => GitHubItemKind::PullRequest,
+            "issues" => GitHubItemKind::Issue,
+            _ => anyhow::bail!("Invalid GitHub URL: {} (not a pull request or issue)", url),
+        };
+        let number = number
+            .parse()
+            .ok()
+            .filter(|number| *number > 0)
+            .with_context(|| format!("Invalid GitHub URL: {} (bad number: {})", url, number))?;
+
+        Ok(Self {
+            host,
+            owner: owner.to_string(),
+            repo: repo.trim_end_matches(".git").to_string(),
+            kind,
+            number,
+        })
+    }
+
+    /// Parse a pull request URL, rejecting issue URLs
+    pub fn parse_pull_request(url: &str) -> Result<Self> {
+        let item = Self::parse(url)?;
+        if item.kind != GitHubItemKind::PullRequest {
+            anyhow::bail!("Not a pull request URL: {}", url);
+        }
+        Ok(item)
+    }
+
+    /// Whether this is on github.com rather than an enterprise host
+    pub fn is_github_com(&self) -> bool {
+        self.host == GITHUB_HOST
+    }
+
+    /// The repository as `gh --repo` takes it: `OWNER/REPO`, prefixed by
+    /// the host for enterprise hosts
+    pub fn repo_spec(&self) -> String {
+        if self.is_github_com() {
+            format!("{}/{}", self.owner, self.repo)
+        } else {
+            format!("{}/{}/{}", self.host, self.owner, self.repo)
+        }
+    }
+
+    /// The canonical web URL
+    pub fn web_url(&self) -> String {
+        format!(
+            "https://{}/{}/{}/{}/{}",
+            self.host,
+            self.owner,
+            self.repo,
+            self.kind.segment(),
+            self.number
+        )
+    }
+}
+
+impl fmt::Display for GitHubItemUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}#{}", self.owner, self.repo, self.number)
+    }
+}
@@ -0,0 +1,228 @@
+//! Standalone HTML reports of sessions.
+//!
+//! A single page with inline CSS and no scripts, for sharing a session with
+//! people who don't run gules: details, prompt, plan and progress, bash
+//! output and colored diffs. Everything taken from the API is escaped.
+
+use crate::markdown_output::{pull_request_url, state_name};
+use jules_rs::types::activity::{Activity, Artifact};
+use jules_rs::types::session::Session;
+use std::fmt::Write;
+
+const STYLE: &str = "\
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; color: #1f2328; line-height: 1.5; }
+dl { display: grid; grid-template-columns: max-content auto; gap: 0.25rem 1rem; }
+dt { font-weight: 600; }
+dd { margin: 0; }
+blockquote { margin: 0; padding: 0 1rem; border-left: 0.25rem solid #d0d7de; color: #59636e; white-space: pre-wrap; }
+section { border-top: 1px solid #d0d7de; margin-top: 1.5rem; }
+.meta { color: #59636e; font-size: 0.875rem; }
+.text { white-space: pre-wrap; }
+pre { background: #f6f8fa; padding: 1rem; overflow-x: auto; border-radius: 6px; font-size: 0.85rem; }
+.add { color: #1a7f37; }
+.del { color: #d1242f; }
+.hunk { color: #0969da; }
+.header { font-weight: 600; }
+";
+
+/// A session report as a complete HTML document, with its activities
+/// (oldest first) as a timeline
+pub fn session_html(session: &Session, activities: &[&Activity]) -> String {
+    let title = escape(session.title.as_deref().unwrap_or("Untitled session"));
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        title, STYLE, title
+    );
+
+    html.push_str("<dl>\n");
+    detail(
+        &mut html,
+        "Session",
+        &format!("<code>{}</code>", escape(&session.id)),
+    );
+    detail(&mut html, "State", state_name(session));
+    detail(
+        &mut html,
+        "Source",
+        &format!("<code>{}</code>", escape(&session.source_context.source)),
+    );
+    if let Some(context) = &session.source_context.github_repo_context {
+        detail(
+            &mut html,
+            "Branch",
+            &format!("<code>{}</code>", escape(&context.starting_branch)),
+        );
+    }
+    if let Some(created) = &session.create_time {
+        detail(&mut html, "Created", &escape(created));
+    }
+    if let Some(updated) = &session.update_time {
+        detail(&mut html, "Updated", &escape(updated));
+    }
+    if let Some(url) = &session.url {
+        detail(&mut html, "Jules", &link(url));
+    }
+    if let Some(url) = pull_request_url(session) {
+        detail(&mut html, "Pull request", &link(url));
+    }
+    html.push_str("</dl>\n");
+
+    let _ = writeln!(
+        html,
+        "<h2>Prompt</h2>\n<blockquote>{}</blockquote>",
+        escape(session.prompt.trim_end())
+    );
+
+    if !activities.is_empty() {
+        let _ = writeln!(html, "<h2>Activities ({})</h2>", activities.len());
+        for (i, activity) in activities.iter().enumerate() {
+            html.push_str(&activity_html(i + 1, activity));
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// One activity as a `<section>`
+fn activity_html(number: usize, activity: &Activity) -> String {
+    let mut html = format!(
+        "<section>\n<h3>{}. {}</h3>\n<p class=\"meta\">{} · {} · <code>{}</code></p>\n",
+        number,
+        escape(&activity.activity_type()),
+        escape(&activity.create_time),
+        escape(&activity.originator),
+        escape(&activity.id)
+    );
+
+    if let Some(plan) = activity.plan_generated.as_ref().map(|p| &p.plan) {
+        html.push_str("<ol>\n");
+        for step in &plan.steps {
+            let title = step.title.as_deref().unwrap_or("Untitled step");
+            let _ = write!(html, "<li><strong>{}</strong>", escape(title));
+            if let Some(description) = step.description.as_deref().filter(|d| !d.is_empty()) {
+                let _ = write!(html, "<br>{}", escape(description));
+            }
+            html.push_str("</li>\n");
+        }
+        html.push_str("</ol>\n");
+    } else if let Some(progress) = &activity.progress_updated {
+        if let Some(title) = &progress.title {
+            let _ = writeln!(html, "<p><strong>{}</strong></p>", escape(title));
+        }
+        if let Some(description) = progress.description.as_deref().filter(|d| !d.is_empty()) {
+            let _ = writeln!(
+                html,
+                "<p class=\"text\">{}</p>",
+                escape(description.trim_end())
+            );
+        }
+    } else if let Some(content) = activity.content() {
+        let _ = writeln!(html, "<p class=\"text\">{}</p>", escape(content.trim_end()));
+    }
+
+    for artifact in &activity.artifacts {
+        html.push_str(&artifact_html(artifact));
+    }
+    html.push_str("</section>\n");
+    html
+}
+
+fn artifact_html(artifact: &Artifact) -> String {
+    let mut html = String::new();
+    if let Some(bash) = &artifact.bash_output {
+        let exit = bash
+            .exit_code
+            .map(|code| format!(" (exit code {})", code))
+            .unwrap_or_default();
+        let _ = writeln!(html, "<p><strong>Command</strong>{}:</p>", exit);
+        let mut console = format!("$ {}", bash.command.as_deref().unwrap_or("").trim());
+        if let Some(output) = bash.output.as_deref().filter(|o| !o.trim().is_empty()) {
+            console.push('\n');
+            console.push_str(output.trim_end());
+        }
+        let _ = writeln!(html, "<pre>{}</pre>", escape(&console));
+    }
+
+    if let Some(git_patch) = artifact
+        .change_set
+        .as_ref()
+        .and_then(|change_set| change_set.git_patch.as_ref())
+    {
+        if let Some(message) = &git_patch.suggested_commit_message {
+            let _ = writeln!(
+                html,
+                "<p><strong>Suggested commit:</strong> {}</p>",
+                escape(message)
+            );
+        }
+        match git_patch.unidiff_patch.as_deref().filter(|p| !p.is_empty()) {
+            Some(patch) => html.push_str(&diff_html(patch)),
+            None => html.push_str("<p><em>Change set without a diff</em></p>\n"),
+        }
+    }
+
+    if let Some(media) = &artifact.media {
+        let _ = writeln!(
+            html,
+            "<p><em>Media: {}</em></p>",
+            escape(media.mime_type.as_deref().unwrap_or("unknown type"))
+        );
+    }
+    html
+}
+
+/// A unified diff in a `<pre>`, each line classed by its kind
+fn diff_html(patch: &str) -> String {
+    let mut html = String::from("<pre>");
+    for line in patch.trim_end().lines() {
+        let class =
+            if line.starts_with("+++") || line.starts_with("---") || line.starts_with("diff ") {
+                Some("header")
+            } else if line.starts_with("@@") {
+                Some("hunk")
+            } else if line.starts_with('+') {
+                Some("add")
+            } else if line.starts_with('-') {
+                Some("del")
+            } else {
+                None
+            };
+        match class {
+            Some(class) => {
+                let _ = writeln!(html, "<span class=\"{}\">{}</span>", class, escape(line));
+            }
+            None => {
+                let _ = writeln!(html, "{}", escape(line));
+            }
+        }
+    }
+    html.push_str("</pre>\n");
+    html
+}
+
+fn detail(html: &mut String, term: &str, value: &str) {
+    let _ = writeln!(html, "<dt>{}</dt><dd>{}</dd>", term, value);
+}
+
+fn link(url: &str) -> String {
+    let url = escape(url);
+    format!("<a href=\"{}\">{}</a>", url, url)
+}
+
+/// Text escaped for HTML content and attribute values
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
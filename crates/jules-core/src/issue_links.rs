@@ -0,0 +1,124 @@
+//! Links between GitHub issues and the sessions working on them
+//! (`gules link issue`, `gules links list`).
+//!
+//! Recorded when a session is created from an issue or linked by hand, so
+//! `issue-status`, reports and completion comments know the issue a session
+//! belongs to (and the sessions of an issue) without scraping issue
+//! comments.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use jules_rs::SessionName;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::atomic_file::write_atomic;
+
+/// A session working on a GitHub issue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueLink {
+    pub owner: String,
+    pub repo: String,
+    pub issue: u64,
+    pub session_id: String,
+    pub linked_at: DateTime<Utc>,
+}
+
+impl IssueLink {
+    /// Whether this links issue `issue` of `owner`/`repo` (case-insensitively,
+    /// like GitHub)
+    pub fn is_for_issue(&self, owner: &str, repo: &str, issue: u64) -> bool {
+        self.issue == issue
+            && self.owner.eq_ignore_ascii_case(owner)
+            && self.repo.eq_ignore_ascii_case(repo)
+    }
+
+    /// The issue as GitHub writes references to it, e.g. `octo/app#12`
+    pub fn issue_ref(&self) -> String {
+        format!("{}/{}#{}", self.owner, self.repo, self.issue)
+    }
+}
+
+/// Issue-session links, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssueLinks {
+    #[serde(default)]
+    pub links: Vec<IssueLink>,
+}
+
+/// Get the issue links file path
+pub fn get_links_path() -> Result<PathBuf> {
+    let data_dir =
+        dirs::data_dir().ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+    Ok(data_dir.join("gules").join("links.json"))
+}
+
+impl IssueLinks {
+    /// Load the links from the default location (empty if missing)
+    pub fn load() -> Result<Self> {
+        Self::load_from(&get_links_path()?)
+    }
+
+    /// Load the links from `path` (empty if missing)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).context("Failed to read issue links")?;
+        serde_json::from_str(&contents).context("Failed to parse issue links")
+    }
+
+    /// Save the links to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_links_path()?)
+    }
+
+    /// Save the links to `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create data directory")?;
+        }
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize issue links")?;
+        write_atomic(path, contents).context("Failed to write issue links")?;
+        Ok(())
+    }
+
+    /// Link a session, given by ID or resource name, to an issue; returns
+    /// false if they were already linked
+    pub fn link(&mut self, owner: &str, repo: &str, issue: u64, session_id: &str) -> Result<bool> {
+        let session_id = SessionName::from_id_or_name(session_id)?.id().to_string();
+        if self
+            .links
+            .iter()
+            .any(|link| link.session_id == session_id && link.is_for_issue(owner, repo, issue))
+        {
+            return Ok(false);
+        }
+        self.links.push(IssueLink {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            issue,
+            session_id,
+            linked_at: Utc::now(),
+        });
+        Ok(true)
+    }
+
+    /// The sessions linked to an issue, oldest first
+    pub fn for_issue(&self, owner: &str, repo: &str, issue: u64) -> Vec<&IssueLink> {
+        self.links
+            .iter()
+            .filter(|link| link.is_for_issue(owner, repo, issue))
+            .collect()
+    }
+
+    /// The issues a session is linked to
+    pub fn for_session(&self, session_id: &str) -> Vec<&IssueLink> {
+        self.links
+            .iter()
+            .filter(|link| link.session_id == session_id)
+            .collect()
+    }
+}
@@ -3,15 +3,57 @@
 //! Shared utilities for Gules CLI and MCP server.
 //!
 //! This crate contains common functionality used by both the CLI and MCP
-//! server implementations, including configuration management and display
-//! utilities. It's not published to crates.io as it's internal to the
-//! Gules ecosystem.
+//! server implementations, including configuration management, display
+//! utilities and request metrics. It's not published to crates.io as it's
+//! internal to the Gules ecosystem.
 
 pub mod activity_cache;
+pub mod activity_store;
+pub mod atomic_file;
+pub mod bash_index;
+pub mod browser;
+pub mod cache_archive;
+pub mod cache_crypto;
+pub mod cache_events;
+pub mod cache_lock;
 pub mod config;
+pub mod csv_output;
 pub mod display;
+pub mod editor;
+pub mod exit_code;
+pub mod git;
+pub mod github_cache;
+pub mod github_url;
+pub mod html_output;
+pub mod issue_links;
+pub mod markdown_output;
+pub mod metrics;
+pub mod ndjson_output;
+pub mod patch_files;
+pub mod progress;
+pub mod prompt;
+pub mod recent_sessions;
+pub mod search_index;
+pub mod session_cache;
+pub mod session_match;
+pub mod session_notes;
+pub mod session_refs;
+pub mod session_report;
+pub mod sorting;
+pub mod source_cache;
+pub mod sqlite_cache;
+pub mod table_columns;
+pub mod task_file;
+pub mod task_queue;
+pub mod template_output;
+pub mod time_range;
+pub mod webhook;
+pub mod yaml_output;
 
 // Re-export commonly used types
 pub use activity_cache::*;
+pub use activity_store::{open_store, ActivityStore, FileStore, MemoryStore};
 pub use config::*;
 pub use display::*;
+pub use metrics::*;
+pub use sqlite_cache::{ActivityQuery, SqliteCache};
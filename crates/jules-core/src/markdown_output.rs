@@ -0,0 +1,196 @@
+//! Markdown reports of sessions and activities.
+//!
+//! Rendered for pasting into pull requests and issues: a heading per
+//! activity, plan steps as numbered lists, bash output and diffs in fenced
+//! code blocks. Code fences grow past the longest backtick run in their
+//! content, so embedded Markdown cannot break out of a block.
+
+use jules_rs::types::activity::{Activity, Artifact};
+use jules_rs::types::session::Session;
+use std::fmt::Write;
+
+/// A session list as a Markdown table
+pub fn sessions_markdown(sessions: &[Session]) -> String {
+    let mut md = String::from("| Title | Session ID | State | Created | PR |\n");
+    md.push_str("| --- | --- | --- | --- | --- |\n");
+    for session in sessions {
+        let pr = pull_request_url(session)
+            .map(|url| format!("[PR]({})", url))
+            .unwrap_or_default();
+        let _ = writeln!(
+            md,
+            "| {} | `{}` | {} | {} | {} |",
+            table_cell(session.title.as_deref().unwrap_or("Untitled")),
+            session.id,
+            state_name(session),
+            session.create_time.as_deref().unwrap_or("-"),
+            pr
+        );
+    }
+    md
+}
+
+/// A session report: details, prompt and (if given) its activities
+pub fn session_markdown(session: &Session, activities: &[&Activity]) -> String {
+    let mut md = format!(
+        "# {}\n\n",
+        inline_text(session.title.as_deref().unwrap_or("Untitled session"))
+    );
+
+    let _ = writeln!(md, "- **Session:** `{}`", session.id);
+    let _ = writeln!(md, "- **State:** {}", state_name(session));
+    let _ = writeln!(md, "- **Source:** `{}`", session.source_context.source);
+    if let Some(context) = &session.source_context.github_repo_context {
+        let _ = writeln!(md, "- **Branch:** `{}`", context.starting_branch);
+    }
+    if let Some(created) = &session.create_time {
+        let _ = writeln!(md, "- **Created:** {}", created);
+    }
+    if let Some(updated) = &session.update_time {
+        let _ = writeln!(md, "- **Updated:** {}", updated);
+    }
+    if let Some(url) = &session.url {
+        let _ = writeln!(md, "- **Jules:** {}", url);
+    }
+    if let Some(url) = pull_request_url(session) {
+        let _ = writeln!(md, "- **Pull request:** {}", url);
+    }
+
+    md.push_str("\n## Prompt\n\n");
+    md.push_str(&blockquote(&session.prompt));
+
+    if !activities.is_empty() {
+        md.push('\n');
+        md.push_str(&activities_markdown(activities));
+    }
+    md
+}
+
+/// Activities as Markdown sections
+pub fn activities_markdown(activities: &[&Activity]) -> String {
+    let mut md = format!("## Activities ({})\n", activities.len());
+    for (i, activity) in activities.iter().enumerate() {
+        md.push('\n');
+        md.push_str(&activity_markdown(i + 1, activity));
+    }
+    md
+}
+
+/// One activity as a `###` section
+fn activity_markdown(number: usize, activity: &Activity) -> String {
+    let mut md = format!("### {}. {}\n\n", number, activity.activity_type());
+    let _ = writeln!(
+        md,
+        "_{} · {} · `{}`_\n",
+        activity.create_time, activity.originator, activity.id
+    );
+
+    if let Some(plan) = activity.plan_generated.as_ref().map(|p| &p.plan) {
+        for (i, step) in plan.steps.iter().enumerate() {
+            let title = step.title.as_deref().unwrap_or("Untitled step");
+            let _ = writeln!(md, "{}. **{}**", i + 1, inline_text(title));
+            if let Some(description) = step.description.as_deref().filter(|d| !d.is_empty()) {
+                let _ = writeln!(md, "   {}", inline_text(description));
+            }
+        }
+        md.push('\n');
+    } else if let Some(progress) = &activity.progress_updated {
+        if let Some(title) = &progress.title {
+            let _ = writeln!(md, "**{}**\n", inline_text(title));
+        }
+        if let Some(description) = progress.description.as_deref().filter(|d| !d.is_empty()) {
+            let _ = writeln!(md, "{}\n", description.trim_end());
+        }
+    } else if let Some(content) = activity.content() {
+        let _ = writeln!(md, "{}\n", content.trim_end());
+    }
+
+    for artifact in &activity.artifacts {
+        md.push_str(&artifact_markdown(artifact));
+    }
+    md
+}
+
+fn artifact_markdown(artifact: &Artifact) -> String {
+    let mut md = String::new();
+    if let Some(bash) = &artifact.bash_output {
+        let exit = bash
+            .exit_code
+            .map(|code| format!(" (exit code {})", code))
+            .unwrap_or_default();
+        let _ = writeln!(md, "**Command**{}:\n", exit);
+        let mut console = format!("$ {}", bash.command.as_deref().unwrap_or("").trim());
+        if let Some(output) = bash.output.as_deref().filter(|o| !o.trim().is_empty()) {
+            console.push('\n');
+            console.push_str(output.trim_end());
+        }
+        md.push_str(&fenced("console", &console));
+    }
+
+    if let Some(git_patch) = artifact
+        .change_set
+        .as_ref()
+        .and_then(|change_set| change_set.git_patch.as_ref())
+    {
+        if let Some(message) = &git_patch.suggested_commit_message {
+            let _ = writeln!(md, "**Suggested commit:** {}\n", inline_text(message));
+        }
+        match git_patch.unidiff_patch.as_deref().filter(|p| !p.is_empty()) {
+            Some(patch) => md.push_str(&fenced("diff", patch.trim_end())),
+            None => md.push_str("_Change set without a diff_\n\n"),
+        }
+    }
+
+    if let Some(media) = &artifact.media {
+        let _ = writeln!(
+            md,
+            "_Media: {}_\n",
+            media.mime_type.as_deref().unwrap_or("unknown type")
+        );
+    }
+    md
+}
+
+/// A fenced code block, with a fence longer than any backtick run inside
+pub fn fenced(language: &str, content: &str) -> String {
+    let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{}{}\n{}\n{}\n\n", fence, language, content, fence)
+}
+
+/// Text as a blockquote, line by line
+fn blockquote(text: &str) -> String {
+    let mut md = String::new();
+    for line in text.trim_end().lines() {
+        if line.is_empty() {
+            md.push_str(">\n");
+        } else {
+            let _ = writeln!(md, "> {}", line);
+        }
+    }
+    md
+}
+
+/// Text on a single line (headings, list items)
+pub(crate) fn inline_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Text in a table cell: one line, pipes escaped
+fn table_cell(text: &str) -> String {
+    inline_text(text).replace('|', "\\|")
+}
+
+pub(crate) fn state_name(session: &Session) -> &'static str {
+    session
+        .state
+        .map(|state| state.display_name())
+        .unwrap_or("Unknown")
+}
+
+pub(crate) fn pull_request_url(session: &Session) -> Option<&str> {
+    session
+        .outputs
+        .iter()
+        .find_map(|output| output.pull_request.as_ref()?.url.as_deref())
+}
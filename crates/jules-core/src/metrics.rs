@@ -0,0 +1,191 @@
+//! Prometheus-style aggregation of Jules API request metrics.
+//!
+//! [`PrometheusMetrics`] implements the SDK's [`MetricsSink`] and renders the
+//! collected counters and latency histogram in the Prometheus text exposition
+//! format. The long-running modes (daemon, monitor, MCP server) write them to
+//! the `metrics_file` setting with [`export_metrics`], e.g. for
+//! node_exporter's textfile collector.
+
+use jules_rs::metrics::{MetricsSink, RequestMetrics};
+use jules_rs::JulesClient;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::atomic_file::write_atomic;
+use crate::config::Config;
+
+/// Latency histogram bucket upper bounds, in seconds
+const LATENCY_BUCKETS: [f64; 9] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+#[derive(Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+#[derive(Default)]
+struct MetricsState {
+    /// (method, route, status) -> count
+    requests: BTreeMap<(String, String, String), u64>,
+    /// (route, error class) -> count
+    errors: BTreeMap<(String, String), u64>,
+    /// route -> latency histogram
+    latency: BTreeMap<String, Histogram>,
+}
+
+/// In-memory metrics sink with Prometheus text rendering
+#[derive(Default)]
+pub struct PrometheusMetrics {
+    state: Mutex<MetricsState>,
+}
+
+impl PrometheusMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total number of request attempts recorded
+    pub fn total_requests(&self) -> u64 {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.requests.values().sum()
+    }
+
+    /// Total number of failed attempts recorded
+    pub fn total_errors(&self) -> u64 {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.errors.values().sum()
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+
+        out.push_str("# HELP jules_api_requests_total Jules API request attempts.\n");
+        out.push_str("# TYPE jules_api_requests_total counter\n");
+        for ((method, route, status), count) in &state.requests {
+            let _ = writeln!(
+                out,
+                "jules_api_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                method, route, status, count
+            );
+        }
+
+        out.push_str("# HELP jules_api_errors_total Failed Jules API request attempts.\n");
+        out.push_str("# TYPE jules_api_errors_total counter\n");
+        for ((route, class), count) in &state.errors {
+            let _ = writeln!(
+                out,
+                "jules_api_errors_total{{route=\"{}\",class=\"{}\"}} {}",
+                route, class, count
+            );
+        }
+
+        out.push_str("# HELP jules_api_request_duration_seconds Jules API request latency.\n");
+        out.push_str("# TYPE jules_api_request_duration_seconds histogram\n");
+        for (route, histogram) in &state.latency {
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets.iter()) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "jules_api_request_duration_seconds_bucket{{route=\"{}\",le=\"{}\"}} {}",
+                    route, bound, cumulative
+                );
+            }
+            let _ = writeln!(
+                out,
+                "jules_api_request_duration_seconds_bucket{{route=\"{}\",le=\"+Inf\"}} {}",
+                route, histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "jules_api_request_duration_seconds_sum{{route=\"{}\"}} {}",
+                route, histogram.sum
+            );
+            let _ = writeln!(
+                out,
+                "jules_api_request_duration_seconds_count{{route=\"{}\"}} {}",
+                route, histogram.count
+            );
+        }
+
+        out
+    }
+}
+
+impl MetricsSink for PrometheusMetrics {
+    fn record(&self, metrics: &RequestMetrics<'_>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let status = metrics
+            .status
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "none".to_string());
+        *state
+            .requests
+            .entry((metrics.method.to_string(), metrics.route.clone(), status))
+            .or_default() += 1;
+
+        if let Some(class) = metrics.error {
+            *state
+                .errors
+                .entry((metrics.route.clone(), class.as_str().to_string()))
+                .or_default() += 1;
+        }
+
+        let seconds = metrics.latency.as_secs_f64();
+        let histogram = state.latency.entry(metrics.route.clone()).or_default();
+        if let Some(index) = LATENCY_BUCKETS.iter().position(|bound| seconds <= *bound) {
+            histogram.buckets[index] += 1;
+        }
+        histogram.sum += seconds;
+        histogram.count += 1;
+    }
+}
+
+/// [`PrometheusMetrics`] rewritten to a file after every request
+pub struct MetricsFile {
+    metrics: PrometheusMetrics,
+    path: PathBuf,
+    /// Whether a failed write was reported, so it is reported once
+    warned: AtomicBool,
+}
+
+impl MetricsFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            metrics: PrometheusMetrics::new(),
+            path: path.into(),
+            warned: AtomicBool::new(false),
+        }
+    }
+}
+
+impl MetricsSink for MetricsFile {
+    fn record(&self, metrics: &RequestMetrics<'_>) {
+        self.metrics.record(metrics);
+        if let Err(e) = write_atomic(&self.path, self.metrics.render()) {
+            if !self.warned.swap(true, Ordering::Relaxed) {
+                eprintln!(
+                    "Warning: Failed to write metrics to {}: {:#}",
+                    self.path.display(),
+                    e
+                );
+            }
+        }
+    }
+}
+
+/// `client`, writing the metrics of its requests to the `metrics_file`
+/// setting if there is one
+pub fn export_metrics(client: JulesClient, config: &Config) -> JulesClient {
+    match &config.metrics_file {
+        Some(path) => client.with_metrics_sink(Arc::new(MetricsFile::new(path))),
+        None => client,
+    }
+}
@@ -0,0 +1,88 @@
+//! NDJSON (JSON Lines) output for list and streaming commands.
+//!
+//! One compact JSON object per line, flushed as it is written, so output can
+//! be piped into `jq`, `grep` or a log shipper while a `watch` is still
+//! running. Records carry the same fields as `--format json`; `watch` and
+//! `monitor` wrap them in [`WatchEvent`]s.
+
+use anyhow::Result;
+use chrono::Utc;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::{Session, State};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// An event emitted by `watch` and `monitor`
+///
+/// Serialized with an `event` tag (`session`, `activity`, `state_change`)
+/// and the time it was observed.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WatchEvent<'a> {
+    /// A session seen for the first time
+    Session {
+        timestamp: String,
+        session: &'a Session,
+    },
+    /// A new activity of a watched session
+    Activity {
+        timestamp: String,
+        session: &'a str,
+        activity: &'a Activity,
+    },
+    /// A session whose state differs from the previous poll
+    StateChange {
+        timestamp: String,
+        from: Option<State>,
+        to: Option<State>,
+        session: &'a Session,
+    },
+}
+
+impl<'a> WatchEvent<'a> {
+    pub fn session(session: &'a Session) -> Self {
+        Self::Session {
+            timestamp: now(),
+            session,
+        }
+    }
+
+    pub fn activity(session_id: &'a str, activity: &'a Activity) -> Self {
+        Self::Activity {
+            timestamp: now(),
+            session: session_id,
+            activity,
+        }
+    }
+
+    pub fn state_change(from: Option<State>, session: &'a Session) -> Self {
+        Self::StateChange {
+            timestamp: now(),
+            from,
+            to: session.state,
+            session,
+        }
+    }
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339()
+}
+
+/// Write records as NDJSON, flushing after each line
+pub fn write_ndjson<W: Write, T: Serialize>(
+    out: &mut W,
+    records: impl IntoIterator<Item = T>,
+) -> Result<()> {
+    for record in records {
+        serde_json::to_writer(&mut *out, &record)?;
+        out.write_all(b"\n")?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// Print records as NDJSON
+pub fn print_ndjson<T: Serialize>(records: impl IntoIterator<Item = T>) -> Result<()> {
+    write_ndjson(&mut io::stdout().lock(), records)
+}
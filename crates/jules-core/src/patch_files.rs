@@ -0,0 +1,237 @@
+//! Git patches extracted from cached activities.
+//!
+//! Change set artifacts carry their diff as a `unidiffPatch` string inside the
+//! activity JSON. When activities are cached, each patch is also written to a
+//! standalone file under `patches/{session_id}/` in the activity cache
+//! directory, so it can be applied with `git apply` directly.
+//!
+//! Files are named `{NNN}-{activity_id}.patch`, numbered oldest first, so a
+//! shell glob applies them in the order they were produced.
+
+use anyhow::{Context, Result};
+use jules_rs::types::activity::Activity;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::activity_cache::{cache_key, get_cache_dir};
+use crate::atomic_file::write_atomic;
+
+/// Extension of extracted patch files
+pub const PATCH_EXTENSION: &str = "patch";
+
+/// A git patch from a change set artifact
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchFile {
+    /// File name (`{NNN}-{activity_id}.patch`)
+    pub file_name: String,
+    pub activity_id: String,
+    pub base_commit_id: Option<String>,
+    pub suggested_commit_message: Option<String>,
+    /// The unified diff
+    pub patch: String,
+}
+
+/// Get the directory holding the extracted patches of all sessions
+pub fn get_patches_root() -> Result<PathBuf> {
+    Ok(get_cache_dir()?.join("patches"))
+}
+
+/// Get the directory holding the extracted patches of a session
+pub fn get_patch_dir(session_id: &str) -> Result<PathBuf> {
+    Ok(get_patches_root()?.join(cache_key(session_id)?))
+}
+
+/// Patches of the given activities (newest first, as cached), oldest first
+///
+/// Change sets without a diff are skipped.
+pub fn collect_patches(activities: &[Activity]) -> Vec<PatchFile> {
+    let patches = activities
+        .iter()
+        .rev()
+        .flat_map(|activity| {
+            activity.artifacts.iter().filter_map(move |artifact| {
+                let git_patch = artifact.change_set.as_ref()?.git_patch.as_ref()?;
+                let patch = git_patch.unidiff_patch.as_ref().filter(|p| !p.is_empty())?;
+                Some((activity, git_patch, patch))
+            })
+        })
+        .enumerate()
+        .map(|(i, (activity, git_patch, patch))| PatchFile {
+            file_name: format!("{:03}-{}.{}", i + 1, activity.id, PATCH_EXTENSION),
+            activity_id: activity.id.clone(),
+            base_commit_id: git_patch.base_commit_id.clone(),
+            suggested_commit_message: git_patch.suggested_commit_message.clone(),
+            patch: with_trailing_newline(patch),
+        });
+    patches.collect()
+}
+
+/// Lines added and removed in one file of a patch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStat {
+    pub path: String,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+/// Per-file line counts of unified diffs, like `git diff --stat`
+///
+/// Files changed by several patches are counted once, in the order they
+/// first appear. Hunks are measured by their `@@` headers, so removed lines
+/// starting with `--` are not mistaken for file headers.
+pub fn diff_stat<'a>(patches: impl IntoIterator<Item = &'a str>) -> Vec<FileStat> {
+    let mut stats: Vec<FileStat> = Vec::new();
+    for patch in patches {
+        let mut current: Option<usize> = None;
+        let mut old_path: Option<String> = None;
+        let (mut old_left, mut new_left) = (0usize, 0usize);
+
+        for line in patch.lines() {
+            if old_left > 0 || new_left > 0 {
+                let Some(stat) = current.map(|i| &mut stats[i]) else {
+                    break;
+                };
+                match line.chars().next() {
+                    Some('+') => {
+                        stat.insertions += 1;
+                        new_left = new_left.saturating_sub(1);
+                    }
+                    Some('-') => {
+                        stat.deletions += 1;
+                        old_left = old_left.saturating_sub(1);
+                    }
+                    // "\ No newline at end of file"
+                    Some('\\') => {}
+                    _ => {
+                        old_left = old_left.saturating_sub(1);
+                        new_left = new_left.saturating_sub(1);
+                    }
+                }
+            } else if let Some(path) = line.strip_prefix("--- ") {
+                old_path = Some(header_path(path));
+            } else if let Some(path) = line.strip_prefix("+++ ") {
+                // Deleted files have no new path
+                let path = Some(header_path(path))
+                    .filter(|path| path != "/dev/null")
+                    .or(old_path.take())
+                    .unwrap_or_default();
+                current = Some(match stats.iter().position(|stat| stat.path == path) {
+                    Some(i) => i,
+                    None => {
+                        stats.push(FileStat {
+                            path,
+                            insertions: 0,
+                            deletions: 0,
+                        });
+                        stats.len() - 1
+                    }
+                });
+            } else if line.starts_with("@@") {
+                if let Some((old, new)) = hunk_lengths(line) {
+                    (old_left, new_left) = (old, new);
+                }
+            }
+        }
+    }
+    stats
+}
+
+/// Path of a `---`/`+++` header without its `a/`/`b/` prefix or timestamp
+fn header_path(header: &str) -> String {
+    let path = header.split('\t').next().unwrap_or(header).trim_end();
+    path.strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// Old and new line counts of a hunk header (`@@ -1,5 +1,6 @@`)
+fn hunk_lengths(header: &str) -> Option<(usize, usize)> {
+    let mut ranges = header.trim_start_matches('@').split_whitespace();
+    let length = |range: &str| match range.split_once(',') {
+        Some((_, length)) => length.parse().ok(),
+        None => Some(1),
+    };
+    let old = length(ranges.next()?.strip_prefix('-')?)?;
+    let new = length(ranges.next()?.strip_prefix('+')?)?;
+    Some((old, new))
+}
+
+/// `git apply` rejects a patch whose last line is unterminated
+fn with_trailing_newline(patch: &str) -> String {
+    if patch.ends_with('\n') {
+        patch.to_string()
+    } else {
+        format!("{}\n", patch)
+    }
+}
+
+/// Write the patches of `activities` to `dir`
+///
+/// Unchanged files are left alone and `.patch` files that no longer
+/// correspond to an activity are removed. Returns the paths of all patch
+/// files, oldest first.
+pub fn write_patch_files(dir: &Path, activities: &[Activity]) -> Result<Vec<PathBuf>> {
+    let patches = collect_patches(activities);
+    if patches.is_empty() && !dir.exists() {
+        return Ok(Vec::new());
+    }
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create patch directory {}", dir.display()))?;
+
+    let mut paths = Vec::with_capacity(patches.len());
+    for patch in &patches {
+        let path = dir.join(&patch.file_name);
+        let unchanged = fs::read_to_string(&path).is_ok_and(|existing| existing == patch.patch);
+        if !unchanged {
+            write_atomic(&path, &patch.patch)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        paths.push(path);
+    }
+
+    for entry in fs::read_dir(dir).context("Failed to read patch directory")? {
+        let path = entry?.path();
+        let is_patch = path.extension().is_some_and(|ext| ext == PATCH_EXTENSION);
+        if is_patch && !paths.contains(&path) {
+            fs::remove_file(&path).context("Failed to remove stale patch file")?;
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Extract a session's patches into its patch directory
+///
+/// Returns the number of patch files.
+pub fn save_session_patches(session_id: &str, activities: &[Activity]) -> Result<usize> {
+    Ok(write_patch_files(&get_patch_dir(session_id)?, activities)?.len())
+}
+
+/// Remove the extracted patches of a session
+pub fn remove_session_patches(session_id: &str) -> Result<()> {
+    let dir = get_patch_dir(session_id)?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir).context("Failed to remove patch directory")?;
+    }
+    Ok(())
+}
+
+/// Remove the patch directories of sessions that are no longer cached
+pub fn prune_session_patches(cached_sessions: &[String]) -> Result<()> {
+    let root = get_patches_root()?;
+    if !root.exists() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(&root).context("Failed to read patch directory")? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let cached = name
+            .to_str()
+            .is_some_and(|name| cached_sessions.iter().any(|id| id == name));
+        if !cached && entry.path().is_dir() {
+            fs::remove_dir_all(entry.path()).context("Failed to remove patch directory")?;
+        }
+    }
+    Ok(())
+}
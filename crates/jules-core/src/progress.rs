@@ -0,0 +1,73 @@
+//! Spinners for long-running API operations.
+//!
+//! Spinners are `indicatif` bars drawn on stderr and cleared when they are
+//! finished or dropped, so they never end up in piped output. They are hidden
+//! when stderr is not a terminal (pipes, MCP stdio, CI logs) and with
+//! `--quiet`. Spinners share one `MultiProgress`, so one started while another
+//! is running (e.g. per-session fetches during a cache warm) gets its own line.
+
+use crate::display::is_quiet;
+use indicatif::{MultiProgress, ProgressBar};
+use std::io::{self, IsTerminal};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const TICK: Duration = Duration::from_millis(80);
+
+/// Whether spinners are shown
+pub fn progress_enabled() -> bool {
+    !is_quiet() && io::stderr().is_terminal()
+}
+
+fn multi() -> &'static MultiProgress {
+    static MULTI: OnceLock<MultiProgress> = OnceLock::new();
+    MULTI.get_or_init(MultiProgress::new)
+}
+
+/// A spinner with a message; hidden when progress is disabled
+pub struct Spinner {
+    bar: ProgressBar,
+}
+
+impl Spinner {
+    /// Start a spinner showing `message`
+    pub fn start(message: impl Into<String>) -> Self {
+        let bar = if progress_enabled() {
+            let bar = multi().add(ProgressBar::new_spinner());
+            bar.enable_steady_tick(TICK);
+            bar
+        } else {
+            ProgressBar::hidden()
+        };
+        bar.set_message(message.into());
+        Self { bar }
+    }
+
+    /// Replace the message (e.g. with a page or item count)
+    pub fn set_message(&self, message: impl Into<String>) {
+        self.bar.set_message(message.into());
+    }
+
+    /// Run `f` (which prints) with the spinner lines cleared
+    pub fn suspend<T>(&self, f: impl FnOnce() -> T) -> T {
+        if self.bar.is_hidden() {
+            f()
+        } else {
+            multi().suspend(f)
+        }
+    }
+
+    /// Stop the spinner and clear its line
+    pub fn finish(self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        if !self.bar.is_finished() {
+            self.bar.finish_and_clear();
+        }
+        multi().remove(&self.bar);
+    }
+}
@@ -0,0 +1,60 @@
+//! Terminal prompts: confirmations for destructive commands, and questions
+//! for interactive setup.
+
+use anyhow::{Context, Result};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Ask `question` on the terminal and return whether the answer was yes
+///
+/// `assume_yes` (`--yes`) skips the prompt. Without a terminal to ask on,
+/// this fails instead of guessing, so scripts have to pass `--yes`.
+pub fn confirm(question: &str, assume_yes: bool) -> Result<bool> {
+    if assume_yes {
+        return Ok(true);
+    }
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("{} Pass --yes to confirm without a terminal", question);
+    }
+    Ok(is_yes(&read_answer(&format!("{} [y/N]", question))?))
+}
+
+/// Ask `question` on the terminal and return the trimmed answer
+pub fn ask(question: &str) -> Result<String> {
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("Cannot ask '{}' without a terminal", question);
+    }
+    Ok(read_answer(question)?.trim().to_string())
+}
+
+/// Ask a yes/no `question`; an empty answer means `default`
+pub fn ask_yes_no(question: &str, default: bool) -> Result<bool> {
+    let choices = if default { "[Y/n]" } else { "[y/N]" };
+    let answer = ask(&format!("{} {}", question, choices))?;
+    Ok(parse_yes_no(&answer, default))
+}
+
+fn read_answer(question: &str) -> Result<String> {
+    let mut stderr = io::stderr();
+    write!(stderr, "{} ", question)?;
+    stderr.flush()?;
+    let mut answer = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read answer")?;
+    Ok(answer)
+}
+
+/// Whether a prompt answer means yes ("y" or "yes", any case)
+pub fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// A yes/no answer: yes, no ("n" or "no"), or `default` for anything else
+pub fn parse_yes_no(answer: &str, default: bool) -> bool {
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    }
+}
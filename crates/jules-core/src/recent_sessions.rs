@@ -0,0 +1,128 @@
+//! Recently used sessions (`gules recent`).
+//!
+//! Sessions that were created, viewed or watched are remembered most recent
+//! first, so they can be referred to as `@1` (the most recent), `@2`, ...
+//! instead of by their long numeric IDs.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use jules_rs::SessionName;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::atomic_file::write_atomic;
+
+/// How many sessions are remembered
+pub const MAX_RECENT_SESSIONS: usize = 20;
+
+/// A recently used session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentSession {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub used_at: DateTime<Utc>,
+}
+
+/// Recently used sessions, most recent first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecentSessions {
+    #[serde(default)]
+    pub sessions: Vec<RecentSession>,
+}
+
+/// Get the recent sessions file path
+pub fn get_recent_path() -> Result<PathBuf> {
+    let data_dir =
+        dirs::data_dir().ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+    Ok(data_dir.join("gules").join("recent.json"))
+}
+
+/// Parse a recent session reference: `@N` is the Nth most recent session
+/// (1-based); anything else is not a reference
+pub fn parse_recent_ref(value: &str) -> Option<Result<usize>> {
+    let position = value.strip_prefix('@')?;
+    Some(match position.parse::<usize>() {
+        Ok(position) if position > 0 => Ok(position),
+        _ => Err(anyhow::anyhow!(
+            "Invalid session reference '{}': expected @1, @2, ...",
+            value
+        )),
+    })
+}
+
+impl RecentSessions {
+    /// Load the recent sessions from the default location (empty if missing)
+    pub fn load() -> Result<Self> {
+        Self::load_from(&get_recent_path()?)
+    }
+
+    /// Load the recent sessions from `path` (empty if missing)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).context("Failed to read recent sessions")?;
+        serde_json::from_str(&contents).context("Failed to parse recent sessions")
+    }
+
+    /// Save the recent sessions to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_recent_path()?)
+    }
+
+    /// Save the recent sessions to `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create data directory")?;
+        }
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize recent sessions")?;
+        write_atomic(path, contents).context("Failed to write recent sessions")?;
+        Ok(())
+    }
+
+    /// Move a session, given by ID or resource name, to the front, keeping
+    /// its known title when `title` is `None`
+    pub fn touch(&mut self, session_id: &str, title: Option<&str>) -> Result<()> {
+        let id = SessionName::from_id_or_name(session_id)?.id().to_string();
+        let previous = self
+            .sessions
+            .iter()
+            .position(|session| session.id == id)
+            .map(|index| self.sessions.remove(index));
+        let title = title
+            .map(str::to_string)
+            .or_else(|| previous.and_then(|session| session.title));
+        self.sessions.insert(
+            0,
+            RecentSession {
+                id,
+                title,
+                used_at: Utc::now(),
+            },
+        );
+        self.sessions.truncate(MAX_RECENT_SESSIONS);
+        Ok(())
+    }
+
+    /// Resolve `@N` to the ID of the Nth most recent session; other values
+    /// are returned unchanged
+    pub fn resolve(&self, value: &str) -> Result<String> {
+        let Some(position) = parse_recent_ref(value) else {
+            return Ok(value.to_string());
+        };
+        let position = position?;
+        self.sessions
+            .get(position - 1)
+            .map(|session| session.id.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No recent session {}: only {} remembered (see 'gules recent')",
+                    value,
+                    self.sessions.len()
+                )
+            })
+    }
+}
@@ -0,0 +1,384 @@
+//! Full-text search over cached activities.
+//!
+//! Builds an SQLite FTS5 index (trigram tokenizer, so any substring of three
+//! or more characters matches, including file paths and error messages) over
+//! activity messages, bash commands and outputs, and git patches. The index
+//! is refreshed lazily from the activity cache: a session is re-indexed only
+//! when its cache was updated after it was last indexed.
+//!
+//! Regular expressions and searches restricted to one kind of content
+//! (`--type bash`) can't use the index; [`grep_activities`] scans the
+//! cached activities instead.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use jules_rs::types::activity::Activity;
+use regex::{Regex, RegexBuilder};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::activity_cache::{cache_key, get_cache_dir};
+
+/// Index file name inside the cache directory
+const INDEX_FILE_NAME: &str = "search.db";
+
+/// FTS5 trigram queries need at least this many characters
+const MIN_TRIGRAM_LEN: usize = 3;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS indexed_sessions (
+    session_id   TEXT PRIMARY KEY,
+    last_updated TEXT NOT NULL
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS activity_text USING fts5(
+    session_id UNINDEXED,
+    activity_id UNINDEXED,
+    create_time UNINDEXED,
+    activity_type UNINDEXED,
+    content,
+    tokenize = 'trigram'
+);
+";
+
+/// A search result
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub session_id: String,
+    pub activity_id: String,
+    pub create_time: String,
+    pub activity_type: String,
+    /// Lines of the indexed text that contain the pattern
+    pub matches: Vec<String>,
+}
+
+/// Kind of activity content to search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentKind {
+    /// Bash commands and their output
+    Bash,
+    /// Agent and user messages, progress updates and failure reasons
+    Message,
+    /// Git patches and their suggested commit messages
+    Diff,
+}
+
+impl ContentKind {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "bash" => Ok(Self::Bash),
+            "message" | "messages" => Ok(Self::Message),
+            "diff" | "patch" => Ok(Self::Diff),
+            _ => anyhow::bail!(
+                "Unknown content type: {}. Valid options: bash, message, diff",
+                s
+            ),
+        }
+    }
+}
+
+/// Full-text index over cached activities
+pub struct SearchIndex {
+    conn: Connection,
+}
+
+impl SearchIndex {
+    /// Open (or create) the index in the default cache directory
+    pub fn open_default() -> Result<Self> {
+        let cache_dir = get_cache_dir()?;
+        fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
+        Self::open(cache_dir.join(INDEX_FILE_NAME))
+    }
+
+    /// Open (or create) the index at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open search index {}", path.display()))?;
+        Self::init(conn)
+    }
+
+    /// Index kept only in memory (used when the cache is encrypted, so no
+    /// plaintext copy is written to disk)
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)
+            .context("Failed to initialize search index")?;
+        Ok(Self { conn })
+    }
+
+    /// Whether a session must be (re-)indexed given its cache timestamp
+    pub fn needs_update(&self, session_id: &str, last_updated: DateTime<Utc>) -> Result<bool> {
+        let key = cache_key(session_id)?;
+        let indexed: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT last_updated FROM indexed_sessions WHERE session_id = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(indexed.as_deref() != Some(last_updated.to_rfc3339().as_str()))
+    }
+
+    /// Replace the indexed activities of a session
+    pub fn index_session(
+        &mut self,
+        session_id: &str,
+        last_updated: DateTime<Utc>,
+        activities: &[Activity],
+    ) -> Result<()> {
+        let key = cache_key(session_id)?;
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            "DELETE FROM activity_text WHERE session_id = ?1",
+            params![key],
+        )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO activity_text
+                     (session_id, activity_id, create_time, activity_type, content)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for activity in activities {
+                let text = searchable_text(activity);
+                if text.is_empty() {
+                    continue;
+                }
+                stmt.execute(params![
+                    key,
+                    activity.id,
+                    activity.create_time,
+                    activity.activity_type(),
+                    text
+                ])?;
+            }
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO indexed_sessions (session_id, last_updated) VALUES (?1, ?2)",
+            params![key, last_updated.to_rfc3339()],
+        )?;
+
+        tx.commit()
+            .context(format!("Failed to index session {}", key))
+    }
+
+    /// Drop sessions that are no longer cached
+    pub fn retain_sessions(&self, session_ids: &[String]) -> Result<()> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT session_id FROM indexed_sessions")?;
+        let indexed = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        for session_id in indexed.iter().filter(|id| !session_ids.contains(id)) {
+            self.conn.execute(
+                "DELETE FROM activity_text WHERE session_id = ?1",
+                params![session_id],
+            )?;
+            self.conn.execute(
+                "DELETE FROM indexed_sessions WHERE session_id = ?1",
+                params![session_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Case-insensitive substring search, newest first
+    pub fn search(
+        &self,
+        pattern: &str,
+        session_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>> {
+        if pattern.is_empty() {
+            anyhow::bail!("Search pattern must not be empty");
+        }
+
+        // Trigram MATCH needs 3+ characters; shorter patterns fall back to LIKE
+        let (condition, needle) = if pattern.chars().count() >= MIN_TRIGRAM_LEN {
+            (
+                "activity_text MATCH ?1",
+                format!("\"{}\"", pattern.replace('"', "\"\"")),
+            )
+        } else {
+            (
+                "content LIKE ?1 ESCAPE '\\'",
+                format!("%{}%", escape_like(pattern)),
+            )
+        };
+
+        let session_key = session_id.map(cache_key).transpose()?;
+        let sql = format!(
+            "SELECT session_id, activity_id, create_time, activity_type, content
+             FROM activity_text
+             WHERE {} AND (?2 IS NULL OR session_id = ?2)
+             ORDER BY create_time DESC
+             LIMIT ?3",
+            condition
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(params![needle, session_key, limit as i64], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        })?;
+
+        let mut hits = Vec::new();
+        for row in rows {
+            let (session_id, activity_id, create_time, activity_type, content) = row?;
+            hits.push(SearchHit {
+                session_id,
+                activity_id,
+                create_time,
+                activity_type,
+                matches: matching_lines(&content, pattern),
+            });
+        }
+        Ok(hits)
+    }
+}
+
+/// Text of an activity that is worth searching
+pub fn searchable_text(activity: &Activity) -> String {
+    searchable_parts(activity, None).join("\n")
+}
+
+/// Searchable texts of an activity, only those of `kind` if given
+pub fn searchable_parts(activity: &Activity, kind: Option<ContentKind>) -> Vec<&str> {
+    let wanted = |k: ContentKind| kind.is_none_or(|kind| kind == k);
+    let mut parts: Vec<&str> = Vec::new();
+
+    if wanted(ContentKind::Message) {
+        parts.extend(message_parts(activity));
+    }
+    for artifact in &activity.artifacts {
+        if let Some(bash) = artifact
+            .bash_output
+            .as_ref()
+            .filter(|_| wanted(ContentKind::Bash))
+        {
+            parts.extend(bash.command.as_deref());
+            parts.extend(bash.output.as_deref());
+        }
+        if let Some(patch) = artifact
+            .change_set
+            .as_ref()
+            .and_then(|c| c.git_patch.as_ref())
+            .filter(|_| wanted(ContentKind::Diff))
+        {
+            parts.extend(patch.suggested_commit_message.as_deref());
+            parts.extend(patch.unidiff_patch.as_deref());
+        }
+    }
+    parts
+}
+
+fn message_parts(activity: &Activity) -> Vec<&str> {
+    let mut parts: Vec<&str> = Vec::new();
+
+    if let Some(description) = &activity.description {
+        parts.push(description);
+    }
+    if let Some(msg) = activity
+        .agent_messaged
+        .as_ref()
+        .and_then(|m| m.agent_message.as_deref())
+    {
+        parts.push(msg);
+    }
+    if let Some(msg) = activity
+        .user_messaged
+        .as_ref()
+        .and_then(|m| m.user_message.as_deref())
+    {
+        parts.push(msg);
+    }
+    if let Some(progress) = &activity.progress_updated {
+        parts.extend(progress.title.as_deref());
+        parts.extend(progress.description.as_deref());
+    }
+    if let Some(reason) = activity
+        .session_failed
+        .as_ref()
+        .and_then(|f| f.reason.as_deref())
+    {
+        parts.push(reason);
+    }
+    parts
+}
+
+/// Case-insensitive regular expression for [`grep_activities`], matching
+/// `pattern` as plain text if `literal`
+pub fn grep_regex(pattern: &str, literal: bool) -> Result<Regex> {
+    if pattern.is_empty() {
+        anyhow::bail!("Search pattern must not be empty");
+    }
+    let pattern = if literal {
+        regex::escape(pattern)
+    } else {
+        pattern.to_string()
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(true)
+        .build()
+        .with_context(|| format!("Invalid regular expression: {}", pattern))
+}
+
+/// Activities of a session whose content (only of `kind` if given) has
+/// lines matching `regex`, in the order given
+pub fn grep_activities(
+    session_id: &str,
+    activities: &[Activity],
+    regex: &Regex,
+    kind: Option<ContentKind>,
+) -> Vec<SearchHit> {
+    activities
+        .iter()
+        .filter_map(|activity| {
+            let matches: Vec<String> = searchable_parts(activity, kind)
+                .into_iter()
+                .flat_map(str::lines)
+                .filter(|line| regex.is_match(line))
+                .map(|line| line.trim().to_string())
+                .collect();
+            (!matches.is_empty()).then(|| SearchHit {
+                session_id: session_id.to_string(),
+                activity_id: activity.id.clone(),
+                create_time: activity.create_time.clone(),
+                activity_type: activity.activity_type(),
+                matches,
+            })
+        })
+        .collect()
+}
+
+/// Lines containing `pattern` (case-insensitive), trimmed
+fn matching_lines(content: &str, pattern: &str) -> Vec<String> {
+    let needle = pattern.to_lowercase();
+    content
+        .lines()
+        .filter(|line| line.to_lowercase().contains(&needle))
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
+fn escape_like(pattern: &str) -> String {
+    pattern
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
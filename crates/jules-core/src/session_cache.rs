@@ -0,0 +1,185 @@
+//! Local cache of session metadata.
+//!
+//! Stores the last known `Session` objects (state, title, outputs) together
+//! with when each one was refreshed, so listings can be served without an API
+//! round-trip (`gules sessions --cached`) and long-running commands can fall
+//! back to the last known state when the API is unreachable.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use jules_rs::types::session::Session;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::activity_cache::cache_key;
+use crate::atomic_file::write_atomic;
+use crate::cache_lock::with_lock;
+
+/// A session as last seen from the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedSession {
+    pub session: Session,
+    /// When this session was last fetched
+    pub refreshed_at: DateTime<Utc>,
+}
+
+/// All cached sessions, keyed by bare session ID
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionStore {
+    #[serde(default)]
+    pub sessions: BTreeMap<String, CachedSession>,
+    /// When a full session listing was last stored
+    #[serde(default)]
+    pub last_list_refresh: Option<DateTime<Utc>>,
+}
+
+/// Get the session metadata cache file path
+pub fn get_session_store_path() -> Result<PathBuf> {
+    let cache_dir =
+        dirs::cache_dir().ok_or_else(|| anyhow::anyhow!("Could not determine cache directory"))?;
+    Ok(cache_dir.join("gules").join("sessions.json"))
+}
+
+impl SessionStore {
+    /// Load the store from the default location (empty if missing)
+    pub fn load() -> Result<Self> {
+        Self::load_from(&get_session_store_path()?)
+    }
+
+    /// Load the store from `path` (empty if missing)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).context("Failed to read session cache")?;
+        serde_json::from_str(&contents).context("Failed to parse session cache")
+    }
+
+    /// Save the store to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_session_store_path()?)
+    }
+
+    /// Save the store to `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create cache directory")?;
+        }
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize session cache")?;
+        write_atomic(path, contents).context("Failed to write session cache")?;
+        Ok(())
+    }
+
+    /// Insert or refresh a single session
+    pub fn upsert(&mut self, session: &Session) -> Result<()> {
+        let key = cache_key(&session.id)?;
+        self.sessions.insert(
+            key,
+            CachedSession {
+                session: session.clone(),
+                refreshed_at: Utc::now(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Store the result of a session listing
+    pub fn record_list(&mut self, sessions: &[Session]) -> Result<()> {
+        for session in sessions {
+            self.upsert(session)?;
+        }
+        self.last_list_refresh = Some(Utc::now());
+        Ok(())
+    }
+
+    /// Look up a session by ID or resource name
+    pub fn get(&self, session_id: &str) -> Option<&CachedSession> {
+        let key = cache_key(session_id).ok()?;
+        self.sessions.get(&key)
+    }
+
+    /// Remove a session from the store
+    pub fn remove(&mut self, session_id: &str) -> Option<CachedSession> {
+        let key = cache_key(session_id).ok()?;
+        self.sessions.remove(&key)
+    }
+
+    /// Cached sessions, newest first
+    pub fn list(&self) -> Vec<&Session> {
+        let mut sessions: Vec<&Session> = self.sessions.values().map(|c| &c.session).collect();
+        sessions.sort_by(|a, b| b.create_time.cmp(&a.create_time));
+        sessions
+    }
+
+    /// Most recent refresh time of any cached data
+    pub fn last_refresh(&self) -> Option<DateTime<Utc>> {
+        self.sessions
+            .values()
+            .map(|c| c.refreshed_at)
+            .chain(self.last_list_refresh)
+            .max()
+    }
+}
+
+/// Load, modify and save the default store under its lock file
+fn update_store(f: impl FnOnce(&mut SessionStore) -> Result<()>) -> Result<()> {
+    let path = get_session_store_path()?;
+    with_lock(&path.with_extension("json.lock"), || {
+        let mut store = SessionStore::load_from(&path)?;
+        f(&mut store)?;
+        store.save_to(&path)
+    })
+}
+
+/// Merge sessions from another cache (e.g. an imported archive)
+///
+/// An entry replaces the local one only if it was refreshed more recently.
+/// Returns the number of sessions added or updated.
+pub fn import_sessions(sessions: Vec<CachedSession>) -> Result<usize> {
+    let mut imported = 0;
+    update_store(|store| {
+        for cached in sessions {
+            let key = cache_key(&cached.session.id)?;
+            let newer = store
+                .sessions
+                .get(&key)
+                .is_none_or(|local| local.refreshed_at < cached.refreshed_at);
+            if newer {
+                store.sessions.insert(key, cached);
+                imported += 1;
+            }
+        }
+        Ok(())
+    })?;
+    Ok(imported)
+}
+
+/// Best-effort update of the session cache (failures never break a command)
+pub fn remember_sessions(sessions: &[Session]) {
+    let result = update_store(|store| store.record_list(sessions));
+    if let Err(e) = result {
+        eprintln!("Warning: Failed to update session cache: {}", e);
+    }
+}
+
+/// Best-effort removal of a deleted session from the cache
+pub fn forget_session(session_id: &str) {
+    let result = update_store(|store| {
+        store.remove(session_id);
+        Ok(())
+    });
+    if let Err(e) = result {
+        eprintln!("Warning: Failed to update session cache: {}", e);
+    }
+}
+
+/// Best-effort update of a single cached session
+pub fn remember_session(session: &Session) {
+    let result = update_store(|store| store.upsert(session));
+    if let Err(e) = result {
+        eprintln!("Warning: Failed to update session cache: {}", e);
+    }
+}
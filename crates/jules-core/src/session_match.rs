@@ -0,0 +1,106 @@
+//! Matching session IDs against known ones.
+//!
+//! Lets an unambiguous prefix stand for a full session ID, like a git short
+//! hash, and finds close matches to suggest when a session isn't found.
+
+/// Shortest prefix accepted for a session ID
+pub const MIN_PREFIX_LEN: usize = 4;
+
+/// Longest prefix accepted for a session ID
+///
+/// Full session IDs are longer, so one that isn't cached is never taken for
+/// the prefix of another session's ID.
+pub const MAX_PREFIX_LEN: usize = 12;
+
+/// How far (in edits) a known ID may be from a mistyped one to be suggested
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Result of matching a session ID prefix against known IDs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrefixMatch {
+    /// The value is a known ID, or no known ID starts with it
+    NoMatch,
+    /// Exactly one known ID starts with the value
+    Unique(String),
+    /// Several known IDs start with the value
+    Ambiguous(Vec<String>),
+}
+
+/// Match `value` as a prefix of the `known` session IDs
+///
+/// Values shorter than [`MIN_PREFIX_LEN`], longer than [`MAX_PREFIX_LEN`] or
+/// that are known IDs themselves never match, so full IDs are always passed
+/// through as given.
+pub fn match_prefix<'a>(value: &str, known: impl IntoIterator<Item = &'a str>) -> PrefixMatch {
+    if !(MIN_PREFIX_LEN..=MAX_PREFIX_LEN).contains(&value.len()) {
+        return PrefixMatch::NoMatch;
+    }
+    let mut matches: Vec<String> = Vec::new();
+    for id in known {
+        if id == value {
+            return PrefixMatch::NoMatch;
+        }
+        if id.starts_with(value) && !matches.iter().any(|m| m == id) {
+            matches.push(id.to_string());
+        }
+    }
+    match matches.len() {
+        0 => PrefixMatch::NoMatch,
+        1 => PrefixMatch::Unique(matches.remove(0)),
+        _ => {
+            matches.sort();
+            PrefixMatch::Ambiguous(matches)
+        }
+    }
+}
+
+/// Known session IDs close to `value`, closest first, at most `limit`
+///
+/// Close means at most two typos away: a wrong, missing, extra or swapped
+/// character each count as one.
+pub fn suggest_ids<'a>(
+    value: &str,
+    known: impl IntoIterator<Item = &'a str>,
+    limit: usize,
+) -> Vec<String> {
+    let mut candidates: Vec<(usize, &str)> = known
+        .into_iter()
+        .filter(|id| *id != value)
+        .filter_map(|id| {
+            let distance = edit_distance(value, id);
+            (distance <= MAX_SUGGESTION_DISTANCE).then_some((distance, id))
+        })
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    candidates
+        .into_iter()
+        .take(limit)
+        .map(|(_, id)| id.to_string())
+        .collect()
+}
+
+/// Levenshtein distance between two strings, counting adjacent
+/// transpositions as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
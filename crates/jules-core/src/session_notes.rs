@@ -0,0 +1,100 @@
+//! Local notes attached to sessions (`gules note`).
+//!
+//! Free-form text kept next to a session, e.g. "needs a follow-up for the
+//! migration". Notes never leave this machine: they live in the data
+//! directory, so clearing the cache keeps them, and `session --format full`
+//! shows them with the session.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use jules_rs::SessionName;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::atomic_file::write_atomic;
+
+/// A note on a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionNote {
+    pub added_at: DateTime<Utc>,
+    pub text: String,
+}
+
+/// Notes of every session, oldest first per session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NoteStore {
+    /// Notes by session ID
+    #[serde(default)]
+    pub sessions: BTreeMap<String, Vec<SessionNote>>,
+}
+
+/// Get the notes file path
+pub fn get_notes_path() -> Result<PathBuf> {
+    let data_dir =
+        dirs::data_dir().ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+    Ok(data_dir.join("gules").join("notes.json"))
+}
+
+impl NoteStore {
+    /// Load the notes from the default location (empty if missing)
+    pub fn load() -> Result<Self> {
+        Self::load_from(&get_notes_path()?)
+    }
+
+    /// Load the notes from `path` (empty if missing)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).context("Failed to read session notes")?;
+        serde_json::from_str(&contents).context("Failed to parse session notes")
+    }
+
+    /// Save the notes to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_notes_path()?)
+    }
+
+    /// Save the notes to `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create data directory")?;
+        }
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize session notes")?;
+        write_atomic(path, contents).context("Failed to write session notes")?;
+        Ok(())
+    }
+
+    /// Add a note to a session, given by ID or resource name; returns how
+    /// many notes it has now
+    pub fn add(&mut self, session_id: &str, text: &str) -> Result<usize> {
+        let notes = self.sessions.entry(session_key(session_id)?).or_default();
+        notes.push(SessionNote {
+            added_at: Utc::now(),
+            text: text.to_string(),
+        });
+        Ok(notes.len())
+    }
+
+    /// The notes of a session, oldest first
+    pub fn notes(&self, session_id: &str) -> &[SessionNote] {
+        session_key(session_id)
+            .ok()
+            .and_then(|id| self.sessions.get(&id))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Drop every note of a session; returns how many there were
+    pub fn clear(&mut self, session_id: &str) -> Result<usize> {
+        let removed = self.sessions.remove(&session_key(session_id)?);
+        Ok(removed.map_or(0, |notes| notes.len()))
+    }
+}
+
+/// The bare session ID notes are kept under
+fn session_key(session_id: &str) -> Result<String> {
+    Ok(SessionName::from_id_or_name(session_id)?.id().to_string())
+}
@@ -0,0 +1,78 @@
+//! Finding the Jules sessions that text refers to (`issue-status`).
+//!
+//! Issue comments, pull request descriptions, commit messages and branch
+//! names mention sessions in different ways, so the patterns can be
+//! replaced with the `github.session_patterns` setting.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+/// Patterns used when `github.session_patterns` is empty; the first
+/// capture group of each is the session ID
+pub const DEFAULT_SESSION_PATTERNS: &[&str] = &[
+    r"sessions/([a-zA-Z0-9_-]+)",
+    r"session[:\s]+([a-zA-Z0-9_-]+)",
+    r"jules\.google\.com/session/([a-zA-Z0-9_-]+)",
+    r"https://console\.cloud\.google\.com/[^/]*/([a-zA-Z0-9_-]+)",
+];
+
+/// Compiled session reference patterns
+#[derive(Debug, Clone)]
+pub struct SessionPatterns {
+    patterns: Vec<Regex>,
+}
+
+impl SessionPatterns {
+    /// Compile `patterns`, or the defaults when it is empty; each needs a
+    /// capture group for the session ID
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let patterns = if patterns.is_empty() {
+            DEFAULT_SESSION_PATTERNS
+                .iter()
+                .map(|pattern| Regex::new(pattern).expect("default pattern is valid"))
+                .collect()
+        } else {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    let regex = Regex::new(pattern).with_context(|| {
+                        format!("Invalid github.session_patterns entry: {}", pattern)
+                    })?;
+                    if regex.captures_len() < 2 {
+                        anyhow::bail!(
+                            "github.session_patterns entry {} has no capture group for the \
+                             session ID",
+                            pattern
+                        );
+                    }
+                    Ok(regex)
+                })
+                .collect::<Result<_>>()?
+        };
+        Ok(Self { patterns })
+    }
+
+    /// The session IDs `texts` refer to, in order of first mention
+    pub fn extract<'a>(&self, texts: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let mut session_ids = Vec::new();
+        for text in texts {
+            for pattern in &self.patterns {
+                for captures in pattern.captures_iter(text) {
+                    if let Some(id) = captures.get(1) {
+                        let id = id.as_str().to_string();
+                        if !session_ids.contains(&id) {
+                            session_ids.push(id);
+                        }
+                    }
+                }
+            }
+        }
+        session_ids
+    }
+}
+
+impl Default for SessionPatterns {
+    fn default() -> Self {
+        Self::new(&[]).expect("default patterns are valid")
+    }
+}
@@ -0,0 +1,274 @@
+//! Summaries of sessions over a period (`gules report`).
+//!
+//! A report counts the sessions created in a period per state, averages how
+//! long finished sessions took, and lists the pull requests they opened and
+//! why failed sessions failed. Building one is pure; fetching sessions and
+//! failure reasons is up to the caller.
+
+use crate::issue_links::IssueLinks;
+use crate::markdown_output::{inline_text, pull_request_url};
+use crate::time_range::format_duration;
+use chrono::{DateTime, Duration, Utc};
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::{Session, State};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Sessions of a period, summarized
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReport {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub total_sessions: usize,
+    /// Sessions per state, in lifecycle order; states without sessions are left out
+    pub states: Vec<StateCount>,
+    /// Average time from creation to the last update of completed and
+    /// failed sessions
+    pub average_duration_seconds: Option<i64>,
+    pub pull_requests: Vec<ReportedPullRequest>,
+    pub failures: Vec<ReportedFailure>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StateCount {
+    pub state: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportedPullRequest {
+    pub session_id: String,
+    pub title: String,
+    pub url: String,
+    /// Issues the session is linked to, like `octo/app#12`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportedFailure {
+    pub session_id: String,
+    pub title: String,
+    pub reason: Option<String>,
+    /// Issues the session is linked to, like `octo/app#12`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<String>,
+}
+
+impl SessionReport {
+    /// Summarize `sessions` (already filtered to the period and source)
+    ///
+    /// `failure_reasons` maps session IDs to the reason of their
+    /// `sessionFailed` activity, see [`failure_reason`].
+    pub fn new(
+        sessions: &[Session],
+        failure_reasons: &HashMap<String, String>,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        source: Option<&str>,
+    ) -> Self {
+        let mut counts: Vec<(u8, &'static str, usize)> = Vec::new();
+        for session in sessions {
+            let (order, name) = match session.state {
+                Some(state) => (state as u8, state.display_name()),
+                None => (u8::MAX, "Unknown"),
+            };
+            match counts.iter_mut().find(|(o, _, _)| *o == order) {
+                Some((_, _, count)) => *count += 1,
+                None => counts.push((order, name, 1)),
+            }
+        }
+        counts.sort_by_key(|(order, _, _)| *order);
+
+        let durations: Vec<Duration> = sessions.iter().filter_map(session_duration).collect();
+        let average_duration_seconds = (!durations.is_empty()).then(|| {
+            durations.iter().map(Duration::num_seconds).sum::<i64>() / durations.len() as i64
+        });
+
+        let pull_requests = sessions
+            .iter()
+            .filter_map(|session| {
+                Some(ReportedPullRequest {
+                    session_id: session.id.clone(),
+                    title: title(session),
+                    url: pull_request_url(session)?.to_string(),
+                    issues: Vec::new(),
+                })
+            })
+            .collect();
+        let failures = sessions
+            .iter()
+            .filter(|session| session.state == Some(State::Failed))
+            .map(|session| ReportedFailure {
+                session_id: session.id.clone(),
+                title: title(session),
+                reason: failure_reasons.get(&session.id).cloned(),
+                issues: Vec::new(),
+            })
+            .collect();
+
+        Self {
+            since,
+            until,
+            source: source.map(str::to_string),
+            total_sessions: sessions.len(),
+            states: counts
+                .into_iter()
+                .map(|(_, state, count)| StateCount {
+                    state: state.to_string(),
+                    count,
+                })
+                .collect(),
+            average_duration_seconds,
+            pull_requests,
+            failures,
+        }
+    }
+
+    /// Name the issues each pull request and failure is linked to
+    pub fn with_issue_links(mut self, links: &IssueLinks) -> Self {
+        let issues = |session_id: &str| -> Vec<String> {
+            links
+                .for_session(session_id)
+                .iter()
+                .map(|link| link.issue_ref())
+                .collect()
+        };
+        for pr in &mut self.pull_requests {
+            pr.issues = issues(&pr.session_id);
+        }
+        for failure in &mut self.failures {
+            failure.issues = issues(&failure.session_id);
+        }
+        self
+    }
+
+    /// The report as Markdown, for pasting into a weekly update
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("# Jules report: {}\n\n", self.period());
+        if let Some(source) = &self.source {
+            let _ = writeln!(md, "- **Source:** `{}`", source);
+        }
+        let _ = writeln!(md, "- **Sessions:** {}", self.total_sessions);
+        if let Some(average) = self.average_duration() {
+            let _ = writeln!(md, "- **Average duration:** {}", average);
+        }
+        let _ = writeln!(md, "- **Pull requests:** {}", self.pull_requests.len());
+
+        if !self.states.is_empty() {
+            md.push_str("\n## Sessions by state\n\n| State | Sessions |\n| --- | --- |\n");
+            for count in &self.states {
+                let _ = writeln!(md, "| {} | {} |", count.state, count.count);
+            }
+        }
+        if !self.pull_requests.is_empty() {
+            md.push_str("\n## Pull requests\n\n");
+            for pr in &self.pull_requests {
+                let _ = writeln!(
+                    md,
+                    "- [{}]({}) (`{}`){}",
+                    inline_text(&pr.title).replace(']', "\\]"),
+                    pr.url,
+                    pr.session_id,
+                    issues_suffix(&pr.issues)
+                );
+            }
+        }
+        if !self.failures.is_empty() {
+            md.push_str("\n## Failures\n\n");
+            for failure in &self.failures {
+                let _ = writeln!(
+                    md,
+                    "- {} (`{}`){}: {}",
+                    inline_text(&failure.title),
+                    failure.session_id,
+                    issues_suffix(&failure.issues),
+                    inline_text(failure.reason.as_deref().unwrap_or("Unknown reason"))
+                );
+            }
+        }
+        md
+    }
+
+    /// The period, e.g. "2025-01-01 to 2025-01-08"
+    pub fn period(&self) -> String {
+        format!(
+            "{} to {}",
+            self.since.format("%Y-%m-%d"),
+            self.until.format("%Y-%m-%d")
+        )
+    }
+
+    /// The average duration, formatted like `2h 15m`
+    pub fn average_duration(&self) -> Option<String> {
+        self.average_duration_seconds
+            .map(|seconds| format_duration(Duration::seconds(seconds)))
+    }
+}
+
+/// ` for octo/app#12, ...` naming linked issues, or nothing
+pub fn issues_suffix(issues: &[String]) -> String {
+    if issues.is_empty() {
+        String::new()
+    } else {
+        format!(" for {}", issues.join(", "))
+    }
+}
+
+/// Whether a session was created within `[since, until]` and, with
+/// `source`, belongs to it (full name like `sources/github/octo/app`, or
+/// its trailing `octo/app`)
+pub fn in_report(
+    session: &Session,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+    source: Option<&str>,
+) -> bool {
+    let time_ok = parse(session.create_time.as_deref())
+        .is_some_and(|created| created >= since && created <= until);
+    time_ok && source.is_none_or(|source| source_matches(session, source))
+}
+
+/// Whether a session works on `source`: its full name like
+/// `sources/github/octo/app`, or the trailing `octo/app`
+pub fn source_matches(session: &Session, source: &str) -> bool {
+    let name = &session.source_context.source;
+    let segments: Vec<&str> = name.rsplitn(3, '/').take(2).collect();
+    let repo = format!("{}/{}", segments.get(1).unwrap_or(&""), segments[0]);
+    name == source || repo == source
+}
+
+/// How long a finished (completed or failed) session ran: from creation to
+/// its last update
+pub fn session_duration(session: &Session) -> Option<Duration> {
+    if !matches!(session.state, Some(State::Completed | State::Failed)) {
+        return None;
+    }
+    let created = parse(session.create_time.as_deref())?;
+    let updated = parse(session.update_time.as_deref())?;
+    Some(updated - created).filter(|duration| *duration >= Duration::zero())
+}
+
+/// The reason given by a session's `sessionFailed` activity
+pub fn failure_reason(activities: &[Activity]) -> Option<String> {
+    activities
+        .iter()
+        .find_map(|activity| activity.session_failed.as_ref()?.reason.clone())
+}
+
+fn title(session: &Session) -> String {
+    session
+        .title
+        .clone()
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| "Untitled".to_string())
+}
+
+fn parse(time: Option<&str>) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(time?)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
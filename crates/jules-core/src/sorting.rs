@@ -0,0 +1,142 @@
+//! Client-side sorting for list commands (`--sort created --desc`).
+//!
+//! The API decides the order of listings; sorting happens after fetching,
+//! so it applies to table output as well as JSON. Records without a value
+//! for the sort key (no title, unparseable time) always come last, and ties
+//! keep the API order.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use std::cmp::Ordering;
+
+/// Sort key of session listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionSort {
+    Created,
+    Updated,
+    /// Lifecycle order: queued, planning, ..., failed, completed
+    State,
+    /// Case-insensitive
+    Title,
+}
+
+impl SessionSort {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "created" => Ok(Self::Created),
+            "updated" => Ok(Self::Updated),
+            "state" => Ok(Self::State),
+            "title" => Ok(Self::Title),
+            _ => anyhow::bail!(
+                "Unknown sort key: {}. Valid options: created, updated, state, title",
+                s
+            ),
+        }
+    }
+}
+
+/// Sort key of activity listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivitySort {
+    Created,
+    Type,
+    Originator,
+}
+
+impl ActivitySort {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "created" | "time" => Ok(Self::Created),
+            "type" => Ok(Self::Type),
+            "originator" => Ok(Self::Originator),
+            _ => anyhow::bail!(
+                "Unknown sort key: {}. Valid options for activities: created, type, originator",
+                s
+            ),
+        }
+    }
+}
+
+/// A sort key and direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortOrder<K> {
+    pub key: K,
+    pub descending: bool,
+}
+
+impl<K> SortOrder<K> {
+    pub fn new(key: K, descending: bool) -> Self {
+        Self { key, descending }
+    }
+}
+
+/// Sort sessions in place
+pub fn sort_sessions(sessions: &mut [Session], order: SortOrder<SessionSort>) {
+    sessions.sort_by(|a, b| match order.key {
+        SessionSort::Created => compare(
+            parse_time(a.create_time.as_deref()),
+            parse_time(b.create_time.as_deref()),
+            order.descending,
+        ),
+        SessionSort::Updated => compare(
+            parse_time(a.update_time.as_deref()),
+            parse_time(b.update_time.as_deref()),
+            order.descending,
+        ),
+        SessionSort::State => compare(
+            a.state.map(|state| state as u8),
+            b.state.map(|state| state as u8),
+            order.descending,
+        ),
+        SessionSort::Title => compare(title_key(a), title_key(b), order.descending),
+    });
+}
+
+/// Sort activities in place
+pub fn sort_activities(activities: &mut [Activity], order: SortOrder<ActivitySort>) {
+    activities.sort_by(|a, b| match order.key {
+        ActivitySort::Created => compare(
+            parse_time(Some(&a.create_time)),
+            parse_time(Some(&b.create_time)),
+            order.descending,
+        ),
+        ActivitySort::Type => compare(
+            Some(a.activity_type()),
+            Some(b.activity_type()),
+            order.descending,
+        ),
+        ActivitySort::Originator => compare(
+            Some(&a.originator).filter(|o| !o.is_empty()),
+            Some(&b.originator).filter(|o| !o.is_empty()),
+            order.descending,
+        ),
+    });
+}
+
+/// Compare present values in the given direction; missing values go last
+fn compare<T: Ord>(a: Option<T>, b: Option<T>, descending: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if descending => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Timestamps compare as instants; their strings differ in precision
+fn parse_time(time: Option<&str>) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(time?)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+fn title_key(session: &Session) -> Option<String> {
+    session
+        .title
+        .as_deref()
+        .filter(|title| !title.is_empty())
+        .map(str::to_lowercase)
+}
@@ -0,0 +1,215 @@
+//! Local cache of the sources list.
+//!
+//! Keeps a copy of every source returned by `list_sources`, so source ID
+//! completion and resolution work instantly and offline. Refreshed
+//! explicitly with `gules sources refresh`, or whenever a complete,
+//! unfiltered listing is fetched.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use jules_rs::types::source::Source;
+use jules_rs::JulesClient;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::atomic_file::write_atomic;
+use crate::progress::Spinner;
+
+/// Page size used when fetching every source
+const SOURCES_PAGE_SIZE: u32 = 100;
+/// Safety limit on pages fetched during a refresh
+const MAX_SOURCE_PAGES: usize = 50;
+
+/// Cached sources list
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SourceStore {
+    #[serde(default)]
+    pub sources: Vec<Source>,
+    /// When the list was last fetched from the API
+    #[serde(default)]
+    pub refreshed_at: Option<DateTime<Utc>>,
+}
+
+/// Get the sources cache file path
+pub fn get_source_store_path() -> Result<PathBuf> {
+    let cache_dir =
+        dirs::cache_dir().ok_or_else(|| anyhow::anyhow!("Could not determine cache directory"))?;
+    Ok(cache_dir.join("gules").join("sources.json"))
+}
+
+impl SourceStore {
+    /// Load the store from the default location (empty if missing)
+    pub fn load() -> Result<Self> {
+        Self::load_from(&get_source_store_path()?)
+    }
+
+    /// Load the store from `path` (empty if missing)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).context("Failed to read sources cache")?;
+        serde_json::from_str(&contents).context("Failed to parse sources cache")
+    }
+
+    /// Save the store to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_source_store_path()?)
+    }
+
+    /// Save the store to `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create cache directory")?;
+        }
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize sources cache")?;
+        write_atomic(path, contents).context("Failed to write sources cache")?;
+        Ok(())
+    }
+
+    /// Replace the cached list with a complete listing
+    pub fn replace(&mut self, mut sources: Vec<Source>) {
+        sources.sort_by(|a, b| a.id.cmp(&b.id));
+        self.sources = sources;
+        self.refreshed_at = Some(Utc::now());
+    }
+
+    /// Source IDs (`github/owner/repo`), sorted
+    pub fn ids(&self) -> Vec<&str> {
+        self.sources.iter().map(|s| s.id.as_str()).collect()
+    }
+
+    /// The cached source named `name` (`sources/github/owner/repo`),
+    /// ignoring case
+    pub fn find(&self, name: &str) -> Option<&Source> {
+        self.sources
+            .iter()
+            .find(|source| source.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The full source name (`sources/github/owner/repo`) for `spec`: a full
+    /// name, an ID, `owner/repo`, or just `repo` if only one owner has it
+    ///
+    /// Full names are returned as is. `owner/repo` also resolves when it is
+    /// not cached, since its full name is known; a bare `repo` must match
+    /// exactly one cached source. Matching ignores case.
+    pub fn expand(&self, spec: &str) -> Result<String> {
+        if spec.starts_with("sources/") {
+            return Ok(spec.to_string());
+        }
+        let spec = spec.strip_prefix("github/").unwrap_or(spec);
+        let wanted = spec.to_lowercase();
+        let candidates: Vec<&Source> = self
+            .sources
+            .iter()
+            .filter(|source| {
+                let id = source.id.to_lowercase();
+                let repo_path = id.strip_prefix("github/").unwrap_or(&id);
+                let repo = repo_path.rsplit('/').next().unwrap_or_default();
+                if wanted.contains('/') {
+                    repo_path == wanted
+                } else {
+                    repo == wanted
+                }
+            })
+            .collect();
+
+        match candidates.as_slice() {
+            [source] => Ok(source.name.clone()),
+            [] if spec.contains('/') => Ok(format!("sources/github/{}", spec)),
+            [] if self.refreshed_at.is_none() => anyhow::bail!(
+                "Unknown source '{}'. Give it as owner/{} or run 'gules sources refresh' \
+                 so repository names can be resolved",
+                spec,
+                spec
+            ),
+            [] => anyhow::bail!(
+                "Unknown source '{}'. Run 'gules sources' to see the available sources",
+                spec
+            ),
+            _ => {
+                let names: Vec<&str> = candidates.iter().map(|s| s.name.as_str()).collect();
+                anyhow::bail!(
+                    "Ambiguous source '{}', it matches:\n  {}\nGive it as owner/repo",
+                    spec,
+                    names.join("\n  ")
+                )
+            }
+        }
+    }
+}
+
+/// Expand a `--source` value with the cached sources list, see
+/// [`SourceStore::expand`]
+pub fn expand_source(spec: &str) -> Result<String> {
+    if spec.starts_with("sources/") {
+        return Ok(spec.to_string());
+    }
+    SourceStore::load()?.expand(spec)
+}
+
+/// Fetch every source, following page tokens
+pub async fn fetch_all_sources(client: &JulesClient) -> Result<Vec<Source>> {
+    let mut sources = Vec::new();
+    let mut page_token: Option<String> = None;
+    let spinner = Spinner::start("Fetching sources...");
+
+    for _ in 0..MAX_SOURCE_PAGES {
+        let response = client
+            .list_sources(None, Some(SOURCES_PAGE_SIZE), page_token.as_deref())
+            .await?;
+        sources.extend(response.sources);
+        spinner.set_message(format!("Fetching sources... {} so far", sources.len()));
+
+        match response.next_page_token {
+            Some(token) if !token.is_empty() => page_token = Some(token),
+            _ => break,
+        }
+    }
+
+    Ok(sources)
+}
+
+/// Fetch every source from the API and store it in the cache
+pub async fn refresh_sources(client: &JulesClient) -> Result<SourceStore> {
+    let mut store = SourceStore::load()?;
+    store.replace(fetch_all_sources(client).await?);
+    store.save()?;
+    Ok(store)
+}
+
+/// The source named `name` from the cache, or else from a fresh listing;
+/// `None` if the repository isn't connected to Jules
+pub async fn find_source(client: &JulesClient, name: &str) -> Result<Option<Source>> {
+    if let Some(source) = SourceStore::load()?.find(name) {
+        return Ok(Some(source.clone()));
+    }
+    Ok(refresh_sources(client).await?.find(name).cloned())
+}
+
+/// Why sessions can't use the source `name`, and how to connect its
+/// repository to Jules
+pub fn not_connected_message(name: &str) -> String {
+    let repo = name.strip_prefix("sources/github/").unwrap_or(name);
+    let mut message = format!(
+        "{} is not connected to Jules, so sessions can't use it.\n\nTo connect it:\n  \
+         1. Sign in at https://jules.google.com with the account of your API key\n  \
+         2. Connect GitHub there and give the Jules GitHub app access to {}\n",
+        repo, repo
+    );
+    if let Some((owner, _)) = repo.split_once('/') {
+        message.push_str(&format!(
+            "     If the app is installed already, add the repository to it at\n     \
+             https://github.com/settings/installations, or for an organization at\n     \
+             https://github.com/organizations/{}/settings/installations\n",
+            owner
+        ));
+    }
+    message.push_str(&format!(
+        "  3. Check again with 'gules sources check {}'",
+        repo
+    ));
+    message
+}
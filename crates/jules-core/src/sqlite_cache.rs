@@ -0,0 +1,588 @@
+//! SQLite backend for the activity cache.
+//!
+//! An alternative to the per-session JSON files in [`crate::activity_cache`].
+//! Activities are stored one row each, with indexed columns for session,
+//! type, creation time and bash exit code, so filters run as SQL queries
+//! instead of loading and deserializing whole sessions.
+//!
+//! Enable with `backend = "sqlite"` in the `[cache]` section of the config.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use jules_rs::types::activity::{Activity, ListActivitiesResponse};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::activity_cache::{
+    cache_key, get_cache_dir, is_cache_fresh, pinned_sessions, CacheCounters, CacheMetrics,
+    CacheStats, EvictionPolicy, SessionCache, SessionStats,
+};
+use crate::config::CacheConfig;
+
+/// Database file name inside the cache directory
+const DB_FILE_NAME: &str = "activities.db";
+
+/// Schema version stored in `PRAGMA user_version`
+///
+/// Bump it when the schema changes and upgrade older databases in
+/// [`SqliteCache::open`].
+pub const SCHEMA_VERSION: i64 = 2;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    session_id      TEXT PRIMARY KEY,
+    last_page_token TEXT,
+    last_updated    TEXT NOT NULL,
+    created_at      TEXT NOT NULL,
+    last_accessed   TEXT
+);
+
+CREATE TABLE IF NOT EXISTS activities (
+    session_id    TEXT NOT NULL REFERENCES sessions(session_id) ON DELETE CASCADE,
+    activity_id   TEXT NOT NULL,
+    create_time   TEXT NOT NULL,
+    activity_type TEXT NOT NULL,
+    exit_code     INTEGER,
+    has_bash      INTEGER NOT NULL DEFAULT 0,
+    data          TEXT NOT NULL,
+    PRIMARY KEY (session_id, activity_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_activities_time ON activities(session_id, create_time);
+CREATE INDEX IF NOT EXISTS idx_activities_type ON activities(session_id, activity_type);
+CREATE INDEX IF NOT EXISTS idx_activities_exit_code ON activities(exit_code);
+";
+
+/// Filters for [`SqliteCache::query`]
+///
+/// All set fields must match. Results are ordered newest first.
+#[derive(Debug, Clone, Default)]
+pub struct ActivityQuery {
+    /// Restrict to one session (bare ID or `sessions/{id}`)
+    pub session_id: Option<String>,
+    /// Activity types as returned by `Activity::activity_type()` (any of)
+    pub activity_types: Vec<String>,
+    /// Only activities created at or after this RFC 3339 timestamp
+    pub since: Option<String>,
+    /// Only activities created before this RFC 3339 timestamp
+    pub until: Option<String>,
+    /// Only activities with a bash artifact that exited with this code
+    pub exit_code: Option<i32>,
+    /// Only activities that contain bash output
+    pub has_bash_output: bool,
+    /// Only activities with a bash artifact that exited with a non-zero code
+    pub failed_only: bool,
+    /// Maximum number of activities to return
+    pub limit: Option<usize>,
+}
+
+/// Activity cache stored in a single SQLite database
+pub struct SqliteCache {
+    conn: Connection,
+    path: PathBuf,
+    max_sessions: usize,
+    /// Eviction order when a limit is exceeded
+    eviction: EvictionPolicy,
+    /// Maximum total size of the cached activities
+    max_size_bytes: Option<u64>,
+    /// Sessions excluded from eviction
+    pinned: Vec<String>,
+}
+
+impl SqliteCache {
+    /// Open (or create) the database in the default cache directory
+    ///
+    /// Sessions pinned with `gules cache pin` are excluded from eviction.
+    pub fn open_default(max_sessions: usize) -> Result<Self> {
+        let cache_dir = get_cache_dir()?;
+        fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
+        Ok(Self::open(cache_dir.join(DB_FILE_NAME), max_sessions)?.with_pinned(pinned_sessions()?))
+    }
+
+    /// Open the database in the default cache directory with the `[cache]`
+    /// settings
+    ///
+    /// Fails if `encrypt` is set, as the database is not encrypted at rest.
+    pub fn open_configured(config: &CacheConfig) -> Result<Self> {
+        if config.encrypt {
+            anyhow::bail!(
+                "cache.encrypt is not supported with cache.backend = \"sqlite\", which stores activities unencrypted; set cache.backend = \"json\" or cache.encrypt = false"
+            );
+        }
+        Ok(Self::open_default(config.max_sessions)?
+            .with_limits(config.eviction, config.max_size_mb))
+    }
+
+    /// Open (or create) the database at `path`
+    pub fn open(path: impl AsRef<Path>, max_sessions: usize) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let conn = Connection::open(&path)
+            .with_context(|| format!("Failed to open cache database {}", path.display()))?;
+        conn.execute_batch("PRAGMA foreign_keys = ON; PRAGMA journal_mode = WAL;")
+            .context("Failed to configure cache database")?;
+
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version > SCHEMA_VERSION {
+            anyhow::bail!(
+                "Cache database {} was written by a newer version of gules (schema {}, supported {}); upgrade gules or delete the file",
+                path.display(),
+                version,
+                SCHEMA_VERSION
+            );
+        }
+        // Version 0 (unversioned) databases have the version 1 schema
+        conn.execute_batch(SCHEMA)
+            .context("Failed to initialize cache database")?;
+        if version < 2 {
+            let has_last_accessed: bool = conn.query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('sessions') WHERE name = 'last_accessed'",
+                [],
+                |row| row.get(0),
+            )?;
+            if !has_last_accessed {
+                conn.execute_batch("ALTER TABLE sessions ADD COLUMN last_accessed TEXT")
+                    .context("Failed to upgrade cache database")?;
+            }
+        }
+        if version < SCHEMA_VERSION {
+            conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+                .context("Failed to update cache database version")?;
+        }
+
+        Ok(Self {
+            conn,
+            path,
+            max_sessions,
+            eviction: EvictionPolicy::default(),
+            max_size_bytes: None,
+            pinned: Vec::new(),
+        })
+    }
+
+    /// Evict in `eviction` order, also once the cached activities exceed
+    /// `max_size_mb`
+    pub fn with_limits(mut self, eviction: EvictionPolicy, max_size_mb: Option<u64>) -> Self {
+        self.eviction = eviction;
+        self.max_size_bytes = max_size_mb.map(|mb| mb.saturating_mul(1024 * 1024));
+        self
+    }
+
+    /// Exclude these sessions from eviction
+    pub fn with_pinned(mut self, pinned: Vec<String>) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
+    /// Location of the database file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether a session has been cached
+    pub fn has_session(&self, session_id: &str) -> Result<bool> {
+        let key = cache_key(session_id)?;
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM sessions WHERE session_id = ?1",
+            params![key],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Page token stored by the last update of a session
+    pub fn last_page_token(&self, session_id: &str) -> Result<Option<String>> {
+        let key = cache_key(session_id)?;
+        let token = self
+            .conn
+            .query_row(
+                "SELECT last_page_token FROM sessions WHERE session_id = ?1",
+                params![key],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()?;
+        Ok(token.flatten())
+    }
+
+    /// Whether the cached session can be served without asking the API
+    ///
+    /// Returns `false` when the session is not cached at all.
+    pub fn is_fresh(&self, session_id: &str, ttl_seconds: u64) -> Result<bool> {
+        let key = cache_key(session_id)?;
+        let row = self
+            .conn
+            .query_row(
+                "SELECT s.last_updated, EXISTS (
+                     SELECT 1 FROM activities a
+                     WHERE a.session_id = s.session_id
+                       AND a.activity_type IN ('Session Completed', 'Session Failed')
+                 )
+                 FROM sessions s WHERE s.session_id = ?1",
+                params![key],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?)),
+            )
+            .optional()?;
+
+        match row {
+            Some((last_updated, terminal)) => Ok(is_cache_fresh(
+                parse_time(&last_updated)?,
+                terminal,
+                ttl_seconds,
+            )),
+            None => Ok(false),
+        }
+    }
+
+    /// Load all cached activities for a session
+    ///
+    /// With the LRU policy this counts as an access and moves the session to
+    /// the back of the eviction order; use [`SqliteCache::peek_session`] to
+    /// avoid that.
+    pub fn load_session(&self, session_id: &str) -> Result<Option<SessionCache>> {
+        let cache = self.peek_session(session_id)?;
+        if cache.is_some() && self.eviction == EvictionPolicy::Lru {
+            self.conn
+                .execute(
+                    "UPDATE sessions SET last_accessed = ?1 WHERE session_id = ?2",
+                    params![format_time(&Utc::now()), cache_key(session_id)?],
+                )
+                .context("Failed to record cache access")?;
+        }
+        Ok(cache)
+    }
+
+    /// Load all cached activities for a session without counting as an access
+    pub fn peek_session(&self, session_id: &str) -> Result<Option<SessionCache>> {
+        let key = cache_key(session_id)?;
+
+        let row = self
+            .conn
+            .query_row(
+                "SELECT last_page_token, last_updated, created_at FROM sessions WHERE session_id = ?1",
+                params![key],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                },
+            )
+            .optional()
+            .context(format!("Failed to read cache for session {}", key))?;
+
+        let Some((last_page_token, last_updated, created_at)) = row else {
+            return Ok(None);
+        };
+
+        let activities = self.query(&ActivityQuery {
+            session_id: Some(key.clone()),
+            ..Default::default()
+        })?;
+
+        Ok(Some(SessionCache {
+            session_id: key,
+            activities,
+            last_page_token,
+            last_updated: parse_time(&last_updated)?,
+            created_at: parse_time(&created_at)?,
+        }))
+    }
+
+    /// Replace the cached activities for a session
+    pub fn save_session(&mut self, cache: &SessionCache) -> Result<()> {
+        let key = cache_key(&cache.session_id)?;
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            "INSERT INTO sessions (session_id, last_page_token, last_updated, created_at, last_accessed)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(session_id) DO UPDATE SET
+                 last_page_token = excluded.last_page_token,
+                 last_updated = excluded.last_updated,
+                 last_accessed = excluded.last_accessed",
+            params![
+                key,
+                cache.last_page_token,
+                format_time(&cache.last_updated),
+                format_time(&cache.created_at),
+                format_time(&Utc::now())
+            ],
+        )?;
+        tx.execute("DELETE FROM activities WHERE session_id = ?1", params![key])?;
+        insert_activities(&tx, &key, &cache.activities)?;
+        tx.commit()
+            .context(format!("Failed to write cache for session {}", key))?;
+
+        self.evict_except(Some(&key))
+    }
+
+    /// Merge a new API page into the session cache
+    pub fn update_incremental(
+        &mut self,
+        session_id: &str,
+        response: &ListActivitiesResponse,
+    ) -> Result<()> {
+        let key = cache_key(session_id)?;
+        let now = format_time(&Utc::now());
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            "INSERT INTO sessions (session_id, last_page_token, last_updated, created_at, last_accessed)
+             VALUES (?1, ?2, ?3, ?3, ?3)
+             ON CONFLICT(session_id) DO UPDATE SET
+                 last_page_token = excluded.last_page_token,
+                 last_updated = excluded.last_updated,
+                 last_accessed = excluded.last_accessed",
+            params![key, response.next_page_token, now],
+        )?;
+        // Upsert keeps deduplication by activity ID, like merge_activities
+        insert_activities(&tx, &key, &response.activities)?;
+        tx.commit()
+            .context(format!("Failed to write cache for session {}", key))?;
+
+        self.evict_except(Some(&key))
+    }
+
+    /// Query cached activities using the indexed columns
+    pub fn query(&self, query: &ActivityQuery) -> Result<Vec<Activity>> {
+        let mut sql = String::from("SELECT data FROM activities WHERE 1 = 1");
+        let mut values: Vec<rusqlite::types::Value> = Vec::new();
+
+        if let Some(session_id) = &query.session_id {
+            values.push(cache_key(session_id)?.into());
+            sql.push_str(&format!(" AND session_id = ?{}", values.len()));
+        }
+        if !query.activity_types.is_empty() {
+            let mut placeholders = Vec::new();
+            for activity_type in &query.activity_types {
+                values.push(activity_type.clone().into());
+                placeholders.push(format!("?{}", values.len()));
+            }
+            sql.push_str(&format!(
+                " AND activity_type IN ({})",
+                placeholders.join(", ")
+            ));
+        }
+        if let Some(since) = &query.since {
+            values.push(normalize_time(since).into());
+            sql.push_str(&format!(" AND create_time >= ?{}", values.len()));
+        }
+        if let Some(until) = &query.until {
+            values.push(normalize_time(until).into());
+            sql.push_str(&format!(" AND create_time < ?{}", values.len()));
+        }
+        if let Some(exit_code) = query.exit_code {
+            values.push(i64::from(exit_code).into());
+            sql.push_str(&format!(" AND exit_code = ?{}", values.len()));
+        }
+        if query.has_bash_output {
+            sql.push_str(" AND has_bash = 1");
+        }
+        if query.failed_only {
+            sql.push_str(" AND exit_code IS NOT NULL AND exit_code != 0");
+        }
+
+        sql.push_str(" ORDER BY create_time DESC");
+        if let Some(limit) = query.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(values), |row| row.get::<_, String>(0))?;
+
+        let mut activities = Vec::new();
+        for data in rows {
+            let activity: Activity =
+                serde_json::from_str(&data?).context("Failed to parse cached activity")?;
+            activities.push(activity);
+        }
+        Ok(activities)
+    }
+
+    /// Delete the cache for one session
+    pub fn delete_session(&self, session_id: &str) -> Result<()> {
+        let key = cache_key(session_id)?;
+        self.conn
+            .execute("DELETE FROM sessions WHERE session_id = ?1", params![key])
+            .context(format!("Failed to delete cache for session {}", key))?;
+        Ok(())
+    }
+
+    /// Remove every cached session and activity
+    pub fn clear(&self) -> Result<()> {
+        self.conn
+            .execute_batch("DELETE FROM activities; DELETE FROM sessions;")
+            .context("Failed to clear cache database")?;
+        Ok(())
+    }
+
+    /// Run SQLite's integrity check; returns the problems found (empty if ok)
+    pub fn integrity_check(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to check cache database")?;
+        Ok(rows.into_iter().filter(|row| row != "ok").collect())
+    }
+
+    /// Cached session IDs, next to be evicted first
+    pub fn list_sessions(&self) -> Result<Vec<String>> {
+        Ok(self
+            .sessions_by_eviction_order()?
+            .into_iter()
+            .map(|(session_id, _)| session_id)
+            .collect())
+    }
+
+    /// Cached session IDs with the size of their activities, next to be
+    /// evicted first: cached first for FIFO, accessed least recently for LRU
+    fn sessions_by_eviction_order(&self) -> Result<Vec<(String, u64)>> {
+        let order = match self.eviction {
+            EvictionPolicy::Fifo => "s.created_at",
+            EvictionPolicy::Lru => "COALESCE(s.last_accessed, s.last_updated)",
+        };
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT s.session_id, COALESCE(SUM(LENGTH(a.data)), 0)
+             FROM sessions s LEFT JOIN activities a ON a.session_id = s.session_id
+             GROUP BY s.session_id
+             ORDER BY {}, s.session_id",
+            order
+        ))?;
+        let sessions = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(sessions)
+    }
+
+    /// Cache statistics, in the same shape as the JSON backend
+    pub fn stats(&self, enabled: bool) -> Result<CacheStats> {
+        let mut stmt = self.conn.prepare(
+            "SELECT s.session_id, s.last_updated, COUNT(a.activity_id),
+                    COALESCE(SUM(LENGTH(a.data)), 0)
+             FROM sessions s LEFT JOIN activities a ON a.session_id = s.session_id
+             GROUP BY s.session_id
+             ORDER BY s.created_at, s.session_id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+
+        let mut sessions = Vec::new();
+        for row in rows {
+            let (session_id, last_updated, activities, size) = row?;
+            sessions.push(SessionStats {
+                pinned: self.pinned.contains(&session_id),
+                session_id,
+                activities: activities as usize,
+                size_bytes: size as u64,
+                last_updated: parse_time(&last_updated).ok(),
+                counters: CacheCounters::default(),
+            });
+        }
+        let total_size_bytes = fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+
+        Ok(CacheStats {
+            enabled,
+            total_sessions: sessions.len(),
+            max_sessions: self.max_sessions,
+            total_activities: sessions.iter().map(|s| s.activities).sum(),
+            total_size_bytes,
+            cache_dir: self.path.display().to_string(),
+            // `open_configured` refuses to open the database with encryption on
+            encryption_enabled: false,
+            encrypted_sessions: 0,
+            // Counters live in the cache metadata (see `ActivityStore::stats`)
+            metrics: CacheMetrics::default(),
+            sessions,
+        })
+    }
+
+    /// Evict unpinned sessions in eviction order until there are no more
+    /// than `max_sessions` and their activities fit `max_size_mb`
+    pub(crate) fn evict(&self) -> Result<()> {
+        self.evict_except(None)
+    }
+
+    /// [`SqliteCache::evict`], never evicting `keep` (the session just written)
+    fn evict_except(&self, keep: Option<&str>) -> Result<()> {
+        let sessions = self.sessions_by_eviction_order()?;
+        let mut remaining = sessions.len();
+        let mut total_bytes: u64 = sessions.iter().map(|(_, size)| size).sum();
+
+        for (session_id, size) in &sessions {
+            let over_count = remaining > self.max_sessions;
+            let over_size = self.max_size_bytes.is_some_and(|max| total_bytes > max);
+            if !over_count && !over_size {
+                break;
+            }
+            if keep == Some(session_id.as_str()) || self.pinned.contains(session_id) {
+                continue;
+            }
+
+            self.conn
+                .execute(
+                    "DELETE FROM sessions WHERE session_id = ?1",
+                    params![session_id],
+                )
+                .context("Failed to evict old sessions")?;
+            total_bytes = total_bytes.saturating_sub(*size);
+            remaining -= 1;
+        }
+        Ok(())
+    }
+}
+
+/// Insert or replace activity rows for a session
+fn insert_activities(conn: &Connection, session_id: &str, activities: &[Activity]) -> Result<()> {
+    let mut stmt = conn.prepare(
+        "INSERT OR REPLACE INTO activities
+             (session_id, activity_id, create_time, activity_type, exit_code, has_bash, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+
+    for activity in activities {
+        let bash = activity
+            .artifacts
+            .iter()
+            .find_map(|artifact| artifact.bash_output.as_ref());
+        let data = serde_json::to_string(activity).context("Failed to serialize activity")?;
+
+        stmt.execute(params![
+            session_id,
+            activity.id,
+            normalize_time(&activity.create_time),
+            activity.activity_type(),
+            bash.and_then(|b| b.exit_code),
+            bash.is_some(),
+            data
+        ])?;
+    }
+    Ok(())
+}
+
+/// Fixed-width UTC timestamps, so SQL string comparison orders correctly
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+/// Normalize an RFC 3339 timestamp for storage, keeping unparsable values as-is
+fn normalize_time(value: &str) -> String {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| format_time(&t.with_timezone(&Utc)))
+        .unwrap_or_else(|_| value.to_string())
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(value)
+        .context("Invalid timestamp in cache database")?
+        .with_timezone(&Utc))
+}
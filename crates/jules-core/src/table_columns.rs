@@ -0,0 +1,212 @@
+//! Column selection for table output (`--columns id,state,title`).
+//!
+//! Each table has its own column set with a default that matches the
+//! original fixed layout. A selection comes from `--columns` or the
+//! `[display.columns]` config table (keyed by command name) and is applied
+//! process-wide, like the color mode; tables that don't know one of the
+//! selected columns warn and fall back to their default.
+
+use anyhow::Result;
+use std::sync::RwLock;
+
+/// A column of one of the tables
+pub trait TableColumn: Sized + Copy + 'static {
+    /// Table name for messages ("session", "activity", "source")
+    const TABLE: &'static str;
+    /// Column names, including aliases
+    const NAMES: &'static [(&'static str, Self)];
+    /// Columns shown without a selection
+    const DEFAULT: &'static [Self];
+
+    /// Column header
+    fn header(self) -> &'static str;
+}
+
+/// Columns of the sessions table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionColumn {
+    Title,
+    Id,
+    State,
+    Created,
+    Updated,
+    Source,
+    Branch,
+    Pr,
+}
+
+impl TableColumn for SessionColumn {
+    const TABLE: &'static str = "session";
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("title", Self::Title),
+        ("id", Self::Id),
+        ("state", Self::State),
+        ("created", Self::Created),
+        ("updated", Self::Updated),
+        ("source", Self::Source),
+        ("branch", Self::Branch),
+        ("pr", Self::Pr),
+    ];
+    const DEFAULT: &'static [Self] = &[Self::Title, Self::Id, Self::State, Self::Created, Self::Pr];
+
+    fn header(self) -> &'static str {
+        match self {
+            Self::Title => "Title",
+            Self::Id => "Session ID",
+            Self::State => "State",
+            Self::Created => "Created",
+            Self::Updated => "Updated",
+            Self::Source => "Source",
+            Self::Branch => "Branch",
+            Self::Pr => "PR",
+        }
+    }
+}
+
+/// Columns of the activities table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActivityColumn {
+    /// ID and type, stacked in one cell
+    Info,
+    Id,
+    Type,
+    Time,
+    Originator,
+    Content,
+}
+
+impl TableColumn for ActivityColumn {
+    const TABLE: &'static str = "activity";
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("info", Self::Info),
+        ("id", Self::Id),
+        ("type", Self::Type),
+        ("time", Self::Time),
+        ("created", Self::Time),
+        ("originator", Self::Originator),
+        ("content", Self::Content),
+    ];
+    const DEFAULT: &'static [Self] = &[Self::Info, Self::Time, Self::Content];
+
+    fn header(self) -> &'static str {
+        match self {
+            Self::Info => "Info",
+            Self::Id => "Activity ID",
+            Self::Type => "Type",
+            Self::Time => "Time",
+            Self::Originator => "Originator",
+            Self::Content => "Content",
+        }
+    }
+}
+
+/// Columns of the sources table
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceColumn {
+    Owner,
+    Repo,
+    Id,
+    Private,
+    Branches,
+    DefaultBranch,
+}
+
+impl TableColumn for SourceColumn {
+    const TABLE: &'static str = "source";
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("owner", Self::Owner),
+        ("repo", Self::Repo),
+        ("id", Self::Id),
+        ("private", Self::Private),
+        ("branches", Self::Branches),
+        ("default-branch", Self::DefaultBranch),
+        ("default_branch", Self::DefaultBranch),
+    ];
+    const DEFAULT: &'static [Self] = &[
+        Self::Owner,
+        Self::Repo,
+        Self::Id,
+        Self::Private,
+        Self::Branches,
+    ];
+
+    fn header(self) -> &'static str {
+        match self {
+            Self::Owner => "Owner",
+            Self::Repo => "Repository",
+            Self::Id => "Source ID",
+            Self::Private => "Private",
+            Self::Branches => "Branches",
+            Self::DefaultBranch => "Default Branch",
+        }
+    }
+}
+
+/// Parse a comma-separated column list for table `C`
+pub fn parse_columns<C: TableColumn>(spec: &str) -> Result<Vec<C>> {
+    let columns = spec
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            C::NAMES
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(name))
+                .map(|(_, column)| *column)
+                .ok_or_else(|| {
+                    let known: Vec<&str> = C::NAMES.iter().map(|(known, _)| *known).collect();
+                    anyhow::anyhow!(
+                        "Unknown {} column: {}. Valid options: {}",
+                        C::TABLE,
+                        name,
+                        known.join(", ")
+                    )
+                })
+        })
+        .collect::<Result<Vec<C>>>()?;
+
+    if columns.is_empty() {
+        anyhow::bail!("No columns given");
+    }
+    Ok(columns)
+}
+
+/// Check that `spec` names columns of at least one table
+pub fn validate_columns(spec: &str) -> Result<()> {
+    let session = parse_columns::<SessionColumn>(spec);
+    if session.is_ok()
+        || parse_columns::<ActivityColumn>(spec).is_ok()
+        || parse_columns::<SourceColumn>(spec).is_ok()
+    {
+        return Ok(());
+    }
+    session.map(|_| ())
+}
+
+/// Process-wide column selection used by the table printers
+static SELECTED_COLUMNS: RwLock<Option<String>> = RwLock::new(None);
+
+/// Select the columns of all subsequent tables (`None` for the defaults)
+pub fn set_table_columns(spec: Option<String>) {
+    if let Ok(mut selected) = SELECTED_COLUMNS.write() {
+        *selected = spec;
+    }
+}
+
+/// The selected columns of table `C`, or its default
+///
+/// A selection that doesn't fit this table is reported and ignored.
+pub fn selected_columns<C: TableColumn>() -> Vec<C> {
+    let spec = SELECTED_COLUMNS
+        .read()
+        .ok()
+        .and_then(|selected| selected.clone());
+    match spec.map(|spec| parse_columns::<C>(&spec)) {
+        Some(Ok(columns)) => columns,
+        Some(Err(e)) => {
+            eprintln!("Warning: {}; showing the default columns", e);
+            C::DEFAULT.to_vec()
+        }
+        None => C::DEFAULT.to_vec(),
+    }
+}
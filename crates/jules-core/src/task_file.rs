@@ -0,0 +1,87 @@
+//! Task files for creating sessions in bulk (`gules create --file`).
+//!
+//! A task file is a YAML (or, with a `.json` extension, JSON) list of tasks,
+//! or a mapping with a `tasks` list:
+//!
+//! ```yaml
+//! - prompt: Add unit tests for the parser
+//!   source: sources/github/owner/repo
+//!   branch: develop
+//!   title: Parser tests
+//! - prompt: |
+//!     Fix the flaky retry test.
+//!     It fails about once in twenty runs on CI.
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::Path;
+
+use crate::display::truncate_to_width;
+
+/// One session to create
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchTask {
+    pub prompt: String,
+    pub source: Option<String>,
+    pub branch: Option<String>,
+    pub title: Option<String>,
+    pub require_approval: Option<bool>,
+    pub automation_mode: Option<String>,
+}
+
+impl BatchTask {
+    /// Short label for progress lines: the title, else the prompt's first line
+    pub fn label(&self) -> String {
+        let label = match &self.title {
+            Some(title) => title.as_str(),
+            None => self.prompt.lines().next().unwrap_or_default(),
+        };
+        truncate_to_width(label, 50)
+    }
+}
+
+/// Parse a task file, JSON when `json` is set and YAML otherwise
+pub fn parse_tasks(text: &str, json: bool) -> Result<Vec<BatchTask>> {
+    let value: Value = if json {
+        serde_json::from_str(text).context("Invalid JSON")?
+    } else {
+        serde_norway::from_str(text).context("Invalid YAML")?
+    };
+    let items = match value {
+        Value::Array(items) => items,
+        Value::Object(mut map) if map.contains_key("tasks") => match map.remove("tasks") {
+            Some(Value::Array(items)) => items,
+            _ => anyhow::bail!("'tasks' must be a list"),
+        },
+        Value::Null => Vec::new(),
+        _ => anyhow::bail!("Expected a list of tasks, or a mapping with a 'tasks' list"),
+    };
+    if items.is_empty() {
+        anyhow::bail!("No tasks found");
+    }
+
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let task: BatchTask =
+                serde_json::from_value(item).with_context(|| format!("Task {}", i + 1))?;
+            if task.prompt.trim().is_empty() {
+                anyhow::bail!("Task {}: empty prompt", i + 1);
+            }
+            Ok(task)
+        })
+        .collect()
+}
+
+/// Read the tasks of `path`
+pub fn load_tasks(path: &str) -> Result<Vec<BatchTask>> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+    let json = Path::new(path)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    parse_tasks(&text, json).with_context(|| format!("Failed to parse {}", path))
+}
@@ -0,0 +1,106 @@
+//! Local queue of sessions waiting to be created (`gules queue`).
+//!
+//! Each entry holds a complete session request, resolved when it was
+//! queued (source, branch and template from the config in effect then), so
+//! `gules queue run` can submit it later from anywhere. The queue lives in
+//! the data directory rather than the cache, so clearing the cache never
+//! drops pending work.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use jules_rs::types::session::CreateSessionRequest;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::atomic_file::write_atomic;
+
+/// A session request waiting in the queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedTask {
+    /// Queue-local ID, increasing in the order tasks were added
+    pub id: u64,
+    pub added_at: DateTime<Utc>,
+    pub request: CreateSessionRequest,
+}
+
+impl QueuedTask {
+    /// The title, else the prompt's first line
+    pub fn label(&self) -> &str {
+        match &self.request.title {
+            Some(title) => title,
+            None => self.request.prompt.lines().next().unwrap_or_default(),
+        }
+    }
+}
+
+/// Pending session requests, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TaskQueue {
+    /// Last ID handed out, so IDs are never reused
+    #[serde(default)]
+    pub last_id: u64,
+    #[serde(default)]
+    pub tasks: Vec<QueuedTask>,
+}
+
+/// Get the queue file path
+pub fn get_queue_path() -> Result<PathBuf> {
+    let data_dir =
+        dirs::data_dir().ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
+    Ok(data_dir.join("gules").join("queue.json"))
+}
+
+impl TaskQueue {
+    /// Load the queue from the default location (empty if missing)
+    pub fn load() -> Result<Self> {
+        Self::load_from(&get_queue_path()?)
+    }
+
+    /// Load the queue from `path` (empty if missing)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).context("Failed to read task queue")?;
+        serde_json::from_str(&contents).context("Failed to parse task queue")
+    }
+
+    /// Save the queue to the default location
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&get_queue_path()?)
+    }
+
+    /// Save the queue to `path`
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create data directory")?;
+        }
+        let contents =
+            serde_json::to_string_pretty(self).context("Failed to serialize task queue")?;
+        write_atomic(path, contents).context("Failed to write task queue")?;
+        Ok(())
+    }
+
+    /// Append a request; returns its ID
+    pub fn push(&mut self, request: CreateSessionRequest) -> u64 {
+        self.last_id += 1;
+        self.tasks.push(QueuedTask {
+            id: self.last_id,
+            added_at: Utc::now(),
+            request,
+        });
+        self.last_id
+    }
+
+    /// Remove the task with `id`, if queued
+    pub fn remove(&mut self, id: u64) -> Option<QueuedTask> {
+        let index = self.tasks.iter().position(|task| task.id == id)?;
+        Some(self.tasks.remove(index))
+    }
+
+    /// The next task to submit
+    pub fn front(&self) -> Option<&QueuedTask> {
+        self.tasks.first()
+    }
+}
@@ -0,0 +1,107 @@
+//! User-defined output templates (`--template '{{id}} {{state}} {{title}}'`).
+//!
+//! Templates use the [minijinja](https://docs.rs/minijinja) (Jinja2) syntax
+//! and are rendered once per record, against the record's JSON (the same keys
+//! as `--format json`) plus a few convenience fields:
+//!
+//! - sessions: `branch`, `prUrl`
+//! - activities: `type`, `content`
+//!
+//! So `{{outputs[0].pullRequest.url}}`, `{{title|default("Untitled", true)}}`
+//! and `{% if prUrl %}...{% endif %}` all work. Missing values and nulls
+//! render as nothing.
+
+use anyhow::{Context, Result};
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use minijinja::{Environment, Output, State};
+use serde_json::Value;
+
+const NAME: &str = "template";
+
+/// A compiled output template
+#[derive(Debug, Clone)]
+pub struct Template {
+    env: Environment<'static>,
+}
+
+impl Template {
+    /// Compile a template, rejecting syntax errors
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut env = Environment::new();
+        env.set_formatter(format_value);
+        env.add_template_owned(NAME, template.to_string())
+            .with_context(|| format!("Invalid template: {}", template))?;
+        Ok(Self { env })
+    }
+
+    /// Render the template against a JSON record
+    pub fn render(&self, record: &Value) -> Result<String> {
+        let template = self.env.get_template(NAME)?;
+        Ok(template.render(record)?)
+    }
+}
+
+/// Like the default formatter, but nulls render as nothing rather than `none`
+fn format_value(
+    out: &mut Output,
+    state: &State,
+    value: &minijinja::Value,
+) -> std::result::Result<(), minijinja::Error> {
+    if value.is_none() {
+        return Ok(());
+    }
+    minijinja::escape_formatter(out, state, value)
+}
+
+/// Add convenience fields to a record without overriding API fields
+fn with_fields(
+    mut record: Value,
+    fields: impl IntoIterator<Item = (&'static str, Value)>,
+) -> Value {
+    if let Value::Object(map) = &mut record {
+        for (key, value) in fields {
+            map.entry(key).or_insert(value);
+        }
+    }
+    record
+}
+
+/// Template record of a session
+pub fn session_record(session: &Session) -> Value {
+    let branch = session
+        .source_context
+        .github_repo_context
+        .as_ref()
+        .map(|context| context.starting_branch.clone());
+    let pr_url = session
+        .outputs
+        .iter()
+        .find_map(|output| output.pull_request.as_ref()?.url.clone());
+    with_fields(
+        serde_json::to_value(session).unwrap_or_default(),
+        [("branch", branch.into()), ("prUrl", pr_url.into())],
+    )
+}
+
+/// Template record of an activity
+pub fn activity_record(activity: &Activity) -> Value {
+    with_fields(
+        serde_json::to_value(activity).unwrap_or_default(),
+        [
+            ("type", activity.activity_type().into()),
+            ("content", activity.content().into()),
+        ],
+    )
+}
+
+/// Print one rendered line per record
+pub fn print_templated(
+    template: &Template,
+    records: impl IntoIterator<Item = Value>,
+) -> Result<()> {
+    for record in records {
+        println!("{}", template.render(&record)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,145 @@
+//! Time specifications for command-line filters (`--older-than 30d`,
+//! `--since 2h`), and durations formatted the same way.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, Utc};
+
+/// Parse a duration like `30d`, `12h`, `90m`, `2w` or `1d12h`
+///
+/// Units are `s`, `m`, `h`, `d` and `w`; every number needs one.
+pub fn parse_duration(spec: &str) -> Result<Duration> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid duration: {}. Use a number and a unit (s, m, h, d, w), e.g. 30d or 12h",
+            spec
+        )
+    };
+
+    let spec = spec.trim();
+    if spec.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total = Duration::zero();
+    let mut rest = spec;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let amount: i64 = rest[..digits].parse().map_err(|_| invalid())?;
+        let mut chars = rest[digits..].chars();
+        let unit = match chars.next().map(|c| c.to_ascii_lowercase()) {
+            Some('s') => Duration::try_seconds(amount),
+            Some('m') => Duration::try_minutes(amount),
+            Some('h') => Duration::try_hours(amount),
+            Some('d') => Duration::try_days(amount),
+            Some('w') => Duration::try_weeks(amount),
+            _ => None,
+        }
+        .ok_or_else(invalid)?;
+        total = total.checked_add(&unit).ok_or_else(invalid)?;
+        rest = chars.as_str();
+    }
+    Ok(total)
+}
+
+/// Parse a point in time: RFC 3339 (`2025-01-15T10:00:00Z`), a date
+/// (`2025-01-15`, midnight UTC), `now`, or a duration ago (`2h`, `1d12h`)
+pub fn parse_time(spec: &str) -> Result<DateTime<Utc>> {
+    let spec = spec.trim();
+    if spec.eq_ignore_ascii_case("now") {
+        return Ok(Utc::now());
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(spec) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(spec, "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc());
+    }
+    match parse_duration(spec) {
+        Ok(ago) => Ok(Utc::now() - ago),
+        Err(_) => anyhow::bail!(
+            "Invalid time: {}. Use RFC 3339 (2025-01-15T10:00:00Z), a date (2025-01-15), \
+             'now', or a duration ago (2h, 30d)",
+            spec
+        ),
+    }
+}
+
+/// A `--since`/`--until` filter on creation times; either end may be open
+///
+/// `since` is inclusive and `until` exclusive, like the cache queries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeRange {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    /// Parse `--since` and `--until` (see [`parse_time`])
+    pub fn parse(since: Option<&str>, until: Option<&str>) -> Result<Self> {
+        let range = Self {
+            since: since.map(parse_time).transpose()?,
+            until: until.map(parse_time).transpose()?,
+        };
+        if let (Some(since), Some(until)) = (range.since, range.until) {
+            if since > until {
+                anyhow::bail!("--since must be before --until");
+            }
+        }
+        Ok(range)
+    }
+
+    /// Whether neither end is set
+    pub fn is_unbounded(&self) -> bool {
+        self.since.is_none() && self.until.is_none()
+    }
+
+    /// Whether an RFC 3339 timestamp is in the range; a timestamp that
+    /// doesn't parse is only in an unbounded range
+    pub fn contains(&self, timestamp: &str) -> bool {
+        if self.is_unbounded() {
+            return true;
+        }
+        let Ok(time) = DateTime::parse_from_rfc3339(timestamp) else {
+            return false;
+        };
+        let time = time.with_timezone(&Utc);
+        self.since.is_none_or(|since| time >= since) && self.until.is_none_or(|until| time < until)
+    }
+
+    /// `since` as an RFC 3339 timestamp, for cache queries
+    pub fn since_rfc3339(&self) -> Option<String> {
+        self.since
+            .map(|since| since.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+
+    /// `until` as an RFC 3339 timestamp, for cache queries
+    pub fn until_rfc3339(&self) -> Option<String> {
+        self.until
+            .map(|until| until.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+
+/// Format a duration with its two largest units, e.g. `2h 15m` or `3d 4h`
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    let units = [("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)];
+    let parts: Vec<String> = units
+        .iter()
+        .scan(seconds, |rest, (unit, size)| {
+            let amount = *rest / size;
+            *rest %= size;
+            Some((amount, unit))
+        })
+        .skip_while(|(amount, _)| *amount == 0)
+        .take(2)
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, unit)| format!("{}{}", amount, unit))
+        .collect();
+    if parts.is_empty() {
+        "0s".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
@@ -0,0 +1,84 @@
+//! Webhook notifications when a watched session finishes.
+//!
+//! `gules watch --webhook <URL>` (or `notifications.webhook_url` in the
+//! config) POSTs a [`WebhookPayload`] as JSON once the session reaches a
+//! terminal state, e.g. to a Slack relay or a CI job.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::{Session, State};
+use reqwest::Url;
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::markdown_output::pull_request_url;
+use crate::session_report::failure_reason;
+
+/// How long a webhook may take to answer
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON body POSTed to a webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    /// Always `session_finished`
+    pub event: &'static str,
+    pub session_id: String,
+    pub title: Option<String>,
+    pub state: Option<State>,
+    pub source: String,
+    /// The session in the Jules web app
+    pub url: Option<String>,
+    pub pr_url: Option<String>,
+    /// Why the session failed, if it did and said so
+    pub failure_reason: Option<String>,
+    pub timestamp: String,
+}
+
+impl WebhookPayload {
+    /// Payload for a finished `session`; `activities` supply the failure
+    /// reason
+    pub fn new(session: &Session, activities: &[Activity]) -> Self {
+        Self {
+            event: "session_finished",
+            session_id: session.id.clone(),
+            title: session.title.clone(),
+            state: session.state,
+            source: session.source_context.source.clone(),
+            url: session.url.clone(),
+            pr_url: pull_request_url(session).map(str::to_string),
+            failure_reason: match session.state {
+                Some(State::Failed) => failure_reason(activities),
+                _ => None,
+            },
+            timestamp: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Check that `url` is an http(s) URL
+pub fn parse_webhook_url(url: &str) -> Result<Url> {
+    let parsed = Url::parse(url).with_context(|| format!("Invalid webhook URL: {}", url))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        anyhow::bail!("Invalid webhook URL: {} (expected http or https)", url);
+    }
+    Ok(parsed)
+}
+
+/// POST `payload` to `url` as JSON; fails unless it answers with a 2xx
+pub async fn post_webhook(url: &str, payload: &WebhookPayload) -> Result<()> {
+    let response = reqwest::Client::new()
+        .post(parse_webhook_url(url)?)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(payload)?)
+        .timeout(WEBHOOK_TIMEOUT)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach webhook {}", url))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("Webhook {} answered {}", url, status);
+    }
+    Ok(())
+}
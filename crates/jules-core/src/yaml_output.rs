@@ -0,0 +1,155 @@
+//! YAML output for the formatted commands.
+//!
+//! Values are serialized to JSON first and then emitted as block-style YAML,
+//! so the keys and values match `--format json` exactly. Multi-line strings
+//! (messages, patches, command output) become literal blocks, which keeps
+//! them readable. Strings that a YAML parser would read as another type
+//! (numbers, booleans, dates, null) are quoted.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+const INDENT: &str = "  ";
+
+/// Serialize a value as a YAML document (without the `---` marker)
+pub fn to_yaml<T: Serialize>(value: &T) -> Result<String> {
+    let value = serde_json::to_value(value).context("Failed to serialize value")?;
+    let mut yaml = String::new();
+    for line in block_lines(&value) {
+        yaml.push_str(&line);
+        yaml.push('\n');
+    }
+    Ok(yaml)
+}
+
+/// Lines of a value in block context, without indentation
+fn block_lines(value: &Value) -> Vec<String> {
+    match value {
+        Value::Object(map) if !map.is_empty() => map
+            .iter()
+            .flat_map(|(key, value)| nested_lines(&format!("{}:", scalar(key)), value, INDENT))
+            .collect(),
+        Value::Array(items) if !items.is_empty() => items
+            .iter()
+            .flat_map(|item| nested_lines("-", item, INDENT))
+            .collect(),
+        Value::String(s) => match literal_block(s) {
+            Some((header, lines)) => std::iter::once(header).chain(lines).collect(),
+            None => vec![scalar(s)],
+        },
+        _ => vec![inline(value)],
+    }
+}
+
+/// Lines of `value` after a `key:` or `-` prefix
+fn nested_lines(prefix: &str, value: &Value, indent: &str) -> Vec<String> {
+    let indented =
+        |lines: Vec<String>| lines.into_iter().map(move |line| indent_line(indent, line));
+
+    match value {
+        // Mappings in a sequence start on the dash line
+        Value::Object(map) if !map.is_empty() && prefix == "-" => {
+            let mut lines = block_lines(value).into_iter();
+            let first = lines.next().unwrap_or_default();
+            std::iter::once(format!("- {}", first))
+                .chain(lines.map(|line| indent_line(indent, line)))
+                .collect()
+        }
+        Value::Object(map) if !map.is_empty() => std::iter::once(prefix.to_string())
+            .chain(indented(block_lines(value)))
+            .collect(),
+        Value::Array(items) if !items.is_empty() => std::iter::once(prefix.to_string())
+            .chain(indented(block_lines(value)))
+            .collect(),
+        Value::String(s) => match literal_block(s) {
+            Some((header, lines)) => std::iter::once(format!("{} {}", prefix, header))
+                .chain(lines.into_iter().map(|line| indent_line(indent, line)))
+                .collect(),
+            None => vec![format!("{} {}", prefix, scalar(s))],
+        },
+        _ => vec![format!("{} {}", prefix, inline(value))],
+    }
+}
+
+fn indent_line(indent: &str, line: String) -> String {
+    if line.is_empty() {
+        line
+    } else {
+        format!("{}{}", indent, line)
+    }
+}
+
+/// A scalar or empty collection on one line
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        // YAML 1.1 parsers need a signed exponent to read `1e300` as a float
+        Value::Number(n) if !n.to_string().contains("e-") => n.to_string().replace('e', "e+"),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => scalar(s),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+    }
+}
+
+/// Header and content lines of a literal block scalar (`|`), if `s` can be
+/// written as one
+fn literal_block(s: &str) -> Option<(String, Vec<String>)> {
+    let content = s.trim_end_matches('\n');
+    let trailing_newlines = s.len() - content.len();
+    let first_line = content.lines().find(|line| !line.is_empty())?;
+
+    let representable = s.contains('\n')
+        && !first_line.starts_with([' ', '\t'])
+        && !s.chars().any(|c| c.is_control() && c != '\n' && c != '\t');
+    if !representable {
+        return None;
+    }
+
+    let chomping = match trailing_newlines {
+        0 => "|-",
+        1 => "|",
+        _ => "|+",
+    };
+    let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+    lines.extend(std::iter::repeat_n(
+        String::new(),
+        trailing_newlines.saturating_sub(1),
+    ));
+    Some((chomping.to_string(), lines))
+}
+
+/// A string as a plain scalar if that reads back as the same string,
+/// double-quoted otherwise
+fn scalar(s: &str) -> String {
+    if is_plain_safe(s) {
+        s.to_string()
+    } else {
+        // JSON string escapes are valid in YAML double-quoted scalars
+        serde_json::to_string(s).expect("strings always serialize")
+    }
+}
+
+fn is_plain_safe(s: &str) -> bool {
+    const RESERVED: &[&str] = &[
+        "null", "~", "true", "false", "yes", "no", "on", "off", "y", "n", ".inf", "-.inf", ".nan",
+    ];
+    let Some(first) = s.chars().next() else {
+        return false;
+    };
+
+    // Numbers, dates and versions are read as other types by some parsers
+    let numeric_start = first.is_ascii_digit()
+        || (matches!(first, '-' | '+' | '.') && s[1..].starts_with(|c: char| c.is_ascii_digit()));
+
+    !numeric_start
+        && !RESERVED.contains(&s.to_lowercase().as_str())
+        && !"-?:,[]{}#&*!|>'\"%@`".contains(first)
+        && s.trim() == s
+        && !s.ends_with(':')
+        && !s.contains(": ")
+        && !s.contains(" #")
+        && !s.chars().any(char::is_control)
+}
@@ -2,7 +2,12 @@
 
 use chrono::Utc;
 use jules_core::activity_cache::*;
-use jules_rs::types::activity::Activity;
+use jules_core::bash_index::index_entries;
+use jules_core::cache_crypto::{is_encrypted, CacheKey};
+use jules_core::{ActivityQuery, ActivityStore, CacheConfig, FileStore};
+use jules_rs::types::activity::{Activity, Artifact, BashOutput};
+use std::fs;
+use tempfile::TempDir;
 
 /// Helper to create a test activity
 fn create_test_activity(id: &str, session_failed: bool) -> Activity {
@@ -29,11 +34,78 @@ fn create_test_activity(id: &str, session_failed: bool) -> Activity {
     }
 }
 
+/// An activity with a bash output exiting with `exit_code`, if any
+fn bash_activity(id: &str, exit_code: Option<i32>) -> Activity {
+    let mut activity = create_test_activity(id, false);
+    activity.create_time = format!("2024-01-01T00:00:0{}Z", id);
+    activity.artifacts = exit_code
+        .map(|code| {
+            vec![Artifact {
+                change_set: None,
+                media: None,
+                bash_output: Some(BashOutput {
+                    command: Some(format!("cargo test {}", id)),
+                    output: Some("output".to_string()),
+                    exit_code: Some(code),
+                }),
+            }]
+        })
+        .unwrap_or_default();
+    activity
+}
+
+fn session_cache(session_id: &str, activities: Vec<Activity>) -> SessionCache {
+    SessionCache {
+        session_id: session_id.to_string(),
+        activities,
+        last_page_token: None,
+        last_updated: Utc::now(),
+        created_at: Utc::now(),
+    }
+}
+
+/// A cache in a fresh temporary directory
+fn temp_cache() -> (TempDir, CacheDir) {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = CacheDir::new(dir.path());
+    (dir, cache)
+}
+
+fn save(cache: &CacheDir, session_id: &str) {
+    cache
+        .save_session_cache(&session_cache(session_id, vec![]))
+        .unwrap();
+}
+
 #[test]
 fn test_cache_config_defaults() {
     let config = ActivityCacheConfig::default();
     assert!(config.enabled);
     assert_eq!(config.max_sessions, 50);
+    assert_eq!(config.ttl_seconds, DEFAULT_CACHE_TTL_SECONDS);
+}
+
+#[test]
+fn test_cache_ttl_only_expires_running_sessions() {
+    let mut cache = SessionCache {
+        session_id: "123".to_string(),
+        activities: vec![create_test_activity("1", false)],
+        last_page_token: None,
+        last_updated: Utc::now() - chrono::Duration::seconds(600),
+        created_at: Utc::now() - chrono::Duration::seconds(600),
+    };
+
+    // Running session older than the TTL must be refreshed
+    assert!(!cache.is_fresh(300));
+    assert!(cache.is_fresh(3600));
+
+    // Terminal sessions are trusted regardless of age
+    cache.activities.push(create_test_activity("2", true));
+    assert!(cache.is_terminal());
+    assert!(cache.is_fresh(0));
+
+    // TTL of 0 always refreshes running sessions
+    assert!(!is_cache_fresh(Utc::now(), false, 0));
 }
 
 #[test]
@@ -80,8 +152,386 @@ fn test_merge_activities_sorting() {
     assert_eq!(merged[2].id, "1"); // Oldest
 }
 
-// Note: File I/O tests (save, load, clear, etc.) are skipped
-// because they require mocking the cache directory or actual filesystem access.
-// These are better tested through integration tests or manual testing.
-//
-// The core logic (merge_activities, deduplication, sorting) is tested above.
+#[test]
+fn test_cache_key_normalizes_resource_names() {
+    assert_eq!(cache_key("123").unwrap(), "123");
+    assert_eq!(cache_key("sessions/123").unwrap(), "123");
+
+    // Never allow a key that could escape the cache directory
+    assert!(cache_key("../123").is_err());
+    assert!(cache_key("sessions/").is_err());
+}
+
+#[test]
+fn test_sessions_are_compressed_by_default() {
+    let (_dir, cache) = temp_cache();
+    save(&cache, "1");
+
+    assert!(cache.path().join("1.json.zst").exists());
+    assert!(!cache.path().join("1.json").exists());
+    assert_eq!(
+        cache.load_session_cache("1").unwrap().unwrap().session_id,
+        "1"
+    );
+}
+
+#[test]
+fn test_uncompressed_files_are_migrated_on_read() {
+    let (_dir, cache) = temp_cache();
+    fs::create_dir_all(cache.path()).unwrap();
+    let legacy = serde_json::to_string(&session_cache("2", vec![])).unwrap();
+    fs::write(cache.path().join("2.json"), legacy).unwrap();
+
+    assert!(cache.load_session_cache("2").unwrap().is_some());
+    assert!(cache.path().join("2.json.zst").exists());
+    assert!(!cache.path().join("2.json").exists());
+}
+
+#[test]
+fn test_disabling_compression_converts_files() {
+    let (_dir, cache) = temp_cache();
+    save(&cache, "1");
+
+    let config = CacheConfig {
+        compress: false,
+        ..CacheConfig::default()
+    };
+    cache.apply_cache_config(&config).unwrap();
+    assert!(cache.path().join("1.json").exists());
+    assert!(!cache.path().join("1.json.zst").exists());
+}
+
+#[test]
+fn test_enabling_encryption_seals_existing_files() {
+    let (_dir, cache) = temp_cache();
+    let cache = cache.with_key(CacheKey::from_passphrase("test passphrase"));
+    save(&cache, "1");
+
+    let config = CacheConfig {
+        encrypt: true,
+        ..CacheConfig::default()
+    };
+    cache.apply_cache_config(&config).unwrap();
+    let raw = fs::read(cache.path().join("1.json.zst")).unwrap();
+    assert!(is_encrypted(&raw));
+    assert_eq!(
+        cache.load_session_cache("1").unwrap().unwrap().session_id,
+        "1"
+    );
+
+    let stats = cache.get_cache_stats().unwrap();
+    assert!(stats.encryption_enabled);
+    assert_eq!(stats.encrypted_sessions, 1);
+
+    cache.delete_session_cache("1").unwrap();
+    assert!(!cache.path().join("1.json.zst").exists());
+}
+
+#[test]
+fn test_lru_eviction_keeps_the_recently_read_session() {
+    let (_dir, cache) = temp_cache();
+    let config = CacheConfig {
+        max_sessions: 2,
+        eviction: EvictionPolicy::Lru,
+        ..CacheConfig::default()
+    };
+    cache.apply_cache_config(&config).unwrap();
+
+    save(&cache, "1");
+    save(&cache, "2");
+    assert!(cache.load_session_cache("1").unwrap().is_some());
+    save(&cache, "3");
+    assert_eq!(cache.list_cached_sessions().unwrap(), ["1", "3"]);
+    assert!(!cache.path().join("2.json.zst").exists());
+}
+
+#[test]
+fn test_size_eviction_keeps_the_saved_session() {
+    let (_dir, cache) = temp_cache();
+    save(&cache, "1");
+
+    let config = CacheConfig {
+        max_size_mb: Some(0),
+        ..CacheConfig::default()
+    };
+    cache.apply_cache_config(&config).unwrap();
+    save(&cache, "2");
+    assert_eq!(cache.list_cached_sessions().unwrap(), ["2"]);
+}
+
+#[test]
+fn test_pinned_sessions_survive_eviction() {
+    let (_dir, cache) = temp_cache();
+    let config = CacheConfig {
+        max_sessions: 1,
+        ..CacheConfig::default()
+    };
+    cache.apply_cache_config(&config).unwrap();
+    save(&cache, "1");
+
+    assert!(cache.pin_session("sessions/1").unwrap());
+    assert!(!cache.pin_session("1").unwrap());
+    save(&cache, "2");
+    assert_eq!(cache.list_cached_sessions().unwrap(), ["1", "2"]);
+
+    assert!(cache.unpin_session("1").unwrap());
+    assert!(!cache.unpin_session("1").unwrap());
+    save(&cache, "3");
+    assert_eq!(cache.list_cached_sessions().unwrap(), ["3"]);
+}
+
+#[test]
+fn test_verify_finds_and_repairs_problems() {
+    let (_dir, cache) = temp_cache();
+    save(&cache, "1");
+    save(&cache, "2");
+    save(&cache, "3");
+    assert!(cache.verify_cache(false).unwrap().is_healthy());
+
+    // Half-written file, missing file, and a file unknown to the metadata
+    fs::write(cache.path().join("1.json.zst"), b"\x28\xb5\x2f").unwrap();
+    fs::remove_file(cache.path().join("2.json.zst")).unwrap();
+    let orphan = serde_json::to_string(&session_cache("4", vec![])).unwrap();
+    fs::write(cache.path().join("4.json"), orphan).unwrap();
+
+    let report = cache.verify_cache(false).unwrap();
+    assert!(!report.repaired);
+    assert_eq!(report.checked_files, 3);
+    assert_eq!(report.missing_files, ["2"]);
+    assert_eq!(report.orphaned_files, ["4"]);
+    assert_eq!(report.corrupt_files.len(), 1);
+    assert_eq!(report.corrupt_files[0].session_id, "1");
+    assert!(cache.path().join("1.json.zst").exists());
+
+    let report = cache.verify_cache(true).unwrap();
+    assert!(report.repaired);
+    assert!(!cache.path().join("1.json.zst").exists());
+    assert_eq!(cache.list_cached_sessions().unwrap(), ["3", "4"]);
+    assert!(cache.verify_cache(false).unwrap().is_healthy());
+}
+
+#[test]
+fn test_verify_rebuilds_corrupt_metadata() {
+    let (_dir, cache) = temp_cache();
+    save(&cache, "1");
+    save(&cache, "2");
+
+    fs::write(cache.path().join("metadata.json"), "{ not json").unwrap();
+    let report = cache.verify_cache(true).unwrap();
+    assert!(report.metadata_corrupt);
+    assert_eq!(report.orphaned_files, ["1", "2"]);
+    assert_eq!(cache.list_cached_sessions().unwrap(), ["1", "2"]);
+}
+
+#[test]
+fn test_verify_removes_stale_temp_files() {
+    let (_dir, cache) = temp_cache();
+    save(&cache, "1");
+
+    // Left by an interrupted atomic write
+    let temp = cache.path().join(".1.json.zst.4242.tmp");
+    fs::write(&temp, b"partial").unwrap();
+    let report = cache.verify_cache(true).unwrap();
+    assert_eq!(report.stale_temp_files, [".1.json.zst.4242.tmp"]);
+    assert!(!temp.exists());
+    assert!(cache.verify_cache(false).unwrap().is_healthy());
+}
+
+#[test]
+fn test_index_entries() {
+    let entries = index_entries(&[bash_activity("2", Some(1)), bash_activity("1", None)]);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].activity_id, "2");
+    assert_eq!(entries[0].command.as_deref(), Some("cargo test 2"));
+    assert!(entries[0].failed());
+}
+
+#[test]
+fn test_file_store_uses_bash_index() {
+    let (_dir, cache) = temp_cache();
+    let mut store = FileStore::new(cache.clone());
+    store
+        .save_session(&session_cache(
+            "1",
+            vec![
+                bash_activity("3", Some(0)),
+                bash_activity("2", Some(101)),
+                bash_activity("1", None),
+            ],
+        ))
+        .unwrap();
+    store
+        .save_session(&session_cache("2", vec![bash_activity("4", Some(0))]))
+        .unwrap();
+
+    let index = cache.load_bash_index().unwrap();
+    assert_eq!(index.entries("1").len(), 2);
+    assert_eq!(index.entries("2").len(), 1);
+    let failed = index.failed();
+    assert_eq!(failed.len(), 1);
+    assert_eq!((failed[0].0, failed[0].1.exit_code), ("1", Some(101)));
+
+    let query = ActivityQuery {
+        failed_only: true,
+        ..Default::default()
+    };
+    let activities = store.query(&query).unwrap();
+    let ids: Vec<&str> = activities.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["2"]);
+
+    // Sessions missing from the index are still scanned
+    fs::remove_file(cache.path().join("bash_index.json")).unwrap();
+    assert_eq!(store.query(&query).unwrap().len(), 1);
+    assert!(cache.verify_cache(false).unwrap().is_healthy());
+}
+
+#[test]
+fn test_deleting_a_session_drops_its_index_entries() {
+    let (_dir, cache) = temp_cache();
+    cache
+        .save_session_cache(&session_cache("1", vec![bash_activity("2", Some(101))]))
+        .unwrap();
+    cache
+        .save_session_cache(&session_cache("2", vec![bash_activity("4", Some(2))]))
+        .unwrap();
+
+    cache.delete_session_cache("1").unwrap();
+    let index = cache.load_bash_index().unwrap();
+    assert!(!index.contains("1"));
+    assert_eq!(index.failed().len(), 1);
+}
+
+const LEGACY_SESSION: &str = r#"{
+  "session_id": "1",
+  "activities": [],
+  "last_page_token": null,
+  "last_updated": "2024-01-01T00:00:00Z",
+  "created_at": "2024-01-01T00:00:00Z"
+}"#;
+
+const LEGACY_METADATA: &str = r#"{
+  "access_order": ["1"],
+  "config": { "enabled": true, "max_sessions": 50, "compress": false }
+}"#;
+
+fn stored_version(contents: &str) -> Option<u64> {
+    serde_json::from_str::<serde_json::Value>(contents).unwrap()["version"].as_u64()
+}
+
+/// A cache holding an unversioned metadata file and session "1"
+fn legacy_cache() -> (TempDir, CacheDir) {
+    let (dir, cache) = temp_cache();
+    fs::create_dir_all(cache.path()).unwrap();
+    fs::write(cache.path().join("metadata.json"), LEGACY_METADATA).unwrap();
+    fs::write(cache.path().join("1.json"), LEGACY_SESSION).unwrap();
+    (dir, cache)
+}
+
+#[test]
+fn test_unversioned_caches_are_upgraded() {
+    let (_dir, cache) = legacy_cache();
+
+    let metadata = cache.load_metadata().unwrap();
+    assert_eq!(metadata.version, CACHE_FORMAT_VERSION);
+    assert_eq!(metadata.access_order, ["1"]);
+    let saved = fs::read_to_string(cache.path().join("metadata.json")).unwrap();
+    assert_eq!(stored_version(&saved), Some(CACHE_FORMAT_VERSION as u64));
+
+    assert!(cache.load_session_cache("1").unwrap().is_some());
+    let saved = fs::read_to_string(cache.path().join("1.json")).unwrap();
+    assert_eq!(stored_version(&saved), Some(CACHE_FORMAT_VERSION as u64));
+}
+
+#[test]
+fn test_files_from_a_newer_version_are_rejected() {
+    let (_dir, cache) = legacy_cache();
+
+    let newer = LEGACY_SESSION.replacen('{', r#"{ "version": 99,"#, 1);
+    fs::write(cache.path().join("1.json"), newer).unwrap();
+    let error = format!("{:#}", cache.peek_session_cache("1").unwrap_err());
+    assert!(error.contains("newer version"), "{}", error);
+
+    let newer = LEGACY_METADATA.replacen('{', r#"{ "version": 99,"#, 1);
+    fs::write(cache.path().join("metadata.json"), newer).unwrap();
+    let error = format!("{:#}", cache.load_metadata().unwrap_err());
+    assert!(error.contains("newer version"), "{}", error);
+}
+
+#[test]
+fn test_old_caches_that_cannot_be_upgraded_are_cleared() {
+    let (_dir, cache) = legacy_cache();
+    fs::write(cache.path().join("metadata.json"), r#"{"access_order": 5}"#).unwrap();
+
+    let metadata = cache.load_metadata().unwrap();
+    assert!(metadata.access_order.is_empty());
+    assert!(!cache.path().join("1.json").exists());
+}
+
+#[test]
+fn test_corrupt_metadata_is_left_for_repair() {
+    let (_dir, cache) = legacy_cache();
+    fs::write(cache.path().join("metadata.json"), "{").unwrap();
+
+    assert!(cache.load_metadata().is_err());
+    assert!(cache.verify_cache(true).unwrap().repaired);
+}
+
+#[test]
+fn test_cache_access_counters() {
+    let (_dir, cache) = temp_cache();
+    save(&cache, "1");
+    save(&cache, "2");
+
+    // 120 activities take three pages of 50 to fetch in full
+    cache
+        .record_cache_access("1", CacheAccess::Miss, 120)
+        .unwrap();
+    cache
+        .record_cache_access("sessions/1", CacheAccess::Hit, 120)
+        .unwrap();
+    cache
+        .record_cache_access(
+            "1",
+            CacheAccess::IncrementalUpdate { new_activities: 4 },
+            124,
+        )
+        .unwrap();
+    cache.record_cache_access("2", CacheAccess::Hit, 0).unwrap();
+
+    let metrics = cache.cache_metrics().unwrap();
+    let session = metrics.sessions["1"];
+    assert_eq!(
+        (session.hits, session.misses, session.incremental_updates),
+        (1, 1, 1)
+    );
+    assert_eq!(session.api_calls_saved, 3 + 2);
+    assert_eq!(metrics.total.hits, 2);
+    assert_eq!(metrics.total.api_calls_saved, 6);
+    assert_eq!(metrics.total.hit_rate(), Some(0.75));
+    assert_eq!(cache.get_cache_stats().unwrap().metrics, metrics);
+}
+
+#[test]
+fn test_session_counters_go_with_the_session() {
+    let (_dir, cache) = temp_cache();
+    save(&cache, "1");
+    save(&cache, "2");
+    cache
+        .record_cache_access("1", CacheAccess::Hit, 10)
+        .unwrap();
+    cache
+        .record_cache_access("2", CacheAccess::Hit, 10)
+        .unwrap();
+
+    // Lifetime totals stay
+    cache.delete_session_cache("1").unwrap();
+    let metrics = cache.cache_metrics().unwrap();
+    assert!(!metrics.sessions.contains_key("1"));
+    assert_eq!(metrics.total.reads(), 2);
+
+    cache.retain_session_metrics(&[]).unwrap();
+    assert!(cache.cache_metrics().unwrap().sessions.is_empty());
+
+    cache.clear_all_cache().unwrap();
+    assert_eq!(cache.cache_metrics().unwrap().total.reads(), 2);
+}
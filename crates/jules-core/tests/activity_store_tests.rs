@@ -0,0 +1,133 @@
+//! Tests for the backend-agnostic activity store interface.
+
+use chrono::{Duration, Utc};
+use jules_core::activity_store::matches_query;
+use jules_core::{ActivityQuery, ActivityStore, MemoryStore};
+use jules_rs::types::activity::{Activity, Artifact, BashOutput};
+
+fn activity(id: &str, seconds_ago: i64) -> Activity {
+    Activity {
+        name: format!("sessions/s1/activities/{}", id),
+        id: id.to_string(),
+        description: None,
+        create_time: (Utc::now() - Duration::seconds(seconds_ago)).to_rfc3339(),
+        originator: "agent".to_string(),
+        artifacts: vec![],
+        agent_messaged: None,
+        user_messaged: None,
+        plan_generated: None,
+        plan_approved: None,
+        progress_updated: None,
+        session_completed: None,
+        session_failed: None,
+    }
+}
+
+fn bash_activity(id: &str, seconds_ago: i64, exit_code: i32) -> Activity {
+    let mut activity = activity(id, seconds_ago);
+    activity.artifacts = vec![Artifact {
+        change_set: None,
+        media: None,
+        bash_output: Some(BashOutput {
+            command: Some("cargo test".to_string()),
+            output: Some("ok".to_string()),
+            exit_code: Some(exit_code),
+        }),
+    }];
+    activity
+}
+
+#[test]
+fn test_memory_store_merges_by_id() {
+    let mut store = MemoryStore::new(10);
+    store
+        .merge_activities("sessions/s1", vec![activity("2", 10), activity("1", 20)])
+        .unwrap();
+    store
+        .merge_activities("s1", vec![activity("3", 5), activity("2", 10)])
+        .unwrap();
+
+    let cache = store.load_session("s1").unwrap().unwrap();
+    let ids: Vec<&str> = cache.activities.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["3", "2", "1"]);
+    assert_eq!(store.stats().unwrap().total_activities, 3);
+    assert!(store.is_fresh("s1", 60).unwrap());
+    assert!(!store.is_fresh("s2", 60).unwrap());
+}
+
+#[test]
+fn test_memory_store_evicts_oldest() {
+    let mut store = MemoryStore::new(2);
+    for id in ["a", "b", "c"] {
+        store.merge_activities(id, vec![activity("1", 1)]).unwrap();
+    }
+
+    assert_eq!(store.list_sessions().unwrap(), ["b", "c"]);
+    assert!(store.load_session("a").unwrap().is_none());
+
+    store.delete_session("b").unwrap();
+    assert_eq!(store.list_sessions().unwrap(), ["c"]);
+    store.clear().unwrap();
+    assert_eq!(store.stats().unwrap().total_sessions, 0);
+}
+
+#[test]
+fn test_default_query_filters_and_sorts() {
+    let mut store = MemoryStore::new(10);
+    store
+        .merge_activities("s1", vec![bash_activity("1", 30, 1), activity("2", 20)])
+        .unwrap();
+    store
+        .merge_activities("s2", vec![bash_activity("3", 10, 0)])
+        .unwrap();
+
+    let all = store.query(&ActivityQuery::default()).unwrap();
+    let ids: Vec<&str> = all.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["3", "2", "1"]);
+
+    let failed = store
+        .query(&ActivityQuery {
+            exit_code: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].id, "1");
+
+    let session = store
+        .query(&ActivityQuery {
+            session_id: Some("sessions/s1".to_string()),
+            has_bash_output: true,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(session.len(), 1);
+
+    let limited = store
+        .query(&ActivityQuery {
+            limit: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(limited[0].id, "3");
+}
+
+#[test]
+fn test_matches_query_time_range() {
+    let recent = activity("1", 10);
+    let since = (Utc::now() - Duration::seconds(60)).to_rfc3339();
+    let until = (Utc::now() - Duration::seconds(30)).to_rfc3339();
+
+    let query = ActivityQuery {
+        since: Some(since.clone()),
+        ..Default::default()
+    };
+    assert!(matches_query(&recent, &query));
+
+    let query = ActivityQuery {
+        since: Some(since),
+        until: Some(until),
+        ..Default::default()
+    };
+    assert!(!matches_query(&recent, &query));
+}
@@ -0,0 +1,34 @@
+use jules_core::atomic_file::{is_temp_file, write_atomic};
+use std::fs;
+
+#[test]
+fn test_write_atomic_replaces_contents() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("metadata.json");
+
+    write_atomic(&path, "first").unwrap();
+    write_atomic(&path, b"second").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+
+    // No temporary files are left behind
+    let names: Vec<_> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(names, ["metadata.json"]);
+}
+
+#[test]
+fn test_write_atomic_reports_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("missing").join("file.json");
+    assert!(write_atomic(&path, "data").is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_is_temp_file() {
+    assert!(is_temp_file(".123.json.zst.4242.tmp"));
+    assert!(!is_temp_file("123.json.zst"));
+    assert!(!is_temp_file(".lock"));
+}
@@ -0,0 +1,92 @@
+//! Tests for cache export/import archives.
+
+use chrono::{Duration, Utc};
+use jules_core::activity_cache::SessionCache;
+use jules_core::cache_archive::{merge_session_caches, CacheArchive, ARCHIVE_FORMAT_VERSION};
+use jules_core::session_cache::CachedSession;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+
+fn activity(id: &str) -> Activity {
+    serde_json::from_value(serde_json::json!({
+        "name": format!("sessions/s1/activities/{}", id),
+        "id": id,
+        "createTime": format!("2024-01-01T00:00:0{}Z", id),
+        "originator": "agent",
+        "agentMessaged": { "agentMessage": format!("message {}", id) }
+    }))
+    .unwrap()
+}
+
+fn session_cache(session_id: &str, ids: &[&str], minutes_ago: i64) -> SessionCache {
+    let updated = Utc::now() - Duration::minutes(minutes_ago);
+    SessionCache {
+        session_id: session_id.to_string(),
+        activities: ids.iter().map(|id| activity(id)).collect(),
+        last_page_token: Some(format!("token-{}", minutes_ago)),
+        last_updated: updated,
+        created_at: updated,
+    }
+}
+
+fn cached_session(id: &str) -> CachedSession {
+    let session: Session = serde_json::from_value(serde_json::json!({
+        "name": format!("sessions/{}", id),
+        "id": id,
+        "prompt": "Fix the bug",
+        "state": "COMPLETED",
+        "sourceContext": { "source": "sources/github/owner/repo" }
+    }))
+    .unwrap();
+    CachedSession {
+        session,
+        refreshed_at: Utc::now(),
+    }
+}
+
+#[test]
+fn test_archive_roundtrip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cache.tar.zst");
+
+    let archive = CacheArchive::new(
+        vec![
+            session_cache("s1", &["1", "2"], 0),
+            session_cache("s2", &["3"], 0),
+        ],
+        vec![cached_session("s1")],
+    );
+    archive.write(&path).unwrap();
+
+    let read = CacheArchive::read(&path).unwrap();
+    assert_eq!(read.manifest.format_version, ARCHIVE_FORMAT_VERSION);
+    assert_eq!(read.manifest.session_ids, ["s1", "s2"]);
+    assert_eq!(read.caches.len(), 2);
+    assert_eq!(read.caches[0].activities.len(), 2);
+    assert_eq!(read.sessions.len(), 1);
+    assert_eq!(read.sessions[0].session.id, "s1");
+}
+
+#[test]
+fn test_read_rejects_non_archives() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cache.tar.zst");
+    std::fs::write(&path, b"not an archive").unwrap();
+    assert!(CacheArchive::read(&path).is_err());
+}
+
+#[test]
+fn test_merge_keeps_newest_state_and_all_activities() {
+    let local = session_cache("s1", &["1", "2"], 10);
+    let imported = session_cache("s1", &["2", "3"], 1);
+
+    let merged = merge_session_caches(local.clone(), imported.clone());
+    let ids: Vec<&str> = merged.activities.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["3", "2", "1"]);
+    assert_eq!(merged.last_page_token, imported.last_page_token);
+    assert_eq!(merged.created_at, local.created_at);
+
+    // Order of arguments does not matter
+    let merged = merge_session_caches(imported.clone(), local);
+    assert_eq!(merged.last_page_token, imported.last_page_token);
+}
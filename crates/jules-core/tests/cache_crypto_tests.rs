@@ -0,0 +1,26 @@
+//! Tests for cache encryption at rest.
+
+use jules_core::cache_crypto::{is_encrypted, CacheKey};
+
+#[test]
+fn test_seal_and_open_roundtrip() {
+    let key = CacheKey::from_passphrase("correct horse battery staple");
+    let sealed = key.seal(b"export TOKEN=secret").unwrap();
+
+    assert!(is_encrypted(&sealed));
+    assert!(!sealed.windows(6).any(|w| w == b"secret"));
+    assert_eq!(key.open(&sealed).unwrap(), b"export TOKEN=secret");
+}
+
+#[test]
+fn test_open_rejects_wrong_key_and_plain_data() {
+    let sealed = CacheKey::from_passphrase("one").seal(b"data").unwrap();
+
+    assert!(CacheKey::from_passphrase("two").open(&sealed).is_err());
+    assert!(CacheKey::from_passphrase("one")
+        .open(b"{\"plain\": true}")
+        .is_err());
+    assert!(CacheKey::from_passphrase("one")
+        .open(&sealed[..12])
+        .is_err());
+}
@@ -0,0 +1,76 @@
+//! Tests for the daemon event log.
+
+use chrono::{Duration, Utc};
+use jules_core::cache_events::*;
+
+fn new_activities(session_id: &str, ids: &[&str]) -> CacheEvent {
+    CacheEvent::new(
+        session_id,
+        CacheEventKind::NewActivities {
+            activity_ids: ids.iter().map(|id| id.to_string()).collect(),
+        },
+    )
+}
+
+#[test]
+fn test_append_and_read_events() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.jsonl");
+    assert!(read_events_from(&path, None).unwrap().is_empty());
+
+    let first = new_activities("1", &["a"]);
+    append_events_to(&path, std::slice::from_ref(&first)).unwrap();
+    let cutoff = Utc::now();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+
+    let second = CacheEvent::new(
+        "2",
+        CacheEventKind::StateChanged {
+            from: Some("In Progress".to_string()),
+            to: "Completed".to_string(),
+        },
+    );
+    append_events_to(&path, std::slice::from_ref(&second)).unwrap();
+
+    assert_eq!(
+        read_events_from(&path, None).unwrap(),
+        [first, second.clone()]
+    );
+    assert_eq!(read_events_from(&path, Some(cutoff)).unwrap(), [second]);
+
+    // Events are stored as tagged JSON lines
+    let line = std::fs::read_to_string(&path).unwrap();
+    assert!(line.contains(r#""event":"new_activities""#));
+}
+
+#[test]
+fn test_event_log_is_trimmed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.jsonl");
+
+    let events: Vec<CacheEvent> = (0..MAX_EVENTS * 2)
+        .map(|i| new_activities(&i.to_string(), &["a"]))
+        .collect();
+    append_events_to(&path, &events).unwrap();
+
+    let stored = read_events_from(&path, None).unwrap();
+    assert_eq!(stored.len(), MAX_EVENTS);
+    assert_eq!(
+        stored.last().unwrap().session_id,
+        (MAX_EVENTS * 2 - 1).to_string()
+    );
+}
+
+#[test]
+fn test_daemon_liveness() {
+    let mut status = DaemonStatus {
+        pid: 1,
+        started_at: Utc::now() - Duration::minutes(10),
+        last_refresh: Some(Utc::now()),
+        interval_seconds: 60,
+    };
+    assert!(status.is_alive());
+
+    status.last_refresh = Some(Utc::now() - Duration::minutes(5));
+    assert!(!status.is_alive());
+}
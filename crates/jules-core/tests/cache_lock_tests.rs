@@ -0,0 +1,44 @@
+use jules_core::cache_lock::with_lock;
+use std::fs;
+use std::sync::Arc;
+
+#[test]
+fn test_lock_serializes_read_modify_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let lock_path = Arc::new(dir.path().join(".lock"));
+    let counter = Arc::new(dir.path().join("counter"));
+    fs::write(counter.as_ref(), "0").unwrap();
+
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let lock_path = Arc::clone(&lock_path);
+            let counter = Arc::clone(&counter);
+            std::thread::spawn(move || {
+                with_lock(&lock_path, || {
+                    let value: u32 = fs::read_to_string(counter.as_ref())?.parse()?;
+                    std::thread::sleep(std::time::Duration::from_millis(5));
+                    fs::write(counter.as_ref(), (value + 1).to_string())?;
+                    Ok(())
+                })
+                .unwrap();
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(fs::read_to_string(counter.as_ref()).unwrap(), "8");
+}
+
+#[test]
+fn test_lock_is_reentrant() {
+    let dir = tempfile::tempdir().unwrap();
+    let lock_path = dir.path().join("nested").join(".lock");
+
+    let value = with_lock(&lock_path, || with_lock(&lock_path, || Ok(42))).unwrap();
+    assert_eq!(value, 42);
+
+    // Released afterwards
+    assert_eq!(with_lock(&lock_path, || Ok(1)).unwrap(), 1);
+}
@@ -0,0 +1,406 @@
+//! Tests for dotted-key access to the config and project-local configs.
+
+use jules_core::{
+    config_schema, find_project_config, get_config_value, merge_project_config, read_config,
+    set_config_value, unset_config_value, CacheBackend, Config, ConfigOverrides, EvictionPolicy,
+    Profile, SafetyConfig, PROJECT_CONFIG_FILE_NAME,
+};
+
+#[test]
+fn test_set_nested_values() {
+    let mut config = Config::default();
+
+    set_config_value(&mut config, "cache.max_sessions", "100").unwrap();
+    set_config_value(&mut config, "cache.enabled", "false").unwrap();
+    set_config_value(&mut config, "cache.eviction", "lru").unwrap();
+    set_config_value(&mut config, "cache.backend", "\"sqlite\"").unwrap();
+    set_config_value(&mut config, "cache.max_size_mb", "256").unwrap();
+
+    assert_eq!(config.cache.max_sessions, 100);
+    assert!(!config.cache.enabled);
+    assert_eq!(config.cache.eviction, EvictionPolicy::Lru);
+    assert_eq!(config.cache.backend, CacheBackend::Sqlite);
+    assert_eq!(config.cache.max_size_mb, Some(256));
+
+    set_config_value(
+        &mut config,
+        "notifications.webhook_url",
+        "https://example.com/hook",
+    )
+    .unwrap();
+    assert_eq!(
+        config.notifications.webhook_url.as_deref(),
+        Some("https://example.com/hook")
+    );
+
+    set_config_value(&mut config, "notifications.issue_comments", "true").unwrap();
+    assert!(config.notifications.issue_comments);
+
+    set_config_value(&mut config, "github.session_patterns", r#"['jules-(\d+)']"#).unwrap();
+    assert_eq!(config.github.session_patterns, vec![r"jules-(\d+)"]);
+
+    set_config_value(&mut config, "github.app_id", "12345").unwrap();
+    set_config_value(
+        &mut config,
+        "github.app_private_key_path",
+        "/etc/gules/app.pem",
+    )
+    .unwrap();
+    assert_eq!(config.github.app_id, Some(12345));
+    assert_eq!(
+        config.github.app_private_key_path.as_deref(),
+        Some("/etc/gules/app.pem")
+    );
+    assert!(config.github.has_app());
+
+    assert_eq!(config.github.rate_limit_reserve(), 100);
+    set_config_value(&mut config, "github.rate_limit_reserve", "500").unwrap();
+    assert_eq!(config.github.rate_limit_reserve(), 500);
+}
+
+#[test]
+fn test_set_top_level_strings() {
+    let mut config = Config::default();
+
+    set_config_value(&mut config, "api_key", "AIza-secret").unwrap();
+    // Values that parse as other TOML types are kept as strings
+    set_config_value(&mut config, "default_repo", "2024").unwrap();
+    set_config_value(&mut config, "github_token", "ghp_secret").unwrap();
+
+    assert_eq!(config.api_key.as_deref(), Some("AIza-secret"));
+    assert_eq!(config.default_repo.as_deref(), Some("2024"));
+    assert_eq!(config.github_token.as_deref(), Some("ghp_secret"));
+}
+
+#[test]
+fn test_set_rejects_bad_keys_and_types() {
+    let mut config = Config::default();
+
+    let error = set_config_value(&mut config, "cache.max_sessions", "many").unwrap_err();
+    assert!(
+        error.to_string().contains("cache.max_sessions"),
+        "{}",
+        error
+    );
+    assert!(set_config_value(&mut config, "cache.enabled", "maybe").is_err());
+    assert!(set_config_value(&mut config, "cache.eviction", "random").is_err());
+
+    let error = set_config_value(&mut config, "cache.max_sesions", "10").unwrap_err();
+    assert!(error.to_string().contains("Unknown configuration key"));
+    assert!(set_config_value(&mut config, "api_key.nested", "x").is_err());
+    assert!(set_config_value(&mut config, "colour", "red").is_err());
+
+    // Failed updates leave the config untouched
+    assert_eq!(config.cache.max_sessions, 50);
+}
+
+#[test]
+fn test_get_values() {
+    let config = Config::default();
+
+    assert_eq!(
+        get_config_value(&config, "cache.max_sessions").unwrap(),
+        Some(toml::Value::Integer(50))
+    );
+    assert_eq!(get_config_value(&config, "api_key").unwrap(), None);
+    assert_eq!(get_config_value(&config, "cache.unknown").unwrap(), None);
+}
+
+#[test]
+fn test_unset_restores_defaults() {
+    let mut config = Config::default();
+    set_config_value(&mut config, "cache.max_sessions", "5").unwrap();
+    set_config_value(&mut config, "default_owner", "octo").unwrap();
+
+    assert!(unset_config_value(&mut config, "cache.max_sessions").unwrap());
+    assert_eq!(config.cache.max_sessions, 50);
+    assert!(unset_config_value(&mut config, "default_owner").unwrap());
+    assert_eq!(config.default_owner, None);
+
+    // Already unset or unknown
+    assert!(!unset_config_value(&mut config, "default_owner").unwrap());
+    assert!(!unset_config_value(&mut config, "cache.nothing").unwrap());
+    assert!(!unset_config_value(&mut config, "nothing.here").unwrap());
+}
+
+#[test]
+fn test_read_config_does_not_create_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+
+    assert!(read_config(&path).is_err());
+    assert!(!path.exists());
+
+    std::fs::write(&path, "api_key = \"abc\"\n[cache]\nmax_sessions = 5\n").unwrap();
+    let config = read_config(&path).unwrap();
+    assert_eq!(config.api_key.as_deref(), Some("abc"));
+    assert_eq!(config.cache.max_sessions, 5);
+
+    std::fs::write(&path, "[cache\n").unwrap();
+    assert!(read_config(&path).is_err());
+}
+
+#[test]
+fn test_find_project_config_walks_up() {
+    let dir = tempfile::tempdir().unwrap();
+    let nested = dir.path().join("src").join("module");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    assert_eq!(find_project_config(&nested), None);
+
+    let path = dir.path().join(PROJECT_CONFIG_FILE_NAME);
+    std::fs::write(&path, "default_branch = \"develop\"\n").unwrap();
+    assert_eq!(find_project_config(&nested), Some(path.clone()));
+
+    // The closest file wins
+    let inner = nested.join(PROJECT_CONFIG_FILE_NAME);
+    std::fs::write(&inner, "").unwrap();
+    assert_eq!(find_project_config(&nested), Some(inner));
+    assert_eq!(find_project_config(dir.path()), Some(path));
+}
+
+#[test]
+fn test_merge_project_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(PROJECT_CONFIG_FILE_NAME);
+    std::fs::write(
+        &path,
+        r#"
+default_source = "sources/github/octo/app"
+default_branch = "develop"
+automation_mode = "MANUAL"
+
+[templates]
+bugfix = "Fix this bug: {prompt}"
+
+[cache]
+ttl_seconds = 10
+"#,
+    )
+    .unwrap();
+
+    let mut global = Config {
+        api_key: Some("key".to_string()),
+        default_branch: Some("main".to_string()),
+        ..Default::default()
+    };
+    global.cache.max_sessions = 7;
+
+    let config = merge_project_config(&global, &path).unwrap();
+    assert_eq!(config.api_key.as_deref(), Some("key"));
+    assert_eq!(
+        config.default_source.as_deref(),
+        Some("sources/github/octo/app")
+    );
+    assert_eq!(config.default_branch.as_deref(), Some("develop"));
+    assert_eq!(config.automation_mode.as_deref(), Some("MANUAL"));
+    // Nested tables are merged, not replaced
+    assert_eq!(config.cache.ttl_seconds, 10);
+    assert_eq!(config.cache.max_sessions, 7);
+
+    std::fs::write(&path, "[cache]\nmax_sessions = \"many\"\n").unwrap();
+    assert!(merge_project_config(&global, &path).is_err());
+}
+
+#[test]
+fn test_project_config_cannot_set_safety() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(PROJECT_CONFIG_FILE_NAME);
+    std::fs::write(&path, "[safety]\nallowed_sources = [\"octo/*\"]\n").unwrap();
+
+    let mut global = Config::default();
+    global.safety.allowed_sources = vec!["octo/app".to_string()];
+    let error = merge_project_config(&global, &path)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("[safety] can only be set in the global config"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_allowed_sources() {
+    assert!(SafetyConfig::default().allows_source("sources/github/any/repo"));
+
+    let safety = SafetyConfig {
+        allowed_sources: vec!["sources/github/octo/app".to_string(), "acme/*".to_string()],
+    };
+    assert!(safety.allows_source("sources/github/octo/app"));
+    assert!(safety.allows_source("octo/app"));
+    // GitHub names are case-insensitive
+    assert!(safety.allows_source("sources/github/Octo/App"));
+    assert!(safety.allows_source("sources/github/acme/anything"));
+    assert!(!safety.allows_source("sources/github/octo/other"));
+    assert!(!safety.allows_source("sources/github/acme-evil/app"));
+    assert!(safety.check_source("octo/app").is_ok());
+
+    let error = safety
+        .check_source("sources/github/evil/repo")
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        error,
+        "Sessions on sources/github/evil/repo are not allowed: it is not in \
+         safety.allowed_sources (sources/github/octo/app, acme/*)"
+    );
+}
+
+#[test]
+fn test_resolve_source() {
+    let mut config = Config::default();
+    assert!(config.resolve_source(None).is_err());
+
+    config.default_source = Some("sources/github/octo/app".to_string());
+    assert_eq!(
+        config.resolve_source(None).unwrap(),
+        "sources/github/octo/app"
+    );
+    assert_eq!(
+        config
+            .resolve_source(Some("sources/github/octo/other".to_string()))
+            .unwrap(),
+        "sources/github/octo/other"
+    );
+}
+
+#[test]
+fn test_render_prompt() {
+    let mut config = Config::default();
+    config.templates.insert(
+        "bugfix".to_string(),
+        "Fix: {prompt}. Add a test.".to_string(),
+    );
+    config
+        .templates
+        .insert("docs".to_string(), "Update the docs.\n".to_string());
+
+    assert_eq!(config.render_prompt(None, "x").unwrap(), "x");
+    assert_eq!(
+        config.render_prompt(Some("bugfix"), "crash").unwrap(),
+        "Fix: crash. Add a test."
+    );
+    assert_eq!(
+        config.render_prompt(Some("docs"), "for cache").unwrap(),
+        "Update the docs.\n\nfor cache"
+    );
+
+    let err = config.render_prompt(Some("nope"), "x").unwrap_err();
+    assert!(err.to_string().contains("bugfix, docs"));
+}
+
+#[test]
+fn test_set_template_value() {
+    let mut config = Config::default();
+    set_config_value(&mut config, "templates.review", "Review {prompt}").unwrap();
+    assert_eq!(
+        config.templates.get("review").map(String::as_str),
+        Some("Review {prompt}")
+    );
+}
+
+#[test]
+fn test_config_schema() {
+    let schema = config_schema();
+    let properties = &schema["properties"];
+
+    for key in [
+        "api_key",
+        "default_source",
+        "default_branch",
+        "automation_mode",
+        "templates",
+        "cache",
+    ] {
+        assert!(properties.get(key).is_some(), "missing {}", key);
+    }
+    // All settings have defaults, so partial project configs validate
+    assert!(schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .is_none_or(|r| r.is_empty()));
+
+    let schema = serde_json::to_string(&schema).unwrap();
+    assert!(schema.contains("\"lru\""));
+    assert!(schema.contains("\"sqlite\""));
+    assert!(schema.contains("max_sessions"));
+}
+
+#[test]
+fn test_set_profile_values() {
+    let mut config = Config::default();
+
+    set_config_value(&mut config, "profiles.work.api_key", "work-key").unwrap();
+    set_config_value(
+        &mut config,
+        "profiles.work.api_url",
+        "http://localhost:8080",
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.profiles.get("work"),
+        Some(&Profile {
+            api_key: Some("work-key".to_string()),
+            api_url: Some("http://localhost:8080".to_string()),
+        })
+    );
+    assert!(set_config_value(&mut config, "profiles.work.token", "x").is_err());
+}
+
+#[test]
+fn test_overrides_apply_profile_then_flags() {
+    let mut config = Config {
+        api_key: Some("personal-key".to_string()),
+        api_url: Some("https://example.com/v1".to_string()),
+        ..Default::default()
+    };
+    config.profiles.insert(
+        "work".to_string(),
+        Profile {
+            api_key: Some("work-key".to_string()),
+            api_url: None,
+        },
+    );
+
+    let overrides = ConfigOverrides {
+        profile: Some("work".to_string()),
+        ..Default::default()
+    };
+    let applied = overrides.apply(config.clone()).unwrap();
+    assert_eq!(applied.api_key.as_deref(), Some("work-key"));
+    // Settings the profile leaves out keep their top-level value
+    assert_eq!(applied.api_url.as_deref(), Some("https://example.com/v1"));
+
+    let overrides = ConfigOverrides {
+        api_key: Some("flag-key".to_string()),
+        profile: Some("work".to_string()),
+        base_url: Some("http://localhost:8080".to_string()),
+    };
+    let applied = overrides.apply(config.clone()).unwrap();
+    assert_eq!(applied.api_key.as_deref(), Some("flag-key"));
+    assert_eq!(applied.api_url.as_deref(), Some("http://localhost:8080"));
+
+    assert_eq!(
+        ConfigOverrides::default()
+            .apply(config.clone())
+            .unwrap()
+            .api_key
+            .as_deref(),
+        Some("personal-key")
+    );
+}
+
+#[test]
+fn test_overrides_reject_unknown_profile() {
+    let overrides = ConfigOverrides {
+        profile: Some("missing".to_string()),
+        ..Default::default()
+    };
+    let err = overrides.apply(Config::default()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unknown profile 'missing' (available: none)"
+    );
+}
@@ -0,0 +1,123 @@
+//! Tests for CSV output of sessions, activities and sources.
+
+use jules_core::csv_output::*;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use jules_rs::types::source::Source;
+use serde_json::json;
+
+fn to_csv(columns: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut out = Vec::new();
+    write_csv(&mut out, columns, rows).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_csv_escape() {
+    assert_eq!(csv_escape("plain"), "plain");
+    assert_eq!(csv_escape(""), "");
+    assert_eq!(csv_escape("a,b"), "\"a,b\"");
+    assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(csv_escape("line\nbreak"), "\"line\nbreak\"");
+    assert_eq!(csv_escape("修复 🐛"), "修复 🐛");
+}
+
+#[test]
+fn test_write_csv() {
+    let csv = to_csv(
+        &["id", "note"],
+        vec![
+            vec!["1".to_string(), "ok".to_string()],
+            vec!["2".to_string(), "a, \"b\"".to_string()],
+        ],
+    );
+    assert_eq!(csv, "id,note\r\n1,ok\r\n2,\"a, \"\"b\"\"\"\r\n");
+}
+
+#[test]
+fn test_session_row() {
+    let session: Session = serde_json::from_value(json!({
+        "name": "sessions/42",
+        "id": "42",
+        "title": "Fix login, again",
+        "prompt": "Fix the \"login\" bug",
+        "sourceContext": {
+            "source": "sources/github/octo/app",
+            "githubRepoContext": { "startingBranch": "develop" }
+        },
+        "createTime": "2025-01-01T00:00:00Z",
+        "state": "IN_PROGRESS",
+        "outputs": [{ "pullRequest": { "url": "https://github.com/octo/app/pull/7" } }]
+    }))
+    .unwrap();
+
+    let row = session_csv_row(&session);
+    assert_eq!(row.len(), SESSION_CSV_COLUMNS.len());
+    assert_eq!(
+        row,
+        vec![
+            "42",
+            "Fix login, again",
+            "IN_PROGRESS",
+            "2025-01-01T00:00:00Z",
+            "",
+            "sources/github/octo/app",
+            "develop",
+            "https://github.com/octo/app/pull/7",
+            "",
+            "Fix the \"login\" bug",
+        ]
+    );
+
+    let csv = to_csv(SESSION_CSV_COLUMNS, vec![row]);
+    assert!(csv.starts_with("id,title,state,"));
+    assert!(csv.contains("\"Fix login, again\""));
+    assert!(csv.contains("\"Fix the \"\"login\"\" bug\""));
+}
+
+#[test]
+fn test_activity_row() {
+    let activity: Activity = serde_json::from_value(json!({
+        "name": "sessions/42/activities/a1",
+        "id": "a1",
+        "createTime": "2025-01-01T00:00:00Z",
+        "originator": "agent",
+        "progressUpdated": { "title": "Running tests" },
+        "artifacts": [
+            { "bashOutput": { "command": "cargo test", "output": "fail", "exitCode": 101 } },
+            { "bashOutput": { "command": "cargo build", "exitCode": 0 } }
+        ]
+    }))
+    .unwrap();
+
+    let row = activity_csv_row(&activity);
+    assert_eq!(row.len(), ACTIVITY_CSV_COLUMNS.len());
+    assert_eq!(row[0], "a1");
+    assert_eq!(row[2], activity.activity_type());
+    assert_eq!(row[5], "cargo test");
+    assert_eq!(row[6], "101");
+    assert_eq!(row[7], "2");
+}
+
+#[test]
+fn test_source_row() {
+    let source: Source = serde_json::from_value(json!({
+        "name": "sources/github/octo/app",
+        "id": "github/octo/app",
+        "githubRepo": {
+            "owner": "octo",
+            "repo": "app",
+            "isPrivate": true,
+            "defaultBranch": { "displayName": "main" },
+            "branches": [{ "displayName": "main" }, { "displayName": "dev" }]
+        }
+    }))
+    .unwrap();
+
+    let row = source_csv_row(&source);
+    assert_eq!(row.len(), SOURCE_CSV_COLUMNS.len());
+    assert_eq!(
+        row,
+        vec!["github/octo/app", "octo", "app", "true", "main", "2"]
+    );
+}
@@ -0,0 +1,194 @@
+//! Tests for terminal output helpers.
+
+use jules_core::{
+    colors_enabled, display_sessions_table, display_width, fit_column_widths, format_timestamp,
+    icon, pad_to_width, set_color_mode, truncate_to_width, ColorMode, Config, DisplayTimezone,
+    TimeFormat,
+};
+use jules_rs::types::session::{Session, SourceContext};
+
+#[test]
+fn test_color_mode_parse() {
+    assert_eq!(ColorMode::parse("auto").unwrap(), ColorMode::Auto);
+    assert_eq!(ColorMode::parse("ALWAYS").unwrap(), ColorMode::Always);
+    assert_eq!(ColorMode::parse("never").unwrap(), ColorMode::Never);
+    assert!(ColorMode::parse("sometimes").is_err());
+
+    assert!(ColorMode::Always.enabled());
+    assert!(!ColorMode::Never.enabled());
+}
+
+#[test]
+fn test_color_mode_in_config() {
+    assert_eq!(Config::default().color, ColorMode::Auto);
+
+    let config: Config = toml::from_str("color = \"never\"").unwrap();
+    assert_eq!(config.color, ColorMode::Never);
+    assert!(toml::from_str::<Config>("color = \"rainbow\"").is_err());
+}
+
+// The color override is process-wide, so both modes are checked in one test
+#[test]
+fn test_set_color_mode() {
+    set_color_mode(ColorMode::Never);
+    assert!(!colors_enabled());
+    assert_eq!(icon("💬", "*"), "*");
+    assert_eq!(colored::Colorize::red("x").to_string(), "x");
+
+    set_color_mode(ColorMode::Always);
+    assert!(colors_enabled());
+    assert_eq!(icon("💬", "*"), "💬");
+    assert_ne!(colored::Colorize::red("x").to_string(), "x");
+}
+
+#[test]
+fn test_time_format_parse() {
+    assert_eq!(TimeFormat::parse("ISO").unwrap(), TimeFormat::Iso);
+    assert!(TimeFormat::parse("epoch").is_err());
+
+    assert_eq!(DisplayTimezone::parse("UTC").unwrap(), DisplayTimezone::Utc);
+    assert_eq!(
+        DisplayTimezone::parse("local").unwrap(),
+        DisplayTimezone::Local
+    );
+    assert!(matches!(
+        DisplayTimezone::parse("+02:00").unwrap(),
+        DisplayTimezone::Fixed(_)
+    ));
+    assert!(DisplayTimezone::parse("Mars/Olympus").is_err());
+}
+
+#[test]
+fn test_format_timestamp() {
+    let ts = "2025-01-01T09:30:00.123Z";
+    let plus_two = Some(DisplayTimezone::parse("+02:00").unwrap());
+
+    assert_eq!(
+        format_timestamp(ts, TimeFormat::Iso, None),
+        "2025-01-01T09:30:00Z"
+    );
+    assert_eq!(
+        format_timestamp(ts, TimeFormat::Iso, plus_two),
+        "2025-01-01T11:30:00+02:00"
+    );
+    assert_eq!(
+        format_timestamp(ts, TimeFormat::Local, plus_two),
+        "2025-01-01 11:30:00"
+    );
+    assert_eq!(
+        format_timestamp(ts, TimeFormat::Local, Some(DisplayTimezone::Utc)),
+        "2025-01-01 09:30:00"
+    );
+    // Old timestamps show the date in the configured zone
+    assert_eq!(
+        format_timestamp("2024-12-31T23:30:00Z", TimeFormat::Relative, plus_two),
+        "Jan 01, 2025"
+    );
+    assert_eq!(
+        format_timestamp("not a time", TimeFormat::Iso, None),
+        "not a time"
+    );
+}
+
+#[test]
+fn test_display_config() {
+    let config: Config =
+        toml::from_str("[display]\ntimezone = \"-05:00\"\ntime_format = \"iso\"").unwrap();
+    assert_eq!(config.display.time_format, TimeFormat::Iso);
+    assert!(matches!(
+        config.display.timezone().unwrap(),
+        Some(DisplayTimezone::Fixed(_))
+    ));
+    assert_eq!(Config::default().display.time_format, TimeFormat::Relative);
+}
+
+#[test]
+fn test_truncate_ascii() {
+    assert_eq!(truncate_to_width("short", 10), "short");
+    assert_eq!(truncate_to_width("exactly10!", 10), "exactly10!");
+    assert_eq!(truncate_to_width("a longer title", 10), "a longe...");
+    assert_eq!(truncate_to_width("abcdef", 2), "ab");
+}
+
+#[test]
+fn test_truncate_multibyte() {
+    // Slicing these by byte offset used to panic
+    let cjk = "修复登录页面的错误处理";
+    assert_eq!(display_width(cjk), 22);
+    let truncated = truncate_to_width(cjk, 10);
+    assert_eq!(truncated, "修复登...");
+    assert!(display_width(&truncated) <= 10);
+
+    let accented = "Café résumé naïve façade";
+    assert_eq!(truncate_to_width(accented, 8), "Café ...");
+
+    // Emoji sequences are kept whole
+    let emoji = "🚀 Deploy 👨‍👩‍👧 family feature";
+    let truncated = truncate_to_width(emoji, 12);
+    assert!(truncated.ends_with("..."));
+    assert!(display_width(&truncated) <= 12);
+    assert!(emoji.starts_with(truncated.trim_end_matches("...")));
+}
+
+#[test]
+fn test_pad_to_width() {
+    assert_eq!(pad_to_width("ab", 4), "ab  ");
+    assert_eq!(pad_to_width("日本", 6), "日本  ");
+    assert_eq!(pad_to_width("too long", 3), "too long");
+}
+
+#[test]
+fn test_sessions_table_with_wide_titles() {
+    let session = |id: &str, title: &str| Session {
+        name: format!("sessions/{}", id),
+        id: id.to_string(),
+        title: Some(title.to_string()),
+        prompt: String::new(),
+        source_context: SourceContext {
+            source: "sources/github/octo/app".to_string(),
+            github_repo_context: None,
+        },
+        require_plan_approval: None,
+        automation_mode: None,
+        create_time: None,
+        update_time: None,
+        state: None,
+        url: None,
+        outputs: Vec::new(),
+    };
+
+    display_sessions_table(&[
+        session("1", &"修复".repeat(40)),
+        session("2", &"🎉 release ".repeat(10)),
+        session("3", "plain"),
+    ]);
+}
+
+#[test]
+fn test_fit_column_widths_without_terminal() {
+    // Content clamped to the bounds, as in a pipe
+    let columns = [(80, (20, 50)), (20, (20, 20)), (3, (6, 6))];
+    assert_eq!(fit_column_widths(&columns, None), vec![50, 20, 6]);
+}
+
+#[test]
+fn test_fit_column_widths_grows_on_wide_terminal() {
+    let columns = [(80, (20, 50)), (20, (20, 20)), (3, (6, 6))];
+    // 2 separators leave 198 columns; the title fits its content
+    assert_eq!(fit_column_widths(&columns, Some(200)), vec![80, 20, 6]);
+    // Partly, as far as the terminal allows
+    assert_eq!(fit_column_widths(&columns, Some(100)), vec![72, 20, 6]);
+}
+
+#[test]
+fn test_fit_column_widths_shrinks_on_narrow_terminal() {
+    let columns = [(80, (20, 50)), (30, (15, 30)), (20, (20, 20))];
+    // 58 of 60 columns are left after separators; the fixed column keeps 20
+    let widths = fit_column_widths(&columns, Some(60));
+    assert_eq!(widths.iter().sum::<usize>(), 58);
+    assert_eq!(widths[2], 20);
+    assert!(widths[0] >= 20 && widths[1] >= 15);
+
+    // Never below the minimums
+    assert_eq!(fit_column_widths(&columns, Some(10)), vec![20, 15, 20]);
+}
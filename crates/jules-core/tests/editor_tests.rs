@@ -0,0 +1,75 @@
+//! Tests for reading prompts from stdin and the editor.
+
+use jules_core::editor::{edit_text, message_from_input, non_empty_prompt, prompt_from_input};
+use std::fs;
+
+#[test]
+fn test_prompt_from_argument() {
+    let prompt = prompt_from_input("Fix the bug", "ignored".as_bytes()).unwrap();
+    assert_eq!(prompt, "Fix the bug");
+}
+
+#[test]
+fn test_prompt_from_stdin() {
+    let input = "Fix the bug\n\n```rust\nfn main() {}\n```\n\n";
+    let prompt = prompt_from_input("-", input.as_bytes()).unwrap();
+    assert_eq!(prompt, "Fix the bug\n\n```rust\nfn main() {}\n```");
+}
+
+#[test]
+fn test_empty_prompt_is_rejected() {
+    assert!(prompt_from_input("-", "\n  \n".as_bytes()).is_err());
+    assert!(prompt_from_input("", "".as_bytes()).is_err());
+    assert!(non_empty_prompt("  ".to_string()).is_err());
+}
+
+#[test]
+fn test_message_from_argument_and_stdin() {
+    let message = message_from_input(Some("Looks good"), None, "ignored".as_bytes()).unwrap();
+    assert_eq!(message, "Looks good");
+
+    let input = "Tests fail:\n\n    at parse (src/lib.rs:10)\n\n";
+    let message = message_from_input(Some("-"), None, input.as_bytes()).unwrap();
+    assert_eq!(message, "Tests fail:\n\n    at parse (src/lib.rs:10)");
+
+    let message = message_from_input(None, Some("-"), input.as_bytes()).unwrap();
+    assert_eq!(message, "Tests fail:\n\n    at parse (src/lib.rs:10)");
+}
+
+#[test]
+fn test_message_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("msg.md");
+    fs::write(&path, "  indented \"quoted\" $HOME\n").unwrap();
+
+    let message =
+        message_from_input(None, Some(path.to_str().unwrap()), "ignored".as_bytes()).unwrap();
+    assert_eq!(message, "  indented \"quoted\" $HOME");
+
+    let missing = dir.path().join("missing.md");
+    assert!(message_from_input(None, Some(missing.to_str().unwrap()), "".as_bytes()).is_err());
+}
+
+#[test]
+fn test_empty_message_is_rejected() {
+    let error = message_from_input(Some("-"), None, " \n".as_bytes()).unwrap_err();
+    assert_eq!(error.to_string(), "Empty message, nothing sent");
+    assert!(message_from_input(Some(""), None, "".as_bytes()).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_edit_text() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // An "editor" that appends a line to the file it is given
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("editor.sh");
+    fs::write(&script, "#!/bin/sh\necho 'Edited line' >> \"$1\"\n").unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    std::env::remove_var("VISUAL");
+    std::env::set_var("EDITOR", &script);
+
+    let edited = edit_text("Draft\n").unwrap();
+    assert_eq!(edited, "Draft\nEdited line\n");
+}
@@ -0,0 +1,43 @@
+//! Tests for exit code classification.
+
+use anyhow::Context;
+use jules_core::exit_code::*;
+use jules_rs::types::error::HttpError;
+
+fn http_error(status: u16) -> anyhow::Error {
+    HttpError::from_response(status, None, None, "").into()
+}
+
+#[test]
+fn test_http_errors() {
+    assert_eq!(exit_code(&http_error(404)), NOT_FOUND);
+    assert_eq!(exit_code(&http_error(401)), AUTH_FAILED);
+    assert_eq!(exit_code(&http_error(403)), AUTH_FAILED);
+    assert_eq!(exit_code(&http_error(504)), TIMEOUT);
+    assert_eq!(exit_code(&http_error(500)), API_ERROR);
+    assert_eq!(exit_code(&http_error(429)), API_ERROR);
+}
+
+#[test]
+fn test_cause_chain_is_searched() {
+    let error = Err::<(), _>(http_error(404))
+        .context("Failed to fetch session 42")
+        .unwrap_err();
+    assert_eq!(exit_code(&error), NOT_FOUND);
+}
+
+#[test]
+fn test_explicit_exit_errors() {
+    let error: anyhow::Error = ExitError::new(SESSION_FAILED, "Session 42 failed").into();
+    assert_eq!(error.to_string(), "Session 42 failed");
+    assert_eq!(exit_code(&error), SESSION_FAILED);
+
+    let error = anyhow::Error::from(ExitError::new(WATCH_TIMEOUT, "Timed out"))
+        .context("Failed to watch session 42");
+    assert_eq!(exit_code(&error), WATCH_TIMEOUT);
+}
+
+#[test]
+fn test_other_errors() {
+    assert_eq!(exit_code(&anyhow::anyhow!("API key not configured")), ERROR);
+}
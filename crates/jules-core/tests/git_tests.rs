@@ -0,0 +1,113 @@
+//! Tests for running git.
+
+use jules_core::git::{git, git_with_input, github_repo, in_work_tree, repo_context, RepoContext};
+use std::fs;
+use std::path::Path;
+
+fn init_repo(dir: &Path) {
+    git(dir, &["init", "--quiet"]).unwrap();
+    git(dir, &["config", "user.name", "Test"]).unwrap();
+    git(dir, &["config", "user.email", "test@example.com"]).unwrap();
+    fs::write(dir.join("README.md"), "old\n").unwrap();
+    git(dir, &["add", "README.md"]).unwrap();
+    git(dir, &["commit", "--quiet", "-m", "Initial commit"]).unwrap();
+}
+
+#[test]
+fn test_in_work_tree() {
+    let temp = tempfile::tempdir().unwrap();
+    assert!(!in_work_tree(temp.path()));
+    init_repo(temp.path());
+    assert!(in_work_tree(temp.path()));
+}
+
+#[test]
+fn test_git_returns_trimmed_stdout() {
+    let temp = tempfile::tempdir().unwrap();
+    init_repo(temp.path());
+    assert_eq!(
+        git(temp.path(), &["log", "-1", "--format=%s"]).unwrap(),
+        "Initial commit"
+    );
+}
+
+#[test]
+fn test_git_failure_includes_stderr() {
+    let temp = tempfile::tempdir().unwrap();
+    init_repo(temp.path());
+    let error = git(temp.path(), &["checkout", "no-such-branch"]).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("git checkout no-such-branch failed:"));
+}
+
+#[test]
+fn test_git_with_input_applies_patch() {
+    let temp = tempfile::tempdir().unwrap();
+    init_repo(temp.path());
+    let patch = "--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-old\n+new\n";
+    git_with_input(temp.path(), &["apply", "--index", "-"], patch).unwrap();
+    assert_eq!(
+        fs::read_to_string(temp.path().join("README.md")).unwrap(),
+        "new\n"
+    );
+    assert_eq!(
+        git(temp.path(), &["diff", "--cached", "--name-only"]).unwrap(),
+        "README.md"
+    );
+}
+
+#[test]
+fn test_github_repo_from_remote_urls() {
+    let expected = Some(("octo".to_string(), "app".to_string()));
+    assert_eq!(github_repo("https://github.com/octo/app.git"), expected);
+    assert_eq!(github_repo("https://github.com/octo/app"), expected);
+    assert_eq!(github_repo("git@github.com:octo/app.git"), expected);
+    assert_eq!(github_repo("ssh://git@github.com/octo/app.git\n"), expected);
+    assert_eq!(github_repo("https://gitlab.com/octo/app.git"), None);
+    assert_eq!(github_repo("https://github.com/octo"), None);
+}
+
+#[test]
+fn test_repo_context() {
+    let temp = tempfile::tempdir().unwrap();
+    assert_eq!(repo_context(temp.path()), None);
+    init_repo(temp.path());
+    assert_eq!(repo_context(temp.path()), None);
+
+    git(
+        temp.path(),
+        &["remote", "add", "origin", "git@github.com:octo/api.git"],
+    )
+    .unwrap();
+    let expected = RepoContext {
+        owner: "octo".to_string(),
+        repo: "api".to_string(),
+        branch: None,
+    };
+    // The branch was never pushed
+    assert_eq!(repo_context(temp.path()), Some(expected.clone()));
+
+    let branch = git(temp.path(), &["rev-parse", "--abbrev-ref", "HEAD"]).unwrap();
+    git(
+        temp.path(),
+        &[
+            "update-ref",
+            &format!("refs/remotes/origin/{}", branch),
+            "HEAD",
+        ],
+    )
+    .unwrap();
+    git(
+        temp.path(),
+        &["branch", "--set-upstream-to", &format!("origin/{}", branch)],
+    )
+    .unwrap();
+    assert_eq!(
+        repo_context(temp.path()),
+        Some(RepoContext {
+            branch: Some(branch),
+            ..expected
+        })
+    );
+}
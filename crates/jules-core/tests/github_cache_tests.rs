@@ -0,0 +1,142 @@
+//! Tests for the GitHub response and rate limit cache.
+
+use chrono::{TimeZone, Utc};
+use jules_core::github_cache::{RateLimit, RateLimits, ResponseCache, MAX_RESPONSE_AGE};
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, SystemTime};
+
+fn limit(resource: &str, remaining: u64, reset: i64) -> RateLimit {
+    RateLimit {
+        resource: resource.to_string(),
+        limit: 5000,
+        remaining,
+        used: 5000 - remaining,
+        reset: Utc.timestamp_opt(reset, 0).unwrap(),
+    }
+}
+
+#[test]
+fn test_rate_limit_from_headers() {
+    let headers: HashMap<&str, &str> = [
+        ("x-ratelimit-limit", "5000"),
+        ("x-ratelimit-remaining", "4990"),
+        ("x-ratelimit-used", "10"),
+        ("x-ratelimit-reset", "1700000000"),
+        ("x-ratelimit-resource", "graphql"),
+    ]
+    .into();
+    let parsed = RateLimit::from_headers(|name| headers.get(name).copied()).unwrap();
+    assert_eq!(parsed, {
+        let mut expected = limit("graphql", 4990, 1_700_000_000);
+        expected.used = 10;
+        expected
+    });
+
+    // Responses without rate limit headers, e.g. from a cache in between
+    assert!(RateLimit::from_headers(|_| None).is_none());
+    let partial: HashMap<&str, &str> = [("x-ratelimit-limit", "5000")].into();
+    assert!(RateLimit::from_headers(|name| partial.get(name).copied()).is_none());
+}
+
+#[test]
+fn test_rate_limits_from_json() {
+    let response = serde_json::json!({
+        "resources": {
+            "core": {"limit": 5000, "used": 4900, "remaining": 100, "reset": 1700000000},
+            "graphql": {"limit": 5000, "used": 0, "remaining": 5000, "reset": 1700000100},
+            "broken": {"limit": "many"},
+        },
+        "rate": {"limit": 5000, "used": 4900, "remaining": 100, "reset": 1700000000},
+    });
+    let limits = RateLimits::from_json(&response);
+    assert_eq!(
+        limits.resources.keys().collect::<Vec<_>>(),
+        ["core", "graphql"]
+    );
+    assert_eq!(limits.resources["core"], limit("core", 100, 1_700_000_000));
+}
+
+#[test]
+fn test_low_rate_limit_waits_for_reset() {
+    let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+    let mut limits = RateLimits::default();
+    limits.record(limit("core", 100, 1_700_000_600));
+    limits.record(limit("graphql", 101, 1_700_000_600));
+    limits.record(limit("search", 0, 1_699_999_999));
+
+    assert_eq!(
+        limits.resources["core"].wait_for(100, now),
+        Some(Duration::from_secs(600))
+    );
+    assert!(limits.low("core", 100, now).is_some());
+    assert!(limits.low("core", 99, now).is_none());
+    assert!(limits.low("graphql", 100, now).is_none());
+    // Already reset
+    assert!(limits.low("search", 100, now).is_none());
+    assert!(limits.low("unknown", 100, now).is_none());
+}
+
+#[test]
+fn test_rate_limits_roundtrip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("github").join("rate_limits.json");
+    assert!(RateLimits::load_from(&path).unwrap().resources.is_empty());
+
+    let mut limits = RateLimits::default();
+    limits.record(limit("core", 10, 1_700_000_000));
+    limits.record(limit("core", 9, 1_700_000_000));
+    limits.save_to(&path).unwrap();
+
+    let loaded = RateLimits::load_from(&path).unwrap();
+    assert_eq!(loaded.resources.len(), 1);
+    assert_eq!(loaded.resources["core"].remaining, 9);
+}
+
+#[test]
+fn test_response_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ResponseCache::new(dir.path().join("responses"));
+    let url = "/repos/octo/app/issues?state=open&per_page=100&page=1";
+    assert!(cache.get(url).is_none());
+
+    cache.put(url, "W/\"abc\"", "[]").unwrap();
+    cache.put(url, "W/\"def\"", "[{}]").unwrap();
+    let cached = cache.get(url).unwrap();
+    assert_eq!(cached.etag, "W/\"def\"");
+    assert_eq!(cached.body, "[{}]");
+    assert!(cache.get("/repos/octo/app/issues/1").is_none());
+}
+
+#[test]
+fn test_response_cache_pruning() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ResponseCache::new(dir.path().join("responses"));
+    for url in ["/a", "/b", "/c"] {
+        cache.put(url, "\"e\"", &"x".repeat(1000)).unwrap();
+    }
+    assert_eq!(cache.usage().unwrap().0, 3);
+
+    // `/a` was last used over a week ago
+    let stale = fs::read_dir(cache.dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| fs::read_to_string(path).unwrap().contains("\"/a\""))
+        .unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&stale)
+        .unwrap()
+        .set_modified(SystemTime::now() - MAX_RESPONSE_AGE - Duration::from_secs(60))
+        .unwrap();
+    assert_eq!(cache.prune(MAX_RESPONSE_AGE, u64::MAX).unwrap(), 1);
+    assert!(cache.get("/a").is_none());
+
+    // Over the size limit, the oldest go first
+    let (_, size) = cache.usage().unwrap();
+    assert_eq!(cache.prune(MAX_RESPONSE_AGE, size - 1).unwrap(), 1);
+    assert_eq!(cache.usage().unwrap().0, 1);
+
+    cache.clear().unwrap();
+    assert_eq!(cache.usage().unwrap(), (0, 0));
+}
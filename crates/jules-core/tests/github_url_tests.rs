@@ -0,0 +1,76 @@
+//! Tests for parsing GitHub pull request and issue URLs.
+
+use jules_core::github_url::{GitHubItemKind, GitHubItemUrl};
+
+#[test]
+fn test_parse_pull_request() {
+    let pr = GitHubItemUrl::parse("https://github.com/kiwina/gules/pull/42").unwrap();
+    assert_eq!(pr.host, "github.com");
+    assert_eq!(pr.owner, "kiwina");
+    assert_eq!(pr.repo, "gules");
+    assert_eq!(pr.kind, GitHubItemKind::PullRequest);
+    assert_eq!(pr.number, 42);
+    assert!(pr.is_github_com());
+    assert_eq!(pr.repo_spec(), "kiwina/gules");
+    assert_eq!(pr.to_string(), "kiwina/gules#42");
+}
+
+#[test]
+fn test_parse_trailing_paths() {
+    for url in [
+        "https://github.com/kiwina/gules/pull/42/",
+        "https://github.com/kiwina/gules/pull/42/files",
+        "https://github.com/kiwina/gules/pull/42/commits/abc123",
+        "https://github.com/kiwina/gules/pull/42#issuecomment-1",
+        "https://github.com/kiwina/gules/pull/42?w=1",
+        "http://www.github.com/kiwina/gules/pull/42",
+        "  https://GitHub.com/kiwina/gules/pull/42\n",
+    ] {
+        let pr = GitHubItemUrl::parse_pull_request(url).unwrap();
+        assert_eq!(
+            pr.web_url(),
+            "https://github.com/kiwina/gules/pull/42",
+            "{}",
+            url
+        );
+    }
+}
+
+#[test]
+fn test_parse_enterprise_host() {
+    let pr = GitHubItemUrl::parse("https://git.example.com/team/service/pull/7/files").unwrap();
+    assert_eq!(pr.host, "git.example.com");
+    assert_eq!(pr.owner, "team");
+    assert_eq!(pr.repo, "service");
+    assert_eq!(pr.number, 7);
+    assert!(!pr.is_github_com());
+    assert_eq!(pr.repo_spec(), "git.example.com/team/service");
+}
+
+#[test]
+fn test_parse_issue() {
+    let issue = GitHubItemUrl::parse("https://github.com/kiwina/gules/issues/12").unwrap();
+    assert_eq!(issue.kind, GitHubItemKind::Issue);
+    assert_eq!(issue.number, 12);
+    assert_eq!(issue.web_url(), "https://github.com/kiwina/gules/issues/12");
+
+    let error = GitHubItemUrl::parse_pull_request("https://github.com/kiwina/gules/issues/12")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Not a pull request"), "{}", error);
+}
+
+#[test]
+fn test_parse_invalid() {
+    for url in [
+        "not a url",
+        "ftp://github.com/kiwina/gules/pull/42",
+        "https://github.com/kiwina/gules",
+        "https://github.com/kiwina/gules/tree/main/src",
+        "https://github.com/kiwina/gules/pull/abc",
+        "https://github.com/kiwina/gules/pull/0",
+        "https://github.com/kiwina/gules/pull",
+    ] {
+        assert!(GitHubItemUrl::parse(url).is_err(), "{}", url);
+    }
+}
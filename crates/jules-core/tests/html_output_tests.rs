@@ -0,0 +1,68 @@
+//! Tests for standalone HTML reports.
+
+mod common;
+
+use jules_core::html_output::*;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use serde_json::json;
+
+fn session() -> Session {
+    common::session(
+        "42",
+        json!({
+            "title": "Fix <login>",
+            "prompt": "Fix the login bug & keep the API stable",
+            "state": "COMPLETED",
+            "outputs": [{ "pullRequest": { "url": "https://github.com/octo/app/pull/7" } }]
+        }),
+    )
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(
+        escape(r#"<a href="x">'&'</a>"#),
+        "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+    );
+    assert_eq!(escape("plain"), "plain");
+}
+
+#[test]
+fn test_session_report() {
+    let activity: Activity = serde_json::from_value(json!({
+        "name": "sessions/42/activities/a1",
+        "id": "a1",
+        "createTime": "2025-01-01T00:01:00Z",
+        "originator": "agent",
+        "progressUpdated": { "title": "Ran tests" },
+        "artifacts": [
+            { "bashOutput": { "command": "cargo test", "output": "ok\n", "exitCode": 0 } },
+            { "changeSet": { "source": "sources/github/octo/app", "gitPatch": { "unidiffPatch": "@@ -1 +1 @@\n-<old>\n+new\n" } } }
+        ]
+    }))
+    .unwrap();
+
+    let html = session_html(&session(), &[&activity]);
+    assert!(html.starts_with("<!DOCTYPE html>\n"));
+    assert!(html.contains("<title>Fix &lt;login&gt;</title>"));
+    assert!(html.contains("<h1>Fix &lt;login&gt;</h1>"));
+    assert!(html.contains("<dt>State</dt><dd>Completed</dd>"));
+    assert!(html.contains(
+        "<a href=\"https://github.com/octo/app/pull/7\">https://github.com/octo/app/pull/7</a>"
+    ));
+    assert!(html.contains("<blockquote>Fix the login bug &amp; keep the API stable</blockquote>"));
+    assert!(html.contains("<h2>Activities (1)</h2>"));
+    assert!(html
+        .contains("<p><strong>Command</strong> (exit code 0):</p>\n<pre>$ cargo test\nok</pre>"));
+    assert!(html.contains("<span class=\"hunk\">@@ -1 +1 @@</span>"));
+    assert!(html.contains("<span class=\"del\">-&lt;old&gt;</span>"));
+    assert!(html.contains("<span class=\"add\">+new</span>"));
+    assert!(html.ends_with("</html>\n"));
+}
+
+#[test]
+fn test_session_report_without_activities() {
+    let html = session_html(&session(), &[]);
+    assert!(!html.contains("<h2>Activities"));
+}
@@ -0,0 +1,90 @@
+//! Tests for gap-free incremental activity updates.
+
+use jules_core::activity_cache::fetch_new_activities;
+use jules_rs::types::activity::Activity;
+use jules_rs::JulesClient;
+
+fn activity_json(id: u32) -> serde_json::Value {
+    serde_json::json!({
+        "name": format!("sessions/s1/activities/a{}", id),
+        "id": format!("a{}", id),
+        "createTime": format!("2024-01-01T00:00:{:02}Z", id),
+        "originator": "agent"
+    })
+}
+
+fn activity(id: u32) -> Activity {
+    serde_json::from_value(activity_json(id)).unwrap()
+}
+
+fn page(ids: &[u32], next: Option<&str>) -> String {
+    let activities: Vec<_> = ids.iter().map(|id| activity_json(*id)).collect();
+    let mut body = serde_json::json!({ "activities": activities });
+    if let Some(next) = next {
+        body["nextPageToken"] = next.into();
+    }
+    body.to_string()
+}
+
+fn client(server: &mockito::Server) -> JulesClient {
+    JulesClient::with_config(jules_rs::JulesConfig {
+        api_key: "test-key".to_string(),
+        base_url: server.url(),
+    })
+}
+
+fn ids(activities: &[Activity]) -> Vec<&str> {
+    activities.iter().map(|a| a.id.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_newest_first_stops_at_cached_history() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("GET", "/sessions/s1/activities?pageSize=50")
+        .with_body(page(&[6, 5], Some("p2")))
+        .create_async()
+        .await;
+    let second = server
+        .mock("GET", "/sessions/s1/activities?pageSize=50&pageToken=p2")
+        .with_body(page(&[4, 3], Some("p3")))
+        .create_async()
+        .await;
+    let third = server
+        .mock("GET", "/sessions/s1/activities?pageSize=50&pageToken=p3")
+        .with_body(page(&[2, 1], None))
+        .expect(0)
+        .create_async()
+        .await;
+
+    let known = vec![activity(3), activity(2), activity(1)];
+    let new = fetch_new_activities(&client(&server), "s1", &known)
+        .await
+        .unwrap();
+    assert_eq!(ids(&new), ["a6", "a5", "a4"]);
+
+    first.assert_async().await;
+    second.assert_async().await;
+    third.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_oldest_first_listing_is_walked_to_the_end() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/sessions/s1/activities?pageSize=50")
+        .with_body(page(&[1, 2], Some("p2")))
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/sessions/s1/activities?pageSize=50&pageToken=p2")
+        .with_body(page(&[3, 4], None))
+        .create_async()
+        .await;
+
+    let known = vec![activity(2), activity(1)];
+    let new = fetch_new_activities(&client(&server), "sessions/s1", &known)
+        .await
+        .unwrap();
+    assert_eq!(ids(&new), ["a4", "a3"]);
+}
@@ -0,0 +1,60 @@
+//! Tests for issue-session links.
+
+use jules_core::issue_links::{IssueLink, IssueLinks};
+use tempfile::TempDir;
+
+fn sessions<'a>(links: impl IntoIterator<Item = &'a IssueLink>) -> Vec<&'a str> {
+    links
+        .into_iter()
+        .map(|link| link.session_id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_link_and_look_up() {
+    let mut links = IssueLinks::default();
+    assert!(links.link("kiwina", "gules", 12, "111").unwrap());
+    assert!(links.link("kiwina", "gules", 12, "sessions/222").unwrap());
+    assert!(links.link("kiwina", "gules", 13, "111").unwrap());
+
+    assert_eq!(
+        sessions(links.for_issue("kiwina", "gules", 12)),
+        vec!["111", "222"]
+    );
+    // Owners and repositories match case-insensitively, like on GitHub
+    assert_eq!(
+        sessions(links.for_issue("Kiwina", "Gules", 13)),
+        vec!["111"]
+    );
+    assert!(links.for_issue("kiwina", "other", 12).is_empty());
+
+    let issues: Vec<u64> = links
+        .for_session("111")
+        .iter()
+        .map(|link| link.issue)
+        .collect();
+    assert_eq!(issues, vec![12, 13]);
+    assert_eq!(links.links[0].issue_ref(), "kiwina/gules#12");
+}
+
+#[test]
+fn test_link_twice() {
+    let mut links = IssueLinks::default();
+    assert!(links.link("kiwina", "gules", 12, "111").unwrap());
+    assert!(!links.link("KIWINA", "gules", 12, "sessions/111").unwrap());
+    assert_eq!(links.links.len(), 1);
+}
+
+#[test]
+fn test_save_and_load() {
+    let temp = TempDir::new().unwrap();
+    let path = temp.path().join("links.json");
+    assert!(IssueLinks::load_from(&path).unwrap().links.is_empty());
+
+    let mut links = IssueLinks::default();
+    links.link("kiwina", "gules", 12, "111").unwrap();
+    links.save_to(&path).unwrap();
+
+    let loaded = IssueLinks::load_from(&path).unwrap();
+    assert_eq!(loaded.links, links.links);
+}
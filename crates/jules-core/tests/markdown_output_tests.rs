@@ -0,0 +1,77 @@
+//! Tests for Markdown reports.
+
+mod common;
+
+use jules_core::markdown_output::*;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use serde_json::json;
+
+fn session() -> Session {
+    common::session(
+        "42",
+        json!({
+            "title": "Fix | login",
+            "prompt": "Fix the login bug\n\nKeep the API stable",
+            "sourceContext": {
+                "source": "sources/github/octo/app",
+                "githubRepoContext": { "startingBranch": "develop" }
+            },
+            "createTime": "2025-01-01T00:00:00Z",
+            "state": "COMPLETED",
+            "outputs": [{ "pullRequest": { "url": "https://github.com/octo/app/pull/7" } }]
+        }),
+    )
+}
+
+#[test]
+fn test_fenced_outgrows_backticks() {
+    assert_eq!(fenced("diff", "+a"), "```diff\n+a\n```\n\n");
+    assert_eq!(
+        fenced("", "```rust\nfn main() {}\n```"),
+        "````\n```rust\nfn main() {}\n```\n````\n\n"
+    );
+}
+
+#[test]
+fn test_sessions_table() {
+    let md = sessions_markdown(&[session()]);
+    let lines: Vec<&str> = md.lines().collect();
+    assert_eq!(lines[0], "| Title | Session ID | State | Created | PR |");
+    assert_eq!(
+        lines[2],
+        "| Fix \\| login | `42` | Completed | 2025-01-01T00:00:00Z | [PR](https://github.com/octo/app/pull/7) |"
+    );
+}
+
+#[test]
+fn test_session_report() {
+    let activity: Activity = serde_json::from_value(json!({
+        "name": "sessions/42/activities/a1",
+        "id": "a1",
+        "createTime": "2025-01-01T00:01:00Z",
+        "originator": "agent",
+        "progressUpdated": { "title": "Ran tests" },
+        "artifacts": [
+            { "bashOutput": { "command": "cargo test", "output": "ok\n", "exitCode": 0 } },
+            { "changeSet": { "source": "sources/github/octo/app", "gitPatch": { "unidiffPatch": "+fixed\n", "suggestedCommitMessage": "Fix login" } } }
+        ]
+    }))
+    .unwrap();
+
+    let md = session_markdown(&session(), &[&activity]);
+    assert!(md.starts_with("# Fix | login\n\n- **Session:** `42`\n"));
+    assert!(md.contains("- **Branch:** `develop`\n"));
+    assert!(md.contains("- **Pull request:** https://github.com/octo/app/pull/7\n"));
+    assert!(md.contains("## Prompt\n\n> Fix the login bug\n>\n> Keep the API stable\n"));
+    assert!(md.contains("## Activities (1)\n"));
+    assert!(md.contains("**Ran tests**\n"));
+    assert!(md.contains("**Command** (exit code 0):\n\n```console\n$ cargo test\nok\n```\n"));
+    assert!(md.contains("**Suggested commit:** Fix login\n\n```diff\n+fixed\n```\n"));
+}
+
+#[test]
+fn test_session_report_without_activities() {
+    let md = session_markdown(&session(), &[]);
+    assert!(!md.contains("## Activities"));
+}
@@ -0,0 +1,60 @@
+//! Tests for Prometheus metrics aggregation.
+
+use jules_core::metrics::{MetricsFile, PrometheusMetrics};
+use jules_rs::metrics::{ErrorClass, MetricsSink, RequestMetrics};
+use std::time::Duration;
+
+fn attempt(route: &str, status: Option<u16>, error: Option<ErrorClass>) -> RequestMetrics<'static> {
+    RequestMetrics {
+        method: "GET",
+        route: route.to_string(),
+        status,
+        latency: Duration::from_millis(120),
+        error,
+        attempt: 0,
+    }
+}
+
+#[test]
+fn test_prometheus_metrics_counts_requests_and_errors() {
+    let metrics = PrometheusMetrics::new();
+    metrics.record(&attempt("/sessions", Some(200), None));
+    metrics.record(&attempt("/sessions", Some(200), None));
+    metrics.record(&attempt(
+        "/sessions/{id}",
+        Some(429),
+        Some(ErrorClass::RateLimited),
+    ));
+
+    assert_eq!(metrics.total_requests(), 3);
+    assert_eq!(metrics.total_errors(), 1);
+
+    let rendered = metrics.render();
+    assert!(rendered
+        .contains("jules_api_requests_total{method=\"GET\",route=\"/sessions\",status=\"200\"} 2"));
+    assert!(rendered
+        .contains("jules_api_errors_total{route=\"/sessions/{id}\",class=\"rate_limited\"} 1"));
+    // 120ms falls in the 0.25s bucket, cumulative from there on
+    assert!(rendered
+        .contains("jules_api_request_duration_seconds_bucket{route=\"/sessions\",le=\"0.1\"} 0"));
+    assert!(rendered
+        .contains("jules_api_request_duration_seconds_bucket{route=\"/sessions\",le=\"0.25\"} 2"));
+    assert!(rendered.contains("jules_api_request_duration_seconds_count{route=\"/sessions\"} 2"));
+}
+
+#[test]
+fn test_metrics_file_is_rewritten_after_each_request() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("gules.prom");
+    let metrics = MetricsFile::new(&path);
+
+    metrics.record(&attempt("/sessions", Some(200), None));
+    let rendered = std::fs::read_to_string(&path).unwrap();
+    assert!(rendered
+        .contains("jules_api_requests_total{method=\"GET\",route=\"/sessions\",status=\"200\"} 1"));
+
+    metrics.record(&attempt("/sessions", Some(200), None));
+    let rendered = std::fs::read_to_string(&path).unwrap();
+    assert!(rendered
+        .contains("jules_api_requests_total{method=\"GET\",route=\"/sessions\",status=\"200\"} 2"));
+}
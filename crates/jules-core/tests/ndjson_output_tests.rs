@@ -0,0 +1,84 @@
+//! Tests for NDJSON output.
+
+mod common;
+
+use jules_core::ndjson_output::*;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::{Session, State};
+use serde_json::{json, Value};
+
+fn lines(out: Vec<u8>) -> Vec<Value> {
+    String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn session() -> Session {
+    common::session(
+        "42",
+        json!({
+            "prompt": "Fix the login bug\nand add a test",
+            "state": "COMPLETED"
+        }),
+    )
+}
+
+#[test]
+fn test_one_record_per_line() {
+    let mut out = Vec::new();
+    write_ndjson(
+        &mut out,
+        [json!({ "id": "1", "text": "a\nb" }), json!({ "id": "2" })],
+    )
+    .unwrap();
+
+    let text = String::from_utf8(out.clone()).unwrap();
+    assert_eq!(text, "{\"id\":\"1\",\"text\":\"a\\nb\"}\n{\"id\":\"2\"}\n");
+    assert_eq!(lines(out).len(), 2);
+}
+
+#[test]
+fn test_no_records_no_output() {
+    let mut out = Vec::new();
+    write_ndjson(&mut out, Vec::<Value>::new()).unwrap();
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_watch_events() {
+    let session = session();
+    let activity: Activity = serde_json::from_value(json!({
+        "name": "sessions/42/activities/a1",
+        "id": "a1",
+        "createTime": "2025-01-01T00:00:00Z",
+        "originator": "agent"
+    }))
+    .unwrap();
+
+    let mut out = Vec::new();
+    write_ndjson(
+        &mut out,
+        [
+            WatchEvent::session(&session),
+            WatchEvent::activity(&session.id, &activity),
+            WatchEvent::state_change(Some(State::InProgress), &session),
+        ],
+    )
+    .unwrap();
+
+    let events = lines(out);
+    assert_eq!(events[0]["event"], "session");
+    assert_eq!(events[0]["session"]["id"], "42");
+    assert!(events[0]["timestamp"].is_string());
+
+    assert_eq!(events[1]["event"], "activity");
+    assert_eq!(events[1]["session"], "42");
+    assert_eq!(events[1]["activity"]["id"], "a1");
+
+    assert_eq!(events[2]["event"], "state_change");
+    assert_eq!(events[2]["from"], "IN_PROGRESS");
+    assert_eq!(events[2]["to"], "COMPLETED");
+    assert_eq!(events[2]["session"]["id"], "42");
+}
@@ -0,0 +1,132 @@
+use jules_core::patch_files::{collect_patches, diff_stat, write_patch_files, FileStat};
+use jules_rs::types::activity::{Activity, Artifact, ChangeSet, GitPatch};
+use std::fs;
+
+fn activity(id: &str, patch: Option<&str>) -> Activity {
+    Activity {
+        name: format!("sessions/s1/activities/{}", id),
+        id: id.to_string(),
+        description: None,
+        create_time: "2024-01-01T00:00:00Z".to_string(),
+        originator: "agent".to_string(),
+        artifacts: patch
+            .map(|patch| {
+                vec![Artifact {
+                    change_set: Some(ChangeSet {
+                        source: "sources/github/o/r".to_string(),
+                        git_patch: Some(GitPatch {
+                            unidiff_patch: Some(patch.to_string()),
+                            base_commit_id: Some("abc123".to_string()),
+                            suggested_commit_message: None,
+                        }),
+                    }),
+                    media: None,
+                    bash_output: None,
+                }]
+            })
+            .unwrap_or_default(),
+        agent_messaged: None,
+        user_messaged: None,
+        plan_generated: None,
+        plan_approved: None,
+        progress_updated: None,
+        session_completed: None,
+        session_failed: None,
+    }
+}
+
+const PATCH: &str = "diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -1 +1 @@\n-a\n+b";
+
+#[test]
+fn test_collect_patches_oldest_first() {
+    // Cached activities are newest first
+    let activities = vec![
+        activity("c", Some(PATCH)),
+        activity("b", None),
+        activity("a", Some(PATCH)),
+    ];
+
+    let patches = collect_patches(&activities);
+    let names: Vec<&str> = patches.iter().map(|p| p.file_name.as_str()).collect();
+    assert_eq!(names, ["001-a.patch", "002-c.patch"]);
+    assert_eq!(patches[0].base_commit_id.as_deref(), Some("abc123"));
+    assert!(patches[0].patch.ends_with("+b\n"));
+}
+
+#[test]
+fn test_write_patch_files_removes_stale() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("001-old.patch"), "stale").unwrap();
+    fs::write(dir.path().join("notes.txt"), "keep").unwrap();
+
+    let paths = write_patch_files(dir.path(), &[activity("a", Some(PATCH))]).unwrap();
+    assert_eq!(paths, [dir.path().join("001-a.patch")]);
+    assert_eq!(
+        fs::read_to_string(&paths[0]).unwrap(),
+        format!("{}\n", PATCH)
+    );
+    assert!(!dir.path().join("001-old.patch").exists());
+    assert!(dir.path().join("notes.txt").exists());
+}
+
+#[test]
+fn test_write_patch_files_without_patches() {
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("patches");
+
+    let paths = write_patch_files(&target, &[activity("a", None)]).unwrap();
+    assert!(paths.is_empty());
+    assert!(!target.exists());
+}
+
+#[test]
+fn test_diff_stat_counts_lines_per_file() {
+    let patch = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@
+ fn a() {}
+--- not a header, a removed line
++fn b() {}
++fn c() {}
+ fn d() {}
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+--- a/old.txt
++++ /dev/null
+@@ -1,2 +0,0 @@
+-one
+-two
+\\ No newline at end of file
+";
+    let stats = diff_stat([patch]);
+    assert_eq!(
+        stats,
+        vec![
+            FileStat {
+                path: "src/lib.rs".to_string(),
+                insertions: 2,
+                deletions: 1,
+            },
+            FileStat {
+                path: "old.txt".to_string(),
+                insertions: 0,
+                deletions: 2,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_diff_stat_merges_files_across_patches() {
+    let first = "--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-old\n+new\n";
+    let second = "--- /dev/null\n+++ b/NEW.md\n@@ -0,0 +1 @@\n+hello\n--- a/README.md\n+++ b/README.md\n@@ -1 +1,2 @@\n new\n+more\n";
+    let stats = diff_stat([first, second]);
+    let summary: Vec<(&str, usize, usize)> = stats
+        .iter()
+        .map(|stat| (stat.path.as_str(), stat.insertions, stat.deletions))
+        .collect();
+    assert_eq!(summary, vec![("README.md", 2, 1), ("NEW.md", 1, 0)]);
+}
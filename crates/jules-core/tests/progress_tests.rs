@@ -0,0 +1,24 @@
+//! Tests for progress spinners.
+
+use jules_core::progress::{progress_enabled, Spinner};
+use jules_core::set_quiet;
+
+#[test]
+fn test_spinner_without_terminal() {
+    // Test output is captured, so spinners are inert but still usable
+    let spinner = Spinner::start("Fetching...");
+    spinner.set_message("Fetching... 50 so far");
+    assert_eq!(spinner.suspend(|| 42), 42);
+
+    // A second spinner while the first is alive gets its own line
+    let nested = Spinner::start("Nested");
+    drop(nested);
+    spinner.finish();
+}
+
+#[test]
+fn test_quiet_disables_progress() {
+    set_quiet(true);
+    assert!(!progress_enabled());
+    set_quiet(false);
+}
@@ -0,0 +1,28 @@
+//! Tests for terminal prompts.
+
+use jules_core::prompt::{confirm, is_yes, parse_yes_no};
+
+#[test]
+fn test_is_yes() {
+    assert!(is_yes("y"));
+    assert!(is_yes("Yes\n"));
+    assert!(is_yes("  YES  "));
+    assert!(!is_yes(""));
+    assert!(!is_yes("\n"));
+    assert!(!is_yes("no"));
+    assert!(!is_yes("yep"));
+}
+
+#[test]
+fn test_confirm_assume_yes_skips_prompt() {
+    assert!(confirm("Delete everything?", true).unwrap());
+}
+
+#[test]
+fn test_parse_yes_no_defaults() {
+    assert!(parse_yes_no("", true));
+    assert!(!parse_yes_no("", false));
+    assert!(parse_yes_no("Y", false));
+    assert!(!parse_yes_no("no", true));
+    assert!(parse_yes_no("maybe", true));
+}
@@ -0,0 +1,78 @@
+//! Tests for recently used sessions and `@N` shortcuts.
+
+use jules_core::recent_sessions::{parse_recent_ref, RecentSessions, MAX_RECENT_SESSIONS};
+use tempfile::TempDir;
+
+fn ids(recent: &RecentSessions) -> Vec<&str> {
+    recent
+        .sessions
+        .iter()
+        .map(|session| session.id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_touch_moves_session_to_front() {
+    let mut recent = RecentSessions::default();
+    recent.touch("1", Some("First")).unwrap();
+    recent.touch("2", Some("Second")).unwrap();
+    recent.touch("sessions/1", None).unwrap();
+
+    assert_eq!(ids(&recent), vec!["1", "2"]);
+    // Touching without a title keeps the known one
+    assert_eq!(recent.sessions[0].title.as_deref(), Some("First"));
+}
+
+#[test]
+fn test_touch_keeps_the_most_recent_sessions() {
+    let mut recent = RecentSessions::default();
+    for id in 0..MAX_RECENT_SESSIONS + 5 {
+        recent.touch(&id.to_string(), None).unwrap();
+    }
+
+    assert_eq!(recent.sessions.len(), MAX_RECENT_SESSIONS);
+    assert_eq!(recent.sessions[0].id, (MAX_RECENT_SESSIONS + 4).to_string());
+}
+
+#[test]
+fn test_parse_recent_ref() {
+    assert_eq!(parse_recent_ref("@1").unwrap().unwrap(), 1);
+    assert_eq!(parse_recent_ref("@12").unwrap().unwrap(), 12);
+    assert!(parse_recent_ref("@0").unwrap().is_err());
+    assert!(parse_recent_ref("@last").unwrap().is_err());
+    assert!(parse_recent_ref("123").is_none());
+    assert!(parse_recent_ref("sessions/123").is_none());
+}
+
+#[test]
+fn test_resolve() {
+    let mut recent = RecentSessions::default();
+    recent.touch("111", None).unwrap();
+    recent.touch("222", None).unwrap();
+
+    assert_eq!(recent.resolve("@1").unwrap(), "222");
+    assert_eq!(recent.resolve("@2").unwrap(), "111");
+    assert_eq!(recent.resolve("333").unwrap(), "333");
+    let error = recent.resolve("@3").unwrap_err().to_string();
+    assert!(error.contains("only 2 remembered"), "{}", error);
+}
+
+#[test]
+fn test_save_and_load() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("gules").join("recent.json");
+
+    let mut recent = RecentSessions::default();
+    recent.touch("42", Some("Fix the login bug")).unwrap();
+    recent.save_to(&path).unwrap();
+
+    let loaded = RecentSessions::load_from(&path).unwrap();
+    assert_eq!(loaded.sessions, recent.sessions);
+}
+
+#[test]
+fn test_load_missing_file_is_empty() {
+    let dir = TempDir::new().unwrap();
+    let recent = RecentSessions::load_from(&dir.path().join("recent.json")).unwrap();
+    assert!(recent.sessions.is_empty());
+}
@@ -0,0 +1,156 @@
+//! Tests for the full-text search index.
+
+use chrono::Utc;
+use jules_core::search_index::{
+    grep_activities, grep_regex, searchable_parts, searchable_text, ContentKind, SearchIndex,
+};
+use jules_rs::types::activity::{Activity, AgentMessaged, Artifact, BashOutput};
+
+fn activity(id: &str, message: Option<&str>, bash: Option<(&str, &str)>) -> Activity {
+    Activity {
+        name: format!("sessions/s1/activities/{}", id),
+        id: id.to_string(),
+        description: None,
+        create_time: format!("2024-01-01T00:00:0{}Z", id),
+        originator: "agent".to_string(),
+        artifacts: bash
+            .map(|(command, output)| {
+                vec![Artifact {
+                    change_set: None,
+                    media: None,
+                    bash_output: Some(BashOutput {
+                        command: Some(command.to_string()),
+                        output: Some(output.to_string()),
+                        exit_code: Some(1),
+                    }),
+                }]
+            })
+            .unwrap_or_default(),
+        agent_messaged: message.map(|m| AgentMessaged {
+            agent_message: Some(m.to_string()),
+        }),
+        user_messaged: None,
+        plan_generated: None,
+        plan_approved: None,
+        progress_updated: None,
+        session_completed: None,
+        session_failed: None,
+    }
+}
+
+#[test]
+fn test_search_finds_substrings_in_bash_output() {
+    let mut index = SearchIndex::open_in_memory().unwrap();
+    let now = Utc::now();
+
+    index
+        .index_session(
+            "s1",
+            now,
+            &[
+                activity("1", Some("Refactored the parser"), None),
+                activity(
+                    "2",
+                    None,
+                    Some((
+                        "cargo test",
+                        "error[E0382]: borrow of moved value\n  --> src/main.rs:10",
+                    )),
+                ),
+            ],
+        )
+        .unwrap();
+    index
+        .index_session(
+            "sessions/s2",
+            now,
+            &[activity("3", Some("Touched src/main.rs"), None)],
+        )
+        .unwrap();
+
+    let hits = index.search("src/main.rs", None, 10).unwrap();
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].activity_id, "3");
+    assert_eq!(hits[1].matches, ["--> src/main.rs:10"]);
+
+    let hits = index.search("BORROW", Some("s1"), 10).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].session_id, "s1");
+
+    // Short patterns fall back to a substring scan
+    assert_eq!(index.search("rs", Some("s2"), 10).unwrap().len(), 1);
+    assert!(index.search("", None, 10).is_err());
+}
+
+#[test]
+fn test_index_refresh_and_retain() {
+    let mut index = SearchIndex::open_in_memory().unwrap();
+    let then = Utc::now() - chrono::Duration::seconds(60);
+
+    assert!(index.needs_update("s1", then).unwrap());
+    index
+        .index_session("s1", then, &[activity("1", Some("first"), None)])
+        .unwrap();
+    assert!(!index.needs_update("s1", then).unwrap());
+    assert!(index.needs_update("s1", Utc::now()).unwrap());
+
+    index.retain_sessions(&[]).unwrap();
+    assert!(index.search("first", None, 10).unwrap().is_empty());
+    assert!(index.needs_update("s1", then).unwrap());
+}
+
+#[test]
+fn test_searchable_text_includes_commands() {
+    let text = searchable_text(&activity("1", Some("hello"), Some(("ls -la", "total 0"))));
+    assert!(text.contains("hello"));
+    assert!(text.contains("ls -la"));
+    assert!(text.contains("total 0"));
+}
+
+#[test]
+fn test_searchable_parts_by_kind() {
+    let activity = activity("1", Some("hello"), Some(("ls -la", "total 0")));
+    assert_eq!(
+        searchable_parts(&activity, Some(ContentKind::Bash)),
+        vec!["ls -la", "total 0"]
+    );
+    assert_eq!(
+        searchable_parts(&activity, Some(ContentKind::Message)),
+        vec!["hello"]
+    );
+    assert!(searchable_parts(&activity, Some(ContentKind::Diff)).is_empty());
+}
+
+#[test]
+fn test_grep_activities() {
+    let activities = vec![
+        activity("1", Some("connect ECONNREFUSED 127.0.0.1"), None),
+        activity(
+            "2",
+            None,
+            Some(("npm test", "Error: connect econnrefused\nok")),
+        ),
+    ];
+
+    let regex = grep_regex(r"econn\w+", false).unwrap();
+    let hits = grep_activities("s1", &activities, &regex, None);
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].matches, vec!["connect ECONNREFUSED 127.0.0.1"]);
+    assert_eq!(hits[1].matches, vec!["Error: connect econnrefused"]);
+
+    let bash_only = grep_activities("s1", &activities, &regex, Some(ContentKind::Bash));
+    assert_eq!(bash_only.len(), 1);
+    assert_eq!(bash_only[0].activity_id, "2");
+
+    // Literal patterns don't treat metacharacters specially
+    let literal = grep_regex("127.0.0.1", true).unwrap();
+    assert_eq!(grep_activities("s1", &activities, &literal, None).len(), 1);
+}
+
+#[test]
+fn test_grep_regex_rejects_invalid_patterns() {
+    assert!(grep_regex("(unclosed", false).is_err());
+    assert!(grep_regex("", true).is_err());
+    assert_eq!(ContentKind::parse("Bash").unwrap(), ContentKind::Bash);
+    assert!(ContentKind::parse("plan").is_err());
+}
@@ -0,0 +1,66 @@
+//! Tests for the session metadata cache.
+
+mod common;
+
+use jules_core::session_cache::SessionStore;
+use jules_rs::types::session::Session;
+
+fn session(id: &str, create_time: &str, title: &str) -> Session {
+    common::session(
+        id,
+        serde_json::json!({
+            "title": title,
+            "createTime": create_time,
+            "state": "IN_PROGRESS",
+            "sourceContext": { "source": "sources/github/owner/repo" }
+        }),
+    )
+}
+
+#[test]
+fn test_session_store_roundtrip_and_ordering() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sessions.json");
+
+    let mut store = SessionStore::load_from(&path).unwrap();
+    assert!(store.last_refresh().is_none());
+
+    store
+        .record_list(&[
+            session("1", "2024-01-01T00:00:00Z", "old"),
+            session("2", "2024-02-01T00:00:00Z", "new"),
+        ])
+        .unwrap();
+    store.save_to(&path).unwrap();
+
+    let store = SessionStore::load_from(&path).unwrap();
+    let ids: Vec<&str> = store.list().iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, ["2", "1"]);
+    assert!(store.last_list_refresh.is_some());
+    assert_eq!(
+        store.get("sessions/1").unwrap().session.title.as_deref(),
+        Some("old")
+    );
+}
+
+#[test]
+fn test_session_store_upsert_replaces() {
+    let mut store = SessionStore::default();
+    store
+        .upsert(&session("1", "2024-01-01T00:00:00Z", "before"))
+        .unwrap();
+    store
+        .upsert(&session("1", "2024-01-01T00:00:00Z", "after"))
+        .unwrap();
+
+    assert_eq!(store.sessions.len(), 1);
+    assert_eq!(
+        store.get("1").unwrap().session.title.as_deref(),
+        Some("after")
+    );
+    assert!(store.last_list_refresh.is_none());
+    assert!(store.last_refresh().is_some());
+
+    assert!(store.remove("sessions/1").is_some());
+    assert!(store.list().is_empty());
+}
@@ -0,0 +1,81 @@
+//! Tests for session ID prefixes and suggestions.
+
+use jules_core::session_match::{match_prefix, suggest_ids, PrefixMatch};
+
+const KNOWN: [&str; 4] = [
+    "1234567890123",
+    "1234567899999",
+    "5550001112223",
+    "9876543210987",
+];
+
+#[test]
+fn test_unique_prefix() {
+    assert_eq!(
+        match_prefix("5550", KNOWN),
+        PrefixMatch::Unique("5550001112223".to_string())
+    );
+    assert_eq!(
+        match_prefix("12345678901", KNOWN),
+        PrefixMatch::Unique("1234567890123".to_string())
+    );
+}
+
+#[test]
+fn test_ambiguous_prefix() {
+    assert_eq!(
+        match_prefix("123456789", KNOWN),
+        PrefixMatch::Ambiguous(vec![
+            "1234567890123".to_string(),
+            "1234567899999".to_string()
+        ])
+    );
+}
+
+#[test]
+fn test_no_prefix_match() {
+    // Full IDs, unknown values and short prefixes pass through
+    assert_eq!(match_prefix("9876543210987", KNOWN), PrefixMatch::NoMatch);
+    assert_eq!(match_prefix("4444", KNOWN), PrefixMatch::NoMatch);
+    assert_eq!(match_prefix("555", KNOWN), PrefixMatch::NoMatch);
+    // An uncached full ID is not expanded to a longer cached one
+    assert_eq!(
+        match_prefix("9876543210987", ["98765432109870"]),
+        PrefixMatch::NoMatch
+    );
+}
+
+#[test]
+fn test_suggest_typos() {
+    // One wrong digit
+    assert_eq!(
+        suggest_ids("5550001112224", KNOWN, 3),
+        vec!["5550001112223"]
+    );
+    // Two swapped digits
+    assert_eq!(
+        suggest_ids("9876543201987", KNOWN, 3),
+        vec!["9876543210987"]
+    );
+    // A missing digit
+    assert_eq!(suggest_ids("555000111223", KNOWN, 3), vec!["5550001112223"]);
+}
+
+#[test]
+fn test_suggest_closest_first() {
+    assert_eq!(
+        suggest_ids("55500", ["55599", "55510"], 3),
+        vec!["55510", "55599"]
+    );
+    assert_eq!(suggest_ids("55500", ["55599", "55510"], 1), vec!["55510"]);
+    // Ties are ordered by ID
+    assert_eq!(
+        suggest_ids("1234567899129", KNOWN, 3),
+        vec!["1234567890123", "1234567899999"]
+    );
+}
+
+#[test]
+fn test_suggest_nothing_close() {
+    assert!(suggest_ids("1111111111111", KNOWN, 3).is_empty());
+}
@@ -0,0 +1,61 @@
+//! Tests for local session notes.
+
+use jules_core::session_notes::NoteStore;
+use tempfile::TempDir;
+
+#[test]
+fn test_notes_are_kept_per_session_in_order() {
+    let mut store = NoteStore::default();
+    assert_eq!(store.add("42", "Needs a migration follow-up").unwrap(), 1);
+    assert_eq!(
+        store
+            .add("sessions/42", "Ask about the flaky test")
+            .unwrap(),
+        2
+    );
+    assert_eq!(store.add("7", "Unrelated").unwrap(), 1);
+
+    let texts: Vec<&str> = store
+        .notes("42")
+        .iter()
+        .map(|note| note.text.as_str())
+        .collect();
+    assert_eq!(
+        texts,
+        vec!["Needs a migration follow-up", "Ask about the flaky test"]
+    );
+    assert_eq!(store.notes("sessions/7").len(), 1);
+    assert!(store.notes("8").is_empty());
+}
+
+#[test]
+fn test_clear() {
+    let mut store = NoteStore::default();
+    store.add("42", "One").unwrap();
+    store.add("42", "Two").unwrap();
+
+    assert_eq!(store.clear("sessions/42").unwrap(), 2);
+    assert!(store.notes("42").is_empty());
+    assert_eq!(store.clear("42").unwrap(), 0);
+}
+
+#[test]
+fn test_invalid_session_ids_are_rejected() {
+    let mut store = NoteStore::default();
+    assert!(store.add("sessions/", "text").is_err());
+    assert!(store.add("42/activities/1", "text").is_err());
+}
+
+#[test]
+fn test_save_and_load() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("gules").join("notes.json");
+    assert!(NoteStore::load_from(&path).unwrap().sessions.is_empty());
+
+    let mut store = NoteStore::default();
+    store.add("42", "Remember the migration").unwrap();
+    store.save_to(&path).unwrap();
+
+    let loaded = NoteStore::load_from(&path).unwrap();
+    assert_eq!(loaded.notes("42"), store.notes("42"));
+}
@@ -0,0 +1,44 @@
+//! Tests for finding session references in issue and pull request text.
+
+use jules_core::session_refs::SessionPatterns;
+
+#[test]
+fn test_default_patterns() {
+    let patterns = SessionPatterns::default();
+    let texts = [
+        "Jules is working on it: sessions/1234567890",
+        "session: abc-123\nSee https://jules.google.com/session/9876543210",
+        "Follow-up to sessions/1234567890",
+    ];
+    assert_eq!(
+        patterns.extract(texts),
+        vec!["1234567890", "abc-123", "9876543210"]
+    );
+    assert!(patterns.extract(["Fix the parser"]).is_empty());
+}
+
+#[test]
+fn test_custom_patterns_replace_defaults() {
+    let patterns = SessionPatterns::new(&[r"jules-(\d+)".to_string()]).unwrap();
+    assert_eq!(
+        patterns.extract(["jules-42-fix-parser", "sessions/7"]),
+        vec!["42"]
+    );
+}
+
+#[test]
+fn test_invalid_patterns() {
+    let error = SessionPatterns::new(&["jules-(".to_string()])
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains("Invalid github.session_patterns entry"),
+        "{}",
+        error
+    );
+
+    let error = SessionPatterns::new(&[r"jules-\d+".to_string()])
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("no capture group"), "{}", error);
+}
@@ -0,0 +1,184 @@
+//! Tests for period summaries of sessions.
+
+mod common;
+
+use chrono::{Duration, TimeZone, Utc};
+use jules_core::issue_links::IssueLinks;
+use jules_core::session_report::*;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn session(id: &str, state: &str, created: &str, updated: &str) -> Session {
+    common::session(
+        id,
+        json!({
+            "title": format!("Session {}", id),
+            "prompt": "Do things",
+            "state": state,
+            "createTime": created,
+            "updateTime": updated,
+        }),
+    )
+}
+
+#[test]
+fn test_in_report() {
+    let since = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let until = Utc.with_ymd_and_hms(2025, 1, 8, 0, 0, 0).unwrap();
+    let inside = session(
+        "1",
+        "COMPLETED",
+        "2025-01-03T10:00:00Z",
+        "2025-01-03T11:00:00Z",
+    );
+    let before = session(
+        "2",
+        "COMPLETED",
+        "2024-12-31T23:59:59Z",
+        "2025-01-01T01:00:00Z",
+    );
+
+    assert!(in_report(&inside, since, until, None));
+    assert!(!in_report(&before, since, until, None));
+    assert!(in_report(&inside, since, until, Some("octo/app")));
+    assert!(in_report(
+        &inside,
+        since,
+        until,
+        Some("sources/github/octo/app")
+    ));
+    assert!(!in_report(&inside, since, until, Some("app")));
+    assert!(!in_report(&inside, since, until, Some("octo/other")));
+}
+
+#[test]
+fn test_session_duration_only_when_finished() {
+    let completed = session(
+        "1",
+        "COMPLETED",
+        "2025-01-03T10:00:00Z",
+        "2025-01-03T12:30:00Z",
+    );
+    let running = session(
+        "2",
+        "IN_PROGRESS",
+        "2025-01-03T10:00:00Z",
+        "2025-01-03T12:30:00Z",
+    );
+    assert_eq!(session_duration(&completed), Some(Duration::minutes(150)));
+    assert_eq!(session_duration(&running), None);
+}
+
+#[test]
+fn test_report() {
+    let mut with_pr = session(
+        "1",
+        "COMPLETED",
+        "2025-01-02T10:00:00Z",
+        "2025-01-02T11:00:00Z",
+    );
+    with_pr.outputs = serde_json::from_value(json!([
+        { "pullRequest": { "url": "https://github.com/octo/app/pull/7" } }
+    ]))
+    .unwrap();
+    let sessions = vec![
+        with_pr,
+        session(
+            "2",
+            "FAILED",
+            "2025-01-03T10:00:00Z",
+            "2025-01-03T13:00:00Z",
+        ),
+        session(
+            "3",
+            "IN_PROGRESS",
+            "2025-01-04T10:00:00Z",
+            "2025-01-04T10:05:00Z",
+        ),
+        session(
+            "4",
+            "COMPLETED",
+            "2025-01-05T10:00:00Z",
+            "2025-01-05T12:00:00Z",
+        ),
+    ];
+    let reasons = HashMap::from([("2".to_string(), "Tests kept failing".to_string())]);
+    let since = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let until = Utc.with_ymd_and_hms(2025, 1, 8, 0, 0, 0).unwrap();
+
+    let report = SessionReport::new(&sessions, &reasons, since, until, None);
+    assert_eq!(report.total_sessions, 4);
+    let states: Vec<(&str, usize)> = report
+        .states
+        .iter()
+        .map(|count| (count.state.as_str(), count.count))
+        .collect();
+    assert_eq!(
+        states,
+        vec![("In Progress", 1), ("Failed", 1), ("Completed", 2)]
+    );
+    // (1h + 3h + 2h) / 3
+    assert_eq!(report.average_duration_seconds, Some(7200));
+    assert_eq!(report.average_duration().as_deref(), Some("2h"));
+    assert_eq!(report.pull_requests.len(), 1);
+    assert_eq!(
+        report.pull_requests[0].url,
+        "https://github.com/octo/app/pull/7"
+    );
+    assert_eq!(
+        report.failures[0].reason.as_deref(),
+        Some("Tests kept failing")
+    );
+
+    let md = report.to_markdown();
+    assert!(md.starts_with("# Jules report: 2025-01-01 to 2025-01-08\n"));
+    assert!(md.contains("- **Average duration:** 2h\n"));
+    assert!(md.contains("| Completed | 2 |\n"));
+    assert!(md.contains("- [Session 1](https://github.com/octo/app/pull/7) (`1`)\n"));
+    assert!(md.contains("- Session 2 (`2`): Tests kept failing\n"));
+
+    let mut links = IssueLinks::default();
+    links.link("octo", "app", 12, "1").unwrap();
+    links.link("octo", "app", 13, "2").unwrap();
+    links.link("octo", "app", 14, "2").unwrap();
+    let report = report.with_issue_links(&links);
+    assert_eq!(report.pull_requests[0].issues, vec!["octo/app#12"]);
+    assert_eq!(
+        report.failures[0].issues,
+        vec!["octo/app#13", "octo/app#14"]
+    );
+
+    let md = report.to_markdown();
+    assert!(
+        md.contains("- [Session 1](https://github.com/octo/app/pull/7) (`1`) for octo/app#12\n")
+    );
+    assert!(md.contains("- Session 2 (`2`) for octo/app#13, octo/app#14: Tests kept failing\n"));
+}
+
+#[test]
+fn test_failure_reason() {
+    let activities: Vec<Activity> = serde_json::from_value(json!([
+        {
+            "name": "sessions/2/activities/b",
+            "id": "b",
+            "createTime": "2025-01-03T13:00:00Z",
+            "originator": "system",
+            "sessionFailed": { "reason": "Tests kept failing" }
+        },
+        {
+            "name": "sessions/2/activities/a",
+            "id": "a",
+            "createTime": "2025-01-03T10:00:00Z",
+            "originator": "agent",
+            "agentMessaged": { "agentMessage": "Starting" }
+        }
+    ]))
+    .unwrap();
+    assert_eq!(
+        failure_reason(&activities).as_deref(),
+        Some("Tests kept failing")
+    );
+    assert_eq!(failure_reason(&activities[1..]), None);
+}
@@ -0,0 +1,95 @@
+//! Tests for client-side sorting of listings.
+
+mod common;
+
+use jules_core::sorting::*;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use serde_json::json;
+
+fn session(id: &str, title: Option<&str>, created: Option<&str>, state: &str) -> Session {
+    common::session(
+        id,
+        json!({
+            "title": title,
+            "prompt": "p",
+            "createTime": created,
+            "state": state
+        }),
+    )
+}
+
+fn ids(sessions: &[Session]) -> Vec<&str> {
+    sessions.iter().map(|s| s.id.as_str()).collect()
+}
+
+fn sessions() -> Vec<Session> {
+    vec![
+        session("a", Some("beta"), Some("2025-01-02T00:00:00Z"), "COMPLETED"),
+        session("b", None, None, "QUEUED"),
+        // Later than "a" despite sorting lower as a string
+        session(
+            "c",
+            Some("Alpha"),
+            Some("2025-01-02T00:00:00.5Z"),
+            "IN_PROGRESS",
+        ),
+        session("d", Some("gamma"), Some("2025-01-01T00:00:00Z"), "FAILED"),
+    ]
+}
+
+#[test]
+fn test_parse_sort_keys() {
+    assert_eq!(SessionSort::parse("Created").unwrap(), SessionSort::Created);
+    assert!(SessionSort::parse("size").is_err());
+    assert_eq!(ActivitySort::parse("time").unwrap(), ActivitySort::Created);
+    assert!(ActivitySort::parse("title").is_err());
+}
+
+#[test]
+fn test_sort_sessions_by_created() {
+    let mut list = sessions();
+    sort_sessions(&mut list, SortOrder::new(SessionSort::Created, false));
+    assert_eq!(ids(&list), vec!["d", "a", "c", "b"]);
+
+    // Missing values stay last when descending
+    sort_sessions(&mut list, SortOrder::new(SessionSort::Created, true));
+    assert_eq!(ids(&list), vec!["c", "a", "d", "b"]);
+}
+
+#[test]
+fn test_sort_sessions_by_title_and_state() {
+    let mut list = sessions();
+    sort_sessions(&mut list, SortOrder::new(SessionSort::Title, false));
+    assert_eq!(ids(&list), vec!["c", "a", "d", "b"]);
+
+    sort_sessions(&mut list, SortOrder::new(SessionSort::State, false));
+    assert_eq!(ids(&list), vec!["b", "c", "d", "a"]);
+}
+
+#[test]
+fn test_sort_activities() {
+    let activity = |id: &str, created: &str, originator: &str| -> Activity {
+        serde_json::from_value(json!({
+            "name": format!("sessions/1/activities/{}", id),
+            "id": id,
+            "createTime": created,
+            "originator": originator
+        }))
+        .unwrap()
+    };
+    let mut list = vec![
+        activity("1", "2025-01-01T00:00:02Z", "user"),
+        activity("2", "2025-01-01T00:00:01Z", "agent"),
+        activity("3", "2025-01-01T00:00:03Z", "agent"),
+    ];
+
+    sort_activities(&mut list, SortOrder::new(ActivitySort::Created, true));
+    let ids: Vec<_> = list.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, vec!["3", "1", "2"]);
+
+    // Stable: ties keep their previous order
+    sort_activities(&mut list, SortOrder::new(ActivitySort::Originator, false));
+    let ids: Vec<_> = list.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, vec!["3", "2", "1"]);
+}
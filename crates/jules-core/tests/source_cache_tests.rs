@@ -0,0 +1,131 @@
+//! Tests for the sources cache.
+
+use jules_core::source_cache::{fetch_all_sources, not_connected_message, SourceStore};
+use jules_rs::types::source::Source;
+use jules_rs::JulesClient;
+
+fn source(id: &str) -> Source {
+    serde_json::from_value(serde_json::json!({
+        "name": format!("sources/{}", id),
+        "id": id,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_source_store_roundtrip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sources.json");
+
+    let mut store = SourceStore::load_from(&path).unwrap();
+    assert!(store.refreshed_at.is_none());
+
+    store.replace(vec![source("github/b/repo"), source("github/a/repo")]);
+    store.save_to(&path).unwrap();
+
+    let store = SourceStore::load_from(&path).unwrap();
+    assert!(store.refreshed_at.is_some());
+    assert_eq!(store.ids(), ["github/a/repo", "github/b/repo"]);
+}
+
+#[test]
+fn test_expand_source_shorthands() {
+    let mut store = SourceStore::default();
+    store.replace(vec![
+        source("github/octo/api"),
+        source("github/octo/web"),
+        source("github/acme/Web"),
+    ]);
+
+    let expand = |spec: &str| store.expand(spec).unwrap();
+    assert_eq!(expand("sources/github/x/y"), "sources/github/x/y");
+    assert_eq!(expand("github/octo/api"), "sources/github/octo/api");
+    assert_eq!(expand("octo/api"), "sources/github/octo/api");
+    assert_eq!(expand("Octo/API"), "sources/github/octo/api");
+    assert_eq!(expand("api"), "sources/github/octo/api");
+    // owner/repo needs no cache entry
+    assert_eq!(expand("other/repo"), "sources/github/other/repo");
+
+    let err = store.expand("web").unwrap_err().to_string();
+    assert!(err.contains("Ambiguous source 'web'"), "{}", err);
+    assert!(err.contains("sources/github/octo/web"), "{}", err);
+    assert!(err.contains("sources/github/acme/Web"), "{}", err);
+
+    let err = store.expand("missing").unwrap_err().to_string();
+    assert!(err.contains("Unknown source 'missing'"), "{}", err);
+}
+
+#[test]
+fn test_expand_bare_repo_without_cache() {
+    let err = SourceStore::default()
+        .expand("api")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("gules sources refresh"), "{}", err);
+}
+
+#[test]
+fn test_find_cached_source() {
+    let mut store = SourceStore::default();
+    store.replace(vec![source("github/octo/api")]);
+
+    assert_eq!(
+        store.find("sources/github/Octo/API").map(|s| s.id.as_str()),
+        Some("github/octo/api")
+    );
+    assert!(store.find("sources/github/octo/web").is_none());
+}
+
+#[test]
+fn test_not_connected_message() {
+    let message = not_connected_message("sources/github/octo/api");
+    assert!(
+        message.starts_with("octo/api is not connected to Jules"),
+        "{}",
+        message
+    );
+    assert!(message.contains("https://jules.google.com"), "{}", message);
+    assert!(
+        message.contains("https://github.com/organizations/octo/settings/installations"),
+        "{}",
+        message
+    );
+    assert!(
+        message.ends_with("'gules sources check octo/api'"),
+        "{}",
+        message
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_all_sources_follows_page_tokens() {
+    let mut server = mockito::Server::new_async().await;
+
+    let first = server
+        .mock("GET", "/sources?pageSize=100")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"sources": [{"name": "sources/github/a/one", "id": "github/a/one"}], "nextPageToken": "p2"}"#,
+        )
+        .create_async()
+        .await;
+    let second = server
+        .mock("GET", "/sources?pageSize=100&pageToken=p2")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"sources": [{"name": "sources/github/a/two", "id": "github/a/two"}]}"#)
+        .create_async()
+        .await;
+
+    let client = JulesClient::with_config(jules_rs::JulesConfig {
+        api_key: "test-key".to_string(),
+        base_url: server.url(),
+    });
+
+    let sources = fetch_all_sources(&client).await.unwrap();
+    assert_eq!(sources.len(), 2);
+
+    first.assert_async().await;
+    second.assert_async().await;
+}
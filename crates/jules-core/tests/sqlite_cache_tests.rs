@@ -0,0 +1,311 @@
+//! Tests for the SQLite activity cache backend.
+
+use chrono::{Duration, Utc};
+use jules_core::{ActivityQuery, CacheConfig, EvictionPolicy, SessionStatsSort, SqliteCache};
+use jules_rs::types::activity::{
+    Activity, AgentMessaged, Artifact, BashOutput, ListActivitiesResponse,
+};
+
+fn activity(id: &str, seconds_ago: i64) -> Activity {
+    Activity {
+        name: format!("sessions/s1/activities/{}", id),
+        id: id.to_string(),
+        description: None,
+        create_time: (Utc::now() - Duration::seconds(seconds_ago)).to_rfc3339(),
+        originator: "agent".to_string(),
+        artifacts: vec![],
+        agent_messaged: None,
+        user_messaged: None,
+        plan_generated: None,
+        plan_approved: None,
+        progress_updated: None,
+        session_completed: None,
+        session_failed: None,
+    }
+}
+
+fn bash_activity(id: &str, seconds_ago: i64, exit_code: i32) -> Activity {
+    let mut activity = activity(id, seconds_ago);
+    activity.artifacts = vec![Artifact {
+        change_set: None,
+        media: None,
+        bash_output: Some(BashOutput {
+            command: Some("cargo test".to_string()),
+            output: Some("ok".to_string()),
+            exit_code: Some(exit_code),
+        }),
+    }];
+    activity
+}
+
+fn message_activity(id: &str, seconds_ago: i64) -> Activity {
+    let mut activity = activity(id, seconds_ago);
+    activity.agent_messaged = Some(AgentMessaged {
+        agent_message: Some("hello".to_string()),
+    });
+    activity
+}
+
+fn page(activities: Vec<Activity>, token: Option<&str>) -> ListActivitiesResponse {
+    ListActivitiesResponse {
+        activities,
+        next_page_token: token.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_incremental_updates_deduplicate() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = SqliteCache::open(dir.path().join("cache.db"), 10).unwrap();
+
+    store
+        .update_incremental(
+            "s1",
+            &page(vec![activity("1", 30), activity("2", 20)], Some("t1")),
+        )
+        .unwrap();
+    store
+        .update_incremental(
+            "sessions/s1",
+            &page(vec![activity("2", 20), activity("3", 10)], None),
+        )
+        .unwrap();
+
+    let cache = store.load_session("s1").unwrap().unwrap();
+    let ids: Vec<&str> = cache.activities.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, ["3", "2", "1"]);
+    assert_eq!(cache.last_page_token, None);
+    assert!(store.has_session("sessions/s1").unwrap());
+}
+
+#[test]
+fn test_query_uses_indexed_filters() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = SqliteCache::open(dir.path().join("cache.db"), 10).unwrap();
+
+    store
+        .update_incremental(
+            "s1",
+            &page(
+                vec![
+                    bash_activity("1", 40, 0),
+                    bash_activity("2", 30, 101),
+                    message_activity("3", 20),
+                    activity("4", 10),
+                ],
+                None,
+            ),
+        )
+        .unwrap();
+
+    let failed = store
+        .query(&ActivityQuery {
+            session_id: Some("s1".to_string()),
+            exit_code: Some(101),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].id, "2");
+
+    let failed_only = store
+        .query(&ActivityQuery {
+            failed_only: true,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(failed_only.len(), 1);
+    assert_eq!(failed_only[0].id, "2");
+
+    let bash = store
+        .query(&ActivityQuery {
+            has_bash_output: true,
+            limit: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(bash.len(), 1);
+    assert_eq!(bash[0].id, "2");
+
+    let messages = store
+        .query(&ActivityQuery {
+            activity_types: vec!["Agent Messaged".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].id, "3");
+
+    let recent = store
+        .query(&ActivityQuery {
+            since: Some((Utc::now() - Duration::seconds(25)).to_rfc3339()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(recent.len(), 2);
+}
+
+#[test]
+fn test_eviction_delete_and_clear() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = SqliteCache::open(dir.path().join("cache.db"), 2).unwrap();
+
+    for session in ["a", "b", "c"] {
+        store
+            .update_incremental(session, &page(vec![activity("1", 10)], None))
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    // Oldest session evicted (FIFO), its activities cascade
+    assert_eq!(store.list_sessions().unwrap(), ["b", "c"]);
+    assert_eq!(store.stats(true).unwrap().total_activities, 2);
+
+    store.delete_session("b").unwrap();
+    assert!(!store.has_session("b").unwrap());
+
+    store.clear().unwrap();
+    assert_eq!(store.stats(true).unwrap().total_sessions, 0);
+}
+
+#[test]
+fn test_pinned_sessions_are_not_evicted() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = SqliteCache::open(dir.path().join("cache.db"), 2)
+        .unwrap()
+        .with_pinned(vec!["a".to_string()]);
+
+    for session in ["a", "b", "c"] {
+        store
+            .update_incremental(session, &page(vec![activity("1", 10)], None))
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    assert_eq!(store.list_sessions().unwrap(), ["a", "c"]);
+}
+
+#[test]
+fn test_lru_eviction_keeps_recently_read_sessions() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = SqliteCache::open(dir.path().join("cache.db"), 2)
+        .unwrap()
+        .with_limits(EvictionPolicy::Lru, None);
+
+    for session in ["a", "b"] {
+        store
+            .update_incremental(session, &page(vec![activity("1", 10)], None))
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    // Reading `a` makes `b` the least recently used; peeking doesn't count
+    store.load_session("a").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    store.peek_session("b").unwrap();
+    store
+        .update_incremental("c", &page(vec![activity("1", 10)], None))
+        .unwrap();
+
+    assert_eq!(store.list_sessions().unwrap(), ["a", "c"]);
+}
+
+#[test]
+fn test_size_limit_evicts_oldest_sessions() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = SqliteCache::open(dir.path().join("cache.db"), 10)
+        .unwrap()
+        .with_limits(EvictionPolicy::Fifo, Some(1));
+
+    for session in ["a", "b", "c"] {
+        let mut large = bash_activity("1", 10, 0);
+        large.artifacts[0].bash_output.as_mut().unwrap().output = Some("x".repeat(400 * 1024));
+        store
+            .update_incremental(session, &page(vec![large], None))
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    // 3 × 400 KiB exceed 1 MiB; dropping `a` is enough
+    assert_eq!(store.list_sessions().unwrap(), ["b", "c"]);
+}
+
+#[test]
+fn test_per_session_stats() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = SqliteCache::open(dir.path().join("cache.db"), 10)
+        .unwrap()
+        .with_pinned(vec!["b".to_string()]);
+
+    store
+        .update_incremental("a", &page(vec![activity("1", 10)], None))
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    store
+        .update_incremental(
+            "b",
+            &page(vec![activity("2", 10), message_activity("3", 5)], None),
+        )
+        .unwrap();
+
+    let mut stats = store.stats(true).unwrap();
+    let ids: Vec<&str> = stats
+        .sessions
+        .iter()
+        .map(|s| s.session_id.as_str())
+        .collect();
+    assert_eq!(ids, ["a", "b"]);
+    assert_eq!(stats.sessions[1].activities, 2);
+    assert!(stats.sessions[1].pinned);
+    assert!(stats.sessions[1].size_bytes > stats.sessions[0].size_bytes);
+    assert!(stats.sessions[0].last_updated.is_some());
+
+    stats.sort_sessions(SessionStatsSort::Activities);
+    assert_eq!(stats.sessions[0].session_id, "b");
+    stats.sort_sessions(SessionStatsSort::Updated);
+    assert_eq!(stats.sessions[0].session_id, "b");
+    assert_eq!(
+        SessionStatsSort::parse("SIZE").unwrap(),
+        SessionStatsSort::Size
+    );
+    assert!(SessionStatsSort::parse("color").is_err());
+}
+
+#[test]
+fn test_encryption_is_rejected() {
+    let config = CacheConfig {
+        encrypt: true,
+        ..Default::default()
+    };
+    let error = SqliteCache::open_configured(&config).err().unwrap();
+    assert!(error.to_string().contains("cache.encrypt is not supported"));
+}
+
+#[test]
+fn test_schema_versions() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("activities.db");
+    drop(SqliteCache::open(&db, 10).unwrap());
+
+    // Version 1 databases gain the access time column used by LRU eviction
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    conn.execute_batch("ALTER TABLE sessions DROP COLUMN last_accessed")
+        .unwrap();
+    conn.pragma_update(None, "user_version", 1).unwrap();
+    drop(conn);
+    drop(SqliteCache::open(&db, 10).unwrap());
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    let version: i64 = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(version, 2);
+    conn.execute("UPDATE sessions SET last_accessed = NULL", [])
+        .unwrap();
+    drop(conn);
+
+    // Databases from a newer gules are rejected
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    conn.pragma_update(None, "user_version", 99).unwrap();
+    drop(conn);
+    let error = SqliteCache::open(&db, 10).err().unwrap().to_string();
+    assert!(error.contains("newer version"), "{}", error);
+}
@@ -0,0 +1,92 @@
+//! Tests for table column selection.
+
+use jules_core::table_columns::*;
+use jules_core::Config;
+
+#[test]
+fn test_parse_columns() {
+    assert_eq!(
+        parse_columns::<SessionColumn>("id, STATE,title,pr").unwrap(),
+        vec![
+            SessionColumn::Id,
+            SessionColumn::State,
+            SessionColumn::Title,
+            SessionColumn::Pr
+        ]
+    );
+    assert_eq!(
+        parse_columns::<ActivityColumn>("created,type,content").unwrap(),
+        vec![
+            ActivityColumn::Time,
+            ActivityColumn::Type,
+            ActivityColumn::Content
+        ]
+    );
+    assert_eq!(
+        parse_columns::<SourceColumn>("repo,default-branch").unwrap(),
+        vec![SourceColumn::Repo, SourceColumn::DefaultBranch]
+    );
+
+    let err = parse_columns::<SessionColumn>("id,content").unwrap_err();
+    assert!(err.to_string().contains("Unknown session column: content"));
+    assert!(parse_columns::<SessionColumn>(" , ").is_err());
+}
+
+#[test]
+fn test_validate_columns() {
+    // Valid for at least one table
+    assert!(validate_columns("id,state,title").is_ok());
+    assert!(validate_columns("type,content").is_ok());
+    assert!(validate_columns("owner,repo").is_ok());
+    assert!(validate_columns("id,colour").is_err());
+}
+
+#[test]
+fn test_defaults_match_original_layout() {
+    assert_eq!(
+        SessionColumn::DEFAULT
+            .iter()
+            .map(|column| column.header())
+            .collect::<Vec<_>>(),
+        vec!["Title", "Session ID", "State", "Created", "PR"]
+    );
+    assert_eq!(
+        ActivityColumn::DEFAULT
+            .iter()
+            .map(|column| column.header())
+            .collect::<Vec<_>>(),
+        vec!["Info", "Time", "Content"]
+    );
+}
+
+// The selection is process-wide, so it is checked in one test
+#[test]
+fn test_selected_columns() {
+    assert_eq!(
+        selected_columns::<SessionColumn>(),
+        SessionColumn::DEFAULT.to_vec()
+    );
+
+    set_table_columns(Some("id,state".to_string()));
+    assert_eq!(
+        selected_columns::<SessionColumn>(),
+        vec![SessionColumn::Id, SessionColumn::State]
+    );
+    // Not a sources selection: the sources table keeps its default
+    assert_eq!(
+        selected_columns::<SourceColumn>(),
+        SourceColumn::DEFAULT.to_vec()
+    );
+
+    set_table_columns(None);
+}
+
+#[test]
+fn test_columns_in_config() {
+    let config: Config =
+        toml::from_str("[display.columns]\nsessions = \"id,state,title\"\n").unwrap();
+    assert_eq!(
+        config.display.columns.get("sessions").map(String::as_str),
+        Some("id,state,title")
+    );
+}
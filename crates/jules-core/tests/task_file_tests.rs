@@ -0,0 +1,94 @@
+//! Tests for task files.
+
+use jules_core::task_file::{parse_tasks, BatchTask};
+
+#[test]
+fn test_yaml_list() {
+    let tasks = parse_tasks(
+        "\
+- prompt: Add tests
+  source: sources/github/octo/app
+  branch: develop
+  title: Tests
+- prompt: |
+    Fix the flaky test.
+    It fails on CI.
+",
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        tasks,
+        vec![
+            BatchTask {
+                prompt: "Add tests".to_string(),
+                source: Some("sources/github/octo/app".to_string()),
+                branch: Some("develop".to_string()),
+                title: Some("Tests".to_string()),
+                require_approval: None,
+                automation_mode: None,
+            },
+            BatchTask {
+                prompt: "Fix the flaky test.\nIt fails on CI.\n".to_string(),
+                source: None,
+                branch: None,
+                title: None,
+                require_approval: None,
+                automation_mode: None,
+            },
+        ]
+    );
+    assert_eq!(tasks[0].label(), "Tests");
+    assert_eq!(tasks[1].label(), "Fix the flaky test.");
+}
+
+#[test]
+fn test_tasks_mapping_and_json() {
+    let yaml = parse_tasks("tasks:\n  - prompt: One\n", false).unwrap();
+    assert_eq!(yaml[0].prompt, "One");
+
+    let json = parse_tasks(
+        r#"{"tasks": [{"prompt": "One", "require_approval": true}]}"#,
+        true,
+    )
+    .unwrap();
+    assert_eq!(json[0].prompt, "One");
+    assert_eq!(json[0].require_approval, Some(true));
+}
+
+#[test]
+fn test_flow_style() {
+    let tasks = parse_tasks(
+        "\
+- {prompt: One, source: octo/app, branch: develop}
+- {prompt: Two, source: octo/app}
+",
+        false,
+    )
+    .unwrap();
+    assert_eq!(tasks[0].branch.as_deref(), Some("develop"));
+    assert_eq!(tasks[1].prompt, "Two");
+}
+
+#[test]
+fn test_invalid_tasks() {
+    let error = format!(
+        "{:#}",
+        parse_tasks("- prompt: One\n- prompt: Two\n  sorce: typo\n", false).unwrap_err()
+    );
+    assert!(error.contains("Task 2"), "{}", error);
+    assert!(error.contains("sorce"), "{}", error);
+
+    let error = format!(
+        "{:#}",
+        parse_tasks("- title: No prompt\n", false).unwrap_err()
+    );
+    assert!(error.contains("prompt"), "{}", error);
+
+    let error = format!("{:#}", parse_tasks("- prompt: '  '\n", false).unwrap_err());
+    assert!(error.contains("Task 1: empty prompt"), "{}", error);
+
+    assert!(parse_tasks("# nothing\n", false).is_err());
+    assert!(parse_tasks("prompt: not a list\n", false).is_err());
+}
@@ -0,0 +1,69 @@
+//! Tests for the local task queue.
+
+use jules_core::task_queue::TaskQueue;
+use jules_rs::types::session::{
+    AutomationMode, CreateSessionRequest, GitHubRepoContext, SourceContext,
+};
+use tempfile::TempDir;
+
+fn request(prompt: &str, title: Option<&str>) -> CreateSessionRequest {
+    CreateSessionRequest {
+        prompt: prompt.to_string(),
+        source_context: SourceContext {
+            source: "sources/github/octo/app".to_string(),
+            github_repo_context: Some(GitHubRepoContext {
+                starting_branch: "main".to_string(),
+            }),
+        },
+        title: title.map(str::to_string),
+        require_plan_approval: Some(false),
+        automation_mode: Some(AutomationMode::AutoCreatePr),
+    }
+}
+
+#[test]
+fn test_push_and_remove_keep_ids_increasing() {
+    let mut queue = TaskQueue::default();
+    assert_eq!(queue.push(request("One", None)), 1);
+    assert_eq!(queue.push(request("Two", None)), 2);
+
+    assert_eq!(queue.remove(1).map(|task| task.id), Some(1));
+    assert!(queue.remove(1).is_none());
+    assert_eq!(queue.front().map(|task| task.id), Some(2));
+
+    // IDs are never reused, even once the queue empties
+    queue.remove(2);
+    assert_eq!(queue.push(request("Three", None)), 3);
+}
+
+#[test]
+fn test_save_and_load_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("nested").join("queue.json");
+    assert!(TaskQueue::load_from(&path).unwrap().tasks.is_empty());
+
+    let mut queue = TaskQueue::default();
+    queue.push(request("Fix the bug\nwith details", None));
+    queue.push(request("Add tests", Some("Tests")));
+    queue.save_to(&path).unwrap();
+
+    let loaded = TaskQueue::load_from(&path).unwrap();
+    assert_eq!(loaded.last_id, 2);
+    assert_eq!(loaded.tasks.len(), 2);
+    assert_eq!(loaded.tasks[0].request.prompt, "Fix the bug\nwith details");
+    assert_eq!(loaded.tasks[0].label(), "Fix the bug");
+    assert_eq!(loaded.tasks[1].label(), "Tests");
+    assert_eq!(
+        loaded.tasks[1]
+            .request
+            .source_context
+            .github_repo_context
+            .as_ref()
+            .map(|context| context.starting_branch.as_str()),
+        Some("main")
+    );
+    assert_eq!(
+        loaded.tasks[1].request.automation_mode,
+        Some(AutomationMode::AutoCreatePr)
+    );
+}
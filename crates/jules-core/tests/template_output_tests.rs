@@ -0,0 +1,123 @@
+//! Tests for user-defined output templates.
+
+mod common;
+
+use jules_core::config::Config;
+use jules_core::template_output::*;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::Session;
+use serde_json::json;
+
+fn session() -> Session {
+    common::session(
+        "42",
+        json!({
+            "title": "Fix login",
+            "prompt": "Fix the login bug",
+            "sourceContext": {
+                "source": "sources/github/octo/app",
+                "githubRepoContext": { "startingBranch": "develop" }
+            },
+            "state": "IN_PROGRESS",
+            "outputs": [{ "pullRequest": { "url": "https://github.com/octo/app/pull/7" } }]
+        }),
+    )
+}
+
+#[test]
+fn test_render_fields() {
+    let template = Template::parse("{{id}} {{ state }} {{title}}").unwrap();
+    assert_eq!(
+        template.render(&session_record(&session())).unwrap(),
+        "42 IN_PROGRESS Fix login"
+    );
+}
+
+#[test]
+fn test_nested_paths_and_convenience_fields() {
+    let template = Template::parse(
+        "{{sourceContext.source}}@{{branch}} {{outputs[0].pullRequest.url}} {{prUrl}}",
+    )
+    .unwrap();
+    assert_eq!(
+        template.render(&session_record(&session())).unwrap(),
+        "sources/github/octo/app@develop https://github.com/octo/app/pull/7 https://github.com/octo/app/pull/7"
+    );
+}
+
+#[test]
+fn test_missing_values_and_defaults() {
+    let record = json!({ "title": "", "count": 3, "gone": null, "tags": ["a"] });
+    let template = Template::parse(
+        "[{{missing}}{{gone}}] {{title|default(\"Untitled\", true)}} {{count}} {{tags[5]|default(\"none\")}}",
+    )
+    .unwrap();
+    assert_eq!(template.render(&record).unwrap(), "[] Untitled 3 none");
+}
+
+#[test]
+fn test_conditionals_and_loops() {
+    let template = Template::parse(
+        "{{id}}{% if prUrl %} -> {{prUrl}}{% endif %}{% for o in outputs %} #{{loop.index}}{% endfor %}",
+    )
+    .unwrap();
+    assert_eq!(
+        template.render(&session_record(&session())).unwrap(),
+        "42 -> https://github.com/octo/app/pull/7 #1"
+    );
+}
+
+#[test]
+fn test_invalid_templates() {
+    assert!(Template::parse("{{id").is_err());
+    assert!(Template::parse("{% if id %}").is_err());
+    assert!(Template::parse("no placeholders").is_ok());
+}
+
+#[test]
+fn test_activity_record() {
+    let activity: Activity = serde_json::from_value(json!({
+        "name": "sessions/42/activities/a1",
+        "id": "a1",
+        "createTime": "2025-01-01T00:00:00Z",
+        "originator": "agent",
+        "progressUpdated": { "title": "Running tests" }
+    }))
+    .unwrap();
+
+    let template = Template::parse("{{id}} {{type}} {{originator}}").unwrap();
+    assert_eq!(
+        template.render(&activity_record(&activity)).unwrap(),
+        format!("a1 {} agent", activity.activity_type())
+    );
+}
+
+#[test]
+fn test_named_output_templates() {
+    let mut config = Config::default();
+    config
+        .output_templates
+        .insert("short".to_string(), "{{id}}: {{title}}".to_string());
+
+    let record = session_record(&session());
+    assert_eq!(
+        config
+            .output_template("short")
+            .unwrap()
+            .render(&record)
+            .unwrap(),
+        "42: Fix login"
+    );
+    // Inline templates are used as given
+    assert_eq!(
+        config
+            .output_template("{{state}}")
+            .unwrap()
+            .render(&record)
+            .unwrap(),
+        "IN_PROGRESS"
+    );
+
+    let err = config.output_template("long").unwrap_err();
+    assert!(err.to_string().contains("available: short"));
+}
@@ -0,0 +1,127 @@
+//! Tests for command-line time specifications.
+
+use chrono::{Duration, TimeZone, Utc};
+use jules_core::time_range::{format_duration, parse_duration, parse_time, TimeRange};
+
+#[test]
+fn test_parse_duration_units() {
+    assert_eq!(parse_duration("45s").unwrap(), Duration::seconds(45));
+    assert_eq!(parse_duration("90m").unwrap(), Duration::minutes(90));
+    assert_eq!(parse_duration("12h").unwrap(), Duration::hours(12));
+    assert_eq!(parse_duration("30d").unwrap(), Duration::days(30));
+    assert_eq!(parse_duration("2W").unwrap(), Duration::weeks(2));
+}
+
+#[test]
+fn test_parse_duration_combined() {
+    assert_eq!(
+        parse_duration("1d12h").unwrap(),
+        Duration::days(1) + Duration::hours(12)
+    );
+    assert_eq!(parse_duration(" 0s ").unwrap(), Duration::zero());
+}
+
+#[test]
+fn test_parse_duration_invalid() {
+    for spec in [
+        "",
+        "30",
+        "d",
+        "30x",
+        "1.5d",
+        "-3d",
+        "30 d",
+        "99999999999999999999d",
+    ] {
+        assert!(
+            parse_duration(spec).is_err(),
+            "{:?} should be rejected",
+            spec
+        );
+    }
+}
+
+#[test]
+fn test_parse_time_absolute() {
+    assert_eq!(
+        parse_time("2025-01-15T10:30:00+02:00").unwrap(),
+        Utc.with_ymd_and_hms(2025, 1, 15, 8, 30, 0).unwrap()
+    );
+    assert_eq!(
+        parse_time("2025-01-15").unwrap(),
+        Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_parse_time_relative() {
+    let before = Utc::now();
+    let two_hours_ago = parse_time("2h").unwrap();
+    let now = parse_time("NOW").unwrap();
+    let after = Utc::now();
+
+    assert!(two_hours_ago >= before - Duration::hours(2));
+    assert!(two_hours_ago <= after - Duration::hours(2));
+    assert!(now >= before && now <= after);
+}
+
+#[test]
+fn test_parse_time_invalid() {
+    for spec in ["", "yesterday", "2025-13-01", "15/01/2025"] {
+        assert!(parse_time(spec).is_err(), "{:?} should be rejected", spec);
+    }
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::seconds(0)), "0s");
+    assert_eq!(format_duration(Duration::seconds(45)), "45s");
+    assert_eq!(format_duration(Duration::minutes(135)), "2h 15m");
+    assert_eq!(format_duration(Duration::hours(3)), "3h");
+    assert_eq!(
+        format_duration(Duration::days(3) + Duration::hours(4) + Duration::minutes(5)),
+        "3d 4h"
+    );
+    assert_eq!(
+        format_duration(Duration::days(1) + Duration::minutes(5)),
+        "1d"
+    );
+}
+
+#[test]
+fn test_time_range_contains() {
+    let range = TimeRange::parse(Some("2025-01-15"), Some("2025-01-16T12:00:00Z")).unwrap();
+    assert!(range.contains("2025-01-15T00:00:00Z"));
+    assert!(range.contains("2025-01-16T13:59:59+02:00"));
+    assert!(!range.contains("2025-01-14T23:59:59Z"));
+    assert!(!range.contains("2025-01-16T12:00:00Z"));
+    assert!(!range.contains("not a time"));
+    assert_eq!(
+        range.since_rfc3339().as_deref(),
+        Some("2025-01-15T00:00:00Z")
+    );
+    assert_eq!(
+        range.until_rfc3339().as_deref(),
+        Some("2025-01-16T12:00:00Z")
+    );
+}
+
+#[test]
+fn test_time_range_open_ends() {
+    let unbounded = TimeRange::parse(None, None).unwrap();
+    assert!(unbounded.is_unbounded());
+    assert!(unbounded.contains("not a time"));
+    assert_eq!(unbounded.since_rfc3339(), None);
+
+    let since = TimeRange::parse(Some("2h"), None).unwrap();
+    assert!(!since.is_unbounded());
+    assert!(since.contains(&Utc::now().to_rfc3339()));
+    assert!(!since.contains("2020-01-01T00:00:00Z"));
+}
+
+#[test]
+fn test_time_range_rejects_reversed_bounds() {
+    let error = TimeRange::parse(Some("2025-01-16"), Some("2025-01-15")).unwrap_err();
+    assert_eq!(error.to_string(), "--since must be before --until");
+    assert!(TimeRange::parse(Some("yesterday"), None).is_err());
+}
@@ -0,0 +1,71 @@
+//! Tests for webhook notifications of finished sessions.
+
+mod common;
+
+use jules_core::webhook::*;
+use jules_rs::types::activity::Activity;
+use jules_rs::types::session::{Session, State};
+use serde_json::json;
+
+fn session(state: &str) -> Session {
+    common::session(
+        "42",
+        json!({
+            "title": "Fix the flaky test",
+            "prompt": "Fix it",
+            "state": state,
+            "url": "https://jules.google.com/session/42",
+            "outputs": [
+                { "pullRequest": { "url": "https://github.com/octo/app/pull/7" } }
+            ],
+        }),
+    )
+}
+
+fn failed_activity() -> Vec<Activity> {
+    serde_json::from_value(json!([{
+        "name": "sessions/42/activities/a",
+        "id": "a",
+        "createTime": "2025-01-03T13:00:00Z",
+        "originator": "system",
+        "sessionFailed": { "reason": "Tests kept failing" }
+    }]))
+    .unwrap()
+}
+
+#[test]
+fn test_payload_of_completed_session() {
+    let payload = WebhookPayload::new(&session("COMPLETED"), &failed_activity());
+
+    assert_eq!(payload.session_id, "42");
+    assert_eq!(payload.state, Some(State::Completed));
+    assert_eq!(payload.source, "sources/github/octo/app");
+    assert_eq!(
+        payload.pr_url.as_deref(),
+        Some("https://github.com/octo/app/pull/7")
+    );
+    // Only failed sessions carry a reason
+    assert_eq!(payload.failure_reason, None);
+
+    let value = serde_json::to_value(&payload).unwrap();
+    assert_eq!(value["event"], "session_finished");
+    assert_eq!(value["state"], "COMPLETED");
+    assert_eq!(value["url"], "https://jules.google.com/session/42");
+}
+
+#[test]
+fn test_payload_of_failed_session() {
+    let payload = WebhookPayload::new(&session("FAILED"), &failed_activity());
+    assert_eq!(
+        payload.failure_reason.as_deref(),
+        Some("Tests kept failing")
+    );
+}
+
+#[test]
+fn test_parse_webhook_url() {
+    assert!(parse_webhook_url("https://hooks.example.com/jules").is_ok());
+    assert!(parse_webhook_url("http://localhost:8080/").is_ok());
+    assert!(parse_webhook_url("ftp://example.com/").is_err());
+    assert!(parse_webhook_url("hooks.example.com").is_err());
+}
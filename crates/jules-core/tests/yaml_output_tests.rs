@@ -0,0 +1,167 @@
+//! Tests for YAML output.
+
+use jules_core::yaml_output::to_yaml;
+use serde_json::json;
+
+#[test]
+fn test_nested_structure() {
+    let value = json!({
+        "id": "abc",
+        "title": "Fix login",
+        "count": 3,
+        "private": false,
+        "url": null,
+        "sourceContext": {
+            "source": "sources/github/octo/app",
+            "githubRepoContext": { "startingBranch": "main" }
+        },
+        "outputs": [
+            { "pullRequest": { "url": "https://github.com/octo/app/pull/7", "title": "Fix" } }
+        ],
+        "tags": ["a", "b"],
+        "empty": [],
+        "none": {}
+    });
+
+    assert_eq!(
+        to_yaml(&value).unwrap(),
+        "\
+count: 3
+empty: []
+id: abc
+none: {}
+outputs:
+  - pullRequest:
+      title: Fix
+      url: https://github.com/octo/app/pull/7
+private: false
+sourceContext:
+  githubRepoContext:
+    startingBranch: main
+  source: sources/github/octo/app
+tags:
+  - a
+  - b
+title: Fix login
+url: null
+"
+    );
+}
+
+#[test]
+fn test_top_level_list() {
+    let value = json!([{ "id": "1", "type": "plan" }, { "id": "2" }, "x"]);
+    assert_eq!(
+        to_yaml(&value).unwrap(),
+        "- id: \"1\"\n  type: plan\n- id: \"2\"\n- x\n"
+    );
+    assert_eq!(to_yaml(&json!([])).unwrap(), "[]\n");
+    assert_eq!(
+        to_yaml(&json!([1.5e300, 2e-7, -3])).unwrap(),
+        "- 1.5e+300\n- 2e-7\n- -3\n"
+    );
+}
+
+#[test]
+fn test_ambiguous_strings_are_quoted() {
+    let value = json!([
+        "123",
+        "2025-01-01T00:00:00Z",
+        "true",
+        "No",
+        "null",
+        "",
+        " padded",
+        "key: value",
+        "- item",
+        "#comment",
+        "trailing:",
+        "tab\there",
+        "say \"hi\"",
+        "-1.5",
+        "plain text",
+        "日本語"
+    ]);
+
+    assert_eq!(
+        to_yaml(&value).unwrap(),
+        r##"- "123"
+- "2025-01-01T00:00:00Z"
+- "true"
+- "No"
+- "null"
+- ""
+- " padded"
+- "key: value"
+- "- item"
+- "#comment"
+- "trailing:"
+- "tab\there"
+- say "hi"
+- "-1.5"
+- plain text
+- 日本語
+"##
+    );
+}
+
+#[test]
+fn test_multiline_strings_use_literal_blocks() {
+    let value = json!({
+        "patch": "diff --git a/x b/x\n+added\n\n-removed\n",
+        "message": "line one\nline two",
+        "kept": "a\n\n\n",
+        "indented": "  starts with spaces\nsecond",
+        "crlf": "a\r\nb"
+    });
+
+    assert_eq!(
+        to_yaml(&value).unwrap(),
+        "\
+crlf: \"a\\r\\nb\"
+indented: \"  starts with spaces\\nsecond\"
+kept: |+
+  a
+
+
+message: |-
+  line one
+  line two
+patch: |
+  diff --git a/x b/x
+  +added
+
+  -removed
+"
+    );
+}
+
+#[test]
+fn test_literal_block_in_list() {
+    let value = json!([{ "output": "ok\nfail" }, "x\ny"]);
+    assert_eq!(
+        to_yaml(&value).unwrap(),
+        "- output: |-\n    ok\n    fail\n- |-\n  x\n  y\n"
+    );
+}
+
+#[test]
+fn test_output_parses_as_yaml() {
+    let value = json!({
+        "id": "abc",
+        "count": 3,
+        "private": false,
+        "url": null,
+        "quoted": "true",
+        "patch": "line one\nline two\n",
+        "outputs": [{ "pullRequest": { "url": "https://github.com/octo/app/pull/7" } }],
+        "tags": ["a", "b"],
+        "empty": [],
+        "none": {}
+    });
+    let yaml = to_yaml(&value).unwrap();
+    assert_eq!(
+        serde_norway::from_str::<serde_json::Value>(&yaml).unwrap(),
+        value
+    );
+}
@@ -3,12 +3,13 @@
 //! Pure SDK MCP server implementation for Jules API.
 //!
 //! This module contains the MCP server with a 1:1 mapping to the Jules API,
-//! exposing 9 core SDK tools without any extended features.
+//! exposing 10 core SDK tools without any extended features.
 //!
 //! For extended features (watch_session, issue_status), use the gules crate
 //! with the "extended-mcp" feature flag.
 
-use jules_core::config::load_config;
+use jules_core::config::{load_config, SafetyConfig};
+use jules_core::metrics::export_metrics;
 use jules_rs::client::JulesClient;
 use rmcp::{
     handler::server::{tool::ToolRouter, wrapper::Parameters},
@@ -27,6 +28,8 @@ use crate::tools::*;
 #[derive(Clone)]
 pub struct AppState {
     pub client: Arc<Mutex<JulesClient>>,
+    /// Guardrails on the sessions `create_session` may start
+    pub safety: Arc<SafetyConfig>,
 }
 
 #[derive(Clone)]
@@ -37,9 +40,10 @@ pub struct GulesServer {
 
 #[tool_router]
 impl GulesServer {
-    pub fn new(client: JulesClient) -> Self {
+    pub fn new(client: JulesClient, safety: SafetyConfig) -> Self {
         let state = AppState {
             client: Arc::new(Mutex::new(client)),
+            safety: Arc::new(safety),
         };
         Self {
             state,
@@ -95,6 +99,14 @@ impl GulesServer {
         handle_approve_plan(&self.state, args).await
     }
 
+    #[tool(description = "Cancel a Jules session that is still running")]
+    async fn cancel_session(
+        &self,
+        Parameters(args): Parameters<CancelSessionArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        handle_cancel_session(&self.state, args).await
+    }
+
     #[tool(description = "List available sources/repositories")]
     async fn list_sources(
         &self,
@@ -145,12 +157,13 @@ impl ServerHandler for GulesServer {
             },
             instructions: Some(
                 "Gules MCP Server - Interact with Google's Jules AI coding agent.\n\n\
-                 Available SDK tools (9 pure 1:1 mappings):\n\
+                 Available SDK tools (10 pure 1:1 mappings):\n\
                  - create_session: Create a new Jules coding session\n\
                  - get_session: Get details of a session\n\
                  - list_sessions: List all sessions\n\
                  - send_message: Send a message to a session\n\
                  - approve_plan: Approve a plan in a session\n\
+                 - cancel_session: Cancel a running session\n\
                  - list_sources: List available sources\n\
                  - get_source: Get details of a source\n\
                  - list_activities: List activities in a session\n\
@@ -182,10 +195,13 @@ pub async fn start_mcp_server() -> anyhow::Result<()> {
     })?;
 
     // Create Jules API client
-    let client = JulesClient::new(config.api_key.unwrap_or_default());
+    let client = export_metrics(
+        JulesClient::new(config.api_key.clone().unwrap_or_default()),
+        &config,
+    );
 
     // Create and run the server
-    let server = GulesServer::new(client);
+    let server = GulesServer::new(client, config.safety);
     if let Err(e) = server.serve_stdio().await {
         error!("MCP server error: {}", e);
         return Err(anyhow::anyhow!("MCP server error: {}", e));
@@ -17,7 +17,8 @@ use crate::server::AppState;
 pub struct CreateSessionArgs {
     /// The prompt describing what Jules should do
     pub prompt: String,
-    /// Source context (e.g., "sources/github/owner/repo")
+    /// Source context (e.g., "sources/github/owner/repo"); must be one of the
+    /// allowed sources if the `safety.allowed_sources` setting lists any
     pub source: String,
     /// Optional session title
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -35,9 +36,20 @@ fn default_branch() -> String {
     "main".to_string()
 }
 
+/// Parse a session argument given as a bare ID or as `sessions/{id}`
+pub fn parse_session_arg(value: &str) -> Result<SessionName, McpError> {
+    SessionName::from_id_or_name(value).map_err(|e| McpError::invalid_params(e.to_string(), None))
+}
+
+/// Parse an activity argument given as a bare ID or as a full activity name
+fn parse_activity_arg(session: &str, activity: &str) -> Result<ActivityName, McpError> {
+    ActivityName::from_id_or_name(session, activity)
+        .map_err(|e| McpError::invalid_params(e.to_string(), None))
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct GetSessionArgs {
-    /// Session ID to retrieve
+    /// Session ID to retrieve (bare ID or "sessions/{id}")
     pub session_id: String,
 }
 
@@ -57,7 +69,7 @@ fn default_page_size() -> u32 {
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct SendMessageArgs {
-    /// Session ID
+    /// Session ID (bare ID or "sessions/{id}")
     pub session_id: String,
     /// Message to send
     pub message: String,
@@ -65,7 +77,13 @@ pub struct SendMessageArgs {
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct ApprovePlanArgs {
-    /// Session ID
+    /// Session ID (bare ID or "sessions/{id}")
+    pub session_id: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, JsonSchema)]
+pub struct CancelSessionArgs {
+    /// Session ID (bare ID or "sessions/{id}")
     pub session_id: String,
 }
 
@@ -88,13 +106,13 @@ fn default_sources_page_size() -> u32 {
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct GetSourceArgs {
-    /// Source ID
+    /// Source ID (e.g. "github/owner/repo" or "sources/github/owner/repo")
     pub source_id: String,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct ListActivitiesArgs {
-    /// Session ID
+    /// Session ID (bare ID or "sessions/{id}")
     pub session_id: String,
     /// Page size (default: 30)
     #[serde(default = "default_activities_page_size")]
@@ -110,9 +128,9 @@ fn default_activities_page_size() -> u32 {
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, JsonSchema)]
 pub struct GetActivityArgs {
-    /// Session ID
+    /// Session ID (bare ID or "sessions/{id}")
     pub session_id: String,
-    /// Activity ID
+    /// Activity ID (bare ID or full "sessions/{id}/activities/{id}" name)
     pub activity_id: String,
 }
 
@@ -121,13 +139,14 @@ pub async fn handle_create_session(
     state: &AppState,
     args: CreateSessionArgs,
 ) -> Result<CallToolResult, McpError> {
-    let title = args.title.unwrap_or_else(|| {
-        if args.prompt.len() > 50 {
-            format!("{}...", &args.prompt[..47])
-        } else {
-            args.prompt.clone()
-        }
-    });
+    state
+        .safety
+        .check_source(&args.source)
+        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+
+    let title = args
+        .title
+        .unwrap_or_else(|| jules_core::truncate_to_width(&args.prompt, 50));
 
     let automation_mode = args.automation_mode.map(|mode| {
         if mode == "AUTO_CREATE_PR" {
@@ -158,7 +177,7 @@ pub async fn handle_create_session(
         .await
         .map_err(|e| McpError::internal_error(format!("API error: {}", e), None))?;
 
-    let session_id = session.name.clone();
+    let session_id = session.id.clone();
     let session_url = session.url.clone().unwrap_or_default();
     let pr_url = session
         .outputs
@@ -185,11 +204,12 @@ pub async fn handle_get_session(
     state: &AppState,
     args: GetSessionArgs,
 ) -> Result<CallToolResult, McpError> {
+    let name = parse_session_arg(&args.session_id)?;
     let client = state.client.lock().await;
 
     // Use SDK method
     let session = client
-        .get_session(&args.session_id)
+        .get_session(name.id())
         .await
         .map_err(|e| McpError::internal_error(format!("API error: {}", e), None))?;
 
@@ -209,7 +229,10 @@ pub async fn handle_get_session(
 
     let mut summary = format!(
         "Session: {}\nState: {}\nTitle: {}\nURL: {}",
-        args.session_id, state_val, title, url
+        name.id(),
+        state_val,
+        title,
+        url
     );
 
     if !pr_url.is_empty() {
@@ -220,7 +243,7 @@ pub async fn handle_get_session(
         Content::text(summary),
         Content::resource(ResourceContents::text(
             serde_json::to_string_pretty(&session).unwrap(),
-            format!("gules://session/{}", args.session_id),
+            format!("gules://session/{}", name.id()),
         )),
     ]))
 }
@@ -260,17 +283,18 @@ pub async fn handle_send_message(
     state: &AppState,
     args: SendMessageArgs,
 ) -> Result<CallToolResult, McpError> {
+    let name = parse_session_arg(&args.session_id)?;
     let client = state.client.lock().await;
 
     // Use SDK method
     client
-        .send_message(&args.session_id, &args.message)
+        .send_message(name.id(), &args.message)
         .await
         .map_err(|e| McpError::internal_error(format!("API error: {}", e), None))?;
 
     Ok(CallToolResult::success(vec![Content::text(format!(
         "Message sent successfully to session: {}\n\nUse get_session to see the updated session details.",
-        args.session_id
+        name.id()
     ))]))
 }
 
@@ -279,17 +303,38 @@ pub async fn handle_approve_plan(
     state: &AppState,
     args: ApprovePlanArgs,
 ) -> Result<CallToolResult, McpError> {
+    let name = parse_session_arg(&args.session_id)?;
     let client = state.client.lock().await;
 
     // Use SDK method
     client
-        .approve_plan(&args.session_id)
+        .approve_plan(name.id())
         .await
         .map_err(|e| McpError::internal_error(format!("API error: {}", e), None))?;
 
     Ok(CallToolResult::success(vec![Content::text(format!(
         "Plan approved successfully for session: {}\n\nThe session will now execute the approved plan.\nUse get_session to monitor progress.",
-        args.session_id
+        name.id()
+    ))]))
+}
+
+/// Handler for cancel_session tool
+pub async fn handle_cancel_session(
+    state: &AppState,
+    args: CancelSessionArgs,
+) -> Result<CallToolResult, McpError> {
+    let name = parse_session_arg(&args.session_id)?;
+    let client = state.client.lock().await;
+
+    // Use SDK method
+    client
+        .cancel_session(name.id())
+        .await
+        .map_err(|e| McpError::internal_error(format!("API error: {}", e), None))?;
+
+    Ok(CallToolResult::success(vec![Content::text(format!(
+        "Session cancelled: {}\n\nUse get_session to confirm its final state.",
+        name.id()
     ))]))
 }
 
@@ -337,11 +382,13 @@ pub async fn handle_get_source(
     state: &AppState,
     args: GetSourceArgs,
 ) -> Result<CallToolResult, McpError> {
+    let name = SourceName::from_id_or_name(&args.source_id)
+        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
     let client = state.client.lock().await;
 
     // Use SDK method
     let source = client
-        .get_source(&args.source_id)
+        .get_source(name.id())
         .await
         .map_err(|e| McpError::internal_error(format!("API error: {}", e), None))?;
 
@@ -351,7 +398,7 @@ pub async fn handle_get_source(
         Content::text(summary),
         Content::resource(ResourceContents::text(
             serde_json::to_string_pretty(&source).unwrap(),
-            format!("gules://source/{}", args.source_id),
+            format!("gules://source/{}", name.id()),
         )),
     ]))
 }
@@ -361,15 +408,12 @@ pub async fn handle_list_activities(
     state: &AppState,
     args: ListActivitiesArgs,
 ) -> Result<CallToolResult, McpError> {
+    let name = parse_session_arg(&args.session_id)?;
     let client = state.client.lock().await;
 
     // Use SDK method with all parameters
     let response = client
-        .list_activities(
-            &args.session_id,
-            Some(args.page_size),
-            args.page_token.as_deref(),
-        )
+        .list_activities(name.id(), Some(args.page_size), args.page_token.as_deref())
         .await
         .map_err(|e| McpError::internal_error(format!("API error: {}", e), None))?;
 
@@ -377,11 +421,12 @@ pub async fn handle_list_activities(
     let next_token = response.next_page_token.clone();
 
     let mut summary = if activities_count == 0 {
-        format!("No activities found for session: {}", args.session_id)
+        format!("No activities found for session: {}", name.id())
     } else {
         format!(
             "Found {} activity(ies) for session: {}",
-            activities_count, args.session_id
+            activities_count,
+            name.id()
         )
     };
 
@@ -393,7 +438,7 @@ pub async fn handle_list_activities(
         Content::text(summary),
         Content::resource(ResourceContents::text(
             serde_json::to_string_pretty(&response).unwrap(),
-            format!("gules://session/{}/activities", args.session_id),
+            format!("gules://session/{}/activities", name.id()),
         )),
     ]))
 }
@@ -403,11 +448,12 @@ pub async fn handle_get_activity(
     state: &AppState,
     args: GetActivityArgs,
 ) -> Result<CallToolResult, McpError> {
+    let name = parse_activity_arg(&args.session_id, &args.activity_id)?;
     let client = state.client.lock().await;
 
     // Use SDK method
     let activity = client
-        .get_activity(&args.session_id, &args.activity_id)
+        .get_activity(name.parent().id(), name.id())
         .await
         .map_err(|e| McpError::internal_error(format!("API error: {}", e), None))?;
 
@@ -424,7 +470,8 @@ pub async fn handle_get_activity(
             serde_json::to_string_pretty(&activity).unwrap(),
             format!(
                 "gules://session/{}/activity/{}",
-                args.session_id, args.activity_id
+                name.parent().id(),
+                name.id()
             ),
         )),
     ]))
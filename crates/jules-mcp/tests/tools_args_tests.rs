@@ -89,6 +89,17 @@ fn test_approve_plan_args() {
     assert_eq!(deserialized.session_id, "456");
 }
 
+#[test]
+fn test_cancel_session_args() {
+    let args = CancelSessionArgs {
+        session_id: "sessions/789".to_string(),
+    };
+
+    let json = serde_json::to_string(&args).unwrap();
+    let deserialized: CancelSessionArgs = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.session_id, "sessions/789");
+}
+
 #[test]
 fn test_list_sources_args_defaults() {
     let json = r#"{}"#;
@@ -139,6 +150,7 @@ fn test_all_args_have_schemas() {
     let _ = schema_for!(ListSessionsArgs);
     let _ = schema_for!(SendMessageArgs);
     let _ = schema_for!(ApprovePlanArgs);
+    let _ = schema_for!(CancelSessionArgs);
     let _ = schema_for!(ListSourcesArgs);
     let _ = schema_for!(GetSourceArgs);
     let _ = schema_for!(ListActivitiesArgs);
@@ -1,8 +1,12 @@
 use anyhow::{Context, Result};
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
 use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::types::error::ApiError;
+use crate::metrics::{route_template, ErrorClass, MetricsSink, RequestMetrics};
+use crate::types::error::HttpError;
+use crate::types::resource_name::{ActivityName, SessionName, SourceName};
 
 /// Configuration for JulesClient
 #[derive(Clone, Debug)]
@@ -20,10 +24,65 @@ impl Default for JulesConfig {
     }
 }
 
+/// Retry policy for transient failures (HTTP 429, and 503 for idempotent
+/// requests; see [`HttpError::is_retryable_for`])
+///
+/// A server-provided delay (`Retry-After` header or `google.rpc.RetryInfo`)
+/// always takes precedence over the exponential backoff. If the server asks
+/// for a longer wait than `max_delay`, the error is returned immediately so
+/// the caller can decide what to do with `HttpError::retry_after`.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the initial attempt
+    pub max_retries: u32,
+    /// Backoff for the first retry when the server gives no delay
+    pub base_delay: Duration,
+    /// Upper bound for any single wait
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Delay before retry number `attempt` (0-based), or `None` to give up
+    pub fn delay_for(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+
+        match retry_after {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => {
+                let backoff = self.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+                Some(backoff.min(self.max_delay))
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct JulesClient {
     client: Client,
     config: JulesConfig,
+    retry: RetryPolicy,
+    metrics: Option<Arc<dyn MetricsSink>>,
 }
 
 impl JulesClient {
@@ -40,14 +99,33 @@ impl JulesClient {
         Self {
             client: Client::new(),
             config,
+            retry: RetryPolicy::default(),
+            metrics: None,
         }
     }
 
+    /// Report every request attempt to a metrics sink
+    pub fn with_metrics_sink(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.metrics = Some(sink);
+        self
+    }
+
+    /// Replace the retry policy used for rate-limited and unavailable responses
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Get the current configuration
     pub fn config(&self) -> &JulesConfig {
         &self.config
     }
 
+    /// Get the current retry policy
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
     /// List sessions with pagination
     /// Maps directly to GET /sessions endpoint
     pub async fn list_sessions(
@@ -63,8 +141,10 @@ impl JulesClient {
     }
 
     /// Get a session by ID
+    /// Accepts either the bare ID ("123") or the resource name ("sessions/123")
     pub async fn get_session(&self, session_id: &str) -> Result<crate::types::session::Session> {
-        self.get(&format!("/sessions/{}", session_id)).await
+        let name = SessionName::from_id_or_name(session_id)?;
+        self.get(&format!("/{}", name)).await
     }
 
     /// Send a message to a session
@@ -73,17 +153,32 @@ impl JulesClient {
         let request = SendMessageRequest {
             prompt: prompt.to_string(),
         };
+        let name = SessionName::from_id_or_name(session_id)?;
         let _: serde_json::Value = self
-            .post(&format!("/sessions/{}:sendMessage", session_id), &request)
+            .post(&format!("/{}:sendMessage", name), &request)
             .await?;
         Ok(())
     }
 
     /// Approve a plan in a session
     pub async fn approve_plan(&self, session_id: &str) -> Result<()> {
-        let _: serde_json::Value = self
-            .post_empty(&format!("/sessions/{}:approvePlan", session_id))
-            .await?;
+        let name = SessionName::from_id_or_name(session_id)?;
+        let _: serde_json::Value = self.post_empty(&format!("/{}:approvePlan", name)).await?;
+        Ok(())
+    }
+
+    /// Cancel a session that is still running
+    pub async fn cancel_session(&self, session_id: &str) -> Result<()> {
+        let name = SessionName::from_id_or_name(session_id)?;
+        let _: serde_json::Value = self.post_empty(&format!("/{}:cancel", name)).await?;
+        Ok(())
+    }
+
+    /// Delete a session
+    /// Maps directly to DELETE /sessions/{id} endpoint
+    pub async fn delete_session(&self, session_id: &str) -> Result<()> {
+        let name = SessionName::from_id_or_name(session_id)?;
+        let _: serde_json::Value = self.delete(&format!("/{}", name)).await?;
         Ok(())
     }
 
@@ -115,12 +210,12 @@ impl JulesClient {
     }
 
     /// Get a source by ID
-    /// Note: source_id should include the full path (e.g., "sources/github/owner/repo")
+    /// Accepts either the source ID ("github/owner/repo") or the full resource
+    /// name ("sources/github/owner/repo").
     /// The API expects forward slashes to NOT be URL-encoded per gRPC Transcoding syntax
     pub async fn get_source(&self, source_id: &str) -> Result<crate::types::source::Source> {
-        // Remove 'sources/' prefix if present to avoid duplication
-        let source_path = source_id.strip_prefix("sources/").unwrap_or(source_id);
-        self.get(&format!("/sources/{}", source_path)).await
+        let name = SourceName::from_id_or_name(source_id)?;
+        self.get(&format!("/{}", name)).await
     }
 
     /// List activities for a session with pagination
@@ -131,11 +226,8 @@ impl JulesClient {
         page_size: Option<u32>,
         page_token: Option<&str>,
     ) -> Result<crate::types::activity::ListActivitiesResponse> {
-        let mut endpoint = format!(
-            "/sessions/{}/activities?pageSize={}",
-            session_id,
-            page_size.unwrap_or(30)
-        );
+        let name = SessionName::from_id_or_name(session_id)?;
+        let mut endpoint = format!("/{}/activities?pageSize={}", name, page_size.unwrap_or(30));
         if let Some(token) = page_token {
             endpoint.push_str(&format!("&pageToken={}", token));
         }
@@ -143,31 +235,27 @@ impl JulesClient {
     }
 
     /// Get a single activity by ID
+    /// The activity may also be given as its full resource name
+    /// ("sessions/123/activities/456")
     pub async fn get_activity(
         &self,
         session_id: &str,
         activity_id: &str,
     ) -> Result<crate::types::activity::Activity> {
-        self.get(&format!(
-            "/sessions/{}/activities/{}",
-            session_id, activity_id
-        ))
-        .await
+        let name = ActivityName::from_id_or_name(session_id, activity_id)?;
+        self.get(&format!("/{}", name)).await
     }
 
     /// Generic GET request
     pub async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T> {
         let url = format!("{}{}", self.config.base_url, endpoint);
 
-        let response = self
+        let request = self
             .client
             .get(&url)
-            .header("X-Goog-Api-Key", &self.config.api_key)
-            .send()
-            .await
-            .context("Failed to send request")?;
+            .header("X-Goog-Api-Key", &self.config.api_key);
 
-        self.handle_response(response).await
+        self.send("GET", endpoint, request).await
     }
 
     /// Generic POST request
@@ -178,32 +266,129 @@ impl JulesClient {
     ) -> Result<Res> {
         let url = format!("{}{}", self.config.base_url, endpoint);
 
-        let response = self
+        let request = self
             .client
             .post(&url)
             .header("X-Goog-Api-Key", &self.config.api_key)
-            .json(body)
-            .send()
-            .await
-            .context("Failed to send request")?;
+            .json(body);
 
-        self.handle_response(response).await
+        self.send("POST", endpoint, request).await
     }
 
     /// POST with empty body
     pub async fn post_empty<Res: DeserializeOwned>(&self, endpoint: &str) -> Result<Res> {
         let url = format!("{}{}", self.config.base_url, endpoint);
 
-        let response = self
+        let request = self
             .client
             .post(&url)
             .header("X-Goog-Api-Key", &self.config.api_key)
-            .header("Content-Length", "0")
-            .send()
-            .await
-            .context("Failed to send request")?;
+            .header("Content-Length", "0");
+
+        self.send("POST", endpoint, request).await
+    }
+
+    /// Generic DELETE request
+    pub async fn delete<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T> {
+        let url = format!("{}{}", self.config.base_url, endpoint);
+
+        let request = self
+            .client
+            .delete(&url)
+            .header("X-Goog-Api-Key", &self.config.api_key);
+
+        self.send("DELETE", endpoint, request).await
+    }
+
+    /// Send a request, retrying transient failures according to the retry policy
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        request: RequestBuilder,
+    ) -> Result<T> {
+        let mut attempt = 0;
+
+        loop {
+            let started = Instant::now();
+            let response = request
+                .try_clone()
+                .context("Request body cannot be retried")?
+                .send()
+                .await;
+
+            let response = match response {
+                Ok(response) => response,
+                Err(e) => {
+                    self.record(
+                        method,
+                        endpoint,
+                        None,
+                        started,
+                        Some(ErrorClass::Network),
+                        attempt,
+                    );
+                    return Err(anyhow::Error::new(e).context("Failed to send request"));
+                }
+            };
+
+            let status = response.status().as_u16();
+            let error = match self.handle_response(response).await {
+                Ok(value) => {
+                    self.record(method, endpoint, Some(status), started, None, attempt);
+                    return Ok(value);
+                }
+                Err(e) => e,
+            };
+
+            let http_error = error.downcast_ref::<HttpError>();
+            let class = match http_error {
+                Some(http_error) => ErrorClass::from_status(http_error.status),
+                None => ErrorClass::Decode,
+            };
+            self.record(
+                method,
+                endpoint,
+                Some(status),
+                started,
+                Some(class),
+                attempt,
+            );
+
+            let delay = http_error
+                .filter(|http_error| http_error.is_retryable_for(method))
+                .and_then(|http_error| self.retry.delay_for(attempt, http_error.retry_after));
+
+            match delay {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return Err(error),
+            }
+        }
+    }
 
-        self.handle_response(response).await
+    /// Report a finished attempt to the metrics sink, if any
+    fn record(
+        &self,
+        method: &str,
+        endpoint: &str,
+        status: Option<u16>,
+        started: Instant,
+        error: Option<ErrorClass>,
+        attempt: u32,
+    ) {
+        if let Some(sink) = &self.metrics {
+            sink.record(&RequestMetrics {
+                method,
+                route: route_template(endpoint),
+                status,
+                latency: started.elapsed(),
+                error,
+                attempt,
+            });
+        }
     }
 
     /// Handle response with error parsing
@@ -211,22 +396,22 @@ impl JulesClient {
         let status = response.status();
 
         if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string());
+
             // Get the response text first
             let body_text = response.text().await.unwrap_or_default();
 
-            // Try to parse as structured error
-            if let Ok(api_error) = serde_json::from_str::<ApiError>(&body_text) {
-                anyhow::bail!(
-                    "API error {}: {} ({})",
-                    api_error.error.code,
-                    api_error.error.message,
-                    api_error.error.status
-                );
-            } else if !body_text.is_empty() {
-                anyhow::bail!("API error {}: {}", status, body_text);
-            } else {
-                anyhow::bail!("API error: HTTP {}", status);
-            }
+            return Err(HttpError::from_response(
+                status.as_u16(),
+                status.canonical_reason(),
+                retry_after.as_deref(),
+                &body_text,
+            )
+            .into());
         }
 
         response
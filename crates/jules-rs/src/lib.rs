@@ -24,8 +24,10 @@
 //! ```
 
 pub mod client;
+pub mod metrics;
 pub mod types;
 
 // Re-export commonly used types
-pub use client::{JulesClient, JulesConfig};
+pub use client::{JulesClient, JulesConfig, RetryPolicy};
+pub use metrics::{ErrorClass, MetricsSink, RequestMetrics};
 pub use types::*;
@@ -0,0 +1,133 @@
+//! Request metrics hooks.
+//!
+//! Register a [`MetricsSink`] with [`JulesClient::with_metrics_sink`] to
+//! observe every HTTP attempt the client makes (retries included) without
+//! wrapping individual call sites.
+//!
+//! [`JulesClient::with_metrics_sink`]: crate::client::JulesClient::with_metrics_sink
+
+use std::time::Duration;
+
+/// Coarse classification of a failed request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// HTTP 429
+    RateLimited,
+    /// Other 4xx responses
+    Client,
+    /// 5xx responses
+    Server,
+    /// Connection, DNS or TLS failure before a response was received
+    Network,
+    /// Successful status but the body could not be decoded
+    Decode,
+}
+
+impl ErrorClass {
+    /// Stable lowercase label, suitable for metric labels
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorClass::RateLimited => "rate_limited",
+            ErrorClass::Client => "client",
+            ErrorClass::Server => "server",
+            ErrorClass::Network => "network",
+            ErrorClass::Decode => "decode",
+        }
+    }
+
+    /// Classify a non-success HTTP status
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            429 => ErrorClass::RateLimited,
+            500..=599 => ErrorClass::Server,
+            _ => ErrorClass::Client,
+        }
+    }
+}
+
+/// Metrics for a single HTTP attempt
+#[derive(Debug, Clone)]
+pub struct RequestMetrics<'a> {
+    /// HTTP method (`GET`, `POST`)
+    pub method: &'a str,
+    /// Endpoint with IDs replaced by placeholders (e.g. `/sessions/{id}:sendMessage`)
+    pub route: String,
+    /// HTTP status, if a response was received
+    pub status: Option<u16>,
+    /// Time from sending the request to decoding the response
+    pub latency: Duration,
+    /// Error classification, `None` on success
+    pub error: Option<ErrorClass>,
+    /// Attempt number (0 for the first try, >0 for retries)
+    pub attempt: u32,
+}
+
+/// Receiver for per-request metrics
+///
+/// Called synchronously after each attempt, so implementations should be cheap
+/// (update counters, push to a channel) and must not block.
+pub trait MetricsSink: Send + Sync {
+    fn record(&self, metrics: &RequestMetrics<'_>);
+}
+
+/// Turn an endpoint into a low-cardinality route template
+///
+/// `/sessions/123/activities/456?pageSize=5` becomes `/sessions/{id}/activities/{id}`,
+/// and everything under `/sources/` collapses to `/sources/{source}`.
+pub fn route_template(endpoint: &str) -> String {
+    let path = endpoint.split('?').next().unwrap_or(endpoint);
+    let mut route = String::new();
+    let mut previous = "";
+
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        if previous == "sources" {
+            route.push_str("/{source}");
+            break;
+        }
+
+        let (name, method) = match segment.split_once(':') {
+            Some((name, method)) => (name, Some(method)),
+            None => (segment, None),
+        };
+
+        route.push('/');
+        if matches!(previous, "sessions" | "activities") {
+            route.push_str("{id}");
+        } else {
+            route.push_str(name);
+        }
+        if let Some(method) = method {
+            route.push(':');
+            route.push_str(method);
+        }
+        previous = name;
+    }
+
+    if route.is_empty() {
+        route.push('/');
+    }
+    route
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_template() {
+        assert_eq!(route_template("/sessions?pageSize=30"), "/sessions");
+        assert_eq!(route_template("/sessions/123"), "/sessions/{id}");
+        assert_eq!(
+            route_template("/sessions/123:sendMessage"),
+            "/sessions/{id}:sendMessage"
+        );
+        assert_eq!(
+            route_template("/sessions/123/activities/456"),
+            "/sessions/{id}/activities/{id}"
+        );
+        assert_eq!(
+            route_template("/sources/github/owner/repo"),
+            "/sources/{source}"
+        );
+    }
+}
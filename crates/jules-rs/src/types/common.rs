@@ -13,6 +13,9 @@ pub struct ListResponse<T> {
 pub type Timestamp = String;
 
 /// Resource name (e.g., "sessions/123")
+///
+/// See [`SessionName`](super::SessionName), [`ActivityName`](super::ActivityName)
+/// and [`SourceName`](super::SourceName) for validated, typed variants.
 pub type ResourceName = String;
 
 /// Resource ID (e.g., "123")
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
 
 /// API error response
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,4 +13,132 @@ pub struct ErrorDetail {
     pub code: u16,
     pub message: String,
     pub status: String,
+    /// Structured error payloads (`google.rpc.RetryInfo`, `google.rpc.ErrorInfo`, ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<serde_json::Value>,
+}
+
+impl ErrorDetail {
+    /// Retry delay requested by a `google.rpc.RetryInfo` detail, if present
+    pub fn retry_delay(&self) -> Option<Duration> {
+        self.details
+            .iter()
+            .filter(|d| {
+                d.get("@type")
+                    .and_then(|t| t.as_str())
+                    .map(|t| t.ends_with("google.rpc.RetryInfo"))
+                    .unwrap_or(false)
+            })
+            .find_map(|d| d.get("retryDelay").and_then(|v| v.as_str()))
+            .and_then(parse_proto_duration)
+    }
+}
+
+/// Error returned when the API responds with a non-success HTTP status
+///
+/// Returned inside `anyhow::Error`; use `err.downcast_ref::<HttpError>()` to
+/// inspect the status code or the server-requested retry delay.
+#[derive(Debug, Clone)]
+pub struct HttpError {
+    /// HTTP status code
+    pub status: u16,
+    /// Structured error body, if the API returned one
+    pub detail: Option<ErrorDetail>,
+    /// Delay requested by the server (`Retry-After` header or `RetryInfo` detail)
+    pub retry_after: Option<Duration>,
+    message: String,
+}
+
+impl HttpError {
+    /// Build an error from a response status, its `Retry-After` header and body text
+    pub fn from_response(
+        status: u16,
+        reason: Option<&str>,
+        retry_after_header: Option<&str>,
+        body: &str,
+    ) -> Self {
+        let detail = serde_json::from_str::<ApiError>(body)
+            .ok()
+            .map(|api_error| api_error.error);
+
+        let message = if let Some(detail) = &detail {
+            format!(
+                "API error {}: {} ({})",
+                detail.code, detail.message, detail.status
+            )
+        } else {
+            let status_text = match reason {
+                Some(reason) => format!("{} {}", status, reason),
+                None => status.to_string(),
+            };
+            if !body.is_empty() {
+                format!("API error {}: {}", status_text, body)
+            } else {
+                format!("API error: HTTP {}", status_text)
+            }
+        };
+
+        // The header wins over the body: it is what proxies and the frontend set
+        let retry_after = retry_after_header
+            .and_then(parse_retry_after)
+            .or_else(|| detail.as_ref().and_then(|d| d.retry_delay()));
+
+        Self {
+            status,
+            detail,
+            retry_after,
+            message,
+        }
+    }
+
+    /// Whether the request was rejected by rate limiting (HTTP 429)
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == 429
+    }
+
+    /// Whether retrying the same request may succeed (429 and 503)
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.status, 429 | 503)
+    }
+
+    /// Whether a `method` request can be retried without risking that it
+    /// is applied twice: on 429 for any method, as rate-limited requests are
+    /// rejected unprocessed, and on 503 only for idempotent ones (GET, PUT,
+    /// DELETE), as a POST like `create_session` may already have been
+    /// processed
+    pub fn is_retryable_for(&self, method: &str) -> bool {
+        self.is_rate_limited()
+            || (self.is_retryable() && matches!(method, "GET" | "PUT" | "DELETE"))
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+/// Parse a `Retry-After` header value (delta-seconds or HTTP-date)
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delta = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    // A date in the past means "retry now"
+    Some(delta.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Parse a protobuf JSON duration such as `"30s"` or `"1.500s"`
+fn parse_proto_duration(value: &str) -> Option<Duration> {
+    let seconds: f64 = value.trim().strip_suffix('s')?.parse().ok()?;
+    if seconds.is_finite() && seconds >= 0.0 {
+        Some(Duration::from_secs_f64(seconds))
+    } else {
+        None
+    }
 }
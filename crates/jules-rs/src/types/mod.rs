@@ -1,6 +1,7 @@
 pub mod activity;
 pub mod common;
 pub mod error;
+pub mod resource_name;
 pub mod session;
 pub mod source;
 
@@ -8,5 +9,6 @@ pub mod source;
 pub use activity::*;
 pub use common::*;
 pub use error::*;
+pub use resource_name::*;
 pub use session::*;
 pub use source::*;
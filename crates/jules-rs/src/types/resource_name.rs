@@ -0,0 +1,334 @@
+//! Typed resource names.
+//!
+//! The API identifies resources by hierarchical names such as
+//! `sessions/123`, `sessions/123/activities/456` or
+//! `sources/github/owner/repo`. These newtypes validate the shape once and
+//! expose the pieces (`id()`, `parent()`) instead of ad-hoc string slicing.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+const SESSIONS: &str = "sessions";
+const ACTIVITIES: &str = "activities";
+const SOURCES: &str = "sources";
+
+/// Error returned when a string is not a valid resource name or ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceNameError {
+    kind: &'static str,
+    value: String,
+    reason: &'static str,
+}
+
+impl ResourceNameError {
+    fn new(kind: &'static str, value: &str, reason: &'static str) -> Self {
+        Self {
+            kind,
+            value: value.to_string(),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for ResourceNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid {} name '{}': {}",
+            self.kind, self.value, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ResourceNameError {}
+
+/// Validate a single path segment (session or activity ID)
+fn validate_segment(kind: &'static str, value: &str) -> Result<(), ResourceNameError> {
+    if value.is_empty() {
+        return Err(ResourceNameError::new(kind, value, "ID is empty"));
+    }
+    if value.contains('/') {
+        return Err(ResourceNameError::new(
+            kind,
+            value,
+            "ID must not contain '/'",
+        ));
+    }
+    if value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(ResourceNameError::new(
+            kind,
+            value,
+            "ID must not contain whitespace",
+        ));
+    }
+    Ok(())
+}
+
+/// Session resource name: `sessions/{session}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SessionName {
+    id: String,
+}
+
+impl SessionName {
+    /// Build from a bare session ID (`123`)
+    pub fn new(id: impl Into<String>) -> Result<Self, ResourceNameError> {
+        let id = id.into();
+        validate_segment("session", &id)?;
+        Ok(Self { id })
+    }
+
+    /// Parse a full resource name (`sessions/123`)
+    pub fn parse(name: &str) -> Result<Self, ResourceNameError> {
+        let id = name
+            .strip_prefix("sessions/")
+            .ok_or_else(|| ResourceNameError::new("session", name, "expected 'sessions/{id}'"))?;
+        Self::new(id)
+    }
+
+    /// Accept either a bare ID or a full resource name
+    pub fn from_id_or_name(value: &str) -> Result<Self, ResourceNameError> {
+        let value = value.trim();
+        if value.starts_with("sessions/") {
+            Self::parse(value)
+        } else {
+            Self::new(value)
+        }
+    }
+
+    /// Session ID (last path segment)
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Sessions are top-level resources and have no parent
+    pub fn parent(&self) -> Option<&str> {
+        None
+    }
+
+    /// Name of an activity within this session
+    pub fn activity(&self, activity_id: &str) -> Result<ActivityName, ResourceNameError> {
+        ActivityName::new(self.clone(), activity_id)
+    }
+}
+
+impl fmt::Display for SessionName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", SESSIONS, self.id)
+    }
+}
+
+impl FromStr for SessionName {
+    type Err = ResourceNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for SessionName {
+    type Error = ResourceNameError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<SessionName> for String {
+    fn from(name: SessionName) -> Self {
+        name.to_string()
+    }
+}
+
+/// Activity resource name: `sessions/{session}/activities/{activity}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ActivityName {
+    session: SessionName,
+    id: String,
+}
+
+impl ActivityName {
+    /// Build from a parent session and a bare activity ID
+    pub fn new(session: SessionName, id: impl Into<String>) -> Result<Self, ResourceNameError> {
+        let id = id.into();
+        validate_segment("activity", &id)?;
+        Ok(Self { session, id })
+    }
+
+    /// Parse a full resource name (`sessions/123/activities/456`)
+    pub fn parse(name: &str) -> Result<Self, ResourceNameError> {
+        let invalid = || {
+            ResourceNameError::new(
+                "activity",
+                name,
+                "expected 'sessions/{session}/activities/{activity}'",
+            )
+        };
+
+        let rest = name.strip_prefix("sessions/").ok_or_else(invalid)?;
+        let (session_id, activity_id) = rest.split_once("/activities/").ok_or_else(invalid)?;
+        Self::new(SessionName::new(session_id)?, activity_id)
+    }
+
+    /// Accept a bare activity ID within `session`, or a full activity name
+    ///
+    /// `session` may itself be a bare ID or a session name. When a full
+    /// activity name is given, it must belong to that session.
+    pub fn from_id_or_name(session: &str, activity: &str) -> Result<Self, ResourceNameError> {
+        let session = SessionName::from_id_or_name(session)?;
+        let activity = activity.trim();
+
+        if activity.starts_with("sessions/") {
+            let name = Self::parse(activity)?;
+            if name.session != session {
+                return Err(ResourceNameError::new(
+                    "activity",
+                    activity,
+                    "activity belongs to a different session",
+                ));
+            }
+            Ok(name)
+        } else {
+            Self::new(session, activity)
+        }
+    }
+
+    /// Activity ID (last path segment)
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Session that owns this activity
+    pub fn parent(&self) -> &SessionName {
+        &self.session
+    }
+}
+
+impl fmt::Display for ActivityName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.session, ACTIVITIES, self.id)
+    }
+}
+
+impl FromStr for ActivityName {
+    type Err = ResourceNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for ActivityName {
+    type Error = ResourceNameError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<ActivityName> for String {
+    fn from(name: ActivityName) -> Self {
+        name.to_string()
+    }
+}
+
+/// Source resource name: `sources/{source}` where the ID itself is a path
+/// such as `github/owner/repo`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SourceName {
+    id: String,
+}
+
+impl SourceName {
+    /// Build from a source ID (`github/owner/repo`)
+    pub fn new(id: impl Into<String>) -> Result<Self, ResourceNameError> {
+        let id = id.into();
+        if id.is_empty() {
+            return Err(ResourceNameError::new("source", &id, "ID is empty"));
+        }
+        if id.split('/').any(|segment| segment.is_empty()) {
+            return Err(ResourceNameError::new(
+                "source",
+                &id,
+                "ID must not contain empty path segments",
+            ));
+        }
+        if id.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(ResourceNameError::new(
+                "source",
+                &id,
+                "ID must not contain whitespace",
+            ));
+        }
+        Ok(Self { id })
+    }
+
+    /// Parse a full resource name (`sources/github/owner/repo`)
+    pub fn parse(name: &str) -> Result<Self, ResourceNameError> {
+        let id = name
+            .strip_prefix("sources/")
+            .ok_or_else(|| ResourceNameError::new("source", name, "expected 'sources/{id}'"))?;
+        Self::new(id)
+    }
+
+    /// Accept either a source ID or a full resource name
+    pub fn from_id_or_name(value: &str) -> Result<Self, ResourceNameError> {
+        let value = value.trim();
+        if value.starts_with("sources/") {
+            Self::parse(value)
+        } else {
+            Self::new(value)
+        }
+    }
+
+    /// Source ID (everything after `sources/`)
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Sources are top-level resources and have no parent
+    pub fn parent(&self) -> Option<&str> {
+        None
+    }
+
+    /// Owner and repository for GitHub sources (`github/{owner}/{repo}`)
+    pub fn github_repo(&self) -> Option<(&str, &str)> {
+        let mut parts = self.id.splitn(3, '/');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("github"), Some(owner), Some(repo)) if !repo.contains('/') => Some((owner, repo)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SourceName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", SOURCES, self.id)
+    }
+}
+
+impl FromStr for SourceName {
+    type Err = ResourceNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for SourceName {
+    type Error = ResourceNameError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<SourceName> for String {
+    fn from(name: SourceName) -> Self {
+        name.to_string()
+    }
+}
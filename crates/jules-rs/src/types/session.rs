@@ -137,7 +137,7 @@ pub struct PullRequest {
 }
 
 /// Create session request
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateSessionRequest {
     pub prompt: String,
     #[serde(rename = "sourceContext")]
@@ -62,6 +62,54 @@ async fn test_approve_plan_method() {
     _approve_mock.assert_async().await;
 }
 
+#[tokio::test]
+async fn test_cancel_session_method() {
+    let mut server = Server::new_async().await;
+
+    // Mock the cancel endpoint
+    let _cancel_mock = server
+        .mock("POST", "/sessions/session-123:cancel")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let client = JulesClient::with_config(jules_rs::JulesConfig {
+        api_key: "test-key".to_string(),
+        base_url: server.url(),
+    });
+
+    let result = client.cancel_session("sessions/session-123").await;
+
+    assert!(result.is_ok(), "cancel_session should succeed");
+    _cancel_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_delete_session_method() {
+    let mut server = Server::new_async().await;
+
+    // Mock the delete endpoint
+    let _delete_mock = server
+        .mock("DELETE", "/sessions/session-123")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let client = JulesClient::with_config(jules_rs::JulesConfig {
+        api_key: "test-key".to_string(),
+        base_url: server.url(),
+    });
+
+    let result = client.delete_session("session-123").await;
+
+    assert!(result.is_ok(), "delete_session should succeed");
+    _delete_mock.assert_async().await;
+}
+
 #[tokio::test]
 async fn test_list_sessions_with_pagination() {
     let mut server = Server::new_async().await;
@@ -131,6 +131,52 @@ async fn test_get_source_with_slashes_integration() {
     mock.assert_async().await;
 }
 
+#[tokio::test]
+async fn test_session_methods_accept_resource_names() {
+    let mut server = Server::new_async().await;
+
+    let session_mock = server
+        .mock("GET", "/sessions/123456")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"name": "sessions/123456", "id": "123456", "prompt": "Test", "sourceContext": {"source": "sources/github/owner/repo"}}"#)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let activity_mock = server
+        .mock("GET", "/sessions/123456/activities/789")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"name": "sessions/123456/activities/789", "id": "789", "createTime": "2024-01-01T00:00:00Z", "originator": "agent"}"#,
+        )
+        .create_async()
+        .await;
+
+    let client = JulesClient::with_config(jules_rs::client::JulesConfig {
+        api_key: "test-key".to_string(),
+        base_url: server.url(),
+    });
+
+    // Bare ID and full resource name hit the same endpoint
+    client.get_session("123456").await.unwrap();
+    client.get_session("sessions/123456").await.unwrap();
+
+    let activity = client
+        .get_activity("123456", "sessions/123456/activities/789")
+        .await
+        .unwrap();
+    assert_eq!(activity.id, "789");
+
+    // Malformed IDs are rejected before any request is sent
+    assert!(client.get_session("sessions/").await.is_err());
+    assert!(client.get_session("../123456").await.is_err());
+
+    session_mock.assert_async().await;
+    activity_mock.assert_async().await;
+}
+
 #[tokio::test]
 async fn test_error_handling_integration() {
     let mut server = Server::new_async().await;
@@ -165,3 +211,181 @@ async fn test_error_handling_integration() {
 
     mock.assert_async().await;
 }
+
+#[tokio::test]
+async fn test_rate_limited_request_is_retried_after_delay() {
+    let mut server = Server::new_async().await;
+
+    let limited = server
+        .mock("GET", "/sessions/123456")
+        .with_status(429)
+        .with_header("retry-after", "0")
+        .with_body("")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let ok = server
+        .mock("GET", "/sessions/123456")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"name":"sessions/123456","id":"123456","prompt":"p","sourceContext":{"source":"sources/github/o/r"}}"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = JulesClient::with_config(jules_rs::client::JulesConfig {
+        api_key: "test-key".to_string(),
+        base_url: server.url(),
+    });
+
+    let session = client.get_session("123456").await.unwrap();
+    assert_eq!(session.id, "123456");
+
+    limited.assert_async().await;
+    ok.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_unavailable_post_is_not_retried() {
+    let mut server = Server::new_async().await;
+
+    // The session may have been created before the 503
+    let unavailable = server
+        .mock("POST", "/sessions")
+        .with_status(503)
+        .with_body("")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = JulesClient::with_config(jules_rs::client::JulesConfig {
+        api_key: "test-key".to_string(),
+        base_url: server.url(),
+    });
+
+    let request = jules_rs::types::session::CreateSessionRequest {
+        prompt: "Test prompt".to_string(),
+        source_context: jules_rs::types::session::SourceContext {
+            source: "sources/github/owner/repo".to_string(),
+            github_repo_context: None,
+        },
+        title: None,
+        require_plan_approval: None,
+        automation_mode: None,
+    };
+    let error = client.create_session(request).await.unwrap_err();
+    let http_error = error.downcast_ref::<jules_rs::HttpError>().unwrap();
+    assert_eq!(http_error.status, 503);
+    assert!(http_error.is_retryable_for("GET"));
+    assert!(!http_error.is_retryable_for("POST"));
+
+    unavailable.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_rate_limit_error_exposes_retry_after() {
+    let mut server = Server::new_async().await;
+
+    let mock = server
+        .mock("GET", "/sessions/123456")
+        .with_status(429)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"error":{"code":429,"message":"Quota exceeded","status":"RESOURCE_EXHAUSTED",
+                "details":[{"@type":"type.googleapis.com/google.rpc.RetryInfo","retryDelay":"30s"}]}}"#,
+        )
+        .create_async()
+        .await;
+
+    let client = JulesClient::with_config(jules_rs::client::JulesConfig {
+        api_key: "test-key".to_string(),
+        base_url: server.url(),
+    })
+    .with_retry_policy(jules_rs::RetryPolicy::none());
+
+    let error = client.get_session("123456").await.unwrap_err();
+    let http_error = error.downcast_ref::<jules_rs::HttpError>().unwrap();
+    assert!(http_error.is_rate_limited());
+    assert_eq!(
+        http_error.retry_after,
+        Some(std::time::Duration::from_secs(30))
+    );
+    assert!(error.to_string().contains("Quota exceeded"));
+
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_retry_policy_respects_server_delay() {
+    use std::time::Duration;
+
+    let policy = jules_rs::RetryPolicy::default();
+    assert_eq!(
+        policy.delay_for(0, Some(Duration::from_secs(5))),
+        Some(Duration::from_secs(5))
+    );
+    // Longer than max_delay: give up and let the caller handle it
+    assert_eq!(policy.delay_for(0, Some(Duration::from_secs(3600))), None);
+    // Exponential backoff without a server hint
+    assert_eq!(policy.delay_for(1, None), Some(policy.base_delay * 2));
+    assert_eq!(policy.delay_for(policy.max_retries, None), None);
+
+    assert_eq!(
+        jules_rs::parse_retry_after("120"),
+        Some(Duration::from_secs(120))
+    );
+    assert_eq!(
+        jules_rs::parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+        Some(Duration::ZERO)
+    );
+}
+
+#[tokio::test]
+async fn test_metrics_sink_receives_each_attempt() {
+    use jules_rs::metrics::{ErrorClass, MetricsSink, RequestMetrics};
+    use std::sync::{Arc, Mutex};
+
+    type Recorded = (String, Option<u16>, Option<ErrorClass>);
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<Recorded>>);
+
+    impl MetricsSink for Recorder {
+        fn record(&self, metrics: &RequestMetrics<'_>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((metrics.route.clone(), metrics.status, metrics.error));
+        }
+    }
+
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/sessions/missing")
+        .with_status(404)
+        .with_body("")
+        .create_async()
+        .await;
+
+    let recorder = Arc::new(Recorder::default());
+    let client = JulesClient::with_config(jules_rs::client::JulesConfig {
+        api_key: "test-key".to_string(),
+        base_url: server.url(),
+    })
+    .with_metrics_sink(recorder.clone());
+
+    assert!(client.get_session("missing").await.is_err());
+
+    let recorded = recorder.0.lock().unwrap();
+    assert_eq!(
+        *recorded,
+        vec![(
+            "/sessions/{id}".to_string(),
+            Some(404),
+            Some(ErrorClass::Client)
+        )]
+    );
+}
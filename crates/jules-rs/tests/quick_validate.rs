@@ -157,7 +157,7 @@ fn quick_validate_activities_json() {
             println!("   media artifacts: {}", stats.media_artifacts);
 
             // Verify we got some activities (counts will vary with real data)
-            assert!(response.activities.len() > 0, "Expected some activities");
+            assert!(!response.activities.is_empty(), "Expected some activities");
             println!("   Total activities: {}", response.activities.len());
 
             println!("\n✅ All validations passed!");
@@ -0,0 +1,84 @@
+//! Tests for typed resource names
+
+use jules_rs::types::{ActivityName, SessionName, SourceName};
+
+#[test]
+fn test_session_name_parse_and_accessors() {
+    let name: SessionName = "sessions/123".parse().unwrap();
+    assert_eq!(name.id(), "123");
+    assert_eq!(name.parent(), None);
+    assert_eq!(name.to_string(), "sessions/123");
+
+    assert_eq!(SessionName::new("123").unwrap(), name);
+    assert_eq!(SessionName::from_id_or_name("123").unwrap(), name);
+    assert_eq!(
+        SessionName::from_id_or_name(" sessions/123 ").unwrap(),
+        name
+    );
+}
+
+#[test]
+fn test_session_name_rejects_invalid_input() {
+    assert!("123".parse::<SessionName>().is_err());
+    assert!("sessions/".parse::<SessionName>().is_err());
+    assert!("sessions/1/activities/2".parse::<SessionName>().is_err());
+    assert!(SessionName::new("has space").is_err());
+
+    let err = SessionName::parse("oops").unwrap_err();
+    assert!(err.to_string().contains("sessions/{id}"));
+}
+
+#[test]
+fn test_activity_name_parse_and_parent() {
+    let name: ActivityName = "sessions/123/activities/456".parse().unwrap();
+    assert_eq!(name.id(), "456");
+    assert_eq!(name.parent().id(), "123");
+    assert_eq!(name.to_string(), "sessions/123/activities/456");
+
+    let built = SessionName::new("123").unwrap().activity("456").unwrap();
+    assert_eq!(built, name);
+
+    assert!("sessions/123/activities/".parse::<ActivityName>().is_err());
+    assert!("sessions/123".parse::<ActivityName>().is_err());
+}
+
+#[test]
+fn test_source_name_parse_and_github_repo() {
+    let name: SourceName = "sources/github/owner/repo".parse().unwrap();
+    assert_eq!(name.id(), "github/owner/repo");
+    assert_eq!(name.github_repo(), Some(("owner", "repo")));
+    assert_eq!(name.to_string(), "sources/github/owner/repo");
+
+    // No double prefix when given the full name
+    assert_eq!(
+        SourceName::from_id_or_name("sources/github/owner/repo").unwrap(),
+        SourceName::from_id_or_name("github/owner/repo").unwrap()
+    );
+
+    assert!(SourceName::new("github//repo").is_err());
+    assert!("github/owner/repo".parse::<SourceName>().is_err());
+    assert_eq!(SourceName::new("repo-1").unwrap().github_repo(), None);
+}
+
+#[test]
+fn test_resource_names_serde_roundtrip() {
+    let name = SessionName::new("123").unwrap();
+    let json = serde_json::to_string(&name).unwrap();
+    assert_eq!(json, r#""sessions/123""#);
+    let back: SessionName = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, name);
+
+    assert!(serde_json::from_str::<SourceName>(r#""not-a-source""#).is_err());
+}
+
+#[test]
+fn test_activity_name_from_id_or_name() {
+    let bare = ActivityName::from_id_or_name("123", "456").unwrap();
+    let full =
+        ActivityName::from_id_or_name("sessions/123", "sessions/123/activities/456").unwrap();
+    assert_eq!(bare, full);
+    assert_eq!(full.to_string(), "sessions/123/activities/456");
+
+    // A full activity name must belong to the given session
+    assert!(ActivityName::from_id_or_name("999", "sessions/123/activities/456").is_err());
+}